@@ -3,8 +3,12 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.benchmarks.*
+import kotlin.concurrent.thread
 import kotlin.system.measureNanoTime
 
+// Number of threads used for the concurrent test cases
+val THREAD_COUNT = 4
+
 class TestCallbackObj : TestCallbackInterface {
     override fun method(a: Int, b: Int, data: TestData): String {
         return data.bar;
@@ -34,6 +38,20 @@ class TestCallbackObj : TestCallbackInterface {
                     testNoArgsVoidReturn()
                 }
             }
+            TestCase.CALLBACK_HANDLE -> measureNanoTime {
+                for (i in 0UL..count) {
+                    testCallbackHandle(this)
+                }
+            }
+            TestCase.CONCURRENT_CALLBACK_HANDLE -> measureNanoTime {
+                (1..THREAD_COUNT).map {
+                    thread {
+                        for (i in 0UL..count / THREAD_COUNT.toULong()) {
+                            testCallbackHandle(this)
+                        }
+                    }
+                }.forEach { it.join() }
+            }
         }.toULong()
     }
 }
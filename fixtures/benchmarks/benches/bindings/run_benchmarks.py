@@ -3,8 +3,12 @@
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 from benchmarks import *
+import threading
 import time
 
+# Number of threads used for the concurrent test cases
+THREAD_COUNT = 4
+
 class TestCallbackObj:
     def method(self, a, b, data):
         return data.bar
@@ -29,6 +33,20 @@ class TestCallbackObj:
             start = time.perf_counter_ns()
             for i in range(count):
                 test_no_args_void_return()
+        elif test_case == TestCase.CALLBACK_HANDLE:
+            start = time.perf_counter_ns()
+            for i in range(count):
+                test_callback_handle(self)
+        elif test_case == TestCase.CONCURRENT_CALLBACK_HANDLE:
+            def worker():
+                for i in range(count // THREAD_COUNT):
+                    test_callback_handle(self)
+            threads = [threading.Thread(target=worker) for _ in range(THREAD_COUNT)]
+            start = time.perf_counter_ns()
+            for t in threads:
+                t.start()
+            for t in threads:
+                t.join()
         end = time.perf_counter_ns()
         return end - start
 
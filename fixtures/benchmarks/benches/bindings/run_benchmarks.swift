@@ -6,6 +6,11 @@
     import benchmarks
 #endif
 
+import Dispatch
+
+// Number of threads used for the concurrent test cases
+let threadCount = 4
+
 #if os(Linux)
     import Glibc
 #else
@@ -43,6 +48,20 @@ class TestCallbackObj: TestCallbackInterface {
             for _ in 0...count {
                 testNoArgsVoidReturn()
             }
+
+        case TestCase.callbackHandle:
+            start = clock()
+            for _ in 0...count {
+                testCallbackHandle(cb: self)
+            }
+
+        case TestCase.concurrentCallbackHandle:
+            start = clock()
+            DispatchQueue.concurrentPerform(iterations: threadCount) { _ in
+                for _ in 0...(count / UInt64(threadCount)) {
+                    testCallbackHandle(cb: self)
+                }
+            }
         }
         let end = clock()
         return UInt64((end - start) * 1000000000 / CLOCKS_PER_SEC)
@@ -2,7 +2,6 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::env;
 use std::time::Duration;
 
 mod cli;
@@ -17,6 +16,8 @@ pub enum TestCase {
     Function,
     VoidReturn,
     NoArgsVoidReturn,
+    CallbackHandle,
+    ConcurrentCallbackHandle,
 }
 
 pub trait TestCallbackInterface {
@@ -31,6 +32,7 @@ pub fn test_function(_a: i32, _b: i32, data: TestData) -> String {
 }
 pub fn test_void_return(_a: i32, _b: i32, _data: TestData) {}
 pub fn test_no_args_void_return() {}
+pub fn test_callback_handle(_cb: Box<dyn TestCallbackInterface>) {}
 
 pub fn run_benchmarks(language: String, cb: Box<dyn TestCallbackInterface>) {
     let args = Args::parse_for_run_benchmarks();
@@ -54,6 +56,18 @@ pub fn run_benchmarks(language: String, cb: Box<dyn TestCallbackInterface>) {
             })
         });
 
+    c.benchmark_group("handles")
+        .bench_function(format!("{language}-handles-callback"), |b| {
+            b.iter_custom(|count| {
+                Duration::from_nanos(cb.run_test(TestCase::CallbackHandle, count))
+            })
+        })
+        .bench_function(format!("{language}-handles-callback-concurrent"), |b| {
+            b.iter_custom(|count| {
+                Duration::from_nanos(cb.run_test(TestCase::ConcurrentCallbackHandle, count))
+            })
+        });
+
     c.benchmark_group("callbacks")
         // These benchmarks are extra noisy, take extra time to measure them and set a higher noise
         // threshold
@@ -2,6 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::sync::atomic::{AtomicU32, Ordering};
+
 trait ForeignGetters {
     fn get_bool(&self, v: bool, argument_two: bool) -> Result<bool, SimpleError>;
     fn get_string(&self, v: String, arg2: bool) -> Result<String, SimpleError>;
@@ -100,7 +102,7 @@ impl Default for RustGetters {
 
 // Use `Send+Send` because we want to store the callback in an exposed
 // `Send+Sync` object.
-#[allow(clippy::wrong_self_convention)]
+#[allow(clippy::wrong_self_convention, dead_code)]
 trait StoredForeignStringifier: Send + Sync + std::fmt::Debug {
     fn from_simple_type(&self, value: i32) -> String;
     fn from_complex_type(&self, values: Option<Vec<Option<f64>>>) -> String;
@@ -111,8 +113,24 @@ pub struct RustStringifier {
     callback: Box<dyn StoredForeignStringifier>,
 }
 
+// Counted by the lifecycle hook of `StoredForeignStringifier`, which is set by
+// `RustStringifier::new()`
+static DETACHED_STRINGIFIERS: AtomicU32 = AtomicU32::new(0);
+
+fn count_detached_stringifier(_handle: u64) {
+    DETACHED_STRINGIFIERS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn detached_stringifiers_count() -> u32 {
+    DETACHED_STRINGIFIERS.load(Ordering::SeqCst)
+}
+
 impl RustStringifier {
     fn new(callback: Box<dyn StoredForeignStringifier>) -> Self {
+        <dyn StoredForeignStringifier as uniffi::ForeignCallbackInterface>::set_lifecycle_hooks(
+            None,
+            Some(count_detached_stringifier),
+        );
         RustStringifier { callback }
     }
 
@@ -120,6 +138,10 @@ impl RustStringifier {
     fn from_simple_type(&self, value: i32) -> String {
         self.callback.from_simple_type(value)
     }
+
+    fn is_callback_alive(&self) -> bool {
+        uniffi::is_alive(&*self.callback)
+    }
 }
 
 uniffi::include_scaffolding!("callbacks");
@@ -141,3 +141,33 @@ listOf(1, 2).forEach { v ->
     assert(expected == observed) { "callback is sent on construction: $expected != $observed" }
 }
 rustStringifier.destroy()
+
+// Detaching a stringifier that Rust still holds
+val detachedStringifier = StoredKotlinStringifier()
+val detachedRustStringifier = RustStringifier(detachedStringifier)
+val detachedCount = detachedStringifiersCount()
+assert(detachedRustStringifier.isCallbackAlive())
+uniffiDetach(detachedStringifier)
+assert(detachedStringifiersCount() == detachedCount + 1u)
+assert(!detachedRustStringifier.isCallbackAlive())
+try {
+    detachedRustStringifier.fromSimpleType(1)
+    throw RuntimeException("Calling a detached stringifier should fail")
+} catch (e: InternalException) {
+    // Expected failure, Rust doesn't call the object once it's detached
+}
+detachedRustStringifier.destroy()
+assert(detachedStringifiersCount() == detachedCount + 1u)
+
+// 3. The generated conformance checks, enabled in `uniffi.toml`.
+val conformance = ForeignGettersConformance(KotlinGetters(), threads = 2, iterations = 5)
+conformance.checkGetString("some string", false)
+conformance.checkGetString("bad-argument", false)
+conformance.checkGetList(listOf(1, 2), true)
+conformance.checkGetNothing("bad-argument")
+try {
+    conformance.checkGetNothing("unexpected-error")
+    throw RuntimeException("Expected an AssertionError")
+} catch (e: AssertionError) {
+    // Expected failure
+}
@@ -108,6 +108,50 @@ class StoredStringifierTest(unittest.TestCase):
             observed = rust_stringifier.from_simple_type(v)
             self.assertEqual(expected, observed, f"callback is sent on construction: {expected} != {observed}")
 
+    def test_detach(self):
+        python_stringifier = StoredPythonStringifier()
+        rust_stringifier = RustStringifier(python_stringifier)
+        detached = detached_stringifiers_count()
+        self.assertTrue(rust_stringifier.is_callback_alive())
+        uniffi_detach(python_stringifier)
+        self.assertEqual(detached_stringifiers_count(), detached + 1)
+        self.assertFalse(rust_stringifier.is_callback_alive())
+        # Rust doesn't call the object once it's detached
+        with self.assertRaises(InternalError):
+            rust_stringifier.from_simple_type(1)
+        # Dropping the Rust object doesn't detach it again
+        del rust_stringifier
+        self.assertEqual(detached_stringifiers_count(), detached + 1)
+
+    def test_detach_object_passed_twice(self):
+        # Each time the object is passed to Rust it gets a handle of its own, and detaching it
+        # detaches all of them
+        python_stringifier = StoredPythonStringifier()
+        first = RustStringifier(python_stringifier)
+        second = RustStringifier(python_stringifier)
+        detached = detached_stringifiers_count()
+        uniffi_detach(python_stringifier)
+        self.assertEqual(detached_stringifiers_count(), detached + 2)
+        self.assertFalse(first.is_callback_alive())
+        self.assertFalse(second.is_callback_alive())
+
+# Callback interfaces are protocols, so objects that don't inherit from them can be passed too.
+class DuckStringifier:
+    def from_simple_type(self, value):
+        return f"duck: {value}"
+
+    def from_complex_type(self, values):
+        return f"duck: {values}"
+
+class DuckTypingTest(unittest.TestCase):
+    def test_duck_typed_callback(self):
+        rust_stringifier = RustStringifier(DuckStringifier())
+        self.assertEqual(rust_stringifier.from_simple_type(1), "duck: 1")
+
+    def test_missing_method(self):
+        with self.assertRaises(TypeError):
+            RustStringifier(object())
+
 class TestCallbackErrors(unittest.TestCase):
     def test_simple_errors(self):
         callback = PythonGetters()
@@ -128,6 +172,16 @@ class TestCallbackErrors(unittest.TestCase):
         self.assertEqual(cm.exception.code, 20)
         with self.assertRaises(ComplexError.UnexpectedErrorWithReason) as cm:
             rust_getters.get_option(callback, "unexpected-error", True)
-        self.assertEqual(cm.exception.reason, repr(ValueError("unexpected value")))
+        self.assertEqual(cm.exception.reason, "ValueError: unexpected value")
+
+class TestConformanceChecks(unittest.TestCase):
+    def test_conformance(self):
+        conformance = ForeignGettersConformance(PythonGetters(), threads=2, iterations=5)
+        conformance.check_get_string("some string", False)
+        conformance.check_get_string("bad-argument", False)
+        conformance.check_get_list([1, 2], True)
+        conformance.check_get_nothing("bad-argument")
+        with self.assertRaises(AssertionError):
+            conformance.check_get_nothing("unexpected-error")
 
 unittest.main()
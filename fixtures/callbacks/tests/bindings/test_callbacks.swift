@@ -103,6 +103,30 @@ do {
         assert(expected == observed, "callback is sent on construction: \(expected) != \(observed)")
     }
 
+    // Detaching a stringifier that Rust still holds.  Calling it after that fails, which the
+    // proc-macro fixture tests with a method that returns the failure.
+    do {
+        let detachedStringifier = StoredSwiftStringifier()
+        let detachedRustStringifier = RustStringifier(callback: detachedStringifier)
+        let detachedCount = detachedStringifiersCount()
+        assert(detachedRustStringifier.isCallbackAlive())
+        uniffiDetach(detachedStringifier)
+        assert(detachedStringifiersCount() == detachedCount + 1)
+        assert(!detachedRustStringifier.isCallbackAlive())
+    }
+
+    // Each time an object is passed to Rust it gets a handle of its own, and detaching it detaches
+    // all of them
+    do {
+        let detachedStringifier = StoredSwiftStringifier()
+        let first = RustStringifier(callback: detachedStringifier)
+        let second = RustStringifier(callback: detachedStringifier)
+        let detachedCount = detachedStringifiersCount()
+        uniffiDetach(detachedStringifier)
+        assert(detachedStringifiersCount() == detachedCount + 2)
+        assert(!first.isCallbackAlive())
+        assert(!second.isCallbackAlive())
+    }
 
     // 3. Error handling
     do {
@@ -132,7 +156,8 @@ do {
         assertionFailure("getString() should have thrown an exception")
     } catch ComplexError.UnexpectedErrorWithReason(let reason) {
         // Expected exception
-        assert(reason == String(describing: SomeOtherError()))
+        // The reason is the error's type name, which includes the module name, and its description
+        assert(reason.hasSuffix("SomeOtherError: \(SomeOtherError())"))
     }
 
     do {
@@ -150,3 +175,18 @@ do {
     }
 
 }
+
+// 3. The generated conformance checks, enabled in `uniffi.toml`.
+do {
+    let conformance = ForeignGettersConformance(SwiftGetters(), threads: 2, iterations: 5)
+    try! conformance.checkGetString(v: "some string", arg2: false)
+    try! conformance.checkGetString(v: "bad-argument", arg2: false)
+    try! conformance.checkGetList(v: [1, 2], arg2: true)
+    try! conformance.checkGetNothing(v: "bad-argument")
+    do {
+        try conformance.checkGetNothing(v: "unexpected-error")
+        assertionFailure("checkGetNothing() should have thrown an exception")
+    } catch is UniffiConformanceFailure {
+        // Expected failure
+    }
+}
@@ -0,0 +1,17 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+# The code from `uniffi.toml` is in the bindings, and the overridden `Point` is passed to and from
+# Rust like the generated one would be
+
+import code_hooks
+from code_hooks import *
+
+assert code_hooks.footer_function() == 1
+assert Direction.UP.is_vertical()
+assert opposite(Direction.UP) == Direction.DOWN
+
+mid = midpoint(Point(0.0, 0.0), Point(2.0, 4.0))
+assert mid.is_overridden()
+assert mid == Point(1.0, 2.0)
@@ -0,0 +1,16 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The code from `uniffi.toml` is in the bindings, and the overridden `Point` is passed to and from
+// Rust like the generated one would be
+
+import code_hooks
+
+assert(footerFunction() == 1.0)
+assert(Direction.up.isVertical)
+assert(opposite(direction: .up) == .down)
+
+let mid = midpoint(a: Point(x: 0.0, y: 0.0), b: Point(x: 2.0, y: 4.0))
+assert(mid.isOverridden())
+assert(mid == Point(x: 1.0, y: 2.0))
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_code_hooks.py",
+    "tests/bindings/test_code_hooks.kts",
+    "tests/bindings/test_code_hooks.swift",
+);
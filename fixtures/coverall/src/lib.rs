@@ -3,8 +3,9 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
+use std::num::NonZeroU32;
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 use std::time::SystemTime;
 
 use once_cell::sync::Lazy;
@@ -42,11 +43,32 @@ fn throw_macro_error() -> Result<(), CoverallMacroError> {
     Err(CoverallMacroError::TooManyMacros)
 }
 
+#[uniffi::export]
+fn average(total: u32, count: NonZeroU32) -> u32 {
+    total / count
+}
+
+#[uniffi::export]
+fn next_char(c: char) -> char {
+    char::from_u32(u32::from(c) + 1).unwrap_or(c)
+}
+
+#[uniffi::export]
+fn reverse_chars(chars: Vec<char>) -> Vec<char> {
+    chars.into_iter().rev().collect()
+}
+
 #[derive(Debug, thiserror::Error, uniffi::Error)]
 #[uniffi(flat_error)]
 pub enum CoverallFlatMacroError {
     #[error("Too many variants: {num}")]
     TooManyVariants { num: i16 },
+    #[error("Too many patches: {patches}")]
+    TooManyPatches {
+        #[uniffi(error_field)]
+        limit: u32,
+        patches: u32,
+    },
 }
 
 #[uniffi::export]
@@ -54,6 +76,14 @@ fn throw_flat_macro_error() -> Result<(), CoverallFlatMacroError> {
     Err(CoverallFlatMacroError::TooManyVariants { num: 88 })
 }
 
+#[uniffi::export]
+fn check_patches(patches: u32) -> Result<(), CoverallFlatMacroError> {
+    match patches {
+        0..=10 => Ok(()),
+        _ => Err(CoverallFlatMacroError::TooManyPatches { limit: 10, patches }),
+    }
+}
+
 pub enum CoverallRichErrorNoVariantData {
     TooManyPlainVariants,
 }
@@ -139,7 +169,21 @@ pub struct DictWithDefaults {
     integer: u64,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Coordinate {
+    x: i32,
+    y: f64,
+}
+
+fn add_coordinates(a: Coordinate, b: Coordinate) -> Coordinate {
+    Coordinate {
+        x: a.x + b.x,
+        y: a.y + b.y,
+    }
+}
+
 #[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum MaybeSimpleDict {
     Yeah { d: SimpleDict },
     Nah,
@@ -384,6 +428,10 @@ impl Coveralls {
         value.reverse();
         value
     }
+
+    fn sum_bytes(&self, value: &[u8]) -> u64 {
+        value.iter().map(|&b| u64::from(b)).sum()
+    }
 }
 
 impl Drop for Coveralls {
@@ -420,6 +468,41 @@ impl Patch {
     }
 }
 
+// The weak reference tests need an object that's declared with proc-macros, since the types of
+// the UDL aren't in the same module as the functions that are exported with proc-macros
+#[derive(uniffi::Object)]
+pub struct WeakTarget {
+    value: u32,
+}
+
+#[uniffi::export]
+impl WeakTarget {
+    #[uniffi::constructor]
+    fn new(value: u32) -> Arc<Self> {
+        Arc::new(Self { value })
+    }
+
+    fn get_value(&self) -> u32 {
+        self.value
+    }
+}
+
+#[uniffi::export]
+fn downgrade_target(target: Arc<WeakTarget>) -> Weak<WeakTarget> {
+    Arc::downgrade(&target)
+}
+
+// The target is dropped when this returns, so the weak reference is already dead
+#[uniffi::export]
+fn downgrade_dropped_target(value: u32) -> Weak<WeakTarget> {
+    Arc::downgrade(&WeakTarget::new(value))
+}
+
+#[uniffi::export]
+fn is_target_alive(target: Weak<WeakTarget>) -> bool {
+    target.upgrade().is_some()
+}
+
 // This is a small implementation of a counter that allows waiting on one thread,
 // and counting on another thread. We use it to test that the UniFFI generated scaffolding
 // doesn't introduce unexpected locking behaviour between threads.
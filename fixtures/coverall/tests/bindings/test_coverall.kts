@@ -261,4 +261,6 @@ assert(d.integer == 42UL)
 // Test bytes
 Coveralls("test_bytes").use { coveralls ->
     assert(coveralls.reverse("123".toByteArray(Charsets.UTF_8)).toString(Charsets.UTF_8) == "321")
+    assert(coveralls.sumBytes(byteArrayOf(1, 2, -1)) == 258UL)
+    assert(coveralls.sumBytes(byteArrayOf()) == 0UL)
 }
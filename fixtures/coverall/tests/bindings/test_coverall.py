@@ -178,6 +178,9 @@ class TestCoverall(unittest.TestCase):
         # Test panics, which should cause InternalError to be raised
         with self.assertRaises(InternalError) as cm:
             coveralls.maybe_throw_complex(4)
+        # The backtrace is kept out of the message, it's only captured with `RUST_BACKTRACE`
+        self.assertEqual(str(cm.exception), "Invalid input")
+        self.assertIsInstance(cm.exception.rust_backtrace, (str, type(None)))
 
     def test_enums(self):
         e = get_simple_flat_macro_enum(0)
@@ -277,6 +280,10 @@ class TestCoverall(unittest.TestCase):
     def test_bytes(self):
         coveralls = Coveralls("test_bytes")
         self.assertEqual(coveralls.reverse(b"123"), b"321")
+        self.assertEqual(coveralls.reverse(b"12" * 1000), b"21" * 1000)
+        self.assertEqual(coveralls.sum_bytes(b"\x01\x02\xff"), 258)
+        self.assertEqual(coveralls.sum_bytes(bytearray(b"\x01\x02")), 3)
+        self.assertEqual(coveralls.sum_bytes(b""), 0)
 
 class TraitsTest(unittest.TestCase):
     def test_simple(self):
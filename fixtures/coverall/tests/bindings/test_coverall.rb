@@ -128,6 +128,20 @@ class TestCoverall < Test::Unit::TestCase
     end
   end
 
+  def test_flat_error_fields
+    Coverall.check_patches 10
+    err = assert_raise Coverall::CoverallFlatMacroError::TooManyPatches do
+      Coverall.check_patches 11
+    end
+    assert_equal err.message, 'Too many patches: 11'
+    assert_equal err.limit, 10
+
+    err = assert_raise Coverall::CoverallFlatMacroError::TooManyVariants do
+      Coverall.throw_flat_macro_error
+    end
+    assert_equal err.message, 'Too many variants: 88'
+  end
+
   def test_complex_errors
     coveralls = Coverall::Coveralls.new 'test_complex_errors'
     assert_equal coveralls.maybe_throw_complex(0), true
@@ -257,6 +271,51 @@ class TestCoverall < Test::Unit::TestCase
     coveralls = Coverall::Coveralls.new "test_bytes"
     assert_equal coveralls.reverse("123"), "321"
     assert_equal coveralls.reverse("123").encoding, Encoding::BINARY
+    assert_equal coveralls.reverse("12" * 1000), "21" * 1000
+    assert_equal coveralls.sum_bytes("\x01\x02\xff"), 258
+    assert_equal coveralls.sum_bytes(""), 0
+  end
+
+  def test_repr_c_records
+    sum = Coverall.add_coordinates(Coverall::Coordinate.new(1, 0.5), Coverall::Coordinate.new(-3, 2.0))
+    assert_equal sum, Coverall::Coordinate.new(-2, 2.5)
+  end
+
+  def test_invalid_arguments
+    assert_equal Coverall.average(12, 4), 3
+    # Rust can't lift a zero `NonZeroU32`
+    assert_raise_message /Failed to convert arg 'count'/ do
+      Coverall.average(12, 0)
+    end
+    assert_raise ArgumentError do
+      Coverall.average(12, 0)
+    end
+  end
+
+  def test_chars
+    assert_equal Coverall.next_char("a"), "b"
+    assert_equal Coverall.next_char("\u{1F980}"), "\u{1F981}"
+    assert_equal Coverall.reverse_chars(["a", "\u00e9", "\u{1F980}"]), ["\u{1F980}", "\u00e9", "a"]
+    assert_raise ArgumentError do
+      Coverall.next_char("ab")
+    end
+  end
+
+  def test_weak_refs
+    target = Coverall::WeakTarget.new 42
+    weak_target = Coverall.downgrade_target target
+    assert weak_target.alive?
+    assert Coverall.is_target_alive(weak_target)
+    assert_equal weak_target.upgrade.get_value, 42
+
+    dead_target = Coverall.downgrade_dropped_target 7
+    assert !dead_target.alive?
+    assert_nil dead_target.upgrade
+    assert !Coverall.is_target_alive(dead_target)
+
+    assert_raise TypeError do
+      Coverall.is_target_alive target
+    end
   end
 
 end
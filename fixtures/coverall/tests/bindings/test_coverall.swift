@@ -246,4 +246,6 @@ do {
 do {
     let coveralls = Coveralls(name: "test_bytes")
     assert(coveralls.reverse(value: Data("123".utf8)) == Data("321".utf8))
+    assert(coveralls.sumBytes(value: Data([1, 2, 255])) == 258)
+    assert(coveralls.sumBytes(value: Data()) == 0)
 }
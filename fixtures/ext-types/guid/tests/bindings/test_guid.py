@@ -22,13 +22,13 @@ class TestGuid(unittest.TestCase):
         self.assertEqual(helper.maybe_guid, None)
 
     def test_get_guid_errors(self):
-        # This is testing `get_guid` which never returns a result, so everything
-        # is InternalError representing a panic.
+        # This is testing `get_guid` which never returns a result, so the Guids that fail to lift
+        # are an InvalidArgumentError, and everything else is InternalError representing a panic.
         # The fixture hard-codes some Guid strings to return specific errors.
-        with self.assertRaisesRegex(InternalError, "Failed to convert arg 'value': The Guid is too short"):
+        with self.assertRaisesRegex(InvalidArgumentError, "Failed to convert arg 'value': The Guid is too short"):
             get_guid("")
 
-        with self.assertRaisesRegex(InternalError, "Failed to convert arg 'value': Something unexpected went wrong"):
+        with self.assertRaisesRegex(InvalidArgumentError, "Failed to convert arg 'value': Something unexpected went wrong"):
             get_guid("unexpected")
 
         with self.assertRaisesRegex(InternalError, "guid value caused a panic!"):
@@ -40,7 +40,7 @@ class TestGuid(unittest.TestCase):
         with self.assertRaises(GuidError.TooShort):
             try_get_guid("")
 
-        with self.assertRaisesRegex(InternalError, "Failed to convert arg 'value': Something unexpected went wrong"):
+        with self.assertRaisesRegex(InvalidArgumentError, "Failed to convert arg 'value': Something unexpected went wrong"):
             try_get_guid("unexpected")
 
         with self.assertRaisesRegex(InternalError, "guid value caused a panic!"):
@@ -0,0 +1,37 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+pub struct Pair {
+    pub first_value: u32,
+    pub second_value: u32,
+}
+
+pub enum Shape {
+    Square {
+        side_length: u32,
+    },
+    Rectangle {
+        side_length: u32,
+        other_side_length: u32,
+    },
+}
+
+fn make_pair(first_value: u32, second_value: u32) -> Pair {
+    Pair {
+        first_value,
+        second_value,
+    }
+}
+
+fn shape_area(the_shape: Shape) -> u32 {
+    match the_shape {
+        Shape::Square { side_length } => side_length * side_length,
+        Shape::Rectangle {
+            side_length,
+            other_side_length,
+        } => side_length * other_side_length,
+    }
+}
+
+uniffi::include_scaffolding!("field_naming");
@@ -0,0 +1,17 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `uniffi.toml` sets `field_naming = "snake_case"`, so fields and arguments are snake_case
+
+import uniffi.field_naming.*
+
+val pair = makePair(first_value = 1u, second_value = 2u)
+assert(pair.first_value == 1u)
+assert(pair.second_value == 2u)
+assert(pair == Pair(first_value = 1u, second_value = 2u))
+
+val square = Shape.Square(side_length = 3u)
+assert(square.side_length == 3u)
+assert(shapeArea(the_shape = square) == 9u)
+assert(shapeArea(Shape.Rectangle(side_length = 2u, other_side_length = 5u)) == 10u)
@@ -0,0 +1,18 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+# `uniffi.toml` sets `field_naming = "camelCase"`, so fields and arguments are camelCase
+
+from field_naming import *
+
+pair = make_pair(firstValue=1, secondValue=2)
+assert pair.firstValue == 1
+assert pair.secondValue == 2
+assert not hasattr(pair, "first_value")
+assert pair == Pair(firstValue=1, secondValue=2)
+
+square = Shape.SQUARE(sideLength=3)
+assert square.sideLength == 3
+assert shape_area(theShape=square) == 9
+assert shape_area(Shape.RECTANGLE(sideLength=2, otherSideLength=5)) == 10
@@ -0,0 +1,15 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `uniffi.toml` sets `field_naming = "snake_case"`, so fields and arguments are snake_case
+
+import field_naming
+
+let pair = makePair(first_value: 1, second_value: 2)
+assert(pair.first_value == 1)
+assert(pair.second_value == 2)
+assert(pair == Pair(first_value: 1, second_value: 2))
+
+assert(shapeArea(the_shape: .square(side_length: 3)) == 9)
+assert(shapeArea(the_shape: .rectangle(side_length: 2, other_side_length: 5)) == 10)
@@ -0,0 +1,5 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_field_naming.py",
+    "tests/bindings/test_field_naming.kts",
+    "tests/bindings/test_field_naming.swift",
+);
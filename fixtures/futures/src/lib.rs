@@ -3,9 +3,13 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use std::{
+    cell::Cell,
     future::Future,
     pin::Pin,
-    sync::{Arc, Mutex, MutexGuard},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
     task::{Context, Poll, Waker},
     thread,
     time::Duration,
@@ -186,6 +190,26 @@ impl Megaphone {
     pub async fn say_after_with_tokio(self: Arc<Self>, ms: u16, who: String) -> String {
         say_after_with_tokio(ms, who).await.to_uppercase()
     }
+
+    /// An async method that opts out of the tokio runtime of its `impl` block.
+    #[uniffi::async_runtime("none")]
+    pub async fn say_after_without_runtime(self: Arc<Self>, ms: u16, who: String) -> String {
+        assert!(
+            tokio::runtime::Handle::try_current().is_err(),
+            "polled in the tokio runtime"
+        );
+        say_after(ms, who).await.to_uppercase()
+    }
+
+    /// An async method that replaces the tokio runtime of its `impl` block with `CustomRuntime`.
+    #[uniffi::async_runtime(CustomRuntime)]
+    pub async fn say_after_with_custom_runtime(self: Arc<Self>, ms: u16, who: String) -> String {
+        assert!(
+            tokio::runtime::Handle::try_current().is_err(),
+            "polled in the tokio runtime"
+        );
+        say_after_with_custom_runtime(ms, who).await.to_uppercase()
+    }
 }
 
 // Say something after a certain amount of time, by using `tokio::time::sleep`
@@ -197,6 +221,52 @@ pub async fn say_after_with_tokio(ms: u16, who: String) -> String {
     format!("Hello, {who} (with Tokio)!")
 }
 
+thread_local! {
+    static IN_CUSTOM_RUNTIME: Cell<bool> = const { Cell::new(false) };
+}
+
+/// An async runtime that's implemented by the fixture, which only records that the futures are
+/// polled in it.
+pub struct CustomRuntime;
+
+impl uniffi::AsyncRuntime for CustomRuntime {
+    type Future<F: Future + uniffi::MaybeSend> = CustomRuntimeFuture<F>;
+
+    fn wrap<F: Future + uniffi::MaybeSend>(future: F) -> CustomRuntimeFuture<F> {
+        CustomRuntimeFuture {
+            future: Box::pin(future),
+        }
+    }
+}
+
+pub struct CustomRuntimeFuture<F> {
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for CustomRuntimeFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        IN_CUSTOM_RUNTIME.with(|in_runtime| in_runtime.set(true));
+        let poll = self.future.as_mut().poll(cx);
+        IN_CUSTOM_RUNTIME.with(|in_runtime| in_runtime.set(false));
+        poll
+    }
+}
+
+// Say something after a certain amount of time, checking that the future is polled in
+// `CustomRuntime`.
+#[uniffi::export(async_runtime = CustomRuntime)]
+pub async fn say_after_with_custom_runtime(ms: u16, who: String) -> String {
+    TimerFuture::new(Duration::from_millis(ms.into())).await;
+    assert!(
+        IN_CUSTOM_RUNTIME.with(Cell::get),
+        "not polled in the custom runtime"
+    );
+
+    format!("Hello, {who} (with a custom runtime)!")
+}
+
 #[derive(uniffi::Record)]
 pub struct MyRecord {
     pub a: String,
@@ -208,6 +278,34 @@ pub async fn new_my_record(a: String, b: u32) -> MyRecord {
     MyRecord { a, b }
 }
 
+/// An object with only async methods, which is a Swift actor.
+#[derive(uniffi::Object)]
+pub struct Echo {
+    count: AtomicU32,
+}
+
+#[uniffi::export]
+impl Echo {
+    #[uniffi::constructor]
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            count: AtomicU32::new(0),
+        })
+    }
+
+    /// Repeat `message` after a certain time.
+    pub async fn echo_after(self: Arc<Self>, ms: u16, message: String) -> String {
+        TimerFuture::new(Duration::from_millis(ms.into())).await;
+        self.count.fetch_add(1, Ordering::SeqCst);
+        message
+    }
+
+    /// The number of messages repeated so far.
+    pub async fn count(self: Arc<Self>) -> u32 {
+        self.count.load(Ordering::SeqCst)
+    }
+}
+
 /// Non-blocking timer future.
 pub struct BrokenTimerFuture {
     shared_state: Arc<Mutex<SharedState>>,
@@ -86,6 +86,25 @@ class TestFutures(unittest.TestCase):
 
         asyncio.run(test())
 
+    def test_method_without_runtime(self):
+        async def test():
+            megaphone = new_megaphone()
+            result_alice = await megaphone.say_after_without_runtime(20, 'Alice')
+            self.assertEqual(result_alice, 'HELLO, ALICE!')
+
+        asyncio.run(test())
+
+    def test_with_custom_runtime(self):
+        async def test():
+            result_alice = await say_after_with_custom_runtime(20, 'Alice')
+            self.assertEqual(result_alice, 'Hello, Alice (with a custom runtime)!')
+
+            megaphone = new_megaphone()
+            result_bob = await megaphone.say_after_with_custom_runtime(20, 'Bob')
+            self.assertEqual(result_bob, 'HELLO, BOB (WITH A CUSTOM RUNTIME)!')
+
+        asyncio.run(test())
+
     def test_fallible(self):
         async def test():
             result = await fallible_me(False)
@@ -261,4 +261,16 @@ Task {
 	counter.leave()
 }
 
+// Test an object that's generated as an actor.
+counter.enter()
+Task {
+	let echo = Echo()
+	async let first = echo.echoAfter(ms: 100, message: "first")
+	async let second = echo.echoAfter(ms: 100, message: "second")
+	let results = await [first, second]
+	assert(results == ["first", "second"])
+	assert(await echo.count() == 2)
+	counter.leave()
+}
+
 counter.wait()
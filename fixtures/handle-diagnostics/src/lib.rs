@@ -0,0 +1,36 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Mutex;
+
+pub struct Resource;
+
+impl Resource {
+    fn new() -> Self {
+        Self
+    }
+}
+
+pub trait Listener: Send + Sync {
+    fn on_event(&self);
+}
+
+// Holds on to a callback interface until it's released
+pub struct Holder {
+    listener: Mutex<Option<Box<dyn Listener>>>,
+}
+
+impl Holder {
+    fn new(listener: Box<dyn Listener>) -> Self {
+        Self {
+            listener: Mutex::new(Some(listener)),
+        }
+    }
+
+    fn release(&self) {
+        self.listener.lock().unwrap().take();
+    }
+}
+
+uniffi::include_scaffolding!("handle_diagnostics");
@@ -0,0 +1,29 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.handle_diagnostics.*
+
+// The number of live handles of a type in the report
+fun liveHandles(typeName: String) = uniffiDebugLiveHandles().split("\n$typeName 0x").size - 1
+
+assert(uniffiDebugLiveHandles().startsWith("0 live handles"))
+
+// Objects are tracked from when they're passed to Kotlin until Kotlin destroys them
+val resource = Resource()
+assert(liveHandles("Resource") == 1)
+assert(uniffiDebugLiveHandles().contains("Resource 0x"))
+resource.destroy()
+assert(liveHandles("Resource") == 0)
+
+// Callback interfaces are tracked from when Rust gets them until Rust drops them
+class KotlinListener : Listener {
+    override fun onEvent() {}
+}
+
+val holder = Holder(KotlinListener())
+assert(liveHandles("Listener") == 1)
+holder.release()
+assert(liveHandles("Listener") == 0)
+holder.destroy()
+assert(liveHandles("Holder") == 0)
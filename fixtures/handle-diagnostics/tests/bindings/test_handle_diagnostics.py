@@ -0,0 +1,31 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from handle_diagnostics import *
+
+# The number of live handles of a type in the report
+def live_handles(type_name):
+    return uniffi_debug_live_handles().count(f"\n{type_name} 0x")
+
+assert uniffi_debug_live_handles().startswith("0 live handles")
+
+# Objects are tracked from when they're passed to Python until Python frees them
+resource = Resource()
+assert live_handles("Resource") == 1
+assert uniffi_debug_live_handles().startswith("1 live handles")
+del resource
+assert live_handles("Resource") == 0
+
+# Callback interfaces are tracked from when Rust gets them until Rust drops them
+class PythonListener:
+    def on_event(self):
+        pass
+
+holder = Holder(PythonListener())
+assert live_handles("Listener") == 1
+assert live_handles("Holder") == 1
+holder.release()
+assert live_handles("Listener") == 0
+del holder
+assert live_handles("Holder") == 0
@@ -0,0 +1,4 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_handle_diagnostics.py",
+    "tests/bindings/test_handle_diagnostics.kts",
+);
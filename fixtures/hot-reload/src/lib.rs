@@ -0,0 +1,44 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{Arc, Mutex};
+
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: Mutex<u32>,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            value: Mutex::new(0),
+        })
+    }
+
+    fn increment(&self) -> u32 {
+        let mut value = self.value.lock().unwrap();
+        *value += 1;
+        *value
+    }
+}
+
+#[uniffi::export(callback_interface)]
+pub trait Doubler {
+    fn double(&self, value: u32) -> u32;
+}
+
+#[uniffi::export]
+fn double_with(doubler: Box<dyn Doubler>, value: u32) -> u32 {
+    doubler.double(value)
+}
+
+/// Calls `on_step` with each number from 1 to `n` and returns the sum of the results
+#[uniffi::export]
+fn sum_steps(n: u32, on_step: impl Fn(u32) -> u32) -> u32 {
+    (1..=n).map(on_step).sum()
+}
+
+uniffi::setup_scaffolding!();
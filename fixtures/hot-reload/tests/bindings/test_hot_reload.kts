@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.hot_reload.*
+
+class KtDoubler : Doubler {
+    override fun double(value: UInt) = value * 2u
+}
+
+fun libraryCopies() = java.io.File(System.getProperty("java.io.tmpdir"))
+    .listFiles { file -> file.name.startsWith("uniffi-uniffi_hot_reload-") }!!
+    .size
+
+val counter = Counter()
+assert(counter.increment() == 1u)
+assert(doubleWith(KtDoubler(), 3u) == 6u)
+val copiesBefore = libraryCopies()
+
+// Reloading the library invalidates the objects from before, while new ones work
+uniffiReload()
+try {
+    counter.increment()
+    throw RuntimeException("objects from before the reload must not be used")
+} catch (e: IllegalStateException) {
+    // Expected
+}
+counter.destroy()
+Counter().use { assert(it.increment() == 1u) }
+assert(doubleWith(KtDoubler(), 4u) == 8u)
+assert(sumSteps(3u) { it } == 6u)
+
+// The copy of the old library was deleted, and the new one replaces it
+assert(libraryCopies() == copiesBefore)
@@ -0,0 +1,34 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import os
+import uniffi_hot_reload
+from uniffi_hot_reload import *
+
+class PyDoubler(Doubler):
+    def double(self, value):
+        return value * 2
+
+counter = Counter()
+assert counter.increment() == 1
+assert double_with(PyDoubler(), 3) == 6
+old_copy = os.path.dirname(uniffi_hot_reload._UniffiLib._name)
+assert os.path.exists(old_copy)
+
+# Reloading the library invalidates the objects from before, while new ones work
+uniffi_reload()
+try:
+    counter.increment()
+except ValueError:
+    pass
+else:
+    raise AssertionError("objects from before the reload must not be used")
+del counter
+assert Counter().increment() == 1
+assert double_with(PyDoubler(), 4) == 8
+assert sum_steps(3, lambda step: step) == 6
+
+# The copy of the old library was deleted
+assert not os.path.exists(old_copy)
+assert os.path.exists(os.path.dirname(uniffi_hot_reload._UniffiLib._name))
@@ -0,0 +1,34 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use serde_json::Value;
+
+pub struct Event {
+    pub kind: String,
+    pub payload: Value,
+}
+
+// Merge the fields of two objects, any other `patch` replaces `base` entirely.
+fn merge(base: Value, patch: Value) -> Value {
+    match (base, patch) {
+        (Value::Object(mut base), Value::Object(patch)) => {
+            base.extend(patch);
+            Value::Object(base)
+        }
+        (_, patch) => patch,
+    }
+}
+
+fn make_event(kind: String, payload: Value) -> Event {
+    Event { kind, payload }
+}
+
+fn array_items(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items,
+        value => vec![value],
+    }
+}
+
+uniffi::include_scaffolding!("json_values");
@@ -0,0 +1,17 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.json_values.*
+import kotlinx.serialization.json.*
+
+fun parse(text: String) = Json.parseToJsonElement(text)
+
+assert(merge(parse("""{"a": 1, "b": [1, 2]}"""), parse("""{"b": null, "c": "x"}""")) == parse("""{"a": 1, "b": null, "c": "x"}"""))
+assert(merge(JsonPrimitive(1), JsonPrimitive("two")) == JsonPrimitive("two"))
+
+val event = makeEvent("click", buildJsonObject { put("x", 1.5) })
+assert(event.kind == "click")
+assert(event.payload.jsonObject["x"]!!.jsonPrimitive.double == 1.5)
+
+assert(arrayItems(parse("""[1, "a", null]""")) == listOf(parse("1"), JsonPrimitive("a"), JsonNull))
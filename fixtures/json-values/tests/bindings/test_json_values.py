@@ -0,0 +1,16 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+from json_values import *
+
+assert merge({"a": 1, "b": [1, 2]}, {"b": None, "c": "x"}) == {"a": 1, "b": None, "c": "x"}
+assert merge(1, "two") == "two"
+
+event = make_event("click", {"x": 1.5, "y": 2})
+assert event.kind == "click"
+assert event.payload == {"x": 1.5, "y": 2}
+assert event == Event("click", {"y": 2, "x": 1.5})
+
+assert array_items([1, "a", None, [True]]) == [1, "a", None, [True]]
+assert array_items(False) == [False]
@@ -0,0 +1,28 @@
+# frozen_string_literal: true
+
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+require 'test/unit'
+require 'json'
+require 'json_values'
+
+class TestJsonValues < Test::Unit::TestCase
+  def test_json_text
+    merged = JsonValues.merge('{"a": 1, "b": [1, 2]}', '{"b": null}')
+    assert_equal JSON.parse(merged), { 'a' => 1, 'b' => nil }
+  end
+
+  def test_json_in_records
+    event = JsonValues.make_event('click', JSON.generate({ 'x' => 1.5 }))
+    assert_equal event.kind, 'click'
+    assert_equal JSON.parse(event.payload), { 'x' => 1.5 }
+  end
+
+  def test_invalid_json
+    assert_raise JsonValues::InternalError do
+      JsonValues.merge('{not json', '{}')
+    end
+  end
+end
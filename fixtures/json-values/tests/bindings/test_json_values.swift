@@ -0,0 +1,23 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import Foundation
+import json_values
+
+let merged = merge(base: ["a": 1, "b": [1, 2]] as [String: Any], patch: ["b": NSNull(), "c": "x"] as [String: Any]) as! [String: Any]
+assert(merged.count == 3)
+assert(merged["a"] as? Int == 1)
+assert(merged["b"] is NSNull)
+assert(merged["c"] as? String == "x")
+assert(merge(base: 1, patch: "two") as? String == "two")
+
+let event = makeEvent(kind: "click", payload: ["x": 1.5])
+assert(event.kind == "click")
+assert((event.payload as! [String: Any])["x"] as? Double == 1.5)
+
+let items = arrayItems(value: [1, "a", NSNull()] as [Any])
+assert(items.count == 3)
+assert(items[0] as? Int == 1)
+assert(items[1] as? String == "a")
+assert(items[2] is NSNull)
@@ -0,0 +1,56 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::{sync::Arc, time::SystemTime};
+
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+pub enum Direction {
+    North,
+    South,
+}
+
+pub enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+pub struct Counter;
+
+impl Counter {
+    fn new() -> Self {
+        Self
+    }
+}
+
+pub struct Event {
+    name: String,
+    when: SystemTime,
+}
+
+pub struct CounterHolder {
+    counter: Arc<Counter>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AnnotationError {
+    #[error("Failed")]
+    Failed,
+}
+
+fn make_point(x: f64, y: f64) -> Point {
+    Point { x, y }
+}
+
+fn turn(direction: Direction) -> Result<Direction, AnnotationError> {
+    Ok(match direction {
+        Direction::North => Direction::South,
+        Direction::South => Direction::North,
+    })
+}
+
+uniffi::include_scaffolding!("kotlin-annotations");
@@ -0,0 +1,94 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use camino::Utf8PathBuf;
+use uniffi::TargetLanguage;
+use uniffi_testing::UniFFITestHelper;
+
+// Each test generates the bindings in a directory of its own, since tests run in parallel
+fn generate_kotlin(test_name: &str) -> anyhow::Result<String> {
+    let tmp_dir = std::env!("CARGO_TARGET_TMPDIR");
+    let crate_name = std::env!("CARGO_PKG_NAME");
+
+    let test_helper = UniFFITestHelper::new(crate_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, test_name)?;
+
+    uniffi::generate_bindings(
+        &Utf8PathBuf::from("src/kotlin-annotations.udl"),
+        None,
+        vec![TargetLanguage::Kotlin],
+        Some(&out_dir),
+        None,
+        false,
+    )?;
+    Ok(std::fs::read_to_string(
+        out_dir.join("uniffi/kotlin_annotations/kotlin_annotations.kt"),
+    )?)
+}
+
+// The declaration of a class that starts with `header`, with the annotations on the lines above it
+// and up to its opening brace
+fn declaration<'a>(code: &'a str, header: &str) -> &'a str {
+    let start = code
+        .find(&format!("\n{header}"))
+        .unwrap_or_else(|| panic!("`{header}` isn't declared"))
+        + 1;
+    let annotations: usize = code[..start]
+        .lines()
+        .rev()
+        .take_while(|line| line.starts_with('@'))
+        .map(|line| line.len() + 1)
+        .sum();
+    let end = start + code[start..].find('{').unwrap();
+    &code[start - annotations..end]
+}
+
+#[test]
+fn parcelize() -> anyhow::Result<()> {
+    let code = generate_kotlin("parcelize")?;
+    for header in [
+        "data class Point ",
+        "enum class Direction",
+        "sealed class Shape",
+    ] {
+        let declaration = declaration(&code, header);
+        assert!(
+            declaration.starts_with("@Parcelize\n") && declaration.contains(": Parcelable"),
+            "not parcelable: {declaration}"
+        );
+    }
+    assert!(code.contains("\nimport kotlinx.parcelize.Parcelize\n"));
+    assert!(code.contains("\nimport android.os.Parcelable\n"));
+
+    // Objects are only valid in this process, so they can't be parcelled
+    let declaration = declaration(&code, "data class CounterHolder ");
+    assert!(!declaration.contains("Parcel"), "{declaration}");
+    Ok(())
+}
+
+#[test]
+fn serializable() -> anyhow::Result<()> {
+    let code = generate_kotlin("serializable")?;
+    for header in [
+        "data class Point ",
+        "data class Event ",
+        "enum class Direction",
+        "sealed class Shape",
+    ] {
+        let declaration = declaration(&code, header);
+        assert!(
+            declaration.contains("@Serializable\n"),
+            "not serializable: {declaration}"
+        );
+    }
+    // The variants of sealed classes are serializable too
+    let words = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert!(words.contains("@Serializable data class Circle("));
+    // Timestamps are serialized as strings
+    assert!(code.contains("@file:UseSerializers(UniffiInstantSerializer::class)"));
+
+    let declaration = declaration(&code, "data class CounterHolder ");
+    assert!(!declaration.contains("@Serializable"), "{declaration}");
+    Ok(())
+}
@@ -0,0 +1,50 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.fixture.kotlin_jni.*
+
+// Numbers are passed as the signed JNI types, make sure the unsigned ones round-trip
+assert(echoNumbers(-1, 255u, -2, 65535u, -3, 4294967295u, -4, ULong.MAX_VALUE) == listOf(
+    ULong.MAX_VALUE, 255uL, ULong.MAX_VALUE - 1uL, 65535uL, ULong.MAX_VALUE - 2uL, 4294967295uL,
+    ULong.MAX_VALUE - 3uL, ULong.MAX_VALUE,
+))
+assert(echoBool(true))
+assert(!echoBool(false))
+
+// `RustBuffer`s are copied to and from byte arrays by the shim
+assert(echoString("") == "")
+assert(echoString("Hello, JNI! 🦀") == "Hello, JNI! 🦀")
+assert(echoBytes(byteArrayOf(0, 1, -1)).contentEquals(byteArrayOf(0, 1, -1)))
+assert(countWords(listOf("a", "b", "a")) == mapOf("a" to 2u, "b" to 1u))
+
+assert(turnRight(Direction.WEST) == Direction.NORTH)
+val circle = translate(Shape.Circle(Point(1.0, 2.0, "center"), 3.0), 1.0, -1.0)
+assert(circle == Shape.Circle(Point(2.0, 1.0, "center"), 3.0))
+val polygon = translate(Shape.Polygon(listOf(Point(0.0, 0.0, null), Point(1.0, 1.0, null))), 1.0, 1.0)
+assert(polygon == Shape.Polygon(listOf(Point(1.0, 1.0, null), Point(2.0, 2.0, null))))
+
+// Errors are lifted from the call status that the shim fills in
+assert(checkedDiv(6, 3) == 2L)
+try {
+    checkedDiv(1, 0)
+    throw RuntimeException("Should have thrown")
+} catch (e: MathException.DivisionByZero) {
+    // Expected
+}
+try {
+    checkedDiv(Long.MIN_VALUE, -1)
+    throw RuntimeException("Should have thrown")
+} catch (e: MathException.Overflow) {
+    assert(e.value == Long.MIN_VALUE)
+}
+
+// Objects are passed as pointers in `Long`s
+Counter(1uL).use { counter ->
+    assert(counter.increment(2uL) == 3uL)
+    Counter(4uL).use { other ->
+        assert(counter.addFrom(other) == 7uL)
+        assert(sumCounters(listOf(counter, other)) == 11uL)
+    }
+    assert(counter.value() == 7uL)
+}
@@ -0,0 +1,63 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::Arc;
+
+#[derive(Clone, Copy)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Clone, Copy)]
+pub enum ShapeKind {
+    Line,
+    Triangle,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShapeError {
+    #[error("wrong number of points for the shape")]
+    WrongPointCount,
+}
+
+pub struct Shape {
+    kind: ShapeKind,
+    points: Vec<Point>,
+}
+
+impl Shape {
+    fn kind(&self) -> ShapeKind {
+        self.kind
+    }
+
+    fn points(&self) -> Vec<Point> {
+        self.points.clone()
+    }
+}
+
+pub trait Describer {
+    fn describe(&self, kind: ShapeKind, point_count: u32) -> String;
+}
+
+fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
+fn make_shape(kind: ShapeKind, points: Vec<Point>) -> Result<Arc<Shape>, ShapeError> {
+    let expected = match kind {
+        ShapeKind::Line => 2,
+        ShapeKind::Triangle => 3,
+    };
+    if points.len() != expected {
+        return Err(ShapeError::WrongPointCount);
+    }
+    Ok(Arc::new(Shape { kind, points }))
+}
+
+fn describe(shape: Arc<Shape>, describer: Box<dyn Describer>) -> String {
+    describer.describe(shape.kind, shape.points.len() as u32)
+}
+
+uniffi::include_scaffolding!("kotlin_split_files");
@@ -0,0 +1,28 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// `uniffi.toml` sets `split_files = true`, so each type below comes from a file of its own, and
+// the bindings only compile if the files share their helpers correctly.
+
+import uniffi.kotlin_split_files.*
+
+assert(origin() == Point(0, 0))
+
+val line = makeShape(ShapeKind.LINE, listOf(origin(), Point(1, 1)))
+assert(line.kind() == ShapeKind.LINE)
+assert(line.points() == listOf(Point(0, 0), Point(1, 1)))
+
+try {
+    makeShape(ShapeKind.TRIANGLE, listOf(origin()))
+    throw RuntimeException("Should have thrown a WrongPointCount exception")
+} catch (e: ShapeException.WrongPointCount) {
+    // Expected failure
+}
+
+class KotlinDescriber : Describer {
+    override fun describe(kind: ShapeKind, pointCount: UInt) = "$kind with $pointCount points"
+}
+
+assert(describe(line, KotlinDescriber()) == "LINE with 2 points")
+line.destroy()
@@ -26,6 +26,24 @@ mod weapon {
     }
 }
 
+mod status {
+    #[derive(uniffi::Enum, Debug)]
+    pub enum Status {
+        Pending = 1,
+        Done,
+        Failed = -1,
+    }
+}
+
+mod color {
+    #[derive(uniffi::Enum, Debug)]
+    #[non_exhaustive]
+    pub enum Color {
+        Red,
+        Green,
+    }
+}
+
 mod state {
     use super::Person;
 
@@ -47,6 +65,12 @@ mod error {
     pub enum FlatError {
         Overflow(String), // UniFFI should ignore this field, since `flat_error` was specified
         DivideByZero,
+        // Only the error fields are passed to the foreign code
+        TooLarge {
+            #[uniffi(error_field)]
+            limit: u64,
+            value: String,
+        },
     }
 
     #[derive(uniffi::Error)]
@@ -61,6 +85,9 @@ mod error {
             match self {
                 Self::Overflow(s) => write!(f, "FlatError::Overflow({s})"),
                 Self::DivideByZero => write!(f, "FlatError::DivideByZero"),
+                Self::TooLarge { limit, value } => {
+                    write!(f, "FlatError::TooLarge({value} > {limit})")
+                }
             }
         }
     }
@@ -88,12 +115,15 @@ mod calc {
 #[uniffi::export(callback_interface)]
 pub trait Logger {
     fn log(&self, message: String);
+    fn flush(&self) -> Result<bool, uniffi::CallbackError>;
 }
 
 pub use calc::Calculator;
+pub use color::Color;
 pub use error::{ComplexError, FlatError};
 pub use person::Person;
 pub use state::State;
+pub use status::Status;
 pub use weapon::Weapon;
 
 mod test_type_ids {
@@ -139,6 +169,18 @@ mod test_type_ids {
             module_path: "uniffi_fixture_metadata".into(),
             name: "Weapon".into(),
         });
+        check_type_id::<Status>(Type::Enum {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: "Status".into(),
+        });
+        check_type_id::<Color>(Type::Enum {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: "Color".into(),
+        });
+        check_type_id::<ComplexError>(Type::Enum {
+            module_path: "uniffi_fixture_metadata".into(),
+            name: "ComplexError".into(),
+        });
         check_type_id::<Arc<Calculator>>(Type::Object {
             module_path: "uniffi_fixture_metadata".into(),
             name: "Calculator".into(),
@@ -190,6 +232,10 @@ mod test_metadata {
                         default: None,
                     },
                 ],
+                repr_c: false,
+                ord: false,
+                patch_for: None,
+                docstring: None,
             },
         );
     }
@@ -204,17 +250,22 @@ mod test_metadata {
                 variants: vec![
                     VariantMetadata {
                         name: "Rock".into(),
+                        discr: None,
                         fields: vec![],
                     },
                     VariantMetadata {
                         name: "Paper".into(),
+                        discr: None,
                         fields: vec![],
                     },
                     VariantMetadata {
                         name: "Scissors".into(),
+                        discr: None,
                         fields: vec![],
                     },
                 ],
+                non_exhaustive: false,
+                docstring: None,
             },
         );
     }
@@ -229,10 +280,12 @@ mod test_metadata {
                 variants: vec![
                     VariantMetadata {
                         name: "Uninitialized".into(),
+                        discr: None,
                         fields: vec![],
                     },
                     VariantMetadata {
                         name: "Initialized".into(),
+                        discr: None,
                         fields: vec![FieldMetadata {
                             name: "data".into(),
                             ty: Type::String,
@@ -241,6 +294,7 @@ mod test_metadata {
                     },
                     VariantMetadata {
                         name: "Complete".into(),
+                        discr: None,
                         fields: vec![FieldMetadata {
                             name: "result".into(),
                             ty: Type::Record {
@@ -251,6 +305,63 @@ mod test_metadata {
                         }],
                     },
                 ],
+                non_exhaustive: false,
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_enum_with_discriminants() {
+        check_metadata(
+            &status::UNIFFI_META_UNIFFI_FIXTURE_METADATA_ENUM_STATUS,
+            EnumMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "Status".into(),
+                variants: vec![
+                    VariantMetadata {
+                        name: "Pending".into(),
+                        discr: Some(1),
+                        fields: vec![],
+                    },
+                    VariantMetadata {
+                        name: "Done".into(),
+                        discr: None,
+                        fields: vec![],
+                    },
+                    VariantMetadata {
+                        name: "Failed".into(),
+                        discr: Some(-1),
+                        fields: vec![],
+                    },
+                ],
+                non_exhaustive: false,
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_non_exhaustive_enum() {
+        check_metadata(
+            &color::UNIFFI_META_UNIFFI_FIXTURE_METADATA_ENUM_COLOR,
+            EnumMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "Color".into(),
+                variants: vec![
+                    VariantMetadata {
+                        name: "Red".into(),
+                        discr: None,
+                        fields: vec![],
+                    },
+                    VariantMetadata {
+                        name: "Green".into(),
+                        discr: None,
+                        fields: vec![],
+                    },
+                ],
+                non_exhaustive: true,
+                docstring: None,
             },
         );
     }
@@ -266,13 +377,26 @@ mod test_metadata {
                     variants: vec![
                         VariantMetadata {
                             name: "Overflow".into(),
+                            discr: None,
                             fields: vec![],
                         },
                         VariantMetadata {
                             name: "DivideByZero".into(),
+                            discr: None,
                             fields: vec![],
                         },
+                        VariantMetadata {
+                            name: "TooLarge".into(),
+                            discr: None,
+                            fields: vec![FieldMetadata {
+                                name: "limit".into(),
+                                ty: Type::UInt64,
+                                default: None,
+                            }],
+                        },
                     ],
+                    non_exhaustive: false,
+                    docstring: None,
                 },
                 is_flat: true,
             },
@@ -290,10 +414,12 @@ mod test_metadata {
                     variants: vec![
                         VariantMetadata {
                             name: "NotFound".into(),
+                            discr: None,
                             fields: vec![],
                         },
                         VariantMetadata {
                             name: "PermissionDenied".into(),
+                            discr: None,
                             fields: vec![FieldMetadata {
                                 name: "reason".into(),
                                 ty: Type::String,
@@ -302,6 +428,7 @@ mod test_metadata {
                         },
                         VariantMetadata {
                             name: "InvalidWeapon".into(),
+                            discr: None,
                             fields: vec![FieldMetadata {
                                 name: "weapon".into(),
                                 ty: Type::Enum {
@@ -312,6 +439,8 @@ mod test_metadata {
                             }],
                         },
                     ],
+                    non_exhaustive: false,
+                    docstring: None,
                 },
                 is_flat: false,
             },
@@ -327,6 +456,7 @@ mod test_metadata {
                 name: "Calculator".into(),
                 imp: ObjectImpl::Struct,
                 uniffi_traits: vec![],
+                docstring: None,
             },
         );
     }
@@ -357,6 +487,19 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export]
+    #[allow(unused)]
+    pub fn test_func_borrows_object(calculator: &Calculator) {
+        unimplemented!()
+    }
+
+    #[uniffi::export]
+    #[uniffi::sensitive(person)]
+    #[allow(unused)]
+    pub fn test_func_sensitive(name: String, person: Person) {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     #[allow(unused)]
     pub async fn test_async_func(person: Person, weapon: Weapon) -> String {
@@ -368,6 +511,11 @@ mod test_function_metadata {
         unimplemented!()
     }
 
+    #[uniffi::export(namespace = "storage")]
+    pub fn test_func_in_sub_namespace() {
+        unimplemented!()
+    }
+
     #[uniffi::export]
     pub trait CalculatorDisplay: Send + Sync {
         fn display_result(&self, val: String);
@@ -391,6 +539,31 @@ mod test_function_metadata {
         }
     }
 
+    #[uniffi::export(serialized)]
+    impl Calculator {
+        #[allow(unused)]
+        pub fn multiply(&self, a: u8, b: u8) -> u8 {
+            unimplemented!()
+        }
+    }
+
+    // Impl blocks with only constructors, which still declare the policy of the object
+    #[uniffi::export(serialized)]
+    impl Calculator {
+        #[uniffi::constructor]
+        pub fn new_serialized() -> Arc<Self> {
+            unimplemented!()
+        }
+    }
+
+    #[uniffi::export(serialized)]
+    impl Calculator {
+        #[uniffi::constructor]
+        pub fn new_serialized_again() -> Arc<Self> {
+            unimplemented!()
+        }
+    }
+
     #[test]
     fn test_function() {
         check_metadata(
@@ -418,6 +591,7 @@ mod test_function_metadata {
                 return_type: Some(Type::String),
                 throws: None,
                 checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC.checksum()),
+                docstring: None,
             },
         );
     }
@@ -436,6 +610,7 @@ mod test_function_metadata {
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_NO_RETURN.checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -460,6 +635,7 @@ mod test_function_metadata {
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_THAT_THROWS.checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -482,6 +658,68 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_NO_RETURN_THAT_THROWS
                         .checksum(),
                 ),
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_function_that_borrows_object() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_BORROWS_OBJECT,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_borrows_object".into(),
+                is_async: false,
+                inputs: vec![FnParamMetadata {
+                    by_ref: true,
+                    ..FnParamMetadata::simple(
+                        "calculator",
+                        Type::Object {
+                            module_path: "uniffi_fixture_metadata".into(),
+                            name: "Calculator".into(),
+                            imp: ObjectImpl::Struct,
+                        },
+                    )
+                }],
+                return_type: None,
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_BORROWS_OBJECT
+                        .checksum(),
+                ),
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_function_with_sensitive_argument() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_SENSITIVE,
+            FnMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                name: "test_func_sensitive".into(),
+                is_async: false,
+                inputs: vec![
+                    FnParamMetadata::simple("name", Type::String),
+                    FnParamMetadata {
+                        sensitive: true,
+                        ..FnParamMetadata::simple(
+                            "person",
+                            Type::Record {
+                                module_path: "uniffi_fixture_metadata".into(),
+                                name: "Person".into(),
+                            },
+                        )
+                    },
+                ],
+                return_type: None,
+                throws: None,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_FUNC_SENSITIVE.checksum(),
+                ),
+                docstring: None,
             },
         );
     }
@@ -502,9 +740,51 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ADD.checksum(),
                 ),
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_object_concurrency() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_OBJECT_CONCURRENCY_CALCULATOR_MULTIPLY,
+            ObjectConcurrencyMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                self_name: "Calculator".into(),
+                policy: ConcurrencyPolicy::Serialized,
+                methods: vec!["multiply".into()],
+            },
+        );
+        // Each constructor-only block gets an item of its own
+        for metadata in [
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_OBJECT_CONCURRENCY_CALCULATOR_NEW_SERIALIZED[..],
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_OBJECT_CONCURRENCY_CALCULATOR_NEW_SERIALIZED_AGAIN,
+        ] {
+            check_metadata(
+                metadata,
+                ObjectConcurrencyMetadata {
+                    module_path: "uniffi_fixture_metadata".into(),
+                    self_name: "Calculator".into(),
+                    policy: ConcurrencyPolicy::Serialized,
+                    methods: vec![],
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn test_sub_namespace() {
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_SUB_NAMESPACE_TEST_FUNC_IN_SUB_NAMESPACE,
+            SubNamespaceMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                fn_name: "test_func_in_sub_namespace".into(),
+                namespace: "storage".into(),
             },
         );
     }
@@ -538,6 +818,7 @@ mod test_function_metadata {
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_ASYNC_FUNC.checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -563,6 +844,7 @@ mod test_function_metadata {
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_FUNC_TEST_ASYNC_FUNC_THAT_THROWS
                         .checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -583,10 +865,12 @@ mod test_function_metadata {
                 return_type: Some(Type::UInt8),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_ASYNC_SUB
                         .checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -608,10 +892,12 @@ mod test_function_metadata {
                 }),
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATOR_GET_DISPLAY
                         .checksum(),
                 ),
+                docstring: None,
             },
         );
     }
@@ -626,6 +912,7 @@ mod test_function_metadata {
                 index: 0,
                 name: "display_result".into(),
                 is_async: false,
+                has_default: false,
                 inputs: vec![
                     FnParamMetadata::simple("val", Type::String),
                 ],
@@ -634,6 +921,7 @@ mod test_function_metadata {
                 takes_self_by_arc: false,
                 checksum: Some(UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_CALCULATORDISPLAY_DISPLAY_RESULT
                     .checksum()),
+                docstring: None,
             },
         );
     }
@@ -645,6 +933,9 @@ mod test_function_metadata {
             CallbackInterfaceMetadata {
                 module_path: "uniffi_fixture_metadata".into(),
                 name: "Logger".into(),
+                main_thread: false,
+                closure: false,
+                docstring: None,
             },
         );
         check_metadata(
@@ -655,6 +946,7 @@ mod test_function_metadata {
                 index: 0,
                 name: "log".into(),
                 is_async: false,
+                has_default: false,
                 inputs: vec![FnParamMetadata::simple("message", Type::String)],
                 return_type: None,
                 throws: None,
@@ -662,6 +954,31 @@ mod test_function_metadata {
                 checksum: Some(
                     UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_LOG.checksum(),
                 ),
+                docstring: None,
+            },
+        );
+    }
+
+    #[test]
+    fn test_callback_interface_method_that_returns_callback_error() {
+        // The foreign code sees the `Ok` type, unexpected errors never reach it
+        check_metadata(
+            &UNIFFI_META_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_FLUSH,
+            TraitMethodMetadata {
+                module_path: "uniffi_fixture_metadata".into(),
+                trait_name: "Logger".into(),
+                index: 1,
+                name: "flush".into(),
+                is_async: false,
+                has_default: false,
+                inputs: vec![],
+                return_type: Some(Type::Boolean),
+                throws: None,
+                takes_self_by_arc: false,
+                checksum: Some(
+                    UNIFFI_META_CONST_UNIFFI_FIXTURE_METADATA_METHOD_LOGGER_FLUSH.checksum(),
+                ),
+                docstring: None,
             },
         );
     }
@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+pub struct TodoList {
+    items: Mutex<Vec<String>>,
+}
+
+impl TodoList {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add_item(&self, item: String) {
+        self.items.lock().unwrap().push(item);
+    }
+
+    fn get_items(&self) -> Vec<String> {
+        self.items.lock().unwrap().clone()
+    }
+}
+
+fn count_items(list: Arc<TodoList>) -> u32 {
+    list.items.lock().unwrap().len() as u32
+}
+
+uniffi::include_scaffolding!("open_classes");
@@ -0,0 +1,45 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.open_classes.*
+
+// A subclass that wraps a Rust object can override methods, call the Rust ones and be passed to
+// Rust
+class CountingTodoList : TodoList() {
+    var added = 0
+
+    override fun addItem(item: String) {
+        added += 1
+        super.addItem(item)
+    }
+}
+
+val counting = CountingTodoList()
+counting.addItem("Write tests")
+counting.addItem("Run them")
+assert(counting.added == 2)
+assert(counting.getItems() == listOf("Write tests", "Run them"))
+assert(countItems(counting) == 2u)
+counting.destroy()
+
+// A test double that doesn't wrap a Rust object
+class FakeTodoList : TodoList(NoPointer) {
+    override fun getItems() = listOf("Fake item")
+}
+
+val fake = FakeTodoList()
+assert(fake.getItems() == listOf("Fake item"))
+try {
+    fake.addItem("Not overridden")
+    throw RuntimeException("Calling a method that isn't overridden should fail")
+} catch (e: IllegalStateException) {
+    // Expected failure
+}
+try {
+    countItems(fake)
+    throw RuntimeException("Passing a test double to Rust should fail")
+} catch (e: IllegalStateException) {
+    // Expected failure
+}
+fake.destroy()
@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import open_classes
+
+// A subclass that wraps a Rust object can override methods, call the Rust ones and be passed to
+// Rust.  The classes are in another module, so this only compiles if they're `open`.
+class CountingTodoList: TodoList {
+    var added = 0
+
+    override func addItem(item: String) {
+        added += 1
+        super.addItem(item: item)
+    }
+}
+
+do {
+    let counting = CountingTodoList()
+    counting.addItem(item: "Write tests")
+    counting.addItem(item: "Run them")
+    assert(counting.added == 2)
+    assert(counting.getItems() == ["Write tests", "Run them"])
+    assert(countItems(list: counting) == 2)
+}
+
+// A test double that doesn't wrap a Rust object.  Calling a method that it doesn't override
+// crashes, so that isn't tested.
+class FakeTodoList: TodoList {
+    init() {
+        super.init(noPointer: TodoList.NoPointer())
+    }
+
+    required init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
+        super.init(unsafeFromRawPointer: pointer)
+    }
+
+    override func getItems() -> [String] {
+        return ["Fake item"]
+    }
+}
+
+do {
+    let fake = FakeTodoList()
+    assert(fake.getItems() == ["Fake item"])
+}
@@ -0,0 +1,4 @@
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test_open_classes.kts",
+    "tests/bindings/test_open_classes.swift",
+);
@@ -6,9 +6,48 @@ use crate::{BasicError, Object};
 
 #[uniffi::export(callback_interface)]
 pub trait TestCallbackInterface {
+    const PROTOCOL_VERSION: u32 = 2;
+    const GREETING: &'static str = "hello";
+
     fn do_nothing(&self);
     fn add(&self, a: u32, b: u32) -> u32;
     fn optional(&self, a: Option<u32>) -> u32;
     fn try_parse_int(&self, value: String) -> Result<u32, BasicError>;
     fn callback_handler(&self, h: std::sync::Arc<Object>) -> u32;
+    // Unexpected errors, like dividing by zero, return the fallback value instead of panicking
+    #[uniffi::on_error(0)]
+    fn divide(&self, a: u32, b: u32) -> u32;
+    // Unexpected errors are returned as a `CallbackError`
+    fn checked_divide(&self, a: u32, b: u32) -> Result<u32, uniffi::CallbackError>;
+    // Both: the declared error is the inner `Result`, and other errors are a `CallbackError`
+    fn checked_parse_int(
+        &self,
+        value: String,
+    ) -> Result<Result<u32, BasicError>, uniffi::CallbackError>;
+    // Foreign implementations can leave this out and use the default implementation
+    fn double(&self, a: u32) -> u32 {
+        self.add(a, a)
+    }
+}
+
+// Foreign implementations are always called on the main thread, so the trait doesn't need `Send`
+#[uniffi::export(callback_interface, main_thread)]
+pub trait MainThreadCallback {
+    fn thread_name(&self) -> String;
+}
+
+// Called while Rust is borrowing an object, so that the foreign code can try to free it mid-call
+#[uniffi::export(callback_interface)]
+pub trait BorrowingCallback: Send {
+    fn during_call(&self);
+}
+
+// Held by `CallbackHolder`, until the foreign code detaches it
+#[uniffi::export(callback_interface)]
+pub trait HeldCallback: Send {
+    fn value(&self) -> Result<u32, uniffi::CallbackError>;
+    // The foreign object runs this on itself, which doesn't attach it
+    fn doubled(&self) -> u32 {
+        self.value().unwrap_or(0) * 2
+    }
 }
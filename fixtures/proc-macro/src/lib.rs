@@ -2,13 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    num::{FpCategory, NonZeroU32},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, Weak,
+    },
+    thread,
+    time::Duration,
+};
 
 mod callback_interface;
 
-use callback_interface::TestCallbackInterface;
+use callback_interface::{
+    BorrowingCallback, HeldCallback, MainThreadCallback, TestCallbackInterface,
+};
 
-#[derive(uniffi::Record)]
+#[derive(uniffi::Record, PartialEq, Eq, Hash)]
 pub struct One {
     inner: i32,
 }
@@ -31,6 +42,22 @@ pub struct Three {
     obj: Arc<Object>,
 }
 
+// Passed as a C struct rather than in a RustBuffer
+#[derive(uniffi::Record, Clone, Copy)]
+#[repr(C)]
+pub struct Point {
+    x: f64,
+    y: f64,
+}
+
+// Also defines `SettingsPatch` and `Settings::apply_patch()`
+#[derive(uniffi::Record)]
+#[uniffi(patch)]
+pub struct Settings {
+    name: String,
+    volume: u32,
+}
+
 // An object that's not used anywhere (ie, in records, function signatures, etc)
 // should not break things.
 #[derive(uniffi::Object)]
@@ -44,6 +71,34 @@ impl Unused {
     }
 }
 
+// An object with a method that consumes it
+#[derive(uniffi::Object)]
+pub struct Sentence {
+    words: Mutex<Vec<String>>,
+}
+
+#[uniffi::export]
+impl Sentence {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            words: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn add_word(&self, word: String) {
+        self.words.lock().unwrap().push(word);
+    }
+
+    fn finish(self) -> String {
+        self.words.into_inner().unwrap().join(" ")
+    }
+
+    fn finish_with(self, punctuation: String) -> String {
+        self.finish() + &punctuation
+    }
+}
+
 #[uniffi::export]
 pub trait Trait: Send + Sync {
     fn name(&self) -> String;
@@ -77,6 +132,11 @@ impl Object {
         MaybeBool::Uncertain
     }
 
+    // `self: Arc<Self>` methods can move their reference to the object into another thread
+    fn is_heavy_in_thread(self: Arc<Self>) -> MaybeBool {
+        std::thread::spawn(move || self.is_heavy()).join().unwrap()
+    }
+
     fn get_trait(&self, inc: Option<Arc<dyn Trait>>) -> Arc<dyn Trait> {
         inc.unwrap_or_else(|| Arc::new(TraitImpl {}))
     }
@@ -97,6 +157,18 @@ fn take_two(two: Two) -> String {
     two.a
 }
 
+// `two` is lifted out of a `RustBuffer`, which is wiped once it's been read
+#[uniffi::export]
+#[uniffi::sensitive(two)]
+fn take_sensitive_two(prefix: String, two: Two) -> String {
+    format!("{prefix}{}", two.a)
+}
+
+#[uniffi::export]
+fn make_two(a: String) -> Two {
+    Two { a, b: None }
+}
+
 #[uniffi::export]
 fn test_callback_interface(cb: Box<dyn TestCallbackInterface>) {
     cb.do_nothing();
@@ -113,6 +185,161 @@ fn test_callback_interface(cb: Box<dyn TestCallbackInterface>) {
         Err(BasicError::UnexpectedError { .. }),
     ));
     assert_eq!(42, cb.callback_handler(Object::new()));
+    assert_eq!(5, cb.divide(10, 2));
+    assert_eq!(0, cb.divide(10, 0));
+    assert_eq!(Ok(5), cb.checked_divide(10, 2));
+    assert_eq!(Ok(Ok(10)), cb.checked_parse_int("10".to_string()));
+    assert_eq!(
+        Ok(Err(BasicError::InvalidInput)),
+        cb.checked_parse_int("ten".to_string())
+    );
+    assert_eq!(6, cb.double(3));
+    assert_eq!(<dyn TestCallbackInterface>::PROTOCOL_VERSION, 2);
+    assert_eq!(cb.protocol_version(), 2);
+    assert_eq!(cb.greeting(), "hello");
+}
+
+/// The class name of the exception that `checked_divide()` raised, or its result
+#[uniffi::export]
+fn checked_divide_error(cb: Box<dyn TestCallbackInterface>, a: u32, b: u32) -> String {
+    match cb.checked_divide(a, b) {
+        Ok(v) => v.to_string(),
+        Err(uniffi::CallbackError::UnexpectedException { class_name, .. }) => class_name,
+        Err(e) => panic!("unexpected error: {}", e),
+    }
+}
+
+/// The class name of the exception that `checked_parse_int()` raised, or its result
+#[uniffi::export]
+fn checked_parse_int_error(cb: Box<dyn TestCallbackInterface>, value: String) -> String {
+    match cb.checked_parse_int(value) {
+        Ok(Ok(v)) => v.to_string(),
+        Ok(Err(e)) => format!("{e:?}"),
+        Err(uniffi::CallbackError::UnexpectedException { class_name, .. }) => class_name,
+        Err(e) => panic!("unexpected error: {}", e),
+    }
+}
+
+// Counted by the lifecycle hook of `HeldCallback`, which is set by `CallbackHolder::new()`
+static HELD_CALLBACKS_ATTACHED: AtomicU32 = AtomicU32::new(0);
+
+fn count_held_callback(_handle: u64) {
+    HELD_CALLBACKS_ATTACHED.fetch_add(1, Ordering::SeqCst);
+}
+
+/// The number of `HeldCallback`s that Rust was given
+#[uniffi::export]
+fn held_callbacks_attached() -> u32 {
+    HELD_CALLBACKS_ATTACHED.load(Ordering::SeqCst)
+}
+
+#[derive(uniffi::Object)]
+pub struct CallbackHolder {
+    cb: Mutex<Box<dyn HeldCallback>>,
+}
+
+#[uniffi::export]
+impl CallbackHolder {
+    #[uniffi::constructor]
+    fn new(cb: Box<dyn HeldCallback>) -> Arc<Self> {
+        <dyn HeldCallback as uniffi::ForeignCallbackInterface>::set_lifecycle_hooks(
+            Some(count_held_callback),
+            None,
+        );
+        Arc::new(Self { cb: Mutex::new(cb) })
+    }
+
+    fn is_alive(&self) -> bool {
+        uniffi::is_alive(&**self.cb.lock().unwrap())
+    }
+
+    /// The callback's value, or the reason that calling it failed
+    fn value(&self) -> Result<u32, BasicError> {
+        self.cb
+            .lock()
+            .unwrap()
+            .value()
+            .map_err(|e| BasicError::UnexpectedError {
+                reason: e.to_string(),
+            })
+    }
+}
+
+/// Calls the callback from a thread spawned by Rust and returns the name of the thread it ran on
+#[uniffi::export]
+fn call_from_background_thread(cb: Box<dyn MainThreadCallback + Send + Sync>) -> String {
+    std::thread::spawn(move || cb.thread_name()).join().unwrap()
+}
+
+/// Calls `on_step` with each number from 1 to `n` and returns the sum of the results
+#[uniffi::export]
+fn sum_steps(n: u32, on_step: impl Fn(u32) -> u32) -> u32 {
+    (1..=n).map(on_step).sum()
+}
+
+/// A counter whose increments would race without `serialized`, since they read the value and
+/// store it back in separate steps
+#[derive(uniffi::Object)]
+pub struct SlowCounter {
+    value: AtomicU32,
+}
+
+#[uniffi::export(serialized)]
+impl SlowCounter {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            value: Default::default(),
+        })
+    }
+
+    fn increment(&self) -> u32 {
+        let value = self.value.load(Ordering::SeqCst) + 1;
+        thread::sleep(Duration::from_millis(1));
+        self.value.store(value, Ordering::SeqCst);
+        value
+    }
+
+    fn value(&self) -> u32 {
+        self.value.load(Ordering::SeqCst)
+    }
+}
+
+/// Reports each step from 0 to `steps`, which are throttled except for the first and last ones
+#[uniffi::export]
+fn count_steps(steps: u64, progress: uniffi::ProgressSink) -> u64 {
+    for completed in 0..=steps {
+        progress.report(completed, steps);
+    }
+    steps
+}
+
+/// A record that happens to have the same name as `uniffi::ProgressSink`, which is passed like any
+/// other record
+#[derive(uniffi::Record)]
+pub struct ProgressSink {
+    pub steps: u64,
+}
+
+#[uniffi::export]
+fn progress_sink_steps(sink: ProgressSink) -> u64 {
+    sink.steps
+}
+
+// Functions in a sub-namespace, like `storage.key_for()` in Python
+#[uniffi::export(namespace = "storage")]
+fn key_for(table: String, id: u32) -> String {
+    format!("{table}/{id}")
+}
+
+#[uniffi::export(namespace = "storage")]
+fn key_of_two(two: Two) -> String {
+    format!("two/{}", two.a)
+}
+
+#[uniffi::export]
+fn call_greeter(name: String, greeter: Box<dyn Fn(String) -> String + Send + Sync>) -> String {
+    greeter(name)
 }
 
 // Type that's defined in the UDL and not wrapped with #[uniffi::export]
@@ -127,7 +354,7 @@ fn make_zero() -> Zero {
     }
 }
 
-#[derive(uniffi::Enum)]
+#[derive(uniffi::Enum, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MaybeBool {
     True,
     False,
@@ -139,6 +366,101 @@ fn enum_identity(value: MaybeBool) -> MaybeBool {
     value
 }
 
+#[uniffi::export]
+fn count_maybe_bools(values: Vec<MaybeBool>) -> HashMap<MaybeBool, u32> {
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+}
+
+#[uniffi::export]
+fn count_ones(values: Vec<One>) -> HashMap<One, u32> {
+    let mut counts = HashMap::new();
+    for value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    counts
+}
+
+#[uniffi::export]
+fn maybe_bools_by_id(values: Vec<MaybeBool>) -> HashMap<u32, MaybeBool> {
+    (0..).zip(values).collect()
+}
+
+// `FpCategory` is defined in `std`, so it can't derive `uniffi::Enum`.  This copy of its
+// definition generates the same code as the derive, for the type that's in scope.
+#[uniffi::remote(Enum)]
+pub enum FpCategory {
+    Nan,
+    Infinite,
+    Zero,
+    Subnormal,
+    Normal,
+}
+
+#[uniffi::export]
+fn classify(value: f64) -> FpCategory {
+    value.classify()
+}
+
+#[derive(uniffi::Enum)]
+pub enum HttpStatus {
+    Ok = 200,
+    Created,
+    NotFound = 404,
+}
+
+#[uniffi::export]
+fn status_code(status: HttpStatus) -> u16 {
+    status as u16
+}
+
+#[derive(uniffi::Enum)]
+#[non_exhaustive]
+pub enum Shape {
+    Point,
+    Circle { radius: f64 },
+}
+
+#[uniffi::export]
+fn shape_radius(shape: Shape) -> f64 {
+    match shape {
+        Shape::Point => 0.0,
+        Shape::Circle { radius } => radius,
+    }
+}
+
+#[uniffi::export]
+fn make_circle(radius: f64) -> Shape {
+    Shape::Circle { radius }
+}
+
+// Stands in for a crate that doesn't know about UniFFI
+mod vendor {
+    pub struct Version {
+        pub major: u32,
+        pub minor: u32,
+    }
+}
+
+use vendor::Version;
+
+#[uniffi::remote(Record)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[uniffi::export]
+fn next_minor_version(version: Version) -> Version {
+    Version {
+        major: version.major,
+        minor: version.minor + 1,
+    }
+}
+
 #[derive(uniffi::Error, Debug, PartialEq, Eq)]
 #[uniffi(handle_unknown_callback_error)]
 pub enum BasicError {
@@ -169,6 +491,14 @@ pub enum FlatError {
     // are allowed for flat errors
     #[error("OS error: {0}")]
     OsError(std::io::Error),
+
+    // Only the error fields are passed to the foreign code, along with the message
+    #[error("Can't do stuff {times} times")]
+    TooManyTimes {
+        #[uniffi(error_field)]
+        limit: u32,
+        times: u32,
+    },
 }
 
 #[uniffi::export]
@@ -176,6 +506,7 @@ impl Object {
     fn do_stuff(&self, times: u32) -> Result<(), FlatError> {
         match times {
             0 => Err(FlatError::InvalidInput),
+            times if times > 100 => Err(FlatError::TooManyTimes { limit: 100, times }),
             _ => {
                 // do stuff
                 Ok(())
@@ -184,4 +515,208 @@ impl Object {
     }
 }
 
+#[uniffi::export]
+pub const MAX_POINTS: u32 = 100;
+
+#[uniffi::export]
+pub const DEFAULT_NAME: &str = "unnamed";
+
+#[uniffi::export]
+pub const SCALE: f64 = -0.5;
+
+// A hand-written version of what the `bitflags!` macro generates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Flags)]
+#[uniffi(flags(READ, WRITE, EXECUTE, READ_WRITE))]
+pub struct Permissions(u32);
+
+impl Permissions {
+    pub const READ: Self = Self(1);
+    pub const WRITE: Self = Self(1 << 1);
+    pub const EXECUTE: Self = Self(1 << 2);
+    pub const READ_WRITE: Self = Self(Self::READ.0 | Self::WRITE.0);
+
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    pub const fn from_bits_truncate(bits: u32) -> Self {
+        Self(bits & 0b111)
+    }
+}
+
+#[uniffi::export]
+fn add_permissions(perms: Permissions, extra: Permissions) -> Permissions {
+    Permissions(perms.0 | extra.0)
+}
+
+#[uniffi::export]
+fn can_write(perms: Permissions) -> bool {
+    perms.0 & Permissions::WRITE.0 != 0
+}
+
+// Includes a bit that the bindings don't have a name for, like a flag added in a later version
+#[uniffi::export]
+fn future_permissions() -> Permissions {
+    Permissions(Permissions::READ.0 | 1 << 8)
+}
+
+// Items gated with `cfg`.  The fixture is built without the `gated` feature, so only
+// `without_gated_feature()` is in the bindings.
+#[uniffi::export]
+#[cfg(feature = "gated")]
+fn with_gated_feature() -> u32 {
+    1
+}
+
+#[uniffi::export]
+#[cfg(not(feature = "gated"))]
+fn without_gated_feature() -> u32 {
+    0
+}
+
+#[uniffi::export]
+impl Object {
+    #[cfg(feature = "gated")]
+    fn gated_method(&self) -> u32 {
+        1
+    }
+}
+
+uniffi::export_logger!();
+
+#[uniffi::export]
+fn log_message(message: String) {
+    uniffi::deps::log::warn!("{message}");
+}
+
+#[uniffi::export]
+fn check_secret(secret: uniffi::Secret<String>) -> bool {
+    secret.expose() == "hunter2"
+}
+
+#[uniffi::export]
+fn reverse_secret(secret: uniffi::Secret<Vec<u8>>) -> uniffi::Secret<Vec<u8>> {
+    uniffi::Secret::new(secret.expose().iter().rev().copied().collect())
+}
+
+#[uniffi::export]
+fn count_zeros(bytes: &[u8]) -> u32 {
+    bytes.iter().filter(|&&b| b == 0).count() as u32
+}
+
+// `&T` arguments borrow the object without cloning its `Arc`
+#[uniffi::export]
+fn object_is_heavy(obj: &Object) -> MaybeBool {
+    obj.is_heavy()
+}
+
+// The foreign code keeps a borrowed object alive until the call returns, even if it's closed
+// during the call
+#[uniffi::export]
+fn object_is_heavy_after(obj: &Object, cb: Box<dyn BorrowingCallback>) -> MaybeBool {
+    cb.during_call();
+    obj.is_heavy()
+}
+
+#[uniffi::export]
+fn trait_name(t: &dyn Trait) -> String {
+    t.name()
+}
+
+#[uniffi::export]
+fn repeat_byte(byte: u8, count: u32) -> Vec<u8> {
+    vec![byte; count as usize]
+}
+
+#[uniffi::export]
+fn midpoint(a: Point, b: Point) -> Point {
+    Point {
+        x: (a.x + b.x) / 2.0,
+        y: (a.y + b.y) / 2.0,
+    }
+}
+
+#[uniffi::export]
+fn first_point(points: Vec<Point>) -> Option<Point> {
+    points.first().copied()
+}
+
+#[uniffi::export]
+fn patch_settings(mut settings: Settings, patch: SettingsPatch) -> Settings {
+    settings.apply_patch(patch);
+    settings
+}
+
+#[uniffi::export]
+fn max_uuid(ids: Vec<uuid::Uuid>) -> Option<uuid::Uuid> {
+    ids.into_iter().max()
+}
+
+#[uniffi::export]
+fn sum_decimals(values: Vec<rust_decimal::Decimal>) -> rust_decimal::Decimal {
+    values.into_iter().sum()
+}
+
+#[uniffi::export]
+fn join_path(base: std::path::PathBuf, name: String) -> std::path::PathBuf {
+    base.join(name)
+}
+
+#[uniffi::export]
+fn loopback_addr(v6: bool) -> std::net::IpAddr {
+    if v6 {
+        std::net::Ipv6Addr::LOCALHOST.into()
+    } else {
+        std::net::Ipv4Addr::LOCALHOST.into()
+    }
+}
+
+#[uniffi::export]
+fn with_port(addr: std::net::SocketAddr, port: u16) -> std::net::SocketAddr {
+    std::net::SocketAddr::new(addr.ip(), port)
+}
+
+// Values over 1 KiB are compressed, so that the tests cover both the compressed and the
+// uncompressed forms.
+#[uniffi::export]
+fn repeat_text(text: uniffi::Compressed<String>, count: u32) -> uniffi::Compressed<String> {
+    uniffi::set_compression_threshold(1024);
+    uniffi::Compressed::new(text.into_inner().repeat(count as usize))
+}
+
+// Values over 1 KiB are passed in a shared buffer, so that the tests cover both transports.
+#[uniffi::export]
+fn repeat_shared_text(text: uniffi::Shared<String>, count: u32) -> uniffi::Shared<String> {
+    uniffi::Shared::with_threshold(text.into_inner().repeat(count as usize), 1024)
+}
+
+#[uniffi::export]
+fn downgrade_object(obj: Arc<Object>) -> Weak<Object> {
+    Arc::downgrade(&obj)
+}
+
+#[uniffi::export]
+fn is_object_alive(obj: Weak<Object>) -> bool {
+    obj.upgrade().is_some()
+}
+
+#[uniffi::export]
+fn next_char(c: char) -> Option<char> {
+    char::from_u32(u32::from(c) + 1)
+}
+
+#[uniffi::export]
+fn divide_evenly(total: u32, parts: NonZeroU32) -> u32 {
+    total / parts
+}
+
+// Swift can only catch the error of a zero `parts` from a function that throws
+#[uniffi::export]
+fn divide_exactly(total: u32, parts: NonZeroU32) -> Result<u32, BasicError> {
+    if total % parts != 0 {
+        return Err(BasicError::InvalidInput);
+    }
+    Ok(total / parts)
+}
+
 uniffi::include_scaffolding!("proc-macro");
@@ -3,18 +3,108 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 import uniffi.fixture.proc_macro.*;
+import uniffi.fixture.proc_macro.storage.keyFor
+import uniffi.fixture.proc_macro.storage.keyOfTwo
+import kotlin.concurrent.thread
 
 val one = makeOne(123)
 assert(one.inner == 123)
 
 val two = Two("a")
 assert(takeTwo(two) == "a")
+assert(takeSensitiveTwo("b", two) == "ba")
+
+// `Two` and `Point` are pooled, see `uniffi.toml`, so recycled instances are reused
+val recycledTwo = Two("x", listOf(true))
+Two.recycle(recycledTwo)
+val madeTwo = makeTwo("b")
+assert(madeTwo === recycledTwo)
+assert(madeTwo == Two("b"))
+assert(makeTwo("c") !== recycledTwo)
+
+// The pool keeps at most `record_pool_size` instances, the others are dropped
+val recycledTwos = (1..5).map { Two("r$it") }
+recycledTwos.forEach { Two.recycle(it) }
+val reusedTwos = (1..5).map { makeTwo("m$it") }
+assert((0 until 4).all { reusedTwos[it] === recycledTwos[it] })
+assert(recycledTwos.none { it === reusedTwos[4] })
+assert(reusedTwos.map { it.a } == (1..5).map { "m$it" })
+
+// The pool is shared by all threads, and each recycled instance is only reused once
+val sharedTwos = (1..4).map { Two("s$it") }
+sharedTwos.forEach { Two.recycle(it) }
+val threadTwos = java.util.Collections.synchronizedList(mutableListOf<Two>())
+(1..4).map { i -> thread { threadTwos.add(makeTwo("t$i")) } }.forEach { it.join() }
+assert(threadTwos.all { made -> sharedTwos.any { it === made } })
+assert(sharedTwos.all { shared -> threadTwos.count { it === shared } == 1 })
+assert(threadTwos.map { it.a }.toSet() == (1..4).map { "t$it" }.toSet())
+
+// `usage_hooks` is enabled, see `uniffi.toml`
+val usedApis = mutableListOf<String>()
+UniffiUsage.hook = { name -> usedApis.add(name) }
+Object().isHeavy()
+makeOne(1)
+UniffiUsage.hook = null
+makeOne(2)
+assert(usedApis == listOf("Object.new", "Object.is_heavy", "make_one"))
 
 var obj = Object()
 obj = Object.namedCtor(1u)
 assert(obj.isHeavy() == MaybeBool.UNCERTAIN)
+assert(obj.isHeavyInThread() == MaybeBool.UNCERTAIN)
+assert(objectIsHeavy(obj) == MaybeBool.UNCERTAIN)
+
+// Destroying an object while Rust borrows it frees it when the call returns
+val borrowed = Object()
+assert(objectIsHeavyAfter(borrowed, object : BorrowingCallback {
+    override fun duringCall() = borrowed.destroy()
+}) == MaybeBool.UNCERTAIN)
+try {
+    borrowed.isHeavy()
+    throw RuntimeException("Object can't be used after it was destroyed")
+} catch (e: IllegalStateException) {
+    // Expected
+}
+assert(traitName(obj.getTrait(null)) == "TraitImpl")
+
+val sentence = Sentence()
+sentence.addWord("hello")
+sentence.addWord("world")
+assert(sentence.finish() == "hello world")
+try {
+    sentence.addWord("again")
+    throw RuntimeException("Sentence can't be used after it was consumed")
+} catch (e: IllegalStateException) {
+    // It's expected
+}
+
+val greeting = Sentence()
+greeting.addWord("hi")
+assert(greeting.finishWith("!") == "hi!")
 
 assert(enumIdentity(MaybeBool.TRUE) == MaybeBool.TRUE)
+assert(countMaybeBools(listOf(MaybeBool.TRUE, MaybeBool.FALSE, MaybeBool.TRUE)) == mapOf(MaybeBool.TRUE to 2u, MaybeBool.FALSE to 1u))
+assert(countOnes(listOf(One(1), One(2), One(1))) == mapOf(One(1) to 2u, One(2) to 1u))
+assert(maybeBoolsById(listOf(MaybeBool.UNCERTAIN, MaybeBool.TRUE)) == mapOf(0u to MaybeBool.UNCERTAIN, 1u to MaybeBool.TRUE))
+
+assert(classify(0.0) == FpCategory.ZERO)
+assert(classify(Double.NaN) == FpCategory.NAN)
+assert(HttpStatus.CREATED.value == 201L)
+assert(statusCode(HttpStatus.NOT_FOUND) == 404.toUShort())
+assert(makeCircle(2.0) == Shape.Circle(2.0))
+assert(shapeRadius(Shape.Point) == 0.0)
+assert(nextMinorVersion(Version(1u, 2u)) == Version(1u, 3u))
+
+assert(MAX_POINTS == 100u)
+assert(DEFAULT_NAME == "unnamed")
+assert(SCALE == -0.5)
+
+val perms = addPermissions(Permissions.READ, Permissions.WRITE)
+assert(perms == Permissions.READ_WRITE)
+assert(Permissions.WRITE in perms)
+assert(Permissions.EXECUTE !in perms)
+assert(canWrite(Permissions.READ or Permissions.WRITE))
+assert(!canWrite(Permissions.READ))
 
 // just make sure this works / doesn't crash
 val three = Three(obj)
@@ -35,6 +125,14 @@ try {
 } catch (e: FlatException) {
 }
 
+try {
+    obj.doStuff(1000u)
+    throw RuntimeException("doStuff should throw if its argument is over the limit")
+} catch (e: FlatException.TooManyTimes) {
+    assert(e.limit == 100u)
+    assert(e.message == "Can't do stuff 1000 times")
+}
+
 
 class KtTestCallbackInterface : TestCallbackInterface {
     override fun doNothing() { }
@@ -59,6 +157,165 @@ class KtTestCallbackInterface : TestCallbackInterface {
         val v = o.takeError(BasicException.InvalidInput());
         return v
     }
+
+    override fun divide(a: UInt, b: UInt) = a / b
+
+    override fun checkedDivide(a: UInt, b: UInt) = a / b
+
+    override fun checkedParseInt(value: String) = tryParseInt(value)
 }
 
 testCallbackInterface(KtTestCallbackInterface())
+assert(TestCallbackInterface.PROTOCOL_VERSION == 2u)
+assert(TestCallbackInterface.GREETING == "hello")
+// `double` isn't implemented, so it calls the default implementation in Rust
+assert(KtTestCallbackInterface().double(3u) == 6u)
+// Dividing by zero throws an `ArithmeticException`, which Rust gets as a `CallbackError`
+assert(checkedDivideError(KtTestCallbackInterface(), 10u, 0u) == "java.lang.ArithmeticException")
+assert(checkedParseIntError(KtTestCallbackInterface(), "force-unexpected-error") == "java.lang.RuntimeException")
+
+// Rust stops calling a callback once it's detached
+class KtHeldCallback : HeldCallback {
+    override fun value() = 21u
+}
+
+val held = KtHeldCallback()
+val holder = CallbackHolder(held)
+val attached = heldCallbacksAttached()
+// The default implementation runs in Rust, without attaching the object again
+assert(held.doubled() == 42u)
+assert(heldCallbacksAttached() == attached)
+assert(holder.isAlive())
+assert(holder.value() == 21u)
+uniffiDetach(held)
+assert(!holder.isAlive())
+try {
+    holder.value()
+    throw RuntimeException("Calling a detached callback should fail")
+} catch (e: BasicException.UnexpectedException) {
+    // Expected
+}
+
+// Use a single thread executor as the main thread
+val mainExecutor = java.util.concurrent.Executors.newSingleThreadExecutor { Thread(it, "test-main") }
+UniffiMainThread.executor = mainExecutor
+
+class KtMainThreadCallback : MainThreadCallback {
+    override fun threadName() = Thread.currentThread().name
+}
+
+// Without the main thread, calls fail rather than risk waiting for themselves
+try {
+    callFromBackgroundThread(KtMainThreadCallback())
+    throw RuntimeException("main thread callbacks must not be called before the thread is set")
+} catch (e: InternalException) {
+    // Expected
+}
+
+UniffiMainThread.thread = mainExecutor.submit<Thread> { Thread.currentThread() }.get()
+assert(callFromBackgroundThread(KtMainThreadCallback()) == "test-main")
+mainExecutor.shutdown()
+
+// Closure arguments take Kotlin lambdas
+assert(sumSteps(3u) { it * 2u } == 12u)
+assert(callGreeter("Alice") { "Hello, $it" } == "Hello, Alice")
+
+assert(countZeros(byteArrayOf(0, 1, 0)) == 2u)
+assert(countZeros(byteArrayOf()) == 0u)
+repeatByte(7u, 1000u).use { bytes ->
+    assert(bytes.buffer.isDirect())
+    assert(bytes.buffer.remaining() == 1000)
+    assert(bytes.buffer.get(999) == 7.toByte())
+}
+
+assert(midpoint(Point(0.0, 0.0), Point(2.0, 4.0)) == Point(1.0, 2.0))
+val recycledPoint = Point(5.0, 5.0)
+Point.recycle(recycledPoint)
+val mid = midpoint(Point(0.0, 0.0), Point(2.0, 4.0))
+assert(mid === recycledPoint)
+assert(mid == Point(1.0, 2.0))
+assert(firstPoint(listOf(Point(1.0, 2.0), Point(3.0, 4.0))) == Point(1.0, 2.0))
+assert(firstPoint(listOf()) == null)
+
+assert(patchSettings(Settings("a", 1u), SettingsPatch(volume = 5u)) == Settings("a", 5u))
+assert(patchSettings(Settings("a", 1u), SettingsPatch()) == Settings("a", 1u))
+
+val lowUuid = java.util.UUID.fromString("00000000-0000-4000-8000-0000000000ff")
+val highUuid = java.util.UUID.fromString("ff000000-0000-4000-8000-000000000000")
+assert(maxUuid(listOf(lowUuid, highUuid)) == highUuid)
+assert(maxUuid(listOf()) == null)
+
+assert(sumDecimals(listOf(java.math.BigDecimal("0.1"), java.math.BigDecimal("0.2"))) == java.math.BigDecimal("0.3"))
+assert(sumDecimals(listOf(java.math.BigDecimal("1E+3"), java.math.BigDecimal("-0.50"))) == java.math.BigDecimal("999.50"))
+
+assert(joinPath(java.io.File("/tmp"), "data.db") == java.io.File("/tmp/data.db"))
+
+assert(loopbackAddr(false) == java.net.InetAddress.getByName("127.0.0.1"))
+assert(loopbackAddr(true) == java.net.InetAddress.getByName("::1"))
+assert(withPort(java.net.InetSocketAddress(loopbackAddr(true), 80), 65535u) == java.net.InetSocketAddress(loopbackAddr(true), 65535))
+
+assert(repeatText("ab", 3u) == "ababab")
+assert(repeatText("abcd", 10000u) == "abcd".repeat(10000))
+
+assert(repeatSharedText("ab", 3u) == "ababab")
+assert(repeatSharedText("abcd", 10000u) == "abcd".repeat(10000))
+
+// Weak references don't keep the object alive
+val weakObj = Object().let { strongObj ->
+    val weakObj = downgradeObject(strongObj)
+    assert(weakObj.isAlive)
+    assert(isObjectAlive(weakObj))
+    weakObj.upgrade()!!.use { assert(it.isHeavy() == MaybeBool.UNCERTAIN) }
+    strongObj.destroy()
+    weakObj
+}
+assert(!weakObj.isAlive)
+assert(weakObj.upgrade() == null)
+assert(!isObjectAlive(weakObj))
+weakObj.destroy()
+
+assert(nextChar('a') == 'b')
+assert(nextChar('\u00e9') == '\u00ea')
+
+assert(divideEvenly(12u, 4u) == 3u)
+try {
+    divideEvenly(12u, 0u)
+    throw RuntimeException("parts must not be zero")
+} catch (e: IllegalArgumentException) {
+    assert(e.message!!.contains("parts"))
+}
+assert(divideExactly(12u, 4u) == 3u)
+try {
+    divideExactly(13u, 4u)
+    throw RuntimeException("13 isn't divisible by 4")
+} catch (e: BasicException.InvalidInput) {
+    // Expected
+}
+try {
+    divideExactly(12u, 0u)
+    throw RuntimeException("parts must not be zero")
+} catch (e: IllegalArgumentException) {
+    // Expected
+}
+
+// The report is always available.  Whether it lists the handles depends on the
+// `handle-diagnostics` feature, which another fixture enables for the whole workspace, see
+// `fixtures/handle-diagnostics`.
+assert(!uniffiDebugLiveHandles().isEmpty())
+
+// The increments from several threads don't race, since the calls to the counter are serialized
+SlowCounter().use { counter ->
+    val threads = (1..4).map { thread { repeat(10) { counter.increment() } } }
+    threads.forEach { it.join() }
+    assert(counter.value() == 40u)
+}
+
+val reports = mutableListOf<Pair<ULong, ULong>>()
+assert(countSteps(1000uL) { completed, total -> reports.add(Pair(completed, total)) } == 1000uL)
+assert(reports.first() == Pair(0uL, 1000uL))
+assert(reports.last() == Pair(1000uL, 1000uL))
+assert(progressSinkSteps(ProgressSink(3uL)) == 3uL)
+
+// Functions in a sub-namespace are in a package of their own
+assert(keyFor("users", 7u) == "users/7")
+assert(keyOfTwo(Two("a")) == "two/a")
@@ -3,22 +3,129 @@
 # file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 from uniffi_proc_macro import *
+import decimal
+import ipaddress
+import os
+import pathlib
+import queue
+import struct
+import sys
+import threading
+import uuid
 
 one = make_one(123)
 assert one.inner == 123
 
 two = Two("a")
 assert take_two(two) == "a"
+assert take_sensitive_two("b", two) == "ba"
+# Big enough for the buffer to grow while it's written
+assert take_sensitive_two("b", Two("a" * 1000)) == "b" + "a" * 1000
+assert make_two("b") == Two("b")
+
+# `usage_hooks` is enabled, see `uniffi.toml`
+used_apis = []
+set_usage_hook(used_apis.append)
+Object().is_heavy()
+make_one(1)
+set_usage_hook(None)
+make_one(2)
+assert used_apis == ["Object.new", "Object.is_heavy", "make_one"]
 
 obj = Object()
 obj = Object.named_ctor(1)
 assert obj.is_heavy() == MaybeBool.UNCERTAIN
+assert obj.is_heavy_in_thread() == MaybeBool.UNCERTAIN
+assert object_is_heavy(obj) == MaybeBool.UNCERTAIN
+
+# Closing an object while Rust borrows it frees it when the call returns
+class CloseObject(BorrowingCallback):
+    def __init__(self, obj):
+        self.obj = obj
+
+    def during_call(self):
+        self.obj.close()
+
+borrowed = Object()
+assert object_is_heavy_after(borrowed, CloseObject(borrowed)) == MaybeBool.UNCERTAIN
+try:
+    borrowed.is_heavy()
+except ValueError:
+    pass
+else:
+    raise Exception("Object can't be used after it was closed")
+
+sentence = Sentence()
+sentence.add_word("hello")
+sentence.add_word("world")
+assert sentence.finish() == "hello world"
+try:
+    sentence.add_word("again")
+except ValueError:
+    pass
+else:
+    raise Exception("Sentence can't be used after it was consumed")
+
+# The arguments are lowered before the object is consumed, so it's still usable if that fails
+sentence = Sentence()
+sentence.add_word("hi")
+try:
+    sentence.finish_with(1)
+except TypeError:
+    pass
+else:
+    raise Exception("finish_with() takes a string")
+assert sentence.finish_with("!") == "hi!"
 
 trait_impl = obj.get_trait(None)
 assert trait_impl.name() == "TraitImpl"
 assert obj.get_trait(trait_impl).name() == "TraitImpl"
+assert trait_name(trait_impl) == "TraitImpl"
 
 assert enum_identity(MaybeBool.TRUE) == MaybeBool.TRUE
+assert count_maybe_bools([MaybeBool.TRUE, MaybeBool.FALSE, MaybeBool.TRUE]) == {MaybeBool.TRUE: 2, MaybeBool.FALSE: 1}
+assert count_ones([One(1), One(2), One(1)]) == {One(1): 2, One(2): 1}
+assert maybe_bools_by_id([MaybeBool.UNCERTAIN, MaybeBool.TRUE]) == {0: MaybeBool.UNCERTAIN, 1: MaybeBool.TRUE}
+
+assert classify(0.0) == FpCategory.ZERO
+assert classify(float("nan")) == FpCategory.NAN
+assert HttpStatus.CREATED == 201
+assert status_code(HttpStatus(404)) == 404
+assert make_circle(2.0) == Shape.CIRCLE(2.0)
+assert shape_radius(Shape.POINT()) == 0.0
+# Unknown variants keep their raw index and fields, so they're passed back to Rust unchanged.  The
+# index of `Circle` is 2.
+unknown_circle = Shape.UNKNOWN(2, struct.pack(">d", 3.0))
+assert shape_radius(unknown_circle) == 3.0
+assert unknown_circle == Shape.UNKNOWN(2, struct.pack(">d", 3.0))
+assert unknown_circle != Shape.UNKNOWN(1, b"")
+assert unknown_circle != Shape.CIRCLE(3.0)
+assert unknown_circle != 3.0
+assert next_minor_version(Version(1, 2)) == Version(1, 3)
+
+assert MAX_POINTS == 100
+assert DEFAULT_NAME == "unnamed"
+assert SCALE == -0.5
+
+perms = add_permissions(Permissions.READ, Permissions.WRITE)
+assert perms == Permissions.READ_WRITE
+assert Permissions.WRITE in perms
+assert Permissions.EXECUTE not in perms
+assert can_write(Permissions.READ | Permissions.WRITE)
+assert not can_write(Permissions.READ)
+perms = future_permissions()
+assert Permissions.READ in perms
+assert perms.value == (0x101 if sys.version_info >= (3, 11) else 0x1)
+
+# Objects can be closed, with `close()` or a `with` statement
+with Object() as closed_obj:
+    assert closed_obj.is_heavy() == MaybeBool.UNCERTAIN
+try:
+    closed_obj.is_heavy()
+    raise AssertionError("closed objects can't be used")
+except ValueError:
+    pass
+closed_obj.close()
 
 # just make sure this works / doesn't crash
 three = Three(obj)
@@ -41,6 +148,14 @@ except FlatError.InvalidInput:
 else:
     raise Exception("do_stuff should throw if its argument is 0")
 
+try:
+    obj.do_stuff(1000)
+except FlatError.TooManyTimes as e:
+    assert e.limit == 100
+    assert str(e) == "Can't do stuff 1000 times"
+else:
+    raise Exception("do_stuff should throw if its argument is over the limit")
+
 class PyTestCallbackInterface(TestCallbackInterface):
     def do_nothing(self):
         pass
@@ -66,4 +181,199 @@ class PyTestCallbackInterface(TestCallbackInterface):
         v = h.take_error(BasicError.InvalidInput())
         return v
 
+    def divide(self, a, b):
+        return a // b
+
+    def checked_divide(self, a, b):
+        return a // b
+
+    def checked_parse_int(self, value):
+        return self.try_parse_int(value)
+
 test_callback_interface(PyTestCallbackInterface())
+assert TestCallbackInterface.PROTOCOL_VERSION == 2
+assert TestCallbackInterface.GREETING == "hello"
+# `double` isn't implemented, so it calls the default implementation in Rust
+assert PyTestCallbackInterface().double(3) == 6
+# Dividing by zero raises a `ZeroDivisionError`, which Rust gets as a `CallbackError`
+assert checked_divide_error(PyTestCallbackInterface(), 10, 0) == "ZeroDivisionError"
+assert checked_parse_int_error(PyTestCallbackInterface(), "force-unexpected-error") == "KeyError"
+
+# Rust stops calling a callback once it's detached
+class PyHeldCallback(HeldCallback):
+    def value(self):
+        return 21
+
+held = PyHeldCallback()
+holder = CallbackHolder(held)
+attached = held_callbacks_attached()
+# The default implementation runs in Rust, without attaching the object again
+assert held.doubled() == 42
+assert held_callbacks_attached() == attached
+assert holder.is_alive()
+assert holder.value() == 21
+uniffi_detach(held)
+assert not holder.is_alive()
+try:
+    holder.value()
+except BasicError.UnexpectedError:
+    pass
+else:
+    raise Exception("Calling a detached callback should fail")
+
+class PyMainThreadCallback(MainThreadCallback):
+    def thread_name(self):
+        return threading.current_thread().name
+
+# Call Rust from a worker thread, while the main thread runs the scheduled calls
+main_thread_tasks = queue.Queue()
+set_main_thread_scheduler(main_thread_tasks.put)
+results = []
+worker = threading.Thread(target=lambda: results.append(call_from_background_thread(PyMainThreadCallback())))
+worker.start()
+while worker.is_alive():
+    try:
+        main_thread_tasks.get(timeout=0.1)()
+    except queue.Empty:
+        pass
+worker.join()
+assert results == ["MainThread"]
+
+# Closure arguments take any callable
+assert sum_steps(3, lambda n: n * 2) == 12
+assert call_greeter("Alice", lambda name: f"Hello, {name}") == "Hello, Alice"
+
+class PyLogger(Logger):
+    def __init__(self):
+        self.messages = []
+
+    def log(self, level, target, message):
+        self.messages.append((level, target, message))
+
+logger = PyLogger()
+install_logger(logger, LogLevel.WARN)
+log_message("hello")
+assert logger.messages == [(LogLevel.WARN, "uniffi_proc_macro", "hello")]
+# Installing a logger again replaces the previous one
+other_logger = PyLogger()
+install_logger(other_logger, LogLevel.WARN)
+log_message("again")
+assert logger.messages == [(LogLevel.WARN, "uniffi_proc_macro", "hello")]
+assert other_logger.messages == [(LogLevel.WARN, "uniffi_proc_macro", "again")]
+
+assert check_secret("hunter2")
+assert not check_secret("hunter3")
+# `zero_copy_bytes` is enabled, so the secret comes back as a `memoryview`
+assert list(reverse_secret([1, 2, 3])) == [3, 2, 1]
+
+assert count_zeros(b"\x00\x01\x00") == 2
+assert count_zeros(b"") == 0
+view = repeat_byte(7, 1000)
+assert isinstance(view, memoryview)
+assert view.readonly
+assert view.tobytes() == b"\x07" * 1000
+assert bytes(view[10:12]) == b"\x07\x07"
+
+assert midpoint(Point(0.0, 0.0), Point(2.0, 4.0)) == Point(1.0, 2.0)
+assert first_point([Point(1.0, 2.0), Point(3.0, 4.0)]) == Point(1.0, 2.0)
+
+assert patch_settings(Settings("a", 1), SettingsPatch(volume=5)) == Settings("a", 5)
+assert patch_settings(Settings("a", 1), SettingsPatch()) == Settings("a", 1)
+
+low_uuid = uuid.UUID("00000000-0000-4000-8000-0000000000ff")
+high_uuid = uuid.UUID("ff000000-0000-4000-8000-000000000000")
+assert max_uuid([low_uuid, high_uuid]) == high_uuid
+assert max_uuid([]) is None
+
+assert sum_decimals([decimal.Decimal("0.1"), decimal.Decimal("0.2")]) == decimal.Decimal("0.3")
+assert str(sum_decimals([decimal.Decimal("1E+3"), decimal.Decimal("-0.50")])) == "999.50"
+assert join_path(pathlib.Path("/tmp"), "data.db") == pathlib.Path("/tmp/data.db")
+assert join_path("/tmp", "data.db") == pathlib.Path("/tmp/data.db")
+# Paths that aren't valid UTF-8 make it through unchanged
+if os.name == "posix":
+    non_utf8 = pathlib.Path(os.fsdecode(b"/tmp/caf\xe9"))
+    assert os.fsencode(join_path(non_utf8, "data.db")) == b"/tmp/caf\xe9/data.db"
+
+assert loopback_addr(False) == ipaddress.IPv4Address("127.0.0.1")
+assert loopback_addr(True) == ipaddress.IPv6Address("::1")
+assert with_port((ipaddress.ip_address("192.0.2.1"), 80), 65535) == (ipaddress.IPv4Address("192.0.2.1"), 65535)
+assert with_port(("2001:db8::1", 80, 0, 0), 443) == (ipaddress.IPv6Address("2001:db8::1"), 443)
+
+assert repeat_text("ab", 3) == "ababab"
+assert repeat_text("abcd", 10000) == "abcd" * 10000
+
+assert repeat_shared_text("ab", 3) == "ababab"
+assert repeat_shared_text("abcd", 10000) == "abcd" * 10000
+
+# Weak references don't keep the object alive
+strong_obj = Object()
+weak_obj = downgrade_object(strong_obj)
+assert weak_obj.is_alive()
+assert is_object_alive(weak_obj)
+assert weak_obj.upgrade().is_heavy() == MaybeBool.UNCERTAIN
+del strong_obj
+assert not weak_obj.is_alive()
+assert weak_obj.upgrade() is None
+assert not is_object_alive(weak_obj)
+
+assert first_point([]) is None
+
+assert without_gated_feature() == 0
+assert "with_gated_feature" not in globals()
+assert not hasattr(Object, "gated_method")
+
+assert next_char("a") == "b"
+assert next_char("\U0001f980") == "\U0001f981"
+try:
+    next_char("ab")
+except TypeError:
+    pass
+else:
+    raise AssertionError("a char must be a single character")
+
+assert divide_evenly(12, 4) == 3
+try:
+    divide_evenly(12, 0)
+except InvalidArgumentError as e:
+    assert "parts" in str(e)
+else:
+    raise AssertionError("parts must not be zero")
+assert divide_exactly(12, 4) == 3
+try:
+    divide_exactly(13, 4)
+except BasicError.InvalidInput:
+    pass
+else:
+    raise AssertionError("13 isn't divisible by 4")
+try:
+    divide_exactly(12, 0)
+except ValueError:
+    # `InvalidArgumentError` is a `ValueError`
+    pass
+else:
+    raise AssertionError("parts must not be zero")
+
+# The report is always available.  Whether it lists the handles depends on the `handle-diagnostics`
+# feature, which another fixture enables for the whole workspace, see `fixtures/handle-diagnostics`.
+assert uniffi_debug_live_handles()
+
+# The increments from several threads don't race, since the calls to the counter are serialized
+counter = SlowCounter()
+threads = [threading.Thread(target=lambda: [counter.increment() for _ in range(10)]) for _ in range(4)]
+for t in threads:
+    t.start()
+for t in threads:
+    t.join()
+assert counter.value() == 40
+
+reports = []
+assert count_steps(1000, lambda completed, total: reports.append((completed, total))) == 1000
+assert reports[0] == (0, 1000)
+assert reports[-1] == (1000, 1000)
+assert progress_sink_steps(ProgressSink(3)) == 3
+
+# Functions in a sub-namespace are in a submodule, which can be imported too
+assert storage.key_for("users", 7) == "users/7"
+assert "key_for" not in globals()
+from uniffi_proc_macro.storage import key_of_two
+assert key_of_two(Two(a="a")) == "two/a"
@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+import Foundation
 import uniffi_proc_macro
 
 let one = makeOne(inner: 123)
@@ -9,12 +10,63 @@ assert(one.inner == 123)
 
 let two = Two(a: "a")
 assert(takeTwo(two: two) == "a")
+assert(takeSensitiveTwo(prefix: "b", two: two) == "ba")
+assert(makeTwo(a: "b") == Two(a: "b"))
+
+// `usage_hooks` is enabled, see `uniffi.toml`
+var usedApis: [String] = []
+uniffiUsageHook = { name in usedApis.append(name) }
+_ = Object().isHeavy()
+_ = makeOne(inner: 1)
+uniffiUsageHook = nil
+_ = makeOne(inner: 2)
+assert(usedApis == ["Object.new", "Object.is_heavy", "make_one"])
 
 var obj = Object()
 obj = Object.namedCtor(arg: 1)
 assert(obj.isHeavy() == .uncertain)
+assert(obj.isHeavyInThread() == .uncertain)
+assert(objectIsHeavy(obj: obj) == .uncertain)
+assert(traitName(t: obj.getTrait(inc: nil)) == "TraitImpl")
+
+// Calling a method of `sentence` after `finish()` would crash
+let sentence = Sentence()
+sentence.addWord(word: "hello")
+sentence.addWord(word: "world")
+assert(sentence.finish() == "hello world")
+
+let greeting = Sentence()
+greeting.addWord(word: "hi")
+assert(greeting.finishWith(punctuation: "!") == "hi!")
 
 assert(enumIdentity(value: .true) == .true)
+assert(countMaybeBools(values: [.true, .false, .true]) == [.true: 2, .false: 1])
+assert(countOnes(values: [One(inner: 1), One(inner: 2), One(inner: 1)]) == [One(inner: 1): 2, One(inner: 2): 1])
+assert(maybeBoolsById(values: [.uncertain, .true]) == [0: .uncertain, 1: .true])
+
+assert(classify(value: 0.0) == .zero)
+assert(classify(value: .nan) == .nan)
+assert(HttpStatus.created.rawValue == 201)
+assert(statusCode(status: HttpStatus(rawValue: 404)!) == 404)
+assert(makeCircle(radius: 2.0) == .circle(radius: 2.0))
+assert(shapeRadius(shape: .point) == 0.0)
+// Unknown variants keep their raw index and fields, so they're passed back to Rust unchanged.  The
+// index of `circle` is 2.
+let unknownCircle = Shape.unknown(index: 2, fields: withUnsafeBytes(of: Double(3.0).bitPattern.bigEndian) { Array($0) })
+assert(shapeRadius(shape: unknownCircle) == 3.0)
+assert(unknownCircle != .unknown(index: 1, fields: []))
+assert(nextMinorVersion(version: Version(major: 1, minor: 2)) == Version(major: 1, minor: 3))
+
+assert(maxPoints == 100)
+assert(defaultName == "unnamed")
+assert(scale == -0.5)
+
+let perms = addPermissions(perms: .read, extra: .write)
+assert(perms == .readWrite)
+assert(perms.contains(.write))
+assert(!perms.contains(.execute))
+assert(canWrite(perms: [.read, .write]))
+assert(!canWrite(perms: .read))
 
 // just make sure this works / doesn't crash
 let three = Three(obj: obj)
@@ -35,6 +87,14 @@ do {
 } catch FlatError.InvalidInput {
 }
 
+do {
+    try obj.doStuff(times: 1000)
+    fatalError("doStuff should throw if its argument is over the limit")
+} catch let FlatError.TooManyTimes(message, limit) {
+    assert(message == "Can't do stuff 1000 times")
+    assert(limit == 100)
+}
+
 struct SomeOtherError: Error { }
 
 class SwiftTestCallbackInterface : TestCallbackInterface {
@@ -65,6 +125,168 @@ class SwiftTestCallbackInterface : TestCallbackInterface {
         var v = h.takeError(e: BasicError.InvalidInput)
         return v
     }
+
+    // Swift can't raise an unexpected error from a non-throwing method, so handle this here
+    func divide(a: UInt32, b: UInt32) -> UInt32 {
+        return b == 0 ? 0 : a / b
+    }
+
+    func checkedDivide(a: UInt32, b: UInt32) -> UInt32 {
+        return a / b
+    }
+
+    func checkedParseInt(value: String) throws -> UInt32 {
+        return try tryParseInt(value: value)
+    }
 }
 
 testCallbackInterface(cb: SwiftTestCallbackInterface())
+assert(SwiftTestCallbackInterface.protocolVersion == 2)
+assert(SwiftTestCallbackInterface.greeting == "hello")
+// `double` isn't implemented, so it calls the default implementation in Rust
+assert(SwiftTestCallbackInterface().double(a: 3) == 6)
+// Swift can only throw from methods that declare an error, errors of other types are a
+// `CallbackError` in Rust.  The class name is the type name, which includes the module name.
+assert(checkedParseIntError(cb: SwiftTestCallbackInterface(), value: "12") == "12")
+assert(checkedParseIntError(cb: SwiftTestCallbackInterface(), value: "ten") == "InvalidInput")
+assert(checkedParseIntError(cb: SwiftTestCallbackInterface(), value: "force-unexpected-error").hasSuffix(".SomeOtherError"))
+
+// Calls from other threads run on the main queue, so keep the main run loop going while a
+// background thread calls Rust
+class SwiftMainThreadCallback : MainThreadCallback {
+    func threadName() -> String {
+        return Thread.isMainThread ? "main" : "background"
+    }
+}
+
+var mainThreadResult: String? = nil
+DispatchQueue.global().async {
+    let result = callFromBackgroundThread(cb: SwiftMainThreadCallback())
+    DispatchQueue.main.async { mainThreadResult = result }
+}
+while mainThreadResult == nil {
+    RunLoop.main.run(until: Date(timeIntervalSinceNow: 0.01))
+}
+assert(mainThreadResult == "main")
+
+// Rust stops calling a callback once it's detached
+class SwiftHeldCallback : HeldCallback {
+    func value() -> UInt32 { 21 }
+}
+
+do {
+    let held = SwiftHeldCallback()
+    let holder = CallbackHolder(cb: held)
+    let attached = heldCallbacksAttached()
+    // The default implementation runs in Rust, without attaching the object again
+    assert(held.doubled() == 42)
+    assert(heldCallbacksAttached() == attached)
+    assert(holder.isAlive())
+    assert(try! holder.value() == 21)
+    uniffiDetach(held)
+    assert(!holder.isAlive())
+    do {
+        _ = try holder.value()
+        fatalError("Calling a detached callback should fail")
+    } catch BasicError.UnexpectedError {
+        // Expected
+    }
+}
+
+// Closure arguments take Swift closures
+assert(sumSteps(n: 3) { $0 * 2 } == 12)
+assert(callGreeter(name: "Alice") { "Hello, \($0)" } == "Hello, Alice")
+
+assert(countZeros(bytes: Data([0, 1, 0])) == 2)
+assert(countZeros(bytes: Data()) == 0)
+assert(repeatByte(byte: 7, count: 1000) == Data(repeating: 7, count: 1000))
+
+assert(midpoint(a: Point(x: 0.0, y: 0.0), b: Point(x: 2.0, y: 4.0)) == Point(x: 1.0, y: 2.0))
+assert(firstPoint(points: [Point(x: 1.0, y: 2.0), Point(x: 3.0, y: 4.0)]) == Point(x: 1.0, y: 2.0))
+assert(firstPoint(points: []) == nil)
+
+// Records and enums are `Codable`
+let encodedPoint = try! JSONEncoder().encode(Point(x: 1.0, y: 2.0))
+assert(try! JSONDecoder().decode(Point.self, from: encodedPoint) == Point(x: 1.0, y: 2.0))
+let encodedMaybe = try! JSONEncoder().encode([MaybeBool.uncertain, MaybeBool.true])
+assert(try! JSONDecoder().decode([MaybeBool].self, from: encodedMaybe) == [.uncertain, .true])
+
+assert(patchSettings(settings: Settings(name: "a", volume: 1), patch: SettingsPatch(volume: 5)) == Settings(name: "a", volume: 5))
+assert(patchSettings(settings: Settings(name: "a", volume: 1), patch: SettingsPatch()) == Settings(name: "a", volume: 1))
+
+let lowUuid = UUID(uuidString: "00000000-0000-4000-8000-0000000000ff")!
+let highUuid = UUID(uuidString: "ff000000-0000-4000-8000-000000000000")!
+assert(maxUuid(ids: [lowUuid, highUuid]) == highUuid)
+assert(maxUuid(ids: []) == nil)
+
+assert(sumDecimals(values: [Decimal(string: "0.1")!, Decimal(string: "0.2")!]) == Decimal(string: "0.3")!)
+assert(sumDecimals(values: []) == 0)
+
+assert(joinPath(base: URL(fileURLWithPath: "/tmp"), name: "data.db").path == "/tmp/data.db")
+
+assert(loopbackAddr(v6: false) == "127.0.0.1")
+assert(loopbackAddr(v6: true) == "::1")
+assert(withPort(addr: "192.0.2.1:80", port: 65535) == "192.0.2.1:65535")
+assert(withPort(addr: "[2001:db8::1]:80", port: 443) == "[2001:db8::1]:443")
+
+assert(repeatText(text: "ab", count: 3) == "ababab")
+#if canImport(Compression)
+assert(repeatText(text: "abcd", count: 10000) == String(repeating: "abcd", count: 10000))
+#endif
+
+assert(repeatSharedText(text: "ab", count: 3) == "ababab")
+assert(repeatSharedText(text: "abcd", count: 10000) == String(repeating: "abcd", count: 10000))
+
+// Weak references don't keep the object alive
+var strongObj: Object? = Object()
+let weakObj = downgradeObject(obj: strongObj!)
+assert(weakObj.isAlive)
+assert(isObjectAlive(obj: weakObj))
+assert(weakObj.upgrade()!.isHeavy() == .uncertain)
+strongObj = nil
+assert(!weakObj.isAlive)
+assert(weakObj.upgrade() == nil)
+assert(!isObjectAlive(obj: weakObj))
+
+assert(nextChar(c: "a") == "b")
+assert(nextChar(c: "\u{1F980}") == "\u{1F981}")
+
+assert(divideEvenly(total: 12, parts: 4) == 3)
+assert(try! divideExactly(total: 12, parts: 4) == 3)
+do {
+    _ = try divideExactly(total: 13, parts: 4)
+    fatalError("13 isn't divisible by 4")
+} catch BasicError.InvalidInput {
+    // Expected
+}
+// A zero `parts` would be a fatal error for `divideEvenly()`, which doesn't throw
+do {
+    _ = try divideExactly(total: 12, parts: 0)
+    fatalError("parts must not be zero")
+} catch let e as UniffiInvalidArgumentError {
+    assert(e.message.contains("parts"))
+}
+
+// The report is always available.  Whether it lists the handles depends on the
+// `handle-diagnostics` feature, which another fixture enables for the whole workspace, see
+// `fixtures/handle-diagnostics`.
+assert(!uniffiDebugLiveHandles().isEmpty)
+
+// The increments from several threads don't race, since the calls to the counter are serialized
+let counter = SlowCounter()
+DispatchQueue.concurrentPerform(iterations: 4) { _ in
+    for _ in 0..<10 {
+        _ = counter.increment()
+    }
+}
+assert(counter.value() == 40)
+
+var reports: [(UInt64, UInt64)] = []
+assert(countSteps(steps: 1000) { completed, total in reports.append((completed, total)) } == 1000)
+assert(reports.first! == (0, 1000))
+assert(reports.last! == (1000, 1000))
+assert(progressSinkSteps(sink: ProgressSink(steps: 3)) == 3)
+
+// Functions in a sub-namespace are static functions of an enum
+assert(Storage.keyFor(table: "users", id: 7) == "users/7")
+assert(Storage.keyOfTwo(two: Two(a: "a")) == "two/a")
@@ -0,0 +1,177 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(uniffi::Record)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub label: Option<String>,
+}
+
+#[derive(uniffi::Enum)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(uniffi::Enum)]
+pub enum Shape {
+    Circle { center: Point, radius: f64 },
+    Polygon { points: Vec<Point> },
+}
+
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum MathError {
+    #[error("division by zero")]
+    DivisionByZero,
+    #[error("overflow of {value}")]
+    Overflow { value: i64 },
+}
+
+#[uniffi::export]
+#[allow(clippy::too_many_arguments)]
+fn echo_numbers(a: i8, b: u8, c: i16, d: u16, e: i32, f: u32, g: i64, h: u64) -> Vec<u64> {
+    vec![
+        a as u64, b as u64, c as u64, d as u64, e as u64, f as u64, g as u64, h,
+    ]
+}
+
+#[uniffi::export]
+fn echo_float(value: f32) -> f32 {
+    value
+}
+
+#[uniffi::export]
+fn echo_bool(value: bool) -> bool {
+    value
+}
+
+#[uniffi::export]
+fn echo_string(value: String) -> String {
+    value
+}
+
+#[uniffi::export]
+fn echo_bytes(value: Vec<u8>) -> Vec<u8> {
+    value
+}
+
+// The bindings zero the bytes that `words` was written into
+#[uniffi::export]
+#[uniffi::sensitive(words)]
+fn count_words(words: Vec<String>) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for word in words {
+        *counts.entry(word).or_default() += 1;
+    }
+    counts
+}
+
+#[uniffi::export]
+fn turn_right(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+
+#[uniffi::export]
+fn translate(shape: Shape, dx: f64, dy: f64) -> Shape {
+    let translate_point = |p: Point| Point {
+        x: p.x + dx,
+        y: p.y + dy,
+        label: p.label,
+    };
+    match shape {
+        Shape::Circle { center, radius } => Shape::Circle {
+            center: translate_point(center),
+            radius,
+        },
+        Shape::Polygon { points } => Shape::Polygon {
+            points: points.into_iter().map(translate_point).collect(),
+        },
+    }
+}
+
+#[uniffi::export]
+fn checked_div(dividend: i64, divisor: i64) -> Result<i64, MathError> {
+    if divisor == 0 {
+        return Err(MathError::DivisionByZero);
+    }
+    dividend
+        .checked_div(divisor)
+        .ok_or(MathError::Overflow { value: dividend })
+}
+
+#[uniffi::export]
+fn panic_with(message: String) {
+    panic!("{}", message);
+}
+
+#[derive(uniffi::Object)]
+pub struct Counter {
+    value: Mutex<u64>,
+}
+
+#[uniffi::export]
+impl Counter {
+    #[uniffi::constructor]
+    fn new(start: u64) -> Arc<Self> {
+        Arc::new(Self {
+            value: Mutex::new(start),
+        })
+    }
+
+    fn increment(&self, by: u64) -> u64 {
+        let mut value = self.value.lock().unwrap();
+        *value += by;
+        *value
+    }
+
+    fn value(&self) -> u64 {
+        *self.value.lock().unwrap()
+    }
+
+    fn add_from(&self, other: Arc<Counter>) -> u64 {
+        self.increment(other.value())
+    }
+}
+
+#[uniffi::export]
+fn sum_counters(counters: Vec<Arc<Counter>>) -> u64 {
+    counters.iter().map(|c| c.value()).sum()
+}
+
+// An object with its own `close()` method, which the generated one doesn't replace
+#[derive(uniffi::Object)]
+pub struct Connection {
+    closed: Mutex<bool>,
+}
+
+#[uniffi::export]
+impl Connection {
+    #[uniffi::constructor]
+    fn new() -> Arc<Self> {
+        Arc::new(Self {
+            closed: Mutex::new(false),
+        })
+    }
+
+    fn close(&self) {
+        *self.closed.lock().unwrap() = true;
+    }
+
+    fn is_closed(&self) -> bool {
+        *self.closed.lock().unwrap()
+    }
+}
+
+uniffi::setup_scaffolding!();
@@ -0,0 +1,68 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import unittest
+import uniffi_python_cffi
+from uniffi_python_cffi import *
+
+class TestPythonCffi(unittest.TestCase):
+    def test_uses_cffi(self):
+        # The scaffolding functions come from the compiled extension module, not `ctypes`
+        self.assertTrue(hasattr(uniffi_python_cffi, "_uniffi_ffi"))
+
+    def test_numbers(self):
+        self.assertEqual(
+            echo_numbers(-1, 255, -2, 65535, -3, 4294967295, -4, 2**64 - 1),
+            [2**64 - 1, 255, 2**64 - 2, 65535, 2**64 - 3, 4294967295, 2**64 - 4, 2**64 - 1],
+        )
+        self.assertEqual(echo_float(1.5), 1.5)
+        self.assertTrue(echo_bool(True))
+        self.assertFalse(echo_bool(False))
+        # The bindings check the range before cffi converts the arguments
+        with self.assertRaises(ValueError):
+            echo_numbers(128, 0, 0, 0, 0, 0, 0, 0)
+
+    def test_buffers(self):
+        self.assertEqual(echo_string(""), "")
+        self.assertEqual(echo_string("Hello, cffi! 🦀"), "Hello, cffi! 🦀")
+        # `Vec<u8>` is a `sequence<u8>`, which lifts to a list
+        self.assertEqual(echo_bytes(b"\x00\x01\xff"), [0, 1, 255])
+        self.assertEqual(count_words(["a", "b", "a"]), {"a": 2, "b": 1})
+        self.assertEqual(count_words(["word"] * 1000), {"word": 1000})
+
+    def test_enums_and_records(self):
+        self.assertEqual(turn_right(Direction.WEST), Direction.NORTH)
+        circle = translate(Shape.CIRCLE(Point(1.0, 2.0, "center"), 3.0), 1.0, -1.0)
+        self.assertEqual(circle, Shape.CIRCLE(Point(2.0, 1.0, "center"), 3.0))
+        polygon = translate(Shape.POLYGON([Point(0.0, 0.0, None), Point(1.0, 1.0, None)]), 1.0, 1.0)
+        self.assertEqual(polygon, Shape.POLYGON([Point(1.0, 1.0, None), Point(2.0, 2.0, None)]))
+
+    def test_errors(self):
+        self.assertEqual(checked_div(6, 3), 2)
+        with self.assertRaises(MathError.DivisionByZero):
+            checked_div(1, 0)
+        with self.assertRaises(MathError.Overflow) as cm:
+            checked_div(-2**63, -1)
+        self.assertEqual(cm.exception.value, -2**63)
+        with self.assertRaisesRegex(InternalError, "oh no"):
+            panic_with("oh no")
+
+    def test_objects(self):
+        counter = Counter(1)
+        self.assertEqual(counter.increment(2), 3)
+        other = Counter(4)
+        self.assertEqual(counter.add_from(other), 7)
+        self.assertEqual(sum_counters([counter, other]), 11)
+        self.assertEqual(counter.value(), 7)
+
+    def test_close_method_clash(self):
+        conn = Connection()
+        conn.close()
+        self.assertTrue(conn.is_closed())
+        conn.uniffi_close()
+        with self.assertRaises(ValueError):
+            conn.is_closed()
+
+if __name__=='__main__':
+    unittest.main()
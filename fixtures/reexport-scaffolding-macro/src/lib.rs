@@ -87,6 +87,12 @@ mod tests {
         >(
             &library, coveralls_ci.ffi_rustbuffer_alloc().name()
         ));
+        assert!(has_symbol::<
+            unsafe extern "C" fn(&mut RustCallStatus) -> RustBuffer,
+        >(
+            &library,
+            coveralls_ci.ffi_uniffi_debug_live_handles().name()
+        ));
 
         // Top-level function
         assert!(
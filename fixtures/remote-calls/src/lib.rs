@@ -0,0 +1,68 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+fn greet(name: String) -> String {
+    format!("Hello, {name}!")
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DivisionError {
+    #[error("Division by zero")]
+    DivideByZero,
+}
+
+fn divide(a: u32, b: u32) -> Result<u32, DivisionError> {
+    a.checked_div(b).ok_or(DivisionError::DivideByZero)
+}
+
+#[derive(uniffi::Record)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[uniffi::export]
+fn add_points(a: Point, b: Option<Point>) -> Point {
+    match b {
+        Some(b) => Point {
+            x: a.x + b.x,
+            y: a.y + b.y,
+        },
+        None => a,
+    }
+}
+
+#[uniffi::export]
+fn checksum(data: &[u8]) -> u32 {
+    data.iter().map(|b| u32::from(*b)).sum()
+}
+
+#[uniffi::export]
+fn fail() {
+    panic!("remote panic");
+}
+
+// Objects can't be called remotely, so `new_counter()` always calls the library.
+pub struct Counter {
+    count: AtomicU32,
+}
+
+impl Counter {
+    fn increment(&self) -> u32 {
+        self.count.fetch_add(1, Ordering::Relaxed) + 1
+    }
+}
+
+fn new_counter() -> Arc<Counter> {
+    Arc::new(Counter {
+        count: AtomicU32::new(0),
+    })
+}
+
+uniffi::include_scaffolding!("remote_calls");
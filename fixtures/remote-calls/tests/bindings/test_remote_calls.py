@@ -0,0 +1,66 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+import unittest
+import remote_calls
+from remote_calls import *
+
+class LoopbackTransport:
+    # Stands in for a transport to another process, by dispatching the calls in this one.  The
+    # names of the calls are recorded to check that they went through the transport.
+    def __init__(self):
+        self.calls = []
+
+    def call(self, name, args):
+        self.calls.append(name)
+        return uniffi_remote_dispatch(name, args)
+
+class TestRemoteCalls(unittest.TestCase):
+    def setUp(self):
+        self.transport = LoopbackTransport()
+        uniffi_set_remote_transport(self.transport)
+
+    def tearDown(self):
+        uniffi_set_remote_transport(None)
+
+    def test_calls_go_through_the_transport(self):
+        self.assertEqual(greet("Remote"), "Hello, Remote!")
+        self.assertEqual(self.transport.calls, ["uniffi_remote_calls_remote_func_greet"])
+
+    def test_records_and_optionals(self):
+        self.assertEqual(add_points(Point(x=1, y=2), Point(x=3, y=4)), Point(x=4, y=6))
+        self.assertEqual(add_points(Point(x=1, y=2), None), Point(x=1, y=2))
+        self.assertEqual(len(self.transport.calls), 2)
+
+    def test_bytes(self):
+        self.assertEqual(checksum(b"\x01\x02\x03"), 6)
+
+    def test_errors(self):
+        self.assertEqual(divide(6, 3), 2)
+        with self.assertRaises(DivisionError.DivideByZero):
+            divide(1, 0)
+
+    def test_panics(self):
+        with self.assertRaisesRegex(InternalError, "remote panic"):
+            fail()
+
+    def test_objects_call_the_library(self):
+        counter = new_counter()
+        self.assertEqual(counter.increment(), 1)
+        self.assertEqual(self.transport.calls, [])
+        # The scaffolding doesn't have a remote entry point for it either
+        self.assertTrue(hasattr(remote_calls._UniffiLib, "uniffi_remote_calls_remote_func_greet"))
+        self.assertFalse(hasattr(remote_calls._UniffiLib, "uniffi_remote_calls_remote_func_new_counter"))
+
+    def test_dispatch_rejects_unknown_entry_points(self):
+        with self.assertRaises(InternalError):
+            uniffi_remote_dispatch("uniffi_remote_calls_fn_func_greet", b"")
+
+    def test_direct_calls_without_transport(self):
+        uniffi_set_remote_transport(None)
+        self.assertEqual(greet("Local"), "Hello, Local!")
+        self.assertEqual(self.transport.calls, [])
+
+if __name__ == "__main__":
+    unittest.main()
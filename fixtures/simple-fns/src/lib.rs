@@ -42,6 +42,12 @@ fn set_contains(set: Arc<MyHashSet>, value: String) -> bool {
     set.lock().unwrap().contains(&value)
 }
 
+// Hidden from the Python bindings in `uniffi.toml`
+#[uniffi::export]
+fn get_admin_token() -> String {
+    "admin".to_owned()
+}
+
 // This used to generate broken bindings because the type inside `Option` (and
 // other generic builtin types) wasn't being added as a known type.
 #[uniffi::export]
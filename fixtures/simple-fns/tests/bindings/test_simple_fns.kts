@@ -15,3 +15,6 @@ addToSet(aSet, "bar")
 assert(setContains(aSet, "foo"))
 assert(setContains(aSet, "bar"))
 assert(!setContains(aSet, "baz"))
+
+// `get_admin_token` is only hidden from Python
+assert(getAdminToken() == "admin")
@@ -15,3 +15,7 @@ add_to_set(a_set, "bar")
 assert set_contains(a_set, "foo")
 assert set_contains(a_set, "bar")
 assert not set_contains(a_set, "baz")
+
+# `get_admin_token` is hidden from Python, but not from the other languages
+import uniffi_simple_fns
+assert not hasattr(uniffi_simple_fns, "get_admin_token")
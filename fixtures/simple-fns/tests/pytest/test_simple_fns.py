@@ -0,0 +1,21 @@
+# This Source Code Form is subject to the terms of the Mozilla Public
+# License, v. 2.0. If a copy of the MPL was not distributed with this
+# file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+# Runs with `uniffi::python_run_pytest()`, which checks that the pytest runner finds and runs tests
+
+from uniffi_simple_fns import *
+
+def test_strings():
+    assert get_string() == "String created by Rust"
+    assert string_identity("String created by Python") == "String created by Python"
+
+def test_ints():
+    assert get_int() == 1289
+    assert byte_to_u32(255) == 255
+
+def test_set():
+    a_set = new_set()
+    add_to_set(a_set, "foo")
+    assert set_contains(a_set, "foo")
+    assert not set_contains(a_set, "bar")
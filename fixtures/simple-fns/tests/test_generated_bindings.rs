@@ -3,3 +3,12 @@ uniffi::build_foreign_language_testcases!(
     "tests/bindings/test_simple_fns.swift",
     "tests/bindings/test_simple_fns.py",
 );
+
+#[test]
+fn python_pytest() -> uniffi::deps::anyhow::Result<()> {
+    uniffi::python_run_pytest(
+        env!("CARGO_TARGET_TMPDIR"),
+        "uniffi-fixture-simple-fns",
+        "tests/pytest",
+    )
+}
@@ -1,8 +1,13 @@
 /* This Source Code Form is subject to the terms of the Mozilla Public
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct TraitMethods {
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TraitMethods {
     val: String,
 }
 
@@ -18,4 +23,105 @@ impl std::fmt::Display for TraitMethods {
     }
 }
 
+impl uniffi::Restorable for TraitMethods {
+    fn save_state(&self) -> Vec<u8> {
+        self.val.as_bytes().to_vec()
+    }
+
+    fn restore_state(state: &[u8]) -> Option<Self> {
+        Some(Self::new(String::from_utf8(state.to_vec()).ok()?))
+    }
+}
+
+// The same object, but with the traits exported by the proc-macros rather than the UDL
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, uniffi::Object)]
+#[uniffi::export(Debug, Display, Eq, Hash, Ord, Restore)]
+pub struct ProcTraitMethods {
+    val: String,
+}
+
+#[uniffi::export]
+impl ProcTraitMethods {
+    #[uniffi::constructor]
+    fn new(val: String) -> Arc<Self> {
+        Arc::new(Self { val })
+    }
+}
+
+impl std::fmt::Display for ProcTraitMethods {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ProcTraitMethods({})", self.val)
+    }
+}
+
+impl uniffi::Restorable for ProcTraitMethods {
+    fn save_state(&self) -> Vec<u8> {
+        self.val.as_bytes().to_vec()
+    }
+
+    fn restore_state(state: &[u8]) -> Option<Self> {
+        Some(Self {
+            val: String::from_utf8(state.to_vec()).ok()?,
+        })
+    }
+}
+
+// The fields are declared in a different order than they're compared, so that comparing them in
+// the foreign code would give a different result
+#[derive(Debug, PartialEq, Eq)]
+pub struct Version {
+    label: String,
+    major: u32,
+    minor: u32,
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, &self.label).cmp(&(other.major, other.minor, &other.label))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The same record, with `Ord` exported by the proc-macros.  It's passed as a C struct, since it's
+// `#[repr(C)]` and only has numeric fields.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, uniffi::Record)]
+#[uniffi(Ord)]
+#[repr(C)]
+pub struct ProcVersion {
+    major: u32,
+    minor: u32,
+}
+
+// A store that only lives as long as the process, which is enough to test the token round-trip.
+// Real stores would write to disk and sign with a secret key.
+#[derive(Default)]
+struct MemoryHandleStore(Mutex<HashMap<String, Vec<u8>>>);
+
+impl uniffi::HandleStore for MemoryHandleStore {
+    fn save(&self, key: &str, state: &[u8]) {
+        self.0
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), state.to_vec());
+    }
+
+    fn load(&self, key: &str) -> Option<Vec<u8>> {
+        self.0.lock().unwrap().get(key).cloned()
+    }
+
+    fn sign(&self, key: &str) -> String {
+        format!("signed-{}", key.len())
+    }
+}
+
+#[uniffi::export]
+fn use_memory_handle_store() {
+    uniffi::set_handle_store(Arc::new(MemoryHandleStore::default()));
+}
+
 uniffi::include_scaffolding!("trait_methods");
@@ -0,0 +1,35 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.trait_methods.*
+
+useMemoryHandleStore()
+
+val m = TraitMethods("yo")
+assert(m.toString() == "TraitMethods(yo)")
+assert(m == TraitMethods("yo"))
+assert(m != TraitMethods("yoyo"))
+assert(m.hashCode() == TraitMethods("yo").hashCode())
+assert(setOf(m).contains(TraitMethods("yo")))
+assert(TraitMethods("a") < TraitMethods("b"))
+assert(listOf(TraitMethods("b"), TraitMethods("a")).sorted().map { it.toString() } == listOf("TraitMethods(a)", "TraitMethods(b)"))
+assert(m.serializeHandle() == TraitMethods("yo").serializeHandle())
+assert(TraitMethods.restoreHandle(m.serializeHandle()) == m)
+assert(TraitMethods.restoreHandle("TraitMethods-0.forged") == null)
+
+val p = ProcTraitMethods("yo")
+assert(p.toString() == "ProcTraitMethods(yo)")
+assert(p == ProcTraitMethods("yo"))
+assert(p != ProcTraitMethods("yoyo"))
+assert(p.hashCode() == ProcTraitMethods("yo").hashCode())
+assert(setOf(p).contains(ProcTraitMethods("yo")))
+assert(ProcTraitMethods("a") < ProcTraitMethods("b"))
+assert(listOf(ProcTraitMethods("b"), ProcTraitMethods("a")).sorted().map { it.toString() } == listOf("ProcTraitMethods(a)", "ProcTraitMethods(b)"))
+assert(ProcTraitMethods.restoreHandle(p.serializeHandle()) == p)
+assert(ProcTraitMethods.restoreHandle(m.serializeHandle()) == null)
+
+// Rust compares the version numbers first, even though `label` is the first field
+assert(Version("b", 1u, 2u) < Version("a", 1u, 10u))
+assert(listOf(Version("a", 1u, 10u), Version("b", 1u, 2u)).sorted() == listOf(Version("b", 1u, 2u), Version("a", 1u, 10u)))
+assert(ProcVersion(2u, 0u) > ProcVersion(1u, 10u))
@@ -5,6 +5,8 @@
 import unittest
 from trait_methods import *
 
+use_memory_handle_store()
+
 class TestTraitMethods(unittest.TestCase):
     def test_str(self):
         m = TraitMethods("yo")
@@ -29,5 +31,77 @@ class TestTraitMethods(unittest.TestCase):
         d[m] = "m"
         self.assertTrue(m in d)
 
+    def test_ord(self):
+        a = TraitMethods("a")
+        b = TraitMethods("b")
+        self.assertTrue(a < b)
+        self.assertTrue(a <= b)
+        self.assertTrue(b > a)
+        self.assertTrue(b >= a)
+        self.assertFalse(a < a)
+        self.assertTrue(a <= a)
+        self.assertEqual([str(m) for m in sorted([b, a])], ["TraitMethods(a)", "TraitMethods(b)"])
+
+    def test_restore(self):
+        m = TraitMethods("yo")
+        self.assertEqual(m.serialize_handle(), TraitMethods("yo").serialize_handle())
+        self.assertEqual(TraitMethods.restore_handle(m.serialize_handle()), m)
+        self.assertIsNone(TraitMethods.restore_handle("TraitMethods-0.forged"))
+
+class TestOrdRecords(unittest.TestCase):
+    def test_ord(self):
+        a = Version("b", 1, 2)
+        b = Version("a", 1, 10)
+        # Rust compares the numbers first, even though `label` is the first field
+        self.assertTrue(a < b)
+        self.assertTrue(a <= b)
+        self.assertTrue(b > a)
+        self.assertTrue(b >= a)
+        self.assertFalse(a < Version("b", 1, 2))
+        self.assertTrue(a <= Version("b", 1, 2))
+        self.assertEqual(sorted([b, a]), [a, b])
+
+    def test_proc_macro_ord(self):
+        self.assertTrue(ProcVersion(1, 2) < ProcVersion(1, 10))
+        self.assertTrue(ProcVersion(2, 0) > ProcVersion(1, 10))
+        self.assertEqual(sorted([ProcVersion(2, 0), ProcVersion(1, 0)]), [ProcVersion(1, 0), ProcVersion(2, 0)])
+
+    def test_not_comparable_with_other_types(self):
+        with self.assertRaises(TypeError):
+            Version("a", 1, 0) < ProcVersion(1, 0)
+
+class TestProcmacroTraitMethods(unittest.TestCase):
+    def test_str(self):
+        m = ProcTraitMethods("yo")
+        self.assertEqual(str(m), "ProcTraitMethods(yo)")
+
+    def test_repr(self):
+        m = ProcTraitMethods("yo")
+        self.assertEqual(repr(m), 'ProcTraitMethods { val: "yo" }')
+
+    def test_eq(self):
+        m = ProcTraitMethods("yo")
+        self.assertEqual(m, ProcTraitMethods("yo"))
+        self.assertNotEqual(m, ProcTraitMethods("yoyo"))
+
+    def test_hash(self):
+        d = {}
+        m = ProcTraitMethods("m")
+        d[m] = "m"
+        self.assertTrue(m in d)
+        self.assertTrue(ProcTraitMethods("m") in d)
+
+    def test_ord(self):
+        a = ProcTraitMethods("a")
+        b = ProcTraitMethods("b")
+        self.assertTrue(a < b)
+        self.assertTrue(b > a)
+        self.assertEqual([str(m) for m in sorted([b, a])], ["ProcTraitMethods(a)", "ProcTraitMethods(b)"])
+
+    def test_restore(self):
+        m = ProcTraitMethods("yo")
+        self.assertEqual(ProcTraitMethods.restore_handle(m.serialize_handle()), m)
+        self.assertIsNone(ProcTraitMethods.restore_handle(TraitMethods("yo").serialize_handle()))
+
 if __name__=='__main__':
     unittest.main()
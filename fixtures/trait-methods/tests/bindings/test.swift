@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import trait_methods
+
+useMemoryHandleStore()
+
+do {
+    let m = TraitMethods(name: "yo")
+    assert(String(describing: m) == "TraitMethods(yo)")
+    assert(String(reflecting: m) == "TraitMethods { val: \"yo\" }")
+    assert(m == TraitMethods(name: "yo"))
+    assert(m != TraitMethods(name: "yoyo"))
+    assert(Set([m]).contains(TraitMethods(name: "yo")))
+    assert(TraitMethods(name: "a") < TraitMethods(name: "b"))
+    assert([TraitMethods(name: "b"), TraitMethods(name: "a")].sorted().map { String(describing: $0) } == ["TraitMethods(a)", "TraitMethods(b)"])
+    assert(m.serializeHandle() == TraitMethods(name: "yo").serializeHandle())
+    assert(TraitMethods.restoreHandle(m.serializeHandle()) == m)
+    assert(TraitMethods.restoreHandle("TraitMethods-0.forged") == nil)
+}
+
+do {
+    let p = ProcTraitMethods(val: "yo")
+    assert(String(describing: p) == "ProcTraitMethods(yo)")
+    assert(String(reflecting: p) == "ProcTraitMethods { val: \"yo\" }")
+    assert(p == ProcTraitMethods(val: "yo"))
+    assert(p != ProcTraitMethods(val: "yoyo"))
+    assert(Set([p]).contains(ProcTraitMethods(val: "yo")))
+    assert(ProcTraitMethods(val: "a") < ProcTraitMethods(val: "b"))
+    assert([ProcTraitMethods(val: "b"), ProcTraitMethods(val: "a")].sorted().map { String(describing: $0) } == ["ProcTraitMethods(a)", "ProcTraitMethods(b)"])
+    assert(ProcTraitMethods.restoreHandle(p.serializeHandle()) == p)
+    assert(ProcTraitMethods.restoreHandle(TraitMethods(name: "yo").serializeHandle()) == nil)
+}
+
+do {
+    // Rust compares the version numbers first, even though `label` is the first field
+    let a = Version(label: "b", major: 1, minor: 2)
+    let b = Version(label: "a", major: 1, minor: 10)
+    assert(a < b)
+    assert([b, a].sorted() == [a, b])
+    assert(ProcVersion(major: 2, minor: 0) > ProcVersion(major: 1, minor: 10))
+}
@@ -1 +1,5 @@
-uniffi::build_foreign_language_testcases!("tests/bindings/test.py",);
+uniffi::build_foreign_language_testcases!(
+    "tests/bindings/test.py",
+    "tests/bindings/test.kts",
+    "tests/bindings/test.swift",
+);
@@ -17,13 +17,11 @@ WORK_DIR=${TARGET_DIR}/version-mismatch-workdir
 if test -e "${WORK_DIR}"; then rm -r ${WORK_DIR}; fi
 mkdir -p ${WORK_DIR}
 
-# Build the library and generate the bindings
-cargo build
-cargo run -p uniffi-fixture-version-mismatch --bin bindgen -- generate src/api_v1.udl --lib-file $LIBRARY_PATH --language kotlin --out-dir ${WORK_DIR} --no-format
-
-# Rebuild library, forcing the scaffolding to have a different contract version
-export UNIFFI_FORCE_CONTRACT_VERSION=0
+# Build the library, then generate bindings that expect a different contract version.  The
+# bindings must notice the mismatch from the contract version function alone, since the other
+# functions may not exist in a library with another contract version.
 cargo build
+UNIFFI_FORCE_CONTRACT_VERSION=0 cargo run -p uniffi-fixture-version-mismatch --bin bindgen -- generate src/api_v1.udl --lib-file $LIBRARY_PATH --language kotlin --out-dir ${WORK_DIR} --no-format
 
 # Try to run the two together
 cp ${LIBRARY_PATH} ${WORK_DIR}
@@ -31,4 +29,9 @@ cp ${CRATE_ROOT}/bindings/kotlin_test.kts ${WORK_DIR}
 cd ${WORK_DIR}
 
 kotlinc -Werror -d ${WORK_DIR}/uniffi-example-arithmetic.jar -classpath ${CLASSPATH} ${WORK_DIR}/uniffi/fixture_version_mismatch/fixture_version_mismatch.kt
-kotlinc -classpath ${WORK_DIR}:${WORK_DIR}/uniffi-example-arithmetic.jar:${CLASSPATH} -J-ea -Werror -script ${WORK_DIR}/kotlin_test.kts
+# The bindings must fail to load, and say which contract versions differ
+if kotlinc -classpath ${WORK_DIR}:${WORK_DIR}/uniffi-example-arithmetic.jar:${CLASSPATH} -J-ea -Werror -script ${WORK_DIR}/kotlin_test.kts 2> ${WORK_DIR}/stderr.txt; then
+    echo "The bindings loaded a library with another contract version"
+    exit 1
+fi
+grep "but the library has contract version" ${WORK_DIR}/stderr.txt
@@ -3,8 +3,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use camino::Utf8PathBuf;
-use clap::{Parser, Subcommand};
-use uniffi_bindgen::bindings::TargetLanguage;
+use clap::{Parser, Subcommand, ValueEnum};
+use uniffi_bindgen::{bindings::TargetLanguage, bundle::TargetLibrary};
 
 // Structs to help our cmdline parsing. Note that docstrings below form part
 // of the "help" output.
@@ -19,15 +19,22 @@ struct Cli {
     command: Commands,
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum MetadataFormat {
+    Json,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Generate foreign language bindings
     Generate {
-        /// Foreign language(s) for which to build bindings.
+        /// Foreign language(s) for which to build bindings.  Defaults to the languages that have
+        /// an `out_dir` in the config file.
         #[clap(long, short, value_enum)]
         language: Vec<TargetLanguage>,
 
         /// Directory in which to write generated files. Default is same folder as .udl file.
+        /// The `out_dir` of a language in the config file takes precedence.
         #[clap(long, short)]
         out_dir: Option<Utf8PathBuf>,
 
@@ -51,6 +58,22 @@ enum Commands {
         #[clap(long = "crate")]
         crate_name: Option<String>,
 
+        /// Also write an index of the exported items, mapping them to their Rust source location
+        /// and their generated symbols, for IDE plugins.
+        #[clap(long)]
+        ide_index: bool,
+
+        /// Program that generates bindings for a language uniffi-bindgen doesn't support.  It's
+        /// run for each component, with a JSON description of the component on its stdin.  Can be
+        /// passed multiple times.
+        #[clap(long)]
+        external_generator: Vec<String>,
+
+        /// Keep running, and regenerate the bindings whenever the UDL file, the config or the
+        /// library changes.
+        #[clap(long)]
+        watch: bool,
+
         /// Path to the UDL file, or cdylib if `library-mode` is specified
         source: Utf8PathBuf,
     },
@@ -74,6 +97,202 @@ enum Commands {
         /// Path to the library file (.so, .dll, .dylib, or .a)
         path: Utf8PathBuf,
     },
+
+    /// Print the model of the components in a UDL file or library, in the stable format that's
+    /// sent to external generators
+    PrintMetadata {
+        /// Output format.
+        #[clap(long, value_enum, default_value_t = MetadataFormat::Json)]
+        format: MetadataFormat,
+
+        /// Extract proc-macro metadata from a native lib (cdylib or staticlib) for this crate.
+        #[clap(long)]
+        lib_file: Option<Utf8PathBuf>,
+
+        /// Pass in a cdylib path rather than a UDL file
+        #[clap(long = "library")]
+        library_mode: bool,
+
+        /// When `--library` is passed, only print the component of one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Path to the UDL file, or cdylib if `library-mode` is specified
+        source: Utf8PathBuf,
+    },
+
+    /// Compare the interfaces of two builds of a library, and fail if there are breaking changes
+    Diff {
+        /// Only compare the component of one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Only report the changes, even if some are breaking
+        #[clap(long)]
+        allow_breaking: bool,
+
+        /// Path to the old library file (.so, .dll, .dylib, or .a)
+        old: Utf8PathBuf,
+
+        /// Path to the new library file
+        new: Utf8PathBuf,
+    },
+
+    /// Write the interface of the components in a library as UDL files
+    ExportUdl {
+        /// Only export the component of one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Directory in which to write `<namespace>.udl` files.  The UDL is printed if it's not
+        /// passed.
+        #[clap(long, short)]
+        out_dir: Option<Utf8PathBuf>,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        library: Utf8PathBuf,
+    },
+
+    /// Generate cargo-fuzz targets that lift each type exported by a library from arbitrary data
+    Fuzz {
+        /// Only generate targets for one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Directory in which to write the cargo-fuzz project, usually the `fuzz` directory of the
+        /// crate.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// Also generate targets that call each function with arbitrary arguments.  The functions
+        /// are really called, so only pass this if that's safe.
+        #[clap(long)]
+        calls: bool,
+
+        /// Path to the library file (.so, .dll, .dylib, or .a)
+        library: Utf8PathBuf,
+    },
+
+    /// Generate a crate that benchmarks the FFI overhead of calls from Kotlin, Swift and Python,
+    /// run with `cargo bench`
+    FfiBench {
+        /// Directory in which to write the crate.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// Path to a local checkout of the `uniffi` crate to benchmark.  Defaults to the release
+        /// of this uniffi-bindgen.
+        #[clap(long)]
+        uniffi_path: Option<Utf8PathBuf>,
+    },
+
+    /// Assemble libraries built for several targets, along with their bindings, into
+    /// distributable layouts (Android jniLibs, an Apple xcframework and Python wheel contents)
+    Bundle {
+        /// Name of the bundle, used for the xcframework.
+        name: String,
+
+        /// Library built for a target, as `<target-triple>=<path>`.  Can be passed multiple times.
+        #[clap(long = "lib", required = true)]
+        libraries: Vec<TargetLibrary>,
+
+        /// Directory containing the generated bindings for the libraries.
+        #[clap(long)]
+        bindings_dir: Utf8PathBuf,
+
+        /// Directory in which to write the bundle.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+    },
+
+    /// Generate a Swift package for a library, with a script that assembles the xcframework
+    /// it depends on
+    SwiftPackage {
+        /// Name of the package, and of the Swift module for the bindings.
+        name: String,
+
+        /// Directory in which to write the package.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// Target to build the library for.  Can be passed multiple times, defaults to the iOS,
+        /// iOS simulator and macOS targets.
+        #[clap(long = "target")]
+        targets: Vec<String>,
+
+        /// Only generate bindings for one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Do not try to format the generated bindings.
+        #[clap(long, short)]
+        no_format: bool,
+
+        /// Path to the library (cdylib or staticlib) that was built for the host
+        library: Utf8PathBuf,
+    },
+
+    /// Generate a Python package for a library, with a `pyproject.toml` to build it into a wheel
+    PythonPackage {
+        /// Name of the package.
+        name: String,
+
+        /// Directory in which to write the package.
+        #[clap(long, short)]
+        out_dir: Utf8PathBuf,
+
+        /// Wheel platform tag of the library, e.g. `manylinux_2_28_x86_64`.  Defaults to the tag
+        /// of the host platform.
+        #[clap(long)]
+        platform_tag: Option<String>,
+
+        /// Only generate bindings for one crate
+        #[clap(long = "crate")]
+        crate_name: Option<String>,
+
+        /// Do not try to format the generated bindings.
+        #[clap(long, short)]
+        no_format: bool,
+
+        /// Path to the cdylib to package
+        library: Utf8PathBuf,
+    },
+
+    /// Build a crate for Android with cargo-ndk, copy the libraries into `jniLibs` and
+    /// regenerate the Kotlin bindings
+    Android {
+        /// The `jniLibs` directory of the Android module.
+        #[clap(long)]
+        jni_libs_dir: Utf8PathBuf,
+
+        /// Directory in which to write the Kotlin bindings.
+        #[clap(long)]
+        kotlin_dir: Utf8PathBuf,
+
+        /// Target to build for.  Can be passed multiple times, defaults to all the Android ABIs.
+        #[clap(long = "target")]
+        targets: Vec<String>,
+
+        /// Build with the release profile.
+        #[clap(long)]
+        release: bool,
+
+        /// Minimum Android API level to build for.
+        #[clap(long)]
+        platform: Option<u32>,
+
+        /// Path to the `Cargo.toml` of the crate.
+        #[clap(long)]
+        manifest_path: Option<Utf8PathBuf>,
+
+        /// Package to build.  Defaults to the root package.
+        #[clap(long, short)]
+        package: Option<String>,
+
+        /// Do not try to format the generated bindings.
+        #[clap(long, short)]
+        no_format: bool,
+    },
 }
 
 pub fn run_main() -> anyhow::Result<()> {
@@ -88,6 +307,9 @@ pub fn run_main() -> anyhow::Result<()> {
             source,
             crate_name,
             library_mode,
+            ide_index,
+            external_generator,
+            watch,
         } => {
             if library_mode {
                 if lib_file.is_some() {
@@ -97,24 +319,100 @@ pub fn run_main() -> anyhow::Result<()> {
                     panic!("--config is not compatible with --library.  The config file(s) will be found automatically.")
                 }
                 let out_dir = out_dir.expect("--out-dir is required when using --library");
-                if language.is_empty() {
-                    panic!("please specify at least one language with --language")
+                // Returns the files the bindings are generated from, for `--watch`
+                let generate = || -> anyhow::Result<Vec<Utf8PathBuf>> {
+                    let sources = uniffi_bindgen::library_mode::generate_bindings(
+                        &source,
+                        crate_name.clone(),
+                        &language,
+                        &out_dir,
+                        !no_format,
+                    )?;
+                    if language.is_empty()
+                        && external_generator.is_empty()
+                        && sources
+                            .iter()
+                            .all(|s| s.config.configured_languages().is_empty())
+                    {
+                        anyhow::bail!("please specify at least one language with --language, or an `out_dir` for a language in uniffi.toml")
+                    }
+                    for source in &sources {
+                        if let Some(report) =
+                            uniffi_bindgen::library_mode::gated_items_report(&source.ci)
+                        {
+                            println!("{report}");
+                        }
+                    }
+                    for program in &external_generator {
+                        uniffi_bindgen::external_generator::generate_library_bindings(
+                            program, &source, &sources, &out_dir,
+                        )?;
+                    }
+                    if ide_index {
+                        for source in &sources {
+                            uniffi_bindgen::ide_index::write_ide_index(
+                                &source.ci,
+                                &source.config,
+                                &language,
+                                &out_dir,
+                            )?;
+                        }
+                    }
+                    let configs = sources.iter().filter_map(|s| {
+                        Some(s.package.manifest_path.parent()?.join("uniffi.toml"))
+                    });
+                    Ok(std::iter::once(source.clone()).chain(configs).collect())
+                };
+                let inputs = generate()?;
+                if watch {
+                    uniffi_bindgen::watch::watch(&inputs, || generate().map(|_| ()));
                 }
-                uniffi_bindgen::library_mode::generate_bindings(
-                    &source, crate_name, &language, &out_dir, !no_format,
-                )?;
             } else {
                 if crate_name.is_some() {
                     panic!("--crate requires --library.")
                 }
-                uniffi_bindgen::generate_bindings(
-                    &source,
-                    config.as_deref(),
-                    language,
-                    out_dir.as_deref(),
-                    lib_file.as_deref(),
-                    !no_format,
-                )?;
+                let generate = || -> anyhow::Result<()> {
+                    uniffi_bindgen::generate_bindings(
+                        &source,
+                        config.as_deref(),
+                        language.clone(),
+                        out_dir.as_deref(),
+                        lib_file.as_deref(),
+                        !no_format,
+                    )?;
+                    if ide_index {
+                        uniffi_bindgen::generate_ide_index(
+                            &source,
+                            config.as_deref(),
+                            &language,
+                            out_dir.as_deref(),
+                            lib_file.as_deref(),
+                        )?;
+                    }
+                    for program in &external_generator {
+                        uniffi_bindgen::external_generator::generate_bindings(
+                            program,
+                            &source,
+                            config.as_deref(),
+                            out_dir.as_deref(),
+                            lib_file.as_deref(),
+                        )?;
+                    }
+                    Ok(())
+                };
+                generate()?;
+                if watch {
+                    let config_file = match &config {
+                        Some(config) => config.clone(),
+                        None => uniffi_bindgen::guess_crate_root(&source)?.join("uniffi.toml"),
+                    };
+                    let inputs: Vec<Utf8PathBuf> =
+                        [Some(source.clone()), Some(config_file), lib_file.clone()]
+                            .into_iter()
+                            .flatten()
+                            .collect();
+                    uniffi_bindgen::watch::watch(&inputs, generate);
+                }
             }
         }
         Commands::Scaffolding {
@@ -131,6 +429,134 @@ pub fn run_main() -> anyhow::Result<()> {
         Commands::PrintJson { path } => {
             uniffi_bindgen::print_json(&path)?;
         }
+        Commands::PrintMetadata {
+            format: MetadataFormat::Json,
+            lib_file,
+            library_mode,
+            crate_name,
+            source,
+        } => {
+            if library_mode && lib_file.is_some() {
+                panic!("--lib-file is not compatible with --library.")
+            }
+            if crate_name.is_some() && !library_mode {
+                panic!("--crate requires --library.")
+            }
+            uniffi_bindgen::print_metadata(&source, library_mode, crate_name, lib_file.as_deref())?;
+        }
+        Commands::Diff {
+            crate_name,
+            allow_breaking,
+            old,
+            new,
+        } => {
+            let changes = uniffi_bindgen::api_diff::diff_libraries(&old, &new, crate_name)?;
+            for change in &changes {
+                println!("{change}");
+            }
+            let breaking = changes
+                .iter()
+                .filter(|c| c.compatibility == uniffi_bindgen::api_diff::Compatibility::Breaking)
+                .count();
+            if breaking > 0 && !allow_breaking {
+                anyhow::bail!("found {breaking} breaking change(s)");
+            }
+        }
+        Commands::ExportUdl {
+            crate_name,
+            out_dir,
+            library,
+        } => {
+            uniffi_bindgen::udl_export::export_udl(&library, crate_name, out_dir.as_deref())?;
+        }
+        Commands::Fuzz {
+            crate_name,
+            out_dir,
+            calls,
+            library,
+        } => {
+            uniffi_bindgen::fuzz::generate_fuzz_targets(&library, crate_name, &out_dir, calls)?;
+        }
+        Commands::FfiBench {
+            out_dir,
+            uniffi_path,
+        } => {
+            uniffi_bindgen::ffi_bench::generate_ffi_bench(&out_dir, uniffi_path.as_deref())?;
+        }
+        Commands::Bundle {
+            name,
+            libraries,
+            bindings_dir,
+            out_dir,
+        } => {
+            uniffi_bindgen::bundle::assemble_bundle(&name, &libraries, &bindings_dir, &out_dir)?;
+        }
+        Commands::SwiftPackage {
+            name,
+            out_dir,
+            targets,
+            crate_name,
+            no_format,
+            library,
+        } => {
+            let targets = if targets.is_empty() {
+                uniffi_bindgen::swift_package::DEFAULT_TARGETS
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            } else {
+                targets
+            };
+            uniffi_bindgen::swift_package::generate_swift_package(
+                &library, crate_name, &name, &targets, &out_dir, !no_format,
+            )?;
+        }
+        Commands::PythonPackage {
+            name,
+            out_dir,
+            platform_tag,
+            crate_name,
+            no_format,
+            library,
+        } => {
+            uniffi_bindgen::python_package::generate_python_package(
+                &library,
+                crate_name,
+                &name,
+                platform_tag,
+                &out_dir,
+                !no_format,
+            )?;
+        }
+        Commands::Android {
+            jni_libs_dir,
+            kotlin_dir,
+            targets,
+            release,
+            platform,
+            manifest_path,
+            package,
+            no_format,
+        } => {
+            let targets = if targets.is_empty() {
+                uniffi_bindgen::android::DEFAULT_TARGETS
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            } else {
+                targets
+            };
+            let build = uniffi_bindgen::android::AndroidBuild {
+                manifest_path,
+                package,
+                targets,
+                release,
+                platform,
+                jni_libs_dir,
+                kotlin_dir,
+            };
+            uniffi_bindgen::android::build_android(&build, !no_format)?;
+        }
     };
     Ok(())
 }
@@ -2,19 +2,27 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
 /// Reexport items from other uniffi creates
 pub use uniffi_core::*;
 pub use uniffi_macros::*;
 #[cfg(feature = "cli")]
 mod cli;
 #[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::kotlin::run_gradle_test as kotlin_run_gradle_test;
+#[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::kotlin::run_test as kotlin_run_test;
 #[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::python::run_pytest as python_run_pytest;
+#[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::python::run_test as python_run_test;
 #[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::ruby::run_test as ruby_run_test;
 #[cfg(feature = "bindgen-tests")]
 pub use uniffi_bindgen::bindings::swift::run_test as swift_run_test;
+#[cfg(feature = "bindgen-tests")]
+pub use uniffi_bindgen::bindings::swift::run_xctest as swift_run_xctest;
 #[cfg(feature = "bindgen")]
 pub use uniffi_bindgen::{
     bindings::TargetLanguage, generate_bindings, generate_component_scaffolding, print_json,
@@ -24,6 +32,11 @@ pub use uniffi_build::generate_scaffolding;
 #[cfg(feature = "bindgen-tests")]
 pub use uniffi_macros::build_foreign_language_testcases;
 
+#[cfg(feature = "cli")]
+pub fn uniffi_bindgen_main() {
+    cli::run_main().unwrap();
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -32,8 +45,3 @@ mod test {
         t.compile_fail("tests/ui/*.rs");
     }
 }
-
-#[cfg(feature = "cli")]
-pub fn uniffi_bindgen_main() {
-    cli::run_main().unwrap();
-}
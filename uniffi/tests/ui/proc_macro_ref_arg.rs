@@ -0,0 +1,14 @@
+fn main() {}
+
+// Normally this is defined by the scaffolding code, manually define it for the UI test
+pub struct UniFfiTag;
+
+#[uniffi::export]
+fn take_vec(values: &Vec<u32>) -> u32 {
+    values.len() as u32
+}
+
+#[uniffi::export]
+fn take_u32(value: &u32) -> u32 {
+    *value
+}
@@ -0,0 +1,186 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Build a crate for Android and update an Android module with the results.
+//!
+//! This replaces the scripts that get copied between Android projects, and is meant to be run
+//! from a Gradle task before the Kotlin code is compiled:
+//!
+//!   - The library is built for every ABI with [cargo-ndk](https://github.com/bbqsrc/cargo-ndk),
+//!     which sets up the NDK toolchain for each target.
+//!   - The libraries are copied into the module's `jniLibs/<abi>/` directories.
+//!   - The Kotlin bindings are regenerated from one of the libraries, like `generate --library`
+//!     does.  The crates of the components are looked up in the workspace of `--manifest-path`.
+
+use crate::{bindings::TargetLanguage, bundle, library_mode, Result};
+use anyhow::{bail, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::MetadataCommand;
+use fs_err as fs;
+use std::process::Command;
+
+/// The targets that are built when none are passed
+pub const DEFAULT_TARGETS: &[&str] = &[
+    "aarch64-linux-android",
+    "armv7-linux-androideabi",
+    "i686-linux-android",
+    "x86_64-linux-android",
+];
+
+/// What to build, and where to put it
+#[derive(Debug, Clone)]
+pub struct AndroidBuild {
+    /// `Cargo.toml` of the crate, or of its workspace.  Defaults to the one in the current
+    /// directory.
+    pub manifest_path: Option<Utf8PathBuf>,
+    /// Package to build, which must have a `cdylib` target.  Defaults to the root package.
+    pub package: Option<String>,
+    /// Rust target triples to build for
+    pub targets: Vec<String>,
+    /// Build with the release profile
+    pub release: bool,
+    /// Minimum Android API level, passed to cargo-ndk
+    pub platform: Option<u32>,
+    /// The `jniLibs` directory of the Android module
+    pub jni_libs_dir: Utf8PathBuf,
+    /// Directory in which to write the Kotlin bindings
+    pub kotlin_dir: Utf8PathBuf,
+}
+
+/// Build the library for each target, copy it into `jniLibs` and regenerate the Kotlin bindings.
+pub fn build_android(build: &AndroidBuild, try_format_code: bool) -> Result<()> {
+    if build.targets.is_empty() {
+        bail!("no targets to build for");
+    }
+    let abis = build
+        .targets
+        .iter()
+        .map(|target| {
+            bundle::android_abi(target)
+                .with_context(|| format!("`{target}` is not an Android target"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut metadata_command = MetadataCommand::new();
+    if let Some(manifest_path) = &build.manifest_path {
+        metadata_command.manifest_path(manifest_path.clone());
+    }
+    // The dependencies are needed to find the crates of the components when generating the
+    // bindings
+    let metadata = metadata_command
+        .exec()
+        .context("error running cargo metadata")?;
+    let package = match &build.package {
+        Some(name) => metadata
+            .workspace_packages()
+            .into_iter()
+            .find(|p| &p.name == name)
+            .with_context(|| format!("package `{name}` not found"))?,
+        None => metadata
+            .root_package()
+            .context("no root package, pass the package to build")?,
+    };
+    let library_name = package
+        .targets
+        .iter()
+        .find(|t| t.kind.iter().any(|k| k == "cdylib"))
+        .map(|t| t.name.replace('-', "_"))
+        .with_context(|| format!("`{}` needs a `cdylib` crate type", package.name))?;
+
+    let mut cargo = Command::new("cargo");
+    cargo.arg("ndk");
+    for target in &build.targets {
+        cargo.arg("--target").arg(target);
+    }
+    if let Some(platform) = build.platform {
+        cargo.arg("--platform").arg(platform.to_string());
+    }
+    cargo.arg("build").arg("--lib");
+    if let Some(manifest_path) = &build.manifest_path {
+        cargo.arg("--manifest-path").arg(manifest_path);
+    }
+    cargo.arg("--package").arg(&package.name);
+    if build.release {
+        cargo.arg("--release");
+    }
+    let status = cargo
+        .status()
+        .context("error running cargo ndk (is cargo-ndk installed?)")?;
+    if !status.success() {
+        bail!("cargo ndk failed to build {}", package.name);
+    }
+
+    let mut libraries = Vec::new();
+    for (target, abi) in build.targets.iter().zip(abis) {
+        let library = built_library_path(
+            &metadata.target_directory,
+            target,
+            build.release,
+            &library_name,
+        );
+        let jni_libs_dir = build.jni_libs_dir.join(abi);
+        fs::create_dir_all(&jni_libs_dir)?;
+        fs::copy(&library, jni_libs_dir.join(library.file_name().unwrap()))?;
+        libraries.push(library);
+    }
+
+    // The interface is the same for every target, so the bindings can come from any library
+    library_mode::generate_bindings_with_metadata(
+        &metadata,
+        &libraries[0],
+        None,
+        &[TargetLanguage::Kotlin],
+        &build.kotlin_dir,
+        try_format_code,
+    )?;
+    Ok(())
+}
+
+fn built_library_path(
+    target_dir: &Utf8Path,
+    target: &str,
+    release: bool,
+    library_name: &str,
+) -> Utf8PathBuf {
+    let profile = if release { "release" } else { "debug" };
+    target_dir
+        .join(target)
+        .join(profile)
+        .join(format!("lib{library_name}.so"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn library_paths() {
+        assert_eq!(
+            built_library_path(
+                Utf8Path::new("target"),
+                "aarch64-linux-android",
+                true,
+                "arithmetical"
+            ),
+            "target/aarch64-linux-android/release/libarithmetical.so"
+        );
+        assert_eq!(
+            built_library_path(
+                Utf8Path::new("target"),
+                "i686-linux-android",
+                false,
+                "arithmetical"
+            ),
+            "target/i686-linux-android/debug/libarithmetical.so"
+        );
+    }
+
+    #[test]
+    fn default_targets_are_android() {
+        for target in DEFAULT_TARGETS {
+            assert!(bundle::android_abi(target).is_some());
+        }
+        assert_eq!(bundle::android_abi("aarch64-apple-ios"), None);
+    }
+}
@@ -0,0 +1,846 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # API diffs
+//!
+//! Compare the interfaces of two builds of a library, to catch breaking changes in CI before they
+//! reach the foreign code.  The comparison is done on the [ComponentModel] of each component, and
+//! each difference is classified as breaking or additive for code that uses the bindings:
+//!
+//!   - Removing or changing anything is breaking.
+//!   - Adding functions, objects, methods, records, enums and error variants is additive.
+//!   - Adding an argument or a record field is additive if it has a default value.  Adding a default
+//!     value is additive and removing one is breaking.
+//!   - Adding a variant to an enum that's not an error is breaking, since Swift `switch` and Kotlin
+//!     `when` statements need to handle every variant.  `#[non_exhaustive]` enums are the exception,
+//!     since older bindings lift new variants to their `Unknown` variant.
+//!   - Adding a method to a callback interface is breaking, since the foreign implementations need
+//!     to implement it, unless the method has a default implementation.  For the same reason,
+//!     removing the default implementation of a method is breaking and adding one is additive.
+
+use crate::{
+    external_generator::{
+        ArgumentModel, CallableModel, CallbackInterfaceModel, ComponentModel, ConstantModel,
+        EnumModel, FieldModel, FlagsModel, ObjectModel, RecordModel, TypeModel,
+    },
+    library_mode, Result,
+};
+use camino::Utf8Path;
+use std::{collections::BTreeMap, fmt};
+
+/// Whether a change can break the code that uses the bindings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    Breaking,
+    Additive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub compatibility: Compatibility,
+    pub description: String,
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.compatibility {
+            Compatibility::Breaking => write!(f, "breaking: {}", self.description),
+            Compatibility::Additive => write!(f, "additive: {}", self.description),
+        }
+    }
+}
+
+/// Compare the components of two libraries
+///
+/// The components are found like in library mode, so this needs to run in the cargo workspace of
+/// the crates.
+pub fn diff_libraries(
+    old_library: &Utf8Path,
+    new_library: &Utf8Path,
+    crate_name: Option<String>,
+) -> Result<Vec<ApiChange>> {
+    let old = components(old_library, crate_name.clone())?;
+    let new = components(new_library, crate_name)?;
+    let mut changes = Vec::new();
+    for (namespace, old_component) in &old {
+        match new.get(namespace) {
+            Some(new_component) => changes.extend(diff_components(old_component, new_component)),
+            None => changes.push(ApiChange {
+                compatibility: Compatibility::Breaking,
+                description: format!("component `{namespace}` was removed"),
+            }),
+        }
+    }
+    for namespace in new.keys().filter(|namespace| !old.contains_key(*namespace)) {
+        changes.push(ApiChange {
+            compatibility: Compatibility::Additive,
+            description: format!("component `{namespace}` was added"),
+        });
+    }
+    Ok(changes)
+}
+
+fn components(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+) -> Result<BTreeMap<String, ComponentModel>> {
+    Ok(library_mode::find_components(library_path, crate_name)?
+        .iter()
+        .map(|source| {
+            (
+                source.ci.namespace().to_string(),
+                ComponentModel::new(&source.ci),
+            )
+        })
+        .collect())
+}
+
+/// Compare two versions of a component
+pub fn diff_components(old: &ComponentModel, new: &ComponentModel) -> Vec<ApiChange> {
+    let mut differ = Differ::default();
+    differ.items(
+        |name| format!("function `{name}`"),
+        &old.functions,
+        &new.functions,
+        |f| &f.name,
+        Compatibility::Additive,
+        |differ, old, new| differ.callable(&format!("function `{}`", old.name), old, new),
+    );
+    differ.constants(
+        |name| format!("constant `{name}`"),
+        &old.constants,
+        &new.constants,
+    );
+    differ.items(
+        |name| format!("object `{name}`"),
+        &old.objects,
+        &new.objects,
+        |o| &o.name,
+        Compatibility::Additive,
+        Differ::object,
+    );
+    differ.items(
+        |name| format!("record `{name}`"),
+        &old.records,
+        &new.records,
+        |r| &r.name,
+        Compatibility::Additive,
+        Differ::record,
+    );
+    differ.items(
+        |name| format!("enum `{name}`"),
+        &old.enums,
+        &new.enums,
+        |e| &e.name,
+        Compatibility::Additive,
+        Differ::enum_,
+    );
+    differ.items(
+        |name| format!("flags `{name}`"),
+        &old.flags,
+        &new.flags,
+        |f| &f.name,
+        Compatibility::Additive,
+        Differ::flags,
+    );
+    differ.items(
+        |name| format!("callback interface `{name}`"),
+        &old.callback_interfaces,
+        &new.callback_interfaces,
+        |c| &c.name,
+        Compatibility::Additive,
+        Differ::callback_interface,
+    );
+    differ.changes
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<ApiChange>,
+}
+
+impl Differ {
+    fn push(&mut self, compatibility: Compatibility, description: String) {
+        self.changes.push(ApiChange {
+            compatibility,
+            description,
+        });
+    }
+
+    // Compare two lists of items by name, calling `changed` for the items that are in both.
+    // `describe` returns the description of an item from its name.
+    fn items<T>(
+        &mut self,
+        describe: impl Fn(&str) -> String,
+        old: &[T],
+        new: &[T],
+        name: impl Fn(&T) -> &String,
+        added: Compatibility,
+        mut changed: impl FnMut(&mut Self, &T, &T),
+    ) {
+        for old_item in old {
+            match new.iter().find(|new_item| name(new_item) == name(old_item)) {
+                Some(new_item) => changed(self, old_item, new_item),
+                None => self.push(
+                    Compatibility::Breaking,
+                    format!("{} was removed", describe(name(old_item))),
+                ),
+            }
+        }
+        for new_item in new {
+            if !old.iter().any(|old_item| name(old_item) == name(new_item)) {
+                self.push(added, format!("{} was added", describe(name(new_item))));
+            }
+        }
+    }
+
+    fn callable(&mut self, what: &str, old: &CallableModel, new: &CallableModel) {
+        self.arguments(what, &old.arguments, &new.arguments);
+        let old_return = old
+            .return_type
+            .as_ref()
+            .map_or("()".to_string(), type_label);
+        let new_return = new
+            .return_type
+            .as_ref()
+            .map_or("()".to_string(), type_label);
+        if old_return != new_return {
+            self.push(
+                Compatibility::Breaking,
+                format!("{what} now returns `{new_return}` instead of `{old_return}`"),
+            );
+        }
+        match (&old.throws_type, &new.throws_type) {
+            (None, Some(new_throws)) => self.push(
+                Compatibility::Breaking,
+                format!("{what} now throws `{}`", type_label(new_throws)),
+            ),
+            (Some(old_throws), None) => self.push(
+                Compatibility::Breaking,
+                format!("{what} no longer throws `{}`", type_label(old_throws)),
+            ),
+            (Some(old_throws), Some(new_throws))
+                if type_label(old_throws) != type_label(new_throws) =>
+            {
+                self.push(
+                    Compatibility::Breaking,
+                    format!(
+                        "{what} now throws `{}` instead of `{}`",
+                        type_label(new_throws),
+                        type_label(old_throws)
+                    ),
+                )
+            }
+            _ => {}
+        }
+        if old.is_async != new.is_async {
+            let now = if new.is_async { "async" } else { "not async" };
+            self.push(Compatibility::Breaking, format!("{what} is now {now}"));
+        }
+        if old.takes_self_by_value != new.takes_self_by_value {
+            let change = if new.takes_self_by_value {
+                "now consumes the object"
+            } else {
+                "no longer consumes the object"
+            };
+            self.push(Compatibility::Breaking, format!("{what} {change}"));
+        }
+    }
+
+    // Foreign code that relied on a default value needs to pass the value once it's removed
+    fn default_value<T>(&mut self, what: &str, old: &Option<T>, new: &Option<T>) {
+        match (old, new) {
+            (Some(_), None) => self.push(
+                Compatibility::Breaking,
+                format!("{what} no longer has a default value"),
+            ),
+            (None, Some(_)) => self.push(
+                Compatibility::Additive,
+                format!("{what} now has a default value"),
+            ),
+            _ => {}
+        }
+    }
+
+    fn constants(
+        &mut self,
+        describe: impl Fn(&str) -> String,
+        old: &[ConstantModel],
+        new: &[ConstantModel],
+    ) {
+        let describe = &describe;
+        self.items(
+            describe,
+            old,
+            new,
+            |c| &c.name,
+            Compatibility::Additive,
+            |differ, old, new| {
+                let (old_type, new_type) = (type_label(&old.type_), type_label(&new.type_));
+                if old_type != new_type {
+                    differ.push(
+                        Compatibility::Breaking,
+                        format!(
+                            "{} is now `{new_type}` instead of `{old_type}`",
+                            describe(&old.name)
+                        ),
+                    );
+                }
+            },
+        );
+    }
+
+    // Arguments are passed by position in some languages and by name in others, so they're
+    // compared by position and a renamed argument is a breaking change.
+    fn arguments(&mut self, what: &str, old: &[ArgumentModel], new: &[ArgumentModel]) {
+        for (old_arg, new_arg) in old.iter().zip(new) {
+            if old_arg.name != new_arg.name {
+                self.push(
+                    Compatibility::Breaking,
+                    format!(
+                        "argument `{}` of {what} was renamed to `{}`",
+                        old_arg.name, new_arg.name
+                    ),
+                );
+            }
+            let (old_type, new_type) = (type_label(&old_arg.type_), type_label(&new_arg.type_));
+            if old_type != new_type {
+                self.push(
+                    Compatibility::Breaking,
+                    format!(
+                        "argument `{}` of {what} is now `{new_type}` instead of `{old_type}`",
+                        new_arg.name
+                    ),
+                );
+            }
+            self.default_value(
+                &format!("argument `{}` of {what}", new_arg.name),
+                &old_arg.default_value,
+                &new_arg.default_value,
+            );
+        }
+        for old_arg in old.iter().skip(new.len()) {
+            self.push(
+                Compatibility::Breaking,
+                format!("argument `{}` of {what} was removed", old_arg.name),
+            );
+        }
+        for new_arg in new.iter().skip(old.len()) {
+            let compatibility = if new_arg.default_value.is_some() {
+                Compatibility::Additive
+            } else {
+                Compatibility::Breaking
+            };
+            self.push(
+                compatibility,
+                format!("argument `{}` was added to {what}", new_arg.name),
+            );
+        }
+    }
+
+    fn object(&mut self, old: &ObjectModel, new: &ObjectModel) {
+        let name = &old.name;
+        if old.is_trait != new.is_trait {
+            let change = if new.is_trait {
+                "is now a trait interface"
+            } else {
+                "is no longer a trait interface"
+            };
+            self.push(Compatibility::Breaking, format!("object `{name}` {change}"));
+        }
+        match (&old.primary_constructor, &new.primary_constructor) {
+            (Some(old_cons), Some(new_cons)) => {
+                self.callable(&format!("the constructor of `{name}`"), old_cons, new_cons)
+            }
+            (Some(_), None) => self.push(
+                Compatibility::Breaking,
+                format!("the constructor of `{name}` was removed"),
+            ),
+            (None, Some(_)) => self.push(
+                Compatibility::Additive,
+                format!("a constructor was added to `{name}`"),
+            ),
+            (None, None) => {}
+        }
+        self.items(
+            |cons| format!("constructor `{name}.{cons}`"),
+            &old.alternate_constructors,
+            &new.alternate_constructors,
+            |c| &c.name,
+            Compatibility::Additive,
+            |differ, old, new| {
+                differ.callable(&format!("constructor `{name}.{}`", old.name), old, new)
+            },
+        );
+        self.items(
+            |meth| format!("method `{name}.{meth}`"),
+            &old.methods,
+            &new.methods,
+            |m| &m.name,
+            Compatibility::Additive,
+            |differ, old, new| differ.callable(&format!("method `{name}.{}`", old.name), old, new),
+        );
+        // The methods of the traits are what the bindings expose, like `__eq__` or `toString()`,
+        // so only the traits themselves are compared
+        self.items(
+            |trait_| format!("trait `{trait_}` of `{name}`"),
+            &old.traits,
+            &new.traits,
+            |t| &t.trait_,
+            Compatibility::Additive,
+            |_, _, _| {},
+        );
+        self.constants(
+            |constant| format!("constant `{name}.{constant}`"),
+            &old.constants,
+            &new.constants,
+        );
+    }
+
+    fn fields(&mut self, what: &str, old: &[FieldModel], new: &[FieldModel], added: Compatibility) {
+        self.items(
+            |field| format!("field `{field}` of {what}"),
+            old,
+            new,
+            |f| &f.name,
+            added,
+            |differ, old, new| {
+                let (old_type, new_type) = (type_label(&old.type_), type_label(&new.type_));
+                if old_type != new_type {
+                    differ.push(
+                        Compatibility::Breaking,
+                        format!(
+                            "field `{}` of {what} is now `{new_type}` instead of `{old_type}`",
+                            old.name
+                        ),
+                    );
+                }
+                differ.default_value(
+                    &format!("field `{}` of {what}", old.name),
+                    &old.default_value,
+                    &new.default_value,
+                );
+            },
+        );
+    }
+
+    fn record(&mut self, old: &RecordModel, new: &RecordModel) {
+        let what = format!("record `{}`", old.name);
+        // Foreign code constructs records, so new fields need a default value
+        let fields_with_defaults = new
+            .fields
+            .iter()
+            .filter(|f| !old.fields.iter().any(|old_field| old_field.name == f.name))
+            .all(|f| f.default_value.is_some());
+        let added = if fields_with_defaults {
+            Compatibility::Additive
+        } else {
+            Compatibility::Breaking
+        };
+        self.fields(&what, &old.fields, &new.fields, added);
+    }
+
+    fn enum_(&mut self, old: &EnumModel, new: &EnumModel) {
+        let kind = if new.is_error { "error" } else { "enum" };
+        let what = format!("{kind} `{}`", old.name);
+        if old.is_error != new.is_error {
+            let now = if new.is_error {
+                "an error"
+            } else {
+                "no longer an error"
+            };
+            self.push(Compatibility::Breaking, format!("{what} is now {now}"));
+        }
+        if old.is_non_exhaustive != new.is_non_exhaustive {
+            let now = if new.is_non_exhaustive {
+                "non-exhaustive"
+            } else {
+                "exhaustive"
+            };
+            self.push(Compatibility::Breaking, format!("{what} is now {now}"));
+        }
+        // Flat errors only have a message, rather than the fields of their variants
+        if old.is_flat != new.is_flat {
+            let change = if new.is_flat {
+                "is now flat"
+            } else {
+                "is no longer flat"
+            };
+            self.push(Compatibility::Breaking, format!("{what} {change}"));
+        }
+        let added = if new.is_error || (old.is_non_exhaustive && new.is_non_exhaustive) {
+            Compatibility::Additive
+        } else {
+            Compatibility::Breaking
+        };
+        self.items(
+            |variant| format!("variant `{variant}` of {what}"),
+            &old.variants,
+            &new.variants,
+            |v| &v.name,
+            added,
+            |differ, old, new| {
+                let variant = format!("variant `{}` of {what}", old.name);
+                if old.discr != new.discr {
+                    differ.push(
+                        Compatibility::Breaking,
+                        format!(
+                            "the discriminant of {variant} changed from {} to {}",
+                            discr_label(old.discr),
+                            discr_label(new.discr)
+                        ),
+                    );
+                }
+                differ.fields(&variant, &old.fields, &new.fields, Compatibility::Breaking);
+            },
+        );
+    }
+
+    fn flags(&mut self, old: &FlagsModel, new: &FlagsModel) {
+        let what = format!("flags `{}`", old.name);
+        self.items(
+            |flag| format!("flag `{flag}` of {what}"),
+            &old.flags,
+            &new.flags,
+            |f| &f.name,
+            Compatibility::Additive,
+            |differ, old, new| {
+                if old.value != new.value {
+                    differ.push(
+                        Compatibility::Breaking,
+                        format!(
+                            "the value of flag `{}` of {what} changed from {} to {}",
+                            old.name, old.value, new.value
+                        ),
+                    );
+                }
+            },
+        );
+    }
+
+    fn callback_interface(&mut self, old: &CallbackInterfaceModel, new: &CallbackInterfaceModel) {
+        let name = &old.name;
+        // Foreign implementations can leave out methods with a default implementation
+        let methods_with_defaults = new
+            .methods
+            .iter()
+            .filter(|m| !old.methods.iter().any(|old_meth| old_meth.name == m.name))
+            .all(|m| m.has_default);
+        let added = if methods_with_defaults {
+            Compatibility::Additive
+        } else {
+            Compatibility::Breaking
+        };
+        self.items(
+            |meth| format!("callback method `{name}.{meth}`"),
+            &old.methods,
+            &new.methods,
+            |m| &m.name,
+            added,
+            |differ, old, new| {
+                let what = format!("method `{name}.{}`", old.name);
+                differ.callable(&what, old, new);
+                // Foreign implementations that relied on the default implementation need to
+                // implement the method once it's removed
+                match (old.has_default, new.has_default) {
+                    (true, false) => differ.push(
+                        Compatibility::Breaking,
+                        format!("{what} no longer has a default implementation"),
+                    ),
+                    (false, true) => differ.push(
+                        Compatibility::Additive,
+                        format!("{what} now has a default implementation"),
+                    ),
+                    _ => {}
+                }
+            },
+        );
+        self.constants(
+            |constant| format!("constant `{name}.{constant}`"),
+            &old.constants,
+            &new.constants,
+        );
+    }
+}
+
+fn discr_label(discr: Option<i64>) -> String {
+    match discr {
+        Some(discr) => discr.to_string(),
+        None => "none".to_string(),
+    }
+}
+
+/// A type as it's written in Rust, ignoring the module it's defined in
+fn type_label(type_: &TypeModel) -> String {
+    match type_ {
+        TypeModel::UInt8 => "u8".to_string(),
+        TypeModel::Int8 => "i8".to_string(),
+        TypeModel::UInt16 => "u16".to_string(),
+        TypeModel::Int16 => "i16".to_string(),
+        TypeModel::UInt32 => "u32".to_string(),
+        TypeModel::Int32 => "i32".to_string(),
+        TypeModel::UInt64 => "u64".to_string(),
+        TypeModel::Int64 => "i64".to_string(),
+        TypeModel::Float32 => "f32".to_string(),
+        TypeModel::Float64 => "f64".to_string(),
+        TypeModel::Boolean => "bool".to_string(),
+        TypeModel::Char => "char".to_string(),
+        TypeModel::String => "String".to_string(),
+        TypeModel::Bytes => "Vec<u8>".to_string(),
+        TypeModel::Timestamp => "SystemTime".to_string(),
+        TypeModel::Duration => "Duration".to_string(),
+        TypeModel::Url => "Url".to_string(),
+        TypeModel::Uuid => "Uuid".to_string(),
+        TypeModel::Decimal => "Decimal".to_string(),
+        TypeModel::Json => "serde_json::Value".to_string(),
+        TypeModel::Path => "PathBuf".to_string(),
+        TypeModel::IpAddr => "IpAddr".to_string(),
+        TypeModel::SocketAddr => "SocketAddr".to_string(),
+        TypeModel::ForeignExecutor => "ForeignExecutor".to_string(),
+        TypeModel::Object { name, .. } => format!("Arc<{name}>"),
+        TypeModel::Record { name, .. }
+        | TypeModel::Enum { name, .. }
+        | TypeModel::Flags { name, .. }
+        | TypeModel::CallbackInterface { name, .. }
+        | TypeModel::External { name, .. }
+        | TypeModel::Custom { name, .. } => name.clone(),
+        TypeModel::Optional { inner_type } => format!("Option<{}>", type_label(inner_type)),
+        TypeModel::Sequence { inner_type } => format!("Vec<{}>", type_label(inner_type)),
+        TypeModel::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "HashMap<{}, {}>",
+            type_label(key_type),
+            type_label(value_type)
+        ),
+        TypeModel::Compressed { inner_type } => format!("Compressed<{}>", type_label(inner_type)),
+        TypeModel::Shared { inner_type } => format!("Shared<{}>", type_label(inner_type)),
+        TypeModel::Weak { inner_type } => format!("Weak<{}>", type_label(inner_type)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{external_generator::LiteralModel, interface::ComponentInterface};
+
+    fn model(udl: &str) -> ComponentModel {
+        ComponentModel::new(&ComponentInterface::from_webidl(udl).unwrap())
+    }
+
+    fn diff_models(old: &ComponentModel, new: &ComponentModel) -> Vec<String> {
+        diff_components(old, new)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn diff(old_udl: &str, new_udl: &str) -> Vec<String> {
+        diff_models(&model(old_udl), &model(new_udl))
+    }
+
+    #[test]
+    fn functions() {
+        let old = r#"namespace test {
+            u32 add(u32 a, u32 b);
+            void reset();
+        };"#;
+        let new = r#"namespace test {
+            u64 add(u32 a, u32 b, optional u32 c = 0);
+            void clear();
+        };"#;
+        assert_eq!(
+            diff(old, new),
+            vec![
+                "additive: argument `c` was added to function `add`",
+                "breaking: function `add` now returns `u64` instead of `u32`",
+                "breaking: function `reset` was removed",
+                "additive: function `clear` was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_and_enums() {
+        let old = r#"namespace test {
+            [Throws=StoreError]
+            void save(Color color);
+        };
+        enum Color { "Red", "Green" };
+        [Error]
+        enum StoreError { "Full" };"#;
+        let new = r#"namespace test {
+            [Throws=StoreError]
+            void save(Color color);
+        };
+        enum Color { "Red", "Green", "Blue" };
+        [Error]
+        enum StoreError { "Full", "ReadOnly" };"#;
+        assert_eq!(
+            diff(old, new),
+            vec![
+                "breaking: variant `Blue` of enum `Color` was added",
+                "additive: variant `ReadOnly` of error `StoreError` was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn records() {
+        let old = r#"namespace test {};
+        dictionary Point { i32 x; i32 y; };"#;
+        let with_default = r#"namespace test {};
+        dictionary Point { i32 x; i32 y; i32 z = 0; };"#;
+        let changed = r#"namespace test {};
+        dictionary Point { f64 x; i32 y; i32 z; };"#;
+        assert_eq!(
+            diff(old, with_default),
+            vec!["additive: field `z` of record `Point` was added"]
+        );
+        assert_eq!(
+            diff(old, changed),
+            vec![
+                "breaking: field `x` of record `Point` is now `f64` instead of `i32`",
+                "breaking: field `z` of record `Point` was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn default_values() {
+        let old = r#"namespace test {
+            void connect(string host, optional u16 port = 80, optional u32 retries);
+        };
+        dictionary Options { boolean verbose = false; u32 timeout; };"#;
+        let new = r#"namespace test {
+            void connect(string host, optional u16 port, optional u32 retries = 3);
+        };
+        dictionary Options { boolean verbose; u32 timeout = 30; };"#;
+        assert_eq!(
+            diff(old, new),
+            vec![
+                "breaking: argument `port` of function `connect` no longer has a default value",
+                "additive: argument `retries` of function `connect` now has a default value",
+                "breaking: field `verbose` of record `Options` no longer has a default value",
+                "additive: field `timeout` of record `Options` now has a default value",
+            ]
+        );
+    }
+
+    #[test]
+    fn objects() {
+        let old = r#"namespace test {};
+        [Traits=(Display, Eq)]
+        interface Counter {
+            void increment();
+        };
+        interface Store {};"#;
+        let new = r#"namespace test {};
+        [Traits=(Debug, Eq)]
+        interface Counter {
+            void increment();
+        };
+        [Trait]
+        interface Store {};"#;
+        assert_eq!(
+            diff(old, new),
+            vec![
+                "breaking: trait `Display` of `Counter` was removed",
+                "additive: trait `Debug` of `Counter` was added",
+                "breaking: object `Store` is now a trait interface",
+            ]
+        );
+    }
+
+    #[test]
+    fn consuming_methods() {
+        let udl = r#"namespace test {};
+        interface Builder {
+            string build();
+        };"#;
+        let old = model(udl);
+        let mut new = model(udl);
+        new.objects[0].methods[0].takes_self_by_value = true;
+        assert_eq!(
+            diff_models(&old, &new),
+            vec!["breaking: method `Builder.build` now consumes the object"]
+        );
+    }
+
+    #[test]
+    fn flat_errors() {
+        let old = r#"namespace test {};
+        [Error]
+        enum StoreError { "Full" };"#;
+        let new = r#"namespace test {};
+        [Error]
+        interface StoreError { Full(u32 capacity); };"#;
+        assert_eq!(
+            diff(old, new),
+            vec![
+                "breaking: error `StoreError` is no longer flat",
+                "breaking: field `capacity` of variant `Full` of error `StoreError` was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn constants() {
+        let udl = r#"namespace test {};"#;
+        let constant = |name: &str, type_: TypeModel| ConstantModel {
+            name: name.to_string(),
+            type_,
+            value: LiteralModel::Boolean { value: true },
+        };
+        let mut old = model(udl);
+        old.constants = vec![
+            constant("MAX_SIZE", TypeModel::UInt32),
+            constant("DEBUG", TypeModel::Boolean),
+        ];
+        let mut new = model(udl);
+        new.constants = vec![
+            constant("MAX_SIZE", TypeModel::UInt64),
+            constant("VERSION", TypeModel::String),
+        ];
+        assert_eq!(
+            diff_models(&old, &new),
+            vec![
+                "breaking: constant `MAX_SIZE` is now `u64` instead of `u32`",
+                "breaking: constant `DEBUG` was removed",
+                "additive: constant `VERSION` was added",
+            ]
+        );
+    }
+
+    #[test]
+    fn callback_interface_default_methods() {
+        let udl = r#"namespace test {};
+        callback interface Listener {
+            void on_event(string name);
+            void on_error(string message);
+        };"#;
+        let old = model(udl);
+        let mut new = model(udl);
+        new.callback_interfaces[0].methods[1].has_default = true;
+        let mut added = old.clone();
+        added.callback_interfaces[0].methods.push(CallableModel {
+            name: "on_close".to_string(),
+            has_default: true,
+            ..old.callback_interfaces[0].methods[0].clone()
+        });
+        assert_eq!(
+            diff_models(&old, &new),
+            vec!["additive: method `Listener.on_error` now has a default implementation"]
+        );
+        assert_eq!(
+            diff_models(&new, &old),
+            vec!["breaking: method `Listener.on_error` no longer has a default implementation"]
+        );
+        assert_eq!(
+            diff_models(&old, &added),
+            vec!["additive: callback method `Listener.on_close` was added"]
+        );
+    }
+}
@@ -2,7 +2,11 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use heck::{ToLowerCamelCase, ToSnakeCase};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::interface::Type;
 
 /// Config value for template expressions
 ///
@@ -15,3 +19,131 @@ impl TemplateExpression {
         self.0.replace("{}", var)
     }
 }
+
+/// Code from `uniffi.toml` that's inserted into the generated bindings
+///
+/// This lets projects add license headers, imports and helpers to the bindings without changing
+/// the templates.  The code is inserted as-is, it's not rendered as a template.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CodeHooks {
+    preamble: Option<String>,
+    after_imports: Option<String>,
+    footer: Option<String>,
+    #[serde(default)]
+    type_extensions: HashMap<String, String>,
+    #[serde(default)]
+    type_overrides: HashMap<String, String>,
+}
+
+impl CodeHooks {
+    /// Code for the start of the file, e.g. a license header
+    pub fn preamble(&self) -> Option<&str> {
+        self.preamble.as_deref()
+    }
+
+    /// Code for after the imports of the generated code, e.g. more imports
+    pub fn after_imports(&self) -> Option<&str> {
+        self.after_imports.as_deref()
+    }
+
+    /// Code for the end of the file, e.g. helper functions
+    pub fn footer(&self) -> Option<&str> {
+        self.footer.as_deref()
+    }
+
+    /// Code for after the definition of `type_`, e.g. an extension of it
+    ///
+    /// Extensions are looked up by the name of records, enums, flags, objects, callback interfaces
+    /// and custom types.
+    pub fn type_extension(&self, type_: &Type) -> Option<&str> {
+        let name = match type_ {
+            Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Flags { name, .. }
+            | Type::Object { name, .. }
+            | Type::CallbackInterface { name, .. }
+            | Type::Custom { name, .. } => name,
+            _ => return None,
+        };
+        self.type_extensions.get(name).map(String::as_str)
+    }
+
+    pub fn has_type_extensions(&self) -> bool {
+        !self.type_extensions.is_empty()
+    }
+
+    /// Code that replaces the generated definition of `type_`
+    ///
+    /// Overrides are looked up by the name of records and enums.  Only the class or struct of the
+    /// type is replaced, the generated code that passes it to and from Rust is kept, so the
+    /// replacement must have the same fields and variants.
+    pub fn type_override(&self, type_: &Type) -> Option<&str> {
+        let name = match type_ {
+            Type::Record { name, .. } | Type::Enum { name, .. } => name,
+            _ => return None,
+        };
+        self.type_overrides.get(name).map(String::as_str)
+    }
+
+    pub fn has_type_overrides(&self) -> bool {
+        !self.type_overrides.is_empty()
+    }
+}
+
+/// How record fields and function arguments are cased in the bindings
+///
+/// Each language defaults to its idiomatic casing.  This only changes the names in the generated
+/// code, fields and arguments are passed across the FFI by position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldNaming {
+    #[serde(rename = "snake_case")]
+    SnakeCase,
+    #[serde(rename = "camelCase")]
+    CamelCase,
+}
+
+impl FieldNaming {
+    pub fn apply(self, nm: &str) -> String {
+        match self {
+            Self::SnakeCase => nm.to_snake_case(),
+            Self::CamelCase => nm.to_lower_camel_case(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_type_hooks() {
+        let hooks: CodeHooks = toml::from_str(
+            r#"
+            [type_extensions]
+            Point = "extension"
+            Counter = "counter extension"
+
+            [type_overrides]
+            Point = "override"
+            Counter = "ignored"
+            "#,
+        )
+        .unwrap();
+        let record = Type::Record {
+            name: "Point".into(),
+            module_path: "".into(),
+        };
+        let object = Type::Object {
+            name: "Counter".into(),
+            module_path: "".into(),
+            imp: crate::interface::ObjectImpl::Struct,
+        };
+        assert_eq!(hooks.type_extension(&record), Some("extension"));
+        assert_eq!(hooks.type_override(&record), Some("override"));
+        assert_eq!(hooks.type_extension(&object), Some("counter extension"));
+        // Only records and enums can be overridden
+        assert_eq!(hooks.type_override(&object), None);
+        assert_eq!(hooks.type_override(&Type::String), None);
+        assert!(hooks.has_type_overrides());
+    }
+}
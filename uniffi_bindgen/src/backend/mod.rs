@@ -7,5 +7,16 @@ pub mod filters;
 mod types;
 
 pub use crate::interface::{Literal, Type};
-pub use config::TemplateExpression;
+pub use config::{CodeHooks, FieldNaming, TemplateExpression};
 pub use types::CodeType;
+
+/// Whether values of `type_` are serialized as an `i32` length followed by the raw bytes
+///
+/// The bindings can read these values in place, see the `zero_copy_bytes` config options.
+pub fn is_byte_buffer(type_: &Type) -> bool {
+    match type_ {
+        Type::Bytes => true,
+        Type::Sequence { inner_type } => **inner_type == Type::UInt8,
+        _ => false,
+    }
+}
@@ -0,0 +1,363 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::{Context, Result};
+use askama::Template;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// Some config options for the C header.
+// Note that this can only be used to control details *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    header_name: Option<String>,
+}
+
+impl Config {
+    /// The name of the header file, without the `.h` extension.  Defaults to the namespace.
+    pub fn header_name(&self) -> String {
+        self.header_name
+            .clone()
+            .expect("header name should have been set in update_from_ci")
+    }
+
+    pub fn header_filename(&self) -> String {
+        format!("{}.h", self.header_name())
+    }
+}
+
+impl BindingsConfig for Config {
+    const TOML_KEY: &'static str = "c";
+
+    fn update_from_ci(&mut self, ci: &ComponentInterface) {
+        self.header_name
+            .get_or_insert_with(|| ci.namespace().into());
+    }
+
+    fn update_from_cdylib_name(&mut self, _cdylib_name: &str) {}
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+// Generate the C header for the given ComponentInterface, as a string.
+pub fn generate_header(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    CHeader::new(config.clone(), ci)
+        .render()
+        .context("failed to render C header")
+}
+
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "CHeaderTemplate.h")]
+pub struct CHeader<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> CHeader<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    fn include_guard(&self) -> String {
+        format!("UNIFFI_{}_H", c_macro_name(&self.config.header_name()))
+    }
+
+    /// The name of the macro with the expected result of the contract version function
+    fn contract_version_macro(&self) -> String {
+        format!(
+            "UNIFFI_{}_CONTRACT_VERSION",
+            c_macro_name(self.ci.namespace())
+        )
+    }
+
+    /// The names of the macros with the expected results of the checksum functions, and their
+    /// values
+    fn checksum_macros(&self) -> Vec<(String, String, u16)> {
+        self.ci
+            .iter_checksums()
+            .map(|(fn_name, checksum)| {
+                let macro_name = c_macro_name(&fn_name);
+                (fn_name, macro_name, checksum)
+            })
+            .collect()
+    }
+
+    // `FfiDeclarations.h` is shared with the Swift bridging header, which annotates the
+    // nullability of its pointers.  Plain C doesn't have these annotations.
+    fn nullable(&self) -> &'static str {
+        ""
+    }
+
+    fn nonnull(&self) -> &'static str {
+        ""
+    }
+}
+
+// Words that can't be used as identifiers in C or C++, since the header is also included from C++
+static C_KEYWORDS: &[&str] = &[
+    "alignas",
+    "alignof",
+    "and",
+    "and_eq",
+    "asm",
+    "auto",
+    "bitand",
+    "bitor",
+    "bool",
+    "break",
+    "case",
+    "catch",
+    "char",
+    "char16_t",
+    "char32_t",
+    "char8_t",
+    "class",
+    "compl",
+    "concept",
+    "const",
+    "const_cast",
+    "consteval",
+    "constexpr",
+    "constinit",
+    "continue",
+    "co_await",
+    "co_return",
+    "co_yield",
+    "decltype",
+    "default",
+    "delete",
+    "do",
+    "double",
+    "dynamic_cast",
+    "else",
+    "enum",
+    "explicit",
+    "export",
+    "extern",
+    "false",
+    "float",
+    "for",
+    "friend",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "mutable",
+    "namespace",
+    "new",
+    "noexcept",
+    "not",
+    "not_eq",
+    "nullptr",
+    "operator",
+    "or",
+    "or_eq",
+    "private",
+    "protected",
+    "public",
+    "register",
+    "reinterpret_cast",
+    "requires",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "static_assert",
+    "static_cast",
+    "struct",
+    "switch",
+    "template",
+    "this",
+    "thread_local",
+    "throw",
+    "true",
+    "try",
+    "typedef",
+    "typeid",
+    "typename",
+    "typeof",
+    "union",
+    "unsigned",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "wchar_t",
+    "while",
+    "xor",
+    "xor_eq",
+];
+
+// Upper case a name, replacing the characters that aren't valid in a C identifier
+fn c_macro_name(nm: &str) -> String {
+    nm.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+pub mod filters {
+    use super::*;
+
+    /// The C type of an FFI type
+    pub fn header_ffi_type_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "int8_t".into(),
+            FfiType::UInt8 => "uint8_t".into(),
+            FfiType::Int16 => "int16_t".into(),
+            FfiType::UInt16 => "uint16_t".into(),
+            FfiType::Int32 => "int32_t".into(),
+            FfiType::UInt32 => "uint32_t".into(),
+            FfiType::Int64 => "int64_t".into(),
+            FfiType::UInt64 => "uint64_t".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::RustArcPtr(_) => "void*".into(),
+            FfiType::RustBuffer(_) => "RustBuffer".into(),
+            FfiType::ForeignBytes => "ForeignBytes".into(),
+            FfiType::ForeignCallback => "ForeignCallback".into(),
+            FfiType::ForeignExecutorCallback => "UniFfiForeignExecutorCallback".into(),
+            FfiType::ForeignExecutorHandle => "size_t".into(),
+            FfiType::FutureCallback { return_type } => {
+                format!("UniFfiFutureCallback{}", ffi_canonical_name(return_type)?)
+            }
+            FfiType::FutureCallbackData => "void*".into(),
+            FfiType::Struct(name) => format!("UniffiReprC{name}"),
+        })
+    }
+
+    /// A name for an FFI type that can be part of a C identifier
+    pub fn ffi_canonical_name(ffi_type: &FfiType) -> Result<String, askama::Error> {
+        Ok(match ffi_type {
+            FfiType::Int8 => "Int8".into(),
+            FfiType::UInt8 => "UInt8".into(),
+            FfiType::Int16 => "Int16".into(),
+            FfiType::UInt16 => "UInt16".into(),
+            FfiType::Int32 => "Int32".into(),
+            FfiType::UInt32 => "UInt32".into(),
+            FfiType::Int64 => "Int64".into(),
+            FfiType::UInt64 => "UInt64".into(),
+            FfiType::Float32 => "Float".into(),
+            FfiType::Float64 => "Double".into(),
+            FfiType::RustArcPtr(_) | FfiType::FutureCallbackData => "Pointer".into(),
+            _ => header_ffi_type_name(ffi_type)?,
+        })
+    }
+
+    /// An argument or field name that doesn't clash with a C or C++ keyword
+    pub fn c_identifier(nm: &str) -> Result<String, askama::Error> {
+        Ok(if C_KEYWORDS.contains(&nm) {
+            format!("{nm}_")
+        } else {
+            nm.to_string()
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::process::Command;
+
+    const UDL: &str = r#"
+        namespace example {
+            u32 add(u32 a, u32 b);
+            [Throws=ArithmeticError]
+            u64 checked_sub(u64 a, u64 b);
+            Point translate(Point point, i32 auto, i32 register);
+        };
+        [Error]
+        enum ArithmeticError { "IntegerOverflow" };
+        [ReprC]
+        dictionary Point {
+            i32 x;
+            i32 y;
+            i32 char;
+        };
+        interface Counter {
+            constructor();
+            void increment();
+        };
+        callback interface Logger {
+            void log(string message);
+        };
+    "#;
+
+    fn example_header() -> (Config, ComponentInterface, String) {
+        let mut ci = ComponentInterface::from_webidl(UDL).unwrap();
+        ci.derive_ffi_funcs().unwrap();
+        let mut config = Config::default();
+        config.update_from_ci(&ci);
+        let header = generate_header(&config, &ci).unwrap();
+        (config, ci, header)
+    }
+
+    #[test]
+    fn test_generate_header() {
+        let (config, ci, header) = example_header();
+        assert_eq!(config.header_filename(), "example.h");
+        assert!(header.contains("#ifndef UNIFFI_EXAMPLE_H\n"));
+        assert!(header.contains("typedef struct RustCallStatus {"));
+        assert!(header.contains("uint32_t uniffi_example_fn_func_add(uint32_t a, uint32_t b, RustCallStatus* out_status);"));
+        assert!(header.contains(
+            "void* uniffi_example_fn_constructor_counter_new(RustCallStatus* out_status);"
+        ));
+        assert!(header.contains(
+            "void uniffi_example_fn_free_counter(void* ptr, RustCallStatus* out_status);"
+        ));
+        assert!(header.contains("void uniffi_example_fn_init_callback_logger(ForeignCallback callback_stub, RustCallStatus* out_status);"));
+        assert!(header.contains("void uniffi_example_fn_detach_callback_logger(uint64_t handle, RustCallStatus* out_status);"));
+        assert!(header.contains(
+            "void ffi_example_rustbuffer_free(RustBuffer buf, RustCallStatus* out_status);"
+        ));
+        assert!(header.contains("uint32_t ffi_example_uniffi_contract_version(void);"));
+        assert!(header.contains(&format!(
+            "#define UNIFFI_EXAMPLE_CONTRACT_VERSION {}\n",
+            ci.uniffi_contract_version()
+        )));
+        // Names that are C keywords get a trailing underscore
+        assert!(header.contains("    int32_t char_;\n"));
+        assert!(header.contains("UniffiReprCPoint uniffi_example_fn_func_translate(UniffiReprCPoint point, int32_t auto_, int32_t register_, RustCallStatus* out_status);"));
+    }
+
+    // Check that the header compiles as both C and C++, with the C compiler from `CC` like the
+    // Kotlin tests use for the JNI shims.
+    #[test]
+    fn test_header_compiles() {
+        let (config, _, header) = example_header();
+        let out_dir = env::temp_dir().join(format!("uniffi-c-header-{}", std::process::id()));
+        fs_err::create_dir_all(&out_dir).unwrap();
+        fs_err::write(out_dir.join(config.header_filename()), header).unwrap();
+        let source = out_dir.join("main.c");
+        fs_err::write(
+            &source,
+            "#include \"example.h\"\n#include \"example.h\"\nint main(void) { return 0; }\n",
+        )
+        .unwrap();
+
+        for language in ["c", "c++"] {
+            let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+                .args(["-fsyntax-only", "-Wall", "-Werror", "-x", language])
+                .arg(&source)
+                .status()
+                .expect("Failed to spawn the C compiler");
+            assert!(status.success(), "the header doesn't compile as {language}");
+        }
+        fs_err::remove_dir_all(&out_dir).unwrap();
+    }
+}
@@ -0,0 +1,30 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a C header for the scaffolding.
+//!
+//! Rather than bindings for a foreign language, this backend writes a header that declares the
+//! FFI functions of the component, the structs that they pass, and the conventions for calling
+//! them.  Consumers on platforms that UniFFI doesn't generate bindings for can call the
+//! scaffolding through it, from C or from any language that can call C functions.
+
+use anyhow::Result;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_c;
+use super::super::interface::ComponentInterface;
+pub use gen_c::{generate_header, Config};
+
+// Generate the C header for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    _try_format_code: bool,
+) -> Result<()> {
+    let header_file = out_dir.join(config.header_filename());
+    fs::write(header_file, generate_header(config, ci)?)?;
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// The FFI of the `{{ ci.namespace() }}` component.
+//
+// Calling conventions:
+//
+// - Most functions take a `RustCallStatus*` as their last argument, which must point to a
+//   zero-initialized `RustCallStatus`.  After the call, its `code` is:
+//     0: The call succeeded.
+//     1: The call returned an error.  `errorBuf` holds the serialized error, which must be freed
+//        with `{{ ci.ffi_rustbuffer_free().name() }}`.
+//     2: The call panicked.  `errorBuf` holds the panic message and then its backtrace, if it
+//        isn't empty, each serialized like a string in a buffer.  It must also be freed.
+//     3: An argument couldn't be lifted, like a zero for a `NonZeroU32`.  `errorBuf` holds the
+//        message as a UTF-8 string, which must also be freed.
+//   The return value must be ignored unless the `code` is 0.
+//
+// - Strings, records, enums and the other compound types are serialized into `RustBuffer`s, see
+//   https://mozilla.github.io/uniffi-rs/internals/lifting_and_lowering.html for the format.
+//   The buffers passed to Rust are consumed, they must be allocated with
+//   `{{ ci.ffi_rustbuffer_alloc().name() }}` or `{{ ci.ffi_rustbuffer_from_bytes().name() }}`.
+//   The buffers that Rust returns are owned by the caller, who must free them with
+//   `{{ ci.ffi_rustbuffer_free().name() }}`.
+//
+// - Objects are passed as pointers.  The pointers passed to methods are borrowed, while each
+//   pointer returned by a constructor or function must be freed exactly once with the object's
+//   `free` function.
+//
+// - Booleans are passed as `int8_t`s, 0 for false and 1 for true.
+//
+// - Callback interfaces are implemented by registering a `ForeignCallback` with their
+//   `init_callback` function.  It's called with the handle of the object, the index of the method
+//   (starting at 1, method 0 frees the handle), the serialized arguments and a `RustBuffer` for
+//   the result.  It returns 0 on success, 1 if the method returned an error and 2 on unexpected
+//   errors, with the serialized return value or error written to the buffer.
+//
+// - The header was generated for a specific version of the scaffolding.  Callers should check
+//   that the contract version and the checksum functions return the values of the macros below
+//   before calling anything else.
+
+#ifndef {{ self.include_guard() }}
+#define {{ self.include_guard() }}
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define {{ self.contract_version_macro() }} {{ ci.uniffi_contract_version() }}
+{%- for (fn_name, macro_name, checksum) in self.checksum_macros() %}
+#define {{ macro_name }} {{ checksum }} // {{ fn_name }}
+{%- endfor %}
+
+{% include "FfiDeclarations.h" %}
+
+#ifdef __cplusplus
+} // extern "C"
+#endif
+
+#endif // {{ self.include_guard() }}
@@ -0,0 +1,81 @@
+// The following structs are used to implement the lowest level
+// of the FFI, and thus useful to multiple uniffied crates.
+// We ensure they are declared exactly once, with a header guard, UNIFFI_SHARED_H.
+#ifdef UNIFFI_SHARED_H
+    // We also try to prevent mixing versions of shared uniffi header structs.
+    // If you add anything to the #else block, you must increment the version suffix in UNIFFI_SHARED_HEADER_V4
+    #ifndef UNIFFI_SHARED_HEADER_V4
+        #error Combining helper code from multiple versions of uniffi is not supported
+    #endif // ndef UNIFFI_SHARED_HEADER_V4
+#else
+#define UNIFFI_SHARED_H
+#define UNIFFI_SHARED_HEADER_V4
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+
+typedef struct RustBuffer
+{
+    int32_t capacity;
+    int32_t len;
+    uint8_t*{{ self.nullable() }} data;
+} RustBuffer;
+
+typedef int32_t (*ForeignCallback)(uint64_t, int32_t, const uint8_t*{{ self.nonnull() }}, int32_t, RustBuffer*{{ self.nonnull() }});
+
+// Task defined in Rust that the foreign code executes
+typedef void (*UniFfiRustTaskCallback)(const void*{{ self.nullable() }}, int8_t);
+
+// Callback to execute Rust tasks with the foreign executor
+//
+// Args:
+//   executor: ForeignExecutor lowered into a size_t value
+//   delay: Delay in MS
+//   task: UniFfiRustTaskCallback to call
+//   task_data: data to pass the task callback
+typedef int8_t (*UniFfiForeignExecutorCallback)(size_t, uint32_t, UniFfiRustTaskCallback{{ self.nullable() }}, const void*{{ self.nullable() }});
+
+typedef struct ForeignBytes
+{
+    int32_t len;
+    const uint8_t*{{ self.nullable() }} data;
+} ForeignBytes;
+
+// Error definitions
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
+
+// ⚠️ Attention: If you change this #else block (ending in `#endif // def UNIFFI_SHARED_H`) you *must* ⚠️
+// ⚠️ increment the version suffix in all instances of UNIFFI_SHARED_HEADER_V4 in this file.           ⚠️
+#endif // def UNIFFI_SHARED_H
+
+// Structs for records that are passed by value
+{%- for rec in ci.record_definitions() %}
+{%- if rec.is_repr_c() %}
+{%- let struct_name = rec.ffi_type().borrow()|header_ffi_type_name %}
+typedef struct {{ struct_name }}
+{
+    {%- for field in rec.fields() %}
+    {{ field.ffi_type().borrow()|header_ffi_type_name }} {{ field.name()|c_identifier }};
+    {%- endfor %}
+} {{ struct_name }};
+{%- endif %}
+{%- endfor %}
+
+// Callbacks for UniFFI Futures
+{%- for ffi_type in ci.iter_future_callback_params() %}
+typedef void (*UniFfiFutureCallback{{ ffi_type|ffi_canonical_name }})(const void*{{ self.nonnull() }}, {{ ffi_type|header_ffi_type_name }}, RustCallStatus);
+{%- endfor %}
+
+// Scaffolding functions
+{%- for func in ci.iter_ffi_function_definitions() %}
+{% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|header_ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+    {%- for arg in func.arguments() %}
+        {{- arg.type_().borrow()|header_ffi_type_name }} {{ arg.name()|c_identifier -}}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}RustCallStatus*{{ self.nonnull() }} out_status
+    {%- else if func.arguments().is_empty() %}void
+    {%- endif -%}
+);
+{%- endfor %}
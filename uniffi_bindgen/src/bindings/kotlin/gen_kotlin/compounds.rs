@@ -52,6 +52,9 @@ macro_rules! impl_code_type_for_compound {
 
 impl_code_type_for_compound!(OptionalCodeType, "{}?", "Optional{}");
 impl_code_type_for_compound!(SequenceCodeType, "List<{}>", "Sequence{}");
+impl_code_type_for_compound!(CompressedCodeType, "{}", "Compressed{}");
+impl_code_type_for_compound!(SharedCodeType, "{}", "Shared{}");
+impl_code_type_for_compound!(WeakCodeType, "WeakRef<{}>", "Weak{}");
 
 #[derive(Debug)]
 pub struct MapCodeType {
@@ -0,0 +1,325 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Kotlin bindings that call the scaffolding through JNI
+//!
+//! These have the same public API as the JNA bindings, but the scaffolding functions are declared
+//! as `external` functions of a `UniffiLib` object, which a generated C shim implements by calling
+//! the scaffolding functions.  The shim copies `RustBuffer`s to and from Kotlin byte arrays, and
+//! the call status to a `UniffiRustCallStatus`, so the Kotlin code never handles native memory.
+//!
+//! The converters and object implementations are rendered from `PortableTypes.kt`, the template of
+//! the Kotlin/Native bindings, so both support the same types and features.
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+
+use super::multiplatform::check_portable_support;
+use super::{filters, Config, ImportRequirement, KotlinCodeOracle};
+use crate::interface::*;
+
+/// The files of the JNI bindings for a component
+pub struct JniBindings {
+    /// The Kotlin bindings
+    pub kotlin: String,
+    /// The C header of the scaffolding functions, which the shim includes
+    pub header: String,
+    /// The C shim that implements the `external` functions
+    pub shim: String,
+}
+
+/// Generate the JNI bindings for a component
+pub fn generate_jni_bindings(config: &Config, ci: &ComponentInterface) -> Result<JniBindings> {
+    if config.multiplatform() {
+        bail!("Kotlin multiplatform bindings can't use the `jni` FFI backend");
+    }
+    check_portable_support(config, ci, "Kotlin JNI bindings")?;
+    Ok(JniBindings {
+        kotlin: JniWrapper::new(config, ci)
+            .render()
+            .context("failed to render kotlin JNI bindings")?,
+        header: JniHeader { ci }
+            .render()
+            .context("failed to render JNI header")?,
+        shim: JniShim { config, ci }
+            .render()
+            .context("failed to render JNI shim")?,
+    })
+}
+
+// The scaffolding functions that the Kotlin code calls.  The `RustBuffer` functions are only
+// called by the shim.
+fn jni_functions(ci: &ComponentInterface) -> Vec<FfiFunction> {
+    let rust_buffer_fns: HashSet<String> = ci
+        .iter_rust_buffer_ffi_function_definitions()
+        .map(|f| f.name().to_string())
+        .collect();
+    ci.iter_ffi_function_definitions()
+        .filter(|f| !rust_buffer_fns.contains(f.name()))
+        .collect()
+}
+
+// Escape a Java name for the name of a JNI function, see the "Resolving Native Method Names"
+// section of the JNI specification.
+fn jni_mangle(name: &str) -> String {
+    let mut mangled = String::new();
+    for c in name.chars() {
+        match c {
+            '.' | '/' => mangled.push('_'),
+            '_' => mangled.push_str("_1"),
+            ';' => mangled.push_str("_2"),
+            '[' => mangled.push_str("_3"),
+            c if c.is_ascii_alphanumeric() => mangled.push(c),
+            c => {
+                let mut units = [0; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    mangled.push_str(&format!("_0{unit:04x}"));
+                }
+            }
+        }
+    }
+    mangled
+}
+
+/// Renders the declarations and FfiConverters of the JNI bindings
+///
+/// Like `TypeRenderer`, this stores the imports added while rendering, so only call `render()`
+/// once.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "JniTypes.kt")]
+struct JniTypeRenderer<'a> {
+    kotlin_config: &'a Config,
+    ci: &'a ComponentInterface,
+    include_once_names: RefCell<HashSet<String>>,
+    imports: RefCell<BTreeSet<ImportRequirement>>,
+}
+
+impl<'a> JniTypeRenderer<'a> {
+    // The declaration templates are shared with `TypeRenderer`, see its methods for what these do.
+
+    fn multiplatform(&self) -> bool {
+        false
+    }
+
+    fn error_buf_type(&self) -> &str {
+        "ByteArray"
+    }
+
+    // `PortableTypes.kt` is shared with `NativeTypeRenderer`, see its methods for what these do.
+
+    fn jni(&self) -> bool {
+        true
+    }
+
+    fn rust_buffer_type(&self) -> &str {
+        "ByteArray"
+    }
+
+    fn pointer_type(&self) -> &str {
+        "Long"
+    }
+
+    fn parcelize(&self) -> bool {
+        self.kotlin_config.parcelize()
+    }
+
+    fn serializable(&self) -> bool {
+        self.kotlin_config.serializable()
+    }
+
+    fn is_pooled_record(&self, name: &str) -> bool {
+        self.kotlin_config.is_pooled_record(name)
+    }
+
+    fn record_pool_size(&self) -> usize {
+        self.kotlin_config.record_pool_size()
+    }
+
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.kotlin_config.is_zero_copy_bytes(type_)
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.kotlin_config.field_naming())
+    }
+
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+
+    fn include_once_check(&self, name: &str) -> bool {
+        self.include_once_names
+            .borrow_mut()
+            .insert(name.to_string())
+    }
+
+    fn add_import(&self, name: &str) -> &str {
+        self.imports.borrow_mut().insert(ImportRequirement::Import {
+            name: name.to_owned(),
+        });
+        ""
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "JniWrapper.kt")]
+struct JniWrapper<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+    type_helper_code: String,
+    type_imports: BTreeSet<ImportRequirement>,
+}
+
+impl<'a> JniWrapper<'a> {
+    fn new(config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        let type_renderer = JniTypeRenderer {
+            kotlin_config: config,
+            ci,
+            include_once_names: RefCell::new(HashSet::new()),
+            imports: RefCell::new(BTreeSet::new()),
+        };
+        let type_helper_code = type_renderer.render().unwrap();
+        let type_imports = type_renderer.imports.into_inner();
+        Self {
+            config,
+            ci,
+            type_helper_code,
+            type_imports,
+        }
+    }
+
+    fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+
+    fn jni_functions(&self) -> Vec<FfiFunction> {
+        jni_functions(self.ci)
+    }
+
+    // Used by `PortableMacros.kt`
+    fn jni(&self) -> bool {
+        true
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+}
+
+// The shim declares the scaffolding functions with the same header as the Kotlin/Native bindings
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "NativeHeader.h")]
+struct JniHeader<'a> {
+    ci: &'a ComponentInterface,
+}
+
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "JniShim.c")]
+struct JniShim<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> JniShim<'a> {
+    fn jni_functions(&self) -> Vec<FfiFunction> {
+        jni_functions(self.ci)
+    }
+
+    // The name that the JVM looks up for the `external` function of a scaffolding function
+    fn jni_function_name(&self, ffi_name: &str) -> String {
+        format!(
+            "Java_{}_UniffiLib_{}",
+            jni_mangle(&self.config.package_name()),
+            jni_mangle(ffi_name)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::FfiBackend;
+    use super::*;
+
+    fn jni_config() -> Config {
+        Config {
+            package_name: Some("uniffi.my_test".to_string()),
+            ffi_backend: Some(FfiBackend::Jni),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn mangled_names() {
+        assert_eq!(jni_mangle("uniffi.my_test"), "uniffi_my_1test");
+        assert_eq!(jni_mangle("a;b[c"), "a_2b_3c");
+        assert_eq!(jni_mangle("é"), "_000e9");
+    }
+
+    #[test]
+    fn external_functions_and_shim() {
+        const UDL: &str = r#"
+            namespace my_test {
+                u32 add(u32 a, u32 b);
+                Point origin();
+            };
+
+            dictionary Point {
+                i32 x;
+                i32 y;
+                string? label;
+            };
+
+            enum Direction { "North", "South" };
+
+            interface Counter {
+                constructor();
+                void increment(Direction direction);
+                sequence<Point> history();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_jni_bindings(&jni_config(), &ci).unwrap();
+
+        assert!(bindings.kotlin.contains("fun `add`("));
+        assert!(bindings.kotlin.contains("class Counter"));
+        assert!(!bindings.kotlin.contains("com.sun.jna"));
+        for func in jni_functions(&ci) {
+            assert!(
+                bindings
+                    .kotlin
+                    .contains(&format!("external fun {}(", func.name())),
+                "{} is missing from the Kotlin code",
+                func.name()
+            );
+            assert!(
+                bindings.header.contains(func.name()),
+                "{} is missing from the header",
+                func.name()
+            );
+            let jni_name = format!("Java_uniffi_my_1test_UniffiLib_{}", jni_mangle(func.name()));
+            assert!(
+                bindings.shim.contains(&jni_name),
+                "{jni_name} is missing from the shim"
+            );
+        }
+    }
+
+    #[test]
+    fn multiplatform_is_rejected() {
+        let config = Config {
+            multiplatform: Some(true),
+            ..jni_config()
+        };
+        let ci = ComponentInterface::from_webidl("namespace my_test {};").unwrap();
+        assert!(generate_jni_bindings(&config, &ci).is_err());
+    }
+}
@@ -31,3 +31,25 @@ macro_rules! impl_code_type_for_miscellany {
 impl_code_type_for_miscellany!(TimestampCodeType, "java.time.Instant", "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "java.time.Duration", "Duration");
+
+impl_code_type_for_miscellany!(UrlCodeType, "java.net.URI", "Url");
+
+impl_code_type_for_miscellany!(UuidCodeType, "java.util.UUID", "Uuid");
+
+impl_code_type_for_miscellany!(DecimalCodeType, "java.math.BigDecimal", "Decimal");
+
+impl_code_type_for_miscellany!(
+    JsonCodeType,
+    "kotlinx.serialization.json.JsonElement",
+    "Json"
+);
+
+impl_code_type_for_miscellany!(PathCodeType, "java.io.File", "Path");
+
+impl_code_type_for_miscellany!(IpAddrCodeType, "java.net.InetAddress", "IpAddr");
+
+impl_code_type_for_miscellany!(
+    SocketAddrCodeType,
+    "java.net.InetSocketAddress",
+    "SocketAddr"
+);
@@ -11,7 +11,8 @@ use askama::Template;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 
-use crate::backend::{CodeType, TemplateExpression};
+use crate::backend::{CodeHooks, CodeType, FieldNaming, TemplateExpression};
+use crate::ide_index::{ItemKind, ItemRef};
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -22,10 +23,15 @@ mod enum_;
 mod error;
 mod executor;
 mod external;
+mod flags;
+mod jni;
 mod miscellany;
+mod multiplatform;
 mod object;
 mod primitives;
 mod record;
+mod split;
+mod sub_namespace;
 mod variant;
 
 // config options to customize the generated Kotlin.
@@ -37,6 +43,33 @@ pub struct Config {
     custom_types: HashMap<String, CustomTypeConfig>,
     #[serde(default)]
     external_packages: HashMap<String, String>,
+    usage_hooks: Option<bool>,
+    callback_conformance_checks: Option<bool>,
+    #[serde(default)]
+    pooled_records: Vec<String>,
+    record_pool_size: Option<usize>,
+    zero_copy_bytes: Option<bool>,
+    parcelize: Option<bool>,
+    serializable: Option<bool>,
+    multiplatform: Option<bool>,
+    ffi_backend: Option<FfiBackend>,
+    field_naming: Option<FieldNaming>,
+    open_classes: Option<bool>,
+    split_files: Option<bool>,
+    hot_reload: Option<bool>,
+    #[serde(flatten)]
+    code_hooks: CodeHooks,
+}
+
+/// How the bindings call the scaffolding functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FfiBackend {
+    /// Through JNA, which needs no native code besides the Rust library
+    #[serde(rename = "jna")]
+    Jna,
+    /// Through JNI `external` functions, implemented by a generated C shim
+    #[serde(rename = "jni")]
+    Jni,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -63,6 +96,84 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// Whether to generate a hook that's called with the name of each API as it's used.
+    pub fn usage_hooks(&self) -> bool {
+        self.usage_hooks.unwrap_or(false)
+    }
+
+    /// Whether to generate conformance checks for the foreign implementations of callback interfaces.
+    pub fn callback_conformance_checks(&self) -> bool {
+        self.callback_conformance_checks.unwrap_or(false)
+    }
+
+    /// Whether instances of the record `name` can be recycled, to be reused when lifting values.
+    pub fn is_pooled_record(&self, name: &str) -> bool {
+        self.pooled_records.iter().any(|n| n == name)
+    }
+
+    /// The maximum number of recycled instances kept for each pooled record.
+    pub fn record_pool_size(&self) -> usize {
+        self.record_pool_size.unwrap_or(64)
+    }
+
+    /// Whether `bytes` and `Vec<u8>` returned by functions and methods are read in place, without
+    /// copying them.
+    pub fn zero_copy_bytes(&self) -> bool {
+        self.zero_copy_bytes.unwrap_or(false)
+    }
+
+    /// Whether values of `type_` returned by functions and methods are read in place.
+    pub fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.zero_copy_bytes() && crate::backend::is_byte_buffer(type_)
+    }
+
+    /// Whether records and enums implement Android's `Parcelable`, with `@Parcelize`.
+    pub fn parcelize(&self) -> bool {
+        self.parcelize.unwrap_or(false)
+    }
+
+    /// Whether records and enums are annotated with kotlinx.serialization's `@Serializable`.
+    pub fn serializable(&self) -> bool {
+        self.serializable.unwrap_or(false)
+    }
+
+    /// Whether to generate Kotlin Multiplatform bindings, with `expect` declarations in the common
+    /// code and `actual` implementations for the JVM and Kotlin/Native.
+    pub fn multiplatform(&self) -> bool {
+        self.multiplatform.unwrap_or(false)
+    }
+
+    /// How the bindings call the scaffolding functions, through JNA by default.
+    pub fn ffi_backend(&self) -> FfiBackend {
+        self.ffi_backend.unwrap_or(FfiBackend::Jna)
+    }
+
+    /// How record fields and function arguments are cased, camelCase by default.
+    pub fn field_naming(&self) -> FieldNaming {
+        self.field_naming.unwrap_or(FieldNaming::CamelCase)
+    }
+
+    /// Whether object classes are `open`, so they can be subclassed by test doubles.
+    pub fn open_classes(&self) -> bool {
+        self.open_classes.unwrap_or(false)
+    }
+
+    /// Whether to write each type to its own file, with the code they share in a runtime file.
+    pub fn split_files(&self) -> bool {
+        self.split_files.unwrap_or(false)
+    }
+
+    /// Whether the bindings can reload the library after it was rebuilt, with `uniffiReload()`.
+    /// This is meant for development, see `NamespaceLibraryTemplate.kt`.
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload.unwrap_or(false)
+    }
+
+    /// Code from `uniffi.toml` to insert into the generated bindings
+    pub fn code_hooks(&self) -> &CodeHooks {
+        &self.code_hooks
+    }
 }
 
 impl BindingsConfig for Config {
@@ -90,6 +201,11 @@ impl BindingsConfig for Config {
     }
 }
 
+pub use jni::{generate_jni_bindings, JniBindings};
+pub use multiplatform::{generate_multiplatform_bindings, MultiplatformBindings};
+pub use split::generate_split_bindings;
+pub use sub_namespace::generate_sub_namespace_bindings;
+
 // Generate kotlin bindings for the given ComponentInterface, as a string.
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     KotlinWrapper::new(config.clone(), ci)
@@ -97,6 +213,29 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Str
         .context("failed to render kotlin bindings")
 }
 
+/// Get the fully-qualified name of the Kotlin symbol generated for an item, for the IDE index
+pub(crate) fn ide_symbol(config: &Config, item: &ItemRef<'_>) -> String {
+    let oracle = KotlinCodeOracle;
+    let package_name = config.package_name();
+    let fn_name = |nm: &str| oracle.fn_name(nm).trim_matches('`').to_string();
+    match (item.kind, item.parent) {
+        (ItemKind::Error, _) => format!("{package_name}.{}", oracle.error_name(item.name)),
+        (ItemKind::Constructor, Some(parent)) if item.name == "new" => {
+            format!("{package_name}.{}.<init>", oracle.class_name(parent))
+        }
+        (_, Some(parent)) => format!(
+            "{package_name}.{}.{}",
+            oracle.class_name(parent),
+            fn_name(item.name)
+        ),
+        (ItemKind::Function, None) => match item.sub_namespace {
+            Some(ns) => format!("{package_name}.{ns}.{}", fn_name(item.name)),
+            None => format!("{package_name}.{}", fn_name(item.name)),
+        },
+        (_, None) => format!("{package_name}.{}", oracle.class_name(item.name)),
+    }
+}
+
 /// A struct to record a Kotlin import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -127,19 +266,37 @@ impl ImportRequirement {
 pub struct TypeRenderer<'a> {
     kotlin_config: &'a Config,
     ci: &'a ComponentInterface,
+    // The types to render, which is only some of them for the files of split bindings
+    types: Vec<Type>,
+    // Which file of split bindings this renders, `None` when the bindings are a single file
+    split_file: Option<split::SplitFile>,
     // Track included modules for the `include_once()` macro
     include_once_names: RefCell<HashSet<String>>,
     // Track imports added with the `add_import()` macro
     imports: RefCell<BTreeSet<ImportRequirement>>,
+    // Track serializers added with the `add_file_serializer()` macro
+    file_serializers: RefCell<BTreeSet<String>>,
 }
 
 impl<'a> TypeRenderer<'a> {
     fn new(kotlin_config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        Self::for_types(kotlin_config, ci, ci.iter_types().cloned().collect(), None)
+    }
+
+    fn for_types(
+        kotlin_config: &'a Config,
+        ci: &'a ComponentInterface,
+        types: Vec<Type>,
+        split_file: Option<split::SplitFile>,
+    ) -> Self {
         Self {
             kotlin_config,
             ci,
+            types,
+            split_file,
             include_once_names: RefCell::new(HashSet::new()),
             imports: RefCell::new(BTreeSet::new()),
+            file_serializers: RefCell::new(BTreeSet::new()),
         }
     }
 
@@ -154,14 +311,108 @@ impl<'a> TypeRenderer<'a> {
 
     // The following methods are used by the `Types.kt` macros.
 
+    // Whether to record usage of the generated API, see `macros.kt`
+    fn usage_hooks(&self) -> bool {
+        self.kotlin_config.usage_hooks()
+    }
+
+    // Whether to generate `{Name}Conformance` classes, see `CallbackConformance.kt`
+    fn callback_conformance_checks(&self) -> bool {
+        self.kotlin_config.callback_conformance_checks()
+    }
+
+    // Whether to generate a pool of recycled instances for a record, see `RecordTemplate.kt`
+    fn is_pooled_record(&self, name: &str) -> bool {
+        self.kotlin_config.is_pooled_record(name)
+    }
+
+    fn record_pool_size(&self) -> usize {
+        self.kotlin_config.record_pool_size()
+    }
+
+    // Whether `bytes` returned by functions and methods are wrapped in a `RustByteBuffer`, see
+    // `ZeroCopyBytes.kt`
+    fn zero_copy_bytes(&self) -> bool {
+        self.kotlin_config.zero_copy_bytes()
+    }
+
+    // Code from `uniffi.toml` to insert after the definitions of types
+    fn code_hooks(&self) -> &CodeHooks {
+        self.kotlin_config.code_hooks()
+    }
+
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.kotlin_config.is_zero_copy_bytes(type_)
+    }
+
+    // Whether records and enums are annotated with `@Parcelize`, see `RecordTemplate.kt`
+    fn parcelize(&self) -> bool {
+        self.kotlin_config.parcelize()
+    }
+
+    // Whether records and enums are annotated with `@Serializable`, see `SerializationRuntime.kt`
+    fn serializable(&self) -> bool {
+        self.kotlin_config.serializable()
+    }
+
+    // Whether these are the JVM bindings of multiplatform bindings, see `macros.kt`
+    fn multiplatform(&self) -> bool {
+        self.kotlin_config.multiplatform()
+    }
+
+    // The type of the serialized error that `CallStatusErrorHandler`s lift, see `ErrorDeclaration.kt`
+    fn error_buf_type(&self) -> &str {
+        "RustBuffer.ByValue"
+    }
+
+    // The Kotlin rendering of a record field or function argument, see `Config::field_naming()`
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.kotlin_config.field_naming())
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+
+    // Whether object classes are `open`, see `ObjectTemplate.kt`
+    fn open_classes(&self) -> bool {
+        self.kotlin_config.open_classes()
+    }
+
+    // Whether objects check that they're from the current library, see `ObjectRuntime.kt`
+    fn hot_reload(&self) -> bool {
+        self.kotlin_config.hot_reload()
+    }
+
+    // Whether this renders the code that isn't specific to a type, which the type files of split
+    // bindings leave to the runtime file.
+    fn renders_runtime(&self) -> bool {
+        !matches!(self.split_file, Some(split::SplitFile::Type))
+    }
+
+    // Whether to include the runtime template `name` at the top of the runtime file of split
+    // bindings, because one of the type files needed it.
+    fn split_runtime_include(&self, name: &str) -> bool {
+        match &self.split_file {
+            Some(split::SplitFile::Runtime { includes }) => {
+                includes.contains(name) && self.include_once_check(name)
+            }
+            _ => false,
+        }
+    }
+
     // Helper for the including a template, but only once.
     //
     // The first time this is called with a name it will return true, indicating that we should
-    // include the template.  Subsequent calls will return false.
+    // include the template.  Subsequent calls will return false.  The type files of split
+    // bindings record the name but never include the template, see `split_runtime_include()`.
     fn include_once_check(&self, name: &str) -> bool {
-        self.include_once_names
+        let first = self
+            .include_once_names
             .borrow_mut()
-            .insert(name.to_string())
+            .insert(name.to_string());
+        first && self.renders_runtime()
     }
 
     // Helper to add an import statement
@@ -187,6 +438,15 @@ impl<'a> TypeRenderer<'a> {
             });
         ""
     }
+
+    // Helper to add a serializer to the file's `@file:UseSerializers` annotation
+    //
+    // Call this inside your template when a builtin type needs a custom serializer.  Returns an
+    // empty string so that it can be used inside an askama `{{ }}` block.
+    fn add_file_serializer(&self, name: &str) -> &str {
+        self.file_serializers.borrow_mut().insert(name.to_owned());
+        ""
+    }
 }
 
 #[derive(Template)]
@@ -196,6 +456,8 @@ pub struct KotlinWrapper<'a> {
     ci: &'a ComponentInterface,
     type_helper_code: String,
     type_imports: BTreeSet<ImportRequirement>,
+    file_serializers: BTreeSet<String>,
+    split_files: bool,
 }
 
 impl<'a> KotlinWrapper<'a> {
@@ -203,11 +465,14 @@ impl<'a> KotlinWrapper<'a> {
         let type_renderer = TypeRenderer::new(&config, ci);
         let type_helper_code = type_renderer.render().unwrap();
         let type_imports = type_renderer.imports.into_inner();
+        let file_serializers = type_renderer.file_serializers.into_inner();
         Self {
             config,
             ci,
             type_helper_code,
             type_imports,
+            file_serializers,
+            split_files: false,
         }
     }
 
@@ -222,6 +487,39 @@ impl<'a> KotlinWrapper<'a> {
     pub fn imports(&self) -> Vec<ImportRequirement> {
         self.type_imports.iter().cloned().collect()
     }
+
+    pub fn file_serializers(&self) -> Vec<String> {
+        self.file_serializers.iter().cloned().collect()
+    }
+
+    // Whether to record usage of the generated API, see `macros.kt`
+    fn usage_hooks(&self) -> bool {
+        self.config.usage_hooks()
+    }
+
+    // Whether `bytes` returned by functions and methods are wrapped in a `RustByteBuffer`
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.config.is_zero_copy_bytes(type_)
+    }
+
+    // Whether these are the JVM bindings of multiplatform bindings, see `macros.kt`
+    fn multiplatform(&self) -> bool {
+        self.config.multiplatform()
+    }
+
+    // Whether this is the runtime file of split bindings, see `macros.kt`
+    fn split_files(&self) -> bool {
+        self.split_files
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
 }
 
 #[derive(Clone)]
@@ -255,6 +553,11 @@ impl KotlinCodeOracle {
         format!("`{}`", nm.to_string().to_lower_camel_case())
     }
 
+    /// Get the Kotlin rendering of a record field or function argument.
+    fn field_name(&self, nm: &str, naming: FieldNaming) -> String {
+        format!("`{}`", naming.apply(nm))
+    }
+
     /// Get the idiomatic Kotlin rendering of an individual enum variant.
     fn enum_variant_name(&self, nm: &str) -> String {
         nm.to_string().to_shouty_snake_case()
@@ -276,7 +579,9 @@ impl KotlinCodeOracle {
 
     fn ffi_type_label_by_value(ffi_type: &FfiType) -> String {
         match ffi_type {
-            FfiType::RustBuffer(_) => format!("{}.ByValue", Self::ffi_type_label(ffi_type)),
+            FfiType::RustBuffer(_) | FfiType::Struct(_) => {
+                format!("{}.ByValue", Self::ffi_type_label(ffi_type))
+            }
             _ => Self::ffi_type_label(ffi_type),
         }
     }
@@ -304,6 +609,7 @@ impl KotlinCodeOracle {
                 format!("UniFfiFutureCallback{}", Self::ffi_type_label(return_type))
             }
             FfiType::FutureCallbackData => "USize".to_string(),
+            FfiType::Struct(name) => format!("UniffiReprC{name}"),
         }
     }
 }
@@ -333,13 +639,22 @@ impl<T: AsType> AsCodeType for T {
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::IpAddr => Box::new(miscellany::IpAddrCodeType),
+            Type::SocketAddr => Box::new(miscellany::SocketAddrCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
+            Type::Flags { name, .. } => Box::new(flags::FlagsCodeType::new(name)),
             Type::Object { name, .. } => Box::new(object::ObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
@@ -356,6 +671,11 @@ impl<T: AsType> AsCodeType for T {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Compressed { inner_type } => {
+                Box::new(compounds::CompressedCodeType::new(*inner_type))
+            }
+            Type::Shared { inner_type } => Box::new(compounds::SharedCodeType::new(*inner_type)),
+            Type::Weak { inner_type } => Box::new(compounds::WeakCodeType::new(*inner_type)),
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -454,6 +774,79 @@ pub mod filters {
         Ok(KotlinCodeOracle::ffi_type_label_by_value(type_))
     }
 
+    /// Get the C type of a low-level `FfiType`, for the header used by cinterop.
+    pub fn header_ffi_type_name(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FfiType::Int8 => "int8_t".into(),
+            FfiType::UInt8 => "uint8_t".into(),
+            FfiType::Int16 => "int16_t".into(),
+            FfiType::UInt16 => "uint16_t".into(),
+            FfiType::Int32 => "int32_t".into(),
+            FfiType::UInt32 => "uint32_t".into(),
+            FfiType::Int64 => "int64_t".into(),
+            FfiType::UInt64 => "uint64_t".into(),
+            FfiType::Float32 => "float".into(),
+            FfiType::Float64 => "double".into(),
+            FfiType::RustArcPtr(_) => "void*".into(),
+            FfiType::RustBuffer(_) => "RustBuffer".into(),
+            FfiType::ForeignBytes => "ForeignBytes".into(),
+            // Rejected by `check_portable_support()`
+            _ => unreachable!("unsupported type in multiplatform bindings: {type_:?}"),
+        })
+    }
+
+    /// Get the Kotlin type of a low-level `FfiType` in the `external` functions of the JNI
+    /// bindings.  Unsigned values are passed as their signed counterparts, and the C shim copies
+    /// `RustBuffer`s to and from byte arrays.
+    pub fn jni_ffi_type_name(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FfiType::Int8 | FfiType::UInt8 => "Byte".into(),
+            FfiType::Int16 | FfiType::UInt16 => "Short".into(),
+            FfiType::Int32 | FfiType::UInt32 => "Int".into(),
+            FfiType::Int64 | FfiType::UInt64 | FfiType::RustArcPtr(_) => "Long".into(),
+            FfiType::Float32 => "Float".into(),
+            FfiType::Float64 => "Double".into(),
+            FfiType::RustBuffer(_) => "ByteArray".into(),
+            // Rejected by `check_portable_support()`
+            _ => unreachable!("unsupported type in JNI bindings: {type_:?}"),
+        })
+    }
+
+    /// Get the JNI type of a low-level `FfiType`, for the C shim of the JNI bindings.
+    pub fn jni_c_type_name(type_: &FfiType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FfiType::Int8 | FfiType::UInt8 => "jbyte".into(),
+            FfiType::Int16 | FfiType::UInt16 => "jshort".into(),
+            FfiType::Int32 | FfiType::UInt32 => "jint".into(),
+            FfiType::Int64 | FfiType::UInt64 | FfiType::RustArcPtr(_) => "jlong".into(),
+            FfiType::Float32 => "jfloat".into(),
+            FfiType::Float64 => "jdouble".into(),
+            FfiType::RustBuffer(_) => "jbyteArray".into(),
+            // Rejected by `check_portable_support()`
+            _ => unreachable!("unsupported type in JNI bindings: {type_:?}"),
+        })
+    }
+
+    /// Convert the JNI argument `name` to the type that the scaffolding function takes, in the C
+    /// shim of the JNI bindings.
+    pub fn jni_c_arg(type_: &FfiType, name: &str) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FfiType::RustArcPtr(_) => format!("(void*)(intptr_t){name}"),
+            FfiType::RustBuffer(_) => format!("uniffi_jni_rust_buffer_from(env, {name})"),
+            _ => format!("({}){name}", header_ffi_type_name(type_)?),
+        })
+    }
+
+    /// Convert the `value` that a scaffolding function returned to its JNI type, in the C shim of
+    /// the JNI bindings.
+    pub fn jni_c_return(type_: &FfiType, value: &str) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            FfiType::RustArcPtr(_) => format!("(jlong)(intptr_t){value}"),
+            FfiType::RustBuffer(_) => format!("uniffi_jni_consume_rust_buffer(env, {value})"),
+            _ => format!("({}){value}", jni_c_type_name(type_)?),
+        })
+    }
+
     // Some FfiTypes have the same ffi_type_label - this makes a vec of them unique.
     pub fn unique_ffi_types(
         types: impl Iterator<Item = FfiType>,
@@ -482,6 +875,11 @@ pub mod filters {
         Ok(KotlinCodeOracle.enum_variant_name(v.name()))
     }
 
+    /// Get the idiomatic Kotlin rendering of a named flag, which is a constant.
+    pub fn flag_name(f: &Flag) -> Result<String, askama::Error> {
+        Ok(KotlinCodeOracle.enum_variant_name(f.name()))
+    }
+
     /// Get a codetype for idiomatic Kotlin rendering of an individual enum variant.
     pub fn enum_variant(v: &Variant) -> Result<impl AsCodeType, askama::Error> {
         Ok(v.clone())
@@ -518,7 +916,21 @@ pub mod filters {
     /// These are used to avoid name clashes with kotlin identifiers, but sometimes you want to
     /// render the name unquoted.  One example is the message property for errors where we want to
     /// display the name for the user.
-    pub fn unquote(nm: &str) -> Result<String, askama::Error> {
-        Ok(nm.trim_matches('`').to_string())
+    pub fn unquote(nm: impl AsRef<str>) -> Result<String, askama::Error> {
+        Ok(nm.as_ref().trim_matches('`').to_string())
+    }
+
+    /// Render a docstring as a KDoc comment, indented by `spaces`
+    pub fn docstring(docstring: &str, spaces: i32) -> Result<String, askama::Error> {
+        let indent = " ".repeat(spaces as usize);
+        let body = docstring
+            .replace("*/", "*\\/")
+            .lines()
+            .map(|line| match line {
+                "" => format!("{indent} *\n"),
+                line => format!("{indent} * {line}\n"),
+            })
+            .collect::<String>();
+        Ok(format!("{indent}/**\n{body}{indent} */"))
     }
 }
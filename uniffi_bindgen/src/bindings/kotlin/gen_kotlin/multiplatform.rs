@@ -0,0 +1,408 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Kotlin Multiplatform bindings
+//!
+//! The public API is declared in the common code, with `expect` declarations for the functions
+//! and objects.  The JVM `actual`s are the regular JNA bindings, rendered with
+//! `Config::multiplatform()` set, and the Kotlin/Native `actual`s call the scaffolding functions
+//! through cinterop, using a C header that's generated alongside them.
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+
+use super::{filters, Config, ImportRequirement, KotlinCodeOracle, KotlinWrapper};
+use crate::interface::*;
+
+/// The files of the multiplatform bindings for a component
+pub struct MultiplatformBindings {
+    /// The `expect` declarations, for the `commonMain` source set
+    pub common: String,
+    /// The JNA implementation, for the `jvmMain` source set
+    pub jvm: String,
+    /// The cinterop implementation, for the `nativeMain` source set
+    pub native: String,
+    /// The C header that the cinterop bindings are generated from
+    pub native_header: String,
+    /// The cinterop definition file
+    pub cinterop_def: String,
+}
+
+/// Generate the Kotlin Multiplatform bindings for a component
+pub fn generate_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<MultiplatformBindings> {
+    check_portable_support(config, ci, "Kotlin multiplatform bindings")?;
+    Ok(MultiplatformBindings {
+        common: CommonWrapper::new(config, ci)
+            .render()
+            .context("failed to render common kotlin bindings")?,
+        jvm: KotlinWrapper::new(config.clone(), ci)
+            .render()
+            .context("failed to render jvm kotlin bindings")?,
+        native: NativeWrapper::new(config, ci)
+            .render()
+            .context("failed to render native kotlin bindings")?,
+        native_header: NativeHeader { ci }
+            .render()
+            .context("failed to render native kotlin header")?,
+        cinterop_def: format!(
+            "headers = {namespace}.h\nheaderFilter = {namespace}.h\npackage = {package}.cinterop\n",
+            namespace = ci.namespace(),
+            package = config.package_name(),
+        ),
+    })
+}
+
+// The Kotlin/Native and JNI bindings only support the types and features that can be implemented
+// with the plain Kotlin serialization code that they share, without JNA or the JVM classes of the
+// builtin types, so anything else is rejected here rather than generating bindings that don't
+// compile.  `bindings` names the bindings in the error.
+pub(super) fn check_portable_support(
+    config: &Config,
+    ci: &ComponentInterface,
+    bindings: &str,
+) -> Result<()> {
+    let mut unsupported = vec![];
+    if ci.has_async_fns() {
+        unsupported.push("async functions".to_string());
+    }
+    for type_ in ci.iter_types() {
+        match type_ {
+            Type::Char
+            | Type::Timestamp
+            | Type::Duration
+            | Type::Url
+            | Type::Uuid
+            | Type::Decimal
+            | Type::Json
+            | Type::Path
+            | Type::IpAddr
+            | Type::SocketAddr
+            | Type::ForeignExecutor => unsupported.push(format!("the {type_:?} type")),
+            Type::Compressed { .. } => unsupported.push("compressed values".to_string()),
+            Type::Shared { .. } => unsupported.push("shared values".to_string()),
+            Type::Weak { .. } => unsupported.push("`Weak` references".to_string()),
+            Type::CallbackInterface { name, .. } => {
+                unsupported.push(format!("callback interface `{name}`"))
+            }
+            Type::Custom { name, .. } => unsupported.push(format!("custom type `{name}`")),
+            Type::External { name, .. } => unsupported.push(format!("external type `{name}`")),
+            Type::Record { name, .. }
+                if ci.get_record_definition(name).map(|r| r.is_repr_c()) == Some(true) =>
+            {
+                unsupported.push(format!("`#[repr(C)]` record `{name}`"));
+            }
+            _ => {}
+        }
+    }
+    let borrows_bytes = ci
+        .iter_callables()
+        .flat_map(|c| c.arguments())
+        .any(|a| a.borrows_bytes());
+    if borrows_bytes {
+        unsupported.push("`[ByRef] bytes` arguments".to_string());
+    }
+    for ns in ci.sub_namespaces() {
+        unsupported.push(format!("sub-namespace `{ns}`"));
+    }
+    for rec in ci.record_definitions() {
+        if rec.is_ord() {
+            unsupported.push(format!("`Ord` on record `{}`", rec.name()));
+        }
+    }
+    for obj in ci.object_definitions() {
+        if obj.has_consuming_methods() {
+            unsupported.push(format!("methods that consume object `{}`", obj.name()));
+        }
+    }
+    for (option, enabled) in [
+        ("usage_hooks", config.usage_hooks()),
+        ("pooled_records", !config.pooled_records.is_empty()),
+        ("zero_copy_bytes", config.zero_copy_bytes()),
+        ("parcelize", config.parcelize()),
+        ("serializable", config.serializable()),
+        ("open_classes", config.open_classes()),
+        ("split_files", config.split_files()),
+        ("hot_reload", config.hot_reload()),
+        ("preamble", config.code_hooks().preamble().is_some()),
+        (
+            "after_imports",
+            config.code_hooks().after_imports().is_some(),
+        ),
+        ("footer", config.code_hooks().footer().is_some()),
+        ("type_extensions", config.code_hooks().has_type_extensions()),
+        ("type_overrides", config.code_hooks().has_type_overrides()),
+    ] {
+        if enabled {
+            unsupported.push(format!("the `{option}` option"));
+        }
+    }
+    if !unsupported.is_empty() {
+        bail!("{bindings} don't support {}", unsupported.join(", "));
+    }
+    Ok(())
+}
+
+/// Renders the declarations of the common code
+///
+/// Like `TypeRenderer`, this stores the imports added while rendering, so only call `render()`
+/// once.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "CommonTypes.kt")]
+struct CommonTypeRenderer<'a> {
+    kotlin_config: &'a Config,
+    ci: &'a ComponentInterface,
+    include_once_names: RefCell<HashSet<String>>,
+    imports: RefCell<BTreeSet<ImportRequirement>>,
+}
+
+impl<'a> CommonTypeRenderer<'a> {
+    // The declaration templates are shared with `TypeRenderer`, see its methods for what these do.
+
+    fn multiplatform(&self) -> bool {
+        true
+    }
+
+    fn parcelize(&self) -> bool {
+        self.kotlin_config.parcelize()
+    }
+
+    fn serializable(&self) -> bool {
+        self.kotlin_config.serializable()
+    }
+
+    fn is_pooled_record(&self, name: &str) -> bool {
+        self.kotlin_config.is_pooled_record(name)
+    }
+
+    fn record_pool_size(&self) -> usize {
+        self.kotlin_config.record_pool_size()
+    }
+
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.kotlin_config.is_zero_copy_bytes(type_)
+    }
+
+    fn error_buf_type(&self) -> &str {
+        "RustBuffer.ByValue"
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.kotlin_config.field_naming())
+    }
+
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+
+    fn include_once_check(&self, name: &str) -> bool {
+        self.include_once_names
+            .borrow_mut()
+            .insert(name.to_string())
+    }
+
+    fn add_import(&self, name: &str) -> &str {
+        self.imports.borrow_mut().insert(ImportRequirement::Import {
+            name: name.to_owned(),
+        });
+        ""
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "CommonWrapper.kt")]
+struct CommonWrapper<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+    type_declarations: String,
+    type_imports: BTreeSet<ImportRequirement>,
+}
+
+impl<'a> CommonWrapper<'a> {
+    fn new(config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        let type_renderer = CommonTypeRenderer {
+            kotlin_config: config,
+            ci,
+            include_once_names: RefCell::new(HashSet::new()),
+            imports: RefCell::new(BTreeSet::new()),
+        };
+        let type_declarations = type_renderer.render().unwrap();
+        let type_imports = type_renderer.imports.into_inner();
+        Self {
+            config,
+            ci,
+            type_declarations,
+            type_imports,
+        }
+    }
+
+    fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+}
+
+/// Renders the FfiConverters and object implementations of the Kotlin/Native bindings
+///
+/// The JNI bindings render the same template, with `JniTypeRenderer`.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "PortableTypes.kt")]
+struct NativeTypeRenderer<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> NativeTypeRenderer<'a> {
+    // Whether the template is rendered for the JNI bindings
+    fn jni(&self) -> bool {
+        false
+    }
+
+    // The FFI type of `RustBuffer`s
+    fn rust_buffer_type(&self) -> &str {
+        "CValue<RustBuffer>"
+    }
+
+    // The FFI type of object pointers
+    fn pointer_type(&self) -> &str {
+        "COpaquePointer?"
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "NativeWrapper.kt")]
+struct NativeWrapper<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+    type_helper_code: String,
+}
+
+impl<'a> NativeWrapper<'a> {
+    fn new(config: &'a Config, ci: &'a ComponentInterface) -> Self {
+        let type_helper_code = NativeTypeRenderer { config, ci }.render().unwrap();
+        Self {
+            config,
+            ci,
+            type_helper_code,
+        }
+    }
+
+    // Used by `PortableMacros.kt`
+    fn jni(&self) -> bool {
+        false
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+}
+
+#[derive(Template)]
+#[template(syntax = "c", escape = "none", path = "NativeHeader.h")]
+struct NativeHeader<'a> {
+    ci: &'a ComponentInterface,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn multiplatform_config() -> Config {
+        Config {
+            package_name: Some("uniffi.test".to_string()),
+            multiplatform: Some(true),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn expect_and_actual_declarations() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 add(u32 a, u32 b);
+                Point origin();
+            };
+
+            dictionary Point {
+                i32 x;
+                i32 y;
+                string? label;
+            };
+
+            enum Direction { "North", "South" };
+
+            interface Counter {
+                constructor();
+                void increment(Direction direction);
+                sequence<Point> history();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let bindings = generate_multiplatform_bindings(&multiplatform_config(), &ci).unwrap();
+
+        assert!(bindings.common.contains("expect fun `add`("));
+        assert!(bindings.common.contains("data class Point ("));
+        assert!(bindings.common.contains("enum class Direction"));
+        assert!(!bindings.common.contains("com.sun.jna"));
+        assert!(bindings.jvm.contains("actual fun `add`("));
+        assert!(bindings.native.contains("actual fun `add`("));
+        assert!(bindings.native.contains("actual class Counter"));
+        assert!(bindings.native.contains("import uniffi.test.cinterop.*"));
+        for func in ci.iter_ffi_function_definitions() {
+            assert!(
+                bindings.native_header.contains(func.name()),
+                "{} is missing from the header",
+                func.name()
+            );
+        }
+        assert_eq!(
+            bindings.cinterop_def,
+            "headers = test.h\nheaderFilter = test.h\npackage = uniffi.test.cinterop\n"
+        );
+    }
+
+    #[test]
+    fn unsupported_features() {
+        const UDL: &str = r#"
+            namespace test {
+                void register(Listener listener);
+            };
+
+            callback interface Listener {
+                void on_event(timestamp when);
+            };
+
+            [Traits=(Ord)]
+            dictionary Version {
+                u32 major;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let err = generate_multiplatform_bindings(&multiplatform_config(), &ci)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.starts_with("Kotlin multiplatform bindings don't support"));
+        assert!(err.contains("the Timestamp type"));
+        assert!(err.contains("callback interface `Listener`"));
+        assert!(err.contains("`Ord` on record `Version`"));
+    }
+}
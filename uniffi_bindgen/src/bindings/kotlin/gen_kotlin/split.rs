@@ -0,0 +1,136 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Kotlin bindings split across files
+//!
+//! Each record, enum, error, flags, object, callback interface and custom type is written to its
+//! own file.  The rest goes in a runtime file named after the namespace: the FFI declarations, the
+//! helpers, the FfiConverters of the builtin and compound types, and the top-level functions.
+//!
+//! All the files are in the same package.  The helpers that are `private` in a single file are
+//! `internal` instead, see `macros.kt`, and the runtime code that the type templates include with
+//! `include_once_check()` goes in the runtime file rather than in the first type file that needs
+//! it.  Every file gets the imports and `@file:UseSerializers` serializers of all of them, since
+//! the type files use the helpers from the runtime file.
+
+use std::collections::{BTreeSet, HashSet};
+
+use anyhow::{Context, Result};
+use askama::Template;
+
+use super::{Config, ImportRequirement, KotlinCodeOracle, KotlinWrapper, TypeRenderer};
+use crate::interface::*;
+
+/// Which file of split bindings a `TypeRenderer` renders
+pub(super) enum SplitFile {
+    /// The file of a single type
+    Type,
+    /// The runtime file, with the runtime templates that the type files needed
+    Runtime { includes: HashSet<String> },
+}
+
+/// Generate the Kotlin bindings for a component as separate files
+///
+/// Returns the name and contents of each file, starting with the runtime file.
+pub fn generate_split_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(String, String)>> {
+    let mut includes = HashSet::new();
+    let mut imports = BTreeSet::new();
+    let mut file_serializers = BTreeSet::new();
+    let mut type_files = vec![];
+    let mut runtime_types = vec![];
+    for type_ in ci.iter_types() {
+        let file_name = match type_file_name(ci, type_) {
+            Some(file_name) => file_name,
+            None => {
+                runtime_types.push(type_.clone());
+                continue;
+            }
+        };
+        let renderer =
+            TypeRenderer::for_types(config, ci, vec![type_.clone()], Some(SplitFile::Type));
+        let type_code = renderer
+            .render()
+            .with_context(|| format!("failed to render kotlin bindings for {file_name}"))?;
+        includes.extend(renderer.include_once_names.into_inner());
+        imports.extend(renderer.imports.into_inner());
+        file_serializers.extend(renderer.file_serializers.into_inner());
+        type_files.push((file_name, type_code));
+    }
+
+    let renderer = TypeRenderer::for_types(
+        config,
+        ci,
+        runtime_types,
+        Some(SplitFile::Runtime { includes }),
+    );
+    let type_helper_code = renderer
+        .render()
+        .context("failed to render kotlin runtime bindings")?;
+    imports.extend(renderer.imports.into_inner());
+    file_serializers.extend(renderer.file_serializers.into_inner());
+
+    let runtime = KotlinWrapper {
+        config: config.clone(),
+        ci,
+        type_helper_code,
+        type_imports: imports.clone(),
+        file_serializers: file_serializers.clone(),
+        split_files: true,
+    }
+    .render()
+    .context("failed to render kotlin runtime bindings")?;
+    let mut files = vec![(format!("{}.kt", ci.namespace()), runtime)];
+    for (file_name, type_code) in type_files {
+        let contents = KotlinTypeFile {
+            config,
+            type_code,
+            type_imports: &imports,
+            file_serializers: &file_serializers,
+        }
+        .render()
+        .with_context(|| format!("failed to render kotlin bindings for {file_name}"))?;
+        files.push((file_name, contents));
+    }
+    Ok(files)
+}
+
+// The name of the file for a type, or `None` if the type goes in the runtime file
+fn type_file_name(ci: &ComponentInterface, type_: &Type) -> Option<String> {
+    let name = match type_ {
+        Type::Enum { name, .. } if ci.is_name_used_as_error(name) => {
+            KotlinCodeOracle.error_name(name)
+        }
+        Type::Enum { name, .. }
+        | Type::Record { name, .. }
+        | Type::Flags { name, .. }
+        | Type::Object { name, .. }
+        | Type::CallbackInterface { name, .. }
+        | Type::Custom { name, .. } => KotlinCodeOracle.class_name(name),
+        _ => return None,
+    };
+    Some(format!("{name}.kt"))
+}
+
+/// Wraps the code for a type with the package and imports of split bindings
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "TypeFileWrapper.kt")]
+struct KotlinTypeFile<'a> {
+    config: &'a Config,
+    type_code: String,
+    type_imports: &'a BTreeSet<ImportRequirement>,
+    file_serializers: &'a BTreeSet<String>,
+}
+
+impl<'a> KotlinTypeFile<'a> {
+    fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+
+    fn file_serializers(&self) -> Vec<String> {
+        self.file_serializers.iter().cloned().collect()
+    }
+}
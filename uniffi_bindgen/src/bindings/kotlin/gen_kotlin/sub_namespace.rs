@@ -0,0 +1,87 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Kotlin files of sub-namespaces
+//!
+//! The functions exported with `#[uniffi::export(namespace = "...")]` are generated in a package
+//! named after their sub-namespace, inside the package of the bindings, with a file per
+//! sub-namespace.  The types stay in the main package, and so do the helpers that the functions
+//! use, which are `internal` rather than `private` when there are sub-namespaces, see `macros.kt`.
+
+use std::{borrow::Borrow, collections::BTreeSet};
+
+use anyhow::{Context, Result};
+use askama::Template;
+
+use super::{filters, Config, ImportRequirement, KotlinCodeOracle, TypeRenderer};
+use crate::interface::*;
+
+/// Generate the Kotlin files of the sub-namespaces of a component
+///
+/// Returns the name and contents of the file of each sub-namespace.
+pub fn generate_sub_namespace_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(String, String)>> {
+    // The functions can use any of the types, so the files get the imports of all of them
+    let type_renderer = TypeRenderer::new(config, ci);
+    type_renderer
+        .render()
+        .context("failed to render kotlin bindings")?;
+    let type_imports = type_renderer.imports.into_inner();
+    ci.sub_namespaces()
+        .into_iter()
+        .map(|namespace| {
+            let contents = KotlinSubNamespace {
+                config,
+                ci,
+                namespace,
+                type_imports: &type_imports,
+            }
+            .render()
+            .with_context(|| {
+                format!("failed to render kotlin bindings for sub-namespace `{namespace}`")
+            })?;
+            Ok((namespace.to_string(), contents))
+        })
+        .collect()
+}
+
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "SubNamespaceWrapper.kt")]
+struct KotlinSubNamespace<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+    namespace: &'a str,
+    type_imports: &'a BTreeSet<ImportRequirement>,
+}
+
+impl<'a> KotlinSubNamespace<'a> {
+    fn imports(&self) -> Vec<ImportRequirement> {
+        self.type_imports.iter().cloned().collect()
+    }
+
+    // Whether to record usage of the generated API, see `macros.kt`
+    fn usage_hooks(&self) -> bool {
+        self.config.usage_hooks()
+    }
+
+    // Whether `bytes` returned by functions are wrapped in a `RustByteBuffer`
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.config.is_zero_copy_bytes(type_)
+    }
+
+    // Sub-namespaces aren't supported by multiplatform bindings, see `macros.kt`
+    fn multiplatform(&self) -> bool {
+        false
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        KotlinCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+}
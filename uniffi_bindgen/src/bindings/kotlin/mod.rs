@@ -8,11 +8,14 @@ use fs_err as fs;
 use std::process::Command;
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{generate_bindings, Config};
+pub use gen_kotlin::{
+    generate_bindings, generate_jni_bindings, generate_multiplatform_bindings,
+    generate_split_bindings, generate_sub_namespace_bindings, Config, FfiBackend,
+};
 mod test;
 
 use super::super::interface::ComponentInterface;
-pub use test::{run_script, run_test};
+pub use test::{run_gradle_test, run_script, run_test};
 
 pub fn write_bindings(
     config: &Config,
@@ -20,21 +23,140 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
+    if config.ffi_backend() == FfiBackend::Jni {
+        return write_jni_bindings(config, ci, out_dir, try_format_code);
+    }
+    if config.multiplatform() {
+        return write_multiplatform_bindings(config, ci, out_dir, try_format_code);
+    }
+    if config.split_files() {
+        write_split_bindings(config, ci, out_dir, try_format_code)?;
+    } else {
+        let mut kt_file = full_bindings_path(config, out_dir);
+        fs::create_dir_all(&kt_file)?;
+        kt_file.push(format!("{}.kt", ci.namespace()));
+        fs::write(&kt_file, generate_bindings(config, ci)?)?;
+        if try_format_code {
+            format_code(&kt_file);
+        }
+    }
+    write_sub_namespace_bindings(config, ci, out_dir, try_format_code)
+}
+
+// The package of a sub-namespace is in the package of the bindings, so its file is written to a
+// directory named after it, in the package directory.
+fn write_sub_namespace_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    for (namespace, contents) in generate_sub_namespace_bindings(config, ci)? {
+        let namespace_dir = full_bindings_path(config, out_dir).join(&namespace);
+        fs::create_dir_all(&namespace_dir)?;
+        let kt_file = namespace_dir.join(format!("{namespace}.kt"));
+        fs::write(&kt_file, contents)?;
+        if try_format_code {
+            format_code(&kt_file);
+        }
+    }
+    Ok(())
+}
+
+// Split bindings are written to the package directory like a single file, with a file per type
+// next to the `<namespace>.kt` runtime file.  Files of types that were since removed aren't
+// deleted, since the directory may have other sources.
+fn write_split_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let package_dir = full_bindings_path(config, out_dir);
+    fs::create_dir_all(&package_dir)?;
+    for (file_name, contents) in generate_split_bindings(config, ci)? {
+        let kt_file = package_dir.join(file_name);
+        fs::write(&kt_file, contents)?;
+        if try_format_code {
+            format_code(&kt_file);
+        }
+    }
+    Ok(())
+}
+
+// JNI bindings are written to the package directory like the JNA bindings, with the C shim and
+// the header that it includes in a `jni` directory next to it.
+fn write_jni_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let bindings = generate_jni_bindings(config, ci)?;
     let mut kt_file = full_bindings_path(config, out_dir);
     fs::create_dir_all(&kt_file)?;
     kt_file.push(format!("{}.kt", ci.namespace()));
-    fs::write(&kt_file, generate_bindings(config, ci)?)?;
+    fs::write(&kt_file, bindings.kotlin)?;
     if try_format_code {
-        if let Err(e) = Command::new("ktlint").arg("-F").arg(&kt_file).output() {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {e:?}",
-                kt_file.file_name().unwrap(),
-            );
+        format_code(&kt_file);
+    }
+    let jni_dir = out_dir.join("jni");
+    fs::create_dir_all(&jni_dir)?;
+    fs::write(
+        jni_dir.join(format!("{}.h", ci.namespace())),
+        bindings.header,
+    )?;
+    fs::write(
+        jni_dir.join(format!("{}_jni.c", ci.namespace())),
+        bindings.shim,
+    )?;
+    Ok(())
+}
+
+// Multiplatform bindings are written as the source sets of a Kotlin Multiplatform module, with the
+// cinterop definition in `nativeInterop/cinterop`, where Gradle looks for it by default.
+fn write_multiplatform_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let bindings = generate_multiplatform_bindings(config, ci)?;
+    for (source_set, contents) in [
+        ("commonMain", bindings.common),
+        ("jvmMain", bindings.jvm),
+        ("nativeMain", bindings.native),
+    ] {
+        let mut kt_file = full_bindings_path(config, &out_dir.join(source_set).join("kotlin"));
+        fs::create_dir_all(&kt_file)?;
+        kt_file.push(format!("{}.kt", ci.namespace()));
+        fs::write(&kt_file, contents)?;
+        if try_format_code {
+            format_code(&kt_file);
         }
     }
+    let cinterop_dir = out_dir.join("nativeInterop").join("cinterop");
+    fs::create_dir_all(&cinterop_dir)?;
+    fs::write(
+        cinterop_dir.join(format!("{}.h", ci.namespace())),
+        bindings.native_header,
+    )?;
+    fs::write(
+        cinterop_dir.join(format!("{}.def", ci.namespace())),
+        bindings.cinterop_def,
+    )?;
     Ok(())
 }
 
+fn format_code(kt_file: &Utf8Path) {
+    if let Err(e) = Command::new("ktlint").arg("-F").arg(kt_file).output() {
+        println!(
+            "Warning: Unable to auto-format {} using ktlint: {e:?}",
+            kt_file.file_name().unwrap(),
+        );
+    }
+}
+
 fn full_bindings_path(config: &Config, out_dir: &Utf8Path) -> Utf8PathBuf {
     let package_path: Utf8PathBuf = config.package_name().split('.').collect();
     Utf8PathBuf::from(out_dir).join(package_path)
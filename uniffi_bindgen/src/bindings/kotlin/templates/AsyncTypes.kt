@@ -25,7 +25,7 @@ internal interface UniFfiFutureCallback{{ callback_param|ffi_type_name }} : com.
 // Callback handlers for an async call.  These are invoked by Rust when the future is ready.  They
 // lift the return value or error and resume the suspended function.
 {%- for result_type in ci.iter_async_result_types() %}
-{%- let callback_param = result_type.future_callback_param() %}
+{%- let callback_param = ci.future_callback_param(result_type) %}
 
 internal class {{ result_type|future_callback_handler }}(val continuation: {{ result_type|future_continuation_type }})
     : UniFfiFutureCallback{{ callback_param|ffi_type_name }} {
@@ -0,0 +1,40 @@
+{% if self.include_once_check("CallbackConformanceRuntime.kt") %}{% include "CallbackConformanceRuntime.kt" %}{% endif %}
+{{- self.add_import("java.util.concurrent.CountDownLatch") }}
+{{- self.add_import("java.util.concurrent.ExecutionException") }}
+{{- self.add_import("java.util.concurrent.Executors") }}
+{{- self.add_import("java.util.concurrent.TimeUnit") }}
+{{- self.add_import("java.util.concurrent.TimeoutException") }}
+
+/**
+ * Checks that `implementation` behaves the way Rust expects implementations of [{{ type_name }}]
+ * to behave, see `uniffiCheckCallbackConformance()`.
+ *
+ * Each `check` method calls the corresponding method of `implementation` with the given arguments.
+ */
+public class {{ type_name }}Conformance(
+    val implementation: {{ type_name }},
+    val threads: Int = 4,
+    val iterations: Int = 25,
+    val timeoutMillis: Long = 10_000,
+) {
+    {% for meth in cbi.methods() -%}
+    fun {{ format!("check_{}", meth.name())|fn_name }}({% call kt::arg_list_protocol(meth) %}) {
+        uniffiCheckCallbackConformance(
+            "{{ type_name }}.{{ meth.name()|fn_name }}",
+            this.threads,
+            this.iterations,
+            this.timeoutMillis,
+            {%- match meth.throws_type() %}
+            {%- when Some(error_type) %}
+            { e -> e is {{ error_type|error_type_name }} },
+            {%- when None %}
+            { false },
+            {%- endmatch %}
+        ) {
+            this.implementation.{{ meth.name()|fn_name }}(
+                {%- for arg in meth.arguments() %}{{ self.field_name(arg.name()) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+            )
+        }
+    }
+    {% endfor %}
+}
@@ -0,0 +1,59 @@
+/**
+ * Calls `call` the way Rust calls callback interface methods, failing with an `AssertionError`
+ * if the implementation doesn't behave the way the Rust side expects:
+ *
+ *   - Errors: any exception other than the method's declared error type is seen by Rust as an
+ *     unexpected error, so `isDeclaredError` must accept everything that `call` throws.
+ *   - Ordering: the method is called `iterations` times in a row from the current thread and
+ *     each call must return before the next one is made.
+ *   - Threading: Rust may call the method from any thread, so it's then called `iterations`
+ *     times from each of `threads` threads at once, all of which must finish within
+ *     `timeoutMillis`.
+ *
+ * This is only generated when `callback_conformance_checks` is enabled in `uniffi.toml`.
+ */
+@Suppress("TooGenericExceptionCaught")
+internal fun uniffiCheckCallbackConformance(
+    name: String,
+    threads: Int,
+    iterations: Int,
+    timeoutMillis: Long,
+    isDeclaredError: (Throwable) -> Boolean,
+    call: () -> Unit,
+) {
+    fun checkedCall() {
+        try {
+            call()
+        } catch (e: Throwable) {
+            if (!isDeclaredError(e)) {
+                throw AssertionError("$name threw $e, which Rust sees as an unexpected error", e)
+            }
+        }
+    }
+
+    repeat(iterations) { checkedCall() }
+
+    val executor = Executors.newFixedThreadPool(threads)
+    try {
+        val start = CountDownLatch(1)
+        val futures = List(threads) {
+            executor.submit {
+                start.await()
+                repeat(iterations) { checkedCall() }
+            }
+        }
+        start.countDown()
+        val deadline = System.currentTimeMillis() + timeoutMillis
+        for (future in futures) {
+            try {
+                future.get(maxOf(deadline - System.currentTimeMillis(), 0), TimeUnit.MILLISECONDS)
+            } catch (e: ExecutionException) {
+                throw e.cause ?: e
+            } catch (e: TimeoutException) {
+                throw AssertionError("$name didn't return within ${timeoutMillis}ms when called from $threads threads", e)
+            }
+        }
+    } finally {
+        executor.shutdownNow()
+    }
+}
@@ -1,37 +1,34 @@
 internal typealias Handle = Long
-internal class ConcurrentHandleMap<T>(
-    private val leftMap: MutableMap<Handle, T> = mutableMapOf(),
-    private val rightMap: MutableMap<T, Handle> = mutableMapOf()
-) {
-    private val lock = java.util.concurrent.locks.ReentrantLock()
+// Maps handles to callback objects, without taking a lock.
+//
+// Every call to `insert()` returns a fresh handle, even if the object was inserted before: each
+// handle is owned by exactly one Rust-side reference, which removes it when it's dropped.  Handles
+// come from a 64-bit counter, so they're never reused.  This means that using a handle after it's
+// been removed fails the lookup, rather than returning some other object.
+internal class ConcurrentHandleMap<T: Any> {
+    private val map = ConcurrentHashMap<Handle, T>()
     private val currentHandle = AtomicLong(0L)
-    private val stride = 1L
-
-    fun insert(obj: T): Handle =
-        lock.withLock {
-            rightMap[obj] ?:
-                currentHandle.getAndAdd(stride)
-                    .also { handle ->
-                        leftMap[handle] = obj
-                        rightMap[obj] = handle
-                    }
-            }
-
-    fun get(handle: Handle) = lock.withLock {
-        leftMap[handle]
+
+    fun insert(obj: T): Handle {
+        val handle = currentHandle.getAndIncrement()
+        map.put(handle, obj)
+        return handle
     }
 
+    fun get(handle: Handle): T? = map.get(handle)
+
     fun delete(handle: Handle) {
         this.remove(handle)
     }
 
-    fun remove(handle: Handle): T? =
-        lock.withLock {
-            leftMap.remove(handle)?.let { obj ->
-                rightMap.remove(obj)
-                obj
-            }
-        }
+    fun remove(handle: Handle): T? = map.remove(handle)
+
+    // The handles of an object that was inserted, see `uniffiDetach()`
+    fun handlesOf(obj: Any): List<Handle> = map.filterValues { it === obj }.keys.toList()
+    {%- if self.hot_reload() %}
+
+    fun clear() = map.clear()
+    {%- endif %}
 }
 
 interface ForeignCallback : com.sun.jna.Callback {
@@ -41,12 +38,35 @@ interface ForeignCallback : com.sun.jna.Callback {
 // Magic number for the Rust proxy to call using the same mechanism as every other method,
 // to free the callback once it's dropped by Rust.
 internal const val IDX_CALLBACK_FREE = 0
+// Reads the value of a constant, see `uniffi_core/src/ffi/foreigncallbacks.rs`
+internal const val IDX_CALLBACK_CONSTANT = Int.MAX_VALUE
 // Callback return codes
 internal const val UNIFFI_CALLBACK_SUCCESS = 0
 internal const val UNIFFI_CALLBACK_ERROR = 1
 internal const val UNIFFI_CALLBACK_UNEXPECTED_ERROR = 2
 
-public abstract class FfiConverterCallbackInterface<CallbackInterface>(
+// Serializes an unexpected error for Rust: the exception's class name, then its message.  Errors
+// in the bindings' own code use an empty class name.
+// See `CallbackError` in `uniffi_core/src/ffi/foreigncallbacks.rs`
+internal fun uniffiLowerUnexpectedError(className: String, message: String): RustBuffer.ByValue {
+    val rbuf = RustBuffer.alloc(
+        {{ Type::String.borrow()|ffi_converter_name }}.allocationSize(className) + {{ Type::String.borrow()|ffi_converter_name }}.allocationSize(message)
+    )
+    try {
+        val bbuf = rbuf.data!!.getByteBuffer(0, rbuf.capacity.toLong()).also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+        {{ Type::String.borrow()|ffi_converter_name }}.write(className, bbuf)
+        {{ Type::String.borrow()|ffi_converter_name }}.write(message, bbuf)
+        rbuf.writeField("len", bbuf.position())
+        return rbuf
+    } catch (e: Throwable) {
+        RustBuffer.free(rbuf)
+        throw e
+    }
+}
+
+public abstract class FfiConverterCallbackInterface<CallbackInterface: Any>(
     protected val foreignCallback: ForeignCallback
 ): FfiConverter<CallbackInterface, Handle> {
     private val handleMap = ConcurrentHandleMap<CallbackInterface>()
@@ -55,6 +75,25 @@ public abstract class FfiConverterCallbackInterface<CallbackInterface>(
     // This method is generated for each callback interface.
     internal abstract fun register(lib: _UniFFILib)
 
+    {%- if self.hot_reload() %}
+
+    // Forgets the callback interfaces held by a library that was unloaded, see `uniffiReload()`
+    internal fun clearHandles() = handleMap.clear()
+    {%- endif %}
+
+    // Tells Rust that the object behind the handle was detached.
+    // This method is generated for each callback interface.
+    internal abstract fun detachHandle(handle: Handle)
+
+    // Detaches an object, see `uniffiDetach()`.  Rust is told first, so that it doesn't call the
+    // object once it's been removed from the handle map.
+    internal fun detach(value: Any) {
+        handleMap.handlesOf(value).forEach { handle ->
+            detachHandle(handle)
+            handleMap.remove(handle)
+        }
+    }
+
     fun drop(handle: Handle): RustBuffer.ByValue {
         return handleMap.remove(handle).let { RustBuffer.ByValue() }
     }
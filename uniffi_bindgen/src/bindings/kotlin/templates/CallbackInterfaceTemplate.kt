@@ -4,45 +4,79 @@
 
 {% if self.include_once_check("CallbackInterfaceRuntime.kt") %}{% include "CallbackInterfaceRuntime.kt" %}{% endif %}
 {{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
-{{- self.add_import("java.util.concurrent.locks.ReentrantLock") }}
-{{- self.add_import("kotlin.concurrent.withLock") }}
 
 // Declaration and FfiConverters for {{ type_name }} Callback Interface
-
+{%- if cbi.is_closure() %}
+{%- let meth = cbi.methods()[0] %}
+public typealias {{ type_name }} = (
+    {%- for arg in meth.arguments() %}{{ arg|type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+) -> {% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% else %}Unit{% endmatch %}
+{%- else %}
+{% call kt::docstring(cbi, 0) %}
 public interface {{ type_name }} {
-    {% for meth in cbi.methods() -%}
+    {%- for meth in cbi.methods() %}
+    {%- call kt::docstring(meth, 4) %}
     fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %}: {{ return_type|type_name -}}
     {%- else -%}
     {%- endmatch %}
-    {% endfor %}
+    {%- if meth.has_default() %} =
+        // The default implementation in Rust, called with a handle to this object
+        {% call kt::to_ffi_call_with_prefix(format!("{}.lower(this)", ffi_converter_name), meth) %}
+        {%- match meth.return_type() %}
+        {%- when Some with (return_type) %}.let {
+            {{ return_type|lift_fn }}(it)
+        }
+        {%- else %}
+        {%- endmatch %}
+    {%- endif %}
+    {%- endfor %}
+    {%- if !cbi.constants().is_empty() %}
+    companion object {
+        {%- for c in cbi.constants() %}
+        val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+        {%- endfor %}
+    }
+    {%- endif %}
 }
+{%- endif %}
 
 // The ForeignCallback that is passed to Rust.
 internal class {{ foreign_callback }} : ForeignCallback {
     @Suppress("TooGenericExceptionCaught")
     override fun callback(handle: Handle, method: Int, argsData: Pointer, argsLen: Int, outBuf: RustBufferByReference): Int {
-        val cb = {{ ffi_converter_name }}.lift(handle)
+        if (method == IDX_CALLBACK_FREE) {
+            {{ ffi_converter_name }}.drop(handle)
+            // Successful return
+            // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs`
+            return UNIFFI_CALLBACK_SUCCESS
+        }
+        // The handle is gone if the object was detached while Rust was calling it, see
+        // `uniffiDetach()`
+        val cb = try {
+            {{ ffi_converter_name }}.lift(handle)
+        } catch (e: InternalException) {
+            outBuf.setValue(uniffiLowerUnexpectedError("", e.message ?: ""))
+            return UNIFFI_CALLBACK_UNEXPECTED_ERROR
+        }
         return when (method) {
-            IDX_CALLBACK_FREE -> {
-                {{ ffi_converter_name }}.drop(handle)
-                // Successful return
-                // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs`
-                UNIFFI_CALLBACK_SUCCESS
-            }
             {% for meth in cbi.methods() -%}
             {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
             {{ loop.index }} -> {
                 // Call the method, write to outBuf and return a status code
                 // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs` for info
                 try {
+                    {%- if cbi.main_thread() %}
+                    uniffiRunOnMainThread { this.{{ method_name }}(cb, argsData, argsLen, outBuf) }
+                    {%- else %}
                     this.{{ method_name }}(cb, argsData, argsLen, outBuf)
+                    {%- endif %}
                 } catch (e: Throwable) {
                     // Unexpected error
                     try {
-                        // Try to serialize the error into a string
-                        outBuf.setValue({{ Type::String.borrow()|ffi_converter_name }}.lower(e.toString()))
+                        // Try to serialize the error's class name and message
+                        outBuf.setValue(uniffiLowerUnexpectedError(e.javaClass.name, e.message ?: ""))
                     } catch (e: Throwable) {
                         // If that fails, then it's time to give up and just return
                     }
@@ -50,12 +84,29 @@ internal class {{ foreign_callback }} : ForeignCallback {
                 }
             }
             {% endfor %}
+            {%- if !cbi.constants().is_empty() %}
+            IDX_CALLBACK_CONSTANT -> {
+                // Write the value of the constant whose index is in argsData to outBuf
+                val argsBuf = argsData.getByteBuffer(0, argsLen.toLong()).also {
+                    it.order(ByteOrder.BIG_ENDIAN)
+                }
+                when (argsBuf.getInt()) {
+                    {%- for c in cbi.constants() %}
+                    {{ loop.index0 }} -> {
+                        outBuf.setValue({{ c|ffi_converter_name }}.lowerIntoRustBuffer({{ type_name }}.{{ c.name() }}))
+                        UNIFFI_CALLBACK_SUCCESS
+                    }
+                    {%- endfor %}
+                    else -> UNIFFI_CALLBACK_UNEXPECTED_ERROR
+                }
+            }
+            {%- endif %}
             else -> {
                 // An unexpected error happened.
                 // See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs`
                 try {
-                    // Try to serialize the error into a string
-                    outBuf.setValue({{ Type::String.borrow()|ffi_converter_name }}.lower("Invalid Callback index"))
+                    // Try to serialize the error
+                    outBuf.setValue(uniffiLowerUnexpectedError("", "Invalid Callback index"))
                 } catch (e: Throwable) {
                     // If that fails, then it's time to give up and just return
                 }
@@ -125,4 +176,12 @@ public object {{ ffi_converter_name }}: FfiConverterCallbackInterface<{{ type_na
             lib.{{ cbi.ffi_init_callback().name() }}(this.foreignCallback, status)
         }
     }
+
+    override fun detachHandle(handle: Handle) {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ cbi.ffi_detach_callback().name() }}(handle, status)
+        }
+    }
 }
+
+{% if self.callback_conformance_checks() && !cbi.is_closure() %}{% include "CallbackConformance.kt" %}{% endif %}
@@ -0,0 +1,24 @@
+// Chars are passed as their Unicode scalar value.  A Kotlin `Char` is a UTF-16 code unit, so chars
+// outside of the Basic Multilingual Plane, like most emojis, can't be lifted into one.
+public object FfiConverterChar: FfiConverter<Char, Int> {
+    override fun lift(value: Int): Char {
+        if (value > 0xFFFF) {
+            throw InternalException("U+%X doesn't fit in a Kotlin Char".format(value))
+        }
+        return value.toChar()
+    }
+
+    override fun read(buf: ByteBuffer): Char {
+        return lift(buf.getInt())
+    }
+
+    override fun lower(value: Char): Int {
+        return value.code
+    }
+
+    override fun allocationSize(value: Char) = 4
+
+    override fun write(value: Char, buf: ByteBuffer) {
+        buf.putInt(lower(value))
+    }
+}
@@ -0,0 +1,71 @@
+{%- import "macros.kt" as kt %}
+
+{#-
+ # The declarations shared by the JVM and Kotlin/Native implementations of multiplatform
+ # bindings.  Records, enums, flags and errors are plain Kotlin, so they're declared here in
+ # full, using the same templates as the regular bindings.  The FfiConverters are left to each
+ # platform.
+ #}
+
+{%- for type_ in ci.iter_types() %}
+{%- let type_name = type_|type_name %}
+{%- let contains_object_references = ci.item_contains_object_references(type_) %}
+{%- match type_ %}
+
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if !ci.is_name_used_as_error(name) %}
+{% include "EnumDeclaration.kt" %}
+{%- else %}
+{%- let type_name = type_|error_type_name %}
+{%- let ffi_converter_name = type_|error_ffi_converter_name %}
+{% include "ErrorDeclaration.kt" %}
+{%- endif %}
+
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+{% include "FlagsDeclaration.kt" %}
+
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+{% include "RecordDeclaration.kt" %}
+
+{%- when Type::Object { module_path, name, imp } %}
+{%- let obj = ci|get_object_definition(name) %}
+{% include "ObjectInterface.kt" %}
+
+expect class {{ type_name }} : Disposable, {{ type_name }}Interface{% if obj.is_ord() %}, Comparable<{{ type_name }}>{% endif %} {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    constructor({% call kt::arg_list_decl(cons) -%})
+    {%- when None %}
+    {%- endmatch %}
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Restore { serialize, restore } %}
+
+    fun serializeHandle(): String
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+
+    {% if !obj.alternate_constructors().is_empty() || obj.restore_handle_fn().is_some() || !obj.constants().is_empty() -%}
+    companion object {
+        {%- for c in obj.constants() %}
+        val {{ c.name() }}: {{ c|type_name }}
+        {%- endfor %}
+        {%- for cons in obj.alternate_constructors() %}
+        fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ type_name }}
+        {%- endfor %}
+        {%- match obj.restore_handle_fn() %}
+        {%- when Some with (restore) %}
+        fun restoreHandle(token: String): {{ type_name }}?
+        {%- else %}
+        {%- endmatch %}
+    }
+    {% endif %}
+}
+
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
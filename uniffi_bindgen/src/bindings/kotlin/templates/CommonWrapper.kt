@@ -0,0 +1,60 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+{%- match ci.namespace_docstring() %}
+{%- when Some with (docstring) %}
+
+{{ docstring|docstring(0) }}
+{%- when None %}
+{%- endmatch %}
+
+package {{ config.package_name() }};
+
+// The common code of the multiplatform bindings.
+//
+// This declares the public API, which is implemented for the JVM using JNA, and for Kotlin/Native
+// using cinterop, in the bindings of the `jvmMain` and `nativeMain` source sets.
+
+{%- for req in self.imports() %}
+{{ req.render() }}
+{%- endfor %}
+
+{% include "Disposable.kt" %}
+
+// `rustBacktrace` is the backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled
+class InternalException(message: String, val rustBacktrace: String? = null) : Exception(message)
+
+// Public interface members begin here.
+{{ type_declarations }}
+
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+const val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+
+{% match func.throws_type() -%}
+{%- when Some with (throwable) -%}
+@Throws({{ throwable|error_type_name }}::class)
+{% else -%}
+{%- endmatch -%}
+expect fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %})
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}: {{ return_type|type_name }}
+{%- when None %}
+{%- endmatch %}
+{%- endfor %}
+
+/**
+ * Lists the objects that Rust passed to Kotlin and that haven't been freed yet, and the callback
+ * interfaces that Rust still holds, with a backtrace of where each one was created.
+ *
+ * This needs the `handle-diagnostics` feature of the `uniffi` crate, otherwise the report only
+ * says that it's disabled.
+ */
+expect fun uniffiDebugLiveHandles(): String
+
+{% import "macros.kt" as kt %}
@@ -0,0 +1,31 @@
+{%- if self.include_once_check("CompressionRuntime.kt") %}{%- include "CompressionRuntime.kt" %}{%- endif %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ inner_type_name }}> {
+    override fun read(buf: ByteBuffer): {{ inner_type_name }} {
+        when (buf.get().toInt()) {
+            0 -> return {{ inner_type|read_fn }}(buf)
+            1 -> {
+                val uncompressedSize = buf.getInt()
+                val compressedSize = buf.getInt()
+                val uncompressed = uniffiInflate(buf, uncompressedSize, compressedSize)
+                val value = {{ inner_type|read_fn }}(uncompressed)
+                if (uncompressed.hasRemaining()) {
+                    throw InternalException("Junk data left in buffer after decompressing value")
+                }
+                return value
+            }
+            else -> throw InternalException("Unknown compression algorithm")
+        }
+    }
+
+    // Values sent to Rust are never compressed
+    override fun allocationSize(value: {{ inner_type_name }}): Int {
+        return 1 + {{ inner_type|allocation_size_fn }}(value)
+    }
+
+    override fun write(value: {{ inner_type_name }}, buf: ByteBuffer) {
+        buf.put(0)
+        {{ inner_type|write_fn }}(value, buf)
+    }
+}
@@ -0,0 +1,19 @@
+// Compressed values are sent by Rust as raw deflate data, see `uniffi::Compressed`
+internal fun uniffiInflate(buf: ByteBuffer, uncompressedSize: Int, compressedSize: Int): ByteBuffer {
+    // `Inflater` needs an extra dummy byte after the input when `nowrap` is set
+    val compressed = ByteArray(compressedSize + 1)
+    buf.get(compressed, 0, compressedSize)
+    val inflater = java.util.zip.Inflater(true)
+    try {
+        inflater.setInput(compressed)
+        val uncompressed = ByteArray(uncompressedSize)
+        if (inflater.inflate(uncompressed) != uncompressedSize || !inflater.finished()) {
+            throw InternalException("Compressed value has the wrong size")
+        }
+        return ByteBuffer.wrap(uncompressed)
+    } catch (e: java.util.zip.DataFormatException) {
+        throw InternalException("Invalid compressed value: ${e.message}")
+    } finally {
+        inflater.end()
+    }
+}
@@ -0,0 +1,28 @@
+public object FfiConverterDecimal: FfiConverter<java.math.BigDecimal, RustBuffer.ByValue> {
+    // Decimals are passed as strings in plain notation, Rust validates them when it lifts them.
+    override fun lift(value: RustBuffer.ByValue): java.math.BigDecimal {
+        return java.math.BigDecimal(FfiConverterString.lift(value))
+    }
+
+    override fun read(buf: ByteBuffer): java.math.BigDecimal {
+        return java.math.BigDecimal(FfiConverterString.read(buf))
+    }
+
+    override fun lower(value: java.math.BigDecimal): RustBuffer.ByValue {
+        return FfiConverterString.lower(value.toPlainString())
+    }
+
+    override fun allocationSize(value: java.math.BigDecimal): Int {
+        return FfiConverterString.allocationSize(value.toPlainString())
+    }
+
+    override fun write(value: java.math.BigDecimal, buf: ByteBuffer) {
+        FfiConverterString.write(value.toPlainString(), buf)
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiBigDecimalSerializer") }}
+
+object UniffiBigDecimalSerializer: UniffiStringSerializer<java.math.BigDecimal>("java.math.BigDecimal", { it.toPlainString() }, { java.math.BigDecimal(it) })
+{%- endif %}
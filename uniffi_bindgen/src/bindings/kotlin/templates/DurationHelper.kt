@@ -34,3 +34,10 @@ public object FfiConverterDuration: FfiConverterRustBuffer<java.time.Duration> {
         buf.putInt(value.nano)
     }
 }
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiDurationSerializer") }}
+
+// Durations are serialized in ISO-8601 format, like `PT1.5S`
+object UniffiDurationSerializer: UniffiStringSerializer<java.time.Duration>("java.time.Duration", { it.toString() }, { java.time.Duration.parse(it) })
+{%- endif %}
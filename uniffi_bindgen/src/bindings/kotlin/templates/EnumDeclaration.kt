@@ -0,0 +1,92 @@
+{#
+// Kotlin's `enum class` construct doesn't support variants with associated data,
+// but is a little nicer for consumers than its `sealed class` enum pattern.
+// So, we switch here, using `enum class` for enums with no associated data
+// and `sealed class` for the general case.
+#}
+
+{#- Objects are only valid in this process, so enums that hold them can't be parcelled or serialized #}
+{%- let parcelable = self.parcelize() && !contains_object_references %}
+{%- if parcelable %}
+{{- self.add_import("kotlinx.parcelize.Parcelize") }}
+{{- self.add_import("android.os.Parcelable") }}
+{%- endif %}
+{%- let serializable = self.serializable() && !contains_object_references %}
+{%- if serializable %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{%- endif %}
+
+{%- if e.is_flat() %}
+{% call kt::docstring(e, 0) %}
+{% if parcelable -%}
+@Parcelize
+{% endif -%}
+{% if serializable -%}
+@Serializable
+{% endif -%}
+{#- Explicit discriminants from Rust are available as the `value` property -#}
+enum class {{ type_name }}{% if e.has_discriminants() %}(val value: Long){% endif %}{% if parcelable %}: Parcelable{% endif %} {
+    {% for variant in e.variants() -%}
+    {{ variant|variant_name }}{% match variant.discr() %}{% when Some with (discr) %}({{ discr }}L){% when None %}{% endmatch %}{% if loop.last && !e.is_non_exhaustive() %};{% else %},{% endif %}
+    {%- endfor %}
+    {%- if e.is_non_exhaustive() %}
+    /** A variant that was added to the Rust enum after these bindings were generated */
+    UNKNOWN{% if e.has_discriminants() %}(Long.MIN_VALUE){% endif %};
+    {%- endif %}
+}
+
+{% else %}
+{% call kt::docstring(e, 0) %}
+{% if parcelable -%}
+@Parcelize
+{% endif -%}
+{% if serializable -%}
+@Serializable
+{% endif -%}
+sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% else if parcelable %}: Parcelable {% endif %} {
+    {% for variant in e.variants() -%}
+    {% if serializable -%}
+    @Serializable
+    {% endif -%}
+    {% if !variant.has_fields() -%}
+    object {{ variant|enum_variant|type_name }} : {{ type_name }}()
+    {% else -%}
+    data class {{ variant|enum_variant|type_name }}(
+        {% for field in variant.fields() -%}
+        val {{ self.field_name(field.name()) }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
+        {% endfor -%}
+    ) : {{ type_name }}()
+    {%- endif %}
+    {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    /** A variant that was added to the Rust enum after these bindings were generated */
+    {% if serializable -%}
+    @Serializable
+    {% endif -%}
+    object Unknown : {{ type_name }}()
+    {%- endif %}
+
+    {% if contains_object_references %}
+    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
+    override fun destroy() {
+        when(this) {
+            {%- for variant in e.variants() %}
+            is {{ type_name }}.{{ variant|enum_variant|type_name }} -> {
+                {%- if variant.has_fields() %}
+                {% call kt::destroy_fields(variant) %}
+                {% else -%}
+                // Nothing to destroy
+                {%- endif %}
+            }
+            {%- endfor %}
+            {%- if e.is_non_exhaustive() %}
+            is {{ type_name }}.Unknown -> {
+                // Nothing to destroy
+            }
+            {%- endif %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+    {% endif %}
+}
+
+{% endif %}
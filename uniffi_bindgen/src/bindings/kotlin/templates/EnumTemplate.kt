@@ -1,68 +1,62 @@
-{#
-// Kotlin's `enum class` construct doesn't support variants with associated data,
-// but is a little nicer for consumers than its `sealed class` enum pattern.
-// So, we switch here, using `enum class` for enums with no associated data
-// and `sealed class` for the general case.
-#}
+{#- Multiplatform bindings declare the enum in the common code #}
+{%- if !self.multiplatform() %}
+{%- match self.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
 
-{%- if e.is_flat() %}
+{{ type_override }}
+{%- when None %}
+{% include "EnumDeclaration.kt" %}
+{%- endmatch %}
+{%- endif %}
 
-enum class {{ type_name }} {
-    {% for variant in e.variants() -%}
-    {{ variant|variant_name }}{% if loop.last %};{% else %},{% endif %}
-    {%- endfor %}
-}
+{%- if e.is_flat() %}
 
 public object {{ e|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    {%- if e.is_non_exhaustive() %}
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        val index = buf.getInt()
+        // Skip the fields of variants that were added after these bindings were generated
+        val size = buf.getInt()
+        buf.position(buf.position() + size)
+        return when(index) {
+            {%- for variant in e.variants() %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|variant_name }}
+            {%- endfor %}
+            else -> {{ type_name }}.UNKNOWN
+        }
+    }
+    {%- else %}
     override fun read(buf: ByteBuffer) = try {
         {{ type_name }}.values()[buf.getInt() - 1]
     } catch (e: IndexOutOfBoundsException) {
         throw RuntimeException("invalid enum value, something is very wrong!!", e)
     }
+    {%- endif %}
 
     override fun allocationSize(value: {{ type_name }}) = 4
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        {%- if e.is_non_exhaustive() %}
+        if (value == {{ type_name }}.UNKNOWN) {
+            throw IllegalArgumentException("{{ type_name }}.UNKNOWN can't be passed to Rust")
+        }
+        {%- endif %}
         buf.putInt(value.ordinal + 1)
     }
 }
 
 {% else %}
 
-sealed class {{ type_name }}{% if contains_object_references %}: Disposable {% endif %} {
-    {% for variant in e.variants() -%}
-    {% if !variant.has_fields() -%}
-    object {{ variant|enum_variant|type_name }} : {{ type_name }}()
-    {% else -%}
-    data class {{ variant|enum_variant|type_name }}(
-        {% for field in variant.fields() -%}
-        val {{ field.name()|var_name }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
-        {% endfor -%}
-    ) : {{ type_name }}()
-    {%- endif %}
-    {% endfor %}
-
-    {% if contains_object_references %}
-    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
-    override fun destroy() {
-        when(this) {
-            {%- for variant in e.variants() %}
-            is {{ type_name }}.{{ variant|enum_variant|type_name }} -> {
-                {%- if variant.has_fields() %}
-                {% call kt::destroy_fields(variant) %}
-                {% else -%}
-                // Nothing to destroy
-                {%- endif %}
-            }
-            {%- endfor %}
-        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
-    }
-    {% endif %}
-}
-
 public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}>{
     override fun read(buf: ByteBuffer): {{ type_name }} {
+        {%- if e.is_non_exhaustive() %}
+        val index = buf.getInt()
+        // The size of the fields, to skip variants that were added after these bindings were generated
+        val size = buf.getInt()
+        return when(index) {
+        {%- else %}
         return when(buf.getInt()) {
+        {%- endif %}
             {%- for variant in e.variants() %}
             {{ loop.index }} -> {{ type_name }}.{{ variant|enum_variant|type_name }}{% if variant.has_fields() %}(
                 {% for field in variant.fields() -%}
@@ -70,7 +64,14 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
                 {% endfor -%}
             ){%- endif -%}
             {%- endfor %}
+            {%- if e.is_non_exhaustive() %}
+            else -> {
+                buf.position(buf.position() + size)
+                {{ type_name }}.Unknown
+            }
+            {%- else %}
             else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+            {%- endif %}
         }
     }
 
@@ -81,11 +82,14 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
             (
                 4
                 {%- for field in variant.fields() %}
-                + {{ field|allocation_size_fn }}(value.{{ field.name()|var_name }})
+                + {{ field|allocation_size_fn }}(value.{{ self.field_name(field.name()) }})
                 {%- endfor %}
             )
         }
         {%- endfor %}
+        {%- if e.is_non_exhaustive() %}
+        is {{ type_name }}.Unknown -> 4
+        {%- endif %}
     }
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
@@ -94,11 +98,14 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
             is {{ type_name }}.{{ variant|enum_variant|type_name }} -> {
                 buf.putInt({{ loop.index }})
                 {%- for field in variant.fields() %}
-                {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+                {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
                 {%- endfor %}
                 Unit
             }
             {%- endfor %}
+            {%- if e.is_non_exhaustive() %}
+            is {{ type_name }}.Unknown -> throw IllegalArgumentException("{{ type_name }}.Unknown can't be passed to Rust")
+            {%- endif %}
         }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
     }
 }
@@ -0,0 +1,54 @@
+{%- call kt::docstring(e, 0) %}
+{%- if e.is_flat() %}
+sealed class {{ type_name }}(message: String): Exception(message){% if contains_object_references %}, Disposable {% endif %} {
+        // Each variant is a nested class
+        // Flat enums carry a string error message, along with the fields marked with
+        // `#[uniffi(error_field)]`.
+        {% for variant in e.variants() -%}
+        class {{ variant|error_variant|type_name }}(
+            message: String,
+            {%- for field in variant.fields() %}
+            val {{ self.field_name(field.name()) }}: {{ field|type_name }},
+            {%- endfor %}
+        ) : {{ type_name }}(message)
+        {% endfor %}
+{%- else %}
+sealed class {{ type_name }}: Exception(){% if contains_object_references %}, Disposable {% endif %} {
+    // Each variant is a nested class
+    {% for variant in e.variants() -%}
+    {%- let variant_name = variant|error_variant|type_name %}
+    class {{ variant_name }}(
+        {% for field in variant.fields() -%}
+        val {{ self.field_name(field.name()) }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
+        {% endfor -%}
+    ) : {{ type_name }}() {
+        override val message
+            get() = "{%- for field in variant.fields() %}{{ self.field_name(field.name())|unquote }}=${ {{ self.field_name(field.name()) }} }{% if !loop.last %}, {% endif %}{% endfor %}"
+    }
+    {% endfor %}
+{%- endif %}
+
+    {%- if !self.multiplatform() %}
+
+    companion object ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
+        override fun lift(error_buf: {{ self.error_buf_type() }}): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
+    }
+    {%- endif %}
+
+    {% if contains_object_references %}
+    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
+    override fun destroy() {
+        when(this) {
+            {%- for variant in e.variants() %}
+            is {{ type_name }}.{{ variant|error_variant|type_name }} -> {
+                {%- if variant.has_fields() %}
+                {% call kt::destroy_fields(variant) %}
+                {% else -%}
+                // Nothing to destroy
+                {%- endif %}
+            }
+            {%- endfor %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+    {% endif %}
+}
@@ -2,54 +2,9 @@
 {%- let ffi_converter_name = type_|error_ffi_converter_name %}
 {%- let canonical_type_name = type_|error_canonical_name %}
 
-{% if e.is_flat() %}
-sealed class {{ type_name }}(message: String): Exception(message){% if contains_object_references %}, Disposable {% endif %} {
-        // Each variant is a nested class
-        // Flat enums carries a string error message, so no special implementation is necessary.
-        {% for variant in e.variants() -%}
-        class {{ variant|error_variant|type_name }}(message: String) : {{ type_name }}(message)
-        {% endfor %}
-
-    companion object ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
-        override fun lift(error_buf: RustBuffer.ByValue): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
-    }
-}
-{%- else %}
-sealed class {{ type_name }}: Exception(){% if contains_object_references %}, Disposable {% endif %} {
-    // Each variant is a nested class
-    {% for variant in e.variants() -%}
-    {%- let variant_name = variant|error_variant|type_name %}
-    class {{ variant_name }}(
-        {% for field in variant.fields() -%}
-        val {{ field.name()|var_name }}: {{ field|type_name}}{% if loop.last %}{% else %}, {% endif %}
-        {% endfor -%}
-    ) : {{ type_name }}() {
-        override val message
-            get() = "{%- for field in variant.fields() %}{{ field.name()|var_name|unquote }}=${ {{field.name()|var_name }} }{% if !loop.last %}, {% endif %}{% endfor %}"
-    }
-    {% endfor %}
-
-    companion object ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
-        override fun lift(error_buf: RustBuffer.ByValue): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
-    }
-
-    {% if contains_object_references %}
-    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
-    override fun destroy() {
-        when(this) {
-            {%- for variant in e.variants() %}
-            is {{ type_name }}.{{ variant|error_variant|type_name }} -> {
-                {%- if variant.has_fields() %}
-                {% call kt::destroy_fields(variant) %}
-                {% else -%}
-                // Nothing to destroy
-                {%- endif %}
-            }
-            {%- endfor %}
-        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
-    }
-    {% endif %}
-}
+{#- Multiplatform bindings declare the error in the common code #}
+{%- if !self.multiplatform() %}
+{% include "ErrorDeclaration.kt" %}
 {%- endif %}
 
 public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
@@ -57,7 +12,7 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
         {% if e.is_flat() %}
             return when(buf.getInt()) {
             {%- for variant in e.variants() %}
-            {{ loop.index }} -> {{ type_name }}.{{ variant|error_variant|type_name }}({{ Type::String.borrow()|read_fn }}(buf))
+            {{ loop.index }} -> {{ type_name }}.{{ variant|error_variant|type_name }}({{ Type::String.borrow()|read_fn }}(buf){% for field in variant.fields() %}, {{ field|read_fn }}(buf){% endfor %})
             {%- endfor %}
             else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
         }
@@ -86,7 +41,7 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
                 // Add the size for the Int that specifies the variant plus the size needed for all fields
                 4
                 {%- for field in variant.fields() %}
-                + {{ field|allocation_size_fn }}(value.{{ field.name()|var_name }})
+                + {{ field|allocation_size_fn }}(value.{{ self.field_name(field.name()) }})
                 {%- endfor %}
             )
             {%- endfor %}
@@ -99,9 +54,12 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
             {%- for variant in e.variants() %}
             is {{ type_name }}.{{ variant|error_variant|type_name }} -> {
                 buf.putInt({{ loop.index }})
+                {#- Rust only reads the variant of flat errors #}
+                {%- if !e.is_flat() %}
                 {%- for field in variant.fields() %}
-                {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+                {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
                 {%- endfor %}
+                {%- endif %}
                 Unit
             }
             {%- endfor %}
@@ -109,3 +67,9 @@ public object {{ e|ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }
     }
 
 }
+{%- if self.multiplatform() %}
+
+object {{ type_name }}ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
+    override fun lift(error_buf: RustBuffer.ByValue): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
+}
+{%- endif %}
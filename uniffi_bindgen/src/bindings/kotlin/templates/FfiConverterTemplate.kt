@@ -69,3 +69,25 @@ public interface FfiConverterRustBuffer<KotlinType>: FfiConverter<KotlinType, Ru
     override fun lift(value: RustBuffer.ByValue) = liftFromRustBuffer(value)
     override fun lower(value: KotlinType) = lowerIntoRustBuffer(value)
 }
+
+// Lower an argument that's marked as sensitive.
+//
+// Like `lowerIntoRustBuffer()`, this writes the value straight into a `RustBuffer` of the right
+// size, so there's no copy left on the Kotlin side.  The difference is that if writing the value
+// fails, the partly written buffer is zeroed before it's freed.  The Rust side wipes the
+// `RustBuffer` after reading the value out of it.
+internal fun <KotlinType> uniffiLowerSensitive(converter: FfiConverter<KotlinType, RustBuffer.ByValue>, value: KotlinType): RustBuffer.ByValue {
+    val rbuf = RustBuffer.alloc(converter.allocationSize(value))
+    try {
+        val bbuf = rbuf.data!!.getByteBuffer(0, rbuf.capacity.toLong()).also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+        converter.write(value, bbuf)
+        rbuf.writeField("len", bbuf.position())
+        return rbuf
+    } catch (e: Throwable) {
+        rbuf.data!!.setMemory(0, rbuf.capacity.toLong(), 0)
+        RustBuffer.free(rbuf)
+        throw e
+    }
+}
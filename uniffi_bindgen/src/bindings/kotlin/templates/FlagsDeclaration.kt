@@ -0,0 +1,18 @@
+{%- call kt::docstring(flags, 0) %}
+@JvmInline
+value class {{ type_name }}(val bits: UInt) {
+    infix fun or(other: {{ type_name }}) = {{ type_name }}(bits or other.bits)
+
+    infix fun and(other: {{ type_name }}) = {{ type_name }}(bits and other.bits)
+
+    operator fun contains(other: {{ type_name }}) = (bits and other.bits) == other.bits
+
+    fun isEmpty() = bits == 0u
+
+    companion object {
+        fun empty() = {{ type_name }}(0u)
+        {%- for flag in flags.flags() %}
+        val {{ flag|flag_name }} = {{ type_name }}({{ flag.value() }}u)
+        {%- endfor %}
+    }
+}
@@ -0,0 +1,24 @@
+{#- Multiplatform bindings declare the flags in the common code #}
+{%- if !self.multiplatform() %}
+{% include "FlagsDeclaration.kt" %}
+{%- endif %}
+
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Int> {
+    override fun lift(value: Int): {{ type_name }} {
+        return {{ type_name }}(value.toUInt())
+    }
+
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        return lift(buf.getInt())
+    }
+
+    override fun lower(value: {{ type_name }}): Int {
+        return value.bits.toInt()
+    }
+
+    override fun allocationSize(value: {{ type_name }}) = 4
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        buf.putInt(value.bits.toInt())
+    }
+}
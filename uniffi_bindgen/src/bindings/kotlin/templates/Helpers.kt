@@ -19,9 +19,18 @@ internal open class RustCallStatus : Structure() {
     fun isPanic(): Boolean {
         return code == 2.toByte()
     }
+
+    fun isInvalidArgument(): Boolean {
+        return code == 3.toByte()
+    }
 }
 
-class InternalException(message: String) : Exception(message)
+{#- Multiplatform bindings declare this in the common code #}
+{%- if !self.multiplatform() %}
+
+// `rustBacktrace` is the backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled
+class InternalException(message: String, val rustBacktrace: String? = null) : Exception(message)
+{%- endif %}
 
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
 interface CallStatusErrorHandler<E> {
@@ -33,7 +42,7 @@ interface CallStatusErrorHandler<E> {
 // synchronize itself
 
 // Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
-private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+{% call kt::file_private() %} inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
     var status = RustCallStatus();
     val return_value = callback(status)
     checkCallStatus(errorHandler, status)
@@ -41,25 +50,40 @@ private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusE
 }
 
 // Check RustCallStatus and throw an error if the call wasn't successful
-private fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: RustCallStatus) {
+{% call kt::file_private() %} fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: RustCallStatus) {
     if (status.isSuccess()) {
         return
     } else if (status.isError()) {
         throw errorHandler.lift(status.error_buf)
     } else if (status.isPanic()) {
         // when the rust code sees a panic, it tries to construct a rustbuffer
-        // with the message.  but if that code panics, then it just sends back
-        // an empty buffer.
+        // with the message and the backtrace.  but if that code panics, then it
+        // just sends back an empty buffer.
         if (status.error_buf.len > 0) {
-            throw InternalException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
+            throw liftPanic(status.error_buf)
         } else {
             throw InternalException("Rust panic")
         }
+    } else if (status.isInvalidArgument()) {
+        // An argument couldn't be lifted, like a zero for a `NonZeroU32`
+        throw IllegalArgumentException({{ Type::String.borrow()|lift_fn }}(status.error_buf))
     } else {
         throw InternalException("Unknown rust call status: $status.code")
     }
 }
 
+// The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+private fun liftPanic(error_buf: RustBuffer.ByValue): InternalException {
+    try {
+        val buf = error_buf.asByteBuffer()!!
+        val message = {{ Type::String.borrow()|read_fn }}(buf)
+        val backtrace = {{ Type::String.borrow()|read_fn }}(buf)
+        return InternalException(message, backtrace.ifEmpty { null })
+    } finally {
+        RustBuffer.free(error_buf)
+    }
+}
+
 // CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
 object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
     override fun lift(error_buf: RustBuffer.ByValue): InternalException {
@@ -69,7 +93,7 @@ object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
 }
 
 // Call a rust function that returns a plain value
-private inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+{% call kt::file_private() %} inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
     return rustCallWithError(NullCallStatusErrorHandler, callback);
 }
 
@@ -0,0 +1,33 @@
+public object FfiConverterIpAddr: FfiConverterRustBuffer<java.net.InetAddress> {
+    // IP addresses are passed as a version byte, 4 or 6, followed by the 4 or 16 bytes of the
+    // address.  `InetAddress.getByAddress()` doesn't do any name lookups.
+    override fun read(buf: ByteBuffer): java.net.InetAddress {
+        val size = when (val version = buf.get().toInt()) {
+            4 -> 4
+            6 -> 16
+            else -> throw RuntimeException("Unexpected IP address version: $version")
+        }
+        val bytes = ByteArray(size)
+        buf.get(bytes)
+        return java.net.InetAddress.getByAddress(bytes)
+    }
+
+    override fun allocationSize(value: java.net.InetAddress) = 1 + value.address.size
+
+    override fun write(value: java.net.InetAddress, buf: ByteBuffer) {
+        buf.put((if (value is java.net.Inet4Address) 4 else 6).toByte())
+        buf.put(value.address)
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiInetAddressSerializer") }}
+
+// IP addresses are serialized as their text form, like `192.0.2.1` or `2001:db8::1`.  Parsing an
+// IP address literal with `InetAddress.getByName()` doesn't do any name lookups.
+object UniffiInetAddressSerializer: UniffiStringSerializer<java.net.InetAddress>(
+    "java.net.InetAddress",
+    { it.hostAddress },
+    { java.net.InetAddress.getByName(it) }
+)
+{%- endif %}
@@ -0,0 +1,181 @@
+{% include "ReaderWriter.kt" %}
+
+// The status of a call into Rust.  The shim only sets it when the call fails, copying the contents
+// of the error's `RustBuffer` into `errorBuf`.
+class UniffiRustCallStatus {
+    @JvmField var code: Byte = 0
+    @JvmField var errorBuf: ByteArray = ByteArray(0)
+}
+
+// The scaffolding functions, which the C shim implements by calling them.  `RustBuffer`s are passed
+// as byte arrays and pointers as `Long`s.
+internal object UniffiLib {
+    init {
+        // The shim links to the Rust library, so load that first
+        System.loadLibrary("{{ config.cdylib_name() }}")
+        System.loadLibrary("{{ config.cdylib_name() }}_jni")
+    }
+
+    {%- for func in self.jni_functions() %}
+
+    @JvmStatic
+    external fun {{ func.name() }}(
+        {%- for arg in func.arguments() %}
+        {{ arg.name()|var_name }}: {{ arg.type_().borrow()|jni_ffi_type_name }},
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}
+        uniffiOutStatus: UniffiRustCallStatus,
+        {%- endif %}
+    ): {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|jni_ffi_type_name }}{% when None %}Unit{% endmatch %}
+    {%- endfor %}
+}
+
+// Each error has an object that can lift it from the contents of the call status's `RustBuffer`
+interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: ByteArray): E;
+}
+
+// What the library reports about its version, and the checksums of its functions.  This is only
+// read once the contract versions match, since the functions may not exist otherwise.
+private data class UniffiContractInfo(
+    val uniffiVersion: String,
+    val checksums: Map<String, UShort>,
+)
+
+// This runs before the first call into Rust, which waits for it, so it can't use `rustCall()`.
+private fun uniffiContractInfo(): UniffiContractInfo {
+    val reader = UniffiReader(UniffiLib.{{ ci.ffi_uniffi_contract_info().name() }}(UniffiRustCallStatus()))
+    val uniffiVersion = reader.getBytes(reader.getInt()).decodeToString()
+    val checksums: Map<String, UShort> = mapOf(
+        {%- for (name, _checksum) in ci.iter_checksums() %}
+        "{{ name }}" to UniffiLib.{{ name }}().toUShort(),
+        {%- endfor %}
+    )
+    return UniffiContractInfo(uniffiVersion, checksums)
+}
+
+// The contract version and checksums are checked before the first call into Rust, like the JNA
+// bindings do when they load the library.  The contract version is a plain integer, so it can be
+// read from any library, however old.
+private val uniffiInitialized: Boolean by lazy {
+    val contractVersion = UniffiLib.{{ ci.ffi_uniffi_contract_version().name() }}().toUInt()
+    if (contractVersion != {{ ci.uniffi_contract_version() }}u) {
+        throw RuntimeException(
+            "UniFFI contract version mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "(contract version {{ ci.uniffi_contract_version() }}) but the library has contract version $contractVersion. " +
+            "Try cleaning and rebuilding your project"
+        )
+    }
+    val info = uniffiContractInfo()
+    val mismatched = mutableListOf<String>()
+    {%- for (name, expected_checksum) in ci.iter_checksums() %}
+    if (info.checksums["{{ name }}"] != {{ expected_checksum }}.toUShort()) {
+        mismatched.add("{{ name }}")
+    }
+    {%- endfor %}
+    if (mismatched.isNotEmpty()) {
+        throw RuntimeException(
+            "UniFFI API checksum mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "but the library is version ${info.uniffiVersion}, and these functions don't match: " +
+            "${mismatched.joinToString()}. Try cleaning and rebuilding your project"
+        )
+    }
+    true
+}
+
+// Call a rust function that returns a Result<>.  Pass in the error's handler
+private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (UniffiRustCallStatus) -> U): U {
+    check(uniffiInitialized)
+    val status = UniffiRustCallStatus()
+    val returnValue = callback(status)
+    checkCallStatus(errorHandler, status)
+    return returnValue
+}
+
+// Check UniffiRustCallStatus and throw an error if the call wasn't successful
+private fun<E: Exception> checkCallStatus(errorHandler: CallStatusErrorHandler<E>, status: UniffiRustCallStatus) {
+    when (status.code.toInt()) {
+        0 -> return
+        1 -> throw errorHandler.lift(status.errorBuf)
+        2 -> {
+            // when the rust code sees a panic, it tries to construct a rustbuffer
+            // with the message and the backtrace.  but if that code panics, then it
+            // just sends back an empty buffer.
+            if (status.errorBuf.size > 0) {
+                throw liftPanic(UniffiReader(status.errorBuf))
+            } else {
+                throw InternalException("Rust panic")
+            }
+        }
+        // An argument couldn't be lifted, like a zero for a `NonZeroU32`
+        3 -> throw IllegalArgumentException({{ Type::String.borrow()|lift_fn }}(status.errorBuf))
+        else -> throw InternalException("Unknown rust call status: ${status.code}")
+    }
+}
+
+// The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+private fun liftPanic(buf: UniffiReader): InternalException {
+    val message = {{ Type::String.borrow()|read_fn }}(buf)
+    val backtrace = {{ Type::String.borrow()|read_fn }}(buf)
+    return InternalException(message, backtrace.ifEmpty { null })
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: ByteArray): InternalException {
+        return InternalException("Unexpected CALL_ERROR")
+    }
+}
+
+// Call a rust function that returns a plain value
+private inline fun <U> rustCall(callback: (UniffiRustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback);
+}
+
+// The base class for all UniFFI Object types.  This works like `FFIObject` in the JNA bindings,
+// where there's a full description of how it keeps the Rust object alive while its methods are
+// called.
+abstract class FFIObject(
+    protected val pointer: Long
+): Disposable {
+
+    private val wasDestroyed = AtomicBoolean(false)
+    private val callCounter = AtomicLong(1)
+
+    open protected fun freeRustArcPtr() {
+        // To be overridden in subclasses.
+    }
+
+    override fun destroy() {
+        // Only allow a single call to this method.
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            // This decrement always matches the initial count of 1 given at creation time.
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.freeRustArcPtr()
+            }
+        }
+    }
+
+    internal inline fun <R> callWithPointer(block: (ptr: Long) -> R): R {
+        // Check and increment the call counter, to keep the object alive.
+        // This needs a compare-and-set retry loop in case of concurrent updates.
+        do {
+            val c = this.callCounter.get()
+            if (c == 0L) {
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+            }
+            if (c == Long.MAX_VALUE) {
+                throw IllegalStateException("${this.javaClass.simpleName} call counter would overflow")
+            }
+        } while (! this.callCounter.compareAndSet(c, c + 1L))
+        // Now we can safely do the method call without the pointer being freed concurrently.
+        try {
+            return block(this.pointer)
+        } finally {
+            // This decrement always matches the increment we performed above.
+            if (this.callCounter.decrementAndGet() == 0L) {
+                this.freeRustArcPtr()
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+// The JNI functions of the `{{ ci.namespace() }}` component, which implement the `external`
+// functions of `{{ config.package_name() }}.UniffiLib` by calling the scaffolding functions.
+//
+// `RustBuffer`s are copied to and from Java byte arrays, so the Kotlin code never handles native
+// memory, and the call status is only copied to the `UniffiRustCallStatus` when the call fails.
+// This must be built as the `{{ config.cdylib_name() }}_jni` library, linked to the
+// `{{ config.cdylib_name() }}` library, since the Kotlin code loads them both.
+
+#include <jni.h>
+#include <stdint.h>
+
+#include "{{ ci.namespace() }}.h"
+
+// Copy a byte array into a new `RustBuffer`, to hand it over to Rust
+static RustBuffer uniffi_jni_rust_buffer_from(JNIEnv *env, jbyteArray bytes)
+{
+    RustCallStatus status = {0};
+    ForeignBytes foreign_bytes;
+    jbyte *data = (*env)->GetByteArrayElements(env, bytes, NULL);
+    foreign_bytes.len = (*env)->GetArrayLength(env, bytes);
+    foreign_bytes.data = (const uint8_t *)data;
+    // This only fails if the allocation fails, which aborts the process.
+    RustBuffer buf = {{ ci.ffi_rustbuffer_from_bytes().name() }}(foreign_bytes, &status);
+    (*env)->ReleaseByteArrayElements(env, bytes, data, JNI_ABORT);
+    return buf;
+}
+
+// Copy the contents of a `RustBuffer` that Rust handed over into a new byte array, then free it
+static jbyteArray uniffi_jni_consume_rust_buffer(JNIEnv *env, RustBuffer buf)
+{
+    RustCallStatus status = {0};
+    jbyteArray bytes = (*env)->NewByteArray(env, buf.len);
+    if (bytes != NULL && buf.len > 0) {
+        (*env)->SetByteArrayRegion(env, bytes, 0, buf.len, (const jbyte *)buf.data);
+    }
+    {{ ci.ffi_rustbuffer_free().name() }}(buf, &status);
+    return bytes;
+}
+
+// Copy a failed call's status to the `UniffiRustCallStatus` that the Kotlin code passed
+static void uniffi_jni_set_status(JNIEnv *env, jobject out_status, RustCallStatus *status)
+{
+    if (status->code == 0) {
+        return;
+    }
+    jclass status_class = (*env)->GetObjectClass(env, out_status);
+    jfieldID code_field = (*env)->GetFieldID(env, status_class, "code", "B");
+    jfieldID error_buf_field = (*env)->GetFieldID(env, status_class, "errorBuf", "[B");
+    (*env)->SetByteField(env, out_status, code_field, (jbyte)status->code);
+    (*env)->SetObjectField(env, out_status, error_buf_field, uniffi_jni_consume_rust_buffer(env, status->errorBuf));
+}
+{%- for func in self.jni_functions() %}
+
+JNIEXPORT {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|jni_c_type_name }}{% when None %}void{% endmatch %} JNICALL {{ self.jni_function_name(func.name()) }}(
+    JNIEnv *env,
+    jclass cls
+    {%- for arg in func.arguments() %},
+    {{ arg.type_().borrow()|jni_c_type_name }} {{ arg.name() }}
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %},
+    jobject out_status
+    {%- endif %})
+{
+    {%- if func.has_rust_call_status_arg() %}
+    RustCallStatus status = {0};
+    {%- endif %}
+    {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|header_ffi_type_name }} result = {% when None %}{% endmatch %}{{ func.name() }}(
+        {%- for arg in func.arguments() %}
+        {{ arg.type_().borrow()|jni_c_arg(arg.name()) }}{% if !loop.last || func.has_rust_call_status_arg() %},{% endif %}
+        {%- endfor %}
+        {%- if func.has_rust_call_status_arg() %}
+        &status
+        {%- endif %});
+    {%- if func.has_rust_call_status_arg() %}
+    uniffi_jni_set_status(env, out_status, &status);
+    {%- endif %}
+    {%- match func.return_type() %}
+    {%- when Some with (return_type) %}
+    return {{ return_type|jni_c_return("result") }};
+    {%- when None %}
+    {%- endmatch %}
+}
+{%- endfor %}
@@ -0,0 +1,40 @@
+{%- import "macros.kt" as kt %}
+
+{#
+ # The declarations of the JNI bindings are rendered with the same templates as the JNA bindings,
+ # so the public API is the same.  The converters and object implementations are the ones of the
+ # Kotlin/Native bindings, in `PortableTypes.kt`.
+ #}
+{%- for type_ in ci.iter_types() %}
+{%- let type_name = type_|type_name %}
+{%- let ffi_converter_name = type_|ffi_converter_name %}
+{%- let contains_object_references = ci.item_contains_object_references(type_) %}
+{%- match type_ %}
+
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+{% include "FlagsDeclaration.kt" %}
+
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+{% include "RecordDeclaration.kt" %}
+
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if !ci.is_name_used_as_error(name) %}
+{% include "EnumDeclaration.kt" %}
+{%- else %}
+{%- let type_name = type_|error_type_name %}
+{%- let ffi_converter_name = type_|error_ffi_converter_name %}
+{% include "ErrorDeclaration.kt" %}
+{%- endif %}
+
+{%- when Type::Object { module_path, name, imp } %}
+{%- let obj = ci|get_object_definition(name) %}
+{% include "ObjectInterface.kt" %}
+
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{% include "PortableTypes.kt" %}
@@ -0,0 +1,74 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+{%- match ci.namespace_docstring() %}
+{%- when Some with (docstring) %}
+
+{{ docstring|docstring(0) }}
+{%- when None %}
+{%- endmatch %}
+
+package {{ config.package_name() }};
+
+// The JNI implementation of the bindings.
+//
+// This has the same public API as the JNA bindings, but calls the scaffolding functions through
+// the `external` functions of `UniffiLib`, which are implemented by the `{{ ci.namespace() }}_jni.c`
+// shim that's generated alongside this file.  The shim must be built as the
+// `{{ config.cdylib_name() }}_jni` library.
+
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicLong
+{%- for req in self.imports() %}
+{{ req.render() }}
+{%- endfor %}
+
+{% include "Disposable.kt" %}
+
+// `rustBacktrace` is the backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled
+class InternalException(message: String, val rustBacktrace: String? = null) : Exception(message)
+
+{% include "JniRuntime.kt" %}
+
+// Public interface members begin here.
+{{ type_helper_code }}
+
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+const val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+{%- endfor %}
+
+{%- for func in ci.function_definitions() %}
+{% call kt::callable_docstring(func, 0) %}
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+@Throws({{ throwable|error_type_name }}::class)
+{%- else -%}
+{%- endmatch -%}
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name }} {
+    return {{ return_type|lift_fn }}({% call portable::to_ffi_call(func) %})
+}
+{%- when None %}
+fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
+    {% call portable::to_ffi_call(func) %}
+{%- endmatch %}
+{%- endfor %}
+
+/**
+ * Lists the objects that Rust passed to Kotlin and that haven't been freed yet, and the callback
+ * interfaces that Rust still holds, with a backtrace of where each one was created.
+ *
+ * This needs the `handle-diagnostics` feature of the `uniffi` crate, otherwise the report only
+ * says that it's disabled.
+ */
+fun uniffiDebugLiveHandles(): String =
+    {{ Type::String.borrow()|lift_fn }}(rustCall { _status ->
+        UniffiLib.{{ ci.ffi_uniffi_debug_live_handles().name() }}(_status)
+    })
+
+{% import "macros.kt" as kt %}
+{% import "PortableMacros.kt" as portable %}
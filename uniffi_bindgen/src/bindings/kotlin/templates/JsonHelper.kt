@@ -0,0 +1,22 @@
+public object FfiConverterJson: FfiConverter<kotlinx.serialization.json.JsonElement, RustBuffer.ByValue> {
+    // JSON values are passed as JSON text, Rust validates it when it lifts them.
+    override fun lift(value: RustBuffer.ByValue): kotlinx.serialization.json.JsonElement {
+        return kotlinx.serialization.json.Json.parseToJsonElement(FfiConverterString.lift(value))
+    }
+
+    override fun read(buf: ByteBuffer): kotlinx.serialization.json.JsonElement {
+        return kotlinx.serialization.json.Json.parseToJsonElement(FfiConverterString.read(buf))
+    }
+
+    override fun lower(value: kotlinx.serialization.json.JsonElement): RustBuffer.ByValue {
+        return FfiConverterString.lower(value.toString())
+    }
+
+    override fun allocationSize(value: kotlinx.serialization.json.JsonElement): Int {
+        return FfiConverterString.allocationSize(value.toString())
+    }
+
+    override fun write(value: kotlinx.serialization.json.JsonElement, buf: ByteBuffer) {
+        FfiConverterString.write(value.toString(), buf)
+    }
+}
@@ -0,0 +1,34 @@
+/**
+ * Runs the calls to main-thread callback interfaces, which Rust may make from any thread.
+ *
+ * Set `executor` to an executor that runs tasks on the main thread, and `thread` to the main
+ * thread, before passing one of these callback interfaces to Rust.  On Android, for example, use
+ * `ContextCompat.getMainExecutor(context)` and `Looper.getMainLooper().thread`.
+ *
+ * This is only generated when a callback interface is declared with `main_thread`.
+ */
+object UniffiMainThread {
+    @Volatile
+    var executor: java.util.concurrent.Executor? = null
+
+    @Volatile
+    var thread: Thread? = null
+}
+
+internal fun <T> uniffiRunOnMainThread(call: () -> T): T {
+    // Without the thread, a call made on the main thread would wait for itself
+    val thread = UniffiMainThread.thread
+        ?: throw IllegalStateException("UniffiMainThread.thread must be set to call main thread callback interfaces")
+    if (Thread.currentThread() === thread) {
+        return call()
+    }
+    val executor = UniffiMainThread.executor
+        ?: throw IllegalStateException("UniffiMainThread.executor must be set to call main thread callback interfaces")
+    val task = java.util.concurrent.FutureTask<T> { call() }
+    executor.execute(task)
+    try {
+        return task.get()
+    } catch (e: java.util.concurrent.ExecutionException) {
+        throw e.cause ?: e
+    }
+}
@@ -18,16 +18,44 @@ private inline fun <reified Lib : Library> loadIndirect(
 
 internal interface _UniFFILib : Library {
     companion object {
+        {%- if config.hot_reload() %}
+        @Volatile
+        private var instance: _UniFFILib? = null
+
+        // Incremented by `reload()`.  Objects from an older generation belong to a library that was
+        // unloaded.
+        @Volatile
+        internal var generation = 0
+            private set
+
+        internal val INSTANCE: _UniFFILib
+            get() = instance ?: synchronized(this) {
+                instance ?: uniffiLoadLibraryCopy().also { instance = it }
+            }
+
+        @Synchronized
+        internal fun reload() {
+            val oldLib = INSTANCE
+            val lib = uniffiLoadLibraryCopy()
+            instance = lib
+            generation += 1
+            // The new library doesn't hold any callback interfaces yet.
+            {%- for cbi in ci.callback_interface_definitions() %}
+            {{ cbi|ffi_converter_name }}.clearHandles()
+            {%- endfor %}
+            uniffiUnloadLibrary(oldLib)
+        }
+        {%- else %}
         internal val INSTANCE: _UniFFILib by lazy {
             loadIndirect<_UniFFILib>(componentName = "{{ ci.namespace() }}")
             .also { lib: _UniFFILib ->
-                uniffiCheckContractApiVersion(lib)
-                uniffiCheckApiChecksums(lib)
+                uniffiCheckContract(lib)
                 {% for fn in self.initialization_fns() -%}
                 {{ fn }}(lib)
                 {% endfor -%}
             }
         }
+        {%- endif %}
     }
 
     {% for func in ci.iter_ffi_function_definitions() -%}
@@ -37,21 +65,92 @@ internal interface _UniFFILib : Library {
     {% endfor %}
 }
 
-private fun uniffiCheckContractApiVersion(lib: _UniFFILib) {
-    // Get the bindings contract version from our ComponentInterface
-    val bindings_contract_version = {{ ci.uniffi_contract_version() }}
-    // Get the scaffolding contract version by calling the into the dylib
-    val scaffolding_contract_version = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
-    if (bindings_contract_version != scaffolding_contract_version) {
-        throw RuntimeException("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+{%- if config.hot_reload() %}
+// Reloading the library during development, with the `hot_reload` option.
+//
+// The library is loaded from a temporary copy, so that it can be rebuilt while it's loaded, and
+// `uniffiReload()` switches to a copy of the rebuilt library.  Rust state doesn't survive that, so
+// objects remember the generation of the library that they're from, and they can't be used after
+// it was unloaded.  Their pointers are never freed, since they belong to the old library.
+
+private fun uniffiLoadLibraryCopy(): _UniFFILib {
+    val libraryName = findLibraryName(componentName = "{{ ci.namespace() }}")
+    val original = com.sun.jna.NativeLibrary.getInstance(libraryName)
+    val path = original.file?.toPath() ?: throw RuntimeException("Can't find the file of library $libraryName")
+    original.dispose()
+    val copyDir = java.nio.file.Files.createTempDirectory("uniffi-{{ ci.namespace() }}-")
+    val copyPath = copyDir.resolve(path.fileName)
+    java.nio.file.Files.copy(path, copyPath)
+    // The copy is removed when its library is unloaded by `reload()`, and the one that's still
+    // loaded is removed when the JVM exits.  Files registered later are deleted first.
+    copyDir.toFile().deleteOnExit()
+    copyPath.toFile().deleteOnExit()
+    val lib = Native.load<_UniFFILib>(copyPath.toString(), _UniFFILib::class.java)
+    try {
+        uniffiCheckContract(lib)
+        {% for fn in self.initialization_fns() -%}
+        {{ fn }}(lib)
+        {% endfor -%}
+    } catch (e: Throwable) {
+        uniffiUnloadLibrary(lib)
+        throw e
     }
+    return lib
 }
 
-@Suppress("UNUSED_PARAMETER")
-private fun uniffiCheckApiChecksums(lib: _UniFFILib) {
+private fun uniffiUnloadLibrary(lib: _UniFFILib) {
+    val handler = java.lang.reflect.Proxy.getInvocationHandler(lib) as Library.Handler
+    val nativeLibrary = handler.nativeLibrary
+    nativeLibrary.dispose()
+    nativeLibrary.file?.parentFile?.deleteRecursively()
+}
+
+{% endif -%}
+// What the library reports about its version, and the checksums of its functions.  This is only
+// read once the contract versions match, since the functions may not exist otherwise.
+private data class UniffiContractInfo(
+    val uniffiVersion: String,
+    val checksums: Map<String, Short>,
+)
+
+// This runs while the library is loaded, before `rustCall()` can be used, so it calls the
+// scaffolding directly.
+private fun uniffiContractInfo(lib: _UniFFILib): UniffiContractInfo {
+    val status = RustCallStatus()
+    val buf = lib.{{ ci.ffi_uniffi_contract_info().name() }}(status)
+    val byteBuf = buf.asByteBuffer()!!
+    val uniffiVersion = ByteArray(byteBuf.getInt()).also { byteBuf.get(it) }.toString(Charsets.UTF_8)
+    lib.{{ ci.ffi_rustbuffer_free().name() }}(buf, status)
+    val checksums: Map<String, Short> = mapOf(
+        {%- for (name, _checksum) in ci.iter_checksums() %}
+        "{{ name }}" to lib.{{ name }}(),
+        {%- endfor %}
+    )
+    return UniffiContractInfo(uniffiVersion, checksums)
+}
+
+// The contract version is a plain integer, so it can be read from any library, however old.
+private fun uniffiCheckContract(lib: _UniFFILib) {
+    val contractVersion = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
+    if (contractVersion != {{ ci.uniffi_contract_version() }}) {
+        throw RuntimeException(
+            "UniFFI contract version mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "(contract version {{ ci.uniffi_contract_version() }}) but the library has contract version $contractVersion. " +
+            "Try cleaning and rebuilding your project"
+        )
+    }
+    val info = uniffiContractInfo(lib)
+    val mismatched = mutableListOf<String>()
     {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if (lib.{{ name }}() != {{ expected_checksum }}.toShort()) {
-        throw RuntimeException("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    if (info.checksums["{{ name }}"] != {{ expected_checksum }}.toShort()) {
+        mismatched.add("{{ name }}")
     }
     {%- endfor %}
+    if (mismatched.isNotEmpty()) {
+        throw RuntimeException(
+            "UniFFI API checksum mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "but the library is version ${info.uniffiVersion}, and these functions don't match: " +
+            "${mismatched.joinToString()}. Try cleaning and rebuilding your project"
+        )
+    }
 }
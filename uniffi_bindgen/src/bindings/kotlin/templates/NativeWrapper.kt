@@ -0,0 +1,48 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(kotlinx.cinterop.ExperimentalForeignApi::class)
+
+package {{ config.package_name() }};
+
+// The Kotlin/Native implementation of the multiplatform bindings.
+//
+// This calls the scaffolding functions through the `{{ config.package_name() }}.cinterop`
+// package, which cinterop generates from the `{{ ci.namespace() }}.h` header.
+
+import {{ config.package_name() }}.cinterop.*
+import kotlinx.cinterop.*
+import kotlin.concurrent.AtomicInt
+import kotlin.concurrent.AtomicLong
+
+{% include "NativeRuntime.kt" %}
+
+// Public interface members begin here.
+{{ type_helper_code }}
+
+{%- for func in ci.function_definitions() %}
+{%- match func.throws_type() -%}
+{%- when Some with (throwable) %}
+
+@Throws({{ throwable|error_type_name }}::class)
+{%- else %}
+{% endmatch %}
+{%- match func.return_type() -%}
+{%- when Some with (return_type) %}
+actual fun {{ func.name()|fn_name }}({%- call kt::arg_list_protocol(func) -%}): {{ return_type|type_name }} {
+    return {{ return_type|lift_fn }}({% call portable::to_ffi_call(func) %})
+}
+{%- when None %}
+actual fun {{ func.name()|fn_name }}({% call kt::arg_list_protocol(func) %}) =
+    {% call portable::to_ffi_call(func) %}
+{%- endmatch %}
+{%- endfor %}
+
+actual fun uniffiDebugLiveHandles(): String =
+    {{ Type::String.borrow()|lift_fn }}(rustCall { _status ->
+        {{ ci.ffi_uniffi_debug_live_handles().name() }}(_status)
+    })
+
+{% import "macros.kt" as kt %}
+{% import "PortableMacros.kt" as portable %}
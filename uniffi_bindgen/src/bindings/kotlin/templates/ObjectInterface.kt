@@ -0,0 +1,25 @@
+{%- call kt::docstring(obj, 0) %}
+public interface {{ type_name }}Interface {
+    {%- for meth in obj.methods() %}
+    {%- call kt::callable_docstring(meth, 4) %}
+    {%- match meth.throws_type() %}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|error_type_name }}::class)
+    {%- when None %}
+    {%- endmatch %}
+    {%- if meth.is_async() %}
+    suspend fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
+    {%- else %}
+    fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
+    {%- endif %}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) -%}
+    {%- if meth.is_async() %}: {{ return_type|type_name -}}
+    {%- else %}: {% call kt::return_type_name(return_type) -%}
+    {%- endif -%}
+    {%- when None -%}
+    {%- endmatch %}
+    {%- if !loop.last %}
+{% endif %}
+    {%- endfor %}
+}
@@ -1,32 +1,7 @@
-// Interface implemented by anything that can contain an object reference.
-//
-// Such types expose a `destroy()` method that must be called to cleanly
-// dispose of the contained objects. Failure to call this method may result
-// in memory leaks.
-//
-// The easiest way to ensure this method is called is to use the `.use`
-// helper method to execute a block and destroy the object at the end.
-interface Disposable {
-    fun destroy()
-    companion object {
-        fun destroy(vararg args: Any?) {
-            args.filterIsInstance<Disposable>()
-                .forEach(Disposable::destroy)
-        }
-    }
-}
-
-inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
-    try {
-        block(this)
-    } finally {
-        try {
-            // N.B. our implementation is on the nullable type `Disposable?`.
-            this?.destroy()
-        } catch (e: Throwable) {
-            // swallow
-        }
-    }
+{#- Multiplatform bindings declare `Disposable` in the common code #}
+{%- if !self.multiplatform() %}
+{% include "Disposable.kt" %}
+{%- endif %}
 
 // The base class for all UniFFI Object types.
 //
@@ -99,6 +74,10 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 // called *and* all in-flight method calls have completed, avoiding violating any of the expectations
 // of the underlying Rust code.
 //
+// Methods that take `self` by value in Rust consume the object instead.  They hand the pointer over
+// to Rust, so they can only run when there are no other in-flight calls, and they leave the object
+// destroyed without freeing the pointer.
+//
 // In the future we may be able to replace some of this with automatic finalization logic, such as using
 // the new "Cleaner" functionaility in Java 9. The above scheme has been designed to work even if `destroy` is
 // invoked by garbage-collection machinery rather than by calling code (which by the way, it's apparently also
@@ -109,16 +88,45 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 //
 // [1] https://stackoverflow.com/questions/24376768/can-java-finalize-an-object-when-it-is-still-in-scope/24380219
 //
+{%- if self.open_classes() %}
+/**
+ * Passed to the constructor of an open object class by subclasses that don't wrap a Rust object,
+ * like test doubles.
+ */
+object NoPointer
+{% endif %}
 abstract class FFIObject(
     protected val pointer: Pointer
 ): Disposable, AutoCloseable {
 
     private val wasDestroyed = AtomicBoolean(false)
+    private val wasConsumed = AtomicBoolean(false)
     private val callCounter = AtomicLong(1)
+    {%- if self.hot_reload() %}
+    // The generation of the library that the pointer belongs to, see `uniffiReload()`
+    internal val generation = _UniFFILib.generation
+    {%- endif %}
+
+    {%- if self.open_classes() %}
+
+    // An object without a pointer starts out destroyed, so it's never passed to Rust or freed.
+    protected constructor(@Suppress("UNUSED_PARAMETER") noPointer: NoPointer): this(Pointer(0)) {
+        this.wasDestroyed.set(true)
+        this.callCounter.set(0)
+    }
+    {%- endif %}
 
     open protected fun freeRustArcPtr() {
         // To be overridden in subclasses.
     }
+    {%- if self.hot_reload() %}
+
+    internal fun checkGeneration() {
+        check(this.generation == _UniFFILib.generation) {
+            "${this.javaClass.simpleName} object is from before the library was reloaded"
+        }
+    }
+    {%- endif %}
 
     override fun destroy() {
         // Only allow a single call to this method.
@@ -137,12 +145,16 @@ abstract class FFIObject(
     }
 
     internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        {%- if self.hot_reload() %}
+        this.checkGeneration()
+        {%- endif %}
         // Check and increment the call counter, to keep the object alive.
         // This needs a compare-and-set retry loop in case of concurrent updates.
         do {
             val c = this.callCounter.get()
             if (c == 0L) {
-                throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+                val state = if (this.wasConsumed.get()) "consumed" else "destroyed"
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been $state")
             }
             if (c == Long.MAX_VALUE) {
                 throw IllegalStateException("${this.javaClass.simpleName} call counter would overflow")
@@ -158,4 +170,23 @@ abstract class FFIObject(
             }
         }
     }
+
+    internal inline fun <R> consumeWithPointer(block: (ptr: Pointer) -> R): R {
+        {%- if self.hot_reload() %}
+        this.checkGeneration()
+        {%- endif %}
+        // Take the count of 1 given at creation time, which requires that there are no in-flight
+        // calls.  The pointer is never freed after that, Rust takes ownership of it instead.
+        if (!this.callCounter.compareAndSet(1L, 0L)) {
+            val state = when {
+                this.wasConsumed.get() -> "has already been consumed"
+                this.wasDestroyed.get() -> "has already been destroyed"
+                else -> "is in use by another call"
+            }
+            throw IllegalStateException("${this.javaClass.simpleName} object $state")
+        }
+        this.wasConsumed.set(true)
+        this.wasDestroyed.set(true)
+        return block(this.pointer)
+    }
 }
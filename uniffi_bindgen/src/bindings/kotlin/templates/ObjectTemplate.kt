@@ -3,33 +3,42 @@
 {{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
 {{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
 
-public interface {{ type_name }}Interface {
-    {% for meth in obj.methods() -%}
-    {%- match meth.throws_type() -%}
-    {%- when Some with (throwable) -%}
-    @Throws({{ throwable|error_type_name }}::class)
-    {%- when None -%}
-    {%- endmatch %}
-    {% if meth.is_async() -%}
-    suspend fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
-    {%- else -%}
-    fun {{ meth.name()|fn_name }}({% call kt::arg_list_decl(meth) %})
-    {%- endif %}
-    {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %}: {{ return_type|type_name -}}
-    {%- when None -%}
-    {%- endmatch -%}
+{#- Multiplatform bindings declare the interface in the common code #}
+{%- if !self.multiplatform() %}
+{% include "ObjectInterface.kt" %}
+{%- endif %}
+{%- match obj.concurrency() %}
+{%- when Some with (policy) %}
+{%- if policy.name() == "serialized" %}
+// Calls to this object can come from several threads, the Rust code runs one at a time.
+{%- else %}
+// Calls to this object can come from several threads, the Rust object handles them concurrently.
+{%- endif %}
+{%- when None %}
+{%- endmatch %}
+{% call kt::docstring(obj, 0) %}
+{%- if self.open_classes() %}
+open class {{ type_name }} : FFIObject, {{ type_name }}Interface{% if obj.is_ord() %}, Comparable<{{ type_name }}>{% endif %} {
 
-    {% endfor %}
-}
+    constructor(pointer: Pointer) : super(pointer)
 
-class {{ type_name }}(
+    /**
+     * This constructor can be used by subclasses that don't wrap a Rust object, like test doubles.
+     *
+     * The methods that they don't override throw an `IllegalStateException`, and they can't be
+     * passed to Rust.
+     */
+    protected constructor(noPointer: NoPointer) : super(noPointer)
+{%- else %}
+{% call kt::actual() %}class {{ type_name }}(
     pointer: Pointer
-) : FFIObject(pointer), {{ type_name }}Interface {
+) : FFIObject(pointer), {{ type_name }}Interface{% if obj.is_ord() %}, Comparable<{{ type_name }}>{% endif %} {
+{%- endif %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
-    constructor({% call kt::arg_list_decl(cons) -%}) :
+    {%- call kt::callable_docstring(cons, 4) %}
+    {% call kt::actual() %}constructor({% call kt::arg_list_impl(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
     {%- when None %}
     {%- endmatch %}
@@ -43,6 +52,12 @@ class {{ type_name }}(
      * Clients **must** call this method once done with the object, or cause a memory leak.
      */
     override protected fun freeRustArcPtr() {
+        {%- if self.hot_reload() %}
+        if (this.generation != _UniFFILib.generation) {
+            // The pointer belongs to a library that was unloaded
+            return
+        }
+        {%- endif %}
         rustCall() { status ->
             _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(this.pointer, status)
         }
@@ -67,6 +82,7 @@ class {{ type_name }}(
                     uniffiActiveFutureCallbacks.add(callback)
                     continuation.invokeOnCancellation { uniffiActiveFutureCallbacks.remove(callback) }
                     callWithPointer { thisPtr ->
+                        {% call kt::record_usage_start(meth) -%}
                         rustCall { status ->
                             _UniFFILib.INSTANCE.{{ meth.ffi_func().name() }}(
                                 thisPtr,
@@ -77,6 +93,7 @@ class {{ type_name }}(
                                 status,
                             )
                         }
+                        {%- call kt::record_usage_end(meth) %}
                     }
                 } catch (e: Exception) {
                     continuation.resumeWithException(e)
@@ -87,28 +104,105 @@ class {{ type_name }}(
     {%- else -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) -%}
-    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}): {{ return_type|type_name }} =
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}): {% call kt::return_type_name(return_type) %} =
+        {%- if meth.takes_self_by_value() %}
+        run {
+            {%- call kt::lower_args_into_locals(meth) %}
+            consumeWithPointer {
+                {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+            }
+        }.let {
+            {% call kt::return_lift_fn(return_type) %}(it)
+        }
+        {%- else %}
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
-            {{ return_type|lift_fn }}(it)
+            {% call kt::return_lift_fn(return_type) %}(it)
         }
+        {%- endif %}
 
     {%- when None -%}
     override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}) =
+        {%- if meth.takes_self_by_value() %}
+        run {
+            {%- call kt::lower_args_into_locals(meth) %}
+            consumeWithPointer {
+                {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+            }
+        }
+        {%- else %}
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
+        {%- endif %}
     {% endmatch %}
     {% endif %}
     {% endfor %}
 
-    {% if !obj.alternate_constructors().is_empty() -%}
-    companion object {
-        {% for cons in obj.alternate_constructors() -%}
-        fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ type_name }} =
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Display { fmt } %}
+    override fun toString(): String =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", fmt) %}
+        }.let {
+            {{ fmt.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%- when UniffiTrait::Eq { eq, ne } %}
+    {#- `!=` is derived from `equals()` in Kotlin, so only `eq` is used #}
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is {{ type_name }}) return false
+        return callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", eq) %}
+        }.let {
+            {{ eq.return_type().unwrap()|lift_fn }}(it)
+        }
+    }
+    {%- when UniffiTrait::Hash { hash } %}
+    override fun hashCode(): Int =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", hash) %}
+        }.let {
+            {{ hash.return_type().unwrap()|lift_fn }}(it).toInt()
+        }
+    {%- when UniffiTrait::Ord { cmp } %}
+    override fun compareTo(other: {{ type_name }}): Int =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", cmp) %}
+        }.let {
+            {{ cmp.return_type().unwrap()|lift_fn }}(it).toInt()
+        }
+    {%- when UniffiTrait::Restore { serialize, restore } %}
+    {% call kt::actual() %}fun serializeHandle(): String =
+        callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", serialize) %}
+        }.let {
+            {{ serialize.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+
+    {% if !obj.alternate_constructors().is_empty() || obj.restore_handle_fn().is_some() || !obj.constants().is_empty() -%}
+    {% call kt::actual() %}companion object {
+        {%- for c in obj.constants() %}
+        {% call kt::actual() %}val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+        {%- endfor %}
+
+        {%- for cons in obj.alternate_constructors() %}
+        {%- call kt::callable_docstring(cons, 8) %}
+        {% call kt::actual() %}fun {{ cons.name()|fn_name }}({% call kt::arg_list_impl(cons) %}): {{ type_name }} =
             {{ type_name }}({% call kt::to_ffi_call(cons) %})
-        {% endfor %}
+        {%- endfor %}
+        {%- match obj.restore_handle_fn() %}
+        {%- when Some with (restore) %}
+        {%- let restore_type = restore.return_type().unwrap() %}
+        {% call kt::actual() %}fun restoreHandle(token: String): {{ type_name }}? =
+            {{ restore_type|lift_fn }}({% call kt::to_ffi_call(restore) %})
+        {%- else %}
+        {%- endmatch %}
     }
     {% endif %}
 }
@@ -0,0 +1,21 @@
+public object FfiConverterPath: FfiConverterRustBuffer<java.io.File> {
+    // Paths are passed as their raw bytes.  Rust sends them as they are on disk, which on Unix
+    // might not be valid UTF-8, any invalid sequences are replaced when they're decoded here.
+    override fun read(buf: ByteBuffer): java.io.File {
+        return java.io.File(String(FfiConverterByteArray.read(buf), Charsets.UTF_8))
+    }
+
+    override fun allocationSize(value: java.io.File): Int {
+        return FfiConverterByteArray.allocationSize(value.path.toByteArray(Charsets.UTF_8))
+    }
+
+    override fun write(value: java.io.File, buf: ByteBuffer) {
+        FfiConverterByteArray.write(value.path.toByteArray(Charsets.UTF_8), buf)
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiFileSerializer") }}
+
+object UniffiFileSerializer: UniffiStringSerializer<java.io.File>("java.io.File", { it.path }, { java.io.File(it) })
+{%- endif %}
@@ -0,0 +1,85 @@
+{#
+// Template to call into rust from the Kotlin/Native and JNI bindings.  The Kotlin/Native bindings
+// call the scaffolding functions that cinterop generated from the header, the JNI bindings call the
+// `external` functions of `UniffiLib`.  Variable names in `kt::arg_list_protocol` should match up
+// with arg lists passed to rust via `arg_list_lowered`
+#}
+
+{%- macro to_ffi_call(func) -%}
+    {%- call borrow_objects_start(func) %}
+    {%- match func.throws_type() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|error_type_name }}{% if !self.jni() %}ErrorHandler{% endif %})
+    {%- else %}
+    rustCall()
+    {%- endmatch %} { _status ->
+    {% if self.jni() %}UniffiLib.{% endif %}{{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} _status)
+}
+    {%- call borrow_objects_end(func) %}
+{%- endmacro -%}
+
+{%- macro to_ffi_call_with_prefix(prefix, func) %}
+    {%- call borrow_objects_start(func) %}
+    {%- match func.throws_type() %}
+    {%- when Some with (e) %}
+    rustCallWithError({{ e|error_type_name }}{% if !self.jni() %}ErrorHandler{% endif %})
+    {%- else %}
+    rustCall()
+    {%- endmatch %} { _status ->
+    {% if self.jni() %}UniffiLib.{% endif %}{{ func.ffi_func().name() }}(
+        {{- prefix }},
+        {% call arg_list_lowered(func) %}
+        _status)
+}
+    {%- call borrow_objects_end(func) %}
+{%- endmacro %}
+
+{#-
+// See `borrow_objects_start` in `macros.kt`
+-#}
+{%- macro borrow_objects_start(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_object() %}{{ self.field_name(arg.name()) }}.callWithPointer { uniffiBorrowed{{ loop.index }} -> {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro borrow_objects_end(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_object() %} }{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro arg_list_lowered(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.borrows_object() %}
+        uniffiBorrowed{{ loop.index }},
+        {%- else %}
+        {{- arg|lower_fn }}({{ self.field_name(arg.name()) }}),
+        {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// A converter for a numeric type.  cinterop passes it as the Kotlin type, JNI as the signed Kotlin
+// type of the same size, `jni_type_name`.
+-#}
+{%- macro numeric_converter(type_name, jni_type_name, ffi_converter_name, read_fn, write_fn) %}
+{%- if self.jni() %}
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, {{ jni_type_name }}> {
+    override fun lift(value: {{ jni_type_name }}) = value.to{{ type_name }}()
+
+    override fun lower(value: {{ type_name }}) = value.to{{ jni_type_name }}()
+{%- else %}
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, {{ type_name }}> {
+    override fun lift(value: {{ type_name }}) = value
+
+    override fun lower(value: {{ type_name }}) = value
+{%- endif %}
+
+    override fun read(buf: UniffiReader) = buf.{{ read_fn }}()
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        buf.{{ write_fn }}(value)
+    }
+}
+{%- endmacro %}
@@ -0,0 +1,550 @@
+{%- import "macros.kt" as kt %}
+{%- import "PortableMacros.kt" as portable %}
+
+// The FfiConverter interface handles converter types to and from the FFI.  Unlike the JNA
+// bindings, the serialized form is built up in a `UniffiWriter` and then copied into a
+// `RustBuffer`, so there's no need to calculate its size first.  The JNI bindings pass the
+// contents of `RustBuffer`s as byte arrays, and the shim does the copying.
+public interface FfiConverter<KotlinType, FfiType> {
+    // Convert an FFI type to a Kotlin type
+    fun lift(value: FfiType): KotlinType
+
+    // Convert an Kotlin type to an FFI type
+    fun lower(value: KotlinType): FfiType
+
+    // Read a Kotlin type from a `UniffiReader`
+    fun read(buf: UniffiReader): KotlinType
+
+    // Write a Kotlin type to a `UniffiWriter`
+    fun write(value: KotlinType, buf: UniffiWriter)
+
+    // Lower a value into a `RustBuffer`
+    fun lowerIntoRustBuffer(value: KotlinType): {{ self.rust_buffer_type() }} {
+        val buf = UniffiWriter()
+        write(value, buf)
+        {%- if self.jni() %}
+        return buf.toByteArray()
+        {%- else %}
+        return rustBufferFrom(buf.toByteArray())
+        {%- endif %}
+    }
+
+    // Lift a value from a `RustBuffer`
+    fun liftFromRustBuffer(rbuf: {{ self.rust_buffer_type() }}): KotlinType {
+        {%- if self.jni() %}
+        val buf = UniffiReader(rbuf)
+        {%- else %}
+        val buf = UniffiReader(rbuf.consumeBytes())
+        {%- endif %}
+        val item = read(buf)
+        if (buf.hasRemaining()) {
+            throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+        }
+        return item
+    }
+}
+
+// FfiConverter that uses `RustBuffer` as the FfiType
+public interface FfiConverterRustBuffer<KotlinType>: FfiConverter<KotlinType, {{ self.rust_buffer_type() }}> {
+    override fun lift(value: {{ self.rust_buffer_type() }}) = liftFromRustBuffer(value)
+    override fun lower(value: KotlinType) = lowerIntoRustBuffer(value)
+}
+
+{%- for type_ in ci.iter_types() %}
+{%- let type_name = type_|type_name %}
+{%- let ffi_converter_name = type_|ffi_converter_name %}
+
+{#
+ # The Kotlin/Native and JNI counterpart of `Types.kt`, for the types that
+ # `check_portable_support()` accepts.  The backends only differ in the FFI types, see
+ # `numeric_converter` for the numeric ones, and in the JNI bindings the declarations come from
+ # `JniTypes.kt`, so the objects implement their interfaces instead of `expect` classes.
+ #}
+{%- match type_ %}
+
+{%- when Type::Boolean %}
+
+public object FfiConverterBoolean: FfiConverter<Boolean, Byte> {
+    override fun lift(value: Byte) = value.toInt() != 0
+
+    override fun lower(value: Boolean): Byte = if (value) 1 else 0
+
+    override fun read(buf: UniffiReader) = lift(buf.get())
+
+    override fun write(value: Boolean, buf: UniffiWriter) {
+        buf.put(lower(value))
+    }
+}
+
+{%- when Type::Int8 %}
+{% call portable::numeric_converter(type_name, "Byte", ffi_converter_name, "get", "put") %}
+
+{%- when Type::Int16 %}
+{% call portable::numeric_converter(type_name, "Short", ffi_converter_name, "getShort", "putShort") %}
+
+{%- when Type::Int32 %}
+{% call portable::numeric_converter(type_name, "Int", ffi_converter_name, "getInt", "putInt") %}
+
+{%- when Type::Int64 %}
+{% call portable::numeric_converter(type_name, "Long", ffi_converter_name, "getLong", "putLong") %}
+
+{%- when Type::UInt8 %}
+{% call portable::numeric_converter(type_name, "Byte", ffi_converter_name, "getUByte", "putUByte") %}
+
+{%- when Type::UInt16 %}
+{% call portable::numeric_converter(type_name, "Short", ffi_converter_name, "getUShort", "putUShort") %}
+
+{%- when Type::UInt32 %}
+{% call portable::numeric_converter(type_name, "Int", ffi_converter_name, "getUInt", "putUInt") %}
+
+{%- when Type::UInt64 %}
+{% call portable::numeric_converter(type_name, "Long", ffi_converter_name, "getULong", "putULong") %}
+
+{%- when Type::Float32 %}
+{% call portable::numeric_converter(type_name, "Float", ffi_converter_name, "getFloat", "putFloat") %}
+
+{%- when Type::Float64 %}
+{% call portable::numeric_converter(type_name, "Double", ffi_converter_name, "getDouble", "putDouble") %}
+
+{%- when Type::String %}
+
+public object FfiConverterString: FfiConverter<String, {{ self.rust_buffer_type() }}> {
+    // Strings are lifted and lowered as their bare UTF-8 bytes, the `RustBuffer`'s length
+    // is the length of the string.
+    {%- if self.jni() %}
+    override fun lift(value: ByteArray) = value.decodeToString()
+
+    override fun lower(value: String) = toUtf8(value)
+    {%- else %}
+    override fun lift(value: CValue<RustBuffer>) = value.consumeBytes().decodeToString()
+
+    override fun lower(value: String) = rustBufferFrom(toUtf8(value))
+    {%- endif %}
+
+    override fun read(buf: UniffiReader) = buf.getBytes(buf.getInt()).decodeToString()
+
+    override fun write(value: String, buf: UniffiWriter) {
+        val bytes = toUtf8(value)
+        buf.putInt(bytes.size)
+        buf.putBytes(bytes)
+    }
+
+    // Make sure we don't have invalid UTF-16, by failing on lone surrogates.
+    private fun toUtf8(value: String) = value.encodeToByteArray(throwOnInvalidSequence = true)
+}
+
+{%- when Type::Bytes %}
+
+public object FfiConverterByteArray: FfiConverterRustBuffer<ByteArray> {
+    override fun read(buf: UniffiReader) = buf.getBytes(buf.getInt())
+
+    override fun write(value: ByteArray, buf: UniffiWriter) {
+        buf.putInt(value.size)
+        buf.putBytes(value)
+    }
+}
+
+{%- when Type::Optional { inner_type } %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ inner_type_name }}?> {
+    override fun read(buf: UniffiReader): {{ inner_type_name }}? {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return {{ inner_type|read_fn }}(buf)
+    }
+
+    override fun write(value: {{ inner_type_name }}?, buf: UniffiWriter) {
+        if (value == null) {
+            buf.put(0)
+        } else {
+            buf.put(1)
+            {{ inner_type|write_fn }}(value, buf)
+        }
+    }
+}
+
+{%- when Type::Sequence { inner_type } %}
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<List<{{ inner_type_name }}>> {
+    override fun read(buf: UniffiReader): List<{{ inner_type_name }}> {
+        val len = buf.getInt()
+        return List<{{ inner_type_name }}>(len) {
+            {{ inner_type|read_fn }}(buf)
+        }
+    }
+
+    override fun write(value: List<{{ inner_type_name }}>, buf: UniffiWriter) {
+        buf.putInt(value.size)
+        value.forEach {
+            {{ inner_type|write_fn }}(it, buf)
+        }
+    }
+}
+
+{%- when Type::Map { key_type, value_type } %}
+{%- let key_type_name = key_type|type_name %}
+{%- let value_type_name = value_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<Map<{{ key_type_name }}, {{ value_type_name }}>> {
+    override fun read(buf: UniffiReader): Map<{{ key_type_name }}, {{ value_type_name }}> {
+        val items : MutableMap<{{ key_type_name }}, {{ value_type_name }}> = mutableMapOf()
+        val len = buf.getInt()
+        repeat(len) {
+            val k = {{ key_type|read_fn }}(buf)
+            val v = {{ value_type|read_fn }}(buf)
+            items[k] = v
+        }
+        return items
+    }
+
+    override fun write(value: Map<{{ key_type_name }}, {{ value_type_name }}>, buf: UniffiWriter) {
+        buf.putInt(value.size)
+        value.forEach { (k, v) ->
+            {{ key_type|write_fn }}(k, buf)
+            {{ value_type|write_fn }}(v, buf)
+        }
+    }
+}
+
+{%- when Type::Flags { name, module_path } %}
+
+{%- if self.jni() %}
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, Int> {
+    override fun lift(value: Int) = {{ type_name }}(value.toUInt())
+
+    override fun lower(value: {{ type_name }}) = value.bits.toInt()
+{%- else %}
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, UInt> {
+    override fun lift(value: UInt) = {{ type_name }}(value)
+
+    override fun lower(value: {{ type_name }}) = value.bits
+{%- endif %}
+
+    override fun read(buf: UniffiReader) = {{ type_name }}(buf.getUInt())
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        buf.putUInt(value.bits)
+    }
+}
+
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        return {{ type_name }}(
+        {%- for field in rec.fields() %}
+            {{ field|read_fn }}(buf),
+        {%- endfor %}
+        )
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        {%- for field in rec.fields() %}
+            {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
+        {%- endfor %}
+    }
+}
+
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if !ci.is_name_used_as_error(name) %}
+{%- if e.is_flat() %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    {%- if e.is_non_exhaustive() %}
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        val index = buf.getInt()
+        // Skip the fields of variants that were added after these bindings were generated
+        buf.getBytes(buf.getInt())
+        return when(index) {
+            {%- for variant in e.variants() %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|variant_name }}
+            {%- endfor %}
+            else -> {{ type_name }}.UNKNOWN
+        }
+    }
+    {%- else %}
+    override fun read(buf: UniffiReader) = try {
+        {{ type_name }}.values()[buf.getInt() - 1]
+    } catch (e: IndexOutOfBoundsException) {
+        throw RuntimeException("invalid enum value, something is very wrong!!", e)
+    }
+    {%- endif %}
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        {%- if e.is_non_exhaustive() %}
+        if (value == {{ type_name }}.UNKNOWN) {
+            throw IllegalArgumentException("{{ type_name }}.UNKNOWN can't be passed to Rust")
+        }
+        {%- endif %}
+        buf.putInt(value.ordinal + 1)
+    }
+}
+{%- else %}
+
+public object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}>{
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        {%- if e.is_non_exhaustive() %}
+        val index = buf.getInt()
+        // The size of the fields, to skip variants that were added after these bindings were generated
+        val size = buf.getInt()
+        return when(index) {
+        {%- else %}
+        return when(buf.getInt()) {
+        {%- endif %}
+            {%- for variant in e.variants() %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|enum_variant|type_name }}{% if variant.has_fields() %}(
+                {% for field in variant.fields() -%}
+                {{ field|read_fn }}(buf),
+                {% endfor -%}
+            ){%- endif -%}
+            {%- endfor %}
+            {%- if e.is_non_exhaustive() %}
+            else -> {
+                buf.getBytes(size)
+                {{ type_name }}.Unknown
+            }
+            {%- else %}
+            else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+            {%- endif %}
+        }
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        when(value) {
+            {%- for variant in e.variants() %}
+            is {{ type_name }}.{{ variant|enum_variant|type_name }} -> {
+                buf.putInt({{ loop.index }})
+                {%- for field in variant.fields() %}
+                {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
+                {%- endfor %}
+                Unit
+            }
+            {%- endfor %}
+            {%- if e.is_non_exhaustive() %}
+            is {{ type_name }}.Unknown -> throw IllegalArgumentException("{{ type_name }}.Unknown can't be passed to Rust")
+            {%- endif %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+}
+{%- endif %}
+{%- else %}
+{%- let type_name = type_|error_type_name %}
+{%- let ffi_converter_name = type_|error_ffi_converter_name %}
+
+public object {{ ffi_converter_name }} : FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        return when(buf.getInt()) {
+            {%- for variant in e.variants() %}
+            {%- if e.is_flat() %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|error_variant|type_name }}({{ Type::String.borrow()|read_fn }}(buf){% for field in variant.fields() %}, {{ field|read_fn }}(buf){% endfor %})
+            {%- else %}
+            {{ loop.index }} -> {{ type_name }}.{{ variant|error_variant|type_name }}({% if variant.has_fields() %}
+                {% for field in variant.fields() -%}
+                {{ field|read_fn }}(buf),
+                {% endfor -%}
+            {%- endif -%})
+            {%- endif %}
+            {%- endfor %}
+            else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+        }
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        when(value) {
+            {%- for variant in e.variants() %}
+            is {{ type_name }}.{{ variant|error_variant|type_name }} -> {
+                buf.putInt({{ loop.index }})
+                {#- Rust only reads the variant of flat errors #}
+                {%- if !e.is_flat() %}
+                {%- for field in variant.fields() %}
+                {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
+                {%- endfor %}
+                {%- endif %}
+                Unit
+            }
+            {%- endfor %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+}
+
+{%- if !self.jni() %}
+
+object {{ type_name }}ErrorHandler : CallStatusErrorHandler<{{ type_name }}> {
+    override fun lift(error_buf: CValue<RustBuffer>): {{ type_name }} = {{ ffi_converter_name }}.lift(error_buf)
+}
+{%- endif %}
+{%- endif %}
+
+{%- when Type::Object { module_path, name, imp } %}
+{%- let obj = ci|get_object_definition(name) %}
+{%- if self.jni() %}
+{% call kt::docstring(obj, 0) %}
+class {{ type_name }} internal constructor(
+    pointer: Long
+{%- else %}
+
+actual class {{ type_name }} internal constructor(
+    pointer: COpaquePointer?
+{%- endif %}
+) : FFIObject(pointer), {{ type_name }}Interface{% if obj.is_ord() %}, Comparable<{{ type_name }}>{% endif %} {
+
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    {%- if self.jni() %}
+    {%- call kt::callable_docstring(cons, 4) %}
+    constructor({% call kt::arg_list_decl(cons) -%}) :
+    {%- else %}
+    actual constructor({% call kt::arg_list_protocol(cons) -%}) :
+    {%- endif %}
+        this({% call portable::to_ffi_call(cons) %})
+    {%- when None %}
+    {%- endmatch %}
+    {%- if self.jni() %}
+
+    /**
+     * Disconnect the object from the underlying Rust object.
+     *
+     * It can be called more than once, but once called, interacting with the object
+     * causes an `IllegalStateException`.
+     *
+     * Clients **must** call this method once done with the object, or cause a memory leak.
+     */
+    {%- endif %}
+
+    override protected fun freeRustArcPtr() {
+        rustCall() { status ->
+            {% if self.jni() %}UniffiLib.{% endif %}{{ obj.ffi_object_free().name() }}(this.pointer, status)
+        }
+    }
+
+    {% for meth in obj.methods() -%}
+    {%- match meth.throws_type() -%}
+    {%- when Some with (throwable) %}
+    @Throws({{ throwable|error_type_name }}::class)
+    {%- else -%}
+    {%- endmatch -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) -%}
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}): {{ return_type|type_name }} =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", meth) %}
+        }.let {
+            {{ return_type|lift_fn }}(it)
+        }
+
+    {%- when None -%}
+    override fun {{ meth.name()|fn_name }}({% call kt::arg_list_protocol(meth) %}) =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", meth) %}
+        }
+    {% endmatch %}
+    {% endfor %}
+
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Display { fmt } %}
+    override fun toString(): String =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", fmt) %}
+        }.let {
+            {{ fmt.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%- when UniffiTrait::Eq { eq, ne } %}
+    {#- `!=` is derived from `equals()` in Kotlin, so only `eq` is used #}
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is {{ type_name }}) return false
+        return callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", eq) %}
+        }.let {
+            {{ eq.return_type().unwrap()|lift_fn }}(it)
+        }
+    }
+    {%- when UniffiTrait::Hash { hash } %}
+    override fun hashCode(): Int =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", hash) %}
+        }.let {
+            {{ hash.return_type().unwrap()|lift_fn }}(it).toInt()
+        }
+    {%- when UniffiTrait::Ord { cmp } %}
+    override fun compareTo(other: {{ type_name }}): Int =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", cmp) %}
+        }.let {
+            {{ cmp.return_type().unwrap()|lift_fn }}(it).toInt()
+        }
+    {%- when UniffiTrait::Restore { serialize, restore } %}
+    {% if !self.jni() %}actual {% endif %}fun serializeHandle(): String =
+        callWithPointer {
+            {%- call portable::to_ffi_call_with_prefix("it", serialize) %}
+        }.let {
+            {{ serialize.return_type().unwrap()|lift_fn }}(it)
+        }
+    {%- else %}
+    {%- endmatch %}
+    {%- endfor %}
+
+    {% if !obj.alternate_constructors().is_empty() || obj.restore_handle_fn().is_some() || !obj.constants().is_empty() -%}
+    {% if !self.jni() %}actual {% endif %}companion object {
+        {%- for c in obj.constants() %}
+        {% if !self.jni() %}actual {% endif %}val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+        {%- endfor %}
+
+        {%- for cons in obj.alternate_constructors() %}
+        {%- if self.jni() %}
+        {%- call kt::callable_docstring(cons, 8) %}
+        fun {{ cons.name()|fn_name }}({% call kt::arg_list_decl(cons) %}): {{ type_name }} =
+        {%- else %}
+        actual fun {{ cons.name()|fn_name }}({% call kt::arg_list_protocol(cons) %}): {{ type_name }} =
+        {%- endif %}
+            {{ type_name }}({% call portable::to_ffi_call(cons) %})
+        {%- endfor %}
+        {%- match obj.restore_handle_fn() %}
+        {%- when Some with (restore) %}
+        {%- let restore_type = restore.return_type().unwrap() %}
+        {% if !self.jni() %}actual {% endif %}fun restoreHandle(token: String): {{ type_name }}? =
+            {{ restore_type|lift_fn }}({% call portable::to_ffi_call(restore) %})
+        {%- else %}
+        {%- endmatch %}
+    }
+    {% endif %}
+}
+
+public object {{ ffi_converter_name }}: FfiConverter<{{ type_name }}, {{ self.pointer_type() }}> {
+    override fun lower(value: {{ type_name }}): {{ self.pointer_type() }} = value.callWithPointer { it }
+
+    override fun lift(value: {{ self.pointer_type() }}): {{ type_name }} {
+        return {{ type_name }}(value)
+    }
+
+    override fun read(buf: UniffiReader): {{ type_name }} {
+        // The Rust code always writes pointers as 8 bytes, and will
+        // fail to compile if they don't fit.
+        {%- if self.jni() %}
+        return lift(buf.getLong())
+        {%- else %}
+        return lift(buf.getLong().toCPointer<CPointed>())
+        {%- endif %}
+    }
+
+    override fun write(value: {{ type_name }}, buf: UniffiWriter) {
+        // The Rust code always expects pointers written as 8 bytes,
+        // and will fail to compile if they don't fit.
+        {%- if self.jni() %}
+        buf.putLong(lower(value))
+        {%- else %}
+        buf.putLong(lower(value).toLong())
+        {%- endif %}
+    }
+}
+
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
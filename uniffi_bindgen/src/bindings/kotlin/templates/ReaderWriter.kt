@@ -0,0 +1,103 @@
+// Reads values from the serialized form used to pass them in a `RustBuffer`.  Like the JVM's
+// `ByteBuffer`, which the JNA bindings use for this, numbers are big-endian.
+class UniffiReader(private val bytes: ByteArray) {
+    private var position = 0
+
+    fun hasRemaining() = position < bytes.size
+
+    fun get(): Byte {
+        checkRemaining(1)
+        return bytes[position++]
+    }
+
+    fun getShort(): Short = (((get().toInt() and 0xff) shl 8) or (get().toInt() and 0xff)).toShort()
+
+    fun getInt(): Int {
+        var value = 0
+        repeat(4) { value = (value shl 8) or (get().toInt() and 0xff) }
+        return value
+    }
+
+    fun getLong(): Long {
+        var value = 0L
+        repeat(8) { value = (value shl 8) or (get().toLong() and 0xffL) }
+        return value
+    }
+
+    fun getUByte() = get().toUByte()
+
+    fun getUShort() = getShort().toUShort()
+
+    fun getUInt() = getInt().toUInt()
+
+    fun getULong() = getLong().toULong()
+
+    fun getFloat() = Float.fromBits(getInt())
+
+    fun getDouble() = Double.fromBits(getLong())
+
+    fun getBytes(len: Int): ByteArray {
+        checkRemaining(len)
+        return bytes.copyOfRange(position, position + len).also { position += len }
+    }
+
+    private fun checkRemaining(len: Int) {
+        if (len < 0 || bytes.size - position < len) {
+            throw InternalException("not enough data left in buffer, something is very wrong!!")
+        }
+    }
+}
+
+// Writes values in the serialized form used to pass them in a `RustBuffer`
+class UniffiWriter {
+    private var bytes = ByteArray(64)
+    private var size = 0
+
+    fun put(value: Byte) {
+        reserve(1)
+        bytes[size++] = value
+    }
+
+    fun putShort(value: Short) {
+        put((value.toInt() shr 8).toByte())
+        put(value.toByte())
+    }
+
+    fun putInt(value: Int) {
+        for (shift in 24 downTo 0 step 8) {
+            put((value shr shift).toByte())
+        }
+    }
+
+    fun putLong(value: Long) {
+        for (shift in 56 downTo 0 step 8) {
+            put((value shr shift).toByte())
+        }
+    }
+
+    fun putUByte(value: UByte) = put(value.toByte())
+
+    fun putUShort(value: UShort) = putShort(value.toShort())
+
+    fun putUInt(value: UInt) = putInt(value.toInt())
+
+    fun putULong(value: ULong) = putLong(value.toLong())
+
+    fun putFloat(value: Float) = putInt(value.toRawBits())
+
+    fun putDouble(value: Double) = putLong(value.toRawBits())
+
+    fun putBytes(value: ByteArray) {
+        reserve(value.size)
+        value.copyInto(bytes, size)
+        size += value.size
+    }
+
+    fun toByteArray() = bytes.copyOf(size)
+
+    private fun reserve(len: Int) {
+        if (size + len > bytes.size) {
+            bytes = bytes.copyOf(maxOf(bytes.size * 2, size + len))
+        }
+    }
+}
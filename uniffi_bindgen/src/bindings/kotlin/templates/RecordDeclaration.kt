@@ -0,0 +1,64 @@
+{#- Objects are only valid in this process, so records that hold them can't be parcelled or serialized #}
+{%- let parcelable = self.parcelize() && !contains_object_references %}
+{%- if parcelable %}
+{{- self.add_import("kotlinx.parcelize.Parcelize") }}
+{{- self.add_import("android.os.Parcelable") }}
+{%- endif %}
+{%- let serializable = self.serializable() && !contains_object_references %}
+{%- if serializable %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{%- endif %}
+{% call kt::docstring(rec, 0) %}
+{% if parcelable -%}
+@Parcelize
+{% endif -%}
+{% if serializable -%}
+@Serializable
+{% endif -%}
+data class {{ type_name }} (
+    {%- for field in rec.fields() %}
+    var {{ self.field_name(field.name()) }}: {{ field|type_name -}}
+    {%- match field.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
+        {%- else %}
+    {%- endmatch -%}
+    {% if !loop.last %}, {% endif %}
+    {%- endfor %}
+{#- Multiplatform and JNI bindings reject records that forward `Ord`, see `check_portable_support()` #}
+{%- let comparable = rec.is_ord() %}
+) {% if contains_object_references %}: Disposable{% if comparable %}, Comparable<{{ type_name }}>{% endif %} {% else if parcelable %}: Parcelable{% if comparable %}, Comparable<{{ type_name }}>{% endif %} {% else if comparable %}: Comparable<{{ type_name }}> {% endif %}{
+    {% if contains_object_references %}
+    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
+    override fun destroy() {
+        {% call kt::destroy_fields(rec) %}
+    }
+    {% endif %}
+    {%- match rec.ffi_func_cmp() %}
+    {%- when Some(cmp) %}
+
+    // Compares the records with their `Ord` implementation in Rust
+    override fun compareTo(other: {{ type_name }}): Int =
+        rustCall() { _status ->
+            _UniFFILib.INSTANCE.{{ cmp.name() }}({{ rec|lower_fn }}(this), {{ rec|lower_fn }}(other), _status)
+        }.toInt()
+    {%- when None %}
+    {%- endmatch %}
+    {%- if self.is_pooled_record(name) %}
+
+    companion object {
+        // Recycled instances, which are reused by the FfiConverter rather than allocating new ones
+        internal val uniffiPool = java.util.concurrent.ArrayBlockingQueue<{{ type_name }}>({{ self.record_pool_size() }})
+
+        /**
+         * Hand `value` back to be reused for the next `{{ type_name }}` received from Rust.
+         *
+         * Its fields will be overwritten, so `value` must not be used after calling this.  Objects
+         * held by its fields aren't destroyed.
+         */
+        fun recycle(value: {{ type_name }}) {
+            uniffiPool.offer(value)
+        }
+    }
+    {%- endif %}
+}
+
@@ -1,25 +1,71 @@
 {%- let rec = ci|get_record_definition(name) %}
+{#- Multiplatform bindings declare the record in the common code #}
+{%- if !self.multiplatform() %}
+{%- match self.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
 
-data class {{ type_name }} (
+{{ type_override }}
+{%- when None %}
+{% include "RecordDeclaration.kt" %}
+{%- endmatch %}
+{%- endif %}
+
+{%- if rec.is_repr_c() %}
+{%- let struct_name = rec.ffi_type().borrow()|ffi_type_name %}
+
+// `{{ type_name }}` is passed to and from Rust as this struct, rather than in a `RustBuffer`.
+@Structure.FieldOrder({% for field in rec.fields() %}"{{ self.field_name(field.name())|unquote }}"{% if !loop.last %}, {% endif %}{% endfor %})
+open class {{ struct_name }} : Structure() {
     {%- for field in rec.fields() %}
-    var {{ field.name()|var_name }}: {{ field|type_name -}}
-    {%- match field.default_value() %}
-        {%- when Some with(literal) %} = {{ literal|render_literal(field) }}
-        {%- else %}
-    {%- endmatch -%}
-    {% if !loop.last %}, {% endif %}
+    {%- let field_ffi_type = field.ffi_type() %}
+    @JvmField var {{ self.field_name(field.name()) }}: {{ field_ffi_type|ffi_type_name }} =
+    {%- match field.as_type() %}
+    {%- when Type::Float32 %} 0.0f
+    {%- when Type::Float64 %} 0.0
+    {%- else %} 0
+    {%- endmatch %}
     {%- endfor %}
-) {% if contains_object_references %}: Disposable {% endif %}{
-    {% if contains_object_references %}
-    @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
-    override fun destroy() {
-        {% call kt::destroy_fields(rec) %}
-    }
-    {% endif %}
+
+    class ByValue : {{ struct_name }}(), Structure.ByValue
 }
 
+public object {{ rec|ffi_converter_name }}: FfiConverter<{{ type_name }}, {{ struct_name }}.ByValue> {
+    override fun lift(value: {{ struct_name }}.ByValue): {{ type_name }} {
+        {%- if self.is_pooled_record(name) %}
+        {{ type_name }}.uniffiPool.poll()?.let { pooled ->
+            {%- for field in rec.fields() %}
+            pooled.{{ self.field_name(field.name()) }} = {{ field|lift_fn }}(value.{{ self.field_name(field.name()) }})
+            {%- endfor %}
+            return pooled
+        }
+        {%- endif %}
+        return {{ type_name }}(
+        {%- for field in rec.fields() %}
+            {{ field|lift_fn }}(value.{{ self.field_name(field.name()) }}),
+        {%- endfor %}
+        )
+    }
+
+    override fun lower(value: {{ type_name }}): {{ struct_name }}.ByValue {
+        val struct = {{ struct_name }}.ByValue()
+        {%- for field in rec.fields() %}
+        struct.{{ self.field_name(field.name()) }} = {{ field|lower_fn }}(value.{{ self.field_name(field.name()) }})
+        {%- endfor %}
+        return struct
+    }
+{%- else %}
+
 public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+{%- endif %}
     override fun read(buf: ByteBuffer): {{ type_name }} {
+        {%- if self.is_pooled_record(name) %}
+        {{ type_name }}.uniffiPool.poll()?.let { pooled ->
+            {%- for field in rec.fields() %}
+            pooled.{{ self.field_name(field.name()) }} = {{ field|read_fn }}(buf)
+            {%- endfor %}
+            return pooled
+        }
+        {%- endif %}
         return {{ type_name }}(
         {%- for field in rec.fields() %}
             {{ field|read_fn }}(buf),
@@ -29,13 +75,13 @@ public object {{ rec|ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name
 
     override fun allocationSize(value: {{ type_name }}) = (
         {%- for field in rec.fields() %}
-            {{ field|allocation_size_fn }}(value.{{ field.name()|var_name }}){% if !loop.last %} +{% endif%}
+            {{ field|allocation_size_fn }}(value.{{ self.field_name(field.name()) }}){% if !loop.last %} +{% endif%}
         {%- endfor %}
     )
 
     override fun write(value: {{ type_name }}, buf: ByteBuffer) {
         {%- for field in rec.fields() %}
-            {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+            {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
         {%- endfor %}
     }
 }
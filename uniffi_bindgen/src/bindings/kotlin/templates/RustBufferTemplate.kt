@@ -65,10 +65,9 @@ class RustBufferByReference : ByReference(16) {
 }
 
 // This is a helper for safely passing byte references into the rust code.
-// It's not actually used at the moment, because there aren't many things that you
-// can take a direct pointer to in the JVM, and if we're going to copy something
-// then we might as well copy it into a `RustBuffer`. But it's here for API
-// completeness.
+// It's used for `[ByRef] bytes` arguments. We can't take a direct pointer to a
+// `ByteArray` in the JVM, so the bytes are copied into native memory once, which
+// the Rust code then reads in place rather than copying again.
 
 @Structure.FieldOrder("len", "data")
 open class ForeignBytes : Structure() {
@@ -76,4 +75,19 @@ open class ForeignBytes : Structure() {
     @JvmField var data: Pointer? = null
 
     class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is referenced by the struct, so it stays alive for the
+        // duration of the call that the struct is passed to.
+        internal fun borrow(value: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = value.size
+            if (value.isNotEmpty()) {
+                val memory = Memory(value.size.toLong())
+                memory.write(0, value, 0, value.size)
+                bytes.data = memory
+            }
+            return bytes
+        }
+    }
 }
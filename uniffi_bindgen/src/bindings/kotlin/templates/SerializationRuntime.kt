@@ -0,0 +1,26 @@
+{{- self.add_import("kotlinx.serialization.KSerializer") }}
+{{- self.add_import("kotlinx.serialization.Serializable") }}
+{{- self.add_import("kotlinx.serialization.UseSerializers") }}
+{{- self.add_import("kotlinx.serialization.descriptors.PrimitiveKind") }}
+{{- self.add_import("kotlinx.serialization.descriptors.PrimitiveSerialDescriptor") }}
+{{- self.add_import("kotlinx.serialization.encoding.Decoder") }}
+{{- self.add_import("kotlinx.serialization.encoding.Encoder") }}
+
+// Serializer for the builtin types that kotlinx.serialization doesn't support, which are
+// serialized as strings.  The serializers are registered for the whole file with
+// `@file:UseSerializers`, so that they're also used for the values in lists and maps.
+open class UniffiStringSerializer<T>(
+    name: String,
+    private val format: (T) -> String,
+    private val parse: (String) -> T
+): KSerializer<T> {
+    override val descriptor = PrimitiveSerialDescriptor(name, PrimitiveKind.STRING)
+
+    override fun serialize(encoder: Encoder, value: T) {
+        encoder.encodeString(format(value))
+    }
+
+    override fun deserialize(decoder: Decoder): T {
+        return parse(decoder.decodeString())
+    }
+}
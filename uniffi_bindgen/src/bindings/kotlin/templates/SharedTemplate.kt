@@ -0,0 +1,39 @@
+{%- let inner_type_name = inner_type|type_name %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ inner_type_name }}> {
+    override fun read(buf: ByteBuffer): {{ inner_type_name }} {
+        when (buf.get().toInt()) {
+            0 -> return {{ inner_type|read_fn }}(buf)
+            1 -> {
+                // Large values are in a buffer of their own, which we own and have to free
+                val rbuf = RustBuffer.ByValue()
+                rbuf.capacity = buf.getInt()
+                rbuf.len = buf.getInt()
+                val offset = buf.getInt()
+                rbuf.data = Pointer(buf.getLong())
+                try {
+                    val shared = rbuf.asByteBuffer()!!
+                    shared.position(offset)
+                    val value = {{ inner_type|read_fn }}(shared)
+                    if (shared.hasRemaining()) {
+                        throw InternalException("Junk data left in buffer after reading shared value")
+                    }
+                    return value
+                } finally {
+                    RustBuffer.free(rbuf)
+                }
+            }
+            else -> throw InternalException("Unknown transport for shared value")
+        }
+    }
+
+    // Values sent to Rust are always inline
+    override fun allocationSize(value: {{ inner_type_name }}): Int {
+        return 1 + {{ inner_type|allocation_size_fn }}(value)
+    }
+
+    override fun write(value: {{ inner_type_name }}, buf: ByteBuffer) {
+        buf.put(0)
+        {{ inner_type|write_fn }}(value, buf)
+    }
+}
@@ -0,0 +1,43 @@
+public object FfiConverterSocketAddr: FfiConverterRustBuffer<java.net.InetSocketAddress> {
+    // Socket addresses are passed as their IP address followed by the u16 port.  Unresolved
+    // addresses, like the ones created with `InetSocketAddress.createUnresolved()`, don't have an
+    // IP address and can't be passed to Rust.
+    override fun read(buf: ByteBuffer): java.net.InetSocketAddress {
+        val address = FfiConverterIpAddr.read(buf)
+        val port = buf.getShort().toUShort().toInt()
+        return java.net.InetSocketAddress(address, port)
+    }
+
+    override fun allocationSize(value: java.net.InetSocketAddress) =
+        FfiConverterIpAddr.allocationSize(resolvedAddress(value)) + 2
+
+    override fun write(value: java.net.InetSocketAddress, buf: ByteBuffer) {
+        FfiConverterIpAddr.write(resolvedAddress(value), buf)
+        buf.putShort(value.port.toShort())
+    }
+
+    private fun resolvedAddress(value: java.net.InetSocketAddress): java.net.InetAddress {
+        return value.address ?: throw IllegalArgumentException("Unresolved socket address: $value")
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiInetSocketAddressSerializer") }}
+
+// Socket addresses are serialized like `192.0.2.1:443` or `[2001:db8::1]:443`
+object UniffiInetSocketAddressSerializer: UniffiStringSerializer<java.net.InetSocketAddress>(
+    "java.net.InetSocketAddress",
+    {
+        val address = it.address ?: throw IllegalArgumentException("Unresolved socket address: $it")
+        when (address) {
+            is java.net.Inet6Address -> "[${address.hostAddress}]:${it.port}"
+            else -> "${address.hostAddress}:${it.port}"
+        }
+    },
+    {
+        val separator = it.lastIndexOf(':')
+        val host = it.substring(0, separator).removePrefix("[").removeSuffix("]")
+        java.net.InetSocketAddress(java.net.InetAddress.getByName(host), it.substring(separator + 1).toInt())
+    }
+)
+{%- endif %}
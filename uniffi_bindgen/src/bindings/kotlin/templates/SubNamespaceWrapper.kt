@@ -0,0 +1,23 @@
+{%- match config.code_hooks().preamble() -%}
+{%- when Some with (preamble) -%}
+{{ preamble }}
+{% when None -%}
+{%- endmatch -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+
+package {{ config.package_name() }}.{{ namespace }};
+
+// The functions of the `{{ namespace }}` sub-namespace.  The types and the helpers that they use are
+// in the `{{ config.package_name() }}` package.
+
+{% include "Imports.kt" %}
+import {{ config.package_name() }}.*
+
+{%- for func in ci.sub_namespace_function_definitions(namespace) %}
+{%- include "TopLevelFunctionTemplate.kt" %}
+{%- endfor %}
+
+{% import "macros.kt" as kt %}
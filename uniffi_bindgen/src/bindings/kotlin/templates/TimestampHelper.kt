@@ -36,3 +36,10 @@ public object FfiConverterTimestamp: FfiConverterRustBuffer<java.time.Instant> {
         buf.putInt(epochOffset.nano)
     }
 }
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiInstantSerializer") }}
+
+// Timestamps are serialized in ISO-8601 format, like `2023-06-01T12:00:00Z`
+object UniffiInstantSerializer: UniffiStringSerializer<java.time.Instant>("java.time.Instant", { it.toString() }, { java.time.Instant.parse(it) })
+{%- endif %}
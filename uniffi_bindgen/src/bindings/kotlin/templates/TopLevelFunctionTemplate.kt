@@ -1,4 +1,5 @@
 {%- if func.is_async() %}
+{% call kt::callable_docstring(func, 0) %}
 {%- match func.throws_type() -%}
 {%- when Some with (throwable) %}
 @Throws({{ throwable|error_type_name }}::class)
@@ -6,7 +7,7 @@
 {%- endmatch %}
 
 @Suppress("ASSIGNED_BUT_NEVER_ACCESSED_VARIABLE")
-suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name }}{% when None %}{%- endmatch %} {
+{% call kt::actual() %}suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_impl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %} : {{ return_type|type_name }}{% when None %}{%- endmatch %} {
     // Create a new `CoroutineScope` for this operation, suspend the coroutine, and call the
     // scaffolding function, passing it one of the callback handlers from `AsyncTypes.kt`.
     return coroutineScope {
@@ -16,6 +17,7 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
                 val callback = {{ func.result_type().borrow()|future_callback_handler }}(continuation)
                 uniffiActiveFutureCallbacks.add(callback)
                 continuation.invokeOnCancellation { uniffiActiveFutureCallbacks.remove(callback) }
+                {% call kt::record_usage_start(func) -%}
                 rustCall { status ->
                     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
                         {% call kt::arg_list_lowered(func) %}
@@ -25,6 +27,7 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
                         status,
                     )
                 }
+                {%- call kt::record_usage_end(func) %}
             } catch (e: Exception) {
                 continuation.resumeWithException(e)
             }
@@ -33,6 +36,7 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
 }
 
 {%- else %}
+{% call kt::callable_docstring(func, 0) %}
 {%- match func.throws_type() -%}
 {%- when Some with (throwable) %}
 @Throws({{ throwable|error_type_name }}::class)
@@ -41,13 +45,11 @@ suspend fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}){% ma
 
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
-
-fun {{ func.name()|fn_name }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_name }} {
-    return {{ return_type|lift_fn }}({% call kt::to_ffi_call(func) %})
+{% call kt::actual() %}fun {{ func.name()|fn_name }}({%- call kt::arg_list_impl(func) -%}): {% call kt::return_type_name(return_type) %} {
+    return {% call kt::return_lift_fn(return_type) %}({% call kt::to_ffi_call(func) %})
 }
 {% when None %}
-
-fun {{ func.name()|fn_name }}({% call kt::arg_list_decl(func) %}) =
+{% call kt::actual() %}fun {{ func.name()|fn_name }}({% call kt::arg_list_impl(func) %}) =
     {% call kt::to_ffi_call(func) %}
 
 {% endmatch %}
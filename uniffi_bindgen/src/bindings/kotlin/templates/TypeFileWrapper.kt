@@ -0,0 +1,21 @@
+{%- match config.code_hooks().preamble() -%}
+{%- when Some with (preamble) -%}
+{{ preamble }}
+{% when None -%}
+{%- endmatch -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+{%- let file_serializers = self.file_serializers() %}
+{%- if !file_serializers.is_empty() %}
+@file:UseSerializers({% for s in file_serializers %}{{ s }}::class{% if !loop.last %}, {% endif %}{% endfor %})
+{%- endif %}
+
+package {{ config.package_name() }};
+
+// The helpers that this type uses are in the runtime file of the package, which is named after the
+// namespace.
+
+{% include "Imports.kt" %}
+{{ type_code }}
@@ -1,6 +1,29 @@
 {%- import "macros.kt" as kt %}
 
-{%- for type_ in ci.iter_types() %}
+{#-
+ # The runtime file of split bindings has the runtime code that the type files needed, since they
+ # leave it out.  This is a no-op when the bindings are a single file.
+ #}
+{%- if self.split_runtime_include("ObjectRuntime.kt") %}
+{% include "ObjectRuntime.kt" %}
+{%- endif %}
+{%- if self.split_runtime_include("CallbackInterfaceRuntime.kt") %}
+{% include "CallbackInterfaceRuntime.kt" %}
+{%- endif %}
+{%- if self.split_runtime_include("CallbackConformanceRuntime.kt") %}
+{% include "CallbackConformanceRuntime.kt" %}
+{%- endif %}
+{%- if self.split_runtime_include("CompressionRuntime.kt") %}
+{% include "CompressionRuntime.kt" %}
+{%- endif %}
+{%- if self.split_runtime_include("SerializationRuntime.kt") %}
+{% include "SerializationRuntime.kt" %}
+{%- endif %}
+{%- if self.split_runtime_include("WeakRefRuntime.kt") %}
+{% include "WeakRefRuntime.kt" %}
+{%- endif %}
+
+{%- for type_ in self.types %}
 {%- let type_name = type_|type_name %}
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
@@ -20,6 +43,9 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.kt" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.kt" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.kt" %}
 
@@ -64,6 +90,10 @@
 {% include "ErrorTemplate.kt" %}
 {%- endif -%}
 
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+{% include "FlagsTemplate.kt" %}
+
 {%- when Type::Object { module_path, name, imp } %}
 {% include "ObjectTemplate.kt" %}
 
@@ -79,6 +109,15 @@
 {%- when Type::Map { key_type, value_type } %}
 {% include "MapTemplate.kt" %}
 
+{%- when Type::Compressed { inner_type } %}
+{% include "CompressedTemplate.kt" %}
+
+{%- when Type::Shared { inner_type } %}
+{% include "SharedTemplate.kt" %}
+
+{%- when Type::Weak { inner_type } %}
+{% include "WeakTemplate.kt" %}
+
 {%- when Type::CallbackInterface { module_path, name } %}
 {% include "CallbackInterfaceTemplate.kt" %}
 
@@ -91,6 +130,27 @@
 {%- when Type::Duration %}
 {% include "DurationHelper.kt" %}
 
+{%- when Type::Url %}
+{% include "UrlHelper.kt" %}
+
+{%- when Type::Uuid %}
+{% include "UuidHelper.kt" %}
+
+{%- when Type::Decimal %}
+{% include "DecimalHelper.kt" %}
+
+{%- when Type::Json %}
+{% include "JsonHelper.kt" %}
+
+{%- when Type::Path %}
+{% include "PathHelper.kt" %}
+
+{%- when Type::IpAddr %}
+{% include "IpAddrHelper.kt" %}
+
+{%- when Type::SocketAddr %}
+{% include "SocketAddrHelper.kt" %}
+
 {%- when Type::Custom { module_path, name, builtin } %}
 {% include "CustomTypeTemplate.kt" %}
 
@@ -99,8 +159,20 @@
 
 {%- else %}
 {%- endmatch %}
+{%- match self.code_hooks().type_extension(type_) %}
+{%- when Some with (extension) %}
+
+{{ extension }}
+{%- when None %}
+{%- endmatch %}
 {%- endfor %}
 
+{%- if self.renders_runtime() %}
 {%- if ci.has_async_fns() %}
 {% include "AsyncTypes.kt" %}
 {%- endif %}
+
+{%- if self.zero_copy_bytes() %}
+{% include "ZeroCopyBytes.kt" %}
+{%- endif %}
+{%- endif %}
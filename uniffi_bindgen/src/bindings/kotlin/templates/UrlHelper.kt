@@ -0,0 +1,28 @@
+public object FfiConverterUrl: FfiConverter<java.net.URI, RustBuffer.ByValue> {
+    // URLs are passed exactly like strings, Rust validates them when it lifts them.
+    override fun lift(value: RustBuffer.ByValue): java.net.URI {
+        return java.net.URI(FfiConverterString.lift(value))
+    }
+
+    override fun read(buf: ByteBuffer): java.net.URI {
+        return java.net.URI(FfiConverterString.read(buf))
+    }
+
+    override fun lower(value: java.net.URI): RustBuffer.ByValue {
+        return FfiConverterString.lower(value.toString())
+    }
+
+    override fun allocationSize(value: java.net.URI): Int {
+        return FfiConverterString.allocationSize(value.toString())
+    }
+
+    override fun write(value: java.net.URI, buf: ByteBuffer) {
+        FfiConverterString.write(value.toString(), buf)
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiUriSerializer") }}
+
+object UniffiUriSerializer: UniffiStringSerializer<java.net.URI>("java.net.URI", { it.toString() }, { java.net.URI(it) })
+{%- endif %}
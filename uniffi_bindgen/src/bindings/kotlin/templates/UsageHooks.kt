@@ -0,0 +1,17 @@
+/**
+ * Notifies the app each time one of the APIs in this package is used.
+ *
+ * Set `hook` to a function that takes the name of the API, like `"Object.method"`.  The hook is
+ * called on the calling thread before the call into Rust, so it should be cheap and must not throw.
+ *
+ * This is only generated when `usage_hooks` is enabled in `uniffi.toml`.
+ */
+object UniffiUsage {
+    @Volatile
+    var hook: ((String) -> Unit)? = null
+}
+
+internal inline fun <T> uniffiRecordUsage(name: String, call: () -> T): T {
+    UniffiUsage.hook?.invoke(name)
+    return call()
+}
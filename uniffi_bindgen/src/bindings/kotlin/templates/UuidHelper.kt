@@ -0,0 +1,21 @@
+public object FfiConverterUuid: FfiConverterRustBuffer<java.util.UUID> {
+    // UUIDs are passed as their 16 raw bytes, most significant first
+    override fun read(buf: ByteBuffer): java.util.UUID {
+        val mostSigBits = buf.getLong()
+        val leastSigBits = buf.getLong()
+        return java.util.UUID(mostSigBits, leastSigBits)
+    }
+
+    override fun allocationSize(value: java.util.UUID) = 16
+
+    override fun write(value: java.util.UUID, buf: ByteBuffer) {
+        buf.putLong(value.mostSignificantBits)
+        buf.putLong(value.leastSignificantBits)
+    }
+}
+{%- if self.serializable() %}
+{%- if self.include_once_check("SerializationRuntime.kt") %}{% include "SerializationRuntime.kt" %}{% endif %}
+{{- self.add_file_serializer("UniffiUuidSerializer") }}
+
+object UniffiUuidSerializer: UniffiStringSerializer<java.util.UUID>("java.util.UUID", { it.toString() }, { java.util.UUID.fromString(it) })
+{%- endif %}
@@ -0,0 +1,60 @@
+/**
+ * A reference to a Rust object that doesn't keep it alive, from a `Weak` in Rust.
+ *
+ * Use [upgrade] to get the object while it's still alive.  The reference holds a handle to the
+ * Rust `Weak`, so it must be destroyed after use like the objects themselves.
+ */
+class WeakRef<T> internal constructor(
+    private val handle: Long,
+    private val liftObject: (Long) -> T,
+) : Disposable, AutoCloseable {
+    private val wasDestroyed = AtomicBoolean(false)
+    {%- if self.hot_reload() %}
+    // The generation of the library that the handle belongs to, see `uniffiReload()`
+    private val generation = _UniFFILib.generation
+    {%- endif %}
+
+    /**
+     * Get a new reference to the object, or `null` if Rust dropped it.
+     *
+     * The returned object must be destroyed like any other.
+     */
+    fun upgrade(): T? {
+        check(!wasDestroyed.get()) { "WeakRef was destroyed" }
+        {%- if self.hot_reload() %}
+        check(generation == _UniFFILib.generation) { "WeakRef is from before the library was reloaded" }
+        {%- endif %}
+        val ptr = rustCall { _status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_weak_ref_upgrade().name() }}(handle, _status)
+        }
+        return if (ptr == 0L) null else liftObject(ptr)
+    }
+
+    /** Whether the object is still alive. */
+    val isAlive: Boolean
+        get() = upgrade()?.also { Disposable.destroy(it) } != null
+
+    // Rust takes ownership of the handles that it reads, so it's passed a copy of this one.
+    internal fun cloneHandle(): Long {
+        check(!wasDestroyed.get()) { "WeakRef was destroyed" }
+        {%- if self.hot_reload() %}
+        check(generation == _UniFFILib.generation) { "WeakRef is from before the library was reloaded" }
+        {%- endif %}
+        return rustCall { _status ->
+            _UniFFILib.INSTANCE.{{ ci.ffi_weak_ref_clone().name() }}(handle, _status)
+        }
+    }
+
+    override fun destroy() {
+        if (wasDestroyed.compareAndSet(false, true){% if self.hot_reload() %} && generation == _UniFFILib.generation{% endif %}) {
+            rustCall { _status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_weak_ref_free().name() }}(handle, _status)
+            }
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+}
@@ -0,0 +1,15 @@
+{{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
+{%- if self.include_once_check("WeakRefRuntime.kt") %}{% include "WeakRefRuntime.kt" %}{% endif %}
+
+public object {{ ffi_converter_name }}: FfiConverterRustBuffer<{{ type_name }}> {
+    override fun read(buf: ByteBuffer): {{ type_name }} {
+        // The handle is ours now, it's freed when the `WeakRef` is destroyed
+        return WeakRef(buf.getLong()) { {{ inner_type|lift_fn }}(Pointer(it)) }
+    }
+
+    override fun allocationSize(value: {{ type_name }}) = 8
+
+    override fun write(value: {{ type_name }}, buf: ByteBuffer) {
+        buf.putLong(value.cloneHandle())
+    }
+}
@@ -0,0 +1,32 @@
+{%- if self.include_once_check("ObjectRuntime.kt") %}{% include "ObjectRuntime.kt" %}{% endif %}
+{{- self.add_import("java.util.concurrent.atomic.AtomicLong") }}
+{{- self.add_import("java.util.concurrent.atomic.AtomicBoolean") }}
+
+// `bytes` returned by functions and methods, when `zero_copy_bytes` is enabled.
+//
+// `buffer` is a direct `ByteBuffer` that reads the bytes from the buffer Rust allocated, instead of
+// copying them into a `ByteArray`.  Like objects, a `RustByteBuffer` must be released by calling
+// `destroy()`, or with the `.use` helper, and `buffer` must not be used after that.
+class RustByteBuffer private constructor(
+    private val rbuf: RustBuffer.ByValue
+): Disposable, AutoCloseable {
+    private val wasDestroyed = AtomicBoolean(false)
+
+    // Skip the length that the bytes are serialized with
+    val buffer: ByteBuffer = rbuf.data!!.getByteBuffer(4, (rbuf.len - 4).toLong()).asReadOnlyBuffer()
+
+    override fun destroy() {
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            RustBuffer.free(rbuf)
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+
+    companion object {
+        fun lift(rbuf: RustBuffer.ByValue): RustByteBuffer = RustByteBuffer(rbuf)
+    }
+}
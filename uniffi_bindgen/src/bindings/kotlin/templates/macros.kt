@@ -5,20 +5,26 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- call borrow_objects_start(func) %}
+    {%- call record_usage_start(func) %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
-    rustCallWithError({{ e|error_type_name }})
+    rustCallWithError({% call error_handler(e) %})
     {%- else %}
     rustCall()
     {%- endmatch %} { _status ->
     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} _status)
 }
+    {%- call record_usage_end(func) %}
+    {%- call borrow_objects_end(func) %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) %}
+    {%- call borrow_objects_start(func) %}
+    {%- call record_usage_start(func) %}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
-    rustCallWithError({{ e|error_type_name }})
+    rustCallWithError({% call error_handler(e) %})
     {%- else %}
     rustCall()
     {%- endmatch %} { _status ->
@@ -27,22 +33,116 @@
         {% call arg_list_lowered(func) %}
         _status)
 }
+    {%- call record_usage_end(func) %}
+    {%- call borrow_objects_end(func) %}
 {%- endmacro %}
 
+{#-
+// Rust borrows `[ByRef]` object arguments for the duration of the call, without taking its own
+// reference, so the call is wrapped in `callWithPointer()` for each of them.  That holds their call
+// counters until the call returns, so that a concurrent `destroy()` can't free them.
+// `arg_list_lowered` passes the pointers that this binds.
+-#}
+{%- macro borrow_objects_start(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_object() %}{{ self.field_name(arg.name()) }}.callWithPointer { uniffiBorrowed{{ loop.index }} -> {% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro borrow_objects_end(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_object() %} }{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// The `CallStatusErrorHandler` for an error.  Multiplatform bindings declare errors in the common
+// code, so their handler is a separate object rather than the error's companion.
+-#}
+{%- macro error_handler(e) %}
+    {{- e|error_type_name }}{% if self.multiplatform() %}ErrorHandler{% endif %}
+{%- endmacro -%}
+
+{#-
+// The `actual` modifier, for the declarations that multiplatform bindings expect in the common code
+-#}
+{%- macro actual() %}
+    {%- if self.multiplatform() %}actual {% endif %}
+{%- endmacro -%}
+
+{#-
+// The visibility of the helpers that are private to the file, which are `internal` when the
+// bindings are split across files so that the type files can use them, and when there are
+// sub-namespaces so that their files can
+-#}
+{%- macro file_private() %}
+    {%- if self.split_files() || !ci.sub_namespaces().is_empty() %}internal{% else %}private{% endif %}
+{%- endmacro -%}
+
+{#-
+// When `usage_hooks` is enabled, calls are wrapped in `uniffiRecordUsage()`, which notifies
+// `UniffiUsage.hook`.  Otherwise nothing is generated.
+-#}
+{%- macro record_usage_start(func) %}
+    {%- if self.usage_hooks() %}uniffiRecordUsage("{{ func.qualified_name() }}") { {% endif %}
+{%- endmacro -%}
+
+{%- macro record_usage_end(func) %}
+    {%- if self.usage_hooks() %} }{% endif %}
+{%- endmacro -%}
+
+{#-
+// The type returned by a function or method, and the function that lifts it.  When
+// `zero_copy_bytes` is enabled, `bytes` and `Vec<u8>` are returned as a `RustByteBuffer`.
+-#}
+{%- macro return_type_name(return_type) -%}
+    {%- if self.is_zero_copy_bytes(return_type) %}RustByteBuffer{% else %}{{ return_type|type_name }}{% endif -%}
+{%- endmacro -%}
+
+{%- macro return_lift_fn(return_type) -%}
+    {%- if self.is_zero_copy_bytes(return_type) %}RustByteBuffer.lift{% else %}{{ return_type|lift_fn }}{% endif -%}
+{%- endmacro -%}
+
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg|lower_fn }}({{ arg.name()|var_name }}),
+        {%- if func.takes_self_by_value() && !arg.borrows_object() %}
+        uniffiLowered{{ loop.index }},
+        {%- else if arg.borrows_bytes() %}
+        ForeignBytes.borrow({{ self.field_name(arg.name()) }}),
+        {%- else if arg.borrows_object() %}
+        uniffiBorrowed{{ loop.index }},
+        {%- else if arg.wipe_lifted_buffer() %}
+        uniffiLowerSensitive({{ arg|ffi_converter_name }}, {{ self.field_name(arg.name()) }}),
+        {%- else %}
+        {{- arg|lower_fn }}({{ self.field_name(arg.name()) }}),
+        {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// Methods that consume the object lower their arguments before they take its pointer, so that a
+// failure to lower them leaves the object usable.  `arg_list_lowered` passes these locals.
+-#}
+{%- macro lower_args_into_locals(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.borrows_bytes() %}
+            val uniffiLowered{{ loop.index }} = ForeignBytes.borrow({{ self.field_name(arg.name()) }})
+        {%- else if arg.wipe_lifted_buffer() %}
+            val uniffiLowered{{ loop.index }} = uniffiLowerSensitive({{ arg|ffi_converter_name }}, {{ self.field_name(arg.name()) }})
+        {%- else if !arg.borrows_object() %}
+            val uniffiLowered{{ loop.index }} = {{ arg|lower_fn }}({{ self.field_name(arg.name()) }})
+        {%- endif %}
     {%- endfor %}
 {%- endmacro -%}
 
 {#-
 // Arglist as used in kotlin declarations of methods, functions and constructors.
-// Note the var_name and type_name filters.
+// Note the field_name method and the type_name filter.
 -#}
 
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name }}: {%- if !ci.is_name_used_as_error(arg|type_name) %} {{ arg|type_name -}} {%- else %} {{ arg|error_type_name -}} {% endif -%}
+        {{ self.field_name(arg.name()) }}: {%- if !ci.is_name_used_as_error(arg|type_name) %} {{ arg|type_name -}} {%- else %} {{ arg|error_type_name -}} {% endif -%}
         {%- match arg.default_value() %}
         {%- when Some with(literal) %} = {{ literal|render_literal(arg) }}
         {%- else %}
@@ -51,9 +151,17 @@
     {%- endfor %}
 {%- endmacro %}
 
+{#-
+// Arglist of an `actual` function or constructor, whose default values are declared by the
+// `expect` declaration in multiplatform bindings.
+-#}
+{% macro arg_list_impl(func) %}
+    {%- if self.multiplatform() %}{% call arg_list_protocol(func) %}{% else %}{% call arg_list_decl(func) %}{% endif %}
+{%- endmacro %}
+
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name }}: {%- if !ci.is_name_used_as_error(arg|type_name) %} {{ arg|type_name -}} {%- else %} {{ arg|error_type_name -}} {% endif -%}
+        {{ self.field_name(arg.name()) }}: {%- if !ci.is_name_used_as_error(arg|type_name) %} {{ arg|type_name -}} {%- else %} {{ arg|error_type_name -}} {% endif -%}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
@@ -63,7 +171,7 @@
 -#}
 {%- macro arg_list_ffi_decl(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg.name()|var_name }}: {{ arg.type_().borrow()|ffi_type_name_by_value -}},
+        {{- self.field_name(arg.name()) }}: {{ arg.type_().borrow()|ffi_type_name_by_value -}},
     {%- endfor %}
     {%- if func.has_rust_call_status_arg() %}_uniffi_out_err: RustCallStatus, {% endif %}
 {%- endmacro -%}
@@ -72,6 +180,30 @@
 {%- macro destroy_fields(member) %}
     Disposable.destroy(
     {%- for field in member.fields() %}
-        this.{{ field.name()|var_name }}{%- if !loop.last %}, {% endif -%}
+        this.{{ self.field_name(field.name()) }}{%- if !loop.last %}, {% endif -%}
     {% endfor -%})
 {%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, object, record, enum or callback interface as a KDoc
+// comment on its own line, or nothing if it doesn't have one.
+-#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, method or constructor like `docstring`, followed by a note
+// for each sensitive argument.
+-#}
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- match self.docstring_with_notes(callable) %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
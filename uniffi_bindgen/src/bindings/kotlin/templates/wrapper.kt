@@ -1,7 +1,22 @@
+{%- match config.code_hooks().preamble() -%}
+{%- when Some with (preamble) -%}
+{{ preamble }}
+{% when None -%}
+{%- endmatch -%}
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
 
 @file:Suppress("NAME_SHADOWING")
+{%- let file_serializers = self.file_serializers() %}
+{%- if !file_serializers.is_empty() %}
+@file:UseSerializers({% for s in file_serializers %}{{ s }}::class{% if !loop.last %}, {% endif %}{% endfor %})
+{%- endif %}
+{%- match ci.namespace_docstring() %}
+{%- when Some with (docstring) %}
+
+{{ docstring|docstring(0) }}
+{%- when None %}
+{%- endmatch %}
 
 package {{ config.package_name() }};
 
@@ -17,26 +32,19 @@ package {{ config.package_name() }};
 // compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
 // helpers directly inline like we're doing here.
 
-import com.sun.jna.Library
-import com.sun.jna.IntegerType
-import com.sun.jna.Native
-import com.sun.jna.Pointer
-import com.sun.jna.Structure
-import com.sun.jna.Callback
-import com.sun.jna.ptr.*
-import java.nio.ByteBuffer
-import java.nio.ByteOrder
-import java.nio.CharBuffer
-import java.nio.charset.CodingErrorAction
-import java.util.concurrent.ConcurrentHashMap
-
-{%- for req in self.imports() %}
-{{ req.render() }}
-{%- endfor %}
+{% include "Imports.kt" %}
 
 {% include "RustBufferTemplate.kt" %}
 {% include "FfiConverterTemplate.kt" %}
 {% include "Helpers.kt" %}
+{%- if config.usage_hooks() %}
+
+{% include "UsageHooks.kt" %}
+{%- endif %}
+{%- if ci.has_main_thread_callback_interfaces() %}
+
+{% include "MainThread.kt" %}
+{%- endif %}
 
 // Contains loading, initialization code,
 // and the FFI Function declarations in a com.sun.jna.Library.
@@ -45,8 +53,69 @@ import java.util.concurrent.ConcurrentHashMap
 // Public interface members begin here.
 {{ type_helper_code }}
 
-{%- for func in ci.function_definitions() %}
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+const val {{ c.name() }}: {{ c|type_name }} = {{ c.value()|render_literal(c) }}
+{%- endfor %}
+
+{%- for func in ci.root_function_definitions() %}
 {%- include "TopLevelFunctionTemplate.kt" %}
 {%- endfor %}
 
+/**
+ * Lists the objects that Rust passed to Kotlin and that haven't been freed yet, and the callback
+ * interfaces that Rust still holds, with a backtrace of where each one was created.
+ *
+ * This needs the `handle-diagnostics` feature of the `uniffi` crate, otherwise the report only
+ * says that it's disabled.
+ */
+{% call kt::actual() %}fun uniffiDebugLiveHandles(): String =
+    {{ Type::String.borrow()|lift_fn }}(rustCall { _status ->
+        _UniFFILib.INSTANCE.{{ ci.ffi_uniffi_debug_live_handles().name() }}(_status)
+    })
+{%- if ci.has_detachable_callback_interfaces() %}
+
+/**
+ * Tells Rust that `obj` is gone, when it was passed to Rust as a callback interface that Rust may
+ * still hold.
+ *
+ * This calls the `on_detach` hook that Rust set for the callback interface, and from then on
+ * calling the object's methods from Rust fails rather than calling `obj`.  The bindings release
+ * the object too, so that it can be garbage collected even if Rust holds on to it.
+ */
+fun uniffiDetach(obj: Any) {
+    {%- for cbi in ci.callback_interface_definitions() %}
+    {%- if !cbi.is_closure() %}
+    {{ cbi|ffi_converter_name }}.detach(obj)
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
+{%- if config.hot_reload() %}
+
+/**
+ * Load the library again after it was rebuilt, without restarting the JVM.
+ *
+ * The new library is checked like when it's first loaded, and this throws a `RuntimeException` if
+ * its API doesn't match these bindings, in which case the old library is still used.  Regenerate
+ * the bindings and restart after changes to the API.
+ *
+ * The objects from the old library can't be used after this: their methods throw an
+ * `IllegalStateException`.  The old library is unloaded, so there mustn't be any calls to it in
+ * progress, and the threads and tasks that it started must have stopped.
+ *
+ * This is only meant for development, it's generated with the `hot_reload` option.
+ */
+fun uniffiReload() {
+    _UniFFILib.reload()
+}
+{%- endif %}
+{%- match config.code_hooks().footer() %}
+{%- when Some with (footer) %}
+
+{{ footer }}
+{%- when None %}
+{%- endmatch %}
+
 {% import "macros.kt" as kt %}
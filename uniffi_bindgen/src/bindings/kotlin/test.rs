@@ -10,7 +10,7 @@ use anyhow::{bail, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use std::env;
 use std::process::Command;
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{GradleTest, TestEnvironment, UniFFITestHelper};
 
 /// Run Kotlin tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -45,11 +45,14 @@ pub fn run_script(
         false,
     )?;
     let jar_file = build_jar(crate_name, &out_dir, options)?;
+    build_jni_shim(&cdylib_path, &out_dir)?;
 
     let mut command = kotlinc_command(options);
     command
         .arg("-classpath")
         .arg(calc_classpath(vec![&out_dir, &jar_file]))
+        // JNI bindings load their libraries with `System.loadLibrary()`
+        .arg(format!("-J-Djava.library.path={out_dir}"))
         // Enable runtime assertions, for easy testing etc.
         .arg("-J-ea")
         // Our test scripts should not produce any warnings.
@@ -73,6 +76,33 @@ pub fn run_script(
     Ok(())
 }
 
+/// Run the JUnit tests of a Gradle project for a UniFFI test fixture
+///
+/// The bindings are compiled into a jar, which the project's build script should add to the test
+/// classpath from the `UNIFFI_CLASSPATH` environment variable, see [GradleTest].  The test run
+/// fails if any test fails.
+pub fn run_gradle_test(tmp_dir: &str, fixture_name: &str, project_dir: &str) -> Result<()> {
+    let project_dir = Utf8Path::new(".").join(project_dir).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(fixture_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &project_dir)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::Kotlin],
+        &out_dir,
+        false,
+    )?;
+    let jar_file = build_jar(fixture_name, &out_dir, &RunScriptOptions::default())?;
+    build_jni_shim(&cdylib_path, &out_dir)?;
+
+    let mut env = TestEnvironment::new(&out_dir);
+    env.search_paths.push(jar_file);
+    test_helper
+        .run_test_suite(&GradleTest::new(project_dir), &env)?
+        .ensure_passed()
+}
+
 /// Generate kotlin bindings for the given namespace, then use the kotlin
 /// command-line tools to compile them into a .jar file.
 fn build_jar(
@@ -111,6 +141,58 @@ fn build_jar(
     Ok(jar_file)
 }
 
+/// Build the C shim of JNI bindings, if the bindings in `out_dir` have one
+///
+/// The shim is built as the `<cdylib_name>_jni` library next to the cdylib, which it links to.
+/// This needs a C compiler, `cc` or the one in the `CC` environment variable, and `JAVA_HOME` to
+/// find `jni.h`.
+fn build_jni_shim(cdylib_path: &Utf8Path, out_dir: &Utf8Path) -> Result<()> {
+    let jni_dir = out_dir.join("jni");
+    let shims = glob::glob(jni_dir.join("*_jni.c").as_str())?
+        .flatten()
+        .map(|p| String::from(p.to_string_lossy()))
+        .collect::<Vec<String>>();
+    if shims.is_empty() {
+        return Ok(());
+    }
+    let java_home = env::var("JAVA_HOME").context("JAVA_HOME must be set to build a JNI shim")?;
+    // The directory of the platform's `jni_md.h`
+    let platform_include = match env::consts::OS {
+        "macos" => "darwin",
+        "windows" => "win32",
+        os => os,
+    };
+    let (lib_prefix, lib_extension) = (env::consts::DLL_PREFIX, env::consts::DLL_EXTENSION);
+    let cdylib_name = cdylib_path
+        .file_stem()
+        .context("cdylib path has no file name")?
+        .trim_start_matches(lib_prefix);
+    let shim_path = out_dir.join(format!("{lib_prefix}{cdylib_name}_jni.{lib_extension}"));
+
+    let mut command = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()));
+    command
+        .arg("-shared")
+        .arg("-fPIC")
+        .arg(format!("-I{java_home}/include"))
+        .arg(format!("-I{java_home}/include/{platform_include}"))
+        .arg(format!("-I{jni_dir}"))
+        .args(shims)
+        .arg(format!("-L{out_dir}"))
+        .arg(format!("-l{cdylib_name}"))
+        .arg(format!("-Wl,-rpath,{out_dir}"))
+        .arg("-o")
+        .arg(&shim_path);
+    let status = command
+        .spawn()
+        .context("Failed to spawn the C compiler to build the JNI shim")?
+        .wait()
+        .context("Failed to wait for the C compiler when building the JNI shim")?;
+    if !status.success() {
+        bail!("building the JNI shim failed")
+    }
+    Ok(())
+}
+
 fn kotlinc_command(options: &RunScriptOptions) -> Command {
     let mut command = Command::new("kotlinc");
     if !options.show_compiler_messages {
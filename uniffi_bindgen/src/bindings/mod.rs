@@ -7,17 +7,20 @@
 //! This module contains all the code for generating foreign language bindings,
 //! along with some helpers for executing foreign language scripts or tests.
 
-use anyhow::{bail, Result};
-use camino::Utf8Path;
+use anyhow::{anyhow, bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
 use serde::{Deserialize, Serialize};
-use std::fmt;
+use std::{collections::BTreeMap, fmt};
 
 use crate::interface::ComponentInterface;
 
+pub mod c;
 pub mod kotlin;
 pub mod python;
 pub mod ruby;
 pub mod swift;
+pub mod wit;
 
 /// Enumeration of all foreign language targets currently supported by this crate.
 ///
@@ -25,13 +28,15 @@ pub mod swift;
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum TargetLanguage {
     Kotlin,
     Swift,
     Python,
     Ruby,
+    Wit,
+    C,
 }
 
 impl fmt::Display for TargetLanguage {
@@ -41,6 +46,8 @@ impl fmt::Display for TargetLanguage {
             Self::Swift => write!(f, "swift"),
             Self::Python => write!(f, "python"),
             Self::Ruby => write!(f, "ruby"),
+            Self::Wit => write!(f, "wit"),
+            Self::C => write!(f, "c"),
         }
     }
 }
@@ -67,6 +74,8 @@ impl TryFrom<&str> for TargetLanguage {
             "swift" => TargetLanguage::Swift,
             "python" | "py" => TargetLanguage::Python,
             "ruby" | "rb" => TargetLanguage::Ruby,
+            "wit" => TargetLanguage::Wit,
+            "c" | "h" => TargetLanguage::C,
             _ => bail!("Unknown or unsupported target language: \"{value}\""),
         })
     }
@@ -99,8 +108,74 @@ pub struct Config {
     pub(crate) python: python::Config,
     #[serde(default)]
     pub(crate) ruby: ruby::Config,
+    #[serde(default)]
+    pub(crate) wit: wit::Config,
+    #[serde(default)]
+    pub(crate) c: c::Config,
+    // The `out_dir` of each language, relative to the directory of the config file
+    #[serde(skip)]
+    pub(crate) out_dirs: BTreeMap<TargetLanguage, Utf8PathBuf>,
+    // The items to leave out of the bindings of each language, see `interface::visibility`
+    #[serde(skip)]
+    pub(crate) hidden: BTreeMap<TargetLanguage, Vec<String>>,
 }
 
+impl Config {
+    /// Read the `out_dir` option of each language from the `[bindings]` table of a config file
+    /// in `config_dir`.
+    pub(crate) fn load_out_dirs(&mut self, bindings: &toml::Value, config_dir: &Utf8Path) {
+        for language in ALL_LANGUAGES {
+            let out_dir = bindings
+                .get(language.to_string().as_str())
+                .and_then(|table| table.get("out_dir"))
+                .and_then(toml::Value::as_str);
+            if let Some(out_dir) = out_dir {
+                self.out_dirs.insert(language, config_dir.join(out_dir));
+            }
+        }
+    }
+
+    /// Read the `hidden` option of each language from the `[bindings]` table of a config file
+    pub(crate) fn load_hidden_items(&mut self, bindings: &toml::Value) -> Result<()> {
+        for language in ALL_LANGUAGES {
+            let hidden = bindings
+                .get(language.to_string().as_str())
+                .and_then(|table| table.get("hidden"));
+            if let Some(hidden) = hidden {
+                let names = hidden.clone().try_into::<Vec<String>>().map_err(|e| {
+                    anyhow!("`bindings.{language}.hidden` must be a list of item names: {e}")
+                })?;
+                self.hidden.insert(language, names);
+            }
+        }
+        Ok(())
+    }
+
+    /// The directory to write the bindings for `language` to, if the config has one
+    pub fn out_dir(&self, language: TargetLanguage) -> Option<&Utf8Path> {
+        self.out_dirs.get(&language).map(Utf8PathBuf::as_path)
+    }
+
+    /// The languages to generate when none are passed, which are the ones with an `out_dir`
+    pub fn configured_languages(&self) -> Vec<TargetLanguage> {
+        self.out_dirs.keys().copied().collect()
+    }
+
+    /// The items to leave out of the bindings for `language`
+    pub fn hidden_items(&self, language: TargetLanguage) -> &[String] {
+        self.hidden.get(&language).map_or(&[], Vec::as_slice)
+    }
+}
+
+const ALL_LANGUAGES: [TargetLanguage; 6] = [
+    TargetLanguage::Kotlin,
+    TargetLanguage::Swift,
+    TargetLanguage::Python,
+    TargetLanguage::Ruby,
+    TargetLanguage::Wit,
+    TargetLanguage::C,
+];
+
 /// Generate foreign language bindings from a compiled `uniffi` library.
 pub fn write_bindings(
     config: &Config,
@@ -109,6 +184,16 @@ pub fn write_bindings(
     language: TargetLanguage,
     try_format_code: bool,
 ) -> Result<()> {
+    let out_dir = config.out_dir(language).unwrap_or(out_dir);
+    fs::create_dir_all(out_dir)?;
+    let visible_ci;
+    let ci = match config.hidden_items(language) {
+        [] => ci,
+        hidden => {
+            visible_ci = ci.without_hidden_items(hidden)?;
+            &visible_ci
+        }
+    };
     match language {
         TargetLanguage::Kotlin => {
             kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?
@@ -120,6 +205,8 @@ pub fn write_bindings(
             python::write_bindings(&config.python, ci, out_dir, try_format_code)?
         }
         TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
+        TargetLanguage::Wit => wit::write_bindings(&config.wit, ci, out_dir, try_format_code)?,
+        TargetLanguage::C => c::write_bindings(&config.c, ci, out_dir, try_format_code)?,
     }
     Ok(())
 }
@@ -0,0 +1,86 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Python bindings that call the scaffolding through a cffi extension module
+//!
+//! These have the same classes and functions as the `ctypes` bindings, but the scaffolding
+//! functions are called through an extension module that cffi compiles in its "out-of-line API
+//! mode", from a build script that's generated alongside the bindings.  That cuts the cost of each
+//! call, since cffi converts the arguments in C rather than in Python.
+//!
+//! `RustBuffer`s and the call status are plain C structs in this mode, so only the parts of the
+//! runtime that handle them are replaced, see `CffiRuntime.py`.  The features whose support code
+//! needs `ctypes`, like callback interfaces and async functions, aren't supported.
+
+use std::borrow::Borrow;
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+
+use super::Config;
+use crate::interface::*;
+
+// The support code of these features declares `ctypes` types or calls `ctypes` functions
+pub(super) fn check_cffi_support(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    let mut unsupported = vec![];
+    if ci.has_async_fns() {
+        unsupported.push("async functions".to_string());
+    }
+    for type_ in ci.iter_types() {
+        match type_ {
+            Type::ForeignExecutor => unsupported.push(format!("the {type_:?} type")),
+            Type::Shared { .. } => unsupported.push("shared values".to_string()),
+            Type::CallbackInterface { name, .. } => {
+                unsupported.push(format!("callback interface `{name}`"))
+            }
+            Type::External { name, .. } => unsupported.push(format!("external type `{name}`")),
+            Type::Record { name, .. }
+                if ci.get_record_definition(name).map(|r| r.is_repr_c()) == Some(true) =>
+            {
+                unsupported.push(format!("`#[repr(C)]` record `{name}`"));
+            }
+            _ => {}
+        }
+    }
+    let borrows_bytes = ci
+        .iter_callables()
+        .flat_map(|c| c.arguments())
+        .any(|a| a.borrows_bytes());
+    if borrows_bytes {
+        unsupported.push("`[ByRef] bytes` arguments".to_string());
+    }
+    if config.zero_copy_bytes() {
+        unsupported.push("the `zero_copy_bytes` option".to_string());
+    }
+    if config.hot_reload() {
+        unsupported.push("the `hot_reload` option".to_string());
+    }
+    if !unsupported.is_empty() {
+        bail!(
+            "Python bindings with the `cffi` FFI backend don't support {}",
+            unsupported.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Generate the script that builds the cffi extension module of the bindings
+pub fn generate_cffi_build_script(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    check_cffi_support(config, ci)?;
+    CffiBuildScript { config, ci }
+        .render()
+        .context("failed to render cffi build script")
+}
+
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "CffiBuild.py")]
+struct CffiBuildScript<'a> {
+    config: &'a Config,
+    ci: &'a ComponentInterface,
+}
+
+// The declarations are written in C, like the C header
+mod filters {
+    pub use crate::bindings::c::gen_c::filters::{c_identifier, header_ffi_type_name};
+}
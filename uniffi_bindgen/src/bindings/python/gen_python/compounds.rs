@@ -87,7 +87,12 @@ impl MapCodeType {
 
 impl CodeType for MapCodeType {
     fn type_label(&self) -> String {
-        "dict".to_string()
+        // Python 3.8 and below do not support `dict[K, V]`
+        format!(
+            "typing.Dict[{}, {}]",
+            super::PythonCodeOracle.find(&self.key).type_label(),
+            super::PythonCodeOracle.find(&self.value).type_label(),
+        )
     }
 
     fn canonical_name(&self) -> String {
@@ -105,3 +110,85 @@ impl CodeType for MapCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct CompressedCodeType {
+    inner: Type,
+}
+
+impl CompressedCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+// Compressed values are presented as their inner type, only the FfiConverter differs.
+impl CodeType for CompressedCodeType {
+    fn type_label(&self) -> String {
+        super::PythonCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Compressed{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::PythonCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
+#[derive(Debug)]
+pub struct SharedCodeType {
+    inner: Type,
+}
+
+impl SharedCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+// Shared values are presented as their inner type, only the FfiConverter differs.
+impl CodeType for SharedCodeType {
+    fn type_label(&self) -> String {
+        super::PythonCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Shared{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::PythonCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
+#[derive(Debug)]
+pub struct WeakCodeType {
+    inner: Type,
+}
+
+impl WeakCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for WeakCodeType {
+    fn type_label(&self) -> String {
+        "WeakRef".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Weak{}",
+            super::PythonCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+}
@@ -0,0 +1,26 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::backend::CodeType;
+
+#[derive(Debug)]
+pub struct FlagsCodeType {
+    id: String,
+}
+
+impl FlagsCodeType {
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+impl CodeType for FlagsCodeType {
+    fn type_label(&self) -> String {
+        super::PythonCodeOracle.class_name(&self.id)
+    }
+
+    fn canonical_name(&self) -> String {
+        format!("Type{}", self.id)
+    }
+}
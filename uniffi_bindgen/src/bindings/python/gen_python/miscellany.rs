@@ -31,3 +31,17 @@ macro_rules! impl_code_type_for_miscellany {
 impl_code_type_for_miscellany!(TimestampCodeType, "Timestamp");
 
 impl_code_type_for_miscellany!(DurationCodeType, "Duration");
+
+impl_code_type_for_miscellany!(UrlCodeType, "Url");
+
+impl_code_type_for_miscellany!(UuidCodeType, "Uuid");
+
+impl_code_type_for_miscellany!(DecimalCodeType, "Decimal");
+
+impl_code_type_for_miscellany!(JsonCodeType, "Json");
+
+impl_code_type_for_miscellany!(PathCodeType, "Path");
+
+impl_code_type_for_miscellany!(IpAddrCodeType, "IpAddr");
+
+impl_code_type_for_miscellany!(SocketAddrCodeType, "SocketAddr");
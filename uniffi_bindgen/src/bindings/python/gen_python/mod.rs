@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use askama::Template;
 use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use once_cell::sync::Lazy;
@@ -11,21 +11,26 @@ use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-use crate::backend::{CodeType, TemplateExpression};
+use crate::backend::{CodeHooks, CodeType, FieldNaming, TemplateExpression};
+use crate::ide_index::{ItemKind, ItemRef};
 use crate::interface::*;
 use crate::BindingsConfig;
 
 mod callback_interface;
+mod cffi;
 mod compounds;
 mod custom;
 mod enum_;
 mod executor;
 mod external;
+mod flags;
 mod miscellany;
 mod object;
 mod primitives;
 mod record;
 
+pub use cffi::generate_cffi_build_script;
+
 // Taken from Python's `keyword.py` module.
 static KEYWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
     let kwlist = vec![
@@ -73,8 +78,32 @@ static KEYWORDS: Lazy<HashSet<String>> = Lazy::new(|| {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     cdylib_name: Option<String>,
+    module_name: Option<String>,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
+    #[serde(default)]
+    external_packages: HashMap<String, String>,
+    usage_hooks: Option<bool>,
+    callback_conformance_checks: Option<bool>,
+    zero_copy_bytes: Option<bool>,
+    type_stubs: Option<bool>,
+    field_naming: Option<FieldNaming>,
+    ffi_backend: Option<FfiBackend>,
+    hot_reload: Option<bool>,
+    unstable_remote_calls: Option<bool>,
+    #[serde(flatten)]
+    code_hooks: CodeHooks,
+}
+
+/// How the bindings call the scaffolding functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FfiBackend {
+    /// Through `ctypes`, which needs no native code besides the Rust library
+    #[serde(rename = "ctypes")]
+    Ctypes,
+    /// Through a cffi extension module, built from a generated script
+    #[serde(rename = "cffi")]
+    Cffi,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -94,6 +123,83 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// The name of the generated Python module, without the `.py` extension.
+    pub fn module_name(&self) -> String {
+        if let Some(module_name) = &self.module_name {
+            module_name.clone()
+        } else {
+            "uniffi".into()
+        }
+    }
+
+    /// The module that defines an external type, from `external_packages` or the name of the
+    /// type's crate.
+    fn external_type_module_name(&self, module_path: &str) -> String {
+        let crate_name = module_path.split("::").next().unwrap();
+        match self.external_packages.get(crate_name) {
+            Some(name) => name.clone(),
+            None => PythonCodeOracle.fn_name(crate_name),
+        }
+    }
+
+    /// Whether to generate a hook that's called with the name of each API as it's used.
+    pub fn usage_hooks(&self) -> bool {
+        self.usage_hooks.unwrap_or(false)
+    }
+
+    /// Whether to generate conformance checks for the foreign implementations of callback interfaces.
+    pub fn callback_conformance_checks(&self) -> bool {
+        self.callback_conformance_checks.unwrap_or(false)
+    }
+
+    /// Whether `bytes` and `Vec<u8>` returned by functions and methods are read in place, without
+    /// copying them.
+    pub fn zero_copy_bytes(&self) -> bool {
+        self.zero_copy_bytes.unwrap_or(false)
+    }
+
+    /// Whether values of `type_` returned by functions and methods are read in place.
+    pub fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.zero_copy_bytes() && crate::backend::is_byte_buffer(type_)
+    }
+
+    /// Whether to write a `.pyi` file with type stubs for the generated API next to the bindings.
+    pub fn type_stubs(&self) -> bool {
+        self.type_stubs.unwrap_or(false)
+    }
+
+    /// How record fields and function arguments are cased, snake_case by default.
+    pub fn field_naming(&self) -> FieldNaming {
+        self.field_naming.unwrap_or(FieldNaming::SnakeCase)
+    }
+
+    /// How the bindings call the scaffolding functions, through `ctypes` by default.
+    pub fn ffi_backend(&self) -> FfiBackend {
+        self.ffi_backend.unwrap_or(FfiBackend::Ctypes)
+    }
+
+    /// Whether the bindings can reload the library after it was rebuilt, with `uniffi_reload()`.
+    /// This is meant for development, see `HotReload.py`.
+    pub fn hot_reload(&self) -> bool {
+        self.hot_reload.unwrap_or(false)
+    }
+
+    /// Whether the functions can be called from a process that doesn't load the library, through
+    /// a transport set with `uniffi_set_remote_transport()`.  See `RemoteCalls.py`.
+    pub fn remote_calls(&self) -> bool {
+        self.unstable_remote_calls.unwrap_or(false)
+    }
+
+    /// The name of the cffi extension module, which is imported from the bindings' directory.
+    pub fn cffi_module_name(&self) -> String {
+        format!("_uniffi_{}", self.module_name())
+    }
+
+    /// Code from `uniffi.toml` to insert into the generated bindings
+    pub fn code_hooks(&self) -> &CodeHooks {
+        &self.code_hooks
+    }
 }
 
 impl BindingsConfig for Config {
@@ -102,6 +208,8 @@ impl BindingsConfig for Config {
     fn update_from_ci(&mut self, ci: &ComponentInterface) {
         self.cdylib_name
             .get_or_insert_with(|| format!("uniffi_{}", ci.namespace()));
+        self.module_name
+            .get_or_insert_with(|| ci.namespace().to_string());
     }
 
     fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
@@ -109,16 +217,76 @@ impl BindingsConfig for Config {
             .get_or_insert_with(|| cdylib_name.to_string());
     }
 
-    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+    fn update_from_dependency_configs(&mut self, config_map: HashMap<&str, &Self>) {
+        for (crate_name, config) in config_map {
+            if !self.external_packages.contains_key(crate_name) {
+                self.external_packages
+                    .insert(crate_name.to_string(), config.module_name());
+            }
+        }
+    }
 }
 
 // Generate python bindings for the given ComponentInterface, as a string.
 pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    if config.ffi_backend() == FfiBackend::Cffi {
+        cffi::check_cffi_support(config, ci)?;
+    }
+    if config.remote_calls() {
+        check_remote_calls_support(config)?;
+    }
     PythonWrapper::new(config.clone(), ci)
         .render()
         .context("failed to render python bindings")
 }
 
+// The remote call support code is written for the `ctypes` runtime, and reloading the library
+// doesn't work in a process that may not have loaded it.
+fn check_remote_calls_support(config: &Config) -> Result<()> {
+    if config.ffi_backend() == FfiBackend::Cffi {
+        bail!(
+            "Python bindings with the `cffi` FFI backend don't support the `unstable_remote_calls` option"
+        );
+    }
+    if config.hot_reload() {
+        bail!(
+            "The `unstable_remote_calls` and `hot_reload` options of the Python bindings can't be combined"
+        );
+    }
+    Ok(())
+}
+
+// Generate the `.pyi` type stubs for the given ComponentInterface, as a string.
+pub fn generate_python_stubs(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    PythonStubs {
+        config: config.clone(),
+        ci,
+    }
+    .render()
+    .context("failed to render python type stubs")
+}
+
+/// Get the fully-qualified name of the Python symbol generated for an item, for the IDE index
+pub(crate) fn ide_symbol(config: &Config, item: &ItemRef<'_>) -> String {
+    let oracle = PythonCodeOracle;
+    let module_name = config.module_name();
+    match (item.kind, item.parent) {
+        (ItemKind::Constructor, Some(parent)) if item.name == "new" => {
+            format!("{module_name}.{}.__init__", oracle.class_name(parent))
+        }
+        (_, Some(parent)) => format!(
+            "{module_name}.{}.{}",
+            oracle.class_name(parent),
+            oracle.fn_name(item.name)
+        ),
+        (ItemKind::Function, None) => match item.sub_namespace {
+            Some(ns) => format!("{module_name}.{ns}.{}", oracle.fn_name(item.name)),
+            None => format!("{module_name}.{}", oracle.fn_name(item.name)),
+        },
+        (_, None) => format!("{module_name}.{}", oracle.class_name(item.name)),
+    }
+}
+
 /// A struct to record a Python import statement.
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum ImportRequirement {
@@ -180,8 +348,66 @@ impl<'a> TypeRenderer<'a> {
         }
     }
 
+    // Get the module name for an external type
+    fn external_type_module_name(&self, module_path: &str) -> String {
+        self.python_config.external_type_module_name(module_path)
+    }
+
     // The following methods are used by the `Types.py` macros.
 
+    // Whether to record usage of the generated API, see `macros.py`
+    fn usage_hooks(&self) -> bool {
+        self.python_config.usage_hooks()
+    }
+
+    // Whether the library may not be loaded, in a process that makes remote calls.  See
+    // `RemoteCalls.py`.
+    fn remote_calls(&self) -> bool {
+        self.python_config.remote_calls()
+    }
+
+    // Whether to generate `{Name}Conformance` classes, see `CallbackConformance.py`
+    fn callback_conformance_checks(&self) -> bool {
+        self.python_config.callback_conformance_checks()
+    }
+
+    // Whether `bytes` returned by functions and methods are lifted into a `memoryview`, see
+    // `ZeroCopyBytes.py`
+    fn zero_copy_bytes(&self) -> bool {
+        self.python_config.zero_copy_bytes()
+    }
+
+    // Code from `uniffi.toml` to insert after the definitions of types
+    fn code_hooks(&self) -> &CodeHooks {
+        self.python_config.code_hooks()
+    }
+
+    // Whether the scaffolding is called through cffi, where object pointers are `void *` cdata
+    // rather than integers, see `ObjectTemplate.py`
+    fn cffi(&self) -> bool {
+        self.python_config.ffi_backend() == FfiBackend::Cffi
+    }
+
+    // Whether objects remember which library they're from, so that they can't be used after it
+    // was reloaded, see `HotReload.py`
+    fn hot_reload(&self) -> bool {
+        self.python_config.hot_reload()
+    }
+
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.python_config.is_zero_copy_bytes(type_)
+    }
+
+    // The Python rendering of a record field or function argument, see `Config::field_naming()`
+    fn field_name(&self, nm: &str) -> String {
+        PythonCodeOracle.field_name(nm, self.python_config.field_naming())
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+
     // Helper for the including a template, but only once.
     //
     // The first time this is called with a name it will return true, indicating that we should
@@ -251,6 +477,71 @@ impl<'a> PythonWrapper<'a> {
     pub fn imports(&self) -> Vec<ImportRequirement> {
         self.type_imports.iter().cloned().collect()
     }
+
+    // Whether to record usage of the generated API, see `macros.py`
+    fn usage_hooks(&self) -> bool {
+        self.config.usage_hooks()
+    }
+
+    // Whether `bytes` returned by functions and methods are lifted into a `memoryview`
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.config.is_zero_copy_bytes(type_)
+    }
+
+    // Whether the scaffolding is called through cffi, see `CffiRuntime.py`
+    fn cffi(&self) -> bool {
+        self.config.ffi_backend() == FfiBackend::Cffi
+    }
+
+    // Whether `func` calls its remote entry point when there's a remote transport, see
+    // `RemoteCalls.py`
+    fn is_remote_callable(&self, func: &Function) -> bool {
+        self.config.remote_calls() && self.ci.is_remote_callable(func)
+    }
+
+    // Whether the scaffolding registers a callback for the `ForeignExecutor` type, which needs to
+    // be registered again when the library is reloaded
+    fn has_foreign_executor(&self) -> bool {
+        self.ci
+            .iter_types()
+            .any(|t| matches!(t, Type::ForeignExecutor))
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        PythonCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+}
+
+/// Type stubs for the generated module, for type checkers
+///
+/// The generated code is annotated too, but type checkers can't follow all of it: the builtin
+/// type aliases are defined at runtime and enum variants are added to their classes dynamically.
+#[derive(Template)]
+#[template(syntax = "py", escape = "none", path = "Stubs.pyi")]
+pub struct PythonStubs<'a> {
+    ci: &'a ComponentInterface,
+    config: Config,
+}
+
+impl<'a> PythonStubs<'a> {
+    // Whether `bytes` returned by functions and methods are lifted into a `memoryview`
+    fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.config.is_zero_copy_bytes(type_)
+    }
+
+    fn field_name(&self, nm: &str) -> String {
+        PythonCodeOracle.field_name(nm, self.config.field_naming())
+    }
+
+    // Get the module to import an external type from, like `ExternalTemplate.py` does
+    fn external_type_module_name(&self, module_path: &str) -> String {
+        self.config.external_type_module_name(module_path)
+    }
 }
 
 fn fixup_keyword(name: String) -> String {
@@ -284,6 +575,11 @@ impl PythonCodeOracle {
         fixup_keyword(nm.to_string().to_snake_case())
     }
 
+    /// Get the Python rendering of a record field or function argument.
+    fn field_name(&self, nm: &str, naming: FieldNaming) -> String {
+        fixup_keyword(naming.apply(nm))
+    }
+
     /// Get the idiomatic Python rendering of an individual enum variant.
     fn enum_variant_name(&self, nm: &str) -> String {
         fixup_keyword(nm.to_string().to_shouty_snake_case())
@@ -318,6 +614,7 @@ impl PythonCodeOracle {
                 )
             }
             FfiType::FutureCallbackData => "ctypes.c_size_t".to_string(),
+            FfiType::Struct(name) => format!("_UniffiReprC{name}"),
         }
     }
 }
@@ -347,13 +644,22 @@ impl<T: AsType> AsCodeType for T {
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::CharCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::IpAddr => Box::new(miscellany::IpAddrCodeType),
+            Type::SocketAddr => Box::new(miscellany::SocketAddrCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
+            Type::Flags { name, .. } => Box::new(flags::FlagsCodeType::new(name)),
             Type::Object { name, .. } => Box::new(object::ObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
@@ -370,6 +676,11 @@ impl<T: AsType> AsCodeType for T {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Compressed { inner_type } => {
+                Box::new(compounds::CompressedCodeType::new(*inner_type))
+            }
+            Type::Shared { inner_type } => Box::new(compounds::SharedCodeType::new(*inner_type)),
+            Type::Weak { inner_type } => Box::new(compounds::WeakCodeType::new(*inner_type)),
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -451,4 +762,85 @@ pub mod filters {
     pub fn enum_variant_py(nm: &str) -> Result<String, askama::Error> {
         Ok(PythonCodeOracle.enum_variant_name(nm))
     }
+
+    /// Render a docstring as a Python docstring, indented by `spaces`
+    pub fn docstring(docstring: &str, spaces: i32) -> Result<String, askama::Error> {
+        let indent = " ".repeat(spaces as usize);
+        let docstring = docstring
+            .replace('\\', "\\\\")
+            .replace("\"\"\"", "\\\"\"\"");
+        // A one-line docstring can't end with a quote, which would close it early
+        if !docstring.contains('\n') && !docstring.ends_with('"') {
+            return Ok(format!("{indent}\"\"\"{docstring}\"\"\""));
+        }
+        let body = docstring
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".to_string()
+                } else {
+                    format!("{indent}{line}\n")
+                }
+            })
+            .collect::<String>();
+        Ok(format!("{indent}\"\"\"\n{body}{indent}\"\"\""))
+    }
+}
+
+#[cfg(test)]
+mod test_stubs {
+    use super::*;
+
+    #[test]
+    fn test_external_types_are_imported() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {
+                UniffiOneType get_one();
+            };
+            [External="uniffi_one"]
+            typedef extern UniffiOneType;
+            "#,
+        )
+        .unwrap();
+        let stubs = generate_python_stubs(&Config::default(), &ci).unwrap();
+        assert!(stubs.contains("\nfrom uniffi_one import UniffiOneType\n"));
+    }
+
+    #[test]
+    fn test_external_type_module_name() {
+        let ci = ComponentInterface::default();
+        let stubs = PythonStubs {
+            ci: &ci,
+            config: Config::default(),
+        };
+        // Types exported with the proc-macros have the full module path
+        assert_eq!(
+            stubs.external_type_module_name("uniffi_one::types"),
+            "uniffi_one"
+        );
+    }
+
+    #[test]
+    fn test_external_packages_are_imported() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace test {
+                UniffiOneType get_one();
+            };
+            [External="uniffi_one"]
+            typedef extern UniffiOneType;
+            "#,
+        )
+        .unwrap();
+        let config: Config = toml::from_str(
+            r#"
+            [external_packages]
+            uniffi_one = "my_package.uniffi_one"
+            "#,
+        )
+        .unwrap();
+        let stubs = generate_python_stubs(&config, &ci).unwrap();
+        assert!(stubs.contains("\nfrom my_package.uniffi_one import UniffiOneType\n"));
+    }
 }
@@ -56,6 +56,7 @@ macro_rules! impl_code_type_for_primitive {
 }
 
 impl_code_type_for_primitive!(BooleanCodeType, "bool", "Bool");
+impl_code_type_for_primitive!(CharCodeType, "str", "Char");
 impl_code_type_for_primitive!(StringCodeType, "str", "String");
 impl_code_type_for_primitive!(BytesCodeType, "bytes", "Bytes");
 impl_code_type_for_primitive!(Int8CodeType, "int", "Int8");
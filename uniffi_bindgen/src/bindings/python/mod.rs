@@ -11,8 +11,10 @@ use fs_err as fs;
 pub mod gen_python;
 mod test;
 use super::super::interface::ComponentInterface;
-pub use gen_python::{generate_python_bindings, Config};
-pub use test::{run_script, run_test};
+pub use gen_python::{
+    generate_cffi_build_script, generate_python_bindings, generate_python_stubs, Config, FfiBackend,
+};
+pub use test::{run_pytest, run_script, run_test};
 
 // Generate python bindings for the given ComponentInterface, in the given output directory.
 pub fn write_bindings(
@@ -21,9 +23,19 @@ pub fn write_bindings(
     out_dir: &Utf8Path,
     try_format_code: bool,
 ) -> Result<()> {
-    let py_file = out_dir.join(format!("{}.py", ci.namespace()));
+    let py_file = out_dir.join(format!("{}.py", config.module_name()));
     fs::write(&py_file, generate_python_bindings(config, ci)?)?;
 
+    if config.type_stubs() {
+        let pyi_file = out_dir.join(format!("{}.pyi", config.module_name()));
+        fs::write(pyi_file, generate_python_stubs(config, ci)?)?;
+    }
+
+    if config.ffi_backend() == FfiBackend::Cffi {
+        let build_file = out_dir.join(format!("{}_cffi_build.py", config.module_name()));
+        fs::write(build_file, generate_cffi_build_script(config, ci)?)?;
+    }
+
     if try_format_code {
         if let Err(e) = Command::new("yapf").arg(&py_file).output() {
             println!(
@@ -5,14 +5,8 @@ _UniffiPyFuturePointerManager = _UniffiPointerManager()
 # Callback handlers for an async calls.  These are invoked by Rust when the future is ready.  They
 # lift the return value or error and resolve the future, causing the async function to resume.
 {%- for result_type in ci.iter_async_result_types() %}
-@_uniffi_future_callback_t(
-        {%- match result_type.return_type -%}
-        {%- when Some(return_type) -%}
-        {{ return_type|ffi_type|ffi_type_name }}
-        {%- when None -%}
-        ctypes.c_uint8
-        {%- endmatch -%}
-)
+{%- let callback_param = ci.future_callback_param(result_type) %}
+@_uniffi_future_callback_t({{ callback_param|ffi_type_name }})
 def {{ result_type|async_callback_fn }}(future_ptr, result, call_status):
     future = _UniffiPyFuturePointerManager.release_pointer(future_ptr)
     if future.cancelled():
@@ -0,0 +1,34 @@
+{% if self.include_once_check("CallbackConformanceRuntime.py") %}{% include "CallbackConformanceRuntime.py" %}{% endif %}
+{{- self.add_import("threading") }}
+{{- self.add_import("time") }}
+
+# Checks that `implementation` behaves the way Rust expects implementations of `{{ type_name }}` to
+# behave, see `_uniffi_check_callback_conformance()`.
+#
+# Each `check` method calls the corresponding method of `implementation` with the given arguments.
+class {{ type_name }}Conformance:
+    def __init__(self, implementation, threads=4, iterations=25, timeout=10.0):
+        self.implementation = implementation
+        self.threads = threads
+        self.iterations = iterations
+        self.timeout = timeout
+
+    {% for meth in cbi.methods() -%}
+    def {{ format!("check_{}", meth.name())|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+        _uniffi_check_callback_conformance(
+            "{{ type_name }}.{{ meth.name()|fn_name }}",
+            self.threads,
+            self.iterations,
+            self.timeout,
+            {%- match meth.throws_type() %}
+            {%- when Some(err) %}
+            {{ err|type_name }},
+            {%- when None %}
+            None,
+            {%- endmatch %}
+            lambda: self.implementation.{{ meth.name()|fn_name }}(
+                {%- for arg in meth.arguments() %}{{ self.field_name(arg.name()) }}{% if !loop.last %}, {% endif %}{% endfor -%}
+            ),
+        )
+
+    {% endfor %}
@@ -0,0 +1,44 @@
+# Calls `call` the way Rust calls callback interface methods, raising `AssertionError` if the
+# implementation doesn't behave the way the Rust side expects:
+#
+#   - Errors: any exception other than the method's declared error type (`declared_error`, or
+#     `None` if the method doesn't declare one) is seen by Rust as an unexpected error.
+#   - Ordering: the method is called `iterations` times in a row from the current thread and each
+#     call must return before the next one is made.
+#   - Threading: Rust may call the method from any thread, so it's then called `iterations` times
+#     from each of `threads` threads at once, all of which must finish within `timeout` seconds.
+#
+# This is only generated when `callback_conformance_checks` is enabled in `uniffi.toml`.
+def _uniffi_check_callback_conformance(name, threads, iterations, timeout, declared_error, call):
+    def checked_call():
+        try:
+            call()
+        except BaseException as e:
+            if declared_error is None or not isinstance(e, declared_error):
+                raise AssertionError("{} raised {!r}, which Rust sees as an unexpected error".format(name, e)) from e
+
+    for _ in range(iterations):
+        checked_call()
+
+    failures = []
+    start = threading.Event()
+
+    def run():
+        start.wait()
+        try:
+            for _ in range(iterations):
+                checked_call()
+        except BaseException as e:
+            failures.append(e)
+
+    workers = [threading.Thread(target=run, daemon=True) for _ in range(threads)]
+    for worker in workers:
+        worker.start()
+    start.set()
+    deadline = time.monotonic() + timeout
+    for worker in workers:
+        worker.join(max(deadline - time.monotonic(), 0))
+        if worker.is_alive():
+            raise AssertionError("{} didn't return within {}s when called from {} threads".format(name, timeout, threads))
+    if failures:
+        raise failures[0]
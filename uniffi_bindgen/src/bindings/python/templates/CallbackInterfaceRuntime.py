@@ -1,55 +1,65 @@
-import threading
+import itertools
 
 class ConcurrentHandleMap:
     """
-    A map where inserting, getting and removing data is synchronized with a lock.
+    Maps handles to callback objects, without taking a lock.
+
+    Every call to `insert()` returns a fresh handle, even if the object was inserted before: each
+    handle is owned by exactly one Rust-side reference, which removes it when it's dropped.  Handles
+    come from a counter, so they're never reused.  This means that using a handle after it's been
+    removed fails the lookup, rather than returning some other object.
+
+    Taking the next value of an `itertools.count` and the single dict operations used here are
+    atomic under the GIL.
     """
 
     def __init__(self):
         # type Handle = int
-        self._left_map = {}  # type: Dict[Handle, Any]
-        self._right_map = {}  # type: Dict[Any, Handle]
-
-        self._lock = threading.Lock()
-        self._current_handle = 0
-        self._stride = 1
-
+        self._map = {}  # type: Dict[Handle, Any]
+        self._handles = itertools.count()
 
     def insert(self, obj):
-        with self._lock:
-            if obj in self._right_map:
-                return self._right_map[obj]
-            else:
-                handle = self._current_handle
-                self._current_handle += self._stride
-                self._left_map[handle] = obj
-                self._right_map[obj] = handle
-                return handle
+        handle = next(self._handles)
+        self._map[handle] = obj
+        return handle
 
     def get(self, handle):
-        with self._lock:
-            return self._left_map.get(handle)
+        return self._map.get(handle)
 
     def remove(self, handle):
-        with self._lock:
-            if handle in self._left_map:
-                obj = self._left_map.pop(handle)
-                del self._right_map[obj]
-                return obj
+        return self._map.pop(handle, None)
+
+    # The handles of an object that was inserted, see `uniffi_detach()`.  Objects are compared by
+    # identity, so that they don't need to be hashable.
+    def handles_of(self, obj):
+        return [handle for handle, value in list(self._map.items()) if value is obj]
 
 # Magic number for the Rust proxy to call using the same mechanism as every other method,
 # to free the callback once it's dropped by Rust.
 IDX_CALLBACK_FREE = 0
+# Reads the value of a constant, see `uniffi_core/src/ffi/foreigncallbacks.rs`
+IDX_CALLBACK_CONSTANT = 2147483647
 # Return codes for callback calls
 _UNIFFI_CALLBACK_SUCCESS = 0
 _UNIFFI_CALLBACK_ERROR = 1
 _UNIFFI_CALLBACK_UNEXPECTED_ERROR = 2
 
+# Serializes an unexpected error for Rust: the exception's class name, then its message.  Errors
+# in the bindings' own code use an empty class name.
+# See `CallbackError` in `uniffi_core/src/ffi/foreigncallbacks.rs`
+def _uniffi_lower_unexpected_error(class_name, message):
+    with _UniffiRustBuffer.alloc_with_builder() as builder:
+        {{ Type::String.borrow()|ffi_converter_name }}.write(class_name, builder)
+        {{ Type::String.borrow()|ffi_converter_name }}.write(message, builder)
+        return builder.finalize()
+
 class _UniffiConverterCallbackInterface:
     _handle_map = ConcurrentHandleMap()
 
-    def __init__(self, cb):
+    def __init__(self, cb, name, method_names):
         self._foreign_callback = cb
+        self._name = name
+        self._method_names = method_names
 
     def drop(self, handle):
         self.__class__._handle_map.remove(handle)
@@ -67,11 +77,18 @@ class _UniffiConverterCallbackInterface:
         handle = buf.read_u64()
         cls.lift(handle)
 
-    @classmethod
-    def lower(cls, cb):
-        handle = cls._handle_map.insert(cb)
+    def check(self, cb):
+        # Callback interfaces are protocols, so check the object's methods rather than its class.
+        # Without this, a missing method would only show up when Rust calls it.
+        missing = [name for name in self._method_names if not callable(getattr(cb, name, None))]
+        if missing:
+            raise TypeError("{} is not a {}, it's missing: {}".format(
+                type(cb).__name__, self._name, ", ".join(missing)))
+
+    def lower(self, cb):
+        self.check(cb)
+        handle = self.__class__._handle_map.insert(cb)
         return handle
 
-    @classmethod
-    def write(cls, cb, buf):
-        buf.write_u64(cls.lower(cb))
+    def write(self, cb, buf):
+        buf.write_u64(self.lower(cb))
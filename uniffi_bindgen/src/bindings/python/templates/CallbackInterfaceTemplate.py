@@ -4,30 +4,71 @@
 {% if self.include_once_check("CallbackInterfaceRuntime.py") %}{% include "CallbackInterfaceRuntime.py" %}{% endif %}
 
 # Declaration and _UniffiConverters for {{ type_name }} Callback Interface
+{%- if cbi.is_closure() %}
+{%- let meth = cbi.methods()[0] %}
+
+{{ type_name }} = typing.Callable[[
+    {%- for arg in meth.arguments() %}"{{ arg|type_name }}"{% if !loop.last %}, {% endif %}{% endfor -%}
+], {% match meth.return_type() %}{% when Some with (return_type) %}"{{ return_type|type_name }}"{% else %}None{% endmatch %}]
+{%- else %}
+
+# Any object with these methods can be passed as a {{ type_name }}, it doesn't need to inherit
+# from this class.  Constants that the object doesn't define get the values declared here, and
+# methods with a default implementation call the one in Rust.
+class {{ type_name }}(typing.Protocol):
+    {%- call py::docstring(cbi, 4) %}
+    {%- for c in cbi.constants() %}
+    {{ c.name() }} = {{ c.value()|literal_py(c) }}
+    {%- endfor %}
 
-class {{ type_name }}:
     {% for meth in cbi.methods() -%}
     def {{ meth.name()|fn_name }}(self, {% call py::arg_list_decl(meth) %}):
+        {%- call py::docstring(meth, 8) %}
+        {%- if meth.has_default() %}
+        {%- call py::setup_args_extra_indent(meth) %}
+        {%- match meth.return_type() %}
+        {%- when Some(return_type) %}
+        return {{ return_type|lift_fn }}(
+            {% call py::to_ffi_call_with_prefix(format!("{}.lower(self)", ffi_converter_name), meth) %}
+        )
+        {%- when None %}
+        {% call py::to_ffi_call_with_prefix(format!("{}.lower(self)", ffi_converter_name), meth) %}
+        {%- endmatch %}
+        {%- else %}
         raise NotImplementedError
+        {%- endif %}
 
     {% endfor %}
+{%- endif %}
 
 def py_{{ foreign_callback }}(handle, method, args_data, args_len, buf_ptr):
     {% for meth in cbi.methods() -%}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name %}
     def {{ method_name }}(python_callback, args_stream, buf_ptr):
+        {%- if cbi.is_closure() %}
+        method = python_callback
+        {%- else if meth.has_default() %}
+        # Objects that don't define the method use the default implementation
+        method = getattr(python_callback, "{{ meth.name()|fn_name }}", None)
+        if method is None:
+            def method(*args):
+                return {{ type_name }}.{{ meth.name()|fn_name }}(python_callback, *args)
+        {%- else %}
+        method = python_callback.{{ meth.name()|fn_name }}
+        {%- endif %}
+
         {#- Unpacking args from the _UniffiRustBuffer #}
         def makeCall():
             {#- Calling the concrete callback object #}
             {%- if meth.arguments().len() != 0 -%}
-            return python_callback.{{ meth.name()|fn_name }}(
+            return method(
                 {% for arg in meth.arguments() -%}
                 {{ arg|read_fn }}(args_stream)
                 {%- if !loop.last %}, {% endif %}
                 {% endfor -%}
             )
             {%- else %}
-            return python_callback.{{ meth.name()|fn_name }}()
+            return method()
             {%- endif %}
 
         def makeCallAndHandleReturn():
@@ -58,33 +99,56 @@ def py_{{ foreign_callback }}(handle, method, args_data, args_len, buf_ptr):
 
     {% endfor %}
 
-    cb = {{ ffi_converter_name }}.lift(handle)
-    if not cb:
-        raise InternalError("No callback in handlemap; this is a uniffi bug")
-
     if method == IDX_CALLBACK_FREE:
         {{ ffi_converter_name }}.drop(handle)
         # Successfull return
         # See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs`
         return _UNIFFI_CALLBACK_SUCCESS
 
+    # The handle is gone if the object was detached while Rust was calling it, see
+    # `uniffi_detach()`
+    try:
+        cb = {{ ffi_converter_name }}.lift(handle)
+    except InternalError as e:
+        buf_ptr[0] = _uniffi_lower_unexpected_error("", str(e))
+        return _UNIFFI_CALLBACK_UNEXPECTED_ERROR
+
     {% for meth in cbi.methods() -%}
     {% let method_name = format!("invoke_{}", meth.name())|fn_name -%}
     if method == {{ loop.index }}:
         # Call the method and handle any errors
         # See docs of ForeignCallback in `uniffi_core/src/ffi/foreigncallbacks.rs` for details
         try:
+            {%- if cbi.main_thread() %}
+            return _uniffi_run_on_main_thread(lambda: {{ method_name }}(cb, _UniffiRustBufferStream(args_data, args_len), buf_ptr))
+            {%- else %}
             return {{ method_name }}(cb, _UniffiRustBufferStream(args_data, args_len), buf_ptr)
+            {%- endif %}
         except BaseException as e:
             # Catch unexpected errors
             try:
-                # Try to serialize the exception into a String
-                buf_ptr[0] = {{ Type::String.borrow()|lower_fn }}(repr(e))
+                # Try to serialize the exception's class name and message
+                buf_ptr[0] = _uniffi_lower_unexpected_error(type(e).__qualname__, str(e))
             except:
                 # If that fails, just give up
                 pass
             return _UNIFFI_CALLBACK_UNEXPECTED_ERROR
     {% endfor %}
+    {%- if !cbi.constants().is_empty() %}
+    if method == IDX_CALLBACK_CONSTANT:
+        # Write the value of the constant whose index is in args_data to buf_ptr.  Implementations
+        # can override the constants with class attributes of their own, the others get the value
+        # declared by the protocol.
+        index = _UniffiRustBufferStream(args_data, args_len).read_u32()
+        {%- for c in cbi.constants() %}
+        if index == {{ loop.index0 }}:
+            with _UniffiRustBuffer.alloc_with_builder() as builder:
+                {{ c|write_fn }}(getattr(cb, "{{ c.name() }}", {{ type_name }}.{{ c.name() }}), builder)
+                buf_ptr[0] = builder.finalize()
+            return _UNIFFI_CALLBACK_SUCCESS
+        {%- endfor %}
+        return _UNIFFI_CALLBACK_UNEXPECTED_ERROR
+    {%- endif %}
 
     # This should never happen, because an out of bounds method index won't
     # ever be used. Once we can catch errors, we should return an InternalException.
@@ -99,7 +163,21 @@ def py_{{ foreign_callback }}(handle, method, args_data, args_len, buf_ptr):
 # that is in freed memory.
 # That would be...uh...bad. Yeah, that's the word. Bad.
 {{ foreign_callback }} = _UNIFFI_FOREIGN_CALLBACK_T(py_{{ foreign_callback }})
+{%- if self.remote_calls() %}
+if _UniffiLib is not None:
+    _rust_call(lambda err: _UniffiLib.{{ cbi.ffi_init_callback().name() }}({{ foreign_callback }}, err))
+{%- else %}
 _rust_call(lambda err: _UniffiLib.{{ cbi.ffi_init_callback().name() }}({{ foreign_callback }}, err))
+{%- endif %}
 
 # The _UniffiConverter which transforms the Callbacks in to Handles to pass to Rust.
-{{ ffi_converter_name }} = _UniffiConverterCallbackInterface({{ foreign_callback }})
+# Only the methods without a default implementation are required, closures only need to be
+# callable.
+{{ ffi_converter_name }} = _UniffiConverterCallbackInterface({{ foreign_callback }}, "{{ type_name }}", [
+    {%- if cbi.is_closure() %}"__call__"
+    {%- else %}
+    {%- for meth in cbi.methods() %}{% if !meth.has_default() %}"{{ meth.name()|fn_name }}", {% endif %}{% endfor -%}
+    {%- endif %}
+])
+
+{% if self.callback_conformance_checks() && !cbi.is_closure() %}{% include "CallbackConformance.py" %}{% endif %}
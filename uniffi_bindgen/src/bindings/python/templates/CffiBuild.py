@@ -0,0 +1,81 @@
+# This file was autogenerated by some hot garbage in the `uniffi` crate.
+# Trust me, you don't want to mess with it!
+
+# Builds the `{{ config.cffi_module_name() }}` extension module, which the `{{ config.module_name() }}` bindings
+# call the scaffolding functions through.  Run this script to build the module next to the
+# bindings, or list it in the `cffi_modules` of `setup.py` to build it with the wheel:
+#
+#     setup(cffi_modules=["src/<package>/{{ config.module_name() }}_cffi_build.py:ffibuilder"])
+#
+# The module links to the `{{ config.cdylib_name() }}` library, which must be in the same directory
+# as this script, and loads it from its own directory.
+
+import os
+import sys
+
+import cffi
+
+_UNIFFI_DECLARATIONS = """
+typedef struct RustBuffer
+{
+    int32_t capacity;
+    int32_t len;
+    uint8_t* data;
+} RustBuffer;
+
+typedef struct ForeignBytes
+{
+    int32_t len;
+    const uint8_t* data;
+} ForeignBytes;
+
+typedef struct RustCallStatus {
+    int8_t code;
+    RustBuffer errorBuf;
+} RustCallStatus;
+{% for func in ci.iter_ffi_function_definitions() %}
+{% match func.return_type() -%}{%- when Some with (type_) %}{{ type_|header_ffi_type_name }}{% when None %}void{% endmatch %} {{ func.name() }}(
+    {%- for arg in func.arguments() %}
+        {{- arg.type_().borrow()|header_ffi_type_name }} {{ arg.name()|c_identifier -}}{% if !loop.last || func.has_rust_call_status_arg() %}, {% endif %}
+    {%- endfor %}
+    {%- if func.has_rust_call_status_arg() %}RustCallStatus* out_status
+    {%- else if func.arguments().is_empty() %}void
+    {%- endif -%}
+);
+{%- endfor %}
+"""
+
+_uniffi_dir = os.path.dirname(os.path.abspath(__file__))
+
+def _uniffi_module_name():
+    # The module goes next to the bindings, so it's part of their package if they're in one.  This
+    # returns its full name and the directory that contains the top-level package.
+    name = "{{ config.cffi_module_name() }}"
+    directory = _uniffi_dir
+    while os.path.exists(os.path.join(directory, "__init__.py")):
+        directory, package = os.path.split(directory)
+        name = package + "." + name
+    return name, directory
+
+if sys.platform == "darwin":
+    _uniffi_link_args = ["-Wl,-rpath,@loader_path"]
+elif sys.platform.startswith("win"):
+    # Windows looks for the DLLs of an extension module in its directory
+    _uniffi_link_args = []
+else:
+    _uniffi_link_args = ["-Wl,-rpath,$ORIGIN"]
+
+_uniffi_name, _uniffi_root = _uniffi_module_name()
+
+ffibuilder = cffi.FFI()
+ffibuilder.cdef(_UNIFFI_DECLARATIONS)
+ffibuilder.set_source(
+    _uniffi_name,
+    "#include <stdint.h>\n" + _UNIFFI_DECLARATIONS,
+    libraries=["{{ config.cdylib_name() }}"],
+    library_dirs=[_uniffi_dir],
+    extra_link_args=_uniffi_link_args,
+)
+
+if __name__ == "__main__":
+    ffibuilder.compile(tmpdir=_uniffi_root, verbose=True)
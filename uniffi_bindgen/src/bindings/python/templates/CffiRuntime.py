@@ -0,0 +1,252 @@
+# The scaffolding functions are called through the `{{ config.cffi_module_name() }}` extension module,
+# which is built by the `{{ config.module_name() }}_cffi_build.py` script that's generated alongside
+# this file.  `RustBuffer`s and the call status are plain C structs, so this replaces the `ctypes`
+# classes with helpers that work on them.
+if __package__:
+    from .{{ config.cffi_module_name() }} import ffi as _uniffi_ffi, lib as _UniffiLib
+else:
+    from {{ config.cffi_module_name() }} import ffi as _uniffi_ffi, lib as _UniffiLib
+
+class _UniffiRustBuffer:
+    """
+    Helpers for the `RustBuffer` structs passed to and from Rust.
+
+    The structs are cffi cdata, which can't have methods, so these are static methods that take the
+    buffer rather than methods of the buffer like with ctypes.
+    """
+
+    @staticmethod
+    def free(rbuf):
+        return _rust_call(_UniffiLib.{{ ci.ffi_rustbuffer_free().name() }}, rbuf)
+
+    @staticmethod
+    def from_raw_parts(capacity, length, address):
+        """A buffer that Rust described by its fields, like the buffers of shared values"""
+        return _uniffi_ffi.new("RustBuffer *", {
+            "capacity": capacity,
+            "len": length,
+            "data": _uniffi_ffi.cast("uint8_t *", address),
+        })[0]
+
+    @staticmethod
+    @contextlib.contextmanager
+    def alloc_with_builder():
+        """Context-manager to build a buffer using a _UniffiRustBufferBuilder.
+
+        The contents are only copied into a `RustBuffer` by `finalize()`, so there's nothing to free
+        if an error occurs.
+        """
+        yield _UniffiRustBufferBuilder()
+
+    @staticmethod
+    @contextlib.contextmanager
+    def consume_with_stream(rbuf):
+        """Context-manager to consume a buffer using a _UniffiRustBufferStream.
+
+        The contents are copied into a `bytes` and the buffer is freed straight away, so it isn't
+        leaked even if an error occurs.
+        """
+        try:
+            data = _uniffi_ffi.buffer(rbuf.data, rbuf.len)[:] if rbuf.len > 0 else b""
+        finally:
+            _UniffiRustBuffer.free(rbuf)
+        s = _UniffiRustBufferStream(data, len(data))
+        yield s
+        if s.remaining() != 0:
+            raise RuntimeError("junk data left in buffer at end of consume_with_stream")
+
+class _UniffiRustBufferStream:
+    """
+    Helper for structured reading of the bytes copied from a `RustBuffer`
+    """
+
+    def __init__(self, data, len):
+        self.data = data
+        self.len = len
+        self.offset = 0
+
+    def remaining(self):
+        return self.len - self.offset
+
+    def _unpack_from(self, size, format):
+        if self.offset + size > self.len:
+            raise InternalError("read past end of rust buffer")
+        value = struct.unpack_from(format, self.data, self.offset)[0]
+        self.offset += size
+        return value
+
+    def read(self, size):
+        if self.offset + size > self.len:
+            raise InternalError("read past end of rust buffer")
+        data = self.data[self.offset:self.offset+size]
+        self.offset += size
+        return data
+
+    def read_i8(self):
+        return self._unpack_from(1, ">b")
+
+    def read_u8(self):
+        return self._unpack_from(1, ">B")
+
+    def read_i16(self):
+        return self._unpack_from(2, ">h")
+
+    def read_u16(self):
+        return self._unpack_from(2, ">H")
+
+    def read_i32(self):
+        return self._unpack_from(4, ">i")
+
+    def read_u32(self):
+        return self._unpack_from(4, ">I")
+
+    def read_i64(self):
+        return self._unpack_from(8, ">q")
+
+    def read_u64(self):
+        return self._unpack_from(8, ">Q")
+
+    def read_float(self):
+        return self._unpack_from(4, ">f")
+
+    def read_double(self):
+        return self._unpack_from(8, ">d")
+
+class _UniffiRustBufferBuilder:
+    """
+    Helper for structured writing of bytes, which are copied into a `RustBuffer` by `finalize()`.
+    """
+
+    def __init__(self):
+        self.data = bytearray()
+
+    def finalize(self):
+        data = self.data
+        self.data = None
+        pointer = _uniffi_ffi.from_buffer("uint8_t[]", data)
+        foreign_bytes = _uniffi_ffi.new("ForeignBytes *", {"len": len(data), "data": pointer})
+        return _rust_call(_UniffiLib.{{ ci.ffi_rustbuffer_from_bytes().name() }}, foreign_bytes[0])
+
+    def _pack_into(self, format, value):
+        self.data += struct.pack(format, value)
+
+    def write(self, value):
+        self.data += value
+
+    def write_i8(self, v):
+        self._pack_into(">b", v)
+
+    def write_u8(self, v):
+        self._pack_into(">B", v)
+
+    def write_i16(self, v):
+        self._pack_into(">h", v)
+
+    def write_u16(self, v):
+        self._pack_into(">H", v)
+
+    def write_i32(self, v):
+        self._pack_into(">i", v)
+
+    def write_u32(self, v):
+        self._pack_into(">I", v)
+
+    def write_i64(self, v):
+        self._pack_into(">q", v)
+
+    def write_u64(self, v):
+        self._pack_into(">Q", v)
+
+    def write_float(self, v):
+        self._pack_into(">f", v)
+
+    def write_double(self, v):
+        self._pack_into(">d", v)
+
+def _uniffi_lower_sensitive(converter, value):
+    """
+    Lower an argument that's marked as sensitive.
+
+    The bytes that the value was written into are zeroed once they've been copied into the
+    `RustBuffer`.  The copies that `bytearray` leaves behind when it grows aren't.
+    """
+    builder = _UniffiRustBufferBuilder()
+    data = builder.data
+    try:
+        converter.write(value, builder)
+        return builder.finalize()
+    finally:
+        data[:] = bytes(len(data))
+
+class InternalError(Exception):
+    """
+    Raised for Rust panics, and for errors of the bindings themselves.
+
+    `rust_backtrace` is the backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled.
+    """
+    def __init__(self, *args, rust_backtrace=None):
+        super().__init__(*args)
+        self.rust_backtrace = rust_backtrace
+
+class InvalidArgumentError(ValueError):
+    """
+    Raised when Rust can't lift an argument, like a zero for a `NonZeroU32`.
+    """
+    pass
+
+# These match the values from the uniffi::rustcalls module
+_UNIFFI_CALL_SUCCESS = 0
+_UNIFFI_CALL_ERROR = 1
+_UNIFFI_CALL_PANIC = 2
+_UNIFFI_CALL_INVALID_ARGUMENT = 3
+
+def _rust_call(fn, *args):
+    # Call a rust function
+    return _rust_call_with_error(None, fn, *args)
+
+def _rust_call_with_error(error_ffi_converter, fn, *args):
+    # Call a rust function and handle any errors
+    #
+    # This function is used for rust calls that return Result<> and therefore can set the CALL_ERROR status code.
+    # error_ffi_converter must be set to the _UniffiConverter for the error class that corresponds to the result.
+    #
+    # `ffi.new()` zero-initializes the status, and frees it when it's garbage collected.
+    call_status = _uniffi_ffi.new("RustCallStatus *")
+    result = fn(*args, call_status)
+    _uniffi_check_call_status(error_ffi_converter, call_status)
+    return result
+
+def _uniffi_lift_panic(stream):
+    # The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+    message = _UniffiConverterString.read(stream)
+    backtrace = _UniffiConverterString.read(stream)
+    return InternalError(message, rust_backtrace=backtrace or None)
+
+def _uniffi_check_call_status(error_ffi_converter, call_status):
+    if call_status.code == _UNIFFI_CALL_SUCCESS:
+        pass
+    elif call_status.code == _UNIFFI_CALL_ERROR:
+        if error_ffi_converter is None:
+            _UniffiRustBuffer.free(call_status.errorBuf)
+            raise InternalError("_rust_call_with_error: CALL_ERROR, but error_ffi_converter is None")
+        else:
+            raise error_ffi_converter.lift(call_status.errorBuf)
+    elif call_status.code == _UNIFFI_CALL_PANIC:
+        # When the rust code sees a panic, it tries to construct a RustBuffer
+        # with the message and the backtrace.  But if that code panics, then it
+        # just sends back an empty buffer.
+        if call_status.errorBuf.len > 0:
+            with _UniffiRustBuffer.consume_with_stream(call_status.errorBuf) as stream:
+                error = _uniffi_lift_panic(stream)
+            raise error
+        else:
+            raise InternalError("Unknown rust panic")
+    elif call_status.code == _UNIFFI_CALL_INVALID_ARGUMENT:
+        raise InvalidArgumentError(_UniffiConverterString.lift(call_status.errorBuf))
+    else:
+        raise InternalError("Invalid RustCallStatus code: {}".format(
+            call_status.code))
+
+{% include "ContractCheck.py" %}
+
+_uniffi_check_contract(_UniffiLib)
@@ -0,0 +1,28 @@
+class _UniffiConverterChar(_UniffiConverterPrimitive):
+    """
+    Chars are one-character strings, passed as their Unicode scalar value.
+    """
+
+    @classmethod
+    def check(cls, value):
+        if not isinstance(value, str) or len(value) != 1:
+            raise TypeError("expected a string of length 1, not {!r}".format(value))
+        if 0xD800 <= ord(value) <= 0xDFFF:
+            raise ValueError("surrogates aren't valid chars: {!r}".format(value))
+        return super().check(value)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_u32())
+
+    @classmethod
+    def write_unchecked(cls, value, buf):
+        buf.write_u32(ord(value))
+
+    @staticmethod
+    def lift(value):
+        return chr(value)
+
+    @staticmethod
+    def lowerUnchecked(value):
+        return ord(value)
@@ -0,0 +1,34 @@
+{{- self.add_import("zlib") }}
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    # Values sent to Rust are never compressed
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_u8(0)
+        {{ inner_ffi_converter }}.write(value, buf)
+
+    @classmethod
+    def read(cls, buf):
+        algorithm = buf.read_u8()
+        if algorithm == 0:
+            return {{ inner_ffi_converter }}.read(buf)
+        elif algorithm == 1:
+            size = buf.read_u32()
+            compressed_size = buf.read_u32()
+            try:
+                # Negative window bits means raw deflate data, without a zlib header.  The data is
+                # only decompressed up to one byte past the expected size.
+                decompressor = zlib.decompressobj(-15)
+                data = decompressor.decompress(bytes(buf.read(compressed_size)), size + 1)
+            except zlib.error as e:
+                raise InternalError(f"Invalid compressed value: {e}")
+            if len(data) != size or not decompressor.eof:
+                raise InternalError("Compressed value has the wrong size")
+            stream = _UniffiRustBufferStream(data, size)
+            value = {{ inner_ffi_converter }}.read(stream)
+            if stream.remaining() != 0:
+                raise InternalError("Junk data left in buffer after decompressing value")
+            return value
+        else:
+            raise InternalError("Unknown compression algorithm")
@@ -0,0 +1,41 @@
+class _UniffiContractInfo:
+    # What the library reports about its version, and the checksums of its functions.  This is
+    # only read once the contract versions match, since the function that returns it may not exist
+    # or return something else otherwise.
+    def __init__(self, uniffi_version, checksums):
+        self.uniffi_version = uniffi_version
+        self.checksums = checksums
+
+def _uniffi_contract_info(lib):
+    with _UniffiRustBuffer.consume_with_stream(_rust_call(lib.{{ ci.ffi_uniffi_contract_info().name() }})) as stream:
+        uniffi_version = stream.read(stream.read_i32()).decode("utf-8")
+        # The contract version, which was already checked
+        stream.read_u32()
+    checksums = {}
+    {%- for (name, _checksum) in ci.iter_checksums() %}
+    checksums["{{ name }}"] = lib.{{ name }}()
+    {%- endfor %}
+    return _UniffiContractInfo(uniffi_version, checksums)
+
+def _uniffi_check_contract(lib):
+    # The contract version is a plain integer, so it can be read from any library, however old.
+    contract_version = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
+    if contract_version != {{ ci.uniffi_contract_version() }}:
+        raise InternalError(
+            "UniFFI contract version mismatch: the bindings were generated from version {{ ci.uniffi_version() }} "
+            "(contract version {{ ci.uniffi_contract_version() }}) but the library has contract version {}. "
+            "Try cleaning and rebuilding your project".format(contract_version)
+        )
+    info = _uniffi_contract_info(lib)
+    expected_checksums = {
+        {%- for (name, expected_checksum) in ci.iter_checksums() %}
+        "{{ name }}": {{ expected_checksum }},
+        {%- endfor %}
+    }
+    mismatched = [name for name, checksum in expected_checksums.items() if info.checksums.get(name) != checksum]
+    if mismatched:
+        raise InternalError(
+            "UniFFI API checksum mismatch: the bindings were generated from version {{ ci.uniffi_version() }} "
+            "but the library is version {}, and these functions don't match: {}. "
+            "Try cleaning and rebuilding your project".format(info.uniffi_version, ", ".join(mismatched))
+        )
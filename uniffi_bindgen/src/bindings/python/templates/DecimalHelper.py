@@ -0,0 +1,28 @@
+{{- self.add_import("decimal") }}
+# The Decimal type.  Decimals are passed as strings in plain notation, Rust validates them when it
+# lifts them.
+Decimal = decimal.Decimal
+
+class _UniffiConverterDecimal:
+    @staticmethod
+    def _to_str(value):
+        if not isinstance(value, decimal.Decimal):
+            raise TypeError("argument must be decimal.Decimal, not {}".format(type(value).__name__))
+        # The "f" format never uses an exponent, which Rust doesn't accept.
+        return format(value, "f")
+
+    @staticmethod
+    def read(buf):
+        return decimal.Decimal(_UniffiConverterString.read(buf))
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterString.write(_UniffiConverterDecimal._to_str(value), buf)
+
+    @staticmethod
+    def lift(value):
+        return decimal.Decimal(_UniffiConverterString.lift(value))
+
+    @staticmethod
+    def lower(value):
+        return _UniffiConverterString.lower(_UniffiConverterDecimal._to_str(value))
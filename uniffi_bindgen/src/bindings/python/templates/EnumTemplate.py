@@ -4,15 +4,27 @@
 # when none of the variants have associated data, or a generic nested-class
 # construct when they do.
 #}
+{%- match self.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
+
+{{ type_override }}
+
+{%- when None %}
 {% if e.is_flat() %}
 
-class {{ type_name }}(enum.Enum):
+class {{ type_name }}({% if e.has_discriminants() %}enum.IntEnum{% else %}enum.Enum{% endif %}):
+    {%- call py::docstring(e, 4) %}
     {% for variant in e.variants() -%}
-    {{ variant.name()|enum_variant_py }} = {{ loop.index }}
+    {{ variant.name()|enum_variant_py }} = {% match variant.discr() %}{% when Some with (discr) %}{{ discr }}{% when None %}{{ loop.index }}{% endmatch %}
     {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    # A variant that was added to the Rust enum after these bindings were generated
+    UNKNOWN = {% if e.has_discriminants() %}-9223372036854775808{% else %}0{% endif %}
+    {%- endif %}
 {% else %}
 
 class {{ type_name }}:
+    {%- call py::docstring(e, 4) %}
     def __init__(self):
         raise RuntimeError("{{ type_name }} cannot be instantiated directly")
 
@@ -20,31 +32,57 @@ class {{ type_name }}:
     {% for variant in e.variants() -%}
     class {{ variant.name()|enum_variant_py }}:
         {% for field in variant.fields() %}
-            {{- field.name()|var_name }}: "{{- field|type_name }}";
+            {{- self.field_name(field.name()) }}: "{{- field|type_name }}";
         {%- endfor %}
 
         @typing.no_type_check
-        def __init__(self,{% for field in variant.fields() %}{{ field.name()|var_name }}: "{{- field|type_name }}"{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
+        def __init__(self,{% for field in variant.fields() %}{{ self.field_name(field.name()) }}: "{{- field|type_name }}"{% if loop.last %}{% else %}, {% endif %}{% endfor %}):
             {% if variant.has_fields() %}
             {%- for field in variant.fields() %}
-            self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
+            self.{{ self.field_name(field.name()) }} = {{ self.field_name(field.name()) }}
             {%- endfor %}
             {% else %}
             pass
             {% endif %}
 
         def __str__(self):
-            return "{{ type_name }}.{{ variant.name()|enum_variant_py }}({% for field in variant.fields() %}{{ field.name()|var_name }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in variant.fields() %}self.{{ field.name()|var_name }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+            return "{{ type_name }}.{{ variant.name()|enum_variant_py }}({% for field in variant.fields() %}{{ self.field_name(field.name()) }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in variant.fields() %}self.{{ self.field_name(field.name()) }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
 
         def __eq__(self, other):
             if not other.is_{{ variant.name()|var_name }}():
                 return False
             {%- for field in variant.fields() %}
-            if self.{{ field.name()|var_name }} != other.{{ field.name()|var_name }}:
+            if self.{{ self.field_name(field.name()) }} != other.{{ self.field_name(field.name()) }}:
                 return False
             {%- endfor %}
             return True
+
+        # Defining `__eq__` removes the default `__hash__`, which is needed for map keys
+        def __hash__(self):
+            return hash(("{{ variant.name()|enum_variant_py }}",{% for field in variant.fields() %} self.{{ self.field_name(field.name()) }},{% endfor %}))
     {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    # A variant that was added to the Rust enum after these bindings were generated.  It keeps the
+    # raw variant index and fields, so that it's passed back to Rust unchanged.
+    class UNKNOWN:
+        index: int
+        fields: bytes
+
+        def __init__(self, index: int, fields: bytes):
+            self.index = index
+            self.fields = fields
+
+        def __str__(self):
+            return "{{ type_name }}.UNKNOWN(index={}, fields={})".format(self.index, self.fields)
+
+        def __eq__(self, other):
+            if not isinstance(other, {{ type_name }}) or not other.is_unknown():
+                return False
+            return self.index == other.index and self.fields == other.fields
+
+        def __hash__(self):
+            return hash(("UNKNOWN", self.index, self.fields))
+    {%- endif %}
 
     # For each variant, we have an `is_NAME` method for easily checking
     # whether an instance is that variant.
@@ -52,6 +90,10 @@ class {{ type_name }}:
     def is_{{ variant.name()|var_name }}(self) -> bool:
         return isinstance(self, {{ type_name }}.{{ variant.name()|enum_variant_py }})
     {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    def is_unknown(self) -> bool:
+        return isinstance(self, {{ type_name }}.UNKNOWN)
+    {%- endif %}
 
 # Now, a little trick - we make each nested variant class be a subclass of the main
 # enum class, so that method calls and instance checks etc will work intuitively.
@@ -59,13 +101,21 @@ class {{ type_name }}:
 {% for variant in e.variants() -%}
 {{ type_name }}.{{ variant.name()|enum_variant_py }} = type("{{ type_name }}.{{ variant.name()|enum_variant_py }}", ({{ type_name }}.{{variant.name()|enum_variant_py}}, {{ type_name }},), {})  # type: ignore
 {% endfor %}
+{%- if e.is_non_exhaustive() %}
+{{ type_name }}.UNKNOWN = type("{{ type_name }}.UNKNOWN", ({{ type_name }}.UNKNOWN, {{ type_name }},), {})  # type: ignore
+{%- endif %}
 
 {% endif %}
+{%- endmatch %}
 
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
     def read(buf):
         variant = buf.read_i32()
+        {%- if e.is_non_exhaustive() %}
+        # The size of the fields, to skip variants that were added after these bindings were generated
+        size = buf.read_i32()
+        {%- endif %}
 
         {%- for variant in e.variants() %}
         if variant == {{ loop.index }}:
@@ -79,9 +129,29 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
             )
             {%- endif %}
         {%- endfor %}
+        {%- if e.is_non_exhaustive() %}
+        {%- if e.is_flat() %}
+        buf.read(size)
+        return {{ type_name }}.UNKNOWN
+        {%- else %}
+        return {{ type_name }}.UNKNOWN(variant, bytes(buf.read(size)))
+        {%- endif %}
+        {%- else %}
         raise InternalError("Raw enum value doesn't match any cases")
+        {%- endif %}
 
     def write(value, buf):
+        {%- if e.is_non_exhaustive() %}
+        {%- if e.is_flat() %}
+        if value == {{ type_name }}.UNKNOWN:
+            raise ValueError("{{ type_name }}.UNKNOWN can't be passed to Rust")
+        {%- else %}
+        if value.is_unknown():
+            # Rust doesn't expect the size of the fields, it reads them like for the other variants
+            buf.write_i32(value.index)
+            buf.write(value.fields)
+        {%- endif %}
+        {%- endif %}
         {%- for variant in e.variants() %}
         {%- if e.is_flat() %}
         if value == {{ type_name }}.{{ variant.name()|enum_variant_py }}:
@@ -90,7 +160,7 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
         if value.is_{{ variant.name()|var_name }}():
             buf.write_i32({{ loop.index }})
             {%- for field in variant.fields() %}
-            {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+            {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
             {%- endfor %}
         {%- endif %}
         {%- endfor %}
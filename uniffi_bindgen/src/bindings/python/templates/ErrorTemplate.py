@@ -5,6 +5,7 @@
 # __dict__.  All of this happens in dummy class to avoid polluting the module
 # namespace.
 class {{ type_name }}(Exception):
+    {%- call py::docstring(e, 4) %}
     pass
 
 _UniffiTemp{{ type_name }} = {{ type_name }}
@@ -14,19 +15,26 @@ class {{ type_name }}:  # type: ignore
     {%- let variant_type_name = variant.name()|class_name -%}
     {%- if e.is_flat() %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
+        {%- if variant.has_fields() %}
+        def __init__(self, message{% for field in variant.fields() %}, {{ self.field_name(field.name()) }}{% endfor %}):
+            super().__init__(message)
+            {%- for field in variant.fields() %}
+            self.{{ self.field_name(field.name()) }} = {{ self.field_name(field.name()) }}
+            {%- endfor %}
+        {%- endif %}
         def __repr__(self):
             return "{{ type_name }}.{{ variant_type_name }}({})".format(repr(str(self)))
     {%- else %}
     class {{ variant_type_name }}(_UniffiTemp{{ type_name }}):
-        def __init__(self{% for field in variant.fields() %}, {{ field.name()|var_name }}{% endfor %}):
+        def __init__(self{% for field in variant.fields() %}, {{ self.field_name(field.name()) }}{% endfor %}):
             {%- if variant.has_fields() %}
             super().__init__(", ".join([
                 {%- for field in variant.fields() %}
-                "{{ field.name()|var_name }}={!r}".format({{ field.name()|var_name }}),
+                "{{ self.field_name(field.name()) }}={!r}".format({{ self.field_name(field.name()) }}),
                 {%- endfor %}
             ]))
             {%- for field in variant.fields() %}
-            self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
+            self.{{ self.field_name(field.name()) }} = {{ self.field_name(field.name()) }}
             {%- endfor %}
             {%- else %}
             pass
@@ -50,11 +58,10 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
             return {{ type_name }}.{{ variant.name()|class_name }}(
                 {%- if e.is_flat() %}
                 {{ Type::String.borrow()|read_fn }}(buf),
-                {%- else %}
+                {%- endif %}
                 {%- for field in variant.fields() %}
-                {{ field.name()|var_name }}={{ field|read_fn }}(buf),
+                {{ self.field_name(field.name()) }}={{ field|read_fn }}(buf),
                 {%- endfor %}
-                {%- endif %}
             )
         {%- endfor %}
         raise InternalError("Raw enum value doesn't match any cases")
@@ -64,7 +71,10 @@ class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
         {%- for variant in e.variants() %}
         if isinstance(value, {{ type_name }}.{{ variant.name()|class_name }}):
             buf.write_i32({{ loop.index }})
+            {#- Rust only reads the variant of flat errors #}
+            {%- if !e.is_flat() %}
             {%- for field in variant.fields() %}
-            {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+            {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
             {%- endfor %}
+            {%- endif %}
         {%- endfor %}
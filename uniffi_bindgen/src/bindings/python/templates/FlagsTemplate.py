@@ -0,0 +1,32 @@
+
+# `boundary=KEEP` keeps the bits that Rust sets but these bindings don't have a name for, for
+# example flags added in a newer version of the library.  Python only supports it from 3.11, older
+# versions drop those bits when lifting instead.
+class {{ type_name }}(enum.Flag, **({"boundary": enum.KEEP} if hasattr(enum, "KEEP") else {})):
+    {%- call py::docstring(flags, 4) %}
+    {% for flag in flags.flags() -%}
+    {{ flag.name()|enum_variant_py }} = {{ flag.value() }}
+    {% endfor %}
+
+class {{ ffi_converter_name }}(_UniffiConverterPrimitive):
+    @classmethod
+    def check(cls, value):
+        return {{ type_name }}(value)
+
+    @classmethod
+    def read(cls, buf):
+        return cls.lift(buf.read_u32())
+
+    @classmethod
+    def write_unchecked(cls, value, buf):
+        buf.write_u32(value.value)
+
+    @staticmethod
+    def lift(value):
+        if not hasattr(enum, "KEEP"):
+            value &= {{ flags.all_bits() }}
+        return {{ type_name }}(value)
+
+    @staticmethod
+    def lowerUnchecked(value):
+        return value.value
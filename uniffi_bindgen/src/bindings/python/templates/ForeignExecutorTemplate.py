@@ -55,4 +55,9 @@ def _uniffi_executor_callback(eventloop_address, delay, task_ptr, task_data):
         return _UNIFFI_FOREIGN_EXECUTOR_CALLBACK_SUCCESS
 
 # Register the callback with the scaffolding
+{%- if self.remote_calls() %}
+if _UniffiLib is not None:
+    _UniffiLib.uniffi_foreign_executor_callback_set(_uniffi_executor_callback)
+{%- else %}
 _UniffiLib.uniffi_foreign_executor_callback_set(_uniffi_executor_callback)
+{%- endif %}
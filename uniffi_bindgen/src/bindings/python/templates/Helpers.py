@@ -2,6 +2,19 @@
 # This would be a good candidate for isolating in its own ffi-support lib.
 
 class InternalError(Exception):
+    """
+    Raised for Rust panics, and for errors of the bindings themselves.
+
+    `rust_backtrace` is the backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled.
+    """
+    def __init__(self, *args, rust_backtrace=None):
+        super().__init__(*args)
+        self.rust_backtrace = rust_backtrace
+
+class InvalidArgumentError(ValueError):
+    """
+    Raised when Rust can't lift an argument, like a zero for a `NonZeroU32`.
+    """
     pass
 
 class _UniffiRustCallStatus(ctypes.Structure):
@@ -17,6 +30,7 @@ class _UniffiRustCallStatus(ctypes.Structure):
     CALL_SUCCESS = 0
     CALL_ERROR = 1
     CALL_PANIC = 2
+    CALL_INVALID_ARGUMENT = 3
 
     def __str__(self):
         if self.code == _UniffiRustCallStatus.CALL_SUCCESS:
@@ -25,6 +39,8 @@ class _UniffiRustCallStatus(ctypes.Structure):
             return "_UniffiRustCallStatus(CALL_ERROR)"
         elif self.code == _UniffiRustCallStatus.CALL_PANIC:
             return "_UniffiRustCallStatus(CALL_PANIC)"
+        elif self.code == _UniffiRustCallStatus.CALL_INVALID_ARGUMENT:
+            return "_UniffiRustCallStatus(CALL_INVALID_ARGUMENT)"
         else:
             return "_UniffiRustCallStatus(<invalid code>)"
 
@@ -63,6 +79,12 @@ def _rust_call_async(scaffolding_fn, callback_fn, *args):
     _uniffi_check_call_status(None, uniffi_call_status)
     return uniffi_py_future
 
+def _uniffi_lift_panic(stream):
+    # The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+    message = _UniffiConverterString.read(stream)
+    backtrace = _UniffiConverterString.read(stream)
+    return InternalError(message, rust_backtrace=backtrace or None)
+
 def _uniffi_check_call_status(error_ffi_converter, call_status):
     if call_status.code == _UniffiRustCallStatus.CALL_SUCCESS:
         pass
@@ -74,13 +96,16 @@ def _uniffi_check_call_status(error_ffi_converter, call_status):
             raise error_ffi_converter.lift(call_status.error_buf)
     elif call_status.code == _UniffiRustCallStatus.CALL_PANIC:
         # When the rust code sees a panic, it tries to construct a _UniffiRustBuffer
-        # with the message.  But if that code panics, then it just sends back
-        # an empty buffer.
+        # with the message and the backtrace.  But if that code panics, then it
+        # just sends back an empty buffer.
         if call_status.error_buf.len > 0:
-            msg = _UniffiConverterString.lift(call_status.error_buf)
+            with call_status.error_buf.consume_with_stream() as stream:
+                error = _uniffi_lift_panic(stream)
+            raise error
         else:
-            msg = "Unknown rust panic"
-        raise InternalError(msg)
+            raise InternalError("Unknown rust panic")
+    elif call_status.code == _UniffiRustCallStatus.CALL_INVALID_ARGUMENT:
+        raise InvalidArgumentError(_UniffiConverterString.lift(call_status.error_buf))
     else:
         raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(
             call_status.code))
@@ -0,0 +1,66 @@
+# Reloading the library during development, with the `hot_reload` option.
+#
+# The library is loaded from a temporary copy, so that it can be rebuilt while it's loaded, and
+# `uniffi_reload()` switches to a copy of the rebuilt library.  Rust state doesn't survive that, so
+# objects remember the generation of the library that they're from, and they can't be used after
+# it was unloaded.  Their pointers are never freed, since they belong to the old library.
+
+import atexit
+import shutil
+import tempfile
+
+_uniffi_generation = 0
+
+def _uniffi_copy_library(path):
+    copy_dir = tempfile.mkdtemp(prefix="uniffi-{{ ci.namespace() }}-")
+    copy_path = os.path.join(copy_dir, os.path.basename(path))
+    shutil.copy2(path, copy_path)
+    # The copy is removed when its library is unloaded by `uniffi_reload()`, and the one that's
+    # still loaded is removed when Python exits.
+    atexit.register(shutil.rmtree, copy_dir, ignore_errors=True)
+    return copy_path
+
+def _uniffi_unload_library(lib):
+    import _ctypes
+    if sys.platform.startswith("win"):
+        _ctypes.FreeLibrary(lib._handle)
+    else:
+        _ctypes.dlclose(lib._handle)
+    shutil.rmtree(os.path.dirname(lib._name), ignore_errors=True)
+
+def uniffi_reload():
+    """
+    Load the library again after it was rebuilt, without restarting Python.
+
+    The new library is checked like when the module is imported, and this raises an
+    `InternalError` if its API doesn't match these bindings, in which case the old library is still
+    used.  Regenerate the bindings and restart Python after changes to the API.
+
+    The objects from the old library can't be used after this: their methods raise a `ValueError`.
+    The old library is unloaded, so there mustn't be any calls to it in progress, and the threads
+    and tasks that it started must have stopped.
+
+    This is only meant for development, it's generated with the `hot_reload` option.
+    """
+    global _UniffiLib, _uniffi_generation
+    lib = _uniffi_load_indirect()
+    try:
+        _uniffi_setup_lib(lib)
+    except:
+        _uniffi_unload_library(lib)
+        raise
+    old_lib = _UniffiLib
+    _UniffiLib = lib
+    _uniffi_generation += 1
+    {%- if !ci.callback_interface_definitions().is_empty() %}
+    # The new library doesn't hold any callback interfaces yet, and needs the callbacks that call
+    # them.
+    _UniffiConverterCallbackInterface._handle_map = ConcurrentHandleMap()
+    {%- for cbi in ci.callback_interface_definitions() %}
+    _rust_call(lambda err: _UniffiLib.{{ cbi.ffi_init_callback().name() }}(foreignCallback{{ cbi|canonical_name }}, err))
+    {%- endfor %}
+    {%- endif %}
+    {%- if self.has_foreign_executor() %}
+    _UniffiLib.uniffi_foreign_executor_callback_set(_uniffi_executor_callback)
+    {%- endif %}
+    _uniffi_unload_library(old_lib)
@@ -0,0 +1,20 @@
+{{- self.add_import("ipaddress") }}
+# The IpAddr type.  IP addresses are passed as a version byte, 4 or 6, followed by the 4 or 16
+# bytes of the address.  Strings like "192.0.2.1" can be passed to Rust too.
+IpAddr = typing.Union[ipaddress.IPv4Address, ipaddress.IPv6Address]
+
+class _UniffiConverterIpAddr(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        version = buf.read_u8()
+        if version == 4:
+            return ipaddress.IPv4Address(bytes(buf.read(4)))
+        if version == 6:
+            return ipaddress.IPv6Address(bytes(buf.read(16)))
+        raise InternalError("Unexpected IP address version {}".format(version))
+
+    @staticmethod
+    def write(value, buf):
+        value = ipaddress.ip_address(value)
+        buf.write_u8(value.version)
+        buf.write(value.packed)
@@ -0,0 +1,21 @@
+{{- self.add_import("json") }}
+# The Json type.  Values are the objects that the `json` module works with: dicts, lists, strings,
+# numbers, booleans and None.  They're passed as JSON text, Rust validates it when it lifts them.
+Json = typing.Any
+
+class _UniffiConverterJson:
+    @staticmethod
+    def read(buf):
+        return json.loads(_UniffiConverterString.read(buf))
+
+    @staticmethod
+    def write(value, buf):
+        _UniffiConverterString.write(json.dumps(value), buf)
+
+    @staticmethod
+    def lift(value):
+        return json.loads(_UniffiConverterString.lift(value))
+
+    @staticmethod
+    def lower(value):
+        return _UniffiConverterString.lower(json.dumps(value))
@@ -0,0 +1,32 @@
+# Runs the calls to main-thread callback interfaces, which Rust may make from any thread, with the
+# scheduler installed by `set_main_thread_scheduler()`.
+#
+# This is only generated when a callback interface is declared with `main_thread`.
+import concurrent.futures
+import threading
+
+_uniffi_main_thread_scheduler = None
+
+def set_main_thread_scheduler(scheduler):
+    """
+    Set the function that schedules calls on the main thread, like `loop.call_soon_threadsafe`
+    for an asyncio event loop running on the main thread.
+    """
+    global _uniffi_main_thread_scheduler
+    _uniffi_main_thread_scheduler = scheduler
+
+def _uniffi_run_on_main_thread(call):
+    if threading.current_thread() is threading.main_thread():
+        return call()
+    scheduler = _uniffi_main_thread_scheduler
+    if scheduler is None:
+        raise RuntimeError("set_main_thread_scheduler() must be called to call main thread callback interfaces")
+    future = concurrent.futures.Future()
+    def task():
+        try:
+            future.set_result(call())
+        except BaseException as e:
+            future.set_exception(e)
+    scheduler(task)
+    return future.result()
+
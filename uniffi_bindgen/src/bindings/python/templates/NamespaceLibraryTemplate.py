@@ -1,5 +1,20 @@
 # Define some ctypes FFI types that we use in the library
 
+{%- for rec in ci.record_definitions() %}
+{%- if rec.is_repr_c() %}
+
+class {{ rec.ffi_type().borrow()|ffi_type_name }}(ctypes.Structure):
+    """
+    `{{ rec|type_name }}` is passed to and from Rust as this struct, rather than in a RustBuffer
+    """
+    _fields_ = [
+        {%- for field in rec.fields() %}
+        ("{{ field.name() }}", {{ field.ffi_type().borrow()|ffi_type_name }}),
+        {%- endfor %}
+    ]
+{%- endif %}
+{%- endfor %}
+
 """
 ctypes type for the foreign executor callback.  This is a built-in interface for scheduling
 tasks
@@ -49,35 +64,40 @@ def _uniffi_load_indirect():
 
     libname = libname.format("{{ config.cdylib_name() }}")
     path = os.path.join(os.path.dirname(__file__), libname)
+    {%- if config.hot_reload() %}
+    path = _uniffi_copy_library(path)
+    {%- endif %}
     lib = ctypes.cdll.LoadLibrary(path)
     return lib
 
-def _uniffi_check_contract_api_version(lib):
-    # Get the bindings contract version from our ComponentInterface
-    bindings_contract_version = {{ ci.uniffi_contract_version() }}
-    # Get the scaffolding contract version by calling the into the dylib
-    scaffolding_contract_version = lib.{{ ci.ffi_uniffi_contract_version().name() }}()
-    if bindings_contract_version != scaffolding_contract_version:
-        raise InternalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
+{% include "ContractCheck.py" %}
 
-def _uniffi_check_api_checksums(lib):
-    {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if lib.{{ name }}() != {{ expected_checksum }}:
-        raise InternalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
-    {%- else %}
-    pass
+def _uniffi_setup_lib(lib):
+    {%- for func in ci.iter_ffi_function_definitions() %}
+    lib.{{ func.name() }}.argtypes = (
+        {%- call py::arg_list_ffi_decl(func) -%}
+    )
+    lib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
     {%- endfor %}
+    {# Ensure to call the contract verification only after we defined all functions. -#}
+    _uniffi_check_contract(lib)
+{%- if config.hot_reload() %}
+
+{% include "HotReload.py" %}
+{%- endif %}
 
 # A ctypes library to expose the extern-C FFI definitions.
 # This is an implementation detail which will be called internally by the public API.
 
+{%- if config.remote_calls() %}
+# A process that makes remote calls doesn't need the library, see `RemoteCalls.py`
+try:
+    _UniffiLib = _uniffi_load_indirect()
+except OSError:
+    _UniffiLib = None
+else:
+    _uniffi_setup_lib(_UniffiLib)
+{%- else %}
 _UniffiLib = _uniffi_load_indirect()
-{%- for func in ci.iter_ffi_function_definitions() %}
-_UniffiLib.{{ func.name() }}.argtypes = (
-    {%- call py::arg_list_ffi_decl(func) -%}
-)
-_UniffiLib.{{ func.name() }}.restype = {% match func.return_type() %}{% when Some with (type_) %}{{ type_|ffi_type_name }}{% when None %}None{% endmatch %}
-{%- endfor %}
-{# Ensure to call the contract verification only after we defined all functions. -#}
-_uniffi_check_contract_api_version(_UniffiLib)
-_uniffi_check_api_checksums(_UniffiLib)
+_uniffi_setup_lib(_UniffiLib)
+{%- endif %}
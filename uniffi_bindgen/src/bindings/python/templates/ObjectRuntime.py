@@ -0,0 +1,19 @@
+import threading
+
+# Guards the call counters of objects.  This is only held while updating a counter, never during a
+# call.  It's reentrant, since the garbage collector can run `__del__()` in the middle of an update.
+_UNIFFI_CALL_COUNTER_LOCK = threading.RLock()
+
+def _uniffi_call_with_pointers(objects, call):
+    # Call `call` with the pointers to `objects`, which stay alive until the call returns, even if
+    # they're closed concurrently.
+    acquired = []
+    try:
+        pointers = []
+        for obj in objects:
+            pointers.append(obj._uniffi_acquire())
+            acquired.append(obj)
+        return call(*pointers)
+    finally:
+        for obj in acquired:
+            obj._uniffi_release()
@@ -1,21 +1,128 @@
 {%- let obj = ci|get_object_definition(name) %}
+{%- if self.include_once_check("ObjectRuntime.py") %}{% include "ObjectRuntime.py" %}{% endif %}
+{%- match obj.concurrency() %}
+{%- when Some with (policy) %}
+{%- if policy.name() == "serialized" %}
+
+# Calls to this object can come from several threads, the Rust code runs one at a time.
+{%- else %}
+
+# Calls to this object can come from several threads, the Rust object handles them concurrently.
+{%- endif %}
+{%- when None %}
+{%- endmatch %}
 
 class {{ type_name }}:
+    {%- call py::docstring(obj, 4) %}
     _pointer: ctypes.c_void_p
+    {%- for c in obj.constants() %}
+    {{ c.name() }} = {{ c.value()|literal_py(c) }}
+    {%- endfor %}
 
 {%- match obj.primary_constructor() %}
 {%-     when Some with (cons) %}
     def __init__(self, {% call py::arg_list_decl(cons) -%}):
+        {%- call py::callable_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
         self._pointer = {% call py::to_ffi_call(cons) %}
+        {%- if self.hot_reload() %}
+        self._uniffi_generation = _uniffi_generation
+        {%- endif %}
 {%-     when None %}
 {%- endmatch %}
 
     def __del__(self):
-        # In case of partial initialization of instances.
-        pointer = getattr(self, "_pointer", None)
+        self._uniffi_destroy()
+
+    def _uniffi_destroy(self):
+        with _UNIFFI_CALL_COUNTER_LOCK:
+            # In case of partial initialization of instances.
+            pointer = getattr(self, "_pointer", None)
+            if pointer is None:
+                return
+            self._pointer = None
+            if getattr(self, "_uniffi_calls", 0) > 0:
+                # Rust is borrowing the object, the last call to return frees it
+                self._uniffi_pending_free = pointer
+                return
+        self._uniffi_free(pointer)
+
+    def _uniffi_free(self, pointer):
+        {%- if self.hot_reload() %}
+        if getattr(self, "_uniffi_generation", None) != _uniffi_generation:
+            # The object was dropped along with the library that it's from
+            return
+        {%- endif %}
+        _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
+
+    # Count a call that borrows the object, so that it isn't freed until `_uniffi_release()`
+    def _uniffi_acquire(self):
+        with _UNIFFI_CALL_COUNTER_LOCK:
+            pointer = self._uniffi_pointer()
+            self._uniffi_calls = getattr(self, "_uniffi_calls", 0) + 1
+            return pointer
+
+    def _uniffi_release(self):
+        with _UNIFFI_CALL_COUNTER_LOCK:
+            self._uniffi_calls -= 1
+            if self._uniffi_calls > 0:
+                return
+            pointer = getattr(self, "_uniffi_pending_free", None)
+            self._uniffi_pending_free = None
         if pointer is not None:
-            _rust_call(_UniffiLib.{{ obj.ffi_object_free().name() }}, pointer)
+            self._uniffi_free(pointer)
+
+    def _uniffi_pointer(self):
+        pointer = getattr(self, "_pointer", None)
+        if pointer is None:
+            {%- if obj.has_consuming_methods() %}
+            if getattr(self, "_uniffi_consumed", False):
+                raise ValueError("{{ type_name }} object was consumed")
+            {%- endif %}
+            raise ValueError("{{ type_name }} object was closed")
+        {%- if self.hot_reload() %}
+        if self._uniffi_generation != _uniffi_generation:
+            raise ValueError("{{ type_name }} object is from before the library was reloaded")
+        {%- endif %}
+        return pointer
+{%- if obj.has_consuming_methods() %}
+
+    # Methods that consume the object hand the pointer over to Rust, so the object can't be used
+    # or freed after that.
+    def _uniffi_take_pointer(self):
+        with _UNIFFI_CALL_COUNTER_LOCK:
+            pointer = self._uniffi_pointer()
+            if getattr(self, "_uniffi_calls", 0) > 0:
+                raise ValueError("{{ type_name }} object is in use by another call")
+            self._pointer = None
+            self._uniffi_consumed = True
+            return pointer
+{%- endif %}
+
+{%- if obj.has_method("close") %}
+
+    # The interface has its own `close()` method, so the generated one is renamed
+    def uniffi_close(self):
+{%- else %}
+
+    def close(self):
+{%- endif %}
+        """
+        Destroy the Rust object now, rather than when this object is garbage collected.
+
+        This object can't be used after that, calling its methods raises a `ValueError`.  Calling
+        this more than once does nothing.  Objects are also closed when they're used in a `with`
+        statement, at the end of the block.
+
+        It must not be called while another thread is calling a method of this object.
+        """
+        self._uniffi_destroy()
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *exc_info):
+        self._uniffi_destroy()
 
     # Used by alternative constructors or any methods which return this type.
     @classmethod
@@ -24,12 +131,16 @@ class {{ type_name }}:
         # and just create a new instance with the required pointer.
         inst = cls.__new__(cls)
         inst._pointer = pointer
+        {%- if self.hot_reload() %}
+        inst._uniffi_generation = _uniffi_generation
+        {%- endif %}
         return inst
 
 {%- for cons in obj.alternate_constructors() %}
 
     @classmethod
     def {{ cons.name()|fn_name }}(cls, {% call py::arg_list_decl(cons) %}):
+        {%- call py::callable_docstring(cons, 8) %}
         {%- call py::setup_args_extra_indent(cons) %}
         # Call the (fallible) function before creating any half-baked object instances.
         pointer = {% call py::to_ffi_call(cons) %}
@@ -51,15 +162,45 @@ class {{ type_name }}:
         if not isinstance(other, {{ type_name }}):
             return NotImplemented
 
-        return {{ eq.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._pointer", eq) %})
+        return {{ eq.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_self(eq) %})
 
     def __ne__(self, other: object) -> {{ ne.return_type().unwrap()|type_name }}:
         if not isinstance(other, {{ type_name }}):
             return NotImplemented
 
-        return {{ ne.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_prefix("self._pointer", ne) %})
+        return {{ ne.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_self(ne) %})
 {%-         when UniffiTrait::Hash { hash } %}
             {%- call py::method_decl("__hash__", hash) %}
+{%-         when UniffiTrait::Ord { cmp } %}
+    def _uniffi_cmp(self, other: "{{ type_name }}") -> int:
+        return {{ cmp.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call_with_self(cmp) %})
+
+    def __lt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) < 0
+
+    def __le__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) <= 0
+
+    def __gt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) > 0
+
+    def __ge__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) >= 0
+{%-         when UniffiTrait::Restore { serialize, restore } %}
+            {%- call py::method_decl("serialize_handle", serialize) %}
+
+    @staticmethod
+    def restore_handle(token: str) -> "typing.Optional[{{ type_name }}]":
+        {%- call py::setup_args_extra_indent(restore) %}
+        return {{ restore.return_type().unwrap()|lift_fn }}({% call py::to_ffi_call(restore) %})
 {%      endmatch %}
 {% endfor %}
 
@@ -70,13 +211,21 @@ class {{ ffi_converter_name }}:
         ptr = buf.read_u64()
         if ptr == 0:
             raise InternalError("Raw pointer value was null")
+        {%- if self.cffi() %}
+        return cls.lift(_uniffi_ffi.cast("void *", ptr))
+        {%- else %}
         return cls.lift(ptr)
+        {%- endif %}
 
     @classmethod
     def write(cls, value, buf):
         if not isinstance(value, {{ type_name }}):
             raise TypeError("Expected {{ type_name }} instance, {} found".format(type(value).__name__))
+        {%- if self.cffi() %}
+        buf.write_u64(int(_uniffi_ffi.cast("uintptr_t", cls.lower(value))))
+        {%- else %}
         buf.write_u64(cls.lower(value))
+        {%- endif %}
 
     @staticmethod
     def lift(value):
@@ -84,4 +233,4 @@ class {{ ffi_converter_name }}:
 
     @staticmethod
     def lower(value):
-        return value._pointer
+        return value._uniffi_pointer()
@@ -0,0 +1,15 @@
+{{- self.add_import("pathlib") }}
+# The Path type.  Paths are passed as their raw bytes, `os.fsencode()` and `os.fsdecode()` use the
+# "surrogateescape" error handler so paths that aren't valid UTF-8 round-trip unchanged.
+Path = pathlib.Path
+
+class _UniffiConverterPath(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return pathlib.Path(os.fsdecode(_UniffiConverterBytes.read(buf)))
+
+    @staticmethod
+    def write(value, buf):
+        if not isinstance(value, (str, os.PathLike)):
+            raise TypeError("argument must be str or os.PathLike, not {}".format(type(value).__name__))
+        _UniffiConverterBytes.write(os.fsencode(value), buf)
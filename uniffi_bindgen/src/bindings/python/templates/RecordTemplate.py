@@ -1,17 +1,22 @@
 {%- let rec = ci|get_record_definition(name) %}
+{%- match self.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
+{{ type_override }}
+{%- when None %}
 class {{ type_name }}:
+    {%- call py::docstring(rec, 4) %}
     {% for field in rec.fields() %}
-        {{- field.name()|var_name }}: "{{- field|type_name }}";
+        {{- self.field_name(field.name()) }}: "{{- field|type_name }}";
     {%- endfor %}
 
     @typing.no_type_check
     def __init__(self, {% for field in rec.fields() %}
-    {{- field.name()|var_name }}: "{{- field|type_name }}"
+    {{- self.field_name(field.name()) }}: "{{- field|type_name }}"
     {%- if field.default_value().is_some() %} = _DEFAULT{% endif %}
     {%- if !loop.last %}, {% endif %}
     {%- endfor %}):
         {%- for field in rec.fields() %}
-        {%- let field_name = field.name()|var_name %}
+        {%- let field_name = self.field_name(field.name()) %}
         {%- match field.default_value() %}
         {%- when None %}
         self.{{ field_name }} = {{ field_name }}
@@ -24,26 +29,77 @@ class {{ type_name }}:
         {%- endfor %}
 
     def __str__(self):
-        return "{{ type_name }}({% for field in rec.fields() %}{{ field.name()|var_name }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name()|var_name }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
+        return "{{ type_name }}({% for field in rec.fields() %}{{ self.field_name(field.name()) }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ self.field_name(field.name()) }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
 
     def __eq__(self, other):
         {%- for field in rec.fields() %}
-        if self.{{ field.name()|var_name }} != other.{{ field.name()|var_name }}:
+        if self.{{ self.field_name(field.name()) }} != other.{{ self.field_name(field.name()) }}:
             return False
         {%- endfor %}
         return True
 
+    # Defining `__eq__` removes the default `__hash__`, which is needed for map keys
+    def __hash__(self):
+        return hash(({% for field in rec.fields() %}self.{{ self.field_name(field.name()) }},{% if !loop.last %} {% endif %}{% endfor %}))
+{%- match rec.ffi_func_cmp() %}
+{%- when Some(cmp) %}
+
+    # Compares the records with their `Ord` implementation in Rust
+    def _uniffi_cmp(self, other: "{{ type_name }}") -> int:
+        return _rust_call(_UniffiLib.{{ cmp.name() }}, {{ rec|lower_fn }}(self), {{ rec|lower_fn }}(other))
+
+    def __lt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) < 0
+
+    def __le__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) <= 0
+
+    def __gt__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) > 0
+
+    def __ge__(self, other: object) -> bool:
+        if not isinstance(other, {{ type_name }}):
+            return NotImplemented
+        return self._uniffi_cmp(other) >= 0
+{%- when None %}
+{%- endmatch %}
+{%- endmatch %}
+
 class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
     @staticmethod
     def read(buf):
         return {{ type_name }}(
             {%- for field in rec.fields() %}
-            {{ field.name()|var_name }}={{ field|read_fn }}(buf),
+            {{ self.field_name(field.name()) }}={{ field|read_fn }}(buf),
             {%- endfor %}
         )
 
     @staticmethod
     def write(value, buf):
         {%- for field in rec.fields() %}
-        {{ field|write_fn }}(value.{{ field.name()|var_name }}, buf)
+        {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, buf)
         {%- endfor %}
+{%- if rec.is_repr_c() %}
+
+    @staticmethod
+    def lift(value):
+        return {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ self.field_name(field.name()) }}={{ field|lift_fn }}(value.{{ field.name() }}),
+            {%- endfor %}
+        )
+
+    @staticmethod
+    def lower(value):
+        return {{ rec.ffi_type().borrow()|ffi_type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field|lower_fn }}(value.{{ self.field_name(field.name()) }}),
+            {%- endfor %}
+        )
+{%- endif %}
@@ -0,0 +1,99 @@
+# Remote calls, for processes that can't load the library.
+#
+# The arguments of a function are serialized with the same `write()` code that's used for values in
+# `_UniffiRustBuffer`s, and sent with the name of the function's remote entry point over the
+# transport set with `uniffi_set_remote_transport()`.  The process at the other end passes them to
+# `uniffi_remote_dispatch()`, which calls the entry point, and sends the status code of the call
+# back with the serialized result or error.
+
+class _UniffiRemoteArgsBuilder(_UniffiRustBufferBuilder):
+    """
+    Like _UniffiRustBufferBuilder, but writes into a bytearray, since the process that makes a
+    remote call may not have loaded the library to allocate a _UniffiRustBuffer.
+    """
+
+    def __init__(self):
+        self.data = bytearray()
+
+    def _pack_into(self, size, format, value):
+        self.data += struct.pack(format, value)
+
+    def write(self, value):
+        self.data += value
+
+_uniffi_remote_transport = None
+
+def uniffi_set_remote_transport(transport):
+    """
+    Makes the functions of this module call the library through `transport`, rather than calling
+    it directly.  Pass `None` to call the library directly again.
+
+    `transport.call(name, args)` sends the `str` name of a remote entry point and the serialized
+    `bytes` arguments to a process that loaded the library, which passes them to
+    `uniffi_remote_dispatch()`.  It returns the `(code, data)` that `uniffi_remote_dispatch()`
+    returned.
+
+    Only the sync functions that take and return plain data can be called remotely: not objects,
+    callback interfaces, shared or compressed values.  The other functions still call the library
+    directly.
+    """
+    global _uniffi_remote_transport
+    _uniffi_remote_transport = transport
+
+def _uniffi_remote_call(name, error_ffi_converter, return_ffi_converter, *args):
+    # `args` are pairs of an argument and the _UniffiConverter that writes it
+    builder = _UniffiRemoteArgsBuilder()
+    for value, ffi_converter in args:
+        ffi_converter.write(value, builder)
+    code, data = _uniffi_remote_transport.call(name, bytes(builder.data))
+    stream = _UniffiRustBufferStream(data, len(data))
+    if code == _UniffiRustCallStatus.CALL_SUCCESS:
+        result = None if return_ffi_converter is None else return_ffi_converter.read(stream)
+        if stream.remaining() != 0:
+            raise InternalError("junk data left in the result of a remote call")
+        return result
+    elif code == _UniffiRustCallStatus.CALL_ERROR:
+        if error_ffi_converter is None:
+            raise InternalError("_uniffi_remote_call: CALL_ERROR, but error_ffi_converter is None")
+        raise error_ffi_converter.read(stream)
+    elif code == _UniffiRustCallStatus.CALL_PANIC:
+        # Like `_uniffi_check_call_status()`, the message can be missing
+        raise _uniffi_lift_panic(stream) if data else InternalError("Unknown rust panic")
+    elif code == _UniffiRustCallStatus.CALL_INVALID_ARGUMENT:
+        raise InvalidArgumentError(data.decode("utf-8"))
+    else:
+        raise InternalError("Invalid _UniffiRustCallStatus code: {}".format(code))
+
+# Only these entry points can be called by `uniffi_remote_dispatch()`, so that the remote process
+# can't make it call other symbols of the library.
+_UNIFFI_REMOTE_ENTRY_POINTS = frozenset([
+    {%- for func in ci.remote_callable_functions() %}
+    "{{ func.remote_fn_name() }}",
+    {%- endfor %}
+])
+
+def uniffi_remote_dispatch(name: str, args: bytes) -> typing.Tuple[int, bytes]:
+    """
+    Calls a remote entry point of the library, for the transport of a process that called
+    `uniffi_set_remote_transport()`.
+
+    Returns the status code of the call, and the serialized result or error, for the transport to
+    send back.  The library must be built with the `unstable-remote-calls` feature of the `uniffi`
+    crate.
+    """
+    if name not in _UNIFFI_REMOTE_ENTRY_POINTS:
+        raise InternalError("Unknown remote entry point: {}".format(name))
+    if _UniffiLib is None:
+        raise InternalError("uniffi_remote_dispatch: the library isn't loaded in this process")
+    fn = getattr(_UniffiLib, name)
+    fn.argtypes = (_UniffiRustBuffer, ctypes.POINTER(_UniffiRustCallStatus))
+    fn.restype = _UniffiRustBuffer
+    with _UniffiRustBuffer.alloc_with_builder() as builder:
+        builder.write(args)
+        args_buf = builder.finalize()
+    call_status = _UniffiRustCallStatus(code=_UniffiRustCallStatus.CALL_SUCCESS, error_buf=_UniffiRustBuffer(0, 0, None))
+    result = fn(args_buf, ctypes.byref(call_status))
+    buf = result if call_status.code == _UniffiRustCallStatus.CALL_SUCCESS else call_status.error_buf
+    data = bytes(buf.data[0:buf.len]) if buf.len > 0 else b""
+    buf.free()
+    return (call_status.code, data)
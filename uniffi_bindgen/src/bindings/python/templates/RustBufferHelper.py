@@ -49,7 +49,8 @@ class _UniffiConverterPrimitiveFloat(_UniffiConverterPrimitive):
 class _UniffiConverterRustBuffer:
     @classmethod
     def lift(cls, rbuf):
-        with rbuf.consume_with_stream() as stream:
+        # Called on the class rather than the buffer, since the buffers are plain structs with cffi
+        with _UniffiRustBuffer.consume_with_stream(rbuf) as stream:
             return cls.read(stream)
 
     @classmethod
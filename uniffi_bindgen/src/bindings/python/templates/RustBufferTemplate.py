@@ -17,6 +17,11 @@ class _UniffiRustBuffer(ctypes.Structure):
     def free(self):
         return _rust_call(_UniffiLib.{{ ci.ffi_rustbuffer_free().name() }}, self)
 
+    @staticmethod
+    def from_raw_parts(capacity, length, address):
+        """A buffer that Rust described by its fields, like the buffers of shared values"""
+        return _UniffiRustBuffer(capacity, length, ctypes.cast(address, ctypes.POINTER(ctypes.c_char)))
+
     def __str__(self):
         return "_UniffiRustBuffer(capacity={}, len={}, data={})".format(
             self.capacity,
@@ -71,6 +76,14 @@ class _UniffiForeignBytes(ctypes.Structure):
         ("data", ctypes.POINTER(ctypes.c_char)),
     ]
 
+    @staticmethod
+    def borrow(value):
+        # Points directly at the contents of the `bytes` object, which ctypes keeps alive for as
+        # long as the struct.  Other bytes-like objects are copied into a `bytes` first.
+        if not isinstance(value, bytes):
+            value = bytes(value)
+        return _UniffiForeignBytes(len(value), ctypes.cast(value, ctypes.POINTER(ctypes.c_char)))
+
     def __str__(self):
         return "_UniffiForeignBytes(len={}, data={})".format(self.len, self.data[0:self.len])
 
@@ -209,3 +222,42 @@ class _UniffiRustBufferBuilder:
 
     def write_c_size_t(self, v):
         self._pack_into(ctypes.sizeof(ctypes.c_size_t) , "@N", v)
+
+class _UniffiSensitiveRustBufferBuilder(_UniffiRustBufferBuilder):
+    """
+    A _UniffiRustBufferBuilder for arguments that are marked as sensitive.
+
+    Growing a buffer with `reserve()` frees the old allocation as it is, so this copies the bytes
+    into a new buffer instead, and zeroes the old one before freeing it.  A buffer that's discarded
+    is zeroed too.
+    """
+
+    def discard(self):
+        if self.rbuf is not None:
+            self._wipe()
+        super().discard()
+
+    @contextlib.contextmanager
+    def _reserve(self, num_bytes):
+        if self.rbuf.len + num_bytes > self.rbuf.capacity:
+            grown = _UniffiRustBuffer.alloc(max(self.rbuf.capacity * 2, self.rbuf.len + num_bytes))
+            ctypes.memmove(grown.data, self.rbuf.data, self.rbuf.len)
+            grown.len = self.rbuf.len
+            self._wipe()
+            self.rbuf.free()
+            self.rbuf = grown
+        yield None
+        self.rbuf.len += num_bytes
+
+    def _wipe(self):
+        ctypes.memset(self.rbuf.data, 0, self.rbuf.capacity)
+
+def _uniffi_lower_sensitive(converter, value):
+    """Lower an argument that's marked as sensitive, see _UniffiSensitiveRustBufferBuilder"""
+    builder = _UniffiSensitiveRustBufferBuilder()
+    try:
+        converter.write(value, builder)
+    except:
+        builder.discard()
+        raise
+    return builder.finalize()
@@ -0,0 +1,25 @@
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    # Values sent to Rust are always inline
+    @classmethod
+    def write(cls, value, buf):
+        buf.write_u8(0)
+        {{ inner_ffi_converter }}.write(value, buf)
+
+    @classmethod
+    def read(cls, buf):
+        transport = buf.read_u8()
+        if transport == 0:
+            return {{ inner_ffi_converter }}.read(buf)
+        elif transport == 1:
+            # Large values are in a buffer of their own, which we own and have to free
+            capacity = buf.read_i32()
+            length = buf.read_i32()
+            offset = buf.read_i32()
+            rbuf = _UniffiRustBuffer.from_raw_parts(capacity, length, buf.read_u64())
+            with _UniffiRustBuffer.consume_with_stream(rbuf) as stream:
+                stream.read(offset)
+                return {{ inner_ffi_converter }}.read(stream)
+        else:
+            raise InternalError("Unknown transport for shared value")
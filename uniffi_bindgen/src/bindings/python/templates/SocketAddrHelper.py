@@ -0,0 +1,18 @@
+{{- self.add_import("ipaddress") }}
+# The SocketAddr type.  Socket addresses are `(address, port)` tuples, like the ones the `socket`
+# module uses, and are passed as the IP address followed by the u16 port.
+SocketAddr = typing.Tuple[typing.Union[ipaddress.IPv4Address, ipaddress.IPv6Address], int]
+
+class _UniffiConverterSocketAddr(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        address = _UniffiConverterIpAddr.read(buf)
+        return (address, buf.read_u16())
+
+    @staticmethod
+    def write(value, buf):
+        # The `socket` module also uses longer tuples for IPv6 addresses, with the flow info and
+        # scope ID, which aren't passed to Rust.
+        address, port = value[0], value[1]
+        _UniffiConverterIpAddr.write(address, buf)
+        buf.write_u16(port)
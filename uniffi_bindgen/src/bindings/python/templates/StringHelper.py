@@ -22,7 +22,7 @@ class _UniffiConverterString:
 
     @staticmethod
     def lift(buf):
-        with buf.consume_with_stream() as stream:
+        with _UniffiRustBuffer.consume_with_stream(buf) as stream:
             return stream.read(stream.remaining()).decode("utf-8")
 
     @staticmethod
@@ -0,0 +1,294 @@
+# This file was autogenerated by some hot garbage in the `uniffi` crate.
+# Trust me, you don't want to mess with it!
+
+# Type stubs for the `{{ ci.namespace() }}` module, for type checkers like mypy and pyright.
+
+import asyncio
+import datetime
+import decimal
+import enum
+import ipaddress
+import pathlib
+import typing
+import uuid
+
+{%- for type_ in ci.iter_types() %}
+{%- match type_ %}
+{%- when Type::External { module_path, name, kind } %}
+from {{ self.external_type_module_name(module_path) }} import {{ name }}
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+class InternalError(Exception):
+    rust_backtrace: typing.Optional[str]
+class InvalidArgumentError(ValueError): ...
+{%- if ci.has_weak_refs() %}
+
+class WeakRef:
+    def upgrade(self) -> typing.Any: ...
+    def is_alive(self) -> bool: ...
+{%- endif %}
+
+{%- for type_ in ci.iter_types() %}
+{%- let type_name = type_|type_name %}
+{%- match type_ %}
+
+{%- when Type::Timestamp %}
+
+Timestamp = datetime.datetime
+
+{%- when Type::Duration %}
+
+Duration = datetime.timedelta
+
+{%- when Type::Url %}
+
+Url = str
+
+{%- when Type::Uuid %}
+
+Uuid = uuid.UUID
+
+{%- when Type::Decimal %}
+
+Decimal = decimal.Decimal
+
+{%- when Type::Json %}
+
+Json = typing.Any
+
+{%- when Type::Path %}
+
+Path = pathlib.Path
+
+{%- when Type::IpAddr %}
+
+IpAddr = typing.Union[ipaddress.IPv4Address, ipaddress.IPv6Address]
+
+{%- when Type::SocketAddr %}
+
+SocketAddr = typing.Tuple[typing.Union[ipaddress.IPv4Address, ipaddress.IPv6Address], int]
+
+{%- when Type::Custom { name, module_path, builtin } %}
+
+{{ name }} = {{ builtin|type_name }}
+
+{%- when Type::Record { name, module_path } %}
+{%- let rec = ci|get_record_definition(name) %}
+
+class {{ type_name }}:
+    {%- for field in rec.fields() %}
+    {{ self.field_name(field.name()) }}: {{ field|type_name }}
+    {%- endfor %}
+    def __init__(self
+        {%- for field in rec.fields() %}, {{ self.field_name(field.name()) }}: {{ field|type_name }}
+        {%- if field.default_value().is_some() %} = ...{% endif %}
+        {%- endfor %}) -> None: ...
+    def __eq__(self, other: object) -> bool: ...
+
+{%- when Type::Enum { name, module_path } %}
+{%- let e = ci.get_enum_definition(name).unwrap() %}
+{%- if ci.is_name_used_as_error(name) %}
+
+class {{ type_name }}(Exception):
+    {%- for variant in e.variants() %}
+    class {{ variant.name()|class_name }}({{ type_name }}):
+        {%- if e.is_flat() && !variant.has_fields() %} ...
+        {%- else if e.is_flat() %}
+        {%- for field in variant.fields() %}
+        {{ self.field_name(field.name()) }}: {{ field|type_name }}
+        {%- endfor %}
+        def __init__(self, message: str
+            {%- for field in variant.fields() %}, {{ self.field_name(field.name()) }}: {{ field|type_name }}{% endfor %}) -> None: ...
+        {%- else %}
+        {%- for field in variant.fields() %}
+        {{ self.field_name(field.name()) }}: {{ field|type_name }}
+        {%- endfor %}
+        def __init__(self
+            {%- for field in variant.fields() %}, {{ self.field_name(field.name()) }}: {{ field|type_name }}{% endfor %}) -> None: ...
+        {%- endif %}
+    {%- endfor %}
+{%- else if e.is_flat() %}
+
+class {{ type_name }}({% if e.has_discriminants() %}enum.IntEnum{% else %}enum.Enum{% endif %}):
+    {%- for variant in e.variants() %}
+    {{ variant.name()|enum_variant_py }} = {% match variant.discr() %}{% when Some with (discr) %}{{ discr }}{% when None %}{{ loop.index }}{% endmatch %}
+    {%- endfor %}
+    {%- if e.is_non_exhaustive() %}
+    UNKNOWN = {% if e.has_discriminants() %}-9223372036854775808{% else %}0{% endif %}
+    {%- endif %}
+{%- else %}
+
+class {{ type_name }}:
+    {%- for variant in e.variants() %}
+    class {{ variant.name()|enum_variant_py }}({{ type_name }}):
+        {%- for field in variant.fields() %}
+        {{ self.field_name(field.name()) }}: {{ field|type_name }}
+        {%- endfor %}
+        def __init__(self
+            {%- for field in variant.fields() %}, {{ self.field_name(field.name()) }}: {{ field|type_name }}{% endfor %}) -> None: ...
+        def __eq__(self, other: object) -> bool: ...
+        def __hash__(self) -> int: ...
+    {%- endfor %}
+    {%- if e.is_non_exhaustive() %}
+    class UNKNOWN({{ type_name }}):
+        index: int
+        fields: bytes
+        def __init__(self, index: int, fields: bytes) -> None: ...
+        def __eq__(self, other: object) -> bool: ...
+        def __hash__(self) -> int: ...
+    {%- endif %}
+    {%- for variant in e.variants() %}
+    def is_{{ variant.name()|var_name }}(self) -> bool: ...
+    {%- endfor %}
+    {%- if e.is_non_exhaustive() %}
+    def is_unknown(self) -> bool: ...
+    {%- endif %}
+{%- endif %}
+
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+
+class {{ type_name }}(enum.Flag):
+    {%- for flag in flags.flags() %}
+    {{ flag.name()|enum_variant_py }} = {{ flag.value() }}
+    {%- endfor %}
+
+{%- when Type::Object { name, module_path, imp } %}
+{%- let obj = ci|get_object_definition(name) %}
+
+class {{ type_name }}:
+    {%- for c in obj.constants() %}
+    {{ c.name() }}: {{ c|type_name }}
+    {%- endfor %}
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    def __init__({% call arg_list("self", cons) %}) -> None: ...
+    {%- when None %}
+    {%- endmatch %}
+    {%- for cons in obj.alternate_constructors() %}
+    @classmethod
+    def {{ cons.name()|fn_name }}({% call arg_list("cls", cons) %}) -> {{ type_name }}: ...
+    {%- endfor %}
+    {%- if obj.has_method("close") %}
+    def uniffi_close(self) -> None: ...
+    {%- else %}
+    def close(self) -> None: ...
+    {%- endif %}
+    def __enter__(self) -> {{ type_name }}: ...
+    def __exit__(self, *exc_info: typing.Any) -> None: ...
+    {%- for meth in obj.methods() %}
+    {% call method_stub(meth.name()|fn_name, meth) %}
+    {%- endfor %}
+    {%- for tm in obj.uniffi_traits() %}
+    {%- match tm %}
+    {%- when UniffiTrait::Debug { fmt } %}
+    def __repr__(self) -> str: ...
+    {%- when UniffiTrait::Display { fmt } %}
+    def __str__(self) -> str: ...
+    {%- when UniffiTrait::Eq { eq, ne } %}
+    def __eq__(self, other: object) -> bool: ...
+    def __ne__(self, other: object) -> bool: ...
+    {%- when UniffiTrait::Hash { hash } %}
+    def __hash__(self) -> int: ...
+    {%- when UniffiTrait::Ord { cmp } %}
+    def __lt__(self, other: object) -> bool: ...
+    def __le__(self, other: object) -> bool: ...
+    def __gt__(self, other: object) -> bool: ...
+    def __ge__(self, other: object) -> bool: ...
+    {%- when UniffiTrait::Restore { serialize, restore } %}
+    def serialize_handle(self) -> str: ...
+    @staticmethod
+    def restore_handle(token: str) -> typing.Optional[{{ type_name }}]: ...
+    {%- endmatch %}
+    {%- endfor %}
+
+{%- when Type::CallbackInterface { name, module_path } %}
+{%- let cbi = ci|get_callback_interface_definition(name) %}
+
+class {{ type_name }}(typing.Protocol):
+    {%- for c in cbi.constants() %}
+    {{ c.name() }}: {{ c|type_name }}
+    {%- endfor %}
+    {%- for meth in cbi.methods() %}
+    {% call method_stub(meth.name()|fn_name, meth) %}
+    {%- endfor %}
+    {%- if cbi.methods().is_empty() && cbi.constants().is_empty() %} ...{% endif %}
+{%- if config.callback_conformance_checks() %}
+
+class {{ type_name }}Conformance:
+    def __init__(self, implementation: {{ type_name }}, threads: int = ..., iterations: int = ..., timeout: float = ...) -> None: ...
+    {%- for meth in cbi.methods() %}
+    def {{ format!("check_{}", meth.name())|fn_name }}({% call arg_list("self", meth) %}) -> None: ...
+    {%- endfor %}
+{%- endif %}
+
+{%- else %}
+{%- endmatch %}
+{%- endfor %}
+
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+{{ c.name() }}: {{ c|type_name }}
+{%- endfor %}
+
+{%- for func in ci.root_function_definitions() %}
+
+{% if func.is_async() %}async {% endif %}def {{ func.name()|fn_name }}({% call arg_list("", func) %}){% call returns(func) %}: ...
+{%- endfor %}
+
+{#- Sub-namespaces are modules, but they can't have stubs of their own since they aren't files #}
+{%- for ns in ci.sub_namespaces() %}
+
+class {{ ns }}:
+    {%- for func in ci.sub_namespace_function_definitions(ns) %}
+    @staticmethod
+    {% if func.is_async() %}async {% endif %}def {{ func.name()|fn_name }}({% call arg_list("", func) %}){% call returns(func) %}: ...
+    {%- endfor %}
+{%- endfor %}
+{%- if config.usage_hooks() %}
+
+def set_usage_hook(hook: typing.Optional[typing.Callable[[str], None]]) -> None: ...
+{%- endif %}
+{%- if ci.has_detachable_callback_interfaces() %}
+
+def uniffi_detach(obj: typing.Any) -> None: ...
+{%- endif %}
+{%- if config.hot_reload() %}
+
+def uniffi_reload() -> None: ...
+{%- endif %}
+{%- if config.remote_calls() %}
+
+def uniffi_set_remote_transport(transport: typing.Any) -> None: ...
+
+def uniffi_remote_dispatch(name: str, args: bytes) -> typing.Tuple[int, bytes]: ...
+{%- endif %}
+
+{#- The argument list of a function, method or constructor, starting with `receiver` #}
+{%- macro arg_list(receiver, func) -%}
+    {{ receiver }}
+    {%- for arg in func.arguments() -%}
+        {%- if !loop.first || !receiver.is_empty() %}, {% endif -%}
+        {{ self.field_name(arg.name()) }}: {{ arg|type_name }}
+        {%- if arg.default_value().is_some() %} = ...{% endif -%}
+    {%- endfor -%}
+{%- endmacro -%}
+
+{%- macro returns(func) -%}
+    {%- match func.return_type() -%}
+    {%- when Some with (return_type) -%}
+    {%- if func.is_async() %} -> {{ return_type|type_name }}
+    {%- else %} -> {% call py::return_type_name(return_type) %}
+    {%- endif -%}
+    {%- when None %} -> None
+    {%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro method_stub(py_method_name, meth) -%}
+    {% if meth.is_async() %}async {% endif %}def {{ py_method_name }}({% call arg_list("self", meth) %}){% call returns(meth) %}: ...
+{%- endmacro -%}
+
+{% import "macros.py" as py %}
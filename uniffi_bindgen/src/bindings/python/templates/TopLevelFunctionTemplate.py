@@ -1,24 +1,29 @@
 {%- if func.is_async() %}
 
 async def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
-    return await _rust_call_async(
+    return await {% call py::record_usage_start(func) %}_rust_call_async(
         _UniffiLib.{{ func.ffi_func().name() }},
         {{ func.result_type().borrow()|async_callback_fn }},
         {% call py::arg_list_lowered(func) %}
-    )
+    ){% call py::record_usage_end(func) %}
 
 {%- else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
-def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{{ return_type|type_name }}":
+def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}) -> "{% call py::return_type_name(return_type) %}":
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
-    return {{ return_type|lift_fn }}({% call py::to_ffi_call(func) %})
+    {%- call py::remote_call(func) %}
+    return {% call py::return_lift_fn(return_type) %}({% call py::to_ffi_call(func) %})
 {% when None %}
 
 def {{ func.name()|fn_name }}({%- call py::arg_list_decl(func) -%}):
+    {%- call py::callable_docstring(func, 4) %}
     {%- call py::setup_args(func) %}
+    {%- call py::remote_call(func) %}
     {% call py::to_ffi_call(func) %}
 {% endmatch %}
 {%- endif %}
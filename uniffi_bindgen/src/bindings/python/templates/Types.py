@@ -19,6 +19,9 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.py" %}
 
+{%- when Type::Char %}
+{%- include "CharHelper.py" %}
+
 {%- when Type::Int8 %}
 {%- include "Int8Helper.py" %}
 
@@ -64,6 +67,10 @@
 {%- include "EnumTemplate.py" %}
 {% endif %}
 
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+{%- include "FlagsTemplate.py" %}
+
 {%- when Type::Record { name, module_path } %}
 {%- include "RecordTemplate.py" %}
 
@@ -76,6 +83,27 @@
 {%- when Type::Duration %}
 {%- include "DurationHelper.py" %}
 
+{%- when Type::Url %}
+{%- include "UrlHelper.py" %}
+
+{%- when Type::Uuid %}
+{%- include "UuidHelper.py" %}
+
+{%- when Type::Decimal %}
+{%- include "DecimalHelper.py" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.py" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.py" %}
+
+{%- when Type::IpAddr %}
+{%- include "IpAddrHelper.py" %}
+
+{%- when Type::SocketAddr %}
+{%- include "SocketAddrHelper.py" %}
+
 {%- when Type::Optional { inner_type } %}
 {%- include "OptionalTemplate.py" %}
 
@@ -85,6 +113,15 @@
 {%- when Type::Map { key_type, value_type } %}
 {%- include "MapTemplate.py" %}
 
+{%- when Type::Compressed { inner_type } %}
+{%- include "CompressedTemplate.py" %}
+
+{%- when Type::Shared { inner_type } %}
+{%- include "SharedTemplate.py" %}
+
+{%- when Type::Weak { inner_type } %}
+{%- include "WeakTemplate.py" %}
+
 {%- when Type::CallbackInterface { name: id, module_path } %}
 {%- include "CallbackInterfaceTemplate.py" %}
 
@@ -99,8 +136,18 @@
 
 {%- else %}
 {%- endmatch %}
+{%- match self.code_hooks().type_extension(type_) %}
+{%- when Some with (extension) %}
+
+{{ extension }}
+{%- when None %}
+{%- endmatch %}
 {%- endfor %}
 
 {%- if ci.has_async_fns() %}
 {%- include "AsyncTypes.py" %}
 {%- endif %}
+
+{%- if self.zero_copy_bytes() %}
+{%- include "ZeroCopyBytes.py" %}
+{%- endif %}
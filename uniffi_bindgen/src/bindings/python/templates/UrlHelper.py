@@ -0,0 +1,5 @@
+# The Url type.  URLs are passed exactly like strings, Rust validates them when it lifts them.
+Url = str
+
+class _UniffiConverterUrl(_UniffiConverterString):
+    pass
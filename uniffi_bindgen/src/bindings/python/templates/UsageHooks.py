@@ -0,0 +1,16 @@
+# Called with the name of each API in this module as it's used, like `"Object.method"`.  The hook is
+# called on the calling thread before the call into Rust, so it should be cheap.
+#
+# This is only generated when `usage_hooks` is enabled in `uniffi.toml`.
+_uniffi_usage_hook = None
+
+def set_usage_hook(hook):
+    global _uniffi_usage_hook
+    _uniffi_usage_hook = hook
+
+def _uniffi_record_usage(name, call):
+    hook = _uniffi_usage_hook
+    if hook is not None:
+        hook(name)
+    return call()
+
@@ -0,0 +1,12 @@
+{{- self.add_import("uuid") }}
+# The Uuid type.  UUIDs are passed as their 16 raw bytes.
+Uuid = uuid.UUID
+
+class _UniffiConverterUuid(_UniffiConverterRustBuffer):
+    @staticmethod
+    def read(buf):
+        return uuid.UUID(bytes=bytes(buf.read(16)))
+
+    @staticmethod
+    def write(value, buf):
+        buf.write(value.bytes)
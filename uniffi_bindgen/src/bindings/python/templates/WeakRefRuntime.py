@@ -0,0 +1,47 @@
+class WeakRef:
+    """
+    A reference to a Rust object that doesn't keep it alive, from a `Weak` in Rust.
+
+    Use `upgrade()` to get the object while it's still alive.
+    """
+
+    def __init__(self, handle, lift_object):
+        self._handle = handle
+        self._lift_object = lift_object
+        {%- if self.hot_reload() %}
+        self._uniffi_generation = _uniffi_generation
+        {%- endif %}
+
+    def __del__(self):
+        # In case of partial initialization of instances.
+        handle = getattr(self, "_handle", None)
+        if handle is not None:
+            self._handle = None
+            {%- if self.hot_reload() %}
+            if getattr(self, "_uniffi_generation", None) != _uniffi_generation:
+                return
+            {%- endif %}
+            _rust_call(_UniffiLib.{{ ci.ffi_weak_ref_free().name() }}, handle)
+
+    def upgrade(self):
+        """Get a new reference to the object, or `None` if Rust dropped it."""
+        {%- if self.hot_reload() %}
+        if self._uniffi_generation != _uniffi_generation:
+            raise ValueError("WeakRef is from before the library was reloaded")
+        {%- endif %}
+        ptr = _rust_call(_UniffiLib.{{ ci.ffi_weak_ref_upgrade().name() }}, self._handle)
+        if ptr == 0:
+            return None
+        return self._lift_object(ptr)
+
+    def is_alive(self):
+        """Whether the object is still alive."""
+        return self.upgrade() is not None
+
+    # Rust takes ownership of the handles that it reads, so it's passed a copy of this one.
+    def _uniffi_clone_handle(self):
+        {%- if self.hot_reload() %}
+        if self._uniffi_generation != _uniffi_generation:
+            raise ValueError("WeakRef is from before the library was reloaded")
+        {%- endif %}
+        return _rust_call(_UniffiLib.{{ ci.ffi_weak_ref_clone().name() }}, self._handle)
@@ -0,0 +1,22 @@
+{%- if self.include_once_check("WeakRefRuntime.py") %}{% include "WeakRefRuntime.py" %}{% endif %}
+{%- let inner_ffi_converter = inner_type|ffi_converter_name %}
+
+class {{ ffi_converter_name }}(_UniffiConverterRustBuffer):
+    @staticmethod
+    def _lift_object(ptr):
+        {%- if self.cffi() %}
+        return {{ inner_ffi_converter }}.lift(_uniffi_ffi.cast("void *", ptr))
+        {%- else %}
+        return {{ inner_ffi_converter }}.lift(ptr)
+        {%- endif %}
+
+    @classmethod
+    def write(cls, value, buf):
+        if not isinstance(value, WeakRef):
+            raise TypeError("Expected WeakRef instance, {} found".format(type(value).__name__))
+        buf.write_u64(value._uniffi_clone_handle())
+
+    @classmethod
+    def read(cls, buf):
+        # The handle is ours now, it's freed when the `WeakRef` is garbage collected
+        return WeakRef(buf.read_u64(), cls._lift_object)
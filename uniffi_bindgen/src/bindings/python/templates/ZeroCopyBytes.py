@@ -0,0 +1,20 @@
+{{- self.add_import("weakref") }}
+
+class _UniffiConverterBytesNoCopy:
+    """
+    Lifts `bytes` returned by functions and methods when `zero_copy_bytes` is enabled.
+
+    Returns a read-only `memoryview` of the buffer Rust allocated, instead of copying it into a
+    `bytes` object.  The buffer is freed once the `memoryview`, and any slice of it, is garbage
+    collected.
+    """
+
+    @staticmethod
+    def lift(rbuf):
+        if rbuf.len < 4:
+            raise InternalError("Unexpected byte string buffer length")
+        # Skip the length that the bytes are serialized with
+        address = ctypes.cast(rbuf.data, ctypes.c_void_p).value + 4
+        array = (ctypes.c_ubyte * (rbuf.len - 4)).from_address(address)
+        weakref.finalize(array, rbuf.free)
+        return memoryview(array).cast("B").toreadonly()
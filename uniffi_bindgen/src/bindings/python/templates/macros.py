@@ -5,6 +5,8 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- call borrow_objects_start(func) -%}
+    {%- call record_usage_start(func) -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
 _rust_call_with_error({{ e|ffi_converter_name }},
@@ -14,9 +16,13 @@ _rust_call(
     _UniffiLib.{{ func.ffi_func().name() }},
     {%- call arg_list_lowered(func) -%}
 )
+    {%- call record_usage_end(func) -%}
+    {%- call borrow_objects_end(func) -%}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
+    {%- call borrow_objects_start(func) -%}
+    {%- call record_usage_start(func) -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
 _rust_call_with_error(
@@ -28,23 +34,112 @@ _rust_call(
     {{- prefix }},
     {%- call arg_list_lowered(func) -%}
 )
+    {%- call record_usage_end(func) -%}
+    {%- call borrow_objects_end(func) -%}
+{%- endmacro -%}
+
+{#-
+// Call a `&self` method, which borrows the object for the duration of the call
+-#}
+{%- macro to_ffi_call_with_self(func) -%}
+    {%- call borrow_self_start(func) -%}
+    {%- call record_usage_start(func) -%}
+    {%- match func.throws_type() -%}
+    {%- when Some with (e) -%}
+_rust_call_with_error(
+    {{ e|ffi_converter_name }},
+    {%- else -%}
+_rust_call(
+    {%- endmatch -%}
+    _UniffiLib.{{ func.ffi_func().name() }},
+    _uniffi_self_pointer,
+    {%- call arg_list_lowered(func) -%}
+)
+    {%- call record_usage_end(func) -%}
+)
+{%- endmacro -%}
+
+{#-
+// Rust borrows `[ByRef]` object arguments, and the object of `&self` methods, for the duration of
+// the call, without taking a reference of its own.  `_uniffi_call_with_pointers()` holds their call
+// counters until the call returns, so that a concurrent `close()` can't free them, and passes their
+// pointers to the call.  `arg_list_lowered` uses those pointers for the arguments.
+-#}
+{%- macro borrow_objects_start(func) -%}
+    {%- if func.borrows_objects() -%}
+_uniffi_call_with_pointers((
+        {%- call borrowed_objects(func) -%}
+    ), lambda {% call borrowed_pointers(func) %}: {% endif -%}
+{%- endmacro -%}
+
+{%- macro borrow_objects_end(func) -%}
+    {%- if func.borrows_objects() %}){% endif -%}
+{%- endmacro -%}
+
+{%- macro borrow_self_start(func) -%}
+_uniffi_call_with_pointers((self, {% call borrowed_objects(func) %}), lambda _uniffi_self_pointer, {% call borrowed_pointers(func) %}: {% endmacro -%}
+
+{%- macro borrowed_objects(func) -%}
+    {%- for arg in func.arguments() %}{% if arg.borrows_object() %}{{ self.field_name(arg.name()) }}, {% endif %}{% endfor -%}
+{%- endmacro -%}
+
+{%- macro borrowed_pointers(func) -%}
+    {%- for arg in func.arguments() %}{% if arg.borrows_object() %}_uniffi_borrowed{{ loop.index }}, {% endif %}{% endfor -%}
+{%- endmacro -%}
+
+{#-
+// When `usage_hooks` is enabled, calls are wrapped in `_uniffi_record_usage()`, which notifies the
+// hook installed with `set_usage_hook()`.  Otherwise nothing is generated.
+-#}
+{%- macro record_usage_start(func) -%}
+    {%- if self.usage_hooks() %}_uniffi_record_usage("{{ func.qualified_name() }}", lambda: {% endif -%}
+{%- endmacro -%}
+
+{%- macro record_usage_end(func) -%}
+    {%- if self.usage_hooks() %}){% endif -%}
 {%- endmacro -%}
 
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
-        {{ arg|lower_fn }}({{ arg.name()|var_name }})
+        {%- if func.takes_self_by_value() && !arg.borrows_object() %}
+        _uniffi_lowered{{ loop.index }}
+        {%- else if arg.borrows_bytes() %}
+        _UniffiForeignBytes.borrow({{ self.field_name(arg.name()) }})
+        {%- else if arg.borrows_object() %}
+        _uniffi_borrowed{{ loop.index }}
+        {%- else if arg.wipe_lifted_buffer() %}
+        _uniffi_lower_sensitive({{ arg|ffi_converter_name }}, {{ self.field_name(arg.name()) }})
+        {%- else %}
+        {{ arg|lower_fn }}({{ self.field_name(arg.name()) }})
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
 
+{#-
+// Methods that consume the object lower their arguments before they take its pointer, so that a
+// failure to lower them leaves the object usable.  `arg_list_lowered` passes these locals.
+-#}
+{%- macro lower_args_into_locals(func) %}
+        {%- for arg in func.arguments() %}
+        {%- if arg.borrows_bytes() %}
+        _uniffi_lowered{{ loop.index }} = _UniffiForeignBytes.borrow({{ self.field_name(arg.name()) }})
+        {%- else if arg.wipe_lifted_buffer() %}
+        _uniffi_lowered{{ loop.index }} = _uniffi_lower_sensitive({{ arg|ffi_converter_name }}, {{ self.field_name(arg.name()) }})
+        {%- else if !arg.borrows_object() %}
+        _uniffi_lowered{{ loop.index }} = {{ arg|lower_fn }}({{ self.field_name(arg.name()) }})
+        {%- endif %}
+        {%- endfor %}
+{%- endmacro -%}
+
 {#-
 // Arglist as used in Python declarations of methods, functions and constructors.
-// Note the var_name and type_name filters.
+// Note the field_name method and the type_name filter.
 -#}
 
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name }}
+        {{ self.field_name(arg.name()) }}
         {%- match arg.default_value() %}
         {%- when Some with(literal) %}: "typing.Union[object, {{ arg|type_name -}}]" = _DEFAULT
         {%- else %}: "{{ arg|type_name -}}"
@@ -73,8 +168,8 @@ _rust_call(
     {%- match arg.default_value() %}
     {%- when None %}
     {%- when Some with(literal) %}
-    if {{ arg.name()|var_name }} is _DEFAULT:
-        {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+    if {{ self.field_name(arg.name()) }} is _DEFAULT:
+        {{ self.field_name(arg.name()) }} = {{ literal|literal_py(arg.as_type().borrow()) }}
     {%- endmatch %}
     {% endfor -%}
 {%- endmacro -%}
@@ -88,12 +183,42 @@ _rust_call(
         {%- match arg.default_value() %}
         {%- when None %}
         {%- when Some with(literal) %}
-        if {{ arg.name()|var_name }} is _DEFAULT:
-            {{ arg.name()|var_name }} = {{ literal|literal_py(arg.as_type().borrow()) }}
+        if {{ self.field_name(arg.name()) }} is _DEFAULT:
+            {{ self.field_name(arg.name()) }} = {{ literal|literal_py(arg.as_type().borrow()) }}
         {%- endmatch %}
         {% endfor -%}
 {%- endmacro -%}
 
+{#-
+// With the `unstable_remote_calls` option, functions call their remote entry point when there's a
+// remote transport, see `RemoteCalls.py`
+-#}
+{%- macro remote_call(func) %}
+    {%- if self.is_remote_callable(func) %}
+    if _uniffi_remote_transport is not None:
+        return _uniffi_remote_call(
+            "{{ func.remote_fn_name() }}",
+            {% match func.throws_type() %}{% when Some with (e) %}{{ e|ffi_converter_name }}{% when None %}None{% endmatch %},
+            {% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|ffi_converter_name }}{% when None %}None{% endmatch %},
+            {%- for arg in func.arguments() %}
+            ({{ self.field_name(arg.name()) }}, {{ arg|ffi_converter_name }}),
+            {%- endfor %}
+        )
+    {%- endif %}
+{%- endmacro -%}
+
+{#-
+// The type returned by a function or method, and the function that lifts it.  When
+// `zero_copy_bytes` is enabled, `bytes` and `Vec<u8>` are returned as a `memoryview`.
+-#}
+{%- macro return_type_name(return_type) -%}
+    {%- if self.is_zero_copy_bytes(return_type) %}memoryview{% else %}{{ return_type|type_name }}{% endif -%}
+{%- endmacro -%}
+
+{%- macro return_lift_fn(return_type) -%}
+    {%- if self.is_zero_copy_bytes(return_type) %}_UniffiConverterBytesNoCopy.lift{% else %}{{ return_type|lift_fn }}{% endif -%}
+{%- endmacro -%}
+
 {#
  # Macro to call methods
  #}
@@ -101,31 +226,70 @@ _rust_call(
 {%  if meth.is_async() %}
 
     async def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        return await _rust_call_async(
+        return await {% call record_usage_start(meth) %}_rust_call_async(
             _UniffiLib.{{ func.ffi_func().name() }},
             {{ func.result_type().borrow()|async_callback_fn }},
-            self._pointer,
+            self._uniffi_pointer(),
             {% call arg_list_lowered(func) %}
-        )
+        ){% call record_usage_end(meth) %}
 
 {%- else -%}
 {%-     match meth.return_type() %}
 
 {%-         when Some with (return_type) %}
 
-    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{{ return_type|type_name }}":
+    def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}) -> "{% call return_type_name(return_type) %}":
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        return {{ return_type|lift_fn }}(
-            {% call to_ffi_call_with_prefix("self._pointer", meth) %}
+        {%- if meth.takes_self_by_value() %}
+        {%- call lower_args_into_locals(meth) %}
+        {%- endif %}
+        return {% call return_lift_fn(return_type) %}(
+            {%- if meth.takes_self_by_value() %}
+            {% call to_ffi_call_with_prefix("self._uniffi_take_pointer()", meth) %}
+            {%- else %}
+            {% call to_ffi_call_with_self(meth) %}
+            {%- endif %}
         )
 
 {%-         when None %}
 
     def {{ py_method_name }}(self, {% call arg_list_decl(meth) %}):
+        {%- call callable_docstring(meth, 8) %}
         {%- call setup_args_extra_indent(meth) %}
-        {% call to_ffi_call_with_prefix("self._pointer", meth) %}
+        {%- if meth.takes_self_by_value() %}
+        {%- call lower_args_into_locals(meth) %}
+        {% call to_ffi_call_with_prefix("self._uniffi_take_pointer()", meth) %}
+        {%- else %}
+        {% call to_ffi_call_with_self(meth) %}
+        {%- endif %}
 {%      endmatch %}
 {%  endif %}
 
 {% endmacro %}
+
+{#-
+// Renders the docstring of a function, object, record, enum or callback interface on its own
+// line, or nothing if it doesn't have one.
+-#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, method or constructor like `docstring`, followed by a note
+// for each sensitive argument.
+-#}
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- match self.docstring_with_notes(callable) %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
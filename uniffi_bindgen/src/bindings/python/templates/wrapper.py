@@ -1,5 +1,16 @@
+{%- match config.code_hooks().preamble() -%}
+{%- when Some with (preamble) -%}
+{{ preamble }}
+{% when None -%}
+{%- endmatch -%}
 # This file was autogenerated by some hot garbage in the `uniffi` crate.
 # Trust me, you don't want to mess with it!
+{%- match ci.namespace_docstring() %}
+{%- when Some with (docstring) %}
+
+{{ docstring|docstring(0) }}
+{%- when None %}
+{%- endmatch %}
 
 # Common helper code.
 #
@@ -21,6 +32,9 @@ import struct
 import contextlib
 import datetime
 import typing
+{%- if !ci.sub_namespaces().is_empty() %}
+import types
+{%- endif %}
 {%- if ci.has_async_fns() %}
 import asyncio
 {%- endif %}
@@ -28,42 +42,161 @@ import platform
 {%- for req in self.imports() %}
 {{ req.render() }}
 {%- endfor %}
+{%- match config.code_hooks().after_imports() %}
+{%- when Some with (after_imports) %}
+
+{{ after_imports }}
+{%- when None %}
+{%- endmatch %}
 
 # Used for default argument values
 _DEFAULT = object()
 
+{% if self.cffi() %}
+{% include "CffiRuntime.py" %}
+{%- else %}
 {% include "RustBufferTemplate.py" %}
 {% include "Helpers.py" %}
+{%- endif %}
 {% include "PointerManager.py" %}
 {% include "RustBufferHelper.py" %}
+{%- if config.usage_hooks() %}
+{% include "UsageHooks.py" %}
+{%- endif %}
+{%- if ci.has_main_thread_callback_interfaces() %}
+{% include "MainThread.py" %}
+{%- endif %}
+{%- if !self.cffi() %}
 
 # Contains loading, initialization code, and the FFI Function declarations.
 {% include "NamespaceLibraryTemplate.py" %}
+{%- endif %}
+{%- if config.remote_calls() %}
+
+{% include "RemoteCalls.py" %}
+{%- endif %}
 
 # Public interface members begin here.
 {{ type_helper_code }}
 
-{%- for func in ci.function_definitions() %}
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+{{ c.name() }} = {{ c.value()|literal_py(c) }}
+{%- endfor %}
+
+{%- for func in ci.root_function_definitions() %}
 {%- include "TopLevelFunctionTemplate.py" %}
 {%- endfor %}
+{%- if !ci.sub_namespaces().is_empty() %}
+
+def _uniffi_sub_namespace(name):
+    # The functions of a sub-namespace are in a module of their own, which is also added to
+    # `sys.modules` so that it can be imported as `<this module>.<name>`.
+    module = types.ModuleType(f"{__name__}.{name}")
+    sys.modules[module.__name__] = module
+    return module
+{%- endif %}
+
+{%- for ns in ci.sub_namespaces() %}
+
+{{ ns }} = _uniffi_sub_namespace("{{ ns }}")
+{%- for func in ci.sub_namespace_function_definitions(ns) %}
+{%- include "TopLevelFunctionTemplate.py" %}
+{{ ns }}.{{ func.name()|fn_name }} = {{ func.name()|fn_name }}
+del {{ func.name()|fn_name }}
+{%- endfor %}
+{%- endfor %}
+
+def uniffi_debug_live_handles() -> str:
+    """
+    Lists the objects that Rust passed to Python and that haven't been freed yet, and the callback
+    interfaces that Rust still holds, with a backtrace of where each one was created.
+
+    This needs the `handle-diagnostics` feature of the `uniffi` crate, otherwise the report only
+    says that it's disabled.
+    """
+    return {{ Type::String.borrow()|lift_fn }}(_rust_call(_UniffiLib.{{ ci.ffi_uniffi_debug_live_handles().name() }}))
+{%- if ci.has_detachable_callback_interfaces() %}
+
+def uniffi_detach(obj):
+    """
+    Tells Rust that `obj` is gone, when it was passed to Rust as a callback interface that Rust may
+    still hold.
+
+    This calls the `on_detach` hook that Rust set for the callback interface, and from then on
+    calling the object's methods from Rust fails rather than calling `obj`.  The bindings release
+    the object too, so that it can be garbage collected even if Rust holds on to it.
+    """
+    handle_map = _UniffiConverterCallbackInterface._handle_map
+    # Each time the object was passed to Rust it got a handle of its own.  All the callback
+    # interfaces share the handle map, so each of them is told about each handle, and the ones that
+    # don't hold it ignore it.
+    for handle in handle_map.handles_of(obj):
+        {%- for cbi in ci.callback_interface_definitions() %}
+        {%- if !cbi.is_closure() %}
+        _rust_call(_UniffiLib.{{ cbi.ffi_detach_callback().name() }}, handle)
+        {%- endif %}
+        {%- endfor %}
+        handle_map.remove(handle)
+{%- endif %}
+{%- match config.code_hooks().footer() %}
+{%- when Some with (footer) %}
+
+{{ footer }}
+{%- when None %}
+{%- endmatch %}
 
 __all__ = [
     "InternalError",
+    "InvalidArgumentError",
     {%- for e in ci.enum_definitions() %}
     "{{ e|type_name }}",
     {%- endfor %}
+    {%- for flags in ci.flags_definitions() %}
+    "{{ flags|type_name }}",
+    {%- endfor %}
     {%- for record in ci.record_definitions() %}
     "{{ record|type_name }}",
     {%- endfor %}
-    {%- for func in ci.function_definitions() %}
+    {%- for c in ci.constant_definitions() %}
+    "{{ c.name() }}",
+    {%- endfor %}
+    {%- for func in ci.root_function_definitions() %}
     "{{ func.name()|fn_name }}",
     {%- endfor %}
+    {%- for ns in ci.sub_namespaces() %}
+    "{{ ns }}",
+    {%- endfor %}
     {%- for obj in ci.object_definitions() %}
     "{{ obj|type_name }}",
     {%- endfor %}
     {%- for c in ci.callback_interface_definitions() %}
     "{{ c.name()|class_name }}",
+    {%- if config.callback_conformance_checks() && !c.is_closure() %}
+    "{{ c.name()|class_name }}Conformance",
+    {%- endif %}
     {%- endfor %}
+    {%- if config.usage_hooks() %}
+    "set_usage_hook",
+    {%- endif %}
+    {%- if ci.has_main_thread_callback_interfaces() %}
+    "set_main_thread_scheduler",
+    {%- endif %}
+    {%- if ci.has_weak_refs() %}
+    "WeakRef",
+    {%- endif %}
+    "uniffi_debug_live_handles",
+    {%- if ci.has_detachable_callback_interfaces() %}
+    "uniffi_detach",
+    {%- endif %}
+    {%- if config.hot_reload() %}
+    "uniffi_reload",
+    {%- endif %}
+    {%- if config.remote_calls() %}
+    "uniffi_set_remote_transport",
+    "uniffi_remote_dispatch",
+    {%- endif %}
 ]
 
 {% import "macros.py" as py %}
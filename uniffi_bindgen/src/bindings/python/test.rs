@@ -11,7 +11,7 @@ use camino::Utf8Path;
 use std::env;
 use std::ffi::OsString;
 use std::process::Command;
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{Pytest, TestEnvironment, UniFFITestHelper};
 
 /// Run Python tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -45,6 +45,7 @@ pub fn run_script(
         &out_dir,
         false,
     )?;
+    build_cffi_module(&out_dir)?;
 
     let pythonpath = env::var_os("PYTHONPATH").unwrap_or_else(|| OsString::from(""));
     let pythonpath = env::join_paths(
@@ -67,3 +68,48 @@ pub fn run_script(
     }
     Ok(())
 }
+
+/// Run a pytest test suite for a UniFFI test fixture
+///
+/// `tests_path` is a test file or a directory of tests.  The tests can import the fixture's
+/// bindings, and the test run fails if any test fails.
+pub fn run_pytest(tmp_dir: &str, fixture_name: &str, tests_path: &str) -> Result<()> {
+    let tests_path = Utf8Path::new(".").join(tests_path).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(fixture_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &tests_path)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    generate_bindings(
+        &cdylib_path,
+        None,
+        &[TargetLanguage::Python],
+        &out_dir,
+        false,
+    )?;
+    build_cffi_module(&out_dir)?;
+
+    let env = TestEnvironment::new(out_dir);
+    test_helper
+        .run_test_suite(&Pytest::new(tests_path), &env)?
+        .ensure_passed()
+}
+
+/// Build the cffi extension module of the bindings in `out_dir`, if they use the cffi backend
+///
+/// This runs the build script that's generated alongside the bindings, which needs the `cffi`
+/// package and a C compiler.
+fn build_cffi_module(out_dir: &Utf8Path) -> Result<()> {
+    let build_scripts = glob::glob(out_dir.join("*_cffi_build.py").as_str())?.flatten();
+    for build_script in build_scripts {
+        let status = Command::new("python3")
+            .current_dir(out_dir)
+            .arg(&build_script)
+            .spawn()
+            .context("Failed to spawn `python3` when building the cffi module")?
+            .wait()
+            .context("Failed to wait for `python3` when building the cffi module")?;
+        if !status.success() {
+            anyhow::bail!("building the cffi module failed");
+        }
+    }
+    Ok(())
+}
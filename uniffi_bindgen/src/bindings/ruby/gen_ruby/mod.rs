@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::collections::HashMap;
 
+use crate::ide_index::{ItemKind, ItemRef};
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -45,6 +46,7 @@ pub fn canonical_name(t: &Type) -> String {
         Type::String => "string".into(),
         Type::Bytes => "bytes".into(),
         Type::Boolean => "bool".into(),
+        Type::Char => "char".into(),
         // API defined types.
         // Note that these all get unique names, and the parser ensures that the names do not
         // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -53,10 +55,18 @@ pub fn canonical_name(t: &Type) -> String {
         // that the import handling code knows how to find the remote reference.
         Type::Object { name, .. } => format!("Type{name}"),
         Type::Enum { name, .. } => format!("Type{name}"),
+        Type::Flags { name, .. } => format!("Type{name}"),
         Type::Record { name, .. } => format!("Type{name}"),
         Type::CallbackInterface { name, .. } => format!("CallbackInterface{name}"),
         Type::Timestamp => "Timestamp".into(),
         Type::Duration => "Duration".into(),
+        Type::Url => "Url".into(),
+        Type::Uuid => "Uuid".into(),
+        Type::Decimal => "Decimal".into(),
+        Type::Json => "Json".into(),
+        Type::Path => "Path".into(),
+        Type::IpAddr => "IpAddr".into(),
+        Type::SocketAddr => "SocketAddr".into(),
         Type::ForeignExecutor => "ForeignExecutor".into(),
         // Recursive types.
         // These add a prefix to the name of the underlying type.
@@ -65,6 +75,9 @@ pub fn canonical_name(t: &Type) -> String {
         // acccidentally generating name collisions.
         Type::Optional { inner_type } => format!("Optional{}", canonical_name(inner_type)),
         Type::Sequence { inner_type } => format!("Sequence{}", canonical_name(inner_type)),
+        Type::Compressed { inner_type } => format!("Compressed{}", canonical_name(inner_type)),
+        Type::Shared { inner_type } => format!("Shared{}", canonical_name(inner_type)),
+        Type::Weak { inner_type } => format!("Weak{}", canonical_name(inner_type)),
         Type::Map {
             key_type,
             value_type,
@@ -134,6 +147,49 @@ impl<'a> RubyWrapper<'a> {
             canonical_name: &canonical_name,
         }
     }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| {
+            filters::var_name_rb(nm).unwrap_or_else(|_| nm.to_string())
+        })
+    }
+}
+
+/// Get the fully-qualified name of the Ruby symbol generated for an item, for the IDE index
+///
+/// Returns `None` for callback interfaces, which the Ruby bindings don't support.
+pub(crate) fn ide_symbol(ci: &ComponentInterface, item: &ItemRef<'_>) -> Option<String> {
+    if ci
+        .get_callback_interface_definition(item.parent.unwrap_or(item.name))
+        .is_some()
+    {
+        return None;
+    }
+    let module_name = filters::class_name_rb(ci.namespace()).ok()?;
+    let class_name = |nm: &str| filters::class_name_rb(nm).ok();
+    let fn_name = |nm: &str| filters::fn_name_rb(nm).ok();
+    Some(match (item.kind, item.parent) {
+        (ItemKind::Constructor, Some(parent)) if item.name == "new" => {
+            format!("{module_name}::{}#initialize", class_name(parent)?)
+        }
+        (ItemKind::Constructor, Some(parent)) => {
+            format!(
+                "{module_name}::{}.{}",
+                class_name(parent)?,
+                fn_name(item.name)?
+            )
+        }
+        (_, Some(parent)) => {
+            format!(
+                "{module_name}::{}#{}",
+                class_name(parent)?,
+                fn_name(item.name)?
+            )
+        }
+        (ItemKind::Function, None) => format!("{module_name}.{}", fn_name(item.name)?),
+        (_, None) => format!("{module_name}::{}", class_name(item.name)?),
+    })
 }
 
 mod filters {
@@ -154,7 +210,7 @@ mod filters {
             FfiType::Float64 => ":double".to_string(),
             FfiType::RustArcPtr(_) => ":pointer".to_string(),
             FfiType::RustBuffer(_) => "RustBuffer.by_value".to_string(),
-            FfiType::ForeignBytes => "ForeignBytes".to_string(),
+            FfiType::ForeignBytes => "ForeignBytes.by_value".to_string(),
             FfiType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
             FfiType::ForeignExecutorCallback => {
                 unimplemented!("Foreign executors are not implemented")
@@ -165,6 +221,7 @@ mod filters {
             FfiType::FutureCallback { .. } | FfiType::FutureCallbackData => {
                 unimplemented!("Async functions are not implemented")
             }
+            FfiType::Struct(name) => format!("ReprC{name}.by_value"),
         })
     }
 
@@ -211,6 +268,19 @@ mod filters {
         Ok(nm.to_string().to_snake_case())
     }
 
+    /// Render a docstring as Ruby comments, indented by `spaces`
+    pub fn docstring_rb(docstring: &str, spaces: i32) -> Result<String, askama::Error> {
+        let indent = " ".repeat(spaces as usize);
+        Ok(docstring
+            .lines()
+            .map(|line| match line {
+                "" => format!("{indent}#"),
+                line => format!("{indent}# {line}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
     pub fn var_name_rb(nm: &str) -> Result<String, askama::Error> {
         let nm = nm.to_string();
         let prefix = if is_reserved_word(&nm) { "_" } else { "" };
@@ -230,18 +300,32 @@ mod filters {
             Type::Int64 => format!("{ns}::uniffi_in_range({nm}, \"i64\", -2**63, 2**63)"),
             Type::UInt8 => format!("{ns}::uniffi_in_range({nm}, \"u8\", 0, 2**8)"),
             Type::UInt16 => format!("{ns}::uniffi_in_range({nm}, \"u16\", 0, 2**16)"),
-            Type::UInt32 => format!("{ns}::uniffi_in_range({nm}, \"u32\", 0, 2**32)"),
+            // Flags are plain integers in Ruby
+            Type::UInt32 | Type::Flags { .. } => {
+                format!("{ns}::uniffi_in_range({nm}, \"u32\", 0, 2**32)")
+            }
             Type::UInt64 => format!("{ns}::uniffi_in_range({nm}, \"u64\", 0, 2**64)"),
             Type::Float32 | Type::Float64 => nm.to_string(),
             Type::Boolean => format!("{nm} ? true : false"),
-            Type::Object { .. } | Type::Enum { .. } | Type::Record { .. } => nm.to_string(),
-            Type::String => format!("{ns}::uniffi_utf8({nm})"),
+            Type::Char => format!("{ns}::uniffi_char({nm})"),
+            Type::Object { .. } | Type::Enum { .. } | Type::Record { .. } | Type::Weak { .. } => {
+                nm.to_string()
+            }
+            Type::String | Type::Url | Type::Decimal | Type::Json => {
+                format!("{ns}::uniffi_utf8({nm})")
+            }
             Type::Bytes => format!("{ns}::uniffi_bytes({nm})"),
-            Type::Timestamp | Type::Duration => nm.to_string(),
+            // Accepts strings and anything with a `to_path`, like `Pathname`.
+            Type::Path => format!("File.path({nm})"),
+            Type::Timestamp | Type::Duration | Type::Uuid | Type::IpAddr | Type::SocketAddr => {
+                nm.to_string()
+            }
             Type::CallbackInterface { .. } => {
                 panic!("No support for coercing callback interfaces yet")
             }
             Type::Optional { inner_type: t } => format!("({nm} ? {} : nil)", coerce_rb(nm, ns, t)?),
+            Type::Compressed { inner_type: t } => coerce_rb(nm, ns, t)?,
+            Type::Shared { inner_type: t } => coerce_rb(nm, ns, t)?,
             Type::Sequence { inner_type: t } => {
                 let coerce_code = coerce_rb("v", ns, t)?;
                 if coerce_code == "v" {
@@ -250,8 +334,11 @@ mod filters {
                     format!("{nm}.map {{ |v| {coerce_code} }}")
                 }
             }
-            Type::Map { value_type: t, .. } => {
-                let k_coerce_code = coerce_rb("k", ns, &Type::String)?;
+            Type::Map {
+                key_type: k,
+                value_type: t,
+            } => {
+                let k_coerce_code = coerce_rb("k", ns, k)?;
                 let v_coerce_code = coerce_rb("v", ns, t)?;
 
                 if k_coerce_code == "k" && v_coerce_code == "v" {
@@ -268,7 +355,23 @@ mod filters {
         })
     }
 
-    pub fn lower_rb(nm: &str, type_: &Type) -> Result<String, askama::Error> {
+    /// The name of `type_` if it's a `[ReprC]` record, which is passed as a struct rather than in
+    /// a `RustBuffer`
+    fn repr_c_record_name<'a>(type_: &'a Type, ci: &ComponentInterface) -> Option<&'a str> {
+        match type_ {
+            Type::Record { name, .. } if ci.get_record_definition(name)?.is_repr_c() => Some(name),
+            _ => None,
+        }
+    }
+
+    pub fn lower_rb(
+        nm: &str,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        if let Some(name) = repr_c_record_name(type_, ci) {
+            return Ok(format!("{}._uniffi_lower({nm})", class_name_rb(name)?));
+        }
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -279,10 +382,17 @@ mod filters {
             | Type::Int64
             | Type::UInt64
             | Type::Float32
-            | Type::Float64 => nm.to_string(),
+            | Type::Float64
+            | Type::Flags { .. } => nm.to_string(),
             Type::Boolean => format!("({nm} ? 1 : 0)"),
-            Type::String => format!("RustBuffer.allocFromString({nm})"),
-            Type::Bytes => format!("RustBuffer.allocFromBytes({nm})"),
+            // Chars are passed as their Unicode scalar value
+            Type::Char => format!("{nm}.ord"),
+            // URLs, decimals and JSON values are passed as strings
+            Type::String | Type::Url | Type::Decimal | Type::Json => {
+                format!("RustBuffer.allocFromString({nm})")
+            }
+            // Paths are passed as their raw bytes
+            Type::Bytes | Type::Path => format!("RustBuffer.allocFromBytes({nm})"),
             Type::Object { name, .. } => format!("({}._uniffi_lower {nm})", class_name_rb(name)?),
             Type::CallbackInterface { .. } => {
                 panic!("No support for lowering callback interfaces yet")
@@ -291,8 +401,14 @@ mod filters {
             | Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
+            | Type::Compressed { .. }
+            | Type::Shared { .. }
+            | Type::Weak { .. }
             | Type::Timestamp
             | Type::Duration
+            | Type::Uuid
+            | Type::IpAddr
+            | Type::SocketAddr
             | Type::Map { .. } => format!(
                 "RustBuffer.alloc_from_{}({})",
                 class_name_rb(&canonical_name(type_))?,
@@ -304,7 +420,29 @@ mod filters {
         })
     }
 
-    pub fn lift_rb(nm: &str, type_: &Type) -> Result<String, askama::Error> {
+    /// Lower an argument that's marked as sensitive, see `RustBuffer.allocSensitive`
+    pub fn lower_sensitive_rb(
+        nm: &str,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        if repr_c_record_name(type_, ci).is_some() {
+            return lower_rb(nm, type_, ci);
+        }
+        Ok(format!(
+            "RustBuffer.allocSensitive {{ |builder| builder.write_{}({nm}) }}",
+            class_name_rb(&canonical_name(type_))?
+        ))
+    }
+
+    pub fn lift_rb(
+        nm: &str,
+        type_: &Type,
+        ci: &ComponentInterface,
+    ) -> Result<String, askama::Error> {
+        if let Some(name) = repr_c_record_name(type_, ci) {
+            return Ok(format!("{}._uniffi_lift({nm})", class_name_rb(name)?));
+        }
         Ok(match type_ {
             Type::Int8
             | Type::UInt8
@@ -313,11 +451,18 @@ mod filters {
             | Type::Int32
             | Type::UInt32
             | Type::Int64
-            | Type::UInt64 => format!("{nm}.to_i"),
+            | Type::UInt64
+            | Type::Flags { .. } => format!("{nm}.to_i"),
             Type::Float32 | Type::Float64 => format!("{nm}.to_f"),
             Type::Boolean => format!("1 == {nm}"),
-            Type::String => format!("{nm}.consumeIntoString"),
+            Type::Char => format!("[{nm}].pack('U')"),
+            Type::String | Type::Url | Type::Decimal | Type::Json => {
+                format!("{nm}.consumeIntoString")
+            }
             Type::Bytes => format!("{nm}.consumeIntoBytes"),
+            Type::Path => {
+                format!("{nm}.consumeIntoBytes.force_encoding(Encoding.find('filesystem'))")
+            }
             Type::Object { name, .. } => format!("{}._uniffi_allocate({nm})", class_name_rb(name)?),
             Type::CallbackInterface { .. } => {
                 panic!("No support for lifting callback interfaces, yet")
@@ -332,8 +477,14 @@ mod filters {
             Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
+            | Type::Compressed { .. }
+            | Type::Shared { .. }
+            | Type::Weak { .. }
             | Type::Timestamp
             | Type::Duration
+            | Type::Uuid
+            | Type::IpAddr
+            | Type::SocketAddr
             | Type::Map { .. } => format!(
                 "{}.consumeInto{}",
                 nm,
@@ -356,6 +507,12 @@ mod test_type {
         assert_eq!(canonical_name(&Type::UInt8), "u8");
         assert_eq!(canonical_name(&Type::String), "string");
         assert_eq!(canonical_name(&Type::Bytes), "bytes");
+        assert_eq!(canonical_name(&Type::Url), "Url");
+        assert_eq!(canonical_name(&Type::Uuid), "Uuid");
+        assert_eq!(canonical_name(&Type::Decimal), "Decimal");
+        assert_eq!(canonical_name(&Type::Json), "Json");
+        assert_eq!(canonical_name(&Type::Path), "Path");
+        assert_eq!(canonical_name(&Type::SocketAddr), "SocketAddr");
         assert_eq!(
             canonical_name(&Type::Optional {
                 inner_type: Box::new(Type::Sequence {
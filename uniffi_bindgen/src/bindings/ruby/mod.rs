@@ -4,7 +4,7 @@
 
 use std::process::Command;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use camino::Utf8Path;
 use fs_err as fs;
 
@@ -42,6 +42,16 @@ pub fn write_bindings(
 
 pub fn generate_ruby_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    if let Some(obj) = ci
+        .object_definitions()
+        .iter()
+        .find(|obj| obj.has_consuming_methods())
+    {
+        bail!(
+            "Ruby bindings don't support methods that consume an object, like the ones of `{}`",
+            obj.name()
+        );
+    }
     RubyWrapper::new(config.clone(), ci)
         .render()
         .context("failed to render ruby bindings")
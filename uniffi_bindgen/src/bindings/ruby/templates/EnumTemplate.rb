@@ -4,6 +4,10 @@ class {{ e.name()|class_name_rb }}
   {% for variant in e.variants() -%}
   {{ variant.name()|enum_name_rb }} = {{ loop.index }}
   {% endfor %}
+  {%- if e.is_non_exhaustive() %}
+  # A variant that was added to the Rust enum after these bindings were generated
+  UNKNOWN = 0
+  {%- endif %}
 end
 
 {% else %}
@@ -45,6 +49,13 @@ class {{ e.name()|class_name_rb }}
       true
     end
 
+    # `eql?` and `hash` are what `Hash` uses for its keys
+    alias eql? ==
+
+    def hash
+      [self.class{% for field in variant.fields() %}, @{{ field.name()|var_name_rb }}{% endfor %}].hash
+    end
+
     # For each variant, we have an `NAME?` method for easily checking
     # whether an instance is that variant.
     {% for variant in e.variants() %}
@@ -52,8 +63,41 @@ class {{ e.name()|class_name_rb }}
       instance_of? {{ e.name()|class_name_rb }}::{{ variant.name()|enum_name_rb }}
     end
     {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    def unknown?
+      instance_of? {{ e.name()|class_name_rb }}::UNKNOWN
+    end
+    {%- endif %}
   end
   {% endfor %}
+  {%- if e.is_non_exhaustive() %}
+
+  # A variant that was added to the Rust enum after these bindings were generated
+  class UNKNOWN
+    def to_s
+      "{{ e.name()|class_name_rb }}::UNKNOWN()"
+    end
+
+    def ==(other)
+      other.unknown?
+    end
+
+    alias eql? ==
+
+    def hash
+      self.class.hash
+    end
+
+    {% for variant in e.variants() %}
+    def {{ variant.name()|var_name_rb }}?
+      false
+    end
+    {% endfor %}
+    def unknown?
+      true
+    end
+  end
+  {%- endif %}
 end
 
 {% endif %}
@@ -19,12 +19,26 @@ end
 CALL_SUCCESS = 0
 CALL_ERROR = 1
 CALL_PANIC = 2
+CALL_INVALID_ARGUMENT = 3
 {%- for e in ci.enum_definitions() %}
 {% if ci.is_name_used_as_error(e.name()) %}
 {% if e.is_flat() %}
 class {{ e.name()|class_name_rb }}
     {%- for variant in e.variants() %}
+    {%- if variant.has_fields() %}
+    class {{ variant.name()|class_name_rb }} < StandardError
+      def initialize(message, {% for field in variant.fields() %}{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %})
+        {%- for field in variant.fields() %}
+        @{{ field.name()|var_name_rb }} = {{ field.name()|var_name_rb }}
+        {%- endfor %}
+        super(message)
+      end
+
+      attr_reader {% for field in variant.fields() %}:{{ field.name()|var_name_rb }}{% if !loop.last %}, {% endif %}{% endfor %}
+    end
+    {%- else %}
     {{ variant.name()|class_name_rb }} = Class.new StandardError
+    {%- endif %}
     {%- endfor %}
 {% else %}
 module {{ e.name()|class_name_rb }}
@@ -63,7 +77,7 @@ ERROR_MODULE_TO_READER_METHOD = {
 }
 
 private_constant :ERROR_MODULE_TO_READER_METHOD, :CALL_SUCCESS, :CALL_ERROR, :CALL_PANIC,
-                 :RustCallStatus
+                 :CALL_INVALID_ARGUMENT, :RustCallStatus
 
 def self.consume_buffer_into_error(error_module, rust_buffer)
   rust_buffer.consumeWithStream do |stream|
@@ -73,6 +87,22 @@ def self.consume_buffer_into_error(error_module, rust_buffer)
 end
 
 class InternalError < StandardError
+  # The backtrace of a Rust panic, when `RUST_BACKTRACE` is enabled
+  def initialize(message = nil, rust_backtrace = nil)
+    @rust_backtrace = rust_backtrace
+    super(message)
+  end
+
+  attr_reader :rust_backtrace
+end
+
+# The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+def self.consume_buffer_into_panic(rust_buffer)
+  rust_buffer.consumeWithStream do |stream|
+    message = stream.readString
+    backtrace = stream.readString
+    return InternalError.new(message, backtrace.empty? ? nil : backtrace)
+  end
 end
 
 def self.rust_call(fn_name, *args)
@@ -106,16 +136,19 @@ def self.rust_call_with_error(error_module, fn_name, *args)
     end
   when CALL_PANIC
     # When the rust code sees a panic, it tries to construct a RustBuffer
-    # with the message.  But if that code panics, then it just sends back
-    # an empty buffer.
+    # with the message and the backtrace.  But if that code panics, then it
+    # just sends back an empty buffer.
     if status.error_buf.len > 0
-      raise InternalError, status.error_buf.consumeIntoString()
+      raise consume_buffer_into_panic(status.error_buf)
     else
       raise InternalError, "Rust panic"
     end
+  when CALL_INVALID_ARGUMENT
+    # An argument couldn't be lifted, like a zero for a `NonZeroU32`
+    raise ArgumentError, status.error_buf.consumeIntoString()
   else
     raise InternalError, "Unknown call status: #{status.code}"
   end
 end
 
-private_class_method :consume_buffer_into_error
+private_class_method :consume_buffer_into_error, :consume_buffer_into_panic
@@ -0,0 +1,6 @@
+# Flags are passed as integers, combine them with `|` and test them with `&`.
+module {{ flags.name()|class_name_rb }}
+  {% for flag in flags.flags() -%}
+  {{ flag.name()|enum_name_rb }} = {{ flag.value() }}
+  {% endfor %}
+end
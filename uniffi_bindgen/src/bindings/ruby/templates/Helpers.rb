@@ -12,6 +12,13 @@ def self.uniffi_utf8(v)
   v
 end
 
+# Chars are one-character strings
+def self.uniffi_char(v)
+  v = uniffi_utf8(v)
+  raise ArgumentError, "expected a single character, got #{v.inspect}" unless v.length == 1
+  v
+end
+
 def self.uniffi_bytes(v)
   raise TypeError, "no implicit conversion of #{v} into String" unless v.respond_to?(:to_str)
   v.to_str
@@ -8,6 +8,16 @@ module UniFFILib
   {% else %}
   ffi_lib '{{ config.cdylib_name() }}'
   {% endif %}
+  {%- for rec in ci.record_definitions() %}
+  {%- if rec.is_repr_c() %}
+
+  # `{{ rec.name()|class_name_rb }}` is passed to and from Rust as this struct, rather than in a RustBuffer
+  class ReprC{{ rec.name() }} < FFI::Struct
+    layout {% for field in rec.fields() %}:{{ field.name() }}, {{ field.ffi_type().borrow()|type_ffi }}{% if !loop.last %},
+           {% endif %}{% endfor %}
+  end
+  {%- endif %}
+  {%- endfor %}
 
   {% for func in ci.iter_ffi_function_definitions() -%}
   attach_function :{{ func.name() }},
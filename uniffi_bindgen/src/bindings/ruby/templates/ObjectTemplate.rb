@@ -34,6 +34,7 @@ class {{ obj.name()|class_name_rb }}
 
   {%- match obj.primary_constructor() %}
   {%- when Some with (cons) %}
+  {%- call rb::callable_docstring(cons, 2) %}
   def initialize({% call rb::arg_list_decl(cons) -%})
     {%- call rb::coerce_args_extra_indent(cons) %}
     pointer = {% call rb::to_ffi_call(cons) %}
@@ -44,6 +45,7 @@ class {{ obj.name()|class_name_rb }}
   {%- endmatch %}
 
   {% for cons in obj.alternate_constructors() -%}
+  {%- call rb::callable_docstring(cons, 2) %}
   def self.{{ cons.name()|fn_name_rb }}({% call rb::arg_list_decl(cons) %})
     {%- call rb::coerce_args_extra_indent(cons) %}
     # Call the (fallible) function before creating any half-baked object instances.
@@ -57,13 +59,15 @@ class {{ obj.name()|class_name_rb }}
   {%- match meth.return_type() -%}
 
   {%- when Some with (return_type) -%}
+  {%- call rb::callable_docstring(meth, 2) %}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
     {%- call rb::coerce_args_extra_indent(meth) %}
     result = {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
-    return {{ "result"|lift_rb(return_type) }}
+    return {{ "result"|lift_rb(return_type, ci) }}
   end
 
   {%- when None -%}
+  {%- call rb::callable_docstring(meth, 2) %}
   def {{ meth.name()|fn_name_rb }}({% call rb::arg_list_decl(meth) %})
       {%- call rb::coerce_args_extra_indent(meth) %}
       {% call rb::to_ffi_call_with_prefix("@pointer", meth) %}
@@ -17,4 +17,25 @@ class {{ rec.name()|class_name_rb }}
 
     true
   end
+
+  # `eql?` and `hash` are what `Hash` uses for its keys
+  alias eql? ==
+
+  def hash
+    [self.class{% for field in rec.fields() %}, @{{ field.name()|var_name_rb }}{% endfor %}].hash
+  end
+  {%- if rec.is_repr_c() %}
+
+  def self._uniffi_lift(struct)
+    new({% for field in rec.fields() %}{{ "struct[:{}]"|format(field.name())|lift_rb(field.as_type().borrow(), ci) }}{% if !loop.last %}, {% endif %}{% endfor %})
+  end
+
+  def self._uniffi_lower(value)
+    struct = UniFFILib::ReprC{{ rec.name() }}.new
+    {%- for field in rec.fields() %}
+    struct[:{{ field.name() }}] = {{ "value.{}"|format(field.name()|var_name_rb)|lower_rb(field.as_type().borrow(), ci) }}
+    {%- endfor %}
+    struct
+  end
+  {%- endif %}
 end
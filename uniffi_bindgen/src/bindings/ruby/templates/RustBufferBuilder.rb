@@ -105,6 +105,12 @@ class RustBufferBuilder
     pack_into(1, 'c', v ? 1 : 0)
   end
 
+  {% when Type::Char -%}
+
+  def write_Char(v)
+    pack_into(4, 'L>', {{ ci.namespace()|class_name_rb }}::uniffi_char(v).ord)
+  end
+
   {% when Type::String -%}
 
   def write_String(v)
@@ -159,6 +165,64 @@ class RustBufferBuilder
     pack_into 4, 'L>', nanoseconds
   end
 
+  {% when Type::Url -%}
+  # The Url type, passed as a string.
+
+  def write_{{ canonical_type_name }}(v)
+    write_String(v)
+  end
+
+  {% when Type::Decimal -%}
+  # The Decimal type, passed as a string like "-12.50".
+
+  def write_{{ canonical_type_name }}(v)
+    write_String(v)
+  end
+
+  {% when Type::Path -%}
+  # The Path type, passed as the raw bytes of the path.
+
+  def write_{{ canonical_type_name }}(v)
+    write_Bytes(File.path(v))
+  end
+
+  {% when Type::Json -%}
+  # The Json type, passed as JSON text like '{"a": [1, 2]}'.
+
+  def write_{{ canonical_type_name }}(v)
+    write_String(v)
+  end
+
+  {% when Type::Uuid -%}
+  # The Uuid type, passed as 16 raw bytes.  UUIDs are strings like
+  # "67e55044-10b1-426f-9247-bb680e5fe0c8" in Ruby.
+
+  def write_{{ canonical_type_name }}(v)
+    hex = v.delete('-')
+    raise ArgumentError, 'Invalid UUID' unless hex.match?(/\A\h{32}\z/)
+
+    write [hex].pack('H*')
+  end
+
+  {% when Type::IpAddr -%}
+  # The IpAddr type, passed as a version byte, 4 or 6, followed by the bytes of the address.
+  # Addresses are `IPAddr`s in Ruby, strings like "192.0.2.1" are accepted too.
+
+  def write_{{ canonical_type_name }}(v)
+    addr = IPAddr.new(v.to_s)
+    pack_into(1, 'C', addr.ipv4? ? 4 : 6)
+    write addr.hton
+  end
+
+  {% when Type::SocketAddr -%}
+  # The SocketAddr type, passed as the IP address followed by the port.  Socket addresses are
+  # `Addrinfo`s in Ruby.
+
+  def write_{{ canonical_type_name }}(v)
+    write_IpAddr(v.ip_address)
+    pack_into(2, 'S>', v.ip_port)
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -173,6 +237,9 @@ class RustBufferBuilder
   # The Enum type {{ enum_name }}.
 
   def write_{{ canonical_type_name }}(v)
+    {%- if e.is_non_exhaustive() %}
+    raise ArgumentError, "Unknown variants of {{ enum_name }} can't be passed to Rust" if {% if e.is_flat() %}v == {{ enum_name|class_name_rb }}::UNKNOWN{% else %}v.unknown?{% endif %}
+    {%- endif %}
     {%- if e.is_flat() %}
     pack_into(4, 'l>', v)
     {%- else -%}
@@ -188,6 +255,14 @@ class RustBufferBuilder
  end
    {% endif %}
 
+  {% when Type::Flags { name: flags_name, module_path } -%}
+  # The Flags type {{ flags_name }}.
+
+  def write_{{ canonical_type_name }}(v)
+    v = {{ ci.namespace()|class_name_rb }}::uniffi_in_range(v, "u32", 0, 2**32)
+    pack_into(4, 'L>', v)
+  end
+
   {% when Type::Record { name: record_name, module_path } -%}
   {%- let rec = ci|get_record_definition(record_name) -%}
   # The Record type {{ record_name }}.
@@ -198,6 +273,32 @@ class RustBufferBuilder
     {%- endfor %}
   end
 
+  {% when Type::Compressed { inner_type } -%}
+  # The Compressed<T> type for {{ canonical_name(inner_type) }}.  Values sent to Rust are never
+  # compressed.
+
+  def write_{{ canonical_type_name }}(v)
+    pack_into(1, 'c', 0)
+    self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(v)
+  end
+
+  {% when Type::Shared { inner_type } -%}
+  # The Shared<T> type for {{ canonical_name(inner_type) }}.  Values sent to Rust are always
+  # inline.
+
+  def write_{{ canonical_type_name }}(v)
+    pack_into(1, 'c', 0)
+    self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(v)
+  end
+
+  {% when Type::Weak { inner_type } -%}
+  # The Weak<T> type for {{ canonical_name(inner_type) }}.
+
+  def write_{{ canonical_type_name }}(v)
+    raise TypeError.new "Expected a WeakRef instance, got #{v}" unless v.is_a? WeakRef
+    pack_into(8, 'Q>', v._uniffi_clone_handle)
+  end
+
   {% when Type::Optional { inner_type } -%}
   # The Optional<T> type for {{ canonical_name(inner_type) }}.
 
@@ -222,13 +323,13 @@ class RustBufferBuilder
   end
 
   {% when Type::Map { key_type: k, value_type: inner_type } -%}
-  # The Map<T> type for {{ canonical_name(inner_type) }}.
+  # The Map<K, V> type for {{ canonical_name(k) }} keys and {{ canonical_name(inner_type) }} values.
 
   def write_{{ canonical_type_name }}(items)
     pack_into(4, 'l>', items.size)
 
     items.each do |k, v|
-      write_String(k)
+      self.write_{{ canonical_name(k).borrow()|class_name_rb }}(k)
       self.write_{{ canonical_name(inner_type).borrow()|class_name_rb }}(v)
     end
   end
@@ -261,4 +362,39 @@ class RustBufferBuilder
   end
 end
 
+# A RustBufferBuilder for arguments that are marked as sensitive.
+#
+# Growing a buffer with `RustBuffer.reserve` frees the old allocation as it is, so this copies the
+# bytes into a new buffer instead, and zeroes the old one before freeing it.  A buffer that's
+# discarded is zeroed too.
+class SensitiveRustBufferBuilder < RustBufferBuilder
+  def discard
+    wipe unless @rust_buf.nil?
+    super
+  end
+
+  def reserve(num_bytes)
+    grow(num_bytes) if @rust_buf.len + num_bytes > @rust_buf.capacity
+    super
+  end
+
+  private
+
+  def grow(num_bytes)
+    grown = RustBuffer.alloc [@rust_buf.capacity * 2, @rust_buf.len + num_bytes].max
+    bytes = @rust_buf.data.get_bytes(0, @rust_buf.len)
+    grown.data.put_bytes(0, bytes)
+    grown.len = @rust_buf.len
+    bytes.bytesize.times { |i| bytes.setbyte(i, 0) }
+    wipe
+    @rust_buf.free
+    @rust_buf = grown
+  end
+
+  def wipe
+    @rust_buf.data.put_bytes(0, "\0" * @rust_buf.capacity)
+  end
+end
+
 private_constant :RustBufferBuilder
+private_constant :SensitiveRustBufferBuilder
@@ -96,6 +96,12 @@ class RustBufferStream
     raise InternalError, 'Unexpected byte for Boolean type'
   end
 
+  {% when Type::Char -%}
+
+  def readChar
+    [unpack_from(4, 'L>')].pack('U')
+  end
+
   {% when Type::String -%}
 
   def readString
@@ -150,6 +156,62 @@ class RustBufferStream
     Time.at(seconds, nanoseconds, :nanosecond, in: '+00:00').utc
   end
 
+  {% when Type::Url -%}
+  # The Url type, passed as a string.
+
+  def read{{ canonical_type_name }}
+    readString
+  end
+
+  {% when Type::Decimal -%}
+  # The Decimal type, passed as a string.
+
+  def read{{ canonical_type_name }}
+    readString
+  end
+
+  {% when Type::Path -%}
+  # The Path type, passed as the raw bytes of the path.  They're not necessarily valid UTF-8, so
+  # they're tagged with the filesystem encoding like the paths Ruby gets from `Dir`.
+
+  def read{{ canonical_type_name }}
+    readBytes.force_encoding(Encoding.find('filesystem'))
+  end
+
+  {% when Type::Json -%}
+  # The Json type, passed as JSON text.
+
+  def read{{ canonical_type_name }}
+    readString
+  end
+
+  {% when Type::Uuid -%}
+  # The Uuid type, passed as 16 raw bytes.
+
+  def read{{ canonical_type_name }}
+    read(16).unpack1('H*').unpack('a8a4a4a4a12').join('-')
+  end
+
+  {% when Type::IpAddr -%}
+  # The IpAddr type, passed as a version byte, 4 or 6, followed by the bytes of the address.
+
+  def read{{ canonical_type_name }}
+    version = unpack_from 1, 'C'
+    case version
+    when 4 then IPAddr.new_ntoh(read(4))
+    when 6 then IPAddr.new_ntoh(read(16))
+    else raise InternalError, "Unexpected IP address version #{version}"
+    end
+  end
+
+  {% when Type::SocketAddr -%}
+  # The SocketAddr type, passed as the IP address followed by the port.
+
+  def read{{ canonical_type_name }}
+    addr = readIpAddr
+    Addrinfo.tcp(addr.to_s, unpack_from(2, 'S>'))
+  end
+
   {% when Type::Object with { name: object_name, module_path, imp } -%}
   # The Object type {{ object_name }}.
 
@@ -158,6 +220,13 @@ class RustBufferStream
     return {{ object_name|class_name_rb }}._uniffi_allocate(pointer)
   end
 
+  {% when Type::Flags { name, module_path } -%}
+  # The Flags type {{ name }}.
+
+  def read{{ canonical_type_name }}
+    unpack_from 4, 'L>'
+  end
+
   {% when Type::Enum { name, module_path } -%}
   {%- let e = ci|get_enum_definition(name) -%}
   {% if !ci.is_name_used_as_error(name) %}
@@ -166,6 +235,10 @@ class RustBufferStream
 
   def read{{ canonical_type_name }}
     variant = unpack_from 4, 'l>'
+    {%- if e.is_non_exhaustive() %}
+    # The size of the fields, to skip variants that were added after these bindings were generated
+    size = unpack_from 4, 'l>'
+    {%- endif %}
     {% if e.is_flat() -%}
     {%- for variant in e.variants() %}
     if variant == {{ loop.index }}
@@ -173,7 +246,12 @@ class RustBufferStream
     end
     {%- endfor %}
 
+    {% if e.is_non_exhaustive() -%}
+    read size
+    {{ enum_name|class_name_rb }}::UNKNOWN
+    {%- else -%}
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
+    {%- endif %}
     {%- else -%}
     {%- for variant in e.variants() %}
     if variant == {{ loop.index }}
@@ -188,8 +266,13 @@ class RustBufferStream
         {% endif %}
     end
     {%- endfor %}
+    {%- if e.is_non_exhaustive() %}
+    read size
+    {{ enum_name|class_name_rb }}::UNKNOWN.new
+    {%- else %}
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     {%- endif %}
+    {%- endif %}
   end
 
   {% else %}
@@ -204,7 +287,8 @@ class RustBufferStream
     {%- for variant in e.variants() %}
     if variant == {{ loop.index }}
       return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
-        readString()
+        readString(){% for field in variant.fields() %},
+        read{{ canonical_name(field.as_type().borrow()).borrow()|class_name_rb }}(){% endfor %}
       )
     end
     {%- endfor %}
@@ -242,6 +326,89 @@ class RustBufferStream
     )
   end
 
+  {% when Type::Compressed { inner_type } -%}
+  # The Compressed<T> type for {{ canonical_name(inner_type) }}.  Rust compresses large values
+  # with raw deflate.
+
+  def read{{ canonical_type_name }}
+    algorithm = unpack_from 1, 'c'
+
+    if algorithm == 0
+      return read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+    elsif algorithm == 1
+      size = unpack_from 4, 'L>'
+      compressed_size = unpack_from 4, 'L>'
+      inflater = Zlib::Inflate.new(-Zlib::MAX_WBITS)
+      data = String.new
+      begin
+        # Stop as soon as the data is larger than the expected size
+        inflater.inflate(read(compressed_size)) do |chunk|
+          data << chunk
+          raise InternalError, 'Compressed value has the wrong size' if data.bytesize > size
+        end
+        raise InternalError, 'Compressed value has the wrong size' unless inflater.finished? && data.bytesize == size
+      ensure
+        inflater.close
+      end
+
+      # Read the value from a RustBuffer-like struct over the decompressed data, which is owned by
+      # Ruby rather than Rust so it's never freed with `RustBuffer#free`.
+      memory = FFI::MemoryPointer.new(:char, size)
+      memory.put_bytes(0, data)
+      rbuf = RustBuffer.new
+      rbuf[:capacity] = size
+      rbuf[:len] = size
+      rbuf[:data] = memory
+      stream = RustBufferStream.new rbuf
+      value = stream.read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+      raise InternalError, 'junk data left in buffer after decompressing value' if stream.remaining != 0
+
+      return value
+    else
+      raise InternalError, 'Unknown compression algorithm for {{ canonical_type_name }}'
+    end
+  end
+
+  {% when Type::Shared { inner_type } -%}
+  # The Shared<T> type for {{ canonical_name(inner_type) }}.  Rust passes large values in a buffer
+  # of their own, which we free once the value has been read.
+
+  def read{{ canonical_type_name }}
+    transport = unpack_from 1, 'c'
+
+    if transport == 0
+      return read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+    elsif transport == 1
+      rbuf = RustBuffer.new
+      rbuf[:capacity] = unpack_from 4, 'l>'
+      rbuf[:len] = unpack_from 4, 'l>'
+      offset = unpack_from 4, 'l>'
+      rbuf[:data] = FFI::Pointer.new(unpack_from 8, 'Q>')
+
+      begin
+        stream = RustBufferStream.new rbuf
+        stream.read offset
+        value = stream.read{{ canonical_name(inner_type).borrow()|class_name_rb }}
+        raise InternalError, 'junk data left in buffer after reading shared value' if stream.remaining != 0
+      ensure
+        rbuf.free
+      end
+
+      return value
+    else
+      raise InternalError, 'Unknown transport for {{ canonical_type_name }}'
+    end
+  end
+
+  {% when Type::Weak { inner_type } -%}
+  # The Weak<T> type for {{ canonical_name(inner_type) }}.  The handle that Rust wrote is ours
+  # now, it's freed when the WeakRef is GC'd.
+
+  def read{{ canonical_type_name }}
+    handle = unpack_from 8, 'Q>'
+    return WeakRef._uniffi_allocate(handle, ->(pointer) { {{ "FFI::Pointer.new(pointer)"|lift_rb(inner_type, ci) }} })
+  end
+
   {% when Type::Optional { inner_type } -%}
   # The Optional<T> type for {{ canonical_name(inner_type) }}.
 
@@ -275,7 +442,7 @@ class RustBufferStream
   end
 
   {% when Type::Map { key_type: k, value_type: inner_type } -%}
-  # The Map<T> type for {{ canonical_name(inner_type) }}.
+  # The Map<K, V> type for {{ canonical_name(k) }} keys and {{ canonical_name(inner_type) }} values.
 
   def read{{ canonical_type_name }}
     count = unpack_from 4, 'l>'
@@ -283,7 +450,7 @@ class RustBufferStream
 
     items = {}
     count.times do
-      key = readString
+      key = read{{ canonical_name(k).borrow()|class_name_rb }}
       items[key] = read{{ canonical_name(inner_type).borrow()|class_name_rb }}
     end
 
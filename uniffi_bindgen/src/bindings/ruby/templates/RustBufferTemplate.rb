@@ -48,6 +48,20 @@ class RustBuffer < FFI::Struct
     end
   end
 
+  # Lower an argument that's marked as sensitive, see `SensitiveRustBufferBuilder`.
+  def self.allocSensitive
+    builder = SensitiveRustBufferBuilder.new
+
+    begin
+      yield builder
+    rescue => e
+      builder.discard
+      raise e
+    end
+
+    builder.finalize
+  end
+
   # The RustBuffer will be freed once the context-manager exits, ensuring that we don't
   # leak it even if an error occurs.
   def consumeWithStream
@@ -124,6 +138,48 @@ class RustBuffer < FFI::Struct
     end
   end
 
+  {% when Type::Uuid -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::IpAddr -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::SocketAddr -%}
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
   {% when Type::Record { name: record_name, module_path } -%}
   {%- let rec = ci|get_record_definition(record_name) -%}
   # The Record type {{ record_name }}.
@@ -160,6 +216,54 @@ class RustBuffer < FFI::Struct
   end
   {% endif %}
 
+  {% when Type::Compressed { inner_type } -%}
+  # The Compressed<T> type for {{ canonical_name(inner_type) }}.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::Weak { inner_type } -%}
+  # The Weak<T> type for {{ canonical_name(inner_type) }}.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
+  {% when Type::Shared { inner_type } -%}
+  # The Shared<T> type for {{ canonical_name(inner_type) }}.
+
+  def self.alloc_from_{{ canonical_type_name }}(v)
+    RustBuffer.allocWithBuilder do |builder|
+      builder.write_{{ canonical_type_name }}(v)
+      return builder.finalize()
+    end
+  end
+
+  def consumeInto{{ canonical_type_name }}
+    consumeWithStream do |stream|
+      return stream.read{{ canonical_type_name }}
+    end
+  end
+
   {% when Type::Optional { inner_type } -%}
   # The Optional<T> type for {{ canonical_name(inner_type) }}.
 
@@ -227,6 +331,19 @@ module UniFFILib
       self[:data]
     end
 
+    # Used for `[ByRef] bytes` arguments.  Ruby strings can't be pinned, so the bytes are copied
+    # into native memory once, which the Rust code then reads in place.
+    def self.from_bytes(value)
+      memory = FFI::MemoryPointer.new(:uint8, value.bytesize)
+      memory.put_bytes(0, value)
+      bytes = new
+      bytes[:len] = value.bytesize
+      bytes[:data] = memory
+      # Keep the memory alive for as long as the struct
+      bytes.instance_variable_set(:@memory, memory)
+      bytes
+    end
+
     def to_s
       "ForeignBytes(len=#{len}, data=#{data.read_bytes(len)})"
     end
@@ -1,14 +1,14 @@
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
-
+{% call rb::callable_docstring(func, 0) %}
 def self.{{ func.name()|fn_name_rb }}({%- call rb::arg_list_decl(func) -%})
   {%- call rb::coerce_args(func) %}
   result = {% call rb::to_ffi_call(func) %}
-  return {{ "result"|lift_rb(return_type) }}
+  return {{ "result"|lift_rb(return_type, ci) }}
 end
 
 {% when None %}
-
+{% call rb::callable_docstring(func, 0) %}
 def self.{{ func.name()|fn_name_rb }}({%- call rb::arg_list_decl(func) -%})
   {%- call rb::coerce_args(func) %}
   {% call rb::to_ffi_call(func) %}
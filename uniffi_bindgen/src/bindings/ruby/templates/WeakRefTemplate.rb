@@ -0,0 +1,39 @@
+# A reference to a Rust object that doesn't keep it alive, from a `Weak` in Rust.
+#
+# Use `upgrade` to get the object while it's still alive.
+class WeakRef
+
+  # A private helper for initializing instances from a handle that we own, which is freed when
+  # the instance is GC'd.
+  def self._uniffi_allocate(handle, lift_object)
+    inst = allocate
+    inst.instance_variable_set :@handle, handle
+    inst.instance_variable_set :@lift_object, lift_object
+    ObjectSpace.define_finalizer(inst, _uniffi_define_finalizer_by_handle(handle))
+    return inst
+  end
+
+  # N.B. it's important that this does not capture a reference to the actual instance.
+  def self._uniffi_define_finalizer_by_handle(handle)
+    Proc.new do |_id|
+      {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_weak_ref_free().name() }}, handle)
+    end
+  end
+
+  # Get a new reference to the object, or nil if Rust dropped it.
+  def upgrade
+    pointer = {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_weak_ref_upgrade().name() }}, @handle)
+    return nil if pointer == 0
+    return @lift_object.call(pointer)
+  end
+
+  # Whether the object is still alive.
+  def alive?
+    return !upgrade.nil?
+  end
+
+  # Rust takes ownership of the handles that it reads, so it's passed a copy of this one.
+  def _uniffi_clone_handle
+    return {{ ci.namespace()|class_name_rb }}.rust_call(:{{ ci.ffi_weak_ref_clone().name() }}, @handle)
+  end
+end
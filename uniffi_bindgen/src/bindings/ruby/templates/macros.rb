@@ -31,7 +31,13 @@
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg.name()|lower_rb(arg.as_type().borrow()) }}
+        {%- if arg.borrows_bytes() -%}
+        UniFFILib::ForeignBytes.from_bytes({{ arg.name() }})
+        {%- else if arg.wipe_lifted_buffer() -%}
+        {{ arg.name()|lower_sensitive_rb(arg.as_type().borrow(), ci) }}
+        {%- else %}
+        {{- arg.name()|lower_rb(arg.as_type().borrow(), ci) }}
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -71,3 +77,15 @@
         {{ arg.name() }} = {{ arg.name()|coerce_rb(ci.namespace()|class_name_rb, arg.as_type().borrow()) }}
         {%- endfor %}
 {%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, method or constructor as comments on their own lines,
+// followed by a note for each sensitive argument, or nothing if there's neither.
+-#}
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- match self.docstring_with_notes(callable) %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring_rb(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
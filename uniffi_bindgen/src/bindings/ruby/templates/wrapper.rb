@@ -14,6 +14,9 @@
 # helpers directly inline like we're doing here.
 
 require 'ffi'
+require 'ipaddr'
+require 'socket'
+require 'zlib'
 
 
 module {{ ci.namespace()|class_name_rb }}
@@ -27,6 +30,10 @@ module {{ ci.namespace()|class_name_rb }}
   {% include "ErrorTemplate.rb" %}
 
   {% include "NamespaceLibraryTemplate.rb" %}
+  {%- if ci.has_weak_refs() %}
+
+  {% include "WeakRefTemplate.rb" %}
+  {%- endif %}
 
   # Public interface members begin here.
 
@@ -36,6 +43,10 @@ module {{ ci.namespace()|class_name_rb }}
   {% endif %}
   {%- endfor -%}
 
+  {%- for flags in ci.flags_definitions() %}
+  {% include "FlagsTemplate.rb" %}
+  {% endfor %}
+
   {%- for rec in ci.record_definitions() %}
   {% include "RecordTemplate.rb" %}
   {% endfor %}
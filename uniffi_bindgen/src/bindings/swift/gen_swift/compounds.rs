@@ -105,3 +105,88 @@ impl CodeType for MapCodeType {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct CompressedCodeType {
+    inner: Type,
+}
+
+impl CompressedCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+// Compressed values are presented as their inner type, only the FfiConverter differs.
+impl CodeType for CompressedCodeType {
+    fn type_label(&self) -> String {
+        super::SwiftCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Compressed{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::SwiftCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
+#[derive(Debug)]
+pub struct SharedCodeType {
+    inner: Type,
+}
+
+impl SharedCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+// Shared values are presented as their inner type, only the FfiConverter differs.
+impl CodeType for SharedCodeType {
+    fn type_label(&self) -> String {
+        super::SwiftCodeOracle.find(&self.inner).type_label()
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Shared{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+
+    fn literal(&self, literal: &Literal) -> String {
+        super::SwiftCodeOracle.find(&self.inner).literal(literal)
+    }
+}
+
+#[derive(Debug)]
+pub struct WeakCodeType {
+    inner: Type,
+}
+
+impl WeakCodeType {
+    pub fn new(inner: Type) -> Self {
+        Self { inner }
+    }
+}
+
+impl CodeType for WeakCodeType {
+    fn type_label(&self) -> String {
+        format!(
+            "WeakRef<{}>",
+            super::SwiftCodeOracle.find(&self.inner).type_label()
+        )
+    }
+
+    fn canonical_name(&self) -> String {
+        format!(
+            "Weak{}",
+            super::SwiftCodeOracle.find(&self.inner).canonical_name()
+        )
+    }
+}
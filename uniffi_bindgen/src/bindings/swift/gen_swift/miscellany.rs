@@ -29,3 +29,94 @@ impl CodeType for DurationCodeType {
         "Duration".into()
     }
 }
+
+#[derive(Debug)]
+pub struct UrlCodeType;
+
+impl CodeType for UrlCodeType {
+    fn type_label(&self) -> String {
+        "URL".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Url".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct UuidCodeType;
+
+impl CodeType for UuidCodeType {
+    fn type_label(&self) -> String {
+        "UUID".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Uuid".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct DecimalCodeType;
+
+impl CodeType for DecimalCodeType {
+    fn type_label(&self) -> String {
+        "Decimal".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Decimal".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct JsonCodeType;
+
+impl CodeType for JsonCodeType {
+    fn type_label(&self) -> String {
+        "Any".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Json".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct PathCodeType;
+
+impl CodeType for PathCodeType {
+    fn type_label(&self) -> String {
+        "URL".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "Path".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct IpAddrCodeType;
+
+impl CodeType for IpAddrCodeType {
+    fn type_label(&self) -> String {
+        "String".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "IpAddr".into()
+    }
+}
+
+#[derive(Debug)]
+pub struct SocketAddrCodeType;
+
+impl CodeType for SocketAddrCodeType {
+    fn type_label(&self) -> String {
+        "String".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "SocketAddr".into()
+    }
+}
@@ -7,13 +7,14 @@ use std::borrow::Borrow;
 use std::cell::RefCell;
 use std::collections::{BTreeSet, HashMap, HashSet};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use askama::Template;
 use heck::{ToLowerCamelCase, ToUpperCamelCase};
 use serde::{Deserialize, Serialize};
 
 use super::Bindings;
-use crate::backend::{CodeType, TemplateExpression};
+use crate::backend::{CodeHooks, CodeType, FieldNaming, TemplateExpression};
+use crate::ide_index::{ItemKind, ItemRef};
 use crate::interface::*;
 use crate::BindingsConfig;
 
@@ -23,6 +24,7 @@ mod custom;
 mod enum_;
 mod executor;
 mod external;
+mod flags;
 mod miscellany;
 mod object;
 mod primitives;
@@ -136,6 +138,18 @@ pub struct Config {
     ffi_module_filename: Option<String>,
     generate_module_map: Option<bool>,
     omit_argument_labels: Option<bool>,
+    usage_hooks: Option<bool>,
+    callback_conformance_checks: Option<bool>,
+    #[serde(default)]
+    pooled_records: Vec<String>,
+    zero_copy_bytes: Option<bool>,
+    codable: Option<bool>,
+    field_naming: Option<FieldNaming>,
+    open_classes: Option<bool>,
+    #[serde(default)]
+    actors: Vec<String>,
+    #[serde(flatten)]
+    code_hooks: CodeHooks,
     #[serde(default)]
     custom_types: HashMap<String, CustomTypeConfig>,
 }
@@ -201,6 +215,59 @@ impl Config {
     pub fn omit_argument_labels(&self) -> bool {
         self.omit_argument_labels.unwrap_or(false)
     }
+
+    /// Whether to generate a hook that's called with the name of each API as it's used.
+    pub fn usage_hooks(&self) -> bool {
+        self.usage_hooks.unwrap_or(false)
+    }
+
+    /// Whether to generate conformance checks for the foreign implementations of callback interfaces.
+    pub fn callback_conformance_checks(&self) -> bool {
+        self.callback_conformance_checks.unwrap_or(false)
+    }
+
+    /// Whether the record `name` is lifted straight from the Rust buffer, without copying it first.
+    pub fn is_pooled_record(&self, name: &str) -> bool {
+        self.pooled_records.iter().any(|n| n == name)
+    }
+
+    /// Whether `bytes` and `Vec<u8>` returned by functions and methods are read in place, without
+    /// copying them.
+    pub fn zero_copy_bytes(&self) -> bool {
+        self.zero_copy_bytes.unwrap_or(false)
+    }
+
+    /// Whether values of `type_` returned by functions and methods are read in place.
+    pub fn is_zero_copy_bytes(&self, type_: &Type) -> bool {
+        self.zero_copy_bytes() && crate::backend::is_byte_buffer(type_)
+    }
+
+    /// Whether records and enums conform to `Codable`.
+    pub fn codable(&self) -> bool {
+        self.codable.unwrap_or(false)
+    }
+
+    /// How record fields and function arguments are cased, camelCase by default.
+    pub fn field_naming(&self) -> FieldNaming {
+        self.field_naming.unwrap_or(FieldNaming::CamelCase)
+    }
+
+    /// Whether object classes and their methods are `open`, so they can be subclassed by test
+    /// doubles in other modules.
+    pub fn open_classes(&self) -> bool {
+        self.open_classes.unwrap_or(false)
+    }
+
+    /// Whether the object `name` is generated as an `actor` rather than a class.  All its methods
+    /// must be async, see `check_actors()`.
+    pub fn is_actor(&self, name: &str) -> bool {
+        self.actors.iter().any(|n| n == name)
+    }
+
+    /// Code from `uniffi.toml` to insert into the generated bindings
+    pub fn code_hooks(&self) -> &CodeHooks {
+        &self.code_hooks
+    }
 }
 
 impl BindingsConfig for Config {
@@ -224,6 +291,7 @@ impl BindingsConfig for Config {
 /// Generate UniFFI component bindings for Swift, as strings in memory.
 ///
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bindings> {
+    check_actors(config, ci)?;
     let header = BridgingHeader::new(config, ci)
         .render()
         .context("failed to render Swift bridging header")?;
@@ -246,6 +314,55 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bin
     })
 }
 
+// Objects in the `actors` option are generated as a Swift `actor`, whose methods can only be called
+// asynchronously from outside of it, so the bindings can only do that for objects that are used
+// through async methods only.
+fn check_actors(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    for name in &config.actors {
+        let Some(obj) = ci.get_object_definition(name) else {
+            bail!("`{name}` is in the `actors` option, but there's no object with that name");
+        };
+        if let Some(meth) = obj.methods().into_iter().find(|m| !m.is_async()) {
+            bail!(
+                "`{name}` can't be an actor, its method `{}` isn't async",
+                meth.name()
+            );
+        }
+        if obj.has_consuming_methods() {
+            bail!("`{name}` can't be an actor, it has methods that consume it");
+        }
+        if config.open_classes() {
+            bail!("`{name}` can't be an actor with the `open_classes` option");
+        }
+    }
+    Ok(())
+}
+
+/// Get the fully-qualified name of the Swift symbol generated for an item, for the IDE index
+pub(crate) fn ide_symbol(config: &Config, item: &ItemRef<'_>) -> String {
+    let oracle = SwiftCodeOracle;
+    let module_name = config.module_name();
+    match (item.kind, item.parent) {
+        (ItemKind::Constructor, Some(parent)) if item.name == "new" => {
+            format!("{module_name}.{}.init", oracle.class_name(parent))
+        }
+        (_, Some(parent)) => format!(
+            "{module_name}.{}.{}",
+            oracle.class_name(parent),
+            oracle.fn_name(item.name)
+        ),
+        (ItemKind::Function, None) => match item.sub_namespace {
+            Some(ns) => format!(
+                "{module_name}.{}.{}",
+                oracle.class_name(ns),
+                oracle.fn_name(item.name)
+            ),
+            None => format!("{module_name}.{}", oracle.fn_name(item.name)),
+        },
+        (_, None) => format!("{module_name}.{}", oracle.class_name(item.name)),
+    }
+}
+
 /// Renders Swift helper code for all types
 ///
 /// This template is a bit different than others in that it stores internal state from the render
@@ -273,6 +390,21 @@ impl<'a> TypeRenderer<'a> {
 
     // The following methods are used by the `Types.swift` macros.
 
+    // The Swift rendering of a record field or function argument, see `Config::field_naming()`
+    fn field_name(&self, nm: &str) -> String {
+        quote_general_keyword(self.config.field_naming().apply(nm))
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
+
+    // Like `field_name()`, but for argument labels, which need less quoting
+    fn field_label(&self, nm: &str) -> String {
+        quote_arg_keyword(self.config.field_naming().apply(nm))
+    }
+
     // Helper for the including a template, but only once.
     //
     // The first time this is called with a name it will return true, indicating that we should
@@ -314,6 +446,16 @@ impl<'config, 'ci> BridgingHeader<'config, 'ci> {
             ci,
         }
     }
+
+    // Nullability annotations for the pointers in `FfiDeclarations.h`, which is shared with the
+    // C header backend.
+    fn nullable(&self) -> &'static str {
+        " _Nullable"
+    }
+
+    fn nonnull(&self) -> &'static str {
+        " _Nonnull"
+    }
 }
 
 /// Template for generating the `.modulemap` file that exposes the low-level C FFI.
@@ -367,6 +509,15 @@ impl<'a> SwiftWrapper<'a> {
             .filter_map(|ct| ct.initialization_fn())
             .collect()
     }
+
+    fn field_name(&self, nm: &str) -> String {
+        quote_general_keyword(self.config.field_naming().apply(nm))
+    }
+
+    // The docstring of a function, method or constructor, with notes on its sensitive arguments
+    fn docstring_with_notes(&self, callable: &impl Callable) -> Option<String> {
+        callable.docstring_with_notes(&|nm| self.field_name(nm).trim_matches('`').to_string())
+    }
 }
 
 #[derive(Clone)]
@@ -393,13 +544,22 @@ impl SwiftCodeOracle {
             Type::Float32 => Box::new(primitives::Float32CodeType),
             Type::Float64 => Box::new(primitives::Float64CodeType),
             Type::Boolean => Box::new(primitives::BooleanCodeType),
+            Type::Char => Box::new(primitives::UnicodeScalarCodeType),
             Type::String => Box::new(primitives::StringCodeType),
             Type::Bytes => Box::new(primitives::BytesCodeType),
 
             Type::Timestamp => Box::new(miscellany::TimestampCodeType),
             Type::Duration => Box::new(miscellany::DurationCodeType),
+            Type::Url => Box::new(miscellany::UrlCodeType),
+            Type::Uuid => Box::new(miscellany::UuidCodeType),
+            Type::Decimal => Box::new(miscellany::DecimalCodeType),
+            Type::Json => Box::new(miscellany::JsonCodeType),
+            Type::Path => Box::new(miscellany::PathCodeType),
+            Type::IpAddr => Box::new(miscellany::IpAddrCodeType),
+            Type::SocketAddr => Box::new(miscellany::SocketAddrCodeType),
 
             Type::Enum { name, .. } => Box::new(enum_::EnumCodeType::new(name)),
+            Type::Flags { name, .. } => Box::new(flags::FlagsCodeType::new(name)),
             Type::Object { name, .. } => Box::new(object::ObjectCodeType::new(name)),
             Type::Record { name, .. } => Box::new(record::RecordCodeType::new(name)),
             Type::CallbackInterface { name, .. } => {
@@ -416,6 +576,11 @@ impl SwiftCodeOracle {
                 key_type,
                 value_type,
             } => Box::new(compounds::MapCodeType::new(*key_type, *value_type)),
+            Type::Compressed { inner_type } => {
+                Box::new(compounds::CompressedCodeType::new(*inner_type))
+            }
+            Type::Shared { inner_type } => Box::new(compounds::SharedCodeType::new(*inner_type)),
+            Type::Weak { inner_type } => Box::new(compounds::WeakCodeType::new(*inner_type)),
             Type::External { name, .. } => Box::new(external::ExternalCodeType::new(name)),
             Type::Custom { name, .. } => Box::new(custom::CustomCodeType::new(name)),
         }
@@ -467,6 +632,7 @@ impl SwiftCodeOracle {
                 format!("UniFfiFutureCallback{}", self.ffi_type_label(return_type))
             }
             FfiType::FutureCallbackData => "UnsafeMutableRawPointer".into(),
+            FfiType::Struct(name) => format!("UniffiReprC{name}"),
         }
     }
 
@@ -489,6 +655,7 @@ impl SwiftCodeOracle {
 pub mod filters {
     use super::*;
     pub use crate::backend::filters::*;
+    pub use crate::bindings::c::gen_c::filters::c_identifier;
 
     fn oracle() -> &'static SwiftCodeOracle {
         &SwiftCodeOracle
@@ -563,6 +730,7 @@ pub mod filters {
                 SwiftCodeOracle.ffi_type_label_raw(return_type)
             ),
             FfiType::FutureCallbackData => "void* _Nonnull".into(),
+            FfiType::Struct(name) => format!("UniffiReprC{name}"),
         })
     }
 
@@ -628,4 +796,18 @@ pub mod filters {
             }
         ))
     }
+
+    /// Render a docstring as a documentation comment, indented by `spaces`
+    pub fn docstring(docstring: &str, spaces: i32) -> Result<String, askama::Error> {
+        let indent = " ".repeat(spaces as usize);
+        let body = docstring
+            .replace("*/", "*\\/")
+            .lines()
+            .map(|line| match line {
+                "" => format!("{indent} *\n"),
+                line => format!("{indent} * {line}\n"),
+            })
+            .collect::<String>();
+        Ok(format!("{indent}/**\n{body}{indent} */"))
+    }
 }
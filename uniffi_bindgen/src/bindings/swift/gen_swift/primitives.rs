@@ -88,3 +88,17 @@ impl_code_type_for_primitive!(UInt32CodeType, "UInt32");
 impl_code_type_for_primitive!(UInt64CodeType, "UInt64");
 impl_code_type_for_primitive!(Float32CodeType, "Float");
 impl_code_type_for_primitive!(Float64CodeType, "Double");
+
+// `Unicode.Scalar` isn't a valid identifier, so it needs a canonical name of its own
+#[derive(Debug)]
+pub struct UnicodeScalarCodeType;
+
+impl CodeType for UnicodeScalarCodeType {
+    fn type_label(&self) -> String {
+        "Unicode.Scalar".into()
+    }
+
+    fn canonical_name(&self) -> String {
+        "UnicodeScalar".into()
+    }
+}
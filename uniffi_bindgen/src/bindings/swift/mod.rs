@@ -12,10 +12,10 @@
 //! we generate:
 //!
 //!   * A C header file `exampleFFI.h` declaring the low-level structs and functions for calling
-//!    into Rust, along with a corresponding `exampleFFI.modulemap` to expose them to Swift.
+//!     into Rust, along with a corresponding `exampleFFI.modulemap` to expose them to Swift.
 //!
 //!   * A Swift source file `example.swift` that imports the `exampleFFI` module and wraps it
-//!    to provide the higher-level Swift API.
+//!     to provide the higher-level Swift API.
 //!
 //! Most of the concepts in a [`ComponentInterface`] have an obvious counterpart in Swift,
 //! with the details documented in inline comments where appropriate.
@@ -40,17 +40,17 @@ pub use gen_swift::{generate_bindings, Config};
 mod test;
 
 use super::super::interface::ComponentInterface;
-pub use test::{run_script, run_test};
+pub use test::{run_script, run_test, run_xctest};
 
 /// The Swift bindings generated from a [`ComponentInterface`].
 ///
 pub struct Bindings {
     /// The contents of the generated `.swift` file, as a string.
-    library: String,
+    pub(crate) library: String,
     /// The contents of the generated `.h` file, as a string.
-    header: String,
+    pub(crate) header: String,
     /// The contents of the generated `.modulemap` file, as a string.
-    modulemap: Option<String>,
+    pub(crate) modulemap: Option<String>,
 }
 
 /// Write UniFFI component bindings for Swift as files on disk.
@@ -3,9 +3,10 @@
 // Callback handlers for an async calls.  These are invoked by Rust when the future is ready.  They
 // lift the return value or error and resume the suspended function.
 {%- for result_type in ci.iter_async_result_types() %}
+{%- let callback_param = ci.future_callback_param(result_type) %}
 fileprivate func {{ result_type|future_callback }}(
     rawContinutation: UnsafeRawPointer,
-    returnValue: {{ result_type.future_callback_param().borrow()|ffi_type_name }},
+    returnValue: {{ callback_param|ffi_type_name }},
     callStatus: RustCallStatus) {
 
     let continuation = rawContinutation.bindMemory(
@@ -0,0 +1,44 @@
+{%- if self.include_once_check("CallbackConformanceRuntime.swift") %}{%- include "CallbackConformanceRuntime.swift" %}{%- endif %}
+
+/// Checks that `implementation` behaves the way Rust expects implementations of `{{ type_name }}`
+/// to behave, see `uniffiCheckCallbackConformance()`.
+///
+/// Each `check` method calls the corresponding method of `implementation` with the given arguments
+/// and throws `UniffiConformanceFailure` if a check fails.
+public final class {{ type_name }}Conformance {
+    public let implementation: {{ type_name }}
+    public let threads: Int
+    public let iterations: Int
+    public let timeout: TimeInterval
+
+    public init(_ implementation: {{ type_name }}, threads: Int = 4, iterations: Int = 25, timeout: TimeInterval = 10) {
+        self.implementation = implementation
+        self.threads = threads
+        self.iterations = iterations
+        self.timeout = timeout
+    }
+
+    {% for meth in cbi.methods() -%}
+    public func {{ format!("check_{}", meth.name())|fn_name }}({% call swift::arg_list_protocol(meth) %}) throws {
+        try uniffiCheckCallbackConformance(
+            "{{ type_name }}.{{ meth.name()|fn_name }}",
+            threads: self.threads,
+            iterations: self.iterations,
+            timeout: self.timeout,
+            {%- match meth.throws_type() %}
+            {%- when Some(error_type) %}
+            isDeclaredError: { $0 is {{ error_type|type_name }} }
+            {%- when None %}
+            isDeclaredError: { _ in false }
+            {%- endmatch %}
+        ) {
+            {% if meth.return_type().is_some() %}_ = {% endif %}{% if meth.throws() %}try {% endif %}self.implementation.{{ meth.name()|fn_name }}(
+                {%- for arg in meth.arguments() %}
+                {%- if !config.omit_argument_labels() %}{{ self.field_name(arg.name()) }}: {% endif %}{{ self.field_name(arg.name()) }}
+                {%- if !loop.last %}, {% endif %}
+                {%- endfor -%}
+            )
+        }
+    }
+    {% endfor %}
+}
@@ -0,0 +1,72 @@
+/// Thrown by the `{Name}Conformance` checks when a callback interface implementation doesn't
+/// behave the way the Rust side expects.
+public struct UniffiConformanceFailure: Error, CustomStringConvertible {
+    public let description: String
+}
+
+/// Calls `call` the way Rust calls callback interface methods:
+///
+///   - Errors: any error other than the method's declared error type is seen by Rust as an
+///     unexpected error, so `isDeclaredError` must accept everything that `call` throws.
+///   - Ordering: the method is called `iterations` times in a row from the current thread and
+///     each call must return before the next one is made.
+///   - Threading: Rust may call the method from any thread, so it's then called `iterations`
+///     times from each of `threads` threads at once, all of which must finish within `timeout`.
+///
+/// This is only generated when `callback_conformance_checks` is enabled in `uniffi.toml`.
+fileprivate func uniffiCheckCallbackConformance(
+    _ name: String,
+    threads: Int,
+    iterations: Int,
+    timeout: TimeInterval,
+    isDeclaredError: @escaping (Error) -> Bool,
+    _ call: @escaping () throws -> Void
+) throws {
+    func checkedCall() throws {
+        do {
+            try call()
+        } catch {
+            if !isDeclaredError(error) {
+                throw UniffiConformanceFailure(description: "\(name) threw \(error), which Rust sees as an unexpected error")
+            }
+        }
+    }
+
+    for _ in 0..<iterations {
+        try checkedCall()
+    }
+
+    let lock = NSLock()
+    var failure: Error? = nil
+    let group = DispatchGroup()
+    let start = DispatchSemaphore(value: 0)
+    for _ in 0..<threads {
+        group.enter()
+        Thread.detachNewThread {
+            defer { group.leave() }
+            start.wait()
+            do {
+                for _ in 0..<iterations {
+                    try checkedCall()
+                }
+            } catch {
+                lock.lock()
+                if failure == nil {
+                    failure = error
+                }
+                lock.unlock()
+            }
+        }
+    }
+    for _ in 0..<threads {
+        start.signal()
+    }
+    if group.wait(timeout: .now() + timeout) == .timedOut {
+        throw UniffiConformanceFailure(description: "\(name) didn't return within \(timeout)s when called from \(threads) threads")
+    }
+    lock.lock()
+    defer { lock.unlock() }
+    if let failure = failure {
+        throw failure
+    }
+}
@@ -7,33 +7,48 @@ fileprivate extension NSLock {
 }
 
 fileprivate typealias UniFFICallbackHandle = UInt64
+// The number of shards in each handle map, see below
+fileprivate let uniffiHandleMapShards: UniFFICallbackHandle = 16
+
+// Maps handles to callback objects.
+//
+// Every call to `insert()` returns a fresh handle, even if the object was inserted before: each
+// handle is owned by exactly one Rust-side reference, which removes it when it's dropped.  Handles
+// come from 64-bit counters, so they're never reused.  This means that using a handle after it's
+// been removed fails the lookup, rather than returning some other object.
+//
+// Swift dictionaries aren't thread-safe, so the map is split into shards, each with its own lock
+// and counter.  The low bits of a handle are the index of its shard, and `insert()` picks the
+// shard from the current thread, so threads that lower or call callbacks at the same time rarely
+// wait for each other.
 fileprivate class UniFFICallbackHandleMap<T> {
-    private var leftMap: [UniFFICallbackHandle: T] = [:]
-    private var counter: [UniFFICallbackHandle: UInt64] = [:]
-    private var rightMap: [ObjectIdentifier: UniFFICallbackHandle] = [:]
+    private final class Shard {
+        let lock = NSLock()
+        var map: [UniFFICallbackHandle: T] = [:]
+        var currentHandle: UniFFICallbackHandle = 0
+    }
 
-    private let lock = NSLock()
-    private var currentHandle: UniFFICallbackHandle = 0
-    private let stride: UniFFICallbackHandle = 1
+    private let shards: [Shard] = (0..<uniffiHandleMapShards).map { _ in Shard() }
+
+    private func shard(_ handle: UniFFICallbackHandle) -> Shard {
+        shards[Int(handle % uniffiHandleMapShards)]
+    }
 
     func insert(obj: T) -> UniFFICallbackHandle {
-        lock.withLock {
-            let id = ObjectIdentifier(obj as AnyObject)
-            let handle = rightMap[id] ?? {
-                currentHandle += stride
-                let handle = currentHandle
-                leftMap[handle] = obj
-                rightMap[id] = handle
-                return handle
-            }()
-            counter[handle] = (counter[handle] ?? 0) + 1
+        let index = UniFFICallbackHandle(UInt(bitPattern: ObjectIdentifier(Thread.current).hashValue)) % uniffiHandleMapShards
+        let shard = shards[Int(index)]
+        return shard.lock.withLock {
+            shard.currentHandle += 1
+            let handle = shard.currentHandle * uniffiHandleMapShards + index
+            shard.map[handle] = obj
             return handle
         }
     }
 
     func get(handle: UniFFICallbackHandle) -> T? {
-        lock.withLock {
-            leftMap[handle]
+        let shard = shard(handle)
+        return shard.lock.withLock {
+            shard.map[handle]
         }
     }
 
@@ -43,22 +58,47 @@ fileprivate class UniFFICallbackHandleMap<T> {
 
     @discardableResult
     func remove(handle: UniFFICallbackHandle) -> T? {
-        lock.withLock {
-            defer { counter[handle] = (counter[handle] ?? 1) - 1 }
-            guard counter[handle] == 1 else { return leftMap[handle] }
-            let obj = leftMap.removeValue(forKey: handle)
-            if let obj = obj {
-                rightMap.removeValue(forKey: ObjectIdentifier(obj as AnyObject))
+        let shard = shard(handle)
+        return shard.lock.withLock {
+            shard.map.removeValue(forKey: handle)
+        }
+    }
+
+    // The handles of an object that was inserted, see `uniffiDetach()`
+    func handles(of obj: AnyObject) -> [UniFFICallbackHandle] {
+        shards.flatMap { shard in
+            shard.lock.withLock {
+                shard.map.filter { ($0.value as AnyObject) === obj }.map { $0.key }
             }
-            return obj
         }
     }
 }
 
+// Closures aren't objects, so the handle map stores the closures of closure arguments in a box
+fileprivate final class UniffiClosureBox<T> {
+    let closure: T
+
+    init(_ closure: T) {
+        self.closure = closure
+    }
+}
+
 // Magic number for the Rust proxy to call using the same mechanism as every other method,
 // to free the callback once it's dropped by Rust.
 private let IDX_CALLBACK_FREE: Int32 = 0
+// Reads the value of a constant, see `uniffi_core/src/ffi/foreigncallbacks.rs`
+private let IDX_CALLBACK_CONSTANT: Int32 = Int32.max
 // Callback return codes
 private let UNIFFI_CALLBACK_SUCCESS: Int32 = 0
 private let UNIFFI_CALLBACK_ERROR: Int32 = 1
 private let UNIFFI_CALLBACK_UNEXPECTED_ERROR: Int32 = 2
+
+// Serializes an unexpected error for Rust: the error's type name, then its description.  Errors
+// in the bindings' own code use an empty type name.
+// See `CallbackError` in `uniffi_core/src/ffi/foreigncallbacks.rs`
+fileprivate func uniffiLowerUnexpectedError(className: String, message: String) -> RustBuffer {
+    var writer = createWriter()
+    {{ Type::String.borrow()|ffi_converter_name }}.write(className, into: &writer)
+    {{ Type::String.borrow()|ffi_converter_name }}.write(message, into: &writer)
+    return RustBuffer(bytes: writer)
+}
@@ -3,16 +3,58 @@
 {%- if self.include_once_check("CallbackInterfaceRuntime.swift") %}{%- include "CallbackInterfaceRuntime.swift" %}{%- endif %}
 
 // Declaration and FfiConverters for {{ type_name }} Callback Interface
-
+{%- if cbi.is_closure() %}
+{%- let meth = cbi.methods()[0] %}
+public typealias {{ type_name }} = (
+    {%- for arg in meth.arguments() %}{{ arg|type_name }}{% if !loop.last %}, {% endif %}{% endfor -%}
+) {% call swift::throws(meth) %} -> {% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_name }}{% else %}Void{% endmatch %}
+{%- else %}
+{% call swift::docstring(cbi, 0) %}
 public protocol {{ type_name }} : AnyObject {
-    {% for meth in cbi.methods() -%}
+    {%- for meth in cbi.methods() %}
+    {%- call swift::docstring(meth, 4) %}
     func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) -%}
     {%- match meth.return_type() -%}
     {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
     {%- else -%}
     {%- endmatch %}
-    {% endfor %}
+    {%- endfor %}
+    {%- for c in cbi.constants() %}
+    static var {{ c.name()|var_name }}: {{ c|type_name }} { get }
+    {%- endfor %}
+}
+{%- if !cbi.constants().is_empty() %}
+
+// Implementations can override the constants, or use the values declared in Rust.
+public extension {{ type_name }} {
+    {%- for c in cbi.constants() %}
+    static var {{ c.name()|var_name }}: {{ c|type_name }} { {{ c.value()|literal_swift(c) }} }
+    {%- endfor %}
+}
+{%- endif %}
+{%- if cbi.has_default_methods() %}
+
+// Implementations can override these methods, or call the default implementations in Rust.
+public extension {{ type_name }} {
+    {%- for meth in cbi.methods() %}
+    {%- if meth.has_default() %}
+    {%- match meth.return_type() %}
+    {%- when Some with (return_type) %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
+        return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix(format!("{}.lower(self)", ffi_converter_name), meth) %}
+        )
+    }
+    {%- when None %}
+    func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::throws(meth) %} {
+        {% call swift::to_ffi_call_with_prefix(format!("{}.lower(self)", ffi_converter_name), meth) %}
+    }
+    {%- endmatch %}
+    {%- endif %}
+    {%- endfor %}
 }
+{%- endif %}
+{%- endif %}
 
 // The ForeignCallback that is passed to Rust.
 fileprivate let {{ foreign_callback }} : ForeignCallback =
@@ -28,9 +70,9 @@ fileprivate let {{ foreign_callback }} : ForeignCallback =
         {%- match meth.return_type() %}
         {%- when Some(return_type) %}
         func makeCall() throws -> Int32 {
-            let result = {% if meth.throws() %} try{% endif %} swiftCallbackInterface.{{ meth.name()|fn_name }}(
+            let result = {% if meth.throws() %} try{% endif %} swiftCallbackInterface{% if !cbi.is_closure() %}.{{ meth.name()|fn_name }}{% endif %}(
                     {% for arg in meth.arguments() -%}
-                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
+                    {% if !config.omit_argument_labels() && !cbi.is_closure() %}{{ self.field_name(arg.name()) }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
                     {%- if !loop.last %}, {% endif %}
                     {% endfor -%}
                 )
@@ -41,9 +83,9 @@ fileprivate let {{ foreign_callback }} : ForeignCallback =
         }
         {%- when None %}
         func makeCall() throws -> Int32 {
-            try swiftCallbackInterface.{{ meth.name()|fn_name }}(
+            try swiftCallbackInterface{% if !cbi.is_closure() %}.{{ meth.name()|fn_name }}{% endif %}(
                     {% for arg in meth.arguments() -%}
-                    {% if !config.omit_argument_labels() %}{{ arg.name()|var_name }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
+                    {% if !config.omit_argument_labels() && !cbi.is_closure() %}{{ self.field_name(arg.name()) }}: {% endif %} try {{ arg|read_fn }}(from: &reader)
                     {%- if !loop.last %}, {% endif %}
                     {% endfor -%}
                 )
@@ -79,16 +121,46 @@ fileprivate let {{ foreign_callback }} : ForeignCallback =
             do {
                 cb = try {{ ffi_converter_name }}.lift(handle)
             } catch {
-                out_buf.pointee = {{ Type::String.borrow()|lower_fn }}("{{ cbi.name() }}: Invalid handle")
+                out_buf.pointee = uniffiLowerUnexpectedError(className: "", message: "{{ cbi.name() }}: Invalid handle")
                 return UNIFFI_CALLBACK_UNEXPECTED_ERROR
             }
             do {
+                {%- if cbi.main_thread() %}
+                return try uniffiRunOnMainThread { try {{ method_name }}(cb, argsData, argsLen, out_buf) }
+                {%- else %}
                 return try {{ method_name }}(cb, argsData, argsLen, out_buf)
+                {%- endif %}
             } catch let error {
-                out_buf.pointee = {{ Type::String.borrow()|lower_fn }}(String(describing: error))
+                out_buf.pointee = uniffiLowerUnexpectedError(
+                    className: String(reflecting: type(of: error)),
+                    message: String(describing: error)
+                )
                 return UNIFFI_CALLBACK_UNEXPECTED_ERROR
             }
         {% endfor %}
+        {%- if !cbi.constants().is_empty() %}
+        case IDX_CALLBACK_CONSTANT:
+            let cb: {{ cbi|type_name }}
+            do {
+                cb = try {{ ffi_converter_name }}.lift(handle)
+            } catch {
+                out_buf.pointee = uniffiLowerUnexpectedError(className: "", message: "{{ cbi.name() }}: Invalid handle")
+                return UNIFFI_CALLBACK_UNEXPECTED_ERROR
+            }
+            // Write the value of the constant whose index is in argsData to out_buf
+            var reader = createReader(data: Data(bytes: argsData, count: Int(argsLen)))
+            var writer = [UInt8]()
+            switch try? readInt(&reader) as UInt32 {
+            {%- for c in cbi.constants() %}
+            case {{ loop.index0 }}?:
+                {{ c|write_fn }}(type(of: cb).{{ c.name()|var_name }}, into: &writer)
+            {%- endfor %}
+            default:
+                return UNIFFI_CALLBACK_UNEXPECTED_ERROR
+            }
+            out_buf.pointee = RustBuffer(bytes: writer)
+            return UNIFFI_CALLBACK_SUCCESS
+        {%- endif %}
         // This should never happen, because an out of bounds method index won't
         // ever be used. Once we can catch errors, we should return an InternalError.
         // https://github.com/mozilla/uniffi-rs/issues/351
@@ -115,8 +187,25 @@ fileprivate struct {{ ffi_converter_name }} {
     static func drop(handle: UniFFICallbackHandle) {
         handleMap.remove(handle: handle)
     }
+    {%- if !cbi.is_closure() %}
+
+    // Detaches an object, see `uniffiDetach()`.  Rust is told first, so that it doesn't call the
+    // object once it's been removed from the handle map.
+    static func detach(_ obj: AnyObject) {
+        for handle in handleMap.handles(of: obj) {
+            try! rustCall { (err: UnsafeMutablePointer<RustCallStatus>) in
+                {{ cbi.ffi_detach_callback().name() }}(handle, err)
+            }
+            handleMap.remove(handle: handle)
+        }
+    }
+    {%- endif %}
 
+    {%- if cbi.is_closure() %}
+    private static var handleMap = UniFFICallbackHandleMap<UniffiClosureBox<{{ type_name }}>>()
+    {%- else %}
     private static var handleMap = UniFFICallbackHandleMap<{{ type_name }}>()
+    {%- endif %}
 }
 
 extension {{ ffi_converter_name }} : FfiConverter {
@@ -129,7 +218,11 @@ extension {{ ffi_converter_name }} : FfiConverter {
         guard let callback = handleMap.get(handle: handle) else {
             throw UniffiInternalError.unexpectedStaleHandle
         }
+        {%- if cbi.is_closure() %}
+        return callback.closure
+        {%- else %}
         return callback
+        {%- endif %}
     }
 
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
@@ -140,7 +233,11 @@ extension {{ ffi_converter_name }} : FfiConverter {
 
     public static func lower(_ v: SwiftType) -> UniFFICallbackHandle {
         ensureCallbackinitialized();
+        {%- if cbi.is_closure() %}
+        return handleMap.insert(obj: UniffiClosureBox(v))
+        {%- else %}
         return handleMap.insert(obj: v)
+        {%- endif %}
     }
 
     public static func write(_ v: SwiftType, into buf: inout [UInt8]) {
@@ -148,3 +245,5 @@ extension {{ ffi_converter_name }} : FfiConverter {
         writeInt(&buf, lower(v))
     }
 }
+
+{% if config.callback_conformance_checks() && !cbi.is_closure() %}{% include "CallbackConformance.swift" %}{% endif %}
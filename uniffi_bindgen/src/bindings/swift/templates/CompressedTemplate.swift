@@ -0,0 +1,27 @@
+{%- if self.include_once_check("CompressionRuntime.swift") %}{%- include "CompressionRuntime.swift" %}{%- endif %}
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    // Values sent to Rust are never compressed
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, UInt8(0))
+        {{ inner_type|write_fn }}(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as UInt8 {
+        case 0: return try {{ inner_type|read_fn }}(from: &buf)
+        case 1:
+            let uncompressedSize = Int(try readInt(&buf) as UInt32)
+            let compressedSize = Int(try readInt(&buf) as UInt32)
+            let compressed = try readBytes(&buf, count: compressedSize)
+            var reader = createReader(data: try uniffiInflate(compressed, uncompressedSize: uncompressedSize))
+            let value = try {{ inner_type|read_fn }}(from: &reader)
+            if hasRemaining(reader) {
+                throw UniffiInternalError.incompleteData
+            }
+            return value
+        default: throw UniffiInternalError.invalidCompressedData
+        }
+    }
+}
@@ -0,0 +1,23 @@
+#if canImport(Compression)
+import Compression
+#endif
+
+// Compressed values are sent by Rust as raw deflate data, see `uniffi::Compressed`.  The
+// `Compression` framework calls that `COMPRESSION_ZLIB`, it's only available on Apple platforms.
+fileprivate func uniffiInflate(_ compressed: [UInt8], uncompressedSize: Int) throws -> Data {
+#if canImport(Compression)
+    var uncompressed = [UInt8](repeating: 0, count: uncompressedSize)
+    // Decoding exactly `uncompressedSize` bytes doesn't prove that was the whole value, so leave
+    // room for one more.
+    uncompressed.append(0)
+    let size = compression_decode_buffer(
+        &uncompressed, uncompressed.count, compressed, compressed.count, nil, COMPRESSION_ZLIB
+    )
+    guard size == uncompressedSize else {
+        throw UniffiInternalError.invalidCompressedData
+    }
+    return Data(uncompressed[0..<size])
+#else
+    throw UniffiInternalError.invalidCompressedData
+#endif
+}
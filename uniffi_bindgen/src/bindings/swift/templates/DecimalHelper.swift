@@ -0,0 +1,25 @@
+fileprivate struct FfiConverterDecimal: FfiConverter {
+    typealias SwiftType = Decimal
+    typealias FfiType = RustBuffer
+
+    // Decimals are passed as strings in plain notation, Rust validates them when it lifts them.
+    // `Decimal.description` doesn't depend on the current locale, so parse the strings that
+    // Rust sends with a fixed locale too.
+    private static let locale = Locale(identifier: "en_US_POSIX")
+
+    public static func lift(_ value: RustBuffer) throws -> Decimal {
+        return Decimal(string: try FfiConverterString.lift(value), locale: locale)!
+    }
+
+    public static func lower(_ value: Decimal) -> RustBuffer {
+        return FfiConverterString.lower(value.description)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Decimal {
+        return Decimal(string: try FfiConverterString.read(from: &buf), locale: locale)!
+    }
+
+    public static func write(_ value: Decimal, into buf: inout [UInt8]) {
+        FfiConverterString.write(value.description, into: &buf)
+    }
+}
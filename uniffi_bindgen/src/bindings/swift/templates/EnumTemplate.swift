@@ -1,26 +1,50 @@
 // Note that we don't yet support `indirect` for enums.
 // See https://github.com/mozilla/uniffi-rs/issues/396 for further discussion.
-public enum {{ type_name }} {
+{%- match config.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
+
+{{ type_override }}
+{%- when None %}
+{%- call swift::docstring(e, 0) %}
+{#- Explicit discriminants from Rust are used as the raw values, except for non-exhaustive enums
+    since their `unknown` case has associated values #}
+{%- let has_raw_values = e.is_flat() && e.has_discriminants() && !e.is_non_exhaustive() %}
+public enum {{ type_name }}{% if has_raw_values %}: Int64{% endif %} {
     {% for variant in e.variants() %}
-    case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% endif -%}
+    case {{ variant.name()|enum_variant_swift_quoted }}{% if variant.fields().len() > 0 %}({% call swift::field_list_decl(variant) %}){% else if has_raw_values %}{% match variant.discr() %}{% when Some with (discr) %} = {{ discr }}{% when None %}{% endmatch %}{% endif -%}
     {% endfor %}
+    {%- if e.is_non_exhaustive() %}
+    /// A variant that was added to the Rust enum after these bindings were generated.  It keeps
+    /// the raw variant index and fields, so that it's passed back to Rust unchanged.
+    case unknown(index: Int32, fields: [UInt8])
+    {%- endif %}
 }
+{%- endmatch %}
 
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     typealias SwiftType = {{ type_name }}
 
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
         let variant: Int32 = try readInt(&buf)
+        {%- if e.is_non_exhaustive() %}
+        // The size of the fields, to skip variants that were added after these bindings were generated
+        let size: Int32 = try readInt(&buf)
+        {%- endif %}
         switch variant {
         {% for variant in e.variants() %}
         case {{ loop.index }}: return .{{ variant.name()|enum_variant_swift }}{% if variant.has_fields() %}(
             {%- for field in variant.fields() %}
-            {{ field.name()|arg_name }}: try {{ field|read_fn }}(from: &buf)
+            {{ self.field_label(field.name()) }}: try {{ field|read_fn }}(from: &buf)
             {%- if !loop.last %}, {% endif %}
             {%- endfor %}
         ){%- endif %}
         {% endfor %}
+        {%- if e.is_non_exhaustive() %}
+        default:
+            return .unknown(index: variant, fields: try readBytes(&buf, count: Int(size)))
+        {%- else %}
         default: throw UniffiInternalError.unexpectedEnumCase
+        {%- endif %}
         }
     }
 
@@ -28,16 +52,22 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         switch value {
         {% for variant in e.variants() %}
         {% if variant.has_fields() %}
-        case let .{{ variant.name()|enum_variant_swift }}({% for field in variant.fields() %}{{ field.name()|var_name }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
+        case let .{{ variant.name()|enum_variant_swift }}({% for field in variant.fields() %}{{ self.field_name(field.name()) }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
             writeInt(&buf, Int32({{ loop.index }}))
             {% for field in variant.fields() -%}
-            {{ field|write_fn }}({{ field.name()|var_name }}, into: &buf)
+            {{ field|write_fn }}({{ self.field_name(field.name()) }}, into: &buf)
             {% endfor -%}
         {% else %}
         case .{{ variant.name()|enum_variant_swift }}:
             writeInt(&buf, Int32({{ loop.index }}))
         {% endif %}
         {%- endfor %}
+        {%- if e.is_non_exhaustive() %}
+        case let .unknown(index, fields):
+            // Rust doesn't expect the size of the fields, it reads them like for the other variants
+            writeInt(&buf, index)
+            writeBytes(&buf, fields)
+        {%- endif %}
         }
     }
 }
@@ -54,6 +84,11 @@ public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuff
     return {{ ffi_converter_name }}.lower(value)
 }
 
-{% if !contains_object_references %}
+{% if is_hashable %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
+
+{%- if is_codable %}
+
+extension {{ type_name }}: Codable {}
+{% endif %}
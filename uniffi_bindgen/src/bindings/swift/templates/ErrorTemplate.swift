@@ -1,9 +1,14 @@
+{%- match e.docstring() %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(0) }}
+{% when None %}
+{%- endmatch -%}
 public enum {{ type_name }} {
 
     {% if e.is_flat() %}
     {% for variant in e.variants() %}
-    // Simple error enums only carry a message
-    case {{ variant.name()|class_name }}(message: String)
+    // Simple error enums only carry a message, and the fields marked with `#[uniffi(error_field)]`
+    case {{ variant.name()|class_name }}(message: String{% if variant.has_fields() %}, {% call swift::field_list_decl(variant) %}{% endif %})
     {% endfor %}
 
     {%- else %}
@@ -31,6 +36,9 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         {% for variant in e.variants() %}
         case {{ loop.index }}: return .{{ variant.name()|class_name }}(
             message: try {{ Type::String.borrow()|read_fn }}(from: &buf)
+            {%- for field in variant.fields() %},
+            {{ self.field_name(field.name()) }}: try {{ field|read_fn }}(from: &buf)
+            {%- endfor %}
         )
         {% endfor %}
 
@@ -39,7 +47,7 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         {% for variant in e.variants() %}
         case {{ loop.index }}: return .{{ variant.name()|class_name }}{% if variant.has_fields() -%}(
             {% for field in variant.fields() -%}
-            {{ field.name()|var_name }}: try {{ field|read_fn }}(from: &buf)
+            {{ self.field_name(field.name()) }}: try {{ field|read_fn }}(from: &buf)
             {%- if !loop.last %}, {% endif %}
             {% endfor -%}
         ){% endif -%}
@@ -56,7 +64,7 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
         {% if e.is_flat() %}
 
         {% for variant in e.variants() %}
-        case .{{ variant.name()|class_name }}(_ /* message is ignored*/):
+        case .{{ variant.name()|class_name }}(_ /* message is ignored*/{% for field in variant.fields() %}, _{% endfor %}):
             writeInt(&buf, Int32({{ loop.index }}))
         {%- endfor %}
 
@@ -64,10 +72,10 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
 
         {% for variant in e.variants() %}
         {% if variant.has_fields() %}
-        case let .{{ variant.name()|class_name }}({% for field in variant.fields() %}{{ field.name()|var_name }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
+        case let .{{ variant.name()|class_name }}({% for field in variant.fields() %}{{ self.field_name(field.name()) }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
             writeInt(&buf, Int32({{ loop.index }}))
             {% for field in variant.fields() -%}
-            {{ field|write_fn }}({{ field.name()|var_name }}, into: &buf)
+            {{ field|write_fn }}({{ self.field_name(field.name()) }}, into: &buf)
             {% endfor -%}
         {% else %}
         case .{{ variant.name()|class_name }}:
@@ -80,7 +88,7 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
     }
 }
 
-{% if !contains_object_references %}
+{% if is_hashable %}
 extension {{ type_name }}: Equatable, Hashable {}
 {% endif %}
 extension {{ type_name }}: Error { }
@@ -0,0 +1,49 @@
+{%- call swift::docstring(flags, 0) %}
+public struct {{ type_name }}: OptionSet, Hashable {
+    public let rawValue: UInt32
+
+    public init(rawValue: UInt32) {
+        self.rawValue = rawValue
+    }
+    {% for flag in flags.flags() %}
+    public static let {{ flag.name()|var_name }} = {{ type_name }}(rawValue: {{ flag.value() }})
+    {%- endfor %}
+}
+
+public struct {{ ffi_converter_name }}: FfiConverter {
+    typealias FfiType = UInt32
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ value: UInt32) throws -> {{ type_name }} {
+        return {{ type_name }}(rawValue: value)
+    }
+
+    public static func lower(_ value: {{ type_name }}) -> UInt32 {
+        return value.rawValue
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
+        return try lift(readInt(&buf))
+    }
+
+    public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
+        writeInt(&buf, lower(value))
+    }
+}
+
+{#
+We always write these public functions just in case the flags are used as
+an external type by another crate.
+#}
+public func {{ ffi_converter_name }}_lift(_ value: UInt32) throws -> {{ type_name }} {
+    return try {{ ffi_converter_name }}.lift(value)
+}
+
+public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> UInt32 {
+    return {{ ffi_converter_name }}.lower(value)
+}
+
+{%- if is_codable %}
+
+extension {{ type_name }}: Codable {}
+{% endif %}
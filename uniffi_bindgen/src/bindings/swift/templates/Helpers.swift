@@ -9,7 +9,13 @@ fileprivate enum UniffiInternalError: LocalizedError {
     case unexpectedRustCallStatusCode
     case unexpectedRustCallError
     case unexpectedStaleHandle
+    case invalidCompressedData
+    case invalidSharedTransport
+    case invalidUnicodeScalar
+    case invalidUrl
     case rustPanic(_ message: String)
+    // The backtrace is only captured when `RUST_BACKTRACE` is enabled
+    case rustPanicWithBacktrace(_ message: String, backtrace: String)
 
     public var errorDescription: String? {
         switch self {
@@ -21,7 +27,12 @@ fileprivate enum UniffiInternalError: LocalizedError {
         case .unexpectedRustCallStatusCode: return "Unexpected RustCallStatus code"
         case .unexpectedRustCallError: return "CALL_ERROR but no errorClass specified"
         case .unexpectedStaleHandle: return "The object in the handle map has been dropped already"
+        case .invalidCompressedData: return "Compressed value has an unknown algorithm or can't be decompressed"
+        case .invalidSharedTransport: return "Shared value has an unknown transport"
+        case .invalidUnicodeScalar: return "Raw char value isn't a Unicode scalar value"
+        case .invalidUrl: return "Rust URL can't be parsed by Foundation's URL"
         case let .rustPanic(message): return message
+        case let .rustPanicWithBacktrace(message, _): return message
         }
     }
 }
@@ -29,6 +40,18 @@ fileprivate enum UniffiInternalError: LocalizedError {
 fileprivate let CALL_SUCCESS: Int8 = 0
 fileprivate let CALL_ERROR: Int8 = 1
 fileprivate let CALL_PANIC: Int8 = 2
+fileprivate let CALL_INVALID_ARGUMENT: Int8 = 3
+
+/// Thrown when Rust can't lift an argument, like a zero for a `NonZeroU32`
+///
+/// Only functions that throw can report it, for the others it's a fatal error.
+public struct UniffiInvalidArgumentError: LocalizedError {
+    public let message: String
+
+    public var errorDescription: String? {
+        return message
+    }
+}
 
 fileprivate extension RustCallStatus {
     init() {
@@ -64,6 +87,18 @@ private func makeRustCall<T>(
     return returnedVal
 }
 
+// The buffer of a panic has its message, then its backtrace, which is empty if none was captured
+private func uniffiLiftPanic(_ errorBuf: RustBuffer) throws -> UniffiInternalError {
+    defer { errorBuf.deallocate() }
+    var reader = createReader(data: Data(rustBuffer: errorBuf))
+    let message = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+    let backtrace = try {{ Type::String.borrow()|read_fn }}(from: &reader)
+    if backtrace.isEmpty {
+        return .rustPanic(message)
+    }
+    return .rustPanicWithBacktrace(message, backtrace: backtrace)
+}
+
 private func uniffiCheckCallStatus(
     callStatus: RustCallStatus,
     errorHandler: ((RustBuffer) throws -> Error)?
@@ -82,15 +117,18 @@ private func uniffiCheckCallStatus(
 
         case CALL_PANIC:
             // When the rust code sees a panic, it tries to construct a RustBuffer
-            // with the message.  But if that code panics, then it just sends back
-            // an empty buffer.
+            // with the message and the backtrace.  But if that code panics, then it
+            // just sends back an empty buffer.
             if callStatus.errorBuf.len > 0 {
-                throw UniffiInternalError.rustPanic(try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+                throw try uniffiLiftPanic(callStatus.errorBuf)
             } else {
                 callStatus.errorBuf.deallocate()
                 throw UniffiInternalError.rustPanic("Rust panic")
             }
 
+        case CALL_INVALID_ARGUMENT:
+            throw UniffiInvalidArgumentError(message: try {{ Type::String.borrow()|lift_fn }}(callStatus.errorBuf))
+
         default:
             throw UniffiInternalError.unexpectedRustCallStatusCode
     }
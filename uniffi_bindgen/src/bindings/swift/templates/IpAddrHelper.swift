@@ -0,0 +1,41 @@
+fileprivate struct FfiConverterIpAddr: FfiConverterRustBuffer {
+    typealias SwiftType = String
+
+    // IP addresses are strings like "192.0.2.1" or "2001:db8::1", and are passed as a version
+    // byte, 4 or 6, followed by the 4 or 16 bytes of the address.  Lowering a string that isn't
+    // an IP address is a programming error, and crashes.
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> String {
+        let version: UInt8 = try readInt(&buf)
+        let family: Int32
+        let bytes: [UInt8]
+        switch version {
+        case 4:
+            family = AF_INET
+            bytes = try readBytes(&buf, count: 4)
+        case 6:
+            family = AF_INET6
+            bytes = try readBytes(&buf, count: 16)
+        default:
+            throw UniffiInternalError.unexpectedEnumCase
+        }
+        var text = [CChar](repeating: 0, count: Int(INET6_ADDRSTRLEN))
+        guard inet_ntop(family, bytes, &text, socklen_t(text.count)) != nil else {
+            throw UniffiInternalError.unexpectedEnumCase
+        }
+        return String(cString: text)
+    }
+
+    public static func write(_ value: String, into buf: inout [UInt8]) {
+        var v4 = in_addr()
+        var v6 = in6_addr()
+        if inet_pton(AF_INET, value, &v4) == 1 {
+            writeInt(&buf, UInt8(4))
+            withUnsafeBytes(of: &v4) { writeBytes(&buf, $0) }
+        } else if inet_pton(AF_INET6, value, &v6) == 1 {
+            writeInt(&buf, UInt8(6))
+            withUnsafeBytes(of: &v6) { writeBytes(&buf, $0) }
+        } else {
+            fatalError("Invalid IP address: \(value)")
+        }
+    }
+}
@@ -0,0 +1,32 @@
+fileprivate struct FfiConverterJson: FfiConverter {
+    typealias SwiftType = Any
+    typealias FfiType = RustBuffer
+
+    // JSON values are passed as JSON text, Rust validates it when it lifts them.  Values are the
+    // objects that `JSONSerialization` works with: dictionaries, arrays, strings, numbers and
+    // `NSNull`.  Lowering anything else is a programming error, and crashes.
+    private static func parse(_ text: String) throws -> Any {
+        return try JSONSerialization.jsonObject(with: Data(text.utf8), options: [.fragmentsAllowed])
+    }
+
+    private static func serialize(_ value: Any) -> String {
+        let data = try! JSONSerialization.data(withJSONObject: value, options: [.fragmentsAllowed])
+        return String(decoding: data, as: UTF8.self)
+    }
+
+    public static func lift(_ value: RustBuffer) throws -> Any {
+        return try parse(try FfiConverterString.lift(value))
+    }
+
+    public static func lower(_ value: Any) -> RustBuffer {
+        return FfiConverterString.lower(serialize(value))
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> Any {
+        return try parse(try FfiConverterString.read(from: &buf))
+    }
+
+    public static func write(_ value: Any, into buf: inout [UInt8]) {
+        FfiConverterString.write(serialize(value), into: &buf)
+    }
+}
@@ -0,0 +1,10 @@
+/// Runs a call to a main-thread callback interface, which Rust may make from any thread, on the
+/// main queue.
+///
+/// This is only generated when a callback interface is declared with `main_thread`.
+fileprivate func uniffiRunOnMainThread<T>(_ call: () throws -> T) rethrows -> T {
+    if Thread.isMainThread {
+        return try call()
+    }
+    return try DispatchQueue.main.sync(execute: call)
+}
@@ -1,38 +1,114 @@
 {%- let obj = ci|get_object_definition(name) %}
+{%- call swift::docstring(obj, 0) %}
 public protocol {{ obj.name() }}Protocol {
-    {% for meth in obj.methods() -%}
+    {%- for meth in obj.methods() %}
+    {%- call swift::callable_docstring(meth, 4) %}
     func {{ meth.name()|fn_name }}({% call swift::arg_list_protocol(meth) %}) {% call swift::async(meth) %} {% call swift::throws(meth) -%}
     {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %} -> {{ return_type|type_name -}}
+    {%- when Some with (return_type) -%}
+    {%- if meth.is_async() %} -> {{ return_type|type_name -}}
+    {%- else %} -> {% call swift::return_type_name(return_type) -%}
+    {%- endif -%}
     {%- else -%}
     {%- endmatch %}
-    {% endfor %}
+    {%- endfor %}
 }
-
+{% call swift::docstring(obj, 0) %}
+{%- let consumable = obj.has_consuming_methods() %}
+{%- let is_actor = config.is_actor(name) %}
+{%- if is_actor %}
+public actor {{ type_name }}: {{ obj.name() }}Protocol {
+{%- else if config.open_classes() %}
+open class {{ type_name }}: {{ obj.name() }}Protocol {
+{%- else %}
 public class {{ type_name }}: {{ obj.name() }}Protocol {
+{%- endif %}
+{%- if is_actor %}
+    // Only `Sendable` state can be used outside of the actor's isolation, which the pointer isn't,
+    // so it's kept as its bit pattern
+    fileprivate let uniffiPointerBits: UInt
+    fileprivate nonisolated var pointer: UnsafeMutableRawPointer {
+        return UnsafeMutableRawPointer(bitPattern: uniffiPointerBits)!
+    }
+{%- else if consumable %}
+    // Methods that consume the object take the pointer, calling any method after that crashes
+    fileprivate var uniffiPointer: UnsafeMutableRawPointer?
+    fileprivate var pointer: UnsafeMutableRawPointer {
+        guard let pointer = uniffiPointer else {
+            fatalError("{{ type_name }} object has already been consumed")
+        }
+        return pointer
+    }
+
+    fileprivate func uniffiTakePointer() -> UnsafeMutableRawPointer {
+        let pointer = self.pointer
+        self.uniffiPointer = nil
+        return pointer
+    }
+{%- else if config.open_classes() %}
+    fileprivate let pointer: UnsafeMutableRawPointer!
+{%- else %}
     fileprivate let pointer: UnsafeMutableRawPointer
+{%- endif %}
+    {%- for c in obj.constants() %}
+    public static let {{ c.name()|var_name }}: {{ c|type_name }} = {{ c.value()|literal_swift(c) }}
+    {%- endfor %}
 
     // TODO: We'd like this to be `private` but for Swifty reasons,
     // we can't implement `FfiConverter` without making this `required` and we can't
     // make it `required` without making it `public`.
-    required init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
+    {% if config.open_classes() %}public {% endif %}{% if !is_actor %}required {% endif %}init(unsafeFromRawPointer pointer: UnsafeMutableRawPointer) {
+        {%- if is_actor %}
+        self.uniffiPointerBits = UInt(bitPattern: pointer)
+        {%- else if consumable %}
+        self.uniffiPointer = pointer
+        {%- else %}
         self.pointer = pointer
+        {%- endif %}
+    }
+    {%- if config.open_classes() %}
+
+    /// Passed to `init(noPointer:)` by subclasses that don't wrap a Rust object.
+    public struct NoPointer {
+        public init() {}
+    }
+
+    /// This initializer can be used by subclasses that don't wrap a Rust object, like test doubles.
+    ///
+    /// Calling a method that they don't override, or passing them to Rust, crashes.
+    public init(noPointer: NoPointer) {
+        {%- if consumable %}
+        self.uniffiPointer = nil
+        {%- else %}
+        self.pointer = nil
+        {%- endif %}
     }
+    {%- endif %}
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
-    public convenience init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
+    {%- call swift::callable_docstring(cons, 4) %}
+    public {% if !is_actor %}convenience {% endif %}init({% call swift::arg_list_decl(cons) -%}) {% call swift::throws(cons) %} {
         self.init(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
     }
     {%- when None %}
     {%- endmatch %}
 
     deinit {
+        {%- if consumable %}
+        guard let pointer = uniffiPointer else {
+            return
+        }
+        {%- else if config.open_classes() %}
+        guard let pointer = pointer else {
+            return
+        }
+        {%- endif %}
         try! rustCall { {{ obj.ffi_object_free().name() }}(pointer, $0) }
     }
 
     {% for cons in obj.alternate_constructors() %}
-
+{% call swift::callable_docstring(cons, 4) %}
     public static func {{ cons.name()|fn_name }}({% call swift::arg_list_decl(cons) %}) {% call swift::throws(cons) %} -> {{ type_name }} {
         return {{ type_name }}(unsafeFromRawPointer: {% call swift::to_ffi_call(cons) %})
     }
@@ -42,8 +118,8 @@ public class {{ type_name }}: {{ obj.name() }}Protocol {
     {# // TODO: Maybe merge the two templates (i.e the one with a return type and the one without) #}
     {% for meth in obj.methods() -%}
     {%- if meth.is_async() %}
-
-    public func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+{% call swift::callable_docstring(meth, 4) %}
+    {% if config.open_classes() %}open{% else %}public{% endif %} func {{ meth.name()|fn_name }}({%- call swift::arg_list_decl(meth) -%}) async {% call swift::throws(meth) %}{% match meth.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
         // Suspend the function and call the scaffolding function, passing it a callback handler from
         // `AsyncTypes.swift`
         //
@@ -52,7 +128,7 @@ public class {{ type_name }}: {{ obj.name() }}Protocol {
         var continuation: {{ meth.result_type().borrow()|future_continuation_type }}? = nil
         return {% call swift::try(meth) %} await withCheckedThrowingContinuation {
             continuation = $0
-            try! rustCall() {
+            try! {% call swift::record_usage_start(meth) %}rustCall() {
                 {{ meth.ffi_func().name() }}(
                     self.pointer,
                     {% call swift::arg_list_lowered(meth) %}
@@ -62,6 +138,7 @@ public class {{ type_name }}: {{ obj.name() }}Protocol {
                     $0
                 )
             }
+            {%- call swift::record_usage_end(meth) %}
         }
     }
 
@@ -70,23 +147,113 @@ public class {{ type_name }}: {{ obj.name() }}Protocol {
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) %}
-
-    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {{ return_type|type_name }} {
-        return {% call swift::try(meth) %} {{ return_type|lift_fn }}(
+{% call swift::callable_docstring(meth, 4) %}
+    {% if config.open_classes() %}open{% else %}public{% endif %} func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} -> {% call swift::return_type_name(return_type) %} {
+        {%- if meth.takes_self_by_value() %}
+        {%- call swift::lower_args_into_locals(meth) %}
+        {%- endif %}
+        return {% call swift::try(meth) %} {% call swift::return_lift_fn(return_type) %}(
+            {%- if meth.takes_self_by_value() %}
+            {% call swift::to_ffi_call_with_prefix("self.uniffiTakePointer()", meth) %}
+            {%- else %}
             {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+            {%- endif %}
         )
     }
 
     {%- when None %}
-
-    public func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+{% call swift::callable_docstring(meth, 4) %}
+    {% if config.open_classes() %}open{% else %}public{% endif %} func {{ meth.name()|fn_name }}({% call swift::arg_list_decl(meth) %}) {% call swift::throws(meth) %} {
+        {%- if meth.takes_self_by_value() %}
+        {%- call swift::lower_args_into_locals(meth) %}
+        {% call swift::to_ffi_call_with_prefix("self.uniffiTakePointer()", meth) %}
+        {%- else %}
         {% call swift::to_ffi_call_with_prefix("self.pointer", meth) %}
+        {%- endif %}
     }
 
     {%- endmatch -%}
     {%- endif -%}
     {% endfor %}
 }
+{%- if obj.is_thread_safe() && !is_actor %}
+
+// The object is declared `serialized` or `concurrent` in Rust, so it can be used from several
+// threads and tasks.
+extension {{ type_name }}: @unchecked Sendable {}
+{%- endif %}
+
+{%- for tm in obj.uniffi_traits() %}
+{%- match tm %}
+{%- when UniffiTrait::Display { fmt } %}
+
+extension {{ type_name }}: CustomStringConvertible {
+    public {% if is_actor %}nonisolated {% endif %}var description: String {
+        return {% call swift::try(fmt) %} {{ fmt.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", fmt) %}
+        )
+    }
+}
+{%- when UniffiTrait::Debug { fmt } %}
+
+extension {{ type_name }}: CustomDebugStringConvertible {
+    public {% if is_actor %}nonisolated {% endif %}var debugDescription: String {
+        return {% call swift::try(fmt) %} {{ fmt.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", fmt) %}
+        )
+    }
+}
+{%- when UniffiTrait::Eq { eq, ne } %}
+
+extension {{ type_name }}: Equatable {
+    public static func == (lhs: {{ type_name }}, other: {{ type_name }}) -> Bool {
+        return {% call swift::try(eq) %} {{ eq.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("lhs.pointer", eq) %}
+        )
+    }
+
+    public static func != (lhs: {{ type_name }}, other: {{ type_name }}) -> Bool {
+        return {% call swift::try(ne) %} {{ ne.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("lhs.pointer", ne) %}
+        )
+    }
+}
+{%- when UniffiTrait::Hash { hash } %}
+
+extension {{ type_name }}: Hashable {
+    public {% if is_actor %}nonisolated {% endif %}func hash(into hasher: inout Hasher) {
+        let val = {% call swift::try(hash) %} {{ hash.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", hash) %}
+        )
+        hasher.combine(val)
+    }
+}
+{%- when UniffiTrait::Ord { cmp } %}
+
+extension {{ type_name }}: Comparable {
+    public static func < (lhs: {{ type_name }}, other: {{ type_name }}) -> Bool {
+        return {% call swift::try(cmp) %} {{ cmp.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("lhs.pointer", cmp) %}
+        ) < 0
+    }
+}
+{%- when UniffiTrait::Restore { serialize, restore } %}
+
+extension {{ type_name }} {
+    public {% if is_actor %}nonisolated {% endif %}func serializeHandle() -> String {
+        return {% call swift::try(serialize) %} {{ serialize.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call_with_prefix("self.pointer", serialize) %}
+        )
+    }
+
+    public static func restoreHandle(_ token: String) -> {{ type_name }}? {
+        return {% call swift::try(restore) %} {{ restore.return_type().unwrap()|lift_fn }}(
+            {% call swift::to_ffi_call(restore) %}
+        )
+    }
+}
+{%- endmatch %}
+{%- endfor %}
 
 public struct {{ ffi_converter_name }}: FfiConverter {
     typealias FfiType = UnsafeMutableRawPointer
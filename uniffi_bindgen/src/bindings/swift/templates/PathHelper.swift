@@ -0,0 +1,25 @@
+fileprivate struct FfiConverterPath: FfiConverterRustBuffer {
+    typealias SwiftType = URL
+
+    // Paths are passed as their raw bytes, in the file system representation.  Building the URL
+    // from that representation means paths that aren't valid UTF-8 don't need to be decoded.
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> URL {
+        var bytes = try FfiConverterData.read(from: &buf)
+        let isDirectory = bytes.last == UInt8(ascii: "/")
+        bytes.append(0)
+        return bytes.withUnsafeBytes { ptr in
+            URL(
+                fileURLWithFileSystemRepresentation: ptr.bindMemory(to: CChar.self).baseAddress!,
+                isDirectory: isDirectory,
+                relativeTo: nil
+            )
+        }
+    }
+
+    public static func write(_ value: URL, into buf: inout [UInt8]) {
+        let bytes = value.withUnsafeFileSystemRepresentation { ptr in
+            ptr.map { Data(bytes: $0, count: strlen($0)) } ?? Data()
+        }
+        FfiConverterData.write(bytes, into: &buf)
+    }
+}
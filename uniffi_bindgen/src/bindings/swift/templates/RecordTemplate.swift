@@ -1,23 +1,30 @@
 {%- let rec = ci|get_record_definition(name) %}
+{%- match config.code_hooks().type_override(type_) %}
+{%- when Some with (type_override) %}
+
+{{ type_override }}
+{%- when None %}
+{%- call swift::docstring(rec, 0) %}
 public struct {{ type_name }} {
     {%- for field in rec.fields() %}
-    public var {{ field.name()|var_name }}: {{ field|type_name }}
+    public var {{ self.field_name(field.name()) }}: {{ field|type_name }}
     {%- endfor %}
 
     // Default memberwise initializers are never public by default, so we
     // declare one manually.
     public init({% call swift::field_list_decl(rec) %}) {
         {%- for field in rec.fields() %}
-        self.{{ field.name()|var_name }} = {{ field.name()|var_name }}
+        self.{{ self.field_name(field.name()) }} = {{ self.field_name(field.name()) }}
         {%- endfor %}
     }
 }
+{%- endmatch %}
 
-{% if !contains_object_references %}
+{% if is_hashable %}
 extension {{ type_name }}: Equatable, Hashable {
     public static func ==(lhs: {{ type_name }}, rhs: {{ type_name }}) -> Bool {
         {%- for field in rec.fields() %}
-        if lhs.{{ field.name()|var_name }} != rhs.{{ field.name()|var_name }} {
+        if lhs.{{ self.field_name(field.name()) }} != rhs.{{ self.field_name(field.name()) }} {
             return false
         }
         {%- endfor %}
@@ -26,17 +33,91 @@ extension {{ type_name }}: Equatable, Hashable {
 
     public func hash(into hasher: inout Hasher) {
         {%- for field in rec.fields() %}
-        hasher.combine({{ field.name()|var_name }})
+        hasher.combine({{ self.field_name(field.name()) }})
         {%- endfor %}
     }
 }
 {% endif %}
 
+{%- if is_codable %}
+
+extension {{ type_name }}: Codable {}
+{% endif %}
+
+{%- match rec.ffi_func_cmp() %}
+{%- when Some(cmp) %}
+
+// Compares the records with their `Ord` implementation in Rust
+extension {{ type_name }}: Comparable {
+    public static func < (lhs: {{ type_name }}, rhs: {{ type_name }}) -> Bool {
+        return uniffiCompare(lhs, rhs) < 0
+    }
+    {%- if !is_hashable %}
+
+    // `Comparable` requires `Equatable`, which is only generated for hashable records
+    public static func == (lhs: {{ type_name }}, rhs: {{ type_name }}) -> Bool {
+        return uniffiCompare(lhs, rhs) == 0
+    }
+    {%- endif %}
+
+    private static func uniffiCompare(_ lhs: {{ type_name }}, _ rhs: {{ type_name }}) -> Int8 {
+        return try! rustCall {
+            {{ cmp.name() }}({{ rec|lower_fn }}(lhs), {{ rec|lower_fn }}(rhs), $0)
+        }
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
+{%- let ffi_type_name = rec.ffi_type().borrow()|ffi_type_name %}
+{%- if rec.is_repr_c() %}
+
+// `{{ type_name }}` is passed to and from Rust as a `{{ ffi_type_name }}` struct, rather than in a
+// `RustBuffer`.
+public struct {{ ffi_converter_name }}: FfiConverter {
+    typealias FfiType = {{ ffi_type_name }}
+    typealias SwiftType = {{ type_name }}
+
+    public static func lift(_ value: {{ ffi_type_name }}) throws -> {{ type_name }} {
+        return try {{ type_name }}(
+            {%- for field in rec.fields() %}
+            {{ self.field_label(field.name()) }}: {{ field|lift_fn }}(value.{{ field.name() }})
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+    }
+
+    public static func lower(_ value: {{ type_name }}) -> {{ ffi_type_name }} {
+        return {{ ffi_type_name }}(
+            {%- for field in rec.fields() %}
+            {{ field.name() }}: {{ field|lower_fn }}(value.{{ self.field_name(field.name()) }})
+            {%- if !loop.last %}, {% endif %}
+            {%- endfor %}
+        )
+    }
+{%- else %}
+
 public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    {%- if config.is_pooled_record(name) %}
+    // `{{ type_name }}` is listed in `pooled_records`, so it's read straight from the Rust buffer
+    // rather than from a copy of it.  Reading copies all the field values out of the buffer, so it
+    // can be freed afterwards.
+    public static func lift(_ buf: RustBuffer) throws -> {{ type_name }} {
+        var reader = createReader(data: Data(noCopyRustBuffer: buf))
+        let value = try read(from: &reader)
+        if hasRemaining(reader) {
+            throw UniffiInternalError.incompleteData
+        }
+        buf.deallocate()
+        return value
+    }
+    {%- endif %}
+{%- endif %}
+
     public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> {{ type_name }} {
         return try {{ type_name }}(
             {%- for field in rec.fields() %}
-            {{ field.name()|arg_name }}: {{ field|read_fn }}(from: &buf)
+            {{ self.field_label(field.name()) }}: {{ field|read_fn }}(from: &buf)
             {%- if !loop.last %}, {% endif %}
             {%- endfor %}
         )
@@ -44,7 +125,7 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
 
     public static func write(_ value: {{ type_name }}, into buf: inout [UInt8]) {
         {%- for field in rec.fields() %}
-        {{ field|write_fn }}(value.{{ field.name()|var_name }}, into: &buf)
+        {{ field|write_fn }}(value.{{ self.field_name(field.name()) }}, into: &buf)
         {%- endfor %}
     }
 }
@@ -53,10 +134,10 @@ public struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
 We always write these public functions just in case the struct is used as
 an external type by another crate.
 #}
-public func {{ ffi_converter_name }}_lift(_ buf: RustBuffer) throws -> {{ type_name }} {
+public func {{ ffi_converter_name }}_lift(_ buf: {{ ffi_type_name }}) throws -> {{ type_name }} {
     return try {{ ffi_converter_name }}.lift(buf)
 }
 
-public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> RustBuffer {
+public func {{ ffi_converter_name }}_lower(_ value: {{ type_name }}) -> {{ ffi_type_name }} {
     return {{ ffi_converter_name }}.lower(value)
 }
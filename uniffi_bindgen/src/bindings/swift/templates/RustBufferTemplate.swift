@@ -22,6 +22,10 @@ fileprivate extension ForeignBytes {
     init(bufferPointer: UnsafeBufferPointer<UInt8>) {
         self.init(len: Int32(bufferPointer.count), data: bufferPointer.baseAddress)
     }
+
+    init(rawBufferPointer: UnsafeRawBufferPointer) {
+        self.init(bufferPointer: rawBufferPointer.bindMemory(to: UInt8.self))
+    }
 }
 
 // For every type used in the interface, we provide helper methods for conveniently
@@ -37,6 +41,15 @@ fileprivate extension Data {
         // Rust buffer?
         self.init(bytes: rustBuffer.data!, count: Int(rustBuffer.len))
     }
+
+    // Wraps the buffer without copying it.  The `Data` must not be used after the buffer is freed.
+    init(noCopyRustBuffer rustBuffer: RustBuffer) {
+        guard let data = rustBuffer.data, rustBuffer.len > 0 else {
+            self.init()
+            return
+        }
+        self.init(bytesNoCopy: data, count: Int(rustBuffer.len), deallocator: .none)
+    }
 }
 
 // Define reader functionality.  Normally this would be defined in a class or
@@ -181,3 +194,17 @@ extension FfiConverterRustBuffer {
           return RustBuffer(bytes: writer)
     }
 }
+
+// Lower an argument that's marked as sensitive.
+//
+// This is like `FfiConverterRustBuffer.lower()`, but zeroes the bytes that were written on the
+// Swift side once they've been copied into the `RustBuffer`, so that the secret doesn't linger in
+// freed memory.  The Rust side wipes the `RustBuffer` after reading the value out of it.
+fileprivate func uniffiLowerSensitive<C: FfiConverter>(_ converter: C.Type, _ value: C.SwiftType) -> RustBuffer where C.FfiType == RustBuffer {
+    var writer = createWriter()
+    converter.write(value, into: &writer)
+    defer {
+        writer.withUnsafeMutableBytes { $0.initializeMemory(as: UInt8.self, repeating: 0) }
+    }
+    return RustBuffer(bytes: writer)
+}
@@ -0,0 +1,38 @@
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    // Values sent to Rust are always inline
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, UInt8(0))
+        {{ inner_type|write_fn }}(value, into: &buf)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        switch try readInt(&buf) as UInt8 {
+        case 0: return try {{ inner_type|read_fn }}(from: &buf)
+        case 1:
+            // Large values are in a buffer of their own, which we own and have to free
+            let capacity: Int32 = try readInt(&buf)
+            let len: Int32 = try readInt(&buf)
+            let offset: Int32 = try readInt(&buf)
+            let address: UInt64 = try readInt(&buf)
+            let rbuf = RustBuffer(
+                capacity: capacity,
+                len: len,
+                data: UnsafeMutablePointer<UInt8>(bitPattern: UInt(address))
+            )
+            defer { rbuf.deallocate() }
+            var reader = createReader(data: Data(noCopyRustBuffer: rbuf))
+            guard offset >= 0, offset <= len else {
+                throw UniffiInternalError.bufferOverflow
+            }
+            reader.offset = Int(offset)
+            let value = try {{ inner_type|read_fn }}(from: &reader)
+            if hasRemaining(reader) {
+                throw UniffiInternalError.incompleteData
+            }
+            return value
+        default: throw UniffiInternalError.invalidSharedTransport
+        }
+    }
+}
@@ -0,0 +1,25 @@
+fileprivate struct FfiConverterSocketAddr: FfiConverterRustBuffer {
+    typealias SwiftType = String
+
+    // Socket addresses are strings like "192.0.2.1:8080" or "[2001:db8::1]:8080", and are passed
+    // as their IP address followed by the u16 port.  Lowering a string that isn't a socket
+    // address is a programming error, and crashes.
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> String {
+        let address = try FfiConverterIpAddr.read(from: &buf)
+        let port: UInt16 = try readInt(&buf)
+        return address.contains(":") ? "[\(address)]:\(port)" : "\(address):\(port)"
+    }
+
+    public static func write(_ value: String, into buf: inout [UInt8]) {
+        guard let colon = value.lastIndex(of: ":"),
+              let port = UInt16(value[value.index(after: colon)...]) else {
+            fatalError("Invalid socket address: \(value)")
+        }
+        var address = value[..<colon]
+        if address.hasPrefix("[") && address.hasSuffix("]") {
+            address = address.dropFirst().dropLast()
+        }
+        FfiConverterIpAddr.write(String(address), into: &buf)
+        writeInt(&buf, port)
+    }
+}
@@ -1,6 +1,6 @@
 {%- if func.is_async() %}
-
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
+{% call swift::callable_docstring(func, 0) %}
+public {% if func.sub_namespace().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) async {% call swift::throws(func) %}{% match func.return_type() %}{% when Some with (return_type) %} -> {{ return_type|type_name }}{% when None %}{% endmatch %} {
     var continuation: {{ func.result_type().borrow()|future_continuation_type }}? = nil
     // Suspend the function and call the scaffolding function, passing it a callback handler from
     // `AsyncTypes.swift`
@@ -9,7 +9,7 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
     // it's not freed before the callback is invoked.
     return {% call swift::try(func) %} await withCheckedThrowingContinuation {
         continuation = $0
-        try! rustCall() {
+        try! {% call swift::record_usage_start(func) %}rustCall() {
             {{ func.ffi_func().name() }}(
                 {% call swift::arg_list_lowered(func) %}
                 FfiConverterForeignExecutor.lower(UniFfiForeignExecutor()),
@@ -18,6 +18,7 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
                 $0
             )
         }
+        {%- call swift::record_usage_end(func) %}
     }
 }
 
@@ -25,16 +26,16 @@ public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) a
 
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
-
-public func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {{ return_type|type_name }} {
-    return {% call swift::try(func) %} {{ return_type|lift_fn }}(
+{% call swift::callable_docstring(func, 0) %}
+public {% if func.sub_namespace().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({%- call swift::arg_list_decl(func) -%}) {% call swift::throws(func) %} -> {% call swift::return_type_name(return_type) %} {
+    return {% call swift::try(func) %} {% call swift::return_lift_fn(return_type) %}(
         {% call swift::to_ffi_call(func) %}
     )
 }
 
 {%- when None %}
-
-public func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
+{% call swift::callable_docstring(func, 0) %}
+public {% if func.sub_namespace().is_some() %}static {% endif %}func {{ func.name()|fn_name }}({% call swift::arg_list_decl(func) %}) {% call swift::throws(func) %} {
     {% call swift::to_ffi_call(func) %}
 }
 
@@ -4,6 +4,10 @@
 {%- let ffi_converter_name = type_|ffi_converter_name %}
 {%- let canonical_type_name = type_|canonical_name %}
 {%- let contains_object_references = ci.item_contains_object_references(type_) %}
+{#- JSON values are `Any`, which isn't `Equatable` or `Hashable` #}
+{%- let is_hashable = !contains_object_references && !ci.item_contains_json_values(type_) %}
+{#- Objects and JSON values can't be encoded either #}
+{%- let is_codable = config.codable() && is_hashable %}
 
 {#
  # Map `Type` instances to an include statement for that type.
@@ -19,6 +23,9 @@
 {%- when Type::Boolean %}
 {%- include "BooleanHelper.swift" %}
 
+{%- when Type::Char %}
+{%- include "UnicodeScalarHelper.swift" %}
+
 {%- when Type::String %}
 {%- include "StringHelper.swift" %}
 
@@ -61,6 +68,27 @@
 {%- when Type::Duration %}
 {%- include "DurationHelper.swift" %}
 
+{%- when Type::Url %}
+{%- include "UrlHelper.swift" %}
+
+{%- when Type::Uuid %}
+{%- include "UuidHelper.swift" %}
+
+{%- when Type::Decimal %}
+{%- include "DecimalHelper.swift" %}
+
+{%- when Type::Json %}
+{%- include "JsonHelper.swift" %}
+
+{%- when Type::Path %}
+{%- include "PathHelper.swift" %}
+
+{%- when Type::IpAddr %}
+{%- include "IpAddrHelper.swift" %}
+
+{%- when Type::SocketAddr %}
+{%- include "SocketAddrHelper.swift" %}
+
 {%- when Type::CallbackInterface { name, module_path } %}
 {%- include "CallbackInterfaceTemplate.swift" %}
 
@@ -78,6 +106,10 @@
 {%- include "EnumTemplate.swift" %}
 {% endif %}
 
+{%- when Type::Flags { name, module_path } %}
+{%- let flags = ci.get_flags_definition(name).unwrap() %}
+{%- include "FlagsTemplate.swift" %}
+
 {%- when Type::Object{ name, module_path, imp } %}
 {%- include "ObjectTemplate.swift" %}
 
@@ -93,10 +125,29 @@
 {%- when Type::Map { key_type, value_type } %}
 {%- include "MapTemplate.swift" %}
 
+{%- when Type::Compressed { inner_type } %}
+{%- include "CompressedTemplate.swift" %}
+
+{%- when Type::Shared { inner_type } %}
+{%- include "SharedTemplate.swift" %}
+
+{%- when Type::Weak { inner_type } %}
+{%- include "WeakTemplate.swift" %}
+
 {%- else %}
 {%- endmatch %}
+{%- match config.code_hooks().type_extension(type_) %}
+{%- when Some with (extension) %}
+
+{{ extension }}
+{%- when None %}
+{%- endmatch %}
 {%- endfor %}
 
 {%- if ci.has_async_fns() %}
 {%- include "AsyncTypes.swift" %}
 {%- endif %}
+
+{%- if config.zero_copy_bytes() %}
+{%- include "ZeroCopyBytes.swift" %}
+{%- endif %}
@@ -0,0 +1,29 @@
+fileprivate struct FfiConverterUrl: FfiConverter {
+    typealias SwiftType = URL
+    typealias FfiType = RustBuffer
+
+    // URLs are passed exactly like strings, Rust validates them when it lifts them.  Foundation
+    // parses URLs more strictly than the `url` crate, so a URL from Rust may still be rejected here.
+    public static func lift(_ value: RustBuffer) throws -> URL {
+        return try parse(try FfiConverterString.lift(value))
+    }
+
+    public static func lower(_ value: URL) -> RustBuffer {
+        return FfiConverterString.lower(value.absoluteString)
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> URL {
+        return try parse(try FfiConverterString.read(from: &buf))
+    }
+
+    public static func write(_ value: URL, into buf: inout [UInt8]) {
+        FfiConverterString.write(value.absoluteString, into: &buf)
+    }
+
+    private static func parse(_ string: String) throws -> URL {
+        guard let url = URL(string: string) else {
+            throw UniffiInternalError.invalidUrl
+        }
+        return url
+    }
+}
@@ -0,0 +1,10 @@
+/// Called with the name of each API in this module as it's used, like `"Object.method"`.
+///
+/// The hook is called on the calling thread before the call into Rust, so it should be cheap.
+/// This is only generated when `usage_hooks` is enabled in `uniffi.toml`.
+public var uniffiUsageHook: ((String) -> Void)? = nil
+
+fileprivate func uniffiRecordUsage<T>(_ name: String, _ call: () throws -> T) rethrows -> T {
+    uniffiUsageHook?(name)
+    return try call()
+}
@@ -0,0 +1,13 @@
+fileprivate struct FfiConverterUuid: FfiConverterRustBuffer {
+    typealias SwiftType = UUID
+
+    // UUIDs are passed as their 16 raw bytes, in the same order as `UUID.uuid`.
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> UUID {
+        let bytes = try readBytes(&buf, count: 16)
+        return bytes.withUnsafeBytes { UUID(uuid: $0.load(as: uuid_t.self)) }
+    }
+
+    public static func write(_ value: UUID, into buf: inout [UInt8]) {
+        withUnsafeBytes(of: value.uuid) { writeBytes(&buf, $0) }
+    }
+}
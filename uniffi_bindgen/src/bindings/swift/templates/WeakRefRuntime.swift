@@ -0,0 +1,37 @@
+/**
+ * A reference to a Rust object that doesn't keep it alive, from a `Weak` in Rust.
+ *
+ * Use `upgrade()` to get the object while it's still alive.
+ */
+public final class WeakRef<T> {
+    private let handle: UInt64
+    private let liftObject: (UnsafeMutableRawPointer) throws -> T
+
+    fileprivate init(handle: UInt64, liftObject: @escaping (UnsafeMutableRawPointer) throws -> T) {
+        self.handle = handle
+        self.liftObject = liftObject
+    }
+
+    deinit {
+        try! rustCall { {{ ci.ffi_weak_ref_free().name() }}(handle, $0) }
+    }
+
+    /// Get a new reference to the object, or `nil` if Rust dropped it.
+    public func upgrade() -> T? {
+        let v = try! rustCall { {{ ci.ffi_weak_ref_upgrade().name() }}(handle, $0) }
+        guard let ptr = UnsafeMutableRawPointer(bitPattern: UInt(truncatingIfNeeded: v)) else {
+            return nil
+        }
+        return try! liftObject(ptr)
+    }
+
+    /// Whether the object is still alive.
+    public var isAlive: Bool {
+        return upgrade() != nil
+    }
+
+    // Rust takes ownership of the handles that it reads, so it's passed a copy of this one.
+    fileprivate func cloneHandle() -> UInt64 {
+        return try! rustCall { {{ ci.ffi_weak_ref_clone().name() }}(handle, $0) }
+    }
+}
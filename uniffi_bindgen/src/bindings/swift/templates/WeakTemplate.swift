@@ -0,0 +1,15 @@
+{%- if self.include_once_check("WeakRefRuntime.swift") %}{%- include "WeakRefRuntime.swift" %}{%- endif %}
+
+fileprivate struct {{ ffi_converter_name }}: FfiConverterRustBuffer {
+    typealias SwiftType = {{ type_name }}
+
+    public static func write(_ value: SwiftType, into buf: inout [UInt8]) {
+        writeInt(&buf, value.cloneHandle())
+    }
+
+    public static func read(from buf: inout (data: Data, offset: Data.Index)) throws -> SwiftType {
+        // The handle is ours now, it's freed when the `WeakRef` is deinitialized
+        let handle: UInt64 = try readInt(&buf)
+        return WeakRef(handle: handle, liftObject: {{ inner_type|lift_fn }})
+    }
+}
@@ -0,0 +1,17 @@
+// Lifts the `bytes` returned by functions and methods when `zero_copy_bytes` is enabled.
+//
+// The `Data` reads the buffer Rust allocated, instead of copying it, and frees the buffer when
+// it's deallocated.
+fileprivate enum FfiConverterDataNoCopy {
+    static func lift(_ buf: RustBuffer) throws -> Data {
+        guard let data = buf.data, buf.len >= 4 else {
+            throw UniffiInternalError.bufferOverflow
+        }
+        // Skip the length that the bytes are serialized with
+        return Data(
+            bytesNoCopy: UnsafeMutableRawPointer(data + 4),
+            count: Int(buf.len) - 4,
+            deallocator: .custom { _, _ in buf.deallocate() }
+        )
+    }
+}
@@ -6,6 +6,8 @@
 
 {%- macro to_ffi_call(func) -%}
     {%- call try(func) -%}
+    {%- call record_usage_start(func) -%}
+    {%- call borrow_bytes_start(func) -%}
     {%- match func.throws_type() -%}
     {%- when Some with (e) -%}
         rustCallWithError({{ e|ffi_converter_name }}.lift) {
@@ -14,10 +16,14 @@
     {%- endmatch %}
     {{ func.ffi_func().name() }}({% call arg_list_lowered(func) -%} $0)
 }
+    {%- call borrow_bytes_end(func) -%}
+    {%- call record_usage_end(func) -%}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
 {% call try(func) %}
+    {%- call record_usage_start(func) -%}
+    {%- call borrow_bytes_start(func) -%}
     {%- match func.throws_type() %}
     {%- when Some with (e) %}
     rustCallWithError({{ e|ffi_converter_name }}.lift) {
@@ -28,22 +34,93 @@
         {{- prefix }}, {% call arg_list_lowered(func) -%} $0
     )
 }
+    {%- call borrow_bytes_end(func) -%}
+    {%- call record_usage_end(func) -%}
 {%- endmacro %}
 
 {%- macro arg_list_lowered(func) %}
     {%- for arg in func.arguments() %}
-        {{ arg|lower_fn }}({{ arg.name()|var_name }}),
+        {%- if arg.borrows_bytes() %}
+        ForeignBytes(rawBufferPointer: {{ self.field_name(arg.name()) }}Bytes),
+        {%- else if func.takes_self_by_value() %}
+        uniffiLowered{{ loop.index }},
+        {%- else if arg.wipe_lifted_buffer() %}
+        uniffiLowerSensitive({{ arg|ffi_converter_name }}.self, {{ self.field_name(arg.name()) }}),
+        {%- else %}
+        {{ arg|lower_fn }}({{ self.field_name(arg.name()) }}),
+        {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// Methods that consume the object lower their arguments before they take its pointer, like the
+// other bindings do.  `arg_list_lowered` passes these locals.  Borrowed bytes are only valid inside
+// `withUnsafeBytes`, so they're still passed directly.
+-#}
+{%- macro lower_args_into_locals(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.borrows_bytes() %}
+        {%- else if arg.wipe_lifted_buffer() %}
+        let uniffiLowered{{ loop.index }} = uniffiLowerSensitive({{ arg|ffi_converter_name }}.self, {{ self.field_name(arg.name()) }})
+        {%- else %}
+        let uniffiLowered{{ loop.index }} = {{ arg|lower_fn }}({{ self.field_name(arg.name()) }})
+        {%- endif %}
     {%- endfor %}
 {%- endmacro -%}
 
+{#-
+// `[ByRef] bytes` arguments are passed as a pointer to the `Data`, which is only valid inside
+// `withUnsafeBytes`, so the call is nested inside a closure for each of them.
+-#}
+{%- macro borrow_bytes_start(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_bytes() -%}
+        {{ self.field_name(arg.name()) }}.withUnsafeBytes { {{ self.field_name(arg.name()) }}Bytes in try {# space #}
+    {%- endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro borrow_bytes_end(func) %}
+    {%- for arg in func.arguments() %}
+    {%- if arg.borrows_bytes() %} }{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{#-
+// When `usage_hooks` is enabled, calls are wrapped in `uniffiRecordUsage()`, which notifies
+// `uniffiUsageHook`.  Otherwise nothing is generated.
+-#}
+{%- macro record_usage_start(func) %}
+    {%- if config.usage_hooks() -%}
+        uniffiRecordUsage("{{ func.qualified_name() }}") { try {# space #}
+    {%- endif %}
+{%- endmacro -%}
+
+{%- macro record_usage_end(func) %}
+    {%- if config.usage_hooks() %} }{% endif %}
+{%- endmacro -%}
+
+{#-
+// The type returned by a function or method, and the function that lifts it.  When
+// `zero_copy_bytes` is enabled, `bytes` and `Vec<u8>` are returned as a `Data` that's lifted
+// without copying it.
+-#}
+{%- macro return_type_name(return_type) %}
+    {%- if config.is_zero_copy_bytes(return_type) %}Data{% else %}{{ return_type|type_name }}{% endif %}
+{%- endmacro -%}
+
+{%- macro return_lift_fn(return_type) %}
+    {%- if config.is_zero_copy_bytes(return_type) %}FfiConverterDataNoCopy.lift{% else %}{{ return_type|lift_fn }}{% endif %}
+{%- endmacro -%}
+
 {#-
 // Arglist as used in Swift declarations of methods, functions and constructors.
-// Note the var_name and type_name filters.
+// Note the field_name method and the type_name filter.
 -#}
 
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {% if config.omit_argument_labels() %}_ {% endif %}{{ self.field_name(arg.name()) }}: {% if ci.is_closure_type(arg.as_type().borrow()) %}@escaping {% endif %}{{ arg|type_name -}}
         {%- match arg.default_value() %}
         {%- when Some with(literal) %} = {{ literal|literal_swift(arg) }}
         {%- else %}
@@ -54,11 +131,11 @@
 
 {#-
 // Field lists as used in Swift declarations of Records and Enums.
-// Note the var_name and type_name filters.
+// Note the field_name method and the type_name filter.
 -#}
 {% macro field_list_decl(item) %}
     {%- for field in item.fields() -%}
-        {{ field.name()|var_name }}: {{ field|type_name -}}
+        {{ self.field_name(field.name()) }}: {{ field|type_name -}}
         {%- match field.default_value() %}
             {%- when Some with(literal) %} = {{ literal|literal_swift(field) }}
             {%- else %}
@@ -70,7 +147,7 @@
 
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {% if config.omit_argument_labels() %}_ {% endif %}{{ arg.name()|var_name }}: {{ arg|type_name -}}
+        {% if config.omit_argument_labels() %}_ {% endif %}{{ self.field_name(arg.name()) }}: {{ arg|type_name -}}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro %}
@@ -87,3 +164,27 @@
 {%- macro try(func) %}
 {%- if func.throws() %}try {% else %}try! {% endif %}
 {%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, object, record, enum or callback interface as a
+// documentation comment on its own line, or nothing if it doesn't have one.
+-#}
+{%- macro docstring(defn, indent_spaces) %}
+{%- match defn.docstring() %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
+
+{#-
+// Renders the docstring of a function, method or constructor like `docstring`, followed by a note
+// for each sensitive argument.
+-#}
+{%- macro callable_docstring(callable, indent_spaces) %}
+{%- match self.docstring_with_notes(callable) %}
+{%- when Some with (docstring) %}
+{{ docstring|docstring(indent_spaces.to_owned()) }}
+{%- when None %}
+{%- endmatch %}
+{%- endmacro -%}
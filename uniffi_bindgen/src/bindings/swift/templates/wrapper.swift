@@ -1,6 +1,17 @@
+{%- match config.code_hooks().preamble() -%}
+{%- when Some with (preamble) -%}
+{{ preamble }}
+{% when None -%}
+{%- endmatch -%}
 // This file was autogenerated by some hot garbage in the `uniffi` crate.
 // Trust me, you don't want to mess with it!
 {%- import "macros.swift" as swift %}
+{%- match ci.namespace_docstring() %}
+{%- when Some with (docstring) %}
+
+{{ docstring|docstring(0) }}
+{%- when None %}
+{%- endmatch %}
 import Foundation
 {%- for imported_class in self.imports() %}
 import {{ imported_class }}
@@ -12,38 +23,134 @@ import {{ imported_class }}
 #if canImport({{ config.ffi_module_name() }})
 import {{ config.ffi_module_name() }}
 #endif
+{%- match config.code_hooks().after_imports() %}
+{%- when Some with (after_imports) %}
+
+{{ after_imports }}
+{%- when None %}
+{%- endmatch %}
 
 {% include "RustBufferTemplate.swift" %}
 {% include "Helpers.swift" %}
+{%- if config.usage_hooks() %}
+
+{% include "UsageHooks.swift" %}
+{%- endif %}
+{%- if ci.has_main_thread_callback_interfaces() %}
+
+{% include "MainThread.swift" %}
+{%- endif %}
 
 // Public interface members begin here.
 {{ type_helper_code }}
 
-{%- for func in ci.function_definitions() %}
+{%- for c in ci.constant_definitions() %}
+{%- if loop.first %}
+{% endif %}
+public let {{ c.name()|var_name }}: {{ c|type_name }} = {{ c.value()|literal_swift(c) }}
+{%- endfor %}
+
+{%- for func in ci.root_function_definitions() %}
 {%- include "TopLevelFunctionTemplate.swift" %}
 {%- endfor %}
 
+{%- for ns in ci.sub_namespaces() %}
+
+/// The functions of the `{{ ns }}` sub-namespace
+public enum {{ ns|class_name }} {
+{%- for func in ci.sub_namespace_function_definitions(ns) %}
+{%- include "TopLevelFunctionTemplate.swift" %}
+{%- endfor %}
+}
+{%- endfor %}
+
+/**
+ * Lists the objects that Rust passed to Swift and that haven't been freed yet, and the callback
+ * interfaces that Rust still holds, with a backtrace of where each one was created.
+ *
+ * This needs the `handle-diagnostics` feature of the `uniffi` crate, otherwise the report only
+ * says that it's disabled.
+ */
+public func uniffiDebugLiveHandles() -> String {
+    return try! {{ Type::String.borrow()|lift_fn }}(rustCall() {
+        {{ ci.ffi_uniffi_debug_live_handles().name() }}($0)
+    })
+}
+{%- if ci.has_detachable_callback_interfaces() %}
+
+/**
+ * Tells Rust that `obj` is gone, when it was passed to Rust as a callback interface that Rust may
+ * still hold.
+ *
+ * This calls the `on_detach` hook that Rust set for the callback interface, and from then on
+ * calling the object's methods from Rust fails rather than calling `obj`.  The bindings release
+ * the object too, so that it's deinitialized even if Rust holds on to it.
+ */
+public func uniffiDetach(_ obj: AnyObject) {
+    {%- for cbi in ci.callback_interface_definitions() %}
+    {%- if !cbi.is_closure() %}
+    {{ cbi|ffi_converter_name }}.detach(obj)
+    {%- endif %}
+    {%- endfor %}
+}
+{%- endif %}
+{%- match config.code_hooks().footer() %}
+{%- when Some with (footer) %}
+
+{{ footer }}
+{%- when None %}
+{%- endmatch %}
+
+// What the library reports about its version, and the checksums of its functions.  This is only
+// read once the contract versions match, since the functions may not exist otherwise.
+private struct UniffiContractInfo {
+    let uniffiVersion: String
+    let checksums: [String: UInt16]
+}
+
+// This runs before the first call into Rust, which waits for it, so it can't use `rustCall()`.
+private func uniffiContractInfo() -> UniffiContractInfo {
+    var status = RustCallStatus.init()
+    let buf = {{ ci.ffi_uniffi_contract_info().name() }}(&status)
+    var reader = createReader(data: Data(rustBuffer: buf))
+    {{ ci.ffi_rustbuffer_free().name() }}(buf, &status)
+    let versionLen: Int32 = try! readInt(&reader)
+    let uniffiVersion = String(decoding: try! readBytes(&reader, count: Int(versionLen)), as: UTF8.self)
+    let checksums: [String: UInt16] = [
+        {%- for (name, _checksum) in ci.iter_checksums() %}
+        "{{ name }}": {{ name }}(),
+        {%- else %}
+        :
+        {%- endfor %}
+    ]
+    return UniffiContractInfo(uniffiVersion: uniffiVersion, checksums: checksums)
+}
+
 private enum InitializationResult {
     case ok
-    case contractVersionMismatch
-    case apiChecksumMismatch
+    case contractVersionMismatch(UInt32)
+    case apiChecksumMismatch(UniffiContractInfo, [String])
 }
 // Use a global variables to perform the versioning checks. Swift ensures that
 // the code inside is only computed once.
 private var initializationResult: InitializationResult {
-    // Get the bindings contract version from our ComponentInterface
-    let bindings_contract_version = {{ ci.uniffi_contract_version() }}
-    // Get the scaffolding contract version by calling the into the dylib
-    let scaffolding_contract_version = {{ ci.ffi_uniffi_contract_version().name() }}()
-    if bindings_contract_version != scaffolding_contract_version {
-        return InitializationResult.contractVersionMismatch
+    // The contract version is a plain integer, so it can be read from any library, however old.
+    let contractVersion = {{ ci.ffi_uniffi_contract_version().name() }}()
+    if contractVersion != {{ ci.uniffi_contract_version() }} {
+        return InitializationResult.contractVersionMismatch(contractVersion)
     }
 
+    let info = uniffiContractInfo()
+
+    var mismatched: [String] = []
     {%- for (name, expected_checksum) in ci.iter_checksums() %}
-    if ({{ name }}() != {{ expected_checksum }}) {
-        return InitializationResult.apiChecksumMismatch
+    if info.checksums["{{ name }}"] != {{ expected_checksum }} {
+        mismatched.append("{{ name }}")
     }
     {%- endfor %}
+    if !mismatched.isEmpty {
+        return InitializationResult.apiChecksumMismatch(info, mismatched)
+    }
 
     {% for fn in self.initialization_fns() -%}
     {{ fn }}()
@@ -56,9 +163,17 @@ private func uniffiEnsureInitialized() {
     switch initializationResult {
     case .ok:
         break
-    case .contractVersionMismatch:
-        fatalError("UniFFI contract version mismatch: try cleaning and rebuilding your project")
-    case .apiChecksumMismatch:
-        fatalError("UniFFI API checksum mismatch: try cleaning and rebuilding your project")
+    case let .contractVersionMismatch(contractVersion):
+        fatalError(
+            "UniFFI contract version mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "(contract version {{ ci.uniffi_contract_version() }}) but the library has contract version \(contractVersion). " +
+            "Try cleaning and rebuilding your project"
+        )
+    case let .apiChecksumMismatch(info, mismatched):
+        fatalError(
+            "UniFFI API checksum mismatch: the bindings were generated from version {{ ci.uniffi_version() }} " +
+            "but the library is version \(info.uniffiVersion), and these functions don't match: " +
+            "\(mismatched.joined(separator: ", ")). Try cleaning and rebuilding your project"
+        )
     }
 }
@@ -13,7 +13,7 @@ use std::ffi::OsStr;
 use std::fs::{read_to_string, File};
 use std::io::Write;
 use std::process::{Command, Stdio};
-use uniffi_testing::UniFFITestHelper;
+use uniffi_testing::{TestEnvironment, UniFFITestHelper, XcTest};
 
 /// Run Swift tests for a UniFFI test fixture
 pub fn run_test(tmp_dir: &str, fixture_name: &str, script_file: &str) -> Result<()> {
@@ -78,6 +78,35 @@ pub fn run_script(
     Ok(())
 }
 
+/// Run the XCTest tests of a SwiftPM package for a UniFFI test fixture
+///
+/// The bindings are compiled into a module that the package's test targets can import, and the
+/// test run fails if any test fails.
+pub fn run_xctest(tmp_dir: &str, fixture_name: &str, package_dir: &str) -> Result<()> {
+    let package_dir = Utf8Path::new(".").join(package_dir).canonicalize_utf8()?;
+    let test_helper = UniFFITestHelper::new(fixture_name)?;
+    let out_dir = test_helper.create_out_dir(tmp_dir, &package_dir)?;
+    let cdylib_path = test_helper.copy_cdylib_to_out_dir(&out_dir)?;
+    let generated_sources = GeneratedSources::new(fixture_name, &cdylib_path, &out_dir)?;
+    compile_swift_module(
+        &out_dir,
+        &generated_sources.main_module,
+        &generated_sources.generated_swift_files,
+        &generated_sources.module_map,
+        &RunScriptOptions::default(),
+    )?;
+
+    let mut env = TestEnvironment::new(&out_dir);
+    env.compiler_args = vec![
+        "-Xcc".to_string(),
+        format!("-fmodule-map-file={}", generated_sources.module_map),
+    ];
+    env.compiler_args.extend(calc_library_args(&out_dir)?);
+    test_helper
+        .run_test_suite(&XcTest::new(package_dir), &env)?
+        .ensure_passed()
+}
+
 fn compile_swift_module<T: AsRef<OsStr>>(
     out_dir: &Utf8Path,
     module_name: &str,
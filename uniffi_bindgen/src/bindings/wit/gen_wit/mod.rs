@@ -0,0 +1,313 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use anyhow::{bail, Context, Result};
+use askama::Template;
+use heck::ToKebabCase;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::interface::*;
+use crate::BindingsConfig;
+
+// Keywords from the WIT grammar.  Identifiers that match one of these need to be escaped with a
+// leading `%`.
+const RESERVED_WORDS: &[&str] = &[
+    "as",
+    "bool",
+    "borrow",
+    "char",
+    "constructor",
+    "enum",
+    "export",
+    "f32",
+    "f64",
+    "flags",
+    "float32",
+    "float64",
+    "from",
+    "func",
+    "future",
+    "import",
+    "include",
+    "interface",
+    "list",
+    "option",
+    "own",
+    "package",
+    "record",
+    "resource",
+    "result",
+    "s16",
+    "s32",
+    "s64",
+    "s8",
+    "static",
+    "stream",
+    "string",
+    "tuple",
+    "type",
+    "u16",
+    "u32",
+    "u64",
+    "u8",
+    "use",
+    "variant",
+    "with",
+    "world",
+];
+
+fn is_reserved_word(word: &str) -> bool {
+    RESERVED_WORDS.contains(&word)
+}
+
+// Some config options for the WIT generator.
+// Note that this can only be used to control details *that do not affect the underlying component*,
+// since the details of the underlying component are entirely determined by the `ComponentInterface`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Config {
+    package_namespace: Option<String>,
+}
+
+impl Config {
+    /// The namespace part of the WIT package name (`{package_namespace}:{namespace}`).
+    pub fn package_namespace(&self) -> String {
+        self.package_namespace
+            .clone()
+            .unwrap_or_else(|| "uniffi".to_string())
+    }
+}
+
+impl BindingsConfig for Config {
+    const TOML_KEY: &'static str = "wit";
+
+    fn update_from_ci(&mut self, _ci: &ComponentInterface) {}
+
+    fn update_from_cdylib_name(&mut self, _cdylib_name: &str) {}
+
+    fn update_from_dependency_configs(&mut self, _config_map: HashMap<&str, &Self>) {}
+}
+
+// Generate the WIT package for the given ComponentInterface, as a string.
+pub fn generate_wit(config: &Config, ci: &ComponentInterface) -> Result<String> {
+    check_supported(ci)?;
+    WitWrapper::new(config.clone(), ci)
+        .render()
+        .context("failed to render WIT package")
+}
+
+// The component model has no equivalent for callbacks into the foreign code or for our async
+// functions, so we can't generate anything meaningful for interfaces that use them.
+fn check_supported(ci: &ComponentInterface) -> Result<()> {
+    if let Some(cbi) = ci.callback_interface_definitions().first() {
+        bail!(
+            "callback interface `{}`: callback interfaces are not supported by the WIT generator",
+            cbi.name()
+        );
+    }
+    if let Some(f) = ci.iter_ffi_function_definitions().find(|f| f.is_async()) {
+        bail!(
+            "`{}`: async functions are not supported by the WIT generator",
+            f.name()
+        );
+    }
+    if ci.iter_types().any(|t| matches!(t, Type::ForeignExecutor)) {
+        bail!("`ForeignExecutor` is not supported by the WIT generator");
+    }
+    if ci.iter_types().any(|t| matches!(t, Type::Weak { .. })) {
+        bail!("`Weak` references are not supported by the WIT generator");
+    }
+    Ok(())
+}
+
+#[derive(Template)]
+#[template(syntax = "wit", escape = "none", path = "wrapper.wit")]
+pub struct WitWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+
+impl<'a> WitWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        Self { config, ci }
+    }
+
+    fn uses_timestamps(&self) -> bool {
+        self.ci.iter_types().any(|t| matches!(t, Type::Timestamp))
+    }
+
+    fn uses_durations(&self) -> bool {
+        self.ci.iter_types().any(|t| matches!(t, Type::Duration))
+    }
+}
+
+pub mod filters {
+    use super::*;
+
+    /// Convert a UniFFI name into a WIT identifier
+    pub fn wit_name(nm: &str) -> Result<String, askama::Error> {
+        let nm = nm.to_kebab_case();
+        let prefix = if is_reserved_word(&nm) { "%" } else { "" };
+        Ok(format!("{prefix}{nm}"))
+    }
+
+    /// Name of the record that holds the fields of an enum variant
+    pub fn variant_record_name(
+        variant: &Variant,
+        enum_name: &str,
+    ) -> Result<String, askama::Error> {
+        wit_name(&format!("{enum_name}-{}", variant.name()))
+    }
+
+    pub fn type_wit(as_type: &impl AsType) -> Result<String, askama::Error> {
+        Ok(wit_type_label(&as_type.as_type()))
+    }
+
+    /// Type of a function argument.  Objects are borrowed for the duration of the call, like
+    /// UniFFI passes them: the caller keeps its handle and Rust clones the `Arc` if it needs one.
+    pub fn arg_type_wit(arg: &Argument) -> Result<String, askama::Error> {
+        Ok(match arg.as_type() {
+            Type::Object { name, .. } => format!("borrow<{}>", wit_name(&name)?),
+            type_ => wit_type_label(&type_),
+        })
+    }
+
+    /// The ` -> ...` part of a function signature, if needed
+    pub fn return_wit(callable: &impl Callable) -> Result<String, askama::Error> {
+        let return_type = callable.return_type().map(|t| wit_type_label(&t));
+        let throws_type = callable.throws_type().map(|t| wit_type_label(&t));
+        Ok(match (return_type, throws_type) {
+            (None, None) => "".to_string(),
+            (Some(ret), None) => format!(" -> {ret}"),
+            (None, Some(err)) => format!(" -> result<_, {err}>"),
+            (Some(ret), Some(err)) => format!(" -> result<{ret}, {err}>"),
+        })
+    }
+
+    fn wit_type_label(type_: &Type) -> String {
+        match type_ {
+            Type::UInt8 => "u8".into(),
+            Type::Int8 => "s8".into(),
+            Type::UInt16 => "u16".into(),
+            Type::Int16 => "s16".into(),
+            Type::UInt32 => "u32".into(),
+            Type::Int32 => "s32".into(),
+            Type::UInt64 => "u64".into(),
+            Type::Int64 => "s64".into(),
+            Type::Float32 => "f32".into(),
+            Type::Float64 => "f64".into(),
+            Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
+            // UUIDs, decimals, JSON values and network addresses are written in their string form,
+            // WIT doesn't have types for them.
+            Type::String
+            | Type::Url
+            | Type::Uuid
+            | Type::Decimal
+            | Type::Json
+            | Type::IpAddr
+            | Type::SocketAddr => "string".into(),
+            // Paths are written as their raw bytes, which aren't necessarily valid UTF-8.
+            Type::Bytes | Type::Path => "list<u8>".into(),
+            // These are records that we define in the interface, see `wrapper.wit`.
+            Type::Timestamp => "timestamp".into(),
+            Type::Duration => "duration".into(),
+            Type::Object { name, .. }
+            | Type::Record { name, .. }
+            | Type::Enum { name, .. }
+            | Type::Flags { name, .. }
+            | Type::External { name, .. } => wit_name(name).unwrap(),
+            // Custom types are passed as their builtin type.
+            Type::Custom { builtin, .. } => wit_type_label(builtin),
+            Type::Optional { inner_type } => format!("option<{}>", wit_type_label(inner_type)),
+            Type::Sequence { inner_type } => format!("list<{}>", wit_type_label(inner_type)),
+            // Compression and shared buffers are implementation details of the FFI.
+            Type::Compressed { inner_type } | Type::Shared { inner_type } => {
+                wit_type_label(inner_type)
+            }
+            Type::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "list<tuple<{}, {}>>",
+                wit_type_label(key_type),
+                wit_type_label(value_type)
+            ),
+            // Rejected by `check_supported()`
+            Type::CallbackInterface { .. } | Type::ForeignExecutor | Type::Weak { .. } => {
+                unreachable!("{type_:?} is not supported by the WIT generator")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wit_names() {
+        assert_eq!(filters::wit_name("MyRecord").unwrap(), "my-record");
+        assert_eq!(filters::wit_name("num_foos").unwrap(), "num-foos");
+        assert_eq!(filters::wit_name("type").unwrap(), "%type");
+    }
+
+    #[test]
+    fn test_generate_wit() {
+        const UDL: &str = r#"
+            namespace example {
+                u32 add(u32 a, u32 b);
+                [Throws=ArithmeticError]
+                u64 checked_sub(u64 a, u64 b);
+            };
+            [Error]
+            enum ArithmeticError { "IntegerOverflow" };
+            dictionary Point {
+                double x;
+                double y;
+                sequence<string>? labels;
+            };
+            [Enum]
+            interface Shape {
+                Circle(Point center, double radius);
+                Empty();
+            };
+            interface Canvas {
+                constructor();
+                [Name=with_size]
+                constructor(u32 width, u32 height);
+                void draw(Shape shape);
+                record<string, Point> points();
+                Canvas merge(Canvas other);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let wit = generate_wit(&Config::default(), &ci).unwrap();
+        assert!(wit.contains("package uniffi:example;"));
+        assert!(wit.contains("record point {"));
+        assert!(wit.contains("labels: option<list<string>>,"));
+        assert!(wit.contains("enum arithmetic-error {"));
+        assert!(wit.contains("record shape-circle {"));
+        assert!(wit.contains("circle(shape-circle),"));
+        assert!(wit.contains("resource canvas {"));
+        assert!(wit.contains("with-size: static func(width: u32, height: u32) -> canvas;"));
+        assert!(wit.contains("points: func() -> list<tuple<string, point>>;"));
+        assert!(wit.contains("merge: func(other: borrow<canvas>) -> canvas;"));
+        assert!(wit.contains("add: func(a: u32, b: u32) -> u32;"));
+        assert!(wit.contains("checked-sub: func(a: u64, b: u64) -> result<u64, arithmetic-error>;"));
+        assert!(wit.contains("export example;"));
+    }
+
+    #[test]
+    fn test_callback_interfaces_are_rejected() {
+        const UDL: &str = r#"
+            namespace example {};
+            callback interface Logger {
+                void log(string message);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(generate_wit(&Config::default(), &ci).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a WIT world for the WebAssembly component model.
+//!
+//! Rather than bindings for a foreign language, this backend renders the component interface as a
+//! [WIT](https://component-model.bytecodealliance.org/design/wit.html) package, containing an
+//! interface with all the exported items and a world that exports it.  Tools like `wit-bindgen`
+//! can then be used to build a WebAssembly component from the crate, that can be hosted by a
+//! component-model runtime like wasmtime.
+
+use anyhow::Result;
+use camino::Utf8Path;
+use fs_err as fs;
+
+pub mod gen_wit;
+use super::super::interface::ComponentInterface;
+pub use gen_wit::{generate_wit, Config};
+
+// Generate the WIT package for the given ComponentInterface, in the given output directory.
+pub fn write_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Utf8Path,
+    _try_format_code: bool,
+) -> Result<()> {
+    let wit_file = out_dir.join(format!("{}.wit", ci.namespace()));
+    fs::write(wit_file, generate_wit(config, ci)?)?;
+    Ok(())
+}
@@ -0,0 +1,518 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Assemble the libraries built for several targets, plus the generated bindings, into the
+//! layouts that are shipped to SDK consumers.
+//!
+//! This is the step between `cargo build --target ...` and a distributable artifact:
+//!
+//!   - Android libraries go into a `jniLibs/<abi>/` tree, next to the Kotlin bindings.
+//!   - Apple libraries go into an `.xcframework`, with one slice per platform.  Slices with
+//!     several architectures (e.g. the iOS simulator or a universal macOS library) are merged
+//!     with `lipo`, so that part only works on macOS.
+//!   - Desktop libraries (Linux, Windows and macOS) are laid out as wheel contents, next to the
+//!     Python bindings.
+//!
+//! A `manifest.json` file lists every file that was written, along with the target it was
+//! built for.
+
+use crate::Result;
+use anyhow::{bail, Context};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use serde::Serialize;
+use std::{collections::BTreeMap, process::Command, str::FromStr};
+
+/// A library built for a specific target, parsed from `<target-triple>=<path>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetLibrary {
+    pub target: String,
+    pub path: Utf8PathBuf,
+}
+
+impl FromStr for TargetLibrary {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('=') {
+            Some((target, path)) if !target.is_empty() && !path.is_empty() => Ok(Self {
+                target: target.to_string(),
+                path: path.into(),
+            }),
+            _ => bail!("expected `<target-triple>=<path>`, got `{s}`"),
+        }
+    }
+}
+
+/// The kind of distributable a target's library ends up in
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Platform {
+    Android {
+        abi: &'static str,
+    },
+    Apple {
+        slice: AppleSlice,
+        arch: &'static str,
+    },
+    Desktop,
+}
+
+/// An xcframework slice.  The variant order is the order slices are listed in `Info.plist`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum AppleSlice {
+    Ios,
+    IosSimulator,
+    MacCatalyst,
+    MacOs,
+}
+
+impl AppleSlice {
+    fn platform(&self) -> &'static str {
+        match self {
+            Self::Ios | Self::IosSimulator | Self::MacCatalyst => "ios",
+            Self::MacOs => "macos",
+        }
+    }
+
+    fn variant(&self) -> Option<&'static str> {
+        match self {
+            Self::IosSimulator => Some("simulator"),
+            Self::MacCatalyst => Some("maccatalyst"),
+            Self::Ios | Self::MacOs => None,
+        }
+    }
+
+    fn library_identifier(&self, archs: &[&str]) -> String {
+        let mut id = format!("{}-{}", self.platform(), archs.join("_"));
+        if let Some(variant) = self.variant() {
+            id.push('-');
+            id.push_str(variant);
+        }
+        id
+    }
+}
+
+fn classify_target(target: &str) -> Result<Platform> {
+    let arch = target.split('-').next().unwrap_or_default();
+    if target.contains("-linux-android") {
+        let abi = match arch {
+            "aarch64" => "arm64-v8a",
+            "armv7" | "thumbv7neon" => "armeabi-v7a",
+            "i686" => "x86",
+            "x86_64" => "x86_64",
+            _ => bail!("unsupported Android architecture in `{target}`"),
+        };
+        return Ok(Platform::Android { abi });
+    }
+    if target.contains("-apple-") {
+        let arch = match arch {
+            "aarch64" | "arm64" => "arm64",
+            "x86_64" => "x86_64",
+            _ => bail!("unsupported Apple architecture in `{target}`"),
+        };
+        let slice = if target.ends_with("-apple-darwin") {
+            AppleSlice::MacOs
+        } else if target.ends_with("-ios-macabi") {
+            AppleSlice::MacCatalyst
+        } else if target.ends_with("-ios-sim") || target == "x86_64-apple-ios" {
+            AppleSlice::IosSimulator
+        } else if target.ends_with("-apple-ios") {
+            AppleSlice::Ios
+        } else {
+            bail!("unsupported Apple target `{target}`")
+        };
+        return Ok(Platform::Apple { slice, arch });
+    }
+    if target.contains("-linux-") || target.contains("-windows-") {
+        return Ok(Platform::Desktop);
+    }
+    bail!("don't know how to bundle a library for `{target}`")
+}
+
+/// The `jniLibs` ABI directory of an Android target, or `None` for other targets
+pub(crate) fn android_abi(target: &str) -> Option<&'static str> {
+    match classify_target(target) {
+        Ok(Platform::Android { abi }) => Some(abi),
+        _ => None,
+    }
+}
+
+/// The xcframework platform (`ios` or `macos`) of an Apple target, or `None` for other targets
+pub(crate) fn apple_platform(target: &str) -> Option<&'static str> {
+    match classify_target(target) {
+        Ok(Platform::Apple { slice, .. }) => Some(slice.platform()),
+        _ => None,
+    }
+}
+
+/// Describes the files written by [assemble_bundle]
+#[derive(Debug, Default, Serialize)]
+pub struct BundleManifest {
+    pub name: String,
+    pub files: Vec<BundleFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleFile {
+    /// Path relative to the output directory
+    pub path: Utf8PathBuf,
+    /// Target triple the file was built for, or `None` for platform-independent files
+    pub target: Option<String>,
+}
+
+/// Assemble the distributable layouts for the libraries in `libraries` and the bindings in
+/// `bindings_dir`, writing them to `out_dir`.
+pub fn assemble_bundle(
+    name: &str,
+    libraries: &[TargetLibrary],
+    bindings_dir: &Utf8Path,
+    out_dir: &Utf8Path,
+) -> Result<BundleManifest> {
+    if libraries.is_empty() {
+        bail!("no libraries to bundle");
+    }
+    let bindings = list_files(bindings_dir)?;
+    let mut bundle = Bundle {
+        out_dir,
+        manifest: BundleManifest {
+            name: name.to_string(),
+            ..BundleManifest::default()
+        },
+    };
+
+    let mut android = Vec::new();
+    let mut apple: BTreeMap<AppleSlice, Vec<(&'static str, &TargetLibrary)>> = BTreeMap::new();
+    let mut desktop = Vec::new();
+    for lib in libraries {
+        match classify_target(&lib.target)? {
+            Platform::Android { abi } => android.push((abi, lib)),
+            Platform::Apple { slice, arch } => apple.entry(slice).or_default().push((arch, lib)),
+            Platform::Desktop => desktop.push(lib),
+        }
+    }
+    // macOS libraries are used both in the xcframework and by Python
+    desktop.extend(
+        apple
+            .get(&AppleSlice::MacOs)
+            .into_iter()
+            .flatten()
+            .map(|(_, lib)| *lib),
+    );
+
+    if !android.is_empty() {
+        for (abi, lib) in android {
+            let dest = Utf8PathBuf::from(format!("android/jniLibs/{abi}"))
+                .join(library_file_name(&lib.path)?);
+            bundle.copy(&lib.path, &dest, Some(lib.target.as_str()))?;
+        }
+        bundle.copy_bindings(&bindings, bindings_dir, "android/kotlin", &["kt"])?;
+    }
+
+    if !apple.is_empty() {
+        bundle.write_xcframework(name, &apple, &bindings, bindings_dir)?;
+        bundle.copy_bindings(&bindings, bindings_dir, "apple/swift", &["swift"])?;
+    }
+
+    for lib in desktop {
+        let dir = Utf8PathBuf::from(format!("python/{}", lib.target));
+        bundle.copy(
+            &lib.path,
+            &dir.join(library_file_name(&lib.path)?),
+            Some(lib.target.as_str()),
+        )?;
+        bundle.copy_bindings(&bindings, bindings_dir, dir.as_str(), &["py"])?;
+    }
+
+    let manifest_json = serde_json::to_string_pretty(&bundle.manifest)?;
+    fs::write(out_dir.join("manifest.json"), manifest_json)?;
+    Ok(bundle.manifest)
+}
+
+struct Bundle<'a> {
+    out_dir: &'a Utf8Path,
+    manifest: BundleManifest,
+}
+
+impl Bundle<'_> {
+    /// Copy `src` to `dest` (relative to the output directory) and record it in the manifest
+    fn copy(&mut self, src: &Utf8Path, dest: &Utf8Path, target: Option<&str>) -> Result<()> {
+        let full_dest = self.out_dir.join(dest);
+        if let Some(parent) = full_dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(src, &full_dest)?;
+        self.record(dest, target);
+        Ok(())
+    }
+
+    fn record(&mut self, path: &Utf8Path, target: Option<&str>) {
+        self.manifest.files.push(BundleFile {
+            path: path.to_owned(),
+            target: target.map(ToString::to_string),
+        });
+    }
+
+    /// Copy the bindings files with one of `extensions` into `dest_dir`, keeping their path
+    /// relative to `bindings_dir`
+    fn copy_bindings(
+        &mut self,
+        bindings: &[Utf8PathBuf],
+        bindings_dir: &Utf8Path,
+        dest_dir: &str,
+        extensions: &[&str],
+    ) -> Result<()> {
+        for path in bindings_with_extension(bindings, extensions) {
+            let relative = path.strip_prefix(bindings_dir)?;
+            self.copy(path, &Utf8Path::new(dest_dir).join(relative), None)?;
+        }
+        Ok(())
+    }
+
+    fn write_xcframework(
+        &mut self,
+        name: &str,
+        slices: &BTreeMap<AppleSlice, Vec<(&'static str, &TargetLibrary)>>,
+        bindings: &[Utf8PathBuf],
+        bindings_dir: &Utf8Path,
+    ) -> Result<()> {
+        let xcframework_dir = Utf8PathBuf::from(format!("apple/{name}.xcframework"));
+        let headers: Vec<_> = bindings_with_extension(bindings, &["h"]).collect();
+        let modulemaps: Vec<_> = bindings_with_extension(bindings, &["modulemap"]).collect();
+        if modulemaps.len() > 1 {
+            bail!("found more than one modulemap in {bindings_dir}");
+        }
+
+        let mut plist_entries = Vec::new();
+        for (slice, libs) in slices {
+            let archs: Vec<_> = libs.iter().map(|(arch, _)| *arch).collect();
+            let identifier = slice.library_identifier(&archs);
+            let slice_dir = xcframework_dir.join(&identifier);
+            let library_name = library_file_name(&libs[0].1.path)?;
+            let dest = slice_dir.join(library_name);
+            let targets = libs
+                .iter()
+                .map(|(_, lib)| lib.target.as_str())
+                .collect::<Vec<_>>()
+                .join(",");
+            if libs.len() == 1 {
+                self.copy(&libs[0].1.path, &dest, Some(targets.as_str()))?;
+            } else {
+                self.lipo(libs.iter().map(|(_, lib)| lib.path.as_path()), &dest)?;
+                self.record(&dest, Some(targets.as_str()));
+            }
+
+            for header in &headers {
+                self.copy(
+                    header,
+                    &slice_dir.join("Headers").join(header.file_name().unwrap()),
+                    None,
+                )?;
+            }
+            // Xcode expects the modulemap to be named `module.modulemap`
+            if let Some(modulemap) = modulemaps.first() {
+                self.copy(
+                    modulemap,
+                    &slice_dir.join("Headers").join("module.modulemap"),
+                    None,
+                )?;
+            }
+            plist_entries.push(xcframework_plist_entry(
+                &identifier,
+                library_name,
+                *slice,
+                &archs,
+            ));
+        }
+
+        let plist_path = xcframework_dir.join("Info.plist");
+        fs::write(
+            self.out_dir.join(&plist_path),
+            xcframework_plist(&plist_entries),
+        )?;
+        self.record(&plist_path, None);
+        Ok(())
+    }
+
+    /// Merge several single-architecture libraries into `dest` with `lipo`
+    fn lipo<'b>(
+        &self,
+        libraries: impl Iterator<Item = &'b Utf8Path>,
+        dest: &Utf8Path,
+    ) -> Result<()> {
+        let full_dest = self.out_dir.join(dest);
+        if let Some(parent) = full_dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let status = Command::new("lipo")
+            .arg("-create")
+            .args(libraries)
+            .arg("-output")
+            .arg(&full_dest)
+            .status()
+            .context(
+                "error running lipo, which is needed to merge architectures (is this macOS?)",
+            )?;
+        if !status.success() {
+            bail!("lipo failed to create {full_dest}");
+        }
+        Ok(())
+    }
+}
+
+fn library_file_name(path: &Utf8Path) -> Result<&str> {
+    path.file_name()
+        .with_context(|| format!("{path} is not a library file"))
+}
+
+fn bindings_with_extension<'a>(
+    bindings: &'a [Utf8PathBuf],
+    extensions: &'a [&str],
+) -> impl Iterator<Item = &'a Utf8PathBuf> {
+    bindings
+        .iter()
+        .filter(|p| matches!(p.extension(), Some(ext) if extensions.contains(&ext)))
+}
+
+/// Recursively list the files in `dir`, sorted so the output is deterministic
+fn list_files(dir: &Utf8Path) -> Result<Vec<Utf8PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = Utf8PathBuf::try_from(entry?.path())?;
+        if path.is_dir() {
+            files.extend(list_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn xcframework_plist_entry(
+    identifier: &str,
+    library_name: &str,
+    slice: AppleSlice,
+    archs: &[&str],
+) -> String {
+    let archs = archs
+        .iter()
+        .map(|a| format!("\t\t\t\t<string>{a}</string>\n"))
+        .collect::<String>();
+    let variant = slice
+        .variant()
+        .map(|v| format!("\t\t\t<key>SupportedPlatformVariant</key>\n\t\t\t<string>{v}</string>\n"))
+        .unwrap_or_default();
+    format!(
+        "\t\t<dict>\n\
+         \t\t\t<key>HeadersPath</key>\n\
+         \t\t\t<string>Headers</string>\n\
+         \t\t\t<key>LibraryIdentifier</key>\n\
+         \t\t\t<string>{identifier}</string>\n\
+         \t\t\t<key>LibraryPath</key>\n\
+         \t\t\t<string>{library_name}</string>\n\
+         \t\t\t<key>SupportedArchitectures</key>\n\
+         \t\t\t<array>\n\
+         {archs}\
+         \t\t\t</array>\n\
+         \t\t\t<key>SupportedPlatform</key>\n\
+         \t\t\t<string>{platform}</string>\n\
+         {variant}\
+         \t\t</dict>\n",
+        platform = slice.platform(),
+    )
+}
+
+fn xcframework_plist(entries: &[String]) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \t<key>AvailableLibraries</key>\n\
+         \t<array>\n\
+         {}\
+         \t</array>\n\
+         \t<key>CFBundlePackageType</key>\n\
+         \t<string>XFWK</string>\n\
+         \t<key>XCFrameworkFormatVersion</key>\n\
+         \t<string>1.0</string>\n\
+         </dict>\n\
+         </plist>\n",
+        entries.concat()
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_target_library() {
+        assert_eq!(
+            "aarch64-linux-android=target/libfoo.so"
+                .parse::<TargetLibrary>()
+                .unwrap(),
+            TargetLibrary {
+                target: "aarch64-linux-android".to_string(),
+                path: "target/libfoo.so".into(),
+            }
+        );
+        assert!("target/libfoo.so".parse::<TargetLibrary>().is_err());
+        assert!("=target/libfoo.so".parse::<TargetLibrary>().is_err());
+    }
+
+    #[test]
+    fn classify_targets() {
+        assert_eq!(
+            classify_target("armv7-linux-androideabi").unwrap(),
+            Platform::Android { abi: "armeabi-v7a" }
+        );
+        assert_eq!(
+            classify_target("aarch64-apple-ios").unwrap(),
+            Platform::Apple {
+                slice: AppleSlice::Ios,
+                arch: "arm64"
+            }
+        );
+        assert_eq!(
+            classify_target("aarch64-apple-ios-sim").unwrap(),
+            Platform::Apple {
+                slice: AppleSlice::IosSimulator,
+                arch: "arm64"
+            }
+        );
+        assert_eq!(
+            classify_target("x86_64-apple-ios").unwrap(),
+            Platform::Apple {
+                slice: AppleSlice::IosSimulator,
+                arch: "x86_64"
+            }
+        );
+        assert_eq!(
+            classify_target("x86_64-apple-darwin").unwrap(),
+            Platform::Apple {
+                slice: AppleSlice::MacOs,
+                arch: "x86_64"
+            }
+        );
+        assert_eq!(
+            classify_target("x86_64-pc-windows-msvc").unwrap(),
+            Platform::Desktop
+        );
+        assert!(classify_target("wasm32-unknown-unknown").is_err());
+    }
+
+    #[test]
+    fn library_identifiers() {
+        assert_eq!(
+            AppleSlice::IosSimulator.library_identifier(&["arm64", "x86_64"]),
+            "ios-arm64_x86_64-simulator"
+        );
+        assert_eq!(AppleSlice::Ios.library_identifier(&["arm64"]), "ios-arm64");
+        assert_eq!(
+            AppleSlice::MacOs.library_identifier(&["arm64", "x86_64"]),
+            "macos-arm64_x86_64"
+        );
+    }
+}
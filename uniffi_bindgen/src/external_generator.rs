@@ -0,0 +1,1005 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # External generators
+//!
+//! Bindings generators for other languages don't have to live in this crate, or even be written
+//! in Rust.  `uniffi-bindgen generate --external-generator <program>` runs `program` once for each
+//! component, and writes a [GeneratorRequest] to its stdin as JSON.  The request has everything a
+//! generator needs to write the bindings: the component model, the `[bindings]` table of
+//! `uniffi.toml` and the directory to write the files to.
+//!
+//! The component model is a description of the interface that's kept separate from
+//! [ComponentInterface], so that `ComponentInterface` can keep changing without breaking
+//! generators.  The format is described in the manual, any incompatible change to it bumps
+//! [PROTOCOL_VERSION].
+
+use crate::{
+    interface::{
+        Argument, AsType, Callable, CallbackInterface, ComponentInterface, Constant, Constructor,
+        Enum, ExternalKind, FfiFunction, FfiType, Field, Flags, Function, Literal, Method, Object,
+        ObjectImpl, Radix, Record, TraitConstant, Type, UniffiTrait,
+    },
+    library_mode::{calc_cdylib_name, Source},
+    Result,
+};
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use serde::Serialize;
+use std::process::{Command, Stdio};
+
+/// Version of the protocol, stored in the `protocol_version` field of each request
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What an external generator is asked to generate
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratorRequest {
+    pub protocol_version: u32,
+    /// Directory in which to write the bindings
+    pub out_dir: String,
+    /// Crate the component comes from, only known in library mode
+    pub crate_name: Option<String>,
+    /// Name of the cdylib the bindings should load, when it's known
+    pub cdylib_name: Option<String>,
+    /// The `[bindings]` table of the crate's `uniffi.toml`, including the tables for languages
+    /// this crate doesn't know about.  `null` when there's no config.
+    pub config: Option<toml::Value>,
+    pub component: ComponentModel,
+}
+
+/// The components of a UDL file or a library, as printed by `uniffi-bindgen print-metadata`
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentMetadata {
+    pub protocol_version: u32,
+    pub components: Vec<ComponentModel>,
+}
+
+impl ComponentMetadata {
+    pub fn new<'a>(components: impl IntoIterator<Item = &'a ComponentInterface>) -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            components: components.into_iter().map(ComponentModel::new).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentModel {
+    pub namespace: String,
+    pub uniffi_contract_version: u32,
+    /// Scaffolding function that returns the contract version
+    pub contract_version_function: String,
+    /// Scaffolding function that returns the UniFFI version and the contract version of the
+    /// library, as a `RustBuffer` with a string and a `u32`
+    pub contract_info_function: String,
+    /// Scaffolding function that returns the live handles report as a `RustBuffer` string
+    pub debug_live_handles_function: String,
+    pub rustbuffer_functions: RustBufferFunctions,
+    /// Checksum functions, with the value each one should return
+    pub checksums: Vec<ChecksumModel>,
+    /// Every type used by the component, including the types nested in other types
+    pub types: Vec<TypeModel>,
+    pub functions: Vec<CallableModel>,
+    pub constants: Vec<ConstantModel>,
+    pub objects: Vec<ObjectModel>,
+    pub records: Vec<RecordModel>,
+    pub enums: Vec<EnumModel>,
+    pub flags: Vec<FlagsModel>,
+    pub callback_interfaces: Vec<CallbackInterfaceModel>,
+    /// Every scaffolding function, with its C signature
+    pub ffi_functions: Vec<FfiFunctionModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RustBufferFunctions {
+    pub alloc: String,
+    pub from_bytes: String,
+    pub free: String,
+    pub reserve: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecksumModel {
+    pub function: String,
+    pub checksum: u16,
+}
+
+/// A function, method or constructor
+#[derive(Debug, Clone, Serialize)]
+pub struct CallableModel {
+    pub name: String,
+    pub arguments: Vec<ArgumentModel>,
+    pub return_type: Option<TypeModel>,
+    pub throws_type: Option<TypeModel>,
+    pub is_async: bool,
+    /// Scaffolding function that implements it
+    pub ffi_function: String,
+    /// Callback interface methods with a default implementation, which the foreign
+    /// implementations can call through `ffi_function`
+    pub has_default: bool,
+    /// Methods that consume the object: the foreign code passes its pointer to Rust, which takes
+    /// ownership of it, so the object can't be used or freed after the call
+    pub takes_self_by_value: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArgumentModel {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeModel,
+    pub by_ref: bool,
+    pub default_value: Option<LiteralModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ObjectModel {
+    pub name: String,
+    /// `true` for trait interfaces, `false` for structs
+    pub is_trait: bool,
+    pub primary_constructor: Option<CallableModel>,
+    pub alternate_constructors: Vec<CallableModel>,
+    pub methods: Vec<CallableModel>,
+    /// Rust traits the object exposes, e.g. `Display` or `Eq`
+    pub traits: Vec<TraitModel>,
+    pub constants: Vec<ConstantModel>,
+    /// Scaffolding function that frees a handle
+    pub free_function: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraitModel {
+    #[serde(rename = "trait")]
+    pub trait_: String,
+    /// The trait's methods, e.g. `eq` and `ne` for `Eq`.  For `Restore`, `restore` is a function
+    /// rather than a method.
+    pub methods: Vec<CallableModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConstantModel {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeModel,
+    pub value: LiteralModel,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordModel {
+    pub name: String,
+    pub fields: Vec<FieldModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldModel {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: TypeModel,
+    pub default_value: Option<LiteralModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumModel {
+    pub name: String,
+    /// `true` if the enum is thrown by a function or method
+    pub is_error: bool,
+    /// `true` if the variants are passed without their fields, as for most errors
+    pub is_flat: bool,
+    /// `true` for `#[non_exhaustive]` enums.  Rust writes the size of the fields after the variant
+    /// index, and variants the bindings don't know are lifted to an `Unknown` variant.
+    pub is_non_exhaustive: bool,
+    pub variants: Vec<VariantModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantModel {
+    pub name: String,
+    /// The discriminant, if the enum has explicit discriminants
+    pub discr: Option<i64>,
+    pub fields: Vec<FieldModel>,
+}
+
+/// A set of bit flags, passed as a `u32`
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagsModel {
+    pub name: String,
+    pub flags: Vec<FlagModel>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FlagModel {
+    pub name: String,
+    pub value: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CallbackInterfaceModel {
+    pub name: String,
+    pub methods: Vec<CallableModel>,
+    pub constants: Vec<ConstantModel>,
+    /// The foreign implementation must be called on the main thread
+    pub main_thread: bool,
+    /// The type of a closure argument, presented as a function type with a single `invoke` method
+    pub closure: bool,
+    /// Scaffolding function that registers the foreign callback
+    pub init_function: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FfiFunctionModel {
+    pub name: String,
+    pub arguments: Vec<FfiArgumentModel>,
+    pub return_type: Option<FfiTypeModel>,
+    /// `true` if the function takes a trailing `RustCallStatus*` argument
+    pub has_rust_call_status_arg: bool,
+    pub is_async: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FfiArgumentModel {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: FfiTypeModel,
+}
+
+/// The C type of a scaffolding argument or return value, see [FfiType]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FfiTypeModel {
+    #[serde(rename = "uint8")]
+    UInt8,
+    Int8,
+    #[serde(rename = "uint16")]
+    UInt16,
+    Int16,
+    #[serde(rename = "uint32")]
+    UInt32,
+    Int32,
+    #[serde(rename = "uint64")]
+    UInt64,
+    Int64,
+    Float32,
+    Float64,
+    RustArcPtr {
+        object: String,
+    },
+    RustBuffer {
+        external_type: Option<String>,
+    },
+    ForeignBytes,
+    ForeignCallback,
+    ForeignExecutorHandle,
+    ForeignExecutorCallback,
+    FutureCallback {
+        return_type: Box<FfiTypeModel>,
+    },
+    FutureCallbackData,
+    Struct {
+        record: String,
+    },
+}
+
+impl From<&FfiType> for FfiTypeModel {
+    fn from(ffi_type: &FfiType) -> Self {
+        match ffi_type {
+            FfiType::UInt8 => Self::UInt8,
+            FfiType::Int8 => Self::Int8,
+            FfiType::UInt16 => Self::UInt16,
+            FfiType::Int16 => Self::Int16,
+            FfiType::UInt32 => Self::UInt32,
+            FfiType::Int32 => Self::Int32,
+            FfiType::UInt64 => Self::UInt64,
+            FfiType::Int64 => Self::Int64,
+            FfiType::Float32 => Self::Float32,
+            FfiType::Float64 => Self::Float64,
+            FfiType::RustArcPtr(object) => Self::RustArcPtr {
+                object: object.clone(),
+            },
+            FfiType::RustBuffer(external_type) => Self::RustBuffer {
+                external_type: external_type.clone(),
+            },
+            FfiType::ForeignBytes => Self::ForeignBytes,
+            FfiType::ForeignCallback => Self::ForeignCallback,
+            FfiType::ForeignExecutorHandle => Self::ForeignExecutorHandle,
+            FfiType::ForeignExecutorCallback => Self::ForeignExecutorCallback,
+            FfiType::FutureCallback { return_type } => Self::FutureCallback {
+                return_type: Box::new(return_type.as_ref().into()),
+            },
+            FfiType::FutureCallbackData => Self::FutureCallbackData,
+            FfiType::Struct(record) => Self::Struct {
+                record: record.clone(),
+            },
+        }
+    }
+}
+
+/// A type used by the component, see [Type]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TypeModel {
+    #[serde(rename = "uint8")]
+    UInt8,
+    Int8,
+    #[serde(rename = "uint16")]
+    UInt16,
+    Int16,
+    #[serde(rename = "uint32")]
+    UInt32,
+    Int32,
+    #[serde(rename = "uint64")]
+    UInt64,
+    Int64,
+    Float32,
+    Float64,
+    Boolean,
+    Char,
+    String,
+    Bytes,
+    Timestamp,
+    Duration,
+    Url,
+    Uuid,
+    Decimal,
+    Json,
+    Path,
+    IpAddr,
+    SocketAddr,
+    ForeignExecutor,
+    Object {
+        module_path: String,
+        name: String,
+        /// `true` for trait interfaces, `false` for structs
+        is_trait: bool,
+    },
+    Record {
+        module_path: String,
+        name: String,
+    },
+    Enum {
+        module_path: String,
+        name: String,
+    },
+    Flags {
+        module_path: String,
+        name: String,
+    },
+    CallbackInterface {
+        module_path: String,
+        name: String,
+    },
+    Optional {
+        inner_type: Box<TypeModel>,
+    },
+    Sequence {
+        inner_type: Box<TypeModel>,
+    },
+    Map {
+        key_type: Box<TypeModel>,
+        value_type: Box<TypeModel>,
+    },
+    /// Passed compressed, but presented as a plain `inner_type`
+    Compressed {
+        inner_type: Box<TypeModel>,
+    },
+    /// Passed in shared memory when it's large, but presented as a plain `inner_type`
+    Shared {
+        inner_type: Box<TypeModel>,
+    },
+    /// A reference to an object that doesn't keep it alive
+    Weak {
+        inner_type: Box<TypeModel>,
+    },
+    /// A type defined by another crate
+    External {
+        module_path: String,
+        name: String,
+        external_kind: ExternalKindModel,
+    },
+    /// A custom type, passed as its `builtin` type
+    Custom {
+        module_path: String,
+        name: String,
+        builtin: Box<TypeModel>,
+    },
+}
+
+/// What kind of type an external type is, see [ExternalKind]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalKindModel {
+    Interface,
+    /// A record or an enum
+    DataClass,
+    Flags,
+}
+
+impl From<&Type> for TypeModel {
+    fn from(type_: &Type) -> Self {
+        let boxed = |t: &Type| Box::new(Self::from(t));
+        match type_ {
+            Type::UInt8 => Self::UInt8,
+            Type::Int8 => Self::Int8,
+            Type::UInt16 => Self::UInt16,
+            Type::Int16 => Self::Int16,
+            Type::UInt32 => Self::UInt32,
+            Type::Int32 => Self::Int32,
+            Type::UInt64 => Self::UInt64,
+            Type::Int64 => Self::Int64,
+            Type::Float32 => Self::Float32,
+            Type::Float64 => Self::Float64,
+            Type::Boolean => Self::Boolean,
+            Type::Char => Self::Char,
+            Type::String => Self::String,
+            Type::Bytes => Self::Bytes,
+            Type::Timestamp => Self::Timestamp,
+            Type::Duration => Self::Duration,
+            Type::Url => Self::Url,
+            Type::Uuid => Self::Uuid,
+            Type::Decimal => Self::Decimal,
+            Type::Json => Self::Json,
+            Type::Path => Self::Path,
+            Type::IpAddr => Self::IpAddr,
+            Type::SocketAddr => Self::SocketAddr,
+            Type::ForeignExecutor => Self::ForeignExecutor,
+            Type::Object {
+                module_path,
+                name,
+                imp,
+            } => Self::Object {
+                module_path: module_path.clone(),
+                name: name.clone(),
+                is_trait: imp == &ObjectImpl::Trait,
+            },
+            Type::Record { module_path, name } => Self::Record {
+                module_path: module_path.clone(),
+                name: name.clone(),
+            },
+            Type::Enum { module_path, name } => Self::Enum {
+                module_path: module_path.clone(),
+                name: name.clone(),
+            },
+            Type::Flags { module_path, name } => Self::Flags {
+                module_path: module_path.clone(),
+                name: name.clone(),
+            },
+            Type::CallbackInterface { module_path, name } => Self::CallbackInterface {
+                module_path: module_path.clone(),
+                name: name.clone(),
+            },
+            Type::Optional { inner_type } => Self::Optional {
+                inner_type: boxed(inner_type),
+            },
+            Type::Sequence { inner_type } => Self::Sequence {
+                inner_type: boxed(inner_type),
+            },
+            Type::Map {
+                key_type,
+                value_type,
+            } => Self::Map {
+                key_type: boxed(key_type),
+                value_type: boxed(value_type),
+            },
+            Type::Compressed { inner_type } => Self::Compressed {
+                inner_type: boxed(inner_type),
+            },
+            Type::Shared { inner_type } => Self::Shared {
+                inner_type: boxed(inner_type),
+            },
+            Type::Weak { inner_type } => Self::Weak {
+                inner_type: boxed(inner_type),
+            },
+            Type::External {
+                module_path,
+                name,
+                kind,
+            } => Self::External {
+                module_path: module_path.clone(),
+                name: name.clone(),
+                external_kind: match kind {
+                    ExternalKind::Interface => ExternalKindModel::Interface,
+                    ExternalKind::DataClass => ExternalKindModel::DataClass,
+                    ExternalKind::Flags => ExternalKindModel::Flags,
+                },
+            },
+            Type::Custom {
+                module_path,
+                name,
+                builtin,
+            } => Self::Custom {
+                module_path: module_path.clone(),
+                name: name.clone(),
+                builtin: boxed(builtin),
+            },
+        }
+    }
+}
+
+/// The default value of an argument or field, or the value of a constant, see [Literal]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LiteralModel {
+    Boolean {
+        value: bool,
+    },
+    String {
+        value: String,
+    },
+    /// An unsigned integer, with the radix it was written in
+    #[serde(rename = "uint")]
+    UInt {
+        value: u64,
+        radix: RadixModel,
+        #[serde(rename = "type")]
+        type_: TypeModel,
+    },
+    Int {
+        value: i64,
+        radix: RadixModel,
+        #[serde(rename = "type")]
+        type_: TypeModel,
+    },
+    /// A float, as it was written, so that no precision is lost
+    Float {
+        value: String,
+        #[serde(rename = "type")]
+        type_: TypeModel,
+    },
+    /// A variant of an enum without fields
+    Enum {
+        variant: String,
+        #[serde(rename = "type")]
+        type_: TypeModel,
+    },
+    EmptySequence,
+    EmptyMap,
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RadixModel {
+    Decimal,
+    Octal,
+    Hexadecimal,
+}
+
+impl From<&Literal> for LiteralModel {
+    fn from(literal: &Literal) -> Self {
+        let radix = |radix: &Radix| match radix {
+            Radix::Decimal => RadixModel::Decimal,
+            Radix::Octal => RadixModel::Octal,
+            Radix::Hexadecimal => RadixModel::Hexadecimal,
+        };
+        match literal {
+            Literal::Boolean(value) => Self::Boolean { value: *value },
+            Literal::String(value) => Self::String {
+                value: value.clone(),
+            },
+            Literal::UInt(value, r, type_) => Self::UInt {
+                value: *value,
+                radix: radix(r),
+                type_: type_.into(),
+            },
+            Literal::Int(value, r, type_) => Self::Int {
+                value: *value,
+                radix: radix(r),
+                type_: type_.into(),
+            },
+            Literal::Float(value, type_) => Self::Float {
+                value: value.clone(),
+                type_: type_.into(),
+            },
+            Literal::Enum(variant, type_) => Self::Enum {
+                variant: variant.clone(),
+                type_: type_.into(),
+            },
+            Literal::EmptySequence => Self::EmptySequence,
+            Literal::EmptyMap => Self::EmptyMap,
+            Literal::Null => Self::Null,
+        }
+    }
+}
+
+impl ComponentModel {
+    pub fn new(ci: &ComponentInterface) -> Self {
+        Self {
+            namespace: ci.namespace().to_string(),
+            uniffi_contract_version: ci.uniffi_contract_version(),
+            contract_version_function: ci.ffi_uniffi_contract_version().name().to_string(),
+            contract_info_function: ci.ffi_uniffi_contract_info().name().to_string(),
+            debug_live_handles_function: ci.ffi_uniffi_debug_live_handles().name().to_string(),
+            rustbuffer_functions: RustBufferFunctions {
+                alloc: ci.ffi_rustbuffer_alloc().name().to_string(),
+                from_bytes: ci.ffi_rustbuffer_from_bytes().name().to_string(),
+                free: ci.ffi_rustbuffer_free().name().to_string(),
+                reserve: ci.ffi_rustbuffer_reserve().name().to_string(),
+            },
+            checksums: ci
+                .iter_checksums()
+                .map(|(function, checksum)| ChecksumModel { function, checksum })
+                .collect(),
+            types: ci.iter_types().map(Into::into).collect(),
+            functions: ci.function_definitions().iter().map(function).collect(),
+            constants: ci
+                .constant_definitions()
+                .iter()
+                .map(exported_constant)
+                .collect(),
+            objects: ci.object_definitions().iter().map(object).collect(),
+            records: ci.record_definitions().map(record).collect(),
+            enums: ci.enum_definitions().map(|e| enum_(ci, e)).collect(),
+            flags: ci.flags_definitions().map(flags).collect(),
+            callback_interfaces: ci
+                .callback_interface_definitions()
+                .iter()
+                .map(callback_interface)
+                .collect(),
+            ffi_functions: ci
+                .iter_ffi_function_definitions()
+                .map(|f| ffi_function(&f))
+                .collect(),
+        }
+    }
+}
+
+fn function(func: &Function) -> CallableModel {
+    callable(func.name(), func, func.is_async(), func.ffi_func())
+}
+
+fn constructor(cons: &Constructor) -> CallableModel {
+    callable(cons.name(), cons, false, cons.ffi_func())
+}
+
+fn method(meth: &Method) -> CallableModel {
+    CallableModel {
+        has_default: meth.has_default(),
+        takes_self_by_value: meth.takes_self_by_value(),
+        ..callable(meth.name(), meth, meth.is_async(), meth.ffi_func())
+    }
+}
+
+fn callable(
+    name: &str,
+    callable: &impl Callable,
+    is_async: bool,
+    ffi_func: &FfiFunction,
+) -> CallableModel {
+    CallableModel {
+        name: name.to_string(),
+        arguments: callable.arguments().into_iter().map(argument).collect(),
+        return_type: callable.return_type().as_ref().map(Into::into),
+        throws_type: callable.throws_type().as_ref().map(Into::into),
+        is_async,
+        ffi_function: ffi_func.name().to_string(),
+        has_default: false,
+        takes_self_by_value: false,
+    }
+}
+
+fn argument(arg: &Argument) -> ArgumentModel {
+    ArgumentModel {
+        name: arg.name().to_string(),
+        type_: (&arg.as_type()).into(),
+        by_ref: arg.by_ref(),
+        default_value: arg.default_value().map(Into::into),
+    }
+}
+
+fn object(obj: &Object) -> ObjectModel {
+    ObjectModel {
+        name: obj.name().to_string(),
+        is_trait: obj.imp() == &ObjectImpl::Trait,
+        primary_constructor: obj.primary_constructor().map(constructor),
+        alternate_constructors: obj
+            .alternate_constructors()
+            .into_iter()
+            .map(constructor)
+            .collect(),
+        methods: obj.methods().into_iter().map(method).collect(),
+        traits: obj.uniffi_traits().into_iter().map(uniffi_trait).collect(),
+        constants: obj.constants().into_iter().map(constant).collect(),
+        free_function: obj.ffi_object_free().name().to_string(),
+    }
+}
+
+fn uniffi_trait(t: &UniffiTrait) -> TraitModel {
+    let (trait_, methods) = match t {
+        UniffiTrait::Debug { fmt } => ("Debug", vec![method(fmt)]),
+        UniffiTrait::Display { fmt } => ("Display", vec![method(fmt)]),
+        UniffiTrait::Eq { eq, ne } => ("Eq", vec![method(eq), method(ne)]),
+        UniffiTrait::Hash { hash } => ("Hash", vec![method(hash)]),
+        UniffiTrait::Ord { cmp } => ("Ord", vec![method(cmp)]),
+        UniffiTrait::Restore { serialize, restore } => {
+            ("Restore", vec![method(serialize), function(restore)])
+        }
+    };
+    TraitModel {
+        trait_: trait_.to_string(),
+        methods,
+    }
+}
+
+fn constant(constant: &TraitConstant) -> ConstantModel {
+    ConstantModel {
+        name: constant.name().to_string(),
+        type_: (&constant.as_type()).into(),
+        value: constant.value().into(),
+    }
+}
+
+fn exported_constant(constant: &Constant) -> ConstantModel {
+    ConstantModel {
+        name: constant.name().to_string(),
+        type_: (&constant.as_type()).into(),
+        value: constant.value().into(),
+    }
+}
+
+fn field(field: &Field) -> FieldModel {
+    FieldModel {
+        name: field.name().to_string(),
+        type_: (&field.as_type()).into(),
+        default_value: field.default_value().map(Into::into),
+    }
+}
+
+fn record(rec: &Record) -> RecordModel {
+    RecordModel {
+        name: rec.name().to_string(),
+        fields: rec.fields().iter().map(field).collect(),
+    }
+}
+
+fn enum_(ci: &ComponentInterface, e: &Enum) -> EnumModel {
+    EnumModel {
+        name: e.name().to_string(),
+        is_error: ci.is_name_used_as_error(e.name()),
+        is_flat: e.is_flat(),
+        is_non_exhaustive: e.is_non_exhaustive(),
+        variants: e
+            .variants()
+            .iter()
+            .map(|v| VariantModel {
+                name: v.name().to_string(),
+                discr: v.discr(),
+                fields: v.fields().iter().map(field).collect(),
+            })
+            .collect(),
+    }
+}
+
+fn flags(flags: &Flags) -> FlagsModel {
+    FlagsModel {
+        name: flags.name().to_string(),
+        flags: flags
+            .flags()
+            .iter()
+            .map(|f| FlagModel {
+                name: f.name().to_string(),
+                value: f.value(),
+            })
+            .collect(),
+    }
+}
+
+fn callback_interface(cbi: &CallbackInterface) -> CallbackInterfaceModel {
+    CallbackInterfaceModel {
+        name: cbi.name().to_string(),
+        methods: cbi.methods().into_iter().map(method).collect(),
+        constants: cbi.constants().into_iter().map(constant).collect(),
+        main_thread: cbi.main_thread(),
+        closure: cbi.is_closure(),
+        init_function: cbi.ffi_init_callback().name().to_string(),
+    }
+}
+
+fn ffi_function(func: &FfiFunction) -> FfiFunctionModel {
+    FfiFunctionModel {
+        name: func.name().to_string(),
+        arguments: func
+            .arguments()
+            .into_iter()
+            .map(|arg| FfiArgumentModel {
+                name: arg.name().to_string(),
+                type_: (&arg.type_()).into(),
+            })
+            .collect(),
+        return_type: func.return_type().map(Into::into),
+        has_rust_call_status_arg: func.has_rust_call_status_arg(),
+        is_async: func.is_async(),
+    }
+}
+
+/// Run an external generator for a component defined in a UDL file
+pub fn generate_bindings(
+    program: &str,
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    out_dir_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+) -> Result<()> {
+    let (component, _) = crate::load_component(udl_file, config_file_override, library_file)?;
+    let crate_root = crate::guess_crate_root(udl_file).context("Failed to guess crate root")?;
+    let request = GeneratorRequest {
+        protocol_version: PROTOCOL_VERSION,
+        out_dir: crate::get_out_dir(udl_file, out_dir_override)?.to_string(),
+        crate_name: None,
+        cdylib_name: library_file
+            .and_then(calc_cdylib_name)
+            .map(ToString::to_string),
+        config: crate::load_bindings_config_toml(crate_root, config_file_override)?,
+        component: ComponentModel::new(&component),
+    };
+    run_external_generator(program, &request)
+}
+
+/// Run an external generator for each of the components found in a library, see
+/// [crate::library_mode::find_components]
+pub fn generate_library_bindings(
+    program: &str,
+    library_path: &Utf8Path,
+    sources: &[Source],
+    out_dir: &Utf8Path,
+) -> Result<()> {
+    let cdylib_name = calc_cdylib_name(library_path).map(ToString::to_string);
+    for source in sources {
+        let crate_root = source
+            .package
+            .manifest_path
+            .parent()
+            .context("manifest path has no parent")?;
+        let request = GeneratorRequest {
+            protocol_version: PROTOCOL_VERSION,
+            out_dir: out_dir.to_string(),
+            crate_name: Some(source.crate_name.clone()),
+            cdylib_name: cdylib_name.clone(),
+            config: crate::load_bindings_config_toml(crate_root, None)?,
+            component: ComponentModel::new(&source.ci),
+        };
+        run_external_generator(program, &request)?;
+    }
+    Ok(())
+}
+
+/// Run `program`, with the request as JSON on its stdin
+pub fn run_external_generator(program: &str, request: &GeneratorRequest) -> Result<()> {
+    let mut child = Command::new(program)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run external generator `{program}`"))?;
+    // Dropping stdin closes it, so the generator sees the end of the request
+    let stdin = child.stdin.take().expect("stdin is piped");
+    serde_json::to_writer(stdin, request)
+        .with_context(|| format!("failed to send the request to `{program}`"))?;
+    let status = child.wait()?;
+    if !status.success() {
+        bail!(
+            "external generator `{program}` failed to generate the bindings for `{}`",
+            request.component.namespace
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn ffi_types() {
+        let ffi_type = FfiType::FutureCallback {
+            return_type: Box::new(FfiType::RustBuffer(Some("Url".to_string()))),
+        };
+        assert_eq!(
+            serde_json::to_value(FfiTypeModel::from(&ffi_type)).unwrap(),
+            json!({
+                "kind": "future_callback",
+                "return_type": { "kind": "rust_buffer", "external_type": "Url" },
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(FfiTypeModel::from(&FfiType::UInt32)).unwrap(),
+            json!({ "kind": "uint32" })
+        );
+    }
+
+    #[test]
+    fn types() {
+        let type_ = Type::Map {
+            key_type: Box::new(Type::String),
+            value_type: Box::new(Type::Optional {
+                inner_type: Box::new(Type::Object {
+                    module_path: "crate_name".to_string(),
+                    name: "Store".to_string(),
+                    imp: ObjectImpl::Trait,
+                }),
+            }),
+        };
+        assert_eq!(
+            serde_json::to_value(TypeModel::from(&type_)).unwrap(),
+            json!({
+                "kind": "map",
+                "key_type": { "kind": "string" },
+                "value_type": {
+                    "kind": "optional",
+                    "inner_type": {
+                        "kind": "object",
+                        "module_path": "crate_name",
+                        "name": "Store",
+                        "is_trait": true,
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn literals() {
+        let literal = Literal::UInt(255, Radix::Hexadecimal, Type::UInt8);
+        assert_eq!(
+            serde_json::to_value(LiteralModel::from(&literal)).unwrap(),
+            json!({
+                "kind": "uint",
+                "value": 255,
+                "radix": "hexadecimal",
+                "type": { "kind": "uint8" },
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(LiteralModel::from(&Literal::EmptySequence)).unwrap(),
+            json!({ "kind": "empty_sequence" })
+        );
+    }
+
+    #[test]
+    fn component_model() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=StoreError]
+                u32 get_count(string name);
+            };
+
+            interface CounterStore {
+                constructor();
+                void increment_count();
+            };
+
+            [Error]
+            enum StoreError { "Full" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let model = ComponentModel::new(&ci);
+        assert_eq!(model.namespace, "test");
+        assert_eq!(model.functions[0].name, "get_count");
+        assert_eq!(model.functions[0].arguments[0].name, "name");
+        assert_eq!(model.functions[0].return_type, Some(TypeModel::UInt32));
+        assert!(model.functions[0]
+            .ffi_function
+            .ends_with("_fn_func_get_count"));
+
+        let store = &model.objects[0];
+        assert_eq!(store.name, "CounterStore");
+        assert!(store.primary_constructor.is_some());
+        assert_eq!(store.methods[0].name, "increment_count");
+
+        assert!(model.enums[0].is_error);
+        assert!(model.enums[0].is_flat);
+        assert!(model
+            .ffi_functions
+            .iter()
+            .any(|f| f.name == store.free_function));
+    }
+
+    #[test]
+    fn component_metadata() {
+        let ci = ComponentInterface::from_webidl("namespace test { u32 get_count(); };").unwrap();
+        let metadata = serde_json::to_value(ComponentMetadata::new([&ci])).unwrap();
+        assert_eq!(metadata["protocol_version"], json!(PROTOCOL_VERSION));
+        assert_eq!(metadata["components"][0]["namespace"], json!("test"));
+        assert_eq!(
+            metadata["components"][0]["functions"][0]["return_type"],
+            json!({ "kind": "uint32" })
+        );
+    }
+}
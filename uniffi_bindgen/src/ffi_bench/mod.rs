@@ -0,0 +1,168 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a crate that benchmarks the FFI overhead of a UniFFI release.
+//!
+//! The crate exports a function for each benchmark case: scalars, strings, a large record, a
+//! callback interface and an async function.  The cases are benchmarked by matching harnesses,
+//! which all report through the same Rust function:
+//!
+//!   - `src/lib.rs`, with the cases and a Rust baseline that calls them without crossing the FFI.
+//!   - `bindings/bench.kts`, `bindings/bench.swift` and `bindings/bench.py`, which call the cases
+//!     through the bindings.
+//!   - `benches/ffi.rs`, which runs the baseline then each language's harness, so that `cargo
+//!     bench` benchmarks every language at once.
+//!
+//! Generating the crate for two releases, or for a release and a local checkout, measures FFI
+//! overhead regressions between them.
+
+use crate::Result;
+use camino::Utf8Path;
+use fs_err as fs;
+
+/// Write the benchmark crate to `out_dir`
+///
+/// The crate depends on the `uniffi` crate at `uniffi_path` if it's passed, otherwise on the
+/// release of this `uniffi-bindgen`.
+pub fn generate_ffi_bench(out_dir: &Utf8Path, uniffi_path: Option<&Utf8Path>) -> Result<()> {
+    // The path is written to the crate's `Cargo.toml`, so it can't be relative to the current
+    // directory
+    let uniffi_path = uniffi_path.map(Utf8Path::canonicalize_utf8).transpose()?;
+    for dir in ["src", "benches", "bindings"] {
+        fs::create_dir_all(out_dir.join(dir))?;
+    }
+    fs::write(
+        out_dir.join("Cargo.toml"),
+        cargo_toml(uniffi_path.as_deref()),
+    )?;
+    fs::write(
+        out_dir.join("src").join("lib.rs"),
+        include_str!("templates/lib.rs"),
+    )?;
+    fs::write(
+        out_dir.join("benches").join("ffi.rs"),
+        include_str!("templates/ffi.rs"),
+    )?;
+    fs::write(
+        out_dir.join("bindings").join("bench.kts"),
+        include_str!("templates/bench.kts"),
+    )?;
+    fs::write(
+        out_dir.join("bindings").join("bench.swift"),
+        include_str!("templates/bench.swift"),
+    )?;
+    fs::write(
+        out_dir.join("bindings").join("bench.py"),
+        include_str!("templates/bench.py"),
+    )?;
+    Ok(())
+}
+
+fn cargo_toml(uniffi_path: Option<&Utf8Path>) -> String {
+    let uniffi_source = match uniffi_path {
+        Some(path) => format!("path = \"{path}\""),
+        None => format!("version = \"={}\"", env!("CARGO_PKG_VERSION")),
+    };
+    format!(
+        "# This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         # Trust me, you don't want to mess with it!\n\
+         \n\
+         [package]\n\
+         name = \"uniffi-ffi-bench\"\n\
+         version = \"0.0.0\"\n\
+         publish = false\n\
+         edition = \"2021\"\n\
+         \n\
+         [lib]\n\
+         name = \"ffi_bench\"\n\
+         crate-type = [\"lib\", \"cdylib\"]\n\
+         bench = false\n\
+         \n\
+         [dependencies]\n\
+         uniffi = {{ {uniffi_source} }}\n\
+         \n\
+         [dev-dependencies]\n\
+         uniffi = {{ {uniffi_source}, features = [\"bindgen-tests\"] }}\n\
+         \n\
+         [[bench]]\n\
+         name = \"ffi\"\n\
+         harness = false\n\
+         \n\
+         # Keep the benchmarks out of any enclosing workspace\n\
+         [workspace]\n\
+         members = [\".\"]\n"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn uniffi_dependency() {
+        let release = cargo_toml(None);
+        assert!(release.contains(&format!(
+            "uniffi = {{ version = \"={}\" }}\n",
+            env!("CARGO_PKG_VERSION")
+        )));
+        let local = cargo_toml(Some(Utf8Path::new("/src/uniffi-rs/uniffi")));
+        assert!(local.contains(
+            "uniffi = { path = \"/src/uniffi-rs/uniffi\", features = [\"bindgen-tests\"] }\n"
+        ));
+    }
+
+    #[test]
+    fn generated_crate() {
+        let out_dir = env::temp_dir().join(format!("uniffi-ffi-bench-{}", std::process::id()));
+        let out_dir = Utf8Path::from_path(&out_dir).unwrap();
+        generate_ffi_bench(out_dir, None).unwrap();
+        for (path, contents) in [
+            ("src/lib.rs", include_str!("templates/lib.rs")),
+            ("benches/ffi.rs", include_str!("templates/ffi.rs")),
+            ("bindings/bench.kts", include_str!("templates/bench.kts")),
+            (
+                "bindings/bench.swift",
+                include_str!("templates/bench.swift"),
+            ),
+            ("bindings/bench.py", include_str!("templates/bench.py")),
+        ] {
+            assert_eq!(fs::read_to_string(out_dir.join(path)).unwrap(), contents);
+        }
+        assert_eq!(
+            fs::read_to_string(out_dir.join("Cargo.toml")).unwrap(),
+            cargo_toml(None)
+        );
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+
+    // Each case of `lib.rs` starts with a "Case `name`" comment, and must be reported by the Rust
+    // baseline and by every harness.
+    #[test]
+    fn every_case_is_benchmarked() {
+        let lib = include_str!("templates/lib.rs");
+        let cases: Vec<&str> = lib
+            .lines()
+            .filter_map(|line| line.strip_prefix("// Case `"))
+            .map(|rest| rest.split('`').next().unwrap())
+            .collect();
+        assert_eq!(
+            cases,
+            ["scalars", "strings", "large_record", "callbacks", "async"]
+        );
+        for (harness, source) in [
+            ("lib.rs", lib),
+            ("bench.kts", include_str!("templates/bench.kts")),
+            ("bench.swift", include_str!("templates/bench.swift")),
+            ("bench.py", include_str!("templates/bench.py")),
+        ] {
+            for case in &cases {
+                assert!(
+                    source.contains(&format!("\"{case}\"")),
+                    "{harness} doesn't benchmark `{case}`"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+import uniffi.ffi_bench.*
+import kotlinx.coroutines.*
+
+val language = "kotlin"
+val count = iterations()
+
+fun bench(case: String, f: () -> Unit) {
+    val start = System.nanoTime()
+    for (i in 0uL until count) {
+        f()
+    }
+    report(language, case, count, (System.nanoTime() - start).toULong())
+}
+
+class Callback : BenchCallback {
+    override fun call(value: UInt) = value
+}
+
+val record = LargeRecord(
+    id = 1uL,
+    name = "benchmark",
+    description = "a record with enough data to be worth serializing".repeat(4),
+    tags = (0 until 10).map { "tag-$it" },
+    values = (0 until 100).map { it.toDouble() },
+    parent = 0uL,
+)
+
+bench("scalars") { scalars(1, 2L, 3.0, true) }
+bench("strings") { strings("a string that crosses the FFI") }
+bench("large_record") { largeRecord(record) }
+report(language, "callbacks", count, callbacks(Callback(), count))
+runBlocking {
+    val start = System.nanoTime()
+    for (i in 0uL until count) {
+        asyncCall(1u)
+    }
+    report(language, "async", count, (System.nanoTime() - start).toULong())
+}
@@ -0,0 +1,41 @@
+# This file was autogenerated by some hot garbage in the `uniffi` crate.
+# Trust me, you don't want to mess with it!
+
+import asyncio
+import time
+
+from ffi_bench import *
+
+LANGUAGE = "python"
+COUNT = iterations()
+
+def bench(case, f):
+    start = time.perf_counter_ns()
+    for _ in range(COUNT):
+        f()
+    report(LANGUAGE, case, COUNT, time.perf_counter_ns() - start)
+
+class Callback(BenchCallback):
+    def call(self, value):
+        return value
+
+async def bench_async():
+    start = time.perf_counter_ns()
+    for _ in range(COUNT):
+        await async_call(1)
+    report(LANGUAGE, "async", COUNT, time.perf_counter_ns() - start)
+
+record = LargeRecord(
+    id=1,
+    name="benchmark",
+    description="a record with enough data to be worth serializing" * 4,
+    tags=[f"tag-{i}" for i in range(10)],
+    values=[float(i) for i in range(100)],
+    parent=0,
+)
+
+bench("scalars", lambda: scalars(1, 2, 3.0, True))
+bench("strings", lambda: strings("a string that crosses the FFI"))
+bench("large_record", lambda: large_record(record))
+report(LANGUAGE, "callbacks", COUNT, callbacks(Callback(), COUNT))
+asyncio.run(bench_async())
@@ -0,0 +1,48 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+import Foundation
+import ffi_bench
+
+let language = "swift"
+let count = iterations()
+
+func bench(_ testCase: String, _ f: () -> Void) {
+    let start = DispatchTime.now().uptimeNanoseconds
+    for _ in 0..<count {
+        f()
+    }
+    report(language: language, caseName: testCase, iterations: count, elapsedNs: DispatchTime.now().uptimeNanoseconds - start)
+}
+
+class Callback: BenchCallback {
+    func call(value: UInt32) -> UInt32 {
+        return value
+    }
+}
+
+let record = LargeRecord(
+    id: 1,
+    name: "benchmark",
+    description: String(repeating: "a record with enough data to be worth serializing", count: 4),
+    tags: (0..<10).map { "tag-\($0)" },
+    values: (0..<100).map { Double($0) },
+    parent: 0
+)
+
+bench("scalars") { _ = scalars(a: 1, b: 2, c: 3.0, d: true) }
+bench("strings") { _ = strings(value: "a string that crosses the FFI") }
+bench("large_record") { _ = largeRecord(record: record) }
+report(language: language, caseName: "callbacks", iterations: count, elapsedNs: callbacks(callback: Callback(), iterations: count))
+
+let counter = DispatchGroup()
+counter.enter()
+Task {
+    let start = DispatchTime.now().uptimeNanoseconds
+    for _ in 0..<count {
+        _ = await asyncCall(value: 1)
+    }
+    report(language: language, caseName: "async", iterations: count, elapsedNs: DispatchTime.now().uptimeNanoseconds - start)
+    counter.leave()
+}
+counter.wait()
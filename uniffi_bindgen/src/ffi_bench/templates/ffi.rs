@@ -0,0 +1,34 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+//! Run the FFI benchmarks: the Rust baseline, then the harness for each language
+//!
+//! `cargo bench` runs every language, `cargo bench -- python swift` only runs some of them.
+
+const LANGUAGES: [&str; 3] = ["kotlin", "swift", "python"];
+
+fn main() {
+    // Cargo passes `--bench`, which isn't a language
+    let selected: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+    for language in &selected {
+        if !LANGUAGES.contains(&language.as_str()) {
+            panic!("Unknown language {language}, expected one of {LANGUAGES:?}");
+        }
+    }
+    let should_run = |language: &str| selected.is_empty() || selected.iter().any(|s| s == language);
+
+    ffi_bench::run_rust_baseline();
+    let tmp_dir = env!("CARGO_TARGET_TMPDIR");
+    if should_run("kotlin") {
+        uniffi::kotlin_run_test(tmp_dir, "uniffi-ffi-bench", "bindings/bench.kts").unwrap();
+    }
+    if should_run("swift") {
+        uniffi::swift_run_test(tmp_dir, "uniffi-ffi-bench", "bindings/bench.swift").unwrap();
+    }
+    if should_run("python") {
+        uniffi::python_run_test(tmp_dir, "uniffi-ffi-bench", "bindings/bench.py").unwrap();
+    }
+}
@@ -0,0 +1,185 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+//! FFI benchmark cases
+//!
+//! Each case is benchmarked from every language by the harnesses in `bindings/`, and from Rust by
+//! `run_rust_baseline()`, which measures the same calls without crossing the FFI.
+
+use std::{
+    fs::OpenOptions,
+    hint::black_box,
+    io::Write,
+    time::{Duration, Instant},
+};
+
+uniffi::setup_scaffolding!("ffi_bench");
+
+/// Number of calls for each case, set with `UNIFFI_BENCH_ITERATIONS`
+#[uniffi::export]
+pub fn iterations() -> u64 {
+    std::env::var("UNIFFI_BENCH_ITERATIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20_000)
+}
+
+/// Print the result of a case, and append it to the `UNIFFI_BENCH_RESULTS` file if it's set
+///
+/// All the harnesses report through this function, so that the results have the same format.
+#[uniffi::export]
+pub fn report(language: String, case_name: String, iterations: u64, elapsed_ns: u64) {
+    let ns_per_call = elapsed_ns as f64 / iterations.max(1) as f64;
+    println!("{language:<8} {case_name:<14} {ns_per_call:>12.1} ns/call");
+    if let Ok(path) = std::env::var("UNIFFI_BENCH_RESULTS") {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .expect("Error opening UNIFFI_BENCH_RESULTS");
+        writeln!(
+            file,
+            "{{\"language\":\"{language}\",\"case\":\"{case_name}\",\"ns_per_call\":{ns_per_call:.1}}}"
+        )
+        .expect("Error writing UNIFFI_BENCH_RESULTS");
+    }
+}
+
+// Case `scalars`: scalar arguments and return value
+
+#[uniffi::export]
+pub fn scalars(a: i32, b: i64, c: f64, d: bool) -> f64 {
+    if d {
+        a as f64 + b as f64 + c
+    } else {
+        c
+    }
+}
+
+// Case `strings`: a string argument and return value
+
+#[uniffi::export]
+pub fn strings(value: String) -> String {
+    value
+}
+
+// Case `large_record`: a record with strings and sequences, serialized into a RustBuffer both ways
+
+#[derive(uniffi::Record, Clone)]
+pub struct LargeRecord {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub values: Vec<f64>,
+    pub parent: Option<u64>,
+}
+
+#[uniffi::export]
+pub fn large_record(record: LargeRecord) -> LargeRecord {
+    record
+}
+
+// Case `callbacks`: Rust calls a foreign callback interface method.  The foreign harness passes
+// its implementation to `callbacks()`, which makes the calls and returns the elapsed time.
+
+#[uniffi::export(callback_interface)]
+pub trait BenchCallback: Send + Sync {
+    fn call(&self, value: u32) -> u32;
+}
+
+#[uniffi::export]
+pub fn callbacks(callback: Box<dyn BenchCallback>, iterations: u64) -> u64 {
+    let start = Instant::now();
+    for i in 0..iterations {
+        black_box(callback.call(black_box(i as u32)));
+    }
+    elapsed_ns(start.elapsed())
+}
+
+// Case `async`: awaiting an async function that's immediately ready
+
+#[uniffi::export]
+pub async fn async_call(value: u32) -> u32 {
+    value
+}
+
+/// Benchmark the cases from Rust, as a baseline for the foreign harnesses
+pub fn run_rust_baseline() {
+    struct RustCallback;
+
+    impl BenchCallback for RustCallback {
+        fn call(&self, value: u32) -> u32 {
+            value
+        }
+    }
+
+    let count = iterations();
+    let record = sample_record();
+    bench("scalars", count, || {
+        scalars(black_box(1), black_box(2), black_box(3.0), black_box(true));
+    });
+    bench("strings", count, || {
+        strings(black_box("a string that crosses the FFI".to_string()));
+    });
+    bench("large_record", count, || {
+        large_record(black_box(record.clone()));
+    });
+    let elapsed = callbacks(Box::new(RustCallback), count);
+    report("rust".to_string(), "callbacks".to_string(), count, elapsed);
+    bench("async", count, || {
+        block_on(async_call(black_box(1)));
+    });
+}
+
+fn bench(case: &str, count: u64, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..count {
+        f();
+    }
+    report(
+        "rust".to_string(),
+        case.to_string(),
+        count,
+        elapsed_ns(start.elapsed()),
+    );
+}
+
+fn elapsed_ns(elapsed: Duration) -> u64 {
+    elapsed.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+// The record that the harnesses pass to `large_record()`
+fn sample_record() -> LargeRecord {
+    LargeRecord {
+        id: 1,
+        name: "benchmark".to_string(),
+        description: "a record with enough data to be worth serializing".repeat(4),
+        tags: (0..10).map(|i| format!("tag-{i}")).collect(),
+        values: (0..100).map(f64::from).collect(),
+        parent: Some(0),
+    }
+}
+
+// Poll a future that's immediately ready
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker {
+            noop_raw_waker()
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,366 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate cargo-fuzz targets for the types and functions exported by the components in a
+//! library.
+//!
+//! Buggy foreign bindings can pass malformed data to Rust, which must fail to lift with an error,
+//! never panic or cause undefined behavior.  Each target lifts one type from the fuzzer's data with
+//! `uniffi::fuzz_try_lift()`, so the fuzzer reports any panic while lifting.  With `calls`, there's
+//! also a target for each function, which calls it with the fuzzer's data as its arguments through
+//! its remote entry point, see `uniffi::fuzz_remote_call()`.  These are opt-in, since the functions
+//! are called for real.
+//!
+//!   - `Cargo.toml`, which depends on the crates of the components, on the same `uniffi` as them
+//!     and on `libfuzzer-sys`, and lists a `[[bin]]` for each target.
+//!   - `fuzz_targets/<crate>_<type>.rs` for each record, enum, flags and custom type, and
+//!     `fuzz_targets/<crate>_call_<function>.rs` for each function.
+//!
+//! Types that contain handles are skipped, since lifting a handle trusts the foreign code to pass
+//! a valid one, and so are the functions that take or return them.  Flat errors are skipped too,
+//! since they can't be lifted.
+
+use crate::{
+    interface::{ComponentInterface, Type},
+    library_mode::{self, Source},
+    Result,
+};
+use anyhow::bail;
+use camino::Utf8Path;
+use cargo_metadata::DependencyKind;
+use fs_err as fs;
+use heck::ToSnakeCase;
+
+/// Write a cargo-fuzz project for the library at `library_path` to `out_dir`
+///
+/// The targets of all the components are generated, or only the ones of `crate_name` if it's
+/// passed.  `out_dir` is usually the `fuzz` directory of the crate.  With `calls`, the functions
+/// get targets too.
+pub fn generate_fuzz_targets(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    out_dir: &Utf8Path,
+    calls: bool,
+) -> Result<()> {
+    let sources = library_mode::find_components(library_path, crate_name)?;
+    let mut targets: Vec<FuzzTarget> = sources
+        .iter()
+        .flat_map(|source| fuzz_targets(&source.crate_name, &source.ci))
+        .collect();
+    if calls {
+        targets.extend(
+            sources
+                .iter()
+                .flat_map(|source| call_fuzz_targets(&source.crate_name, &source.ci)),
+        );
+    }
+    if targets.is_empty() {
+        bail!("{library_path} doesn't export any types that can be fuzzed");
+    }
+
+    let targets_dir = out_dir.join("fuzz_targets");
+    if targets_dir.exists() {
+        // Remove the targets of types that were since removed
+        fs::remove_dir_all(&targets_dir)?;
+    }
+    fs::create_dir_all(&targets_dir)?;
+    for target in &targets {
+        fs::write(
+            targets_dir.join(format!("{}.rs", target.name)),
+            target_source(target),
+        )?;
+    }
+    fs::write(
+        out_dir.join("Cargo.toml"),
+        cargo_toml(&sources, &targets, calls),
+    )?;
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct FuzzTarget {
+    name: String,
+    kind: FuzzTargetKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum FuzzTargetKind {
+    // Lifts `type_path`
+    Lift {
+        type_path: String,
+        tag_path: String,
+    },
+    // Calls `entry_point`, the remote entry point of a function defined in `crate_name`
+    Call {
+        crate_name: String,
+        entry_point: String,
+    },
+}
+
+fn fuzz_targets(crate_name: &str, ci: &ComponentInterface) -> Vec<FuzzTarget> {
+    ci.iter_types()
+        .filter_map(|type_| {
+            let (module_path, name) = match type_ {
+                Type::Record { module_path, name }
+                | Type::Flags { module_path, name }
+                | Type::Custom {
+                    module_path, name, ..
+                } => (module_path, name),
+                Type::Enum { module_path, name } => {
+                    let flat_error = ci.is_name_used_as_error(name)
+                        && ci.get_enum_definition(name).map(|e| e.is_flat()) == Some(true);
+                    if flat_error {
+                        return None;
+                    }
+                    (module_path, name)
+                }
+                _ => return None,
+            };
+            // Items from UDL have the namespace as their module path, and are at the crate root.
+            // Types from other crates get their targets when fuzzing those crates.
+            let type_path = if module_path.split("::").next() == Some(crate_name) {
+                format!("{module_path}::{name}")
+            } else if module_path == ci.namespace() {
+                format!("{crate_name}::{name}")
+            } else {
+                return None;
+            };
+            if ci.item_contains_handles(type_) {
+                return None;
+            }
+            Some(FuzzTarget {
+                name: format!("{crate_name}_{}", name.to_snake_case()),
+                kind: FuzzTargetKind::Lift {
+                    type_path,
+                    tag_path: format!("{crate_name}::UniFfiTag"),
+                },
+            })
+        })
+        .collect()
+}
+
+// The functions are called through their remote entry points, which take all the arguments in a
+// single buffer, so they can only be called when they have one, see
+// `ComponentInterface::is_remote_callable()`.
+fn call_fuzz_targets(crate_name: &str, ci: &ComponentInterface) -> Vec<FuzzTarget> {
+    ci.remote_callable_functions()
+        .map(|func| FuzzTarget {
+            name: format!("{crate_name}_call_{}", func.name().to_snake_case()),
+            kind: FuzzTargetKind::Call {
+                crate_name: crate_name.to_string(),
+                entry_point: func.remote_fn_name().to_string(),
+            },
+        })
+        .collect()
+}
+
+fn target_source(target: &FuzzTarget) -> String {
+    let body = match &target.kind {
+        FuzzTargetKind::Lift {
+            type_path,
+            tag_path,
+        } => format!(
+            "fuzz_target!(|data: &[u8]| {{\n\
+             \x20   uniffi::fuzz_try_lift::<{type_path}, {tag_path}>(data);\n\
+             }});\n"
+        ),
+        FuzzTargetKind::Call {
+            crate_name,
+            entry_point,
+        } => format!(
+            "// The crate defines the entry point, but nothing else uses it\n\
+             extern crate {crate_name};\n\
+             \n\
+             extern \"C\" {{\n\
+             \x20   fn {entry_point}(\n\
+             \x20       uniffi_args: uniffi::RustBuffer,\n\
+             \x20       call_status: &mut uniffi::RustCallStatus,\n\
+             \x20   ) -> uniffi::RustBuffer;\n\
+             }}\n\
+             \n\
+             fuzz_target!(|data: &[u8]| {{\n\
+             \x20   unsafe {{ uniffi::fuzz_remote_call({entry_point}, data) }};\n\
+             }});\n"
+        ),
+    };
+    format!(
+        "// This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         // Trust me, you don't want to mess with it!\n\
+         \n\
+         #![no_main]\n\
+         \n\
+         use libfuzzer_sys::fuzz_target;\n\
+         \n\
+         {body}"
+    )
+}
+
+fn cargo_toml(sources: &[Source], targets: &[FuzzTarget], calls: bool) -> String {
+    // Depend on the same uniffi as the components, including when it's a path dependency, since
+    // the targets use the `FfiConverter` impls of the components.
+    let uniffi = sources
+        .iter()
+        .flat_map(|s| &s.package.dependencies)
+        .find(|dep| dep.name == "uniffi" && dep.kind == DependencyKind::Normal);
+    let uniffi_dependency = uniffi_dependency(
+        uniffi.and_then(|dep| dep.path.as_deref()),
+        &uniffi.map_or_else(
+            || env!("CARGO_PKG_VERSION").to_string(),
+            |dep| dep.req.to_string(),
+        ),
+        calls,
+    );
+    let dependencies = sources
+        .iter()
+        .map(|s| {
+            let path = s.package.manifest_path.parent().unwrap();
+            format!("{} = {{ path = \"{path}\" }}\n", s.package.name)
+        })
+        .collect::<String>();
+    let bins = targets
+        .iter()
+        .map(|target| {
+            let name = &target.name;
+            format!(
+                "\n\
+                 [[bin]]\n\
+                 name = \"{name}\"\n\
+                 path = \"fuzz_targets/{name}.rs\"\n\
+                 test = false\n\
+                 doc = false\n"
+            )
+        })
+        .collect::<String>();
+    format!(
+        "# This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         # Trust me, you don't want to mess with it!\n\
+         \n\
+         [package]\n\
+         name = \"uniffi-fuzz\"\n\
+         version = \"0.0.0\"\n\
+         publish = false\n\
+         edition = \"2021\"\n\
+         \n\
+         [package.metadata]\n\
+         cargo-fuzz = true\n\
+         \n\
+         [dependencies]\n\
+         libfuzzer-sys = \"0.4\"\n\
+         {uniffi_dependency}\
+         {dependencies}\
+         \n\
+         # Keep the fuzz targets out of the crate's workspace\n\
+         [workspace]\n\
+         members = [\".\"]\n\
+         {bins}"
+    )
+}
+
+// The `uniffi` line of the `[dependencies]`.  The call targets need the remote entry points, which
+// are only built with the `unstable-remote-calls` feature.
+fn uniffi_dependency(path: Option<&Utf8Path>, req: &str, calls: bool) -> String {
+    let source = match path {
+        Some(path) => format!("path = \"{path}\""),
+        None => format!("version = \"{req}\""),
+    };
+    let features = if calls {
+        ", features = [\"unstable-remote-calls\"]"
+    } else {
+        ""
+    };
+    format!("uniffi = {{ {source}{features} }}\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fuzz_target_source() {
+        let target = FuzzTarget {
+            name: "geometry_point".to_string(),
+            kind: FuzzTargetKind::Lift {
+                type_path: "geometry::shapes::Point".to_string(),
+                tag_path: "geometry::UniFfiTag".to_string(),
+            },
+        };
+        let source = target_source(&target);
+        assert!(source.contains("#![no_main]\n"));
+        assert!(source.contains(
+            "uniffi::fuzz_try_lift::<geometry::shapes::Point, geometry::UniFfiTag>(data);\n"
+        ));
+    }
+
+    #[test]
+    fn targets_skip_handles() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace geometry {};
+            dictionary Point { i32 x; i32 y; };
+            enum Direction { "Up", "Down" };
+            [Error]
+            enum GeometryError { "Degenerate" };
+            interface Canvas {};
+            dictionary Layer { Canvas canvas; };
+            "#,
+        )
+        .unwrap();
+        let targets = fuzz_targets("geometry_lib", &ci);
+        assert_eq!(
+            targets.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["geometry_lib_point", "geometry_lib_direction"]
+        );
+        assert_eq!(
+            targets[0].kind,
+            FuzzTargetKind::Lift {
+                type_path: "geometry_lib::Point".to_string(),
+                tag_path: "geometry_lib::UniFfiTag".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn call_targets() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+            namespace geometry {
+                f64 distance(Point a, Point b);
+                Canvas new_canvas();
+            };
+            dictionary Point { f64 x; f64 y; };
+            interface Canvas {};
+            "#,
+        )
+        .unwrap();
+        let targets = call_fuzz_targets("geometry_lib", &ci);
+        assert_eq!(
+            targets,
+            vec![FuzzTarget {
+                name: "geometry_lib_call_distance".to_string(),
+                kind: FuzzTargetKind::Call {
+                    crate_name: "geometry_lib".to_string(),
+                    entry_point: "uniffi_geometry_remote_func_distance".to_string(),
+                },
+            }]
+        );
+        let source = target_source(&targets[0]);
+        assert!(source.contains("extern crate geometry_lib;\n"));
+        assert!(source.contains("    fn uniffi_geometry_remote_func_distance(\n"));
+        assert!(source.contains(
+            "    unsafe { uniffi::fuzz_remote_call(uniffi_geometry_remote_func_distance, data) };\n"
+        ));
+    }
+
+    #[test]
+    fn uniffi_dependencies() {
+        assert_eq!(
+            uniffi_dependency(Some(Utf8Path::new("/src/uniffi-rs/uniffi")), "^0.24", false),
+            "uniffi = { path = \"/src/uniffi-rs/uniffi\" }\n"
+        );
+        assert_eq!(
+            uniffi_dependency(None, "^0.24", true),
+            "uniffi = { version = \"^0.24\", features = [\"unstable-remote-calls\"] }\n"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # IDE index
+//!
+//! A machine-readable index of the items exported by a component, for IDE plugins.  Each item is
+//! mapped to where it's defined in the Rust code and to the symbols generated for it in each
+//! language, which lets an IDE jump between the Rust definition and the generated Kotlin or Swift
+//! code.
+//!
+//! The index is written next to the bindings, as `<namespace>.uniffi-index.json`, when
+//! `uniffi-bindgen generate` is passed `--ide-index`.  The format is described in the manual, any
+//! incompatible change to it bumps [SCHEMA_VERSION].
+//!
+//! Rust locations are only known for the items exported with the proc-macros, items defined in
+//! UDL have a `null` location.
+
+use crate::{
+    bindings::{kotlin, python, ruby, swift, TargetLanguage},
+    interface::ComponentInterface,
+    Config, Result,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the index format, stored in the `schema_version` field
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdeIndex {
+    pub schema_version: u32,
+    pub namespace: String,
+    pub items: Vec<IdeItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdeItem {
+    /// The item's name, or `Type.name` for methods and constructors
+    pub name: String,
+    pub kind: ItemKind,
+    /// Where the item is defined, `None` for items defined in UDL
+    pub rust: Option<RustLocation>,
+    /// The fully-qualified name of the generated symbol, keyed by language
+    ///
+    /// Languages that don't generate a symbol for the item are left out.
+    pub symbols: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Function,
+    Object,
+    Record,
+    Enum,
+    Error,
+    Flags,
+    CallbackInterface,
+    Constructor,
+    Method,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RustLocation {
+    pub module_path: String,
+    /// Path of the source file, as returned by `file!()`
+    pub file: String,
+    pub line: u32,
+}
+
+/// An exported item, as passed to the language backends to get its generated symbol
+pub(crate) struct ItemRef<'a> {
+    pub kind: ItemKind,
+    /// The object or callback interface, for methods and constructors
+    pub parent: Option<&'a str>,
+    pub name: &'a str,
+    /// The sub-namespace, for functions that were exported into one
+    pub sub_namespace: Option<&'a str>,
+}
+
+impl<'a> ItemRef<'a> {
+    fn new(kind: ItemKind, name: &'a str) -> Self {
+        Self {
+            kind,
+            parent: None,
+            name,
+            sub_namespace: None,
+        }
+    }
+
+    fn member(kind: ItemKind, parent: &'a str, name: &'a str) -> Self {
+        Self {
+            kind,
+            parent: Some(parent),
+            name,
+            sub_namespace: None,
+        }
+    }
+
+    /// The name used in the index, and to look up the source location
+    fn qualified_name(&self) -> String {
+        match self.parent {
+            Some(parent) => format!("{parent}.{}", self.name),
+            None => self.name.to_string(),
+        }
+    }
+}
+
+impl IdeIndex {
+    /// Build the index for a component, with the symbols generated for `languages`
+    pub fn new(ci: &ComponentInterface, config: &Config, languages: &[TargetLanguage]) -> Self {
+        let items = exported_items(ci)
+            .into_iter()
+            .map(|item| {
+                let name = item.qualified_name();
+                let rust = ci.source_location(&name).map(|loc| RustLocation {
+                    module_path: loc.module_path.clone(),
+                    file: loc.file.clone(),
+                    line: loc.line,
+                });
+                let symbols = languages
+                    .iter()
+                    .filter_map(|&language| {
+                        symbol(config, ci, language, &item).map(|s| (language.to_string(), s))
+                    })
+                    .collect();
+                IdeItem {
+                    name,
+                    kind: item.kind,
+                    rust,
+                    symbols,
+                }
+            })
+            .collect();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            namespace: ci.namespace().to_string(),
+            items,
+        }
+    }
+}
+
+/// Write the index for a component to `out_dir`, returning the path of the written file
+pub fn write_ide_index(
+    ci: &ComponentInterface,
+    config: &Config,
+    languages: &[TargetLanguage],
+    out_dir: &Utf8Path,
+) -> Result<Utf8PathBuf> {
+    let index = IdeIndex::new(ci, config, languages);
+    let path = out_dir.join(format!("{}.uniffi-index.json", ci.namespace()));
+    fs::create_dir_all(out_dir)?;
+    fs::write(&path, serde_json::to_string_pretty(&index)?)?;
+    Ok(path)
+}
+
+fn exported_items(ci: &ComponentInterface) -> Vec<ItemRef<'_>> {
+    let mut items = vec![];
+    for func in ci.function_definitions() {
+        items.push(ItemRef {
+            sub_namespace: func.sub_namespace(),
+            ..ItemRef::new(ItemKind::Function, func.name())
+        });
+    }
+    for obj in ci.object_definitions() {
+        items.push(ItemRef::new(ItemKind::Object, obj.name()));
+        for cons in obj.constructors() {
+            items.push(ItemRef::member(
+                ItemKind::Constructor,
+                obj.name(),
+                cons.name(),
+            ));
+        }
+        for meth in obj.methods() {
+            items.push(ItemRef::member(ItemKind::Method, obj.name(), meth.name()));
+        }
+    }
+    for rec in ci.record_definitions() {
+        items.push(ItemRef::new(ItemKind::Record, rec.name()));
+    }
+    for e in ci.enum_definitions() {
+        let kind = if ci.is_name_used_as_error(e.name()) {
+            ItemKind::Error
+        } else {
+            ItemKind::Enum
+        };
+        items.push(ItemRef::new(kind, e.name()));
+    }
+    for flags in ci.flags_definitions() {
+        items.push(ItemRef::new(ItemKind::Flags, flags.name()));
+    }
+    for cbi in ci.callback_interface_definitions() {
+        items.push(ItemRef::new(ItemKind::CallbackInterface, cbi.name()));
+        for meth in cbi.methods() {
+            items.push(ItemRef::member(ItemKind::Method, cbi.name(), meth.name()));
+        }
+    }
+    items
+}
+
+fn symbol(
+    config: &Config,
+    ci: &ComponentInterface,
+    language: TargetLanguage,
+    item: &ItemRef<'_>,
+) -> Option<String> {
+    let config = &config.bindings;
+    match language {
+        TargetLanguage::Kotlin => Some(kotlin::gen_kotlin::ide_symbol(&config.kotlin, item)),
+        TargetLanguage::Swift => Some(swift::gen_swift::ide_symbol(&config.swift, item)),
+        TargetLanguage::Python => Some(python::gen_python::ide_symbol(&config.python, item)),
+        TargetLanguage::Ruby => ruby::gen_ruby::ide_symbol(ci, item),
+        // WIT definitions don't have fully-qualified names an IDE could look up
+        TargetLanguage::Wit => None,
+        // The C header only declares the FFI functions, not the items of the component
+        TargetLanguage::C => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ide_index() {
+        const UDL: &str = r#"
+            namespace test {
+                u32 get_count();
+            };
+
+            interface CounterStore {
+                constructor();
+                [Name=with_capacity]
+                constructor(u32 capacity);
+                void increment_count();
+            };
+
+            [Error]
+            enum StoreError { "Full" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        // Like `uniffi-bindgen generate`, fill in the package and module names from the component
+        let mut config = Config::default();
+        config.update_from_ci(&ci);
+        let index = IdeIndex::new(
+            &ci,
+            &config,
+            &[
+                TargetLanguage::Kotlin,
+                TargetLanguage::Swift,
+                TargetLanguage::Python,
+                TargetLanguage::Ruby,
+                TargetLanguage::Wit,
+            ],
+        );
+        assert_eq!(index.schema_version, SCHEMA_VERSION);
+        assert_eq!(index.namespace, "test");
+
+        let symbols = |name: &str| {
+            let item = index.items.iter().find(|i| i.name == name).unwrap();
+            // UDL items don't have a source location
+            assert_eq!(item.rust, None);
+            item.symbols
+                .iter()
+                .map(|(lang, s)| (lang.as_str(), s.as_str()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            symbols("get_count"),
+            vec![
+                ("kotlin", "uniffi.test.getCount"),
+                ("python", "test.get_count"),
+                ("ruby", "Test.get_count"),
+                ("swift", "test.getCount"),
+            ]
+        );
+        assert_eq!(
+            symbols("CounterStore.new"),
+            vec![
+                ("kotlin", "uniffi.test.CounterStore.<init>"),
+                ("python", "test.CounterStore.__init__"),
+                ("ruby", "Test::CounterStore#initialize"),
+                ("swift", "test.CounterStore.init"),
+            ]
+        );
+        assert_eq!(
+            symbols("CounterStore.with_capacity"),
+            vec![
+                ("kotlin", "uniffi.test.CounterStore.withCapacity"),
+                ("python", "test.CounterStore.with_capacity"),
+                ("ruby", "Test::CounterStore.with_capacity"),
+                ("swift", "test.CounterStore.withCapacity"),
+            ]
+        );
+        assert_eq!(
+            symbols("CounterStore.increment_count"),
+            vec![
+                ("kotlin", "uniffi.test.CounterStore.incrementCount"),
+                ("python", "test.CounterStore.increment_count"),
+                ("ruby", "Test::CounterStore#increment_count"),
+                ("swift", "test.CounterStore.incrementCount"),
+            ]
+        );
+        assert_eq!(
+            symbols("StoreError"),
+            vec![
+                ("kotlin", "uniffi.test.StoreException"),
+                ("python", "test.StoreError"),
+                ("ruby", "Test::StoreError"),
+                ("swift", "test.StoreError"),
+            ]
+        );
+    }
+}
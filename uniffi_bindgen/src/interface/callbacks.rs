@@ -36,7 +36,7 @@
 use uniffi_meta::Checksum;
 
 use super::ffi::{FfiArgument, FfiFunction, FfiType};
-use super::object::Method;
+use super::object::{Method, TraitConstant};
 use super::{AsType, Type, TypeIterator};
 
 #[derive(Debug, Clone, Checksum)]
@@ -44,6 +44,13 @@ pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) methods: Vec<Method>,
+    pub(super) constants: Vec<TraitConstant>,
+    // The calls are made on the foreign main thread, which only changes the foreign side
+    #[checksum_ignore]
+    pub(super) main_thread: bool,
+    // Closures use the same FFI as other callback interfaces, only the foreign API differs
+    #[checksum_ignore]
+    pub(super) closure: bool,
     // We don't include the FFIFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -52,6 +59,11 @@ pub struct CallbackInterface {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_init_callback: FfiFunction,
+    #[checksum_ignore]
+    pub(super) ffi_detach_callback: FfiFunction,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl CallbackInterface {
@@ -60,7 +72,12 @@ impl CallbackInterface {
             name,
             module_path: Default::default(),
             methods: Default::default(),
+            constants: Default::default(),
+            main_thread: false,
+            closure: false,
             ffi_init_callback: Default::default(),
+            ffi_detach_callback: Default::default(),
+            docstring: None,
         }
     }
 
@@ -68,14 +85,49 @@ impl CallbackInterface {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn methods(&self) -> Vec<&Method> {
         self.methods.iter().collect()
     }
 
+    /// Should the bindings call the foreign implementation on the main thread?
+    ///
+    /// Calls from other threads are handed over to the main thread, and wait for it to run them.
+    pub fn main_thread(&self) -> bool {
+        self.main_thread
+    }
+
+    /// Is this the type of a closure argument?
+    ///
+    /// These are generated for exported functions that take an `impl Fn(...)` or
+    /// `Box<dyn Fn(...)>`.  The bindings present them as a function type, which the foreign code
+    /// calls instead of the only method, `invoke`.
+    pub fn is_closure(&self) -> bool {
+        self.closure
+    }
+
+    /// Do any of the methods have a default implementation?
+    pub fn has_default_methods(&self) -> bool {
+        self.methods.iter().any(|m| m.has_default())
+    }
+
+    pub fn constants(&self) -> Vec<&TraitConstant> {
+        self.constants.iter().collect()
+    }
+
     pub fn ffi_init_callback(&self) -> &FfiFunction {
         &self.ffi_init_callback
     }
 
+    /// The function that the bindings call when the foreign code detaches an object that Rust
+    /// may still hold, see `ForeignCallbackInternals::detach()`
+    pub fn ffi_detach_callback(&self) -> &FfiFunction {
+        &self.ffi_detach_callback
+    }
+
     pub(super) fn derive_ffi_funcs(&mut self, ci_namespace: &str) {
         self.ffi_init_callback.name =
             uniffi_meta::init_callback_fn_symbol_name(ci_namespace, &self.name);
@@ -84,10 +136,40 @@ impl CallbackInterface {
             type_: FfiType::ForeignCallback,
         }];
         self.ffi_init_callback.return_type = None;
+        self.ffi_detach_callback.name =
+            uniffi_meta::detach_callback_fn_symbol_name(ci_namespace, &self.name);
+        self.ffi_detach_callback.arguments = vec![FfiArgument {
+            name: "handle".to_string(),
+            type_: FfiType::UInt64,
+        }];
+        self.ffi_detach_callback.return_type = None;
+        for method in self.methods.iter_mut().filter(|m| m.has_default()) {
+            method.derive_default_ffi_func();
+        }
+    }
+
+    /// The FFI functions for this callback interface
+    ///
+    /// These are the functions to initialize the callback and to detach objects, plus one function
+    /// for each method with a default implementation.
+    pub fn iter_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> {
+        [&self.ffi_init_callback, &self.ffi_detach_callback]
+            .into_iter()
+            .chain(
+                self.methods
+                    .iter()
+                    .filter(|m| m.has_default())
+                    .map(|m| m.ffi_func()),
+            )
     }
 
     pub fn iter_types(&self) -> TypeIterator<'_> {
-        Box::new(self.methods.iter().flat_map(Method::iter_types))
+        Box::new(
+            self.methods
+                .iter()
+                .flat_map(Method::iter_types)
+                .chain(self.constants.iter().flat_map(TraitConstant::iter_types)),
+        )
     }
 }
 
@@ -100,6 +182,17 @@ impl AsType for CallbackInterface {
     }
 }
 
+impl From<uniffi_meta::CallbackInterfaceMetadata> for CallbackInterface {
+    fn from(meta: uniffi_meta::CallbackInterfaceMetadata) -> Self {
+        Self {
+            main_thread: meta.main_thread,
+            closure: meta.closure,
+            docstring: meta.docstring,
+            ..Self::new(meta.name)
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::ComponentInterface;
@@ -146,4 +239,28 @@ mod test {
         assert_eq!(callbacks_two.methods()[0].name(), "two");
         assert_eq!(callbacks_two.methods()[1].name(), "too");
     }
+
+    #[test]
+    fn test_main_thread() {
+        const UDL: &str = r#"
+            namespace test{};
+            [MainThread]
+            callback interface Delegate {
+                void update();
+            };
+            callback interface Listener {
+                void update();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci
+            .get_callback_interface_definition("Delegate")
+            .unwrap()
+            .main_thread());
+        assert!(!ci
+            .get_callback_interface_definition("Listener")
+            .unwrap()
+            .main_thread());
+        assert!(ci.has_main_thread_callback_interfaces());
+    }
 }
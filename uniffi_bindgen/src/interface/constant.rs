@@ -0,0 +1,52 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Constant definitions for a `ComponentInterface`.
+//!
+//! Constants are exported with `#[uniffi::export]` on a `const` item, they can't be declared in
+//! UDL.  Their value is part of the interface, so the bindings declare them directly rather than
+//! calling into Rust.
+
+use super::{AsType, Literal, Type, TypeIterator};
+use uniffi_meta::Checksum;
+
+/// Represents an exported constant.
+///
+/// Constants are primitives or strings, with a literal value.
+#[derive(Debug, Clone, Checksum)]
+pub struct Constant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+}
+
+impl Constant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Literal {
+        &self.value
+    }
+
+    pub fn iter_types(&self) -> TypeIterator<'_> {
+        self.type_.iter_types()
+    }
+}
+
+impl AsType for Constant {
+    fn as_type(&self) -> Type {
+        self.type_.clone()
+    }
+}
+
+impl From<uniffi_meta::ConstantMetadata> for Constant {
+    fn from(meta: uniffi_meta::ConstantMetadata) -> Self {
+        Self {
+            name: meta.name,
+            type_: meta.ty,
+            value: meta.value,
+        }
+    }
+}
@@ -154,8 +154,13 @@
 //! assert!(ci.is_name_used_as_error(err.name()));
 //! # Ok::<(), anyhow::Error>(())
 //! ```
+//!
+//! Enums that are `#[non_exhaustive]` in Rust get an extra `Unknown` variant in the bindings,
+//! for variants that were added after the bindings were generated.  To make that possible, Rust
+//! writes the size of the fields after the variant index, so the bindings can skip the fields of
+//! variants they don't know.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use uniffi_meta::Checksum;
 
 use super::record::Field;
@@ -189,6 +194,10 @@ pub struct Enum {
     // * For an Enum not used as an error but which has no variants with data, `flat` will be
     //   false when generating the scaffolding but `true` when generating bindings.
     pub(super) flat: bool,
+    pub(super) non_exhaustive: bool,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Enum {
@@ -196,6 +205,10 @@ impl Enum {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn variants(&self) -> &[Variant] {
         &self.variants
     }
@@ -204,6 +217,21 @@ impl Enum {
         self.flat
     }
 
+    /// Can Rust add variants that the bindings don't know about?
+    ///
+    /// If so, the bindings have an `Unknown` variant that unknown variants are lifted to.
+    pub fn is_non_exhaustive(&self) -> bool {
+        self.non_exhaustive
+    }
+
+    /// Does this enum have explicit discriminants?
+    ///
+    /// If so, every variant has a `discr()`, following the Rust rules for variants that don't
+    /// list one: the first is `0` and the others are one more than the previous variant.
+    pub fn has_discriminants(&self) -> bool {
+        self.variants.iter().any(|v| v.discr.is_some())
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.variants.iter().flat_map(Variant::iter_types))
     }
@@ -215,15 +243,33 @@ impl Enum {
         // is actually flat.
         // Real enums are considered flat iff they are actually flat.
         // We don't have that context here, so this is handled by our caller.
+        let mut variants: Vec<Variant> = meta
+            .variants
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_>>()?;
+        if meta.non_exhaustive && variants.iter().any(|v| v.name == "Unknown") {
+            bail!(
+                "`{}` is non-exhaustive, so it can't have an `Unknown` variant: that's the variant \
+                 the bindings use for variants they don't know about",
+                meta.name
+            );
+        }
+        if variants.iter().any(|v| v.discr.is_some()) {
+            let mut next_discr = 0;
+            for variant in variants.iter_mut() {
+                let discr = variant.discr.unwrap_or(next_discr);
+                variant.discr = Some(discr);
+                next_discr = discr.wrapping_add(1);
+            }
+        }
         Ok(Self {
             name: meta.name,
             module_path: meta.module_path,
-            variants: meta
-                .variants
-                .into_iter()
-                .map(TryInto::try_into)
-                .collect::<Result<_>>()?,
+            variants,
             flat,
+            non_exhaustive: meta.non_exhaustive,
+            docstring: meta.docstring,
         })
     }
 }
@@ -243,6 +289,7 @@ impl AsType for Enum {
 #[derive(Debug, Clone, Default, PartialEq, Eq, Checksum)]
 pub struct Variant {
     pub(super) name: String,
+    pub(super) discr: Option<i64>,
     pub(super) fields: Vec<Field>,
 }
 
@@ -251,6 +298,11 @@ impl Variant {
         &self.name
     }
 
+    /// The discriminant of this variant, if the enum has explicit discriminants
+    pub fn discr(&self) -> Option<i64> {
+        self.discr
+    }
+
     pub fn fields(&self) -> &[Field] {
         &self.fields
     }
@@ -270,6 +322,7 @@ impl TryFrom<uniffi_meta::VariantMetadata> for Variant {
     fn try_from(meta: uniffi_meta::VariantMetadata) -> Result<Self> {
         Ok(Self {
             name: meta.name,
+            discr: meta.discr,
             fields: meta
                 .fields
                 .into_iter()
@@ -443,6 +496,59 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_discriminants() {
+        let variant = |name: &str, discr| uniffi_meta::VariantMetadata {
+            name: name.to_string(),
+            discr,
+            fields: vec![],
+        };
+        let e = Enum::try_from_meta(
+            uniffi_meta::EnumMetadata {
+                module_path: "test".to_string(),
+                name: "Testing".to_string(),
+                variants: vec![
+                    variant("one", None),
+                    variant("two", Some(5)),
+                    variant("three", None),
+                    variant("four", Some(-1)),
+                    variant("five", None),
+                ],
+                non_exhaustive: false,
+                docstring: None,
+            },
+            true,
+        )
+        .unwrap();
+        assert!(e.has_discriminants());
+        assert_eq!(
+            e.variants().iter().map(|v| v.discr()).collect::<Vec<_>>(),
+            vec![Some(0), Some(5), Some(6), Some(-1), Some(0)]
+        );
+    }
+
+    #[test]
+    fn test_non_exhaustive() {
+        let meta = |variants: &[&str]| uniffi_meta::EnumMetadata {
+            module_path: "test".to_string(),
+            name: "Testing".to_string(),
+            variants: variants
+                .iter()
+                .map(|name| uniffi_meta::VariantMetadata {
+                    name: name.to_string(),
+                    discr: None,
+                    fields: vec![],
+                })
+                .collect(),
+            non_exhaustive: true,
+            docstring: None,
+        };
+        let e = Enum::try_from_meta(meta(&["One", "Two"]), true).unwrap();
+        assert!(e.is_non_exhaustive());
+        // `Unknown` is reserved for the variants that the bindings don't know about
+        assert!(Enum::try_from_meta(meta(&["One", "Unknown"]), true).is_err());
+    }
+
     // Tests for [Error], which are represented as `Enum`
     #[test]
     fn test_variants() {
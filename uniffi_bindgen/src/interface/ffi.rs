@@ -18,6 +18,7 @@
 /// For the types that involve memory allocation, we make a distinction between
 /// "owned" types (the recipient must free it, or pass it to someone else) and
 /// "borrowed" types (the sender must keep it alive for the duration of the call).
+use super::Argument;
 use uniffi_meta::{ExternalKind, Type};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -62,6 +63,10 @@ pub enum FfiType {
     },
     /// Opaque pointer passed to the FutureCallback
     FutureCallbackData,
+    /// A `#[repr(C)]` struct passed by value, with the same fields as the record it represents.
+    /// This is used instead of a `RustBuffer` for records that only have numeric fields and are
+    /// marked `[ReprC]` or `#[repr(C)]`.  The inner string is the name of the record.
+    Struct(String),
     // TODO: you can imagine a richer structural typesystem here, e.g. `Ref<String>` or something.
     // We don't need that yet and it's possible we never will, so it isn't here for now.
 }
@@ -87,6 +92,8 @@ impl From<&Type> for FfiType {
             Type::Float64 => FfiType::Float64,
             // Booleans lower into an Int8, to work around a bug in JNA.
             Type::Boolean => FfiType::Int8,
+            // Chars are passed as their Unicode scalar value.
+            Type::Char => FfiType::UInt32,
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FfiType::RustBuffer(None),
@@ -97,15 +104,26 @@ impl From<&Type> for FfiType {
             Type::Object { name, .. } => FfiType::RustArcPtr(name.to_owned()),
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface { .. } => FfiType::UInt64,
+            // Flags are passed as their bits.
+            Type::Flags { .. } => FfiType::UInt32,
             Type::ForeignExecutor => FfiType::ForeignExecutorHandle,
+            // URLs, decimals and JSON values are passed as strings.
+            Type::Url | Type::Decimal | Type::Json => FfiType::RustBuffer(None),
             // Other types are serialized into a bytebuffer and deserialized on the other side.
             Type::Enum { .. }
             | Type::Record { .. }
             | Type::Optional { .. }
             | Type::Sequence { .. }
+            | Type::Compressed { .. }
+            | Type::Shared { .. }
+            | Type::Weak { .. }
             | Type::Map { .. }
             | Type::Timestamp
-            | Type::Duration => FfiType::RustBuffer(None),
+            | Type::Duration
+            | Type::Uuid
+            | Type::Path
+            | Type::IpAddr
+            | Type::SocketAddr => FfiType::RustBuffer(None),
             Type::External {
                 name,
                 kind: ExternalKind::Interface,
@@ -116,6 +134,10 @@ impl From<&Type> for FfiType {
                 kind: ExternalKind::DataClass,
                 ..
             } => FfiType::RustBuffer(Some(name.clone())),
+            Type::External {
+                kind: ExternalKind::Flags,
+                ..
+            } => FfiType::UInt32,
             Type::Custom { builtin, .. } => FfiType::from(builtin.as_ref()),
         }
     }
@@ -140,7 +162,7 @@ impl From<&&Type> for FfiType {
 /// from the high-level interface. Each callable thing in the component API will have a
 /// corresponding `FfiFunction` through which it can be invoked, and UniFFI also provides
 /// some built-in `FfiFunction` helpers for use in the foreign language bindings.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiFunction {
     pub(super) name: String,
     pub(super) is_async: bool,
@@ -212,6 +234,41 @@ impl FfiFunction {
     }
 }
 
+impl FfiFunction {
+    /// Pass `#[repr(C)]` records as structs rather than in a `RustBuffer`
+    ///
+    /// `From<&Type>` can't tell those records apart from other records, so this is called once
+    /// the function has been initialized.  `struct_type` returns the `FfiType::Struct` to use for
+    /// a type, or `None` to leave it alone.
+    pub(super) fn use_struct_types(
+        &mut self,
+        arguments: &[Argument],
+        return_type: Option<&Type>,
+        struct_type: &dyn Fn(&Type) -> Option<FfiType>,
+    ) {
+        for ffi_arg in self.arguments.iter_mut() {
+            if let Some(type_) = arguments
+                .iter()
+                .find(|a| a.name == ffi_arg.name)
+                .and_then(|a| struct_type(&a.type_))
+            {
+                ffi_arg.type_ = type_;
+            }
+        }
+        if let Some(type_) = return_type.and_then(struct_type) {
+            if self.is_async {
+                for ffi_arg in self.arguments.iter_mut() {
+                    if let FfiType::FutureCallback { return_type } = &mut ffi_arg.type_ {
+                        **return_type = type_.clone();
+                    }
+                }
+            } else {
+                self.return_type = Some(type_);
+            }
+        }
+    }
+}
+
 impl Default for FfiFunction {
     fn default() -> Self {
         Self {
@@ -228,7 +285,7 @@ impl Default for FfiFunction {
 /// Represents an argument to an FFI function.
 ///
 /// Each argument has a name and a type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FfiArgument {
     pub(super) name: String,
     pub(super) type_: FfiType,
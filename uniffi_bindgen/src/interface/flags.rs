@@ -0,0 +1,93 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Flags definitions for a `ComponentInterface`.
+//!
+//! Flags are sets of named bits, exported with `#[derive(uniffi::Flags)]`.  They can't be
+//! declared in UDL.  The bindings present them as the language's idiomatic flag set type, with a
+//! constant for each named flag.
+
+use uniffi_meta::Checksum;
+
+use super::{AsType, Type};
+
+/// Represents a set of bit flags.
+///
+/// Flags are passed across the FFI as a `u32`.
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
+pub struct Flags {
+    pub(super) name: String,
+    pub(super) module_path: String,
+    pub(super) flags: Vec<Flag>,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
+}
+
+impl Flags {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    pub fn flags(&self) -> &[Flag] {
+        &self.flags
+    }
+
+    /// The bits of all the named flags combined
+    pub fn all_bits(&self) -> u32 {
+        self.flags.iter().fold(0, |bits, flag| bits | flag.value)
+    }
+}
+
+impl AsType for Flags {
+    fn as_type(&self) -> Type {
+        Type::Flags {
+            name: self.name.clone(),
+            module_path: self.module_path.clone(),
+        }
+    }
+}
+
+impl From<uniffi_meta::FlagsMetadata> for Flags {
+    fn from(meta: uniffi_meta::FlagsMetadata) -> Self {
+        Self {
+            name: meta.name,
+            module_path: meta.module_path,
+            flags: meta.flags.into_iter().map(Into::into).collect(),
+            docstring: meta.docstring,
+        }
+    }
+}
+
+/// Represents an individual named flag.
+///
+/// The value can have more than one bit set, for flags that combine other flags.
+#[derive(Debug, Clone, PartialEq, Eq, Checksum)]
+pub struct Flag {
+    pub(super) name: String,
+    pub(super) value: u32,
+}
+
+impl Flag {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+impl From<uniffi_meta::FlagMetadata> for Flag {
+    fn from(meta: uniffi_meta::FlagMetadata) -> Self {
+        Self {
+            name: meta.name,
+            value: meta.value,
+        }
+    }
+}
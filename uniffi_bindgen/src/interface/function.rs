@@ -59,11 +59,20 @@ pub struct Function {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_func: FfiFunction,
+    // Like `ffi_func`, this is determined by the other fields
+    #[checksum_ignore]
+    pub(super) remote_fn_name: String,
     pub(super) throws: Option<Type>,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
     pub(super) checksum: Option<u16>,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
+    // The sub-namespace only changes where the bindings put the function
+    #[checksum_ignore]
+    pub(super) sub_namespace: Option<String>,
 }
 
 impl Function {
@@ -71,6 +80,16 @@ impl Function {
         &self.name
     }
 
+    /// The sub-namespace that the function was exported into, from
+    /// `#[uniffi::export(namespace = "...")]`
+    pub fn sub_namespace(&self) -> Option<&str> {
+        self.sub_namespace.as_deref()
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn is_async(&self) -> bool {
         self.is_async
     }
@@ -91,6 +110,11 @@ impl Function {
         &self.ffi_func
     }
 
+    /// The name of the function's remote entry point, see `uniffi::remote_call()`
+    pub fn remote_fn_name(&self) -> &str {
+        &self.remote_fn_name
+    }
+
     pub fn checksum_fn_name(&self) -> &str {
         &self.checksum_fn_name
     }
@@ -142,6 +166,7 @@ impl From<uniffi_meta::FnParamMetadata> for Argument {
             by_ref: meta.by_ref,
             optional: meta.optional,
             default: meta.default,
+            sensitive: meta.sensitive,
         }
     }
 }
@@ -149,9 +174,10 @@ impl From<uniffi_meta::FnParamMetadata> for Argument {
 impl From<uniffi_meta::FnMetadata> for Function {
     fn from(meta: uniffi_meta::FnMetadata) -> Self {
         let ffi_name = meta.ffi_symbol_name();
+        let remote_fn_name = meta.remote_symbol_name();
         let checksum_fn_name = meta.checksum_symbol_name();
         let is_async = meta.is_async;
-        let return_type = meta.return_type.map(Into::into);
+        let return_type = meta.return_type;
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
 
         let ffi_func = FfiFunction {
@@ -166,9 +192,12 @@ impl From<uniffi_meta::FnMetadata> for Function {
             arguments,
             return_type,
             ffi_func,
+            remote_fn_name,
             throws: meta.throws,
             checksum_fn_name,
             checksum: meta.checksum,
+            docstring: meta.docstring,
+            sub_namespace: None,
         }
     }
 }
@@ -183,6 +212,7 @@ pub struct Argument {
     pub(super) by_ref: bool,
     pub(super) optional: bool,
     pub(super) default: Option<Literal>,
+    pub(super) sensitive: bool,
 }
 
 impl Argument {
@@ -194,6 +224,46 @@ impl Argument {
         self.by_ref
     }
 
+    pub fn sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Should the Rust scaffolding wipe the buffer this argument is lifted from?
+    ///
+    /// This is true for sensitive arguments that are deserialized out of a `RustBuffer`.
+    /// Strings (and custom types built on them) take ownership of the buffer when lifted,
+    /// so there's no copy left behind to wipe.  So do the types that are passed as strings.
+    ///
+    /// The foreign bindings use this to wipe the bytes they wrote for the argument.
+    pub fn wipe_lifted_buffer(&self) -> bool {
+        fn lifts_by_copy(type_: &Type) -> bool {
+            match type_ {
+                Type::String | Type::Url | Type::Decimal | Type::Json | Type::External { .. } => {
+                    false
+                }
+                Type::Custom { builtin, .. } => lifts_by_copy(builtin),
+                _ => matches!(FfiType::from(type_), FfiType::RustBuffer(_)),
+            }
+        }
+        self.sensitive && lifts_by_copy(&self.type_)
+    }
+
+    /// Is this a `[ByRef] bytes` argument?
+    ///
+    /// These are passed as a `ForeignBytes` pointing at the foreign bytes for the duration of the
+    /// call, rather than being copied into a `RustBuffer`, and the Rust function receives a `&[u8]`.
+    pub fn borrows_bytes(&self) -> bool {
+        self.by_ref && matches!(self.type_, Type::Bytes)
+    }
+
+    /// Is this a `[ByRef]` object argument?
+    ///
+    /// The Rust function borrows the foreign code's reference to these for the duration of the
+    /// call, rather than getting a clone of the `Arc`.
+    pub fn borrows_object(&self) -> bool {
+        self.by_ref && matches!(self.type_, Type::Object { .. })
+    }
+
     pub fn is_trait_ref(&self) -> bool {
         matches!(&self.type_, Type::Object { imp, .. } if *imp == ObjectImpl::Trait)
     }
@@ -217,7 +287,11 @@ impl From<&Argument> for FfiArgument {
     fn from(a: &Argument) -> FfiArgument {
         FfiArgument {
             name: a.name.clone(),
-            type_: (&a.type_).into(),
+            type_: if a.borrows_bytes() {
+                FfiType::ForeignBytes
+            } else {
+                (&a.type_).into()
+            },
         }
     }
 }
@@ -241,6 +315,9 @@ impl ResultType {
 
 /// Implemented by function-like types (Function, Method, Constructor)
 pub trait Callable {
+    /// The name of this callable in the interface, e.g. `function` or `Object.method`
+    fn qualified_name(&self) -> String;
+    fn docstring(&self) -> Option<&str>;
     fn arguments(&self) -> Vec<&Argument>;
     fn return_type(&self) -> Option<Type>;
     fn throws_type(&self) -> Option<Type>;
@@ -250,9 +327,50 @@ pub trait Callable {
             throws_type: self.throws_type(),
         }
     }
+    /// Does this callable have any `[ByRef]` object arguments?
+    fn borrows_objects(&self) -> bool {
+        self.arguments().iter().any(|arg| arg.borrows_object())
+    }
+    /// Does this callable consume the object?  Only methods can, see
+    /// `Method::takes_self_by_value()`.  The bindings lower the arguments of these before they
+    /// hand the object's pointer over to Rust.
+    fn takes_self_by_value(&self) -> bool {
+        false
+    }
+    /// The docstring to render in the bindings
+    ///
+    /// This is the docstring, followed by a note for each sensitive argument.  `arg_name` renders
+    /// the names of the arguments, since each language cases them differently.
+    fn docstring_with_notes(&self, arg_name: &dyn Fn(&str) -> String) -> Option<String> {
+        let notes = self
+            .arguments()
+            .iter()
+            .filter(|arg| arg.sensitive())
+            .map(|arg| {
+                format!(
+                    "`{}` is sensitive: the copies that the bindings make of it are wiped, \
+                     as far as the language allows, but the value that's passed in isn't.",
+                    arg_name(arg.name())
+                )
+            })
+            .collect::<Vec<_>>();
+        match (self.docstring(), notes.is_empty()) {
+            (docstring, true) => docstring.map(str::to_string),
+            (None, false) => Some(notes.join("\n\n")),
+            (Some(docstring), false) => Some(format!("{docstring}\n\n{}", notes.join("\n\n"))),
+        }
+    }
 }
 
 impl Callable for Function {
+    fn qualified_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
     fn arguments(&self) -> Vec<&Argument> {
         self.arguments()
     }
@@ -268,6 +386,14 @@ impl Callable for Function {
 
 // Needed because Askama likes to add extra refs to variables
 impl<T: Callable> Callable for &T {
+    fn qualified_name(&self) -> String {
+        (*self).qualified_name()
+    }
+
+    fn docstring(&self) -> Option<&str> {
+        (*self).docstring()
+    }
+
     fn arguments(&self) -> Vec<&Argument> {
         (*self).arguments()
     }
@@ -279,6 +405,10 @@ impl<T: Callable> Callable for &T {
     fn throws_type(&self) -> Option<Type> {
         (*self).throws_type()
     }
+
+    fn takes_self_by_value(&self) -> bool {
+        (*self).takes_self_by_value()
+    }
 }
 
 #[cfg(test)]
@@ -331,4 +461,76 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_sensitive_arguments() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void unlock([Sensitive] string passphrase, [Sensitive] Credentials creds, u32 attempt, [Sensitive] url server);
+            };
+            dictionary Credentials {
+                string user;
+                string password;
+            };
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("unlock").unwrap();
+        let args = func.arguments();
+        assert!(args[0].sensitive());
+        // Strings are lifted in-place, there's no copy to wipe.
+        assert!(!args[0].wipe_lifted_buffer());
+        assert!(args[1].sensitive());
+        assert!(args[1].wipe_lifted_buffer());
+        assert!(!args[2].sensitive());
+        assert!(!args[2].wipe_lifted_buffer());
+        // URLs are passed as strings
+        assert!(args[3].sensitive());
+        assert!(!args[3].wipe_lifted_buffer());
+        Ok(())
+    }
+
+    #[test]
+    fn test_borrowed_bytes_arguments() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void process([ByRef] bytes frame, bytes metadata);
+            };
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("process").unwrap();
+        let args = func.arguments();
+        assert!(args[0].borrows_bytes());
+        assert!(!args[1].borrows_bytes());
+        let ffi_args = func.ffi_func().arguments();
+        assert_eq!(ffi_args[0].type_(), FfiType::ForeignBytes);
+        assert!(matches!(ffi_args[1].type_(), FfiType::RustBuffer(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_borrowed_object_arguments() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void render([ByRef] Canvas canvas, Canvas backup, [ByRef] string title);
+            };
+            interface Canvas {};
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("render").unwrap();
+        let args = func.arguments();
+        assert!(args[0].borrows_object());
+        assert!(!args[1].borrows_object());
+        assert!(!args[2].borrows_object());
+        assert!(func.borrows_objects());
+        // Borrowed objects are passed as the same pointer as owned ones
+        let ffi_args = func.ffi_func().arguments();
+        assert_eq!(ffi_args[0].type_(), ffi_args[1].type_());
+        Ok(())
+    }
 }
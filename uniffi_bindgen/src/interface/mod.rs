@@ -34,6 +34,7 @@
 //!     the following checks:
 //!       * No duplicate names (types, methods, args, etc)
 //!       * No shadowing of builtin names, or names we use in code generation
+//!
 //!     We expect that if the user actually does one of these things, then they *should* get a compile
 //!     error when trying to build the component, because the codegen will be invalid. But we can't
 //!     guarantee that there's not some edge-case where it produces valid-but-incorrect code.
@@ -50,36 +51,45 @@ use std::{
 };
 
 use anyhow::{anyhow, bail, ensure, Result};
+use heck::ToUpperCamelCase;
 
 pub mod universe;
-pub use uniffi_meta::{AsType, ExternalKind, ObjectImpl, Type};
+pub use uniffi_meta::{AsType, ConcurrencyPolicy, ExternalKind, ObjectImpl, Type};
 use universe::{TypeIterator, TypeUniverse};
 
 mod callbacks;
 pub use callbacks::CallbackInterface;
+mod constant;
+pub use constant::Constant;
 mod enum_;
 pub use enum_::{Enum, Variant};
+mod flags;
+pub use flags::{Flag, Flags};
 mod function;
 pub use function::{Argument, Callable, Function, ResultType};
 mod namespace;
 pub use namespace::Namespace;
 mod object;
-pub use object::{Constructor, Method, Object, UniffiTrait};
+pub use object::{Constructor, Method, Object, TraitConstant, UniffiTrait};
 mod record;
 pub use record::{Field, Record};
+mod visibility;
 
 pub mod ffi;
 pub use ffi::{FfiArgument, FfiFunction, FfiType};
 pub use uniffi_meta::Radix;
 use uniffi_meta::{
-    ConstructorMetadata, LiteralMetadata, ObjectMetadata, TraitMethodMetadata,
-    UNIFFI_CONTRACT_VERSION,
+    CfgMetadata, ConstructorMetadata, LiteralMetadata, ObjectConcurrencyMetadata, ObjectMetadata,
+    SourceLocationMetadata, SubNamespaceMetadata, TraitConstantMetadata, TraitMethodMetadata,
+    UniffiTraitMetadata, UNIFFI_CONTRACT_VERSION,
 };
 pub type Literal = LiteralMetadata;
+pub type SourceLocation = SourceLocationMetadata;
+pub type Cfg = CfgMetadata;
 
 /// The main public interface for this module, representing the complete details of an interface exposed
 /// by a rust component and the details of consuming it via an extern-C FFI layer.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct ComponentInterface {
     /// All of the types used in the interface.
     // We can't checksum `self.types`, but its contents are implied by the other fields
@@ -88,13 +98,22 @@ pub struct ComponentInterface {
     /// The high-level API provided by the component.
     enums: BTreeMap<String, Enum>,
     records: BTreeMap<String, Record>,
+    flags: BTreeMap<String, Flags>,
     functions: Vec<Function>,
+    constants: Vec<Constant>,
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
     // Type names which were seen used as an error.
     errors: HashSet<String>,
     // Types which were seen used as callback interface error.
     callback_interface_throws_types: BTreeSet<Type>,
+    // Where the items exported with the proc-macros are defined, keyed by item name.
+    source_locations: BTreeMap<String, SourceLocation>,
+    // The `#[cfg(...)]` predicates of gated items, keyed by item name.  This includes the items
+    // that weren't compiled into the library.
+    cfgs: BTreeMap<String, Cfg>,
+    // The docstring of the namespace, which documents the component as a whole.
+    namespace_docstring: Option<String>,
 }
 
 impl ComponentInterface {
@@ -122,8 +141,11 @@ impl ComponentInterface {
                 self.types.namespace
             );
         }
+        if group.namespace.docstring.is_some() {
+            self.namespace_docstring = group.namespace.docstring.clone();
+        }
         // Unconditionally add the String type, which is used by the panic handling
-        self.types.add_known_type(&uniffi_meta::Type::String)?;
+        self.types.add_known_type(&Type::String)?;
         crate::macro_metadata::add_group_to_ci(self, group)?;
         Ok(())
     }
@@ -136,6 +158,11 @@ impl ComponentInterface {
         self.types.namespace.as_str()
     }
 
+    /// The docstring of the namespace, if the UDL file has one.
+    pub fn namespace_docstring(&self) -> Option<&str> {
+        self.namespace_docstring.as_deref()
+    }
+
     pub fn uniffi_contract_version(&self) -> u32 {
         // This is set by the scripts in the version-mismatch fixture
         let force_version = std::env::var("UNIFFI_FORCE_CONTRACT_VERSION");
@@ -145,6 +172,12 @@ impl ComponentInterface {
         }
     }
 
+    /// The version of UniFFI that generates the bindings, which they report when the library
+    /// doesn't match them.
+    pub fn uniffi_version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
     /// Get the definitions for every Enum type in the interface.
     pub fn enum_definitions(&self) -> impl Iterator<Item = &Enum> {
         self.enums.values()
@@ -165,6 +198,16 @@ impl ComponentInterface {
         self.records.get(name)
     }
 
+    /// Get the definitions for every Flags type in the interface.
+    pub fn flags_definitions(&self) -> impl Iterator<Item = &Flags> {
+        self.flags.values()
+    }
+
+    /// Get a Flags definition by name, or None if no such Flags type is defined.
+    pub fn get_flags_definition(&self, name: &str) -> Option<&Flags> {
+        self.flags.get(name)
+    }
+
     /// Get the definitions for every Function in the interface.
     pub fn function_definitions(&self) -> &[Function] {
         &self.functions
@@ -176,6 +219,38 @@ impl ComponentInterface {
         self.functions.iter().find(|f| f.name == name)
     }
 
+    /// Get the definitions of the functions that aren't in a sub-namespace, which the bindings
+    /// generate at the top level.
+    pub fn root_function_definitions(&self) -> Vec<&Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.sub_namespace.is_none())
+            .collect()
+    }
+
+    /// Get the names of the sub-namespaces that functions were exported into, sorted.
+    pub fn sub_namespaces(&self) -> Vec<&str> {
+        self.functions
+            .iter()
+            .filter_map(|f| f.sub_namespace())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Get the definitions of the functions in the sub-namespace `name`.
+    pub fn sub_namespace_function_definitions(&self, name: &str) -> Vec<&Function> {
+        self.functions
+            .iter()
+            .filter(|f| f.sub_namespace() == Some(name))
+            .collect()
+    }
+
+    /// Get the definitions for every exported constant in the interface.
+    pub fn constant_definitions(&self) -> &[Constant] {
+        &self.constants
+    }
+
     /// Get the definitions for every Object type in the interface.
     pub fn object_definitions(&self) -> &[Object] {
         &self.objects
@@ -192,12 +267,58 @@ impl ComponentInterface {
         &self.callback_interfaces
     }
 
+    /// Are any of the callback interfaces called on the foreign main thread?
+    pub fn has_main_thread_callback_interfaces(&self) -> bool {
+        self.callback_interfaces.iter().any(|cbi| cbi.main_thread())
+    }
+
+    /// Do any of the callback interfaces take foreign objects, rather than closures?
+    ///
+    /// The bindings let the foreign code detach these objects, see
+    /// `ForeignCallbackInternals::detach()`.
+    pub fn has_detachable_callback_interfaces(&self) -> bool {
+        self.callback_interfaces.iter().any(|cbi| !cbi.is_closure())
+    }
+
+    /// Is `ty` the type of a closure argument?
+    pub fn is_closure_type(&self, ty: &Type) -> bool {
+        match ty {
+            Type::CallbackInterface { name, .. } => {
+                self.get_callback_interface_definition(name)
+                    .map(CallbackInterface::is_closure)
+                    == Some(true)
+            }
+            _ => false,
+        }
+    }
+
     /// Get a Callback interface definition by name, or None if no such interface is defined.
     pub fn get_callback_interface_definition(&self, name: &str) -> Option<&CallbackInterface> {
         // TODO: probably we could store these internally in a HashMap to make this easier?
         self.callback_interfaces.iter().find(|o| o.name == name)
     }
 
+    /// Get the Rust source location of an item exported with the proc-macros
+    ///
+    /// `name` is the item's name, or `Type.name` for methods and constructors.  Items defined in
+    /// UDL don't have a source location.
+    pub fn source_location(&self, name: &str) -> Option<&SourceLocation> {
+        self.source_locations.get(name)
+    }
+
+    /// Get the `#[cfg(...)]` predicate of an item exported with the proc-macros
+    ///
+    /// `name` is the item's name, or `Type.name` for methods and constructors.  Items that aren't
+    /// gated don't have one.
+    pub fn cfg(&self, name: &str) -> Option<&Cfg> {
+        self.cfgs.get(name)
+    }
+
+    /// Get the gated items, both those that were compiled into the library and those that weren't
+    pub fn cfgs(&self) -> impl Iterator<Item = &Cfg> {
+        self.cfgs.values()
+    }
+
     /// Get the definitions for every Method type in the interface.
     pub fn iter_callables(&self) -> impl Iterator<Item = &dyn Callable> {
         // Each of the `as &dyn Callable` casts is a trivial cast, but it seems like the clearest
@@ -290,12 +411,61 @@ impl ComponentInterface {
             .any(|t| matches!(t, Type::Object { .. }))
     }
 
+    /// Check whether the given item contains any (possibly nested) handles: objects, callback
+    /// interfaces or foreign executors.
+    ///
+    /// Lifting a handle trusts the foreign code to pass a valid one, so these items can't be lifted
+    /// from arbitrary data.
+    pub fn item_contains_handles(&self, item: &Type) -> bool {
+        self.iter_types_in_item(item).any(|t| {
+            matches!(
+                t,
+                Type::Object { .. }
+                    | Type::CallbackInterface { .. }
+                    | Type::ForeignExecutor
+                    | Type::External {
+                        kind: ExternalKind::Interface,
+                        ..
+                    }
+            )
+        })
+    }
+
+    /// Check whether a function can be called through its remote entry point
+    ///
+    /// Remote calls send the arguments and the result over a transport, so they can only be made
+    /// to sync functions whose values are plain data.  Handles only make sense in the process that
+    /// created them, and shared and compressed values are read and written by the library.
+    pub fn is_remote_callable(&self, func: &Function) -> bool {
+        !func.is_async()
+            && func.iter_types().all(|t| {
+                !self.item_contains_handles(t)
+                    && !self
+                        .iter_types_in_item(t)
+                        .any(|t| matches!(t, Type::Shared { .. } | Type::Compressed { .. }))
+            })
+    }
+
+    /// The functions that can be called through their remote entry points, see
+    /// [Self::is_remote_callable]
+    pub fn remote_callable_functions(&self) -> impl Iterator<Item = &Function> {
+        self.functions.iter().filter(|f| self.is_remote_callable(f))
+    }
+
     /// Check whether the given item contains any (possibly nested) unsigned types
     pub fn item_contains_unsigned_types(&self, item: &Type) -> bool {
         self.iter_types_in_item(item)
             .any(|t| matches!(t, Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64))
     }
 
+    /// Check whether the given item contains any (possibly nested) JSON values
+    ///
+    /// Some languages represent these with types that can't be compared or hashed.
+    pub fn item_contains_json_values(&self, item: &Type) -> bool {
+        self.iter_types_in_item(item)
+            .any(|t| matches!(t, Type::Json))
+    }
+
     /// Check whether the interface contains any optional types
     pub fn contains_optional_types(&self) -> bool {
         self.types
@@ -339,6 +509,20 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function to get the UniFFI version and the contract version of the library.
+    /// The bindings call this to check that they match the scaffolding, and to say which versions
+    /// differ when they don't.
+    pub fn ffi_uniffi_contract_info(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_contract_info", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
@@ -413,6 +597,55 @@ impl ComponentInterface {
         }
     }
 
+    /// Builtin FFI function that reports the live object and callback interface handles.
+    /// This backs the `uniffiDebugLiveHandles()` function of the bindings, which lists the handles
+    /// when the `handle-diagnostics` feature is enabled.
+    pub fn ffi_uniffi_debug_live_handles(&self) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_uniffi_debug_live_handles", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![],
+            return_type: Some(FfiType::RustBuffer(None)),
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Builtin FFI function that upgrades a `WeakRef` handle.
+    /// This returns a new strong reference to the object, or 0 if it was dropped.
+    pub fn ffi_weak_ref_upgrade(&self) -> FfiFunction {
+        self.weak_ref_ffi_function("upgrade", Some(FfiType::UInt64))
+    }
+
+    /// Builtin FFI function that copies a `WeakRef` handle, to pass it to Rust.
+    pub fn ffi_weak_ref_clone(&self) -> FfiFunction {
+        self.weak_ref_ffi_function("clone", Some(FfiType::UInt64))
+    }
+
+    /// Builtin FFI function that frees a `WeakRef` handle.
+    pub fn ffi_weak_ref_free(&self) -> FfiFunction {
+        self.weak_ref_ffi_function("free", None)
+    }
+
+    fn weak_ref_ffi_function(&self, op: &str, return_type: Option<FfiType>) -> FfiFunction {
+        FfiFunction {
+            name: format!("ffi_{}_weak_ref_{op}", self.ffi_namespace()),
+            is_async: false,
+            arguments: vec![FfiArgument {
+                name: "handle".to_string(),
+                type_: FfiType::UInt64,
+            }],
+            return_type,
+            has_rust_call_status_arg: true,
+            is_object_free_function: false,
+        }
+    }
+
+    /// Does this interface use `Weak` references?
+    pub fn has_weak_refs(&self) -> bool {
+        self.iter_types().any(|t| matches!(t, Type::Weak { .. }))
+    }
+
     /// Does this interface contain async functions?
     pub fn has_async_fns(&self) -> bool {
         self.iter_ffi_function_definitions().any(|f| f.is_async())
@@ -422,11 +655,23 @@ impl ComponentInterface {
     pub fn iter_future_callback_params(&self) -> impl Iterator<Item = FfiType> {
         let unique_results = self
             .iter_callables()
-            .map(|c| c.result_type().future_callback_param())
+            .map(|c| self.future_callback_param(&c.result_type()))
             .collect::<BTreeSet<_>>();
         unique_results.into_iter()
     }
 
+    /// Get the `T` parameter for the `FutureCallback<T>` of an async function
+    ///
+    /// This is `result_type.future_callback_param()`, except that `#[repr(C)]` records are
+    /// passed as structs.
+    pub fn future_callback_param(&self, result_type: &ResultType) -> FfiType {
+        result_type
+            .return_type
+            .as_ref()
+            .and_then(|t| repr_c_struct_type(&self.records, t))
+            .unwrap_or_else(|| result_type.future_callback_param())
+    }
+
     /// Iterate over return/throws types for async functions
     pub fn iter_async_result_types(&self) -> impl Iterator<Item = ResultType> {
         let unique_results = self
@@ -446,7 +691,12 @@ impl ComponentInterface {
             .chain(self.iter_rust_buffer_ffi_function_definitions())
             .chain(self.iter_checksum_ffi_functions())
             .chain(self.ffi_foreign_executor_callback_set())
-            .chain([self.ffi_uniffi_contract_version()])
+            .chain(self.iter_weak_ref_ffi_function_definitions())
+            .chain([
+                self.ffi_uniffi_contract_version(),
+                self.ffi_uniffi_contract_info(),
+                self.ffi_uniffi_debug_live_handles(),
+            ])
     }
 
     /// List all FFI functions definitions for user-defined interfaces
@@ -455,6 +705,7 @@ impl ComponentInterface {
     ///   - Top-level functions
     ///   - Object methods
     ///   - Callback interfaces
+    ///   - Comparing records that forward `Ord`
     pub fn iter_user_ffi_function_definitions(&self) -> impl Iterator<Item = &FfiFunction> + '_ {
         iter::empty()
             .chain(
@@ -465,9 +716,10 @@ impl ComponentInterface {
             .chain(
                 self.callback_interfaces
                     .iter()
-                    .map(|cb| cb.ffi_init_callback()),
+                    .flat_map(|cb| cb.iter_ffi_function_definitions()),
             )
             .chain(self.functions.iter().map(|f| &f.ffi_func))
+            .chain(self.records.values().filter_map(Record::ffi_func_cmp))
     }
 
     /// List all FFI functions definitions for RustBuffer functionality.
@@ -481,6 +733,23 @@ impl ComponentInterface {
         .into_iter()
     }
 
+    /// List the FFI functions for `WeakRef` handles
+    ///
+    /// The scaffolding always exports these, but we only include them in the FFI if `Weak`
+    /// references are actually used
+    pub fn iter_weak_ref_ffi_function_definitions(&self) -> impl Iterator<Item = FfiFunction> {
+        let functions = if self.has_weak_refs() {
+            vec![
+                self.ffi_weak_ref_upgrade(),
+                self.ffi_weak_ref_clone(),
+                self.ffi_weak_ref_free(),
+            ]
+        } else {
+            vec![]
+        };
+        functions.into_iter()
+    }
+
     /// The ffi_foreign_executor_callback_set FFI function
     ///
     /// We only include this in the FFI if the `ForeignExecutor` type is actually used
@@ -596,6 +865,28 @@ impl ComponentInterface {
         Ok(())
     }
 
+    /// Adds a flags definition to the `ComponentInterface`.
+    pub(super) fn add_flags_definition(&mut self, defn: Flags) -> Result<()> {
+        match self.flags.entry(defn.name().to_owned()) {
+            Entry::Vacant(v) => {
+                v.insert(defn);
+            }
+            Entry::Occupied(o) => {
+                let existing_def = o.get();
+                if defn != *existing_def {
+                    bail!(
+                        "Mismatching definition for flags `{}`!\n\
+                         existing definition: {existing_def:#?},\n\
+                         new definition: {defn:#?}",
+                        defn.name(),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Called by `APIBuilder` impls to add a newly-parsed function definition to the `ComponentInterface`.
     pub(super) fn add_function_definition(&mut self, defn: Function) -> Result<()> {
         // Since functions are not a first-class type, we have to check for duplicates here
@@ -603,7 +894,7 @@ impl ComponentInterface {
         if self.functions.iter().any(|f| f.name == defn.name) {
             bail!("duplicate function definition: \"{}\"", defn.name);
         }
-        if !matches!(self.types.get_type_definition(defn.name()), None) {
+        if self.types.get_type_definition(defn.name()).is_some() {
             bail!("Conflicting type definition for \"{}\"", defn.name());
         }
         self.types.add_known_types(defn.iter_types())?;
@@ -616,6 +907,16 @@ impl ComponentInterface {
         Ok(())
     }
 
+    /// Adds an exported constant to the `ComponentInterface`.
+    pub(super) fn add_constant_definition(&mut self, defn: Constant) -> Result<()> {
+        if self.constants.iter().any(|c| c.name == defn.name) {
+            bail!("duplicate constant definition: \"{}\"", defn.name);
+        }
+        self.types.add_known_types(defn.iter_types())?;
+        self.constants.push(defn);
+        Ok(())
+    }
+
     pub(super) fn add_constructor_meta(&mut self, meta: ConstructorMetadata) -> Result<()> {
         let object = get_object(&mut self.objects, &meta.self_name)
             .ok_or_else(|| anyhow!("add_constructor_meta: object {} not found", &meta.self_name))?;
@@ -643,6 +944,65 @@ impl ComponentInterface {
         Ok(())
     }
 
+    pub(super) fn add_uniffitrait_meta(&mut self, meta: UniffiTraitMetadata) -> Result<()> {
+        let object = get_object(&mut self.objects, meta.self_name()).ok_or_else(|| {
+            anyhow!(
+                "add_uniffitrait_meta: object {} not found",
+                meta.self_name()
+            )
+        })?;
+        let ut: UniffiTrait = meta.into();
+        self.types.add_known_types(ut.iter_types())?;
+        object.uniffi_traits.push(ut);
+        Ok(())
+    }
+
+    pub(super) fn add_object_concurrency_meta(
+        &mut self,
+        meta: ObjectConcurrencyMetadata,
+    ) -> Result<()> {
+        let object = get_object(&mut self.objects, &meta.self_name).ok_or_else(|| {
+            anyhow!(
+                "add_object_concurrency_meta: object {} not found",
+                meta.self_name
+            )
+        })?;
+        match object.concurrency {
+            Some(policy) if policy != meta.policy => bail!(
+                "`{}` is declared both {} and {}, its impl blocks must agree",
+                meta.self_name,
+                policy.name(),
+                meta.policy.name(),
+            ),
+            _ => object.concurrency = Some(meta.policy),
+        }
+        object.concurrency_methods.extend(meta.methods);
+        Ok(())
+    }
+
+    pub(super) fn add_sub_namespace_meta(&mut self, meta: SubNamespaceMetadata) -> Result<()> {
+        let func = self
+            .functions
+            .iter_mut()
+            .find(|f| f.name == meta.fn_name)
+            .ok_or_else(|| {
+                anyhow!(
+                    "add_sub_namespace_meta: function {} not found",
+                    meta.fn_name
+                )
+            })?;
+        func.sub_namespace = Some(meta.namespace);
+        Ok(())
+    }
+
+    pub(super) fn add_source_location(&mut self, meta: SourceLocation) {
+        self.source_locations.insert(meta.name.clone(), meta);
+    }
+
+    pub(super) fn add_cfg(&mut self, meta: Cfg) {
+        self.cfgs.insert(meta.name.clone(), meta);
+    }
+
     pub(super) fn add_object_meta(&mut self, meta: ObjectMetadata) -> Result<()> {
         self.add_object_definition(meta.into())
     }
@@ -692,6 +1052,33 @@ impl ComponentInterface {
         Ok(())
     }
 
+    pub(super) fn add_trait_constant_meta(&mut self, meta: TraitConstantMetadata) -> Result<()> {
+        let name = &meta.trait_name;
+        let constants =
+            if let Some(cbi) = get_callback_interface(&mut self.callback_interfaces, name) {
+                &mut cbi.constants
+            } else if let Some(object) = get_object(&mut self.objects, name) {
+                &mut object.constants
+            } else {
+                bail!("add_trait_constant_meta: trait {name} not found");
+            };
+        // The index is used to read the constant from callback interface implementations, check
+        // that we're processing the constants in order like for the methods.
+        if constants.len() != meta.index as usize {
+            bail!(
+                "UniFFI internal error: trait constant index mismatch for {}::{} (expected {}, saw {})",
+                meta.trait_name,
+                meta.name,
+                constants.len(),
+                meta.index,
+            );
+        }
+        let constant: TraitConstant = meta.into();
+        self.types.add_known_types(constant.iter_types())?;
+        constants.push(constant);
+        Ok(())
+    }
+
     /// Perform global consistency checks on the declared interface.
     ///
     /// This method checks for consistency problems in the declared interface
@@ -705,11 +1092,39 @@ impl ComponentInterface {
         // Because functions aren't first class types, we need to check here that
         // a function name hasn't already been used as a type name.
         for f in self.functions.iter() {
-            if !matches!(self.types.get_type_definition(f.name()), None) {
+            if self.types.get_type_definition(f.name()).is_some() {
                 bail!("Conflicting type definition for \"{}\"", f.name());
             }
         }
 
+        // The bindings generate a sub-namespace next to the top-level items, as a module or a type
+        for ns in self.sub_namespaces() {
+            uniffi_meta::check_sub_namespace_name(ns)?;
+            if self.functions.iter().any(|f| f.name() == ns)
+                || self.constants.iter().any(|c| c.name() == ns)
+                || self.get_type(&ns.to_upper_camel_case()).is_some()
+            {
+                bail!("sub-namespace `{ns}` conflicts with an item of the same name");
+            }
+        }
+
+        for obj in self.objects.iter() {
+            if let Some(policy) = obj.concurrency {
+                if let Some(meth) = obj
+                    .methods
+                    .iter()
+                    .find(|m| !obj.concurrency_methods.contains(m.name()))
+                {
+                    bail!(
+                        "`{}` is declared {}, but `{}` is in an impl block that doesn't declare it",
+                        obj.name,
+                        policy.name(),
+                        meth.name(),
+                    );
+                }
+            }
+        }
+
         for ty in self.iter_types() {
             match ty {
                 Type::Object { name, .. } => {
@@ -730,6 +1145,28 @@ impl ComponentInterface {
                         "Enum `{name}` has no definition",
                     );
                 }
+                Type::Flags { name, .. } => {
+                    ensure!(
+                        self.flags.contains_key(name),
+                        "Flags `{name}` has no definition",
+                    );
+                }
+                // Map keys are hashed and compared by value in the bindings, which objects and
+                // callback interfaces can't be.  Keys that aren't `Hash + Eq` in Rust, like
+                // floats, are caught by an assertion in the scaffolding.
+                Type::Map { key_type, .. } => {
+                    for t in key_type.iter_types() {
+                        match t {
+                            Type::Object { name, .. } => {
+                                bail!("Object `{name}` can't be used in a map key")
+                            }
+                            Type::CallbackInterface { name, .. } => {
+                                bail!("Callback interface `{name}` can't be used in a map key")
+                            }
+                            _ => {}
+                        }
+                    }
+                }
                 _ => {}
             }
         }
@@ -752,8 +1189,52 @@ impl ComponentInterface {
         for callback in self.callback_interfaces.iter_mut() {
             callback.derive_ffi_funcs(&ci_namespace);
         }
+        for record in self.records.values_mut() {
+            record.derive_ffi_funcs(&ci_namespace);
+        }
+        self.derive_repr_c_struct_types();
         Ok(())
     }
+
+    /// Pass `#[repr(C)]` records by value as structs in the FFI functions
+    fn derive_repr_c_struct_types(&mut self) {
+        if !self.records.values().any(Record::is_repr_c) {
+            return;
+        }
+        let records = &self.records;
+        let struct_type = |t: &Type| repr_c_struct_type(records, t);
+        for func in self.functions.iter_mut() {
+            func.ffi_func.use_struct_types(
+                &func.arguments,
+                func.return_type.as_ref(),
+                &struct_type,
+            );
+        }
+        for obj in self.objects.iter_mut() {
+            for cons in obj.constructors.iter_mut() {
+                cons.ffi_func
+                    .use_struct_types(&cons.arguments, None, &struct_type);
+            }
+            for meth in obj.methods.iter_mut() {
+                meth.ffi_func.use_struct_types(
+                    &meth.arguments,
+                    meth.return_type.as_ref(),
+                    &struct_type,
+                );
+            }
+        }
+    }
+}
+
+// The `FfiType::Struct` for `type_` if it's a `#[repr(C)]` record
+fn repr_c_struct_type(records: &BTreeMap<String, Record>, type_: &Type) -> Option<FfiType> {
+    match type_ {
+        Type::Record { name, .. } => records
+            .get(name)
+            .filter(|r| r.is_repr_c())
+            .map(Record::ffi_type),
+        _ => None,
+    }
 }
 
 fn get_object<'a>(objects: &'a mut [Object], name: &str) -> Option<&'a mut Object> {
@@ -812,11 +1293,11 @@ impl<'a> RecursiveTypeIterator<'a> {
             Type::Record { name, .. }
             | Type::Enum { name, .. }
             | Type::Object { name, .. }
-            | Type::CallbackInterface { name, .. } => {
-                if !self.seen.contains(name.as_str()) {
-                    self.pending.push(type_);
-                    self.seen.insert(name.as_str());
-                }
+            | Type::CallbackInterface { name, .. }
+                if !self.seen.contains(name.as_str()) =>
+            {
+                self.pending.push(type_);
+                self.seen.insert(name.as_str());
             }
             _ => (),
         }
@@ -925,14 +1406,18 @@ mod test {
     variants: [
         Variant {
             name: \"one\",
+            discr: None,
             fields: [],
         },
         Variant {
             name: \"two\",
+            discr: None,
             fields: [],
         },
     ],
     flat: true,
+    non_exhaustive: false,
+    docstring: None,
 },
 new definition: Enum {
     name: \"Testing\",
@@ -940,14 +1425,18 @@ new definition: Enum {
     variants: [
         Variant {
             name: \"three\",
+            discr: None,
             fields: [],
         },
         Variant {
             name: \"four\",
+            discr: None,
             fields: [],
         },
     ],
     flat: true,
+    non_exhaustive: false,
+    docstring: None,
 }",
         );
 
@@ -1024,6 +1513,31 @@ new definition: Enum {
         assert!(ci.types.contains(&Type::Boolean));
     }
 
+    #[test]
+    fn test_map_keys() {
+        const UDL: &str = r#"
+            namespace test {
+                record<Color, u32> count_colors(sequence<Color> colors);
+                record<u32, Color> colors_by_id();
+            };
+            enum Color { "Red", "Green" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.contains_map_types());
+
+        const UDL2: &str = r#"
+            namespace test {
+                record<Thing, u32> count_things();
+            };
+            interface Thing {};
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            "Object `Thing` can't be used in a map key"
+        );
+    }
+
     #[test]
     fn test_no_infinite_recursion_when_walking_types() {
         const UDL: &str = r#"
@@ -1061,4 +1575,119 @@ new definition: Enum {
             imp: ObjectImpl::Struct,
         }));
     }
+
+    #[test]
+    fn test_constants() {
+        let constant = |name: &str, ty: Type, value: Literal| {
+            uniffi_meta::ConstantMetadata {
+                module_path: "test".to_string(),
+                name: name.to_string(),
+                ty,
+                value,
+            }
+            .into()
+        };
+        let mut group = uniffi_udl::parse_udl("namespace test {};").unwrap();
+        group.add_item(constant(
+            "MAX_SIZE",
+            Type::UInt32,
+            Literal::UInt(100, Radix::Decimal, Type::UInt32),
+        ));
+        group.add_item(constant(
+            "DEFAULT_NAME",
+            Type::String,
+            Literal::String("unnamed".to_string()),
+        ));
+        let ci = ComponentInterface::from_metadata(group).unwrap();
+        let names: Vec<_> = ci.constant_definitions().iter().map(|c| c.name()).collect();
+        assert_eq!(names, ["DEFAULT_NAME", "MAX_SIZE"]);
+        assert!(ci.iter_types().any(|t| *t == Type::UInt32));
+
+        let mut group = uniffi_udl::parse_udl("namespace test {};").unwrap();
+        group.add_item(constant(
+            "LIMIT",
+            Type::UInt8,
+            Literal::UInt(1, Radix::Decimal, Type::UInt8),
+        ));
+        group.add_item(constant(
+            "LIMIT",
+            Type::UInt8,
+            Literal::UInt(2, Radix::Decimal, Type::UInt8),
+        ));
+        let err = ComponentInterface::from_metadata(group).unwrap_err();
+        assert_eq!(err.to_string(), "duplicate constant definition: \"LIMIT\"");
+    }
+
+    #[test]
+    fn test_reserved_sub_namespaces() {
+        let check = |namespace: &str| {
+            let mut group = uniffi_udl::parse_udl("namespace test { void tester(); };").unwrap();
+            group.add_item(
+                SubNamespaceMetadata {
+                    module_path: "test".to_string(),
+                    fn_name: "tester".to_string(),
+                    namespace: namespace.to_string(),
+                }
+                .into(),
+            );
+            // `from_metadata()` checks the consistency of the interface
+            ComponentInterface::from_metadata(group).map(|_| ())
+        };
+        check("storage").unwrap();
+        // Keywords in one of the languages, and the modules that the Python bindings import
+        for namespace in ["class", "fun", "self", "sys", "types"] {
+            let err = check(namespace).unwrap_err();
+            assert_eq!(
+                err.root_cause().to_string(),
+                format!("`{namespace}` is a keyword or a name used by the bindings, it can't be a sub-namespace")
+            );
+        }
+        assert!(check("Storage").is_err());
+    }
+
+    #[test]
+    fn test_docstrings() {
+        const UDL: &str = r#"
+            /// The test namespace
+            namespace test {
+                /// A function
+                void tester();
+            };
+            /// An object
+            interface TestObj {
+                /// The constructor
+                constructor();
+                /// A method
+                ///
+                /// With a second paragraph
+                void method();
+            };
+            /// A callback interface
+            callback interface TestCallback {
+                /// A callback method
+                void callback();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(ci.namespace_docstring(), Some("The test namespace"));
+        assert_eq!(
+            ci.get_function_definition("tester").unwrap().docstring(),
+            Some("A function")
+        );
+        let obj = ci.get_object_definition("TestObj").unwrap();
+        assert_eq!(obj.docstring(), Some("An object"));
+        assert_eq!(
+            obj.primary_constructor().unwrap().docstring(),
+            Some("The constructor")
+        );
+        assert_eq!(
+            obj.get_method("method").docstring(),
+            Some("A method\n\nWith a second paragraph")
+        );
+        let cbi = ci
+            .get_callback_interface_definition("TestCallback")
+            .unwrap();
+        assert_eq!(cbi.docstring(), Some("A callback interface"));
+        assert_eq!(cbi.methods()[0].docstring(), Some("A callback method"));
+    }
 }
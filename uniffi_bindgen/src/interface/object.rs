@@ -57,14 +57,15 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::collections::BTreeSet;
 use std::iter;
 
 use anyhow::Result;
-use uniffi_meta::Checksum;
+use uniffi_meta::{Checksum, ConcurrencyPolicy};
 
 use super::ffi::{FfiArgument, FfiFunction, FfiType};
-use super::function::{Argument, Callable};
-use super::{AsType, ObjectImpl, Type, TypeIterator};
+use super::function::{Argument, Callable, Function};
+use super::{AsType, Literal, ObjectImpl, Type, TypeIterator};
 
 /// An "object" is an opaque type that is passed around by reference, can
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
@@ -92,6 +93,15 @@ pub struct Object {
     // a regular method (albeit with a generated name)
     // XXX - this should really be a HashSet, but not enough transient types support hash to make it worthwhile now.
     pub(super) uniffi_traits: Vec<UniffiTrait>,
+    // Constants declared in the exported trait, for trait interfaces
+    pub(super) constants: Vec<TraitConstant>,
+    // From `#[uniffi::export(serialized)]` or `#[uniffi::export(concurrent)]` on the impl blocks.
+    // This only changes how the scaffolding runs the calls, not the API.
+    #[checksum_ignore]
+    pub(super) concurrency: Option<ConcurrencyPolicy>,
+    // The methods in the impl blocks that declared `concurrency`
+    #[checksum_ignore]
+    pub(super) concurrency_methods: BTreeSet<String>,
     // We don't include the FfiFunc in the hash calculation, because:
     //  - it is entirely determined by the other fields,
     //    so excluding it is safe.
@@ -100,6 +110,9 @@ pub struct Object {
     //    avoids a weird circular dependency in the calculation.
     #[checksum_ignore]
     pub(super) ffi_func_free: FfiFunction,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Object {
@@ -107,6 +120,10 @@ impl Object {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     /// Returns the fully qualified name that should be used by Rust code for this object.
     /// Includes `r#`, traits get a leading `dyn`. If we ever supported associated types, then
     /// this would also include them.
@@ -139,6 +156,27 @@ impl Object {
         self.methods.iter().collect()
     }
 
+    pub fn has_method(&self, name: &str) -> bool {
+        self.methods.iter().any(|m| m.name() == name)
+    }
+
+    /// How the methods can be called from several threads, if it was declared
+    pub fn concurrency(&self) -> Option<ConcurrencyPolicy> {
+        self.concurrency
+    }
+
+    /// Can the object be shared between threads?  This is the case when the calls are serialized
+    /// or the object declared that it handles concurrent calls, unless a method can consume it
+    /// while it's in use by another thread.
+    pub fn is_thread_safe(&self) -> bool {
+        self.concurrency.is_some() && !self.has_consuming_methods()
+    }
+
+    /// Do any of the methods consume the object?
+    pub fn has_consuming_methods(&self) -> bool {
+        self.methods.iter().any(|m| m.takes_self_by_value())
+    }
+
     pub fn get_method(&self, name: &str) -> Method {
         let matches: Vec<_> = self.methods.iter().filter(|m| m.name() == name).collect();
         match matches.len() {
@@ -147,10 +185,30 @@ impl Object {
         }
     }
 
+    pub fn constants(&self) -> Vec<&TraitConstant> {
+        self.constants.iter().collect()
+    }
+
     pub fn uniffi_traits(&self) -> Vec<&UniffiTrait> {
         self.uniffi_traits.iter().collect()
     }
 
+    /// Does this object forward the `Ord` trait to the foreign code?
+    pub fn is_ord(&self) -> bool {
+        self.uniffi_traits
+            .iter()
+            .any(|t| matches!(t, UniffiTrait::Ord { .. }))
+    }
+
+    /// The static function that restores this object from a handle token, if it forwards the
+    /// `Restore` trait
+    pub fn restore_handle_fn(&self) -> Option<&Function> {
+        self.uniffi_traits.iter().find_map(|t| match t {
+            UniffiTrait::Restore { restore, .. } => Some(restore),
+            _ => None,
+        })
+    }
+
     pub fn ffi_object_free(&self) -> &FfiFunction {
         &self.ffi_func_free
     }
@@ -159,17 +217,16 @@ impl Object {
         iter::once(&self.ffi_func_free)
             .chain(self.constructors.iter().map(|f| &f.ffi_func))
             .chain(self.methods.iter().map(|f| &f.ffi_func))
-            .chain(
-                self.uniffi_traits
-                    .iter()
-                    .flat_map(|ut| match ut {
-                        UniffiTrait::Display { fmt: m }
-                        | UniffiTrait::Debug { fmt: m }
-                        | UniffiTrait::Hash { hash: m } => vec![m],
-                        UniffiTrait::Eq { eq, ne } => vec![eq, ne],
-                    })
-                    .map(|m| &m.ffi_func),
-            )
+            .chain(self.uniffi_traits.iter().flat_map(|ut| match ut {
+                UniffiTrait::Display { fmt: m }
+                | UniffiTrait::Debug { fmt: m }
+                | UniffiTrait::Hash { hash: m }
+                | UniffiTrait::Ord { cmp: m } => vec![&m.ffi_func],
+                UniffiTrait::Eq { eq, ne } => vec![&eq.ffi_func, &ne.ffi_func],
+                UniffiTrait::Restore { serialize, restore } => {
+                    vec![&serialize.ffi_func, &restore.ffi_func]
+                }
+            }))
     }
 
     pub fn derive_ffi_funcs(&mut self, ci_namespace: &str) -> Result<()> {
@@ -205,6 +262,7 @@ impl Object {
                 .map(Method::iter_types)
                 .chain(self.uniffi_traits.iter().map(UniffiTrait::iter_types))
                 .chain(self.constructors.iter().map(Constructor::iter_types))
+                .chain(self.constants.iter().map(TraitConstant::iter_types))
                 .flatten(),
         )
     }
@@ -230,10 +288,14 @@ impl From<uniffi_meta::ObjectMetadata> for Object {
             constructors: Default::default(),
             methods: Default::default(),
             uniffi_traits: meta.uniffi_traits.into_iter().map(Into::into).collect(),
+            constants: Default::default(),
+            concurrency: None,
+            concurrency_methods: Default::default(),
             ffi_func_free: FfiFunction {
                 name: ffi_free_name,
                 ..Default::default()
             },
+            docstring: meta.docstring,
         }
     }
 }
@@ -254,6 +316,13 @@ impl From<uniffi_meta::UniffiTraitMetadata> for UniffiTrait {
             uniffi_meta::UniffiTraitMetadata::Hash { hash } => {
                 UniffiTrait::Hash { hash: hash.into() }
             }
+            uniffi_meta::UniffiTraitMetadata::Ord { cmp } => UniffiTrait::Ord { cmp: cmp.into() },
+            uniffi_meta::UniffiTraitMetadata::Restore { serialize, restore } => {
+                UniffiTrait::Restore {
+                    serialize: serialize.into(),
+                    restore: restore.into(),
+                }
+            }
         }
     }
 }
@@ -281,6 +350,9 @@ pub struct Constructor {
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
     pub(super) checksum: Option<u16>,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Constructor {
@@ -288,6 +360,10 @@ impl Constructor {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -357,9 +433,10 @@ impl From<uniffi_meta::ConstructorMetadata> for Constructor {
             object_module_path: meta.module_path,
             arguments,
             ffi_func,
-            throws: meta.throws.map(Into::into),
+            throws: meta.throws,
             checksum_fn_name,
             checksum: meta.checksum,
+            docstring: meta.docstring,
         }
     }
 }
@@ -374,6 +451,8 @@ pub struct Method {
     pub(super) object_name: String,
     pub(super) object_module_path: String,
     pub(super) is_async: bool,
+    // Callback interface methods with a default implementation in Rust
+    pub(super) has_default: bool,
     pub(super) object_impl: ObjectImpl,
     pub(super) arguments: Vec<Argument>,
     pub(super) return_type: Option<Type>,
@@ -387,10 +466,14 @@ pub struct Method {
     pub(super) ffi_func: FfiFunction,
     pub(super) throws: Option<Type>,
     pub(super) takes_self_by_arc: bool,
+    pub(super) takes_self_by_value: bool,
     pub(super) checksum_fn_name: String,
     // Force a checksum value, or we'll fallback to the trait.
     #[checksum_ignore]
     pub(super) checksum: Option<u16>,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Method {
@@ -398,10 +481,22 @@ impl Method {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn is_async(&self) -> bool {
         self.is_async
     }
 
+    /// Does this callback interface method have a default implementation?
+    ///
+    /// Foreign implementations that don't define the method call `ffi_func()`, which runs the
+    /// default implementation in Rust.
+    pub fn has_default(&self) -> bool {
+        self.has_default
+    }
+
     pub fn arguments(&self) -> Vec<&Argument> {
         self.arguments.iter().collect()
     }
@@ -421,6 +516,7 @@ impl Method {
             by_ref: !self.takes_self_by_arc,
             optional: false,
             default: None,
+            sensitive: false,
         }]
         .into_iter()
         .chain(self.arguments.iter().cloned())
@@ -459,6 +555,13 @@ impl Method {
         self.takes_self_by_arc
     }
 
+    /// Does this method consume the object?
+    ///
+    /// The foreign object hands its pointer over to Rust, and can't be used after the call.
+    pub fn takes_self_by_value(&self) -> bool {
+        self.takes_self_by_value
+    }
+
     pub fn derive_ffi_func(&mut self, ci_namespace: &str, obj_name: &str) -> Result<()> {
         // The name is already set if the function is defined through a proc-macro invocation
         // rather than in UDL. Don't overwrite it in that case.
@@ -473,6 +576,21 @@ impl Method {
         Ok(())
     }
 
+    /// Derive the FFI function for the default implementation of a callback interface method
+    ///
+    /// Instead of an object pointer, it inputs a handle to the foreign implementation that Rust
+    /// uses to call the other methods.
+    pub(super) fn derive_default_ffi_func(&mut self) {
+        self.ffi_func.init(
+            self.return_type.as_ref().map(Into::into),
+            iter::once(FfiArgument {
+                name: "uniffi_handle".to_string(),
+                type_: FfiType::UInt64,
+            })
+            .chain(self.arguments.iter().map(Into::into)),
+        );
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(
             self.arguments
@@ -488,7 +606,7 @@ impl From<uniffi_meta::MethodMetadata> for Method {
         let ffi_name = meta.ffi_symbol_name();
         let checksum_fn_name = meta.checksum_symbol_name();
         let is_async = meta.is_async;
-        let return_type = meta.return_type.map(Into::into);
+        let return_type = meta.return_type;
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
 
         let ffi_func = FfiFunction {
@@ -502,14 +620,17 @@ impl From<uniffi_meta::MethodMetadata> for Method {
             object_name: meta.self_name,
             object_module_path: meta.module_path,
             is_async,
+            has_default: false,
             object_impl: ObjectImpl::Struct, // will be filled in later
             arguments,
             return_type,
             ffi_func,
-            throws: meta.throws.map(Into::into),
+            throws: meta.throws,
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: meta.takes_self_by_value,
             checksum_fn_name,
             checksum: meta.checksum,
+            docstring: meta.docstring,
         }
     }
 }
@@ -517,34 +638,103 @@ impl From<uniffi_meta::MethodMetadata> for Method {
 impl From<uniffi_meta::TraitMethodMetadata> for Method {
     fn from(meta: uniffi_meta::TraitMethodMetadata) -> Self {
         let checksum_fn_name = meta.checksum_symbol_name();
-        let return_type = meta.return_type.map(Into::into);
+        // Only default implementations have a scaffolding function.  For the other methods, this
+        // is a placeholder value that doesn't affect any behavior.
+        let ffi_func = if meta.has_default {
+            FfiFunction {
+                name: meta.ffi_symbol_name(),
+                ..FfiFunction::default()
+            }
+        } else {
+            FfiFunction::default()
+        };
+        let return_type = meta.return_type;
         let arguments = meta.inputs.into_iter().map(Into::into).collect();
         Self {
             name: meta.name,
             object_name: meta.trait_name,
             object_module_path: meta.module_path,
             is_async: false,
+            has_default: meta.has_default,
             arguments,
             return_type,
-            throws: meta.throws.map(Into::into),
+            throws: meta.throws,
             takes_self_by_arc: meta.takes_self_by_arc,
+            takes_self_by_value: false,
             checksum_fn_name,
             checksum: meta.checksum,
-            // These are placeholder values that don't affect any behavior since we don't create
-            // scaffolding functions for callback interface methods
-            ffi_func: FfiFunction::default(),
+            docstring: meta.docstring,
+            ffi_func,
+            // This is a placeholder value, callback interfaces aren't objects
             object_impl: ObjectImpl::Struct,
         }
     }
 }
 
+/// Represents a constant declared in an exported trait.
+///
+/// The foreign bindings expose these as static members of the interface.  For callback interfaces,
+/// Rust can also read the value from the foreign implementation.
+#[derive(Debug, Clone, Checksum)]
+pub struct TraitConstant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+}
+
+impl TraitConstant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &Literal {
+        &self.value
+    }
+
+    pub fn iter_types(&self) -> TypeIterator<'_> {
+        self.type_.iter_types()
+    }
+}
+
+impl AsType for TraitConstant {
+    fn as_type(&self) -> Type {
+        self.type_.clone()
+    }
+}
+
+impl From<uniffi_meta::TraitConstantMetadata> for TraitConstant {
+    fn from(meta: uniffi_meta::TraitConstantMetadata) -> Self {
+        Self {
+            name: meta.name,
+            type_: meta.ty,
+            value: meta.value,
+        }
+    }
+}
+
 /// The list of traits we support generating helper methods for.
 #[derive(Clone, Debug, Checksum)]
 pub enum UniffiTrait {
-    Debug { fmt: Method },
-    Display { fmt: Method },
-    Eq { eq: Method, ne: Method },
-    Hash { hash: Method },
+    Debug {
+        fmt: Method,
+    },
+    Display {
+        fmt: Method,
+    },
+    Eq {
+        eq: Method,
+        ne: Method,
+    },
+    Hash {
+        hash: Method,
+    },
+    Ord {
+        cmp: Method,
+    },
+    Restore {
+        serialize: Method,
+        restore: Function,
+    },
 }
 
 impl UniffiTrait {
@@ -553,8 +743,12 @@ impl UniffiTrait {
             match self {
                 UniffiTrait::Display { fmt: m }
                 | UniffiTrait::Debug { fmt: m }
-                | UniffiTrait::Hash { hash: m } => vec![m.iter_types()],
+                | UniffiTrait::Hash { hash: m }
+                | UniffiTrait::Ord { cmp: m } => vec![m.iter_types()],
                 UniffiTrait::Eq { eq, ne } => vec![eq.iter_types(), ne.iter_types()],
+                UniffiTrait::Restore { serialize, restore } => {
+                    vec![serialize.iter_types(), restore.iter_types()]
+                }
             }
             .into_iter()
             .flatten(),
@@ -565,19 +759,32 @@ impl UniffiTrait {
         match self {
             UniffiTrait::Display { fmt: m }
             | UniffiTrait::Debug { fmt: m }
-            | UniffiTrait::Hash { hash: m } => {
+            | UniffiTrait::Hash { hash: m }
+            | UniffiTrait::Ord { cmp: m } => {
                 m.derive_ffi_func(ci_namespace, obj_name)?;
             }
             UniffiTrait::Eq { eq, ne } => {
                 eq.derive_ffi_func(ci_namespace, obj_name)?;
                 ne.derive_ffi_func(ci_namespace, obj_name)?;
             }
+            UniffiTrait::Restore { serialize, restore } => {
+                serialize.derive_ffi_func(ci_namespace, obj_name)?;
+                restore.derive_ffi_func(ci_namespace)?;
+            }
         }
         Ok(())
     }
 }
 
 impl Callable for Constructor {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", self.object_name, self.name)
+    }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
     fn arguments(&self) -> Vec<&Argument> {
         self.arguments()
     }
@@ -596,6 +803,14 @@ impl Callable for Constructor {
 }
 
 impl Callable for Method {
+    fn qualified_name(&self) -> String {
+        format!("{}.{}", self.object_name, self.name)
+    }
+
+    fn docstring(&self) -> Option<&str> {
+        self.docstring()
+    }
+
     fn arguments(&self) -> Vec<&Argument> {
         self.arguments()
     }
@@ -607,6 +822,10 @@ impl Callable for Method {
     fn throws_type(&self) -> Option<Type> {
         self.throws_type().cloned()
     }
+
+    fn takes_self_by_value(&self) -> bool {
+        self.takes_self_by_value
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +47,7 @@
 use anyhow::Result;
 use uniffi_meta::Checksum;
 
+use super::ffi::{FfiArgument, FfiFunction, FfiType};
 use super::Literal;
 use super::{AsType, Type, TypeIterator};
 
@@ -55,11 +56,24 @@ use super::{AsType, Type, TypeIterator};
 /// In the FFI these are represented as a byte buffer, which one side explicitly
 /// serializes the data into and the other serializes it out of. So I guess they're
 /// kind of like "pass by clone" values.
+///
+/// The exception is records marked `[ReprC]` in the UDL, or `#[repr(C)]` structs with only
+/// numeric fields when using proc-macros.  Those are passed by value as a C struct with the same
+/// fields when they're arguments or return values, see [FfiType::Struct].
 #[derive(Debug, Clone, PartialEq, Eq, Checksum)]
 pub struct Record {
     pub(super) name: String,
     pub(super) module_path: String,
     pub(super) fields: Vec<Field>,
+    pub(super) repr_c: bool,
+    pub(super) ord: bool,
+    pub(super) patch_for: Option<String>,
+    // The FFI function is derived from the record, so it doesn't change the API
+    #[checksum_ignore]
+    pub(super) ffi_func_cmp: FfiFunction,
+    // Documentation doesn't change the API
+    #[checksum_ignore]
+    pub(super) docstring: Option<String>,
 }
 
 impl Record {
@@ -67,10 +81,66 @@ impl Record {
         &self.name
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn fields(&self) -> &[Field] {
         &self.fields
     }
 
+    /// Is this record passed over the FFI as a `#[repr(C)]` struct?
+    pub fn is_repr_c(&self) -> bool {
+        self.repr_c
+    }
+
+    /// Does this record forward the Rust `Ord` implementation to the foreign code?
+    pub fn is_ord(&self) -> bool {
+        self.ord
+    }
+
+    /// The FFI function that compares two records with `Ord::cmp()`, for records that forward `Ord`
+    ///
+    /// It returns the `Ordering` as an `i8`: -1, 0 or 1.
+    pub fn ffi_func_cmp(&self) -> Option<&FfiFunction> {
+        self.ord.then_some(&self.ffi_func_cmp)
+    }
+
+    pub fn derive_ffi_funcs(&mut self, ci_namespace: &str) {
+        if !self.ord {
+            return;
+        }
+        // The name is already set if the record is defined through a proc-macro invocation
+        // rather than in UDL. Don't overwrite it in that case.
+        if self.ffi_func_cmp.name.is_empty() {
+            self.ffi_func_cmp.name =
+                uniffi_meta::record_cmp_fn_symbol_name(ci_namespace, &self.name);
+        }
+        self.ffi_func_cmp.arguments = ["a", "b"]
+            .into_iter()
+            .map(|name| FfiArgument {
+                name: name.to_string(),
+                type_: self.ffi_type(),
+            })
+            .collect();
+        self.ffi_func_cmp.return_type = Some(FfiType::Int8);
+    }
+
+    /// For a `{name}Patch` record generated by `[Patch]` or `#[uniffi(patch)]`, the name of the
+    /// record that it updates
+    pub fn patch_for(&self) -> Option<&str> {
+        self.patch_for.as_deref()
+    }
+
+    /// The `FfiType` used when this record is an argument or return value
+    pub fn ffi_type(&self) -> FfiType {
+        if self.repr_c {
+            FfiType::Struct(self.name.clone())
+        } else {
+            FfiType::RustBuffer(None)
+        }
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         Box::new(self.fields.iter().flat_map(Field::iter_types))
     }
@@ -89,6 +159,11 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
     type Error = anyhow::Error;
 
     fn try_from(meta: uniffi_meta::RecordMetadata) -> Result<Self> {
+        let ffi_cmp_name = if meta.ord {
+            meta.cmp_ffi_symbol_name()
+        } else {
+            String::new()
+        };
         Ok(Self {
             name: meta.name,
             module_path: meta.module_path,
@@ -97,6 +172,14 @@ impl TryFrom<uniffi_meta::RecordMetadata> for Record {
                 .into_iter()
                 .map(TryInto::try_into)
                 .collect::<Result<_>>()?,
+            repr_c: meta.repr_c,
+            ord: meta.ord,
+            patch_for: meta.patch_for,
+            ffi_func_cmp: FfiFunction {
+                name: ffi_cmp_name,
+                ..Default::default()
+            },
+            docstring: meta.docstring,
         })
     }
 }
@@ -118,6 +201,11 @@ impl Field {
         self.default.as_ref()
     }
 
+    /// The type of this field in the C struct for a `#[repr(C)]` record
+    pub fn ffi_type(&self) -> FfiType {
+        (&self.type_).into()
+    }
+
     pub fn iter_types(&self) -> TypeIterator<'_> {
         self.type_.iter_types()
     }
@@ -200,6 +288,145 @@ mod test {
         assert!(record.fields()[2].default_value().is_none());
     }
 
+    #[test]
+    fn test_repr_c_records() {
+        const UDL: &str = r#"
+            namespace test{
+                Point midpoint(Point a, Point b);
+                Simple simple(Point a);
+            };
+            [ReprC]
+            dictionary Point {
+                f64 x;
+                f64 y;
+            };
+            dictionary Simple {
+                u32 field;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Point").unwrap();
+        assert!(record.is_repr_c());
+        assert_eq!(record.fields()[0].ffi_type(), FfiType::Float64);
+        assert!(!ci.get_record_definition("Simple").unwrap().is_repr_c());
+
+        let ffi_func = ci.get_function_definition("midpoint").unwrap().ffi_func();
+        assert_eq!(
+            ffi_func.arguments()[0].type_(),
+            FfiType::Struct("Point".into())
+        );
+        assert_eq!(
+            ffi_func.return_type(),
+            Some(&FfiType::Struct("Point".into()))
+        );
+        let ffi_func = ci.get_function_definition("simple").unwrap().ffi_func();
+        assert_eq!(ffi_func.return_type(), Some(&FfiType::RustBuffer(None)));
+
+        const BAD_UDL: &str = r#"
+            namespace test{};
+            [ReprC]
+            dictionary Named {
+                string name;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(BAD_UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[ReprC] dictionary `Named` can only have integer and float fields"
+        );
+    }
+
+    #[test]
+    fn test_ord_records() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Traits=(Ord)]
+            dictionary Version {
+                u32 major;
+                string label;
+            };
+            [ReprC, Traits=(Ord)]
+            dictionary Point {
+                i32 x;
+                i32 y;
+            };
+            dictionary Simple {
+                u32 field;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Version").unwrap();
+        assert!(record.is_ord());
+        let ffi_func = record.ffi_func_cmp().unwrap();
+        assert_eq!(ffi_func.name(), "uniffi_test_fn_cmp_version");
+        assert_eq!(ffi_func.arguments().len(), 2);
+        assert_eq!(ffi_func.arguments()[0].type_(), FfiType::RustBuffer(None));
+        assert_eq!(ffi_func.return_type(), Some(&FfiType::Int8));
+
+        let ffi_func = ci
+            .get_record_definition("Point")
+            .unwrap()
+            .ffi_func_cmp()
+            .unwrap();
+        assert_eq!(
+            ffi_func.arguments()[1].type_(),
+            FfiType::Struct("Point".into())
+        );
+
+        let simple = ci.get_record_definition("Simple").unwrap();
+        assert!(!simple.is_ord());
+        assert!(simple.ffi_func_cmp().is_none());
+
+        let names: Vec<_> = ci
+            .iter_ffi_function_definitions()
+            .map(|f| f.name().to_string())
+            .filter(|name| name.contains("_fn_cmp_"))
+            .collect();
+        assert_eq!(
+            names,
+            ["uniffi_test_fn_cmp_point", "uniffi_test_fn_cmp_version"]
+        );
+    }
+
+    #[test]
+    fn test_patch_records() {
+        const UDL: &str = r#"
+            namespace test{
+                void update(SettingsPatch patch);
+            };
+            [Patch]
+            dictionary Settings {
+                string name;
+                u32? limit;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.get_record_definition("Settings").unwrap().patch_for(),
+            None
+        );
+
+        let patch = ci.get_record_definition("SettingsPatch").unwrap();
+        assert_eq!(patch.patch_for(), Some("Settings"));
+        assert_eq!(patch.fields().len(), 2);
+        assert_eq!(patch.fields()[0].name(), "name");
+        assert_eq!(
+            patch.fields()[0].as_type(),
+            Type::Optional {
+                inner_type: Box::new(Type::String)
+            }
+        );
+        assert_eq!(patch.fields()[0].default_value(), Some(&Literal::Null));
+        assert_eq!(
+            patch.fields()[1].as_type(),
+            Type::Optional {
+                inner_type: Box::new(Type::Optional {
+                    inner_type: Box::new(Type::UInt32)
+                })
+            }
+        );
+    }
+
     #[test]
     fn test_that_all_field_types_become_known() {
         const UDL: &str = r#"
@@ -21,7 +21,7 @@ pub use uniffi_meta::{AsType, ExternalKind, ObjectImpl, Type, TypeIterator};
 /// You could imagine this struct doing some clever interning of names and so-on in future,
 /// to reduce the overhead of passing around [Type] instances. For now we just do a whole
 /// lot of cloning.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct TypeUniverse {
     /// The unique prefix that we'll use for namespacing when exposing this component's API.
     pub namespace: String,
@@ -72,16 +72,48 @@ impl TypeUniverse {
             Type::Float32 => self.add_type_definition("f32", type_)?,
             Type::Float64 => self.add_type_definition("f64", type_)?,
             Type::Boolean => self.add_type_definition("bool", type_)?,
+            Type::Char => self.add_type_definition("char", type_)?,
             Type::String => self.add_type_definition("string", type_)?,
             Type::Bytes => self.add_type_definition("bytes", type_)?,
             Type::Timestamp => self.add_type_definition("timestamp", type_)?,
             Type::Duration => self.add_type_definition("duration", type_)?,
+            Type::Url => {
+                self.add_type_definition("url", type_)?;
+                // URLs are passed as strings, so the bindings need the string helpers.
+                self.add_known_type(&Type::String)?;
+            }
+            Type::Uuid => self.add_type_definition("uuid", type_)?,
+            Type::Decimal => {
+                self.add_type_definition("decimal", type_)?;
+                // Decimals are passed as strings, so the bindings need the string helpers.
+                self.add_known_type(&Type::String)?;
+            }
+            Type::Json => {
+                self.add_type_definition("json", type_)?;
+                // JSON values are passed as strings, so the bindings need the string helpers.
+                self.add_known_type(&Type::String)?;
+            }
+            Type::Path => {
+                self.add_type_definition("path", type_)?;
+                // Paths are passed as raw bytes, so the bindings need the bytes helpers.
+                self.add_known_type(&Type::Bytes)?;
+            }
+            Type::IpAddr => {
+                self.add_type_definition("ipaddr", type_)?;
+            }
+            Type::SocketAddr => {
+                self.add_type_definition("socketaddr", type_)?;
+                // Socket addresses are passed as an IP address and a port, so the bindings need
+                // the IP address helpers.
+                self.add_known_type(&Type::IpAddr)?;
+            }
             Type::ForeignExecutor => {
                 self.add_type_definition("ForeignExecutor", type_)?;
             }
             Type::Object { name, .. }
             | Type::Record { name, .. }
             | Type::Enum { name, .. }
+            | Type::Flags { name, .. }
             | Type::CallbackInterface { name, .. }
             | Type::External { name, .. } => self.add_type_definition(name, type_)?,
             Type::Custom { name, builtin, .. } => {
@@ -89,7 +121,11 @@ impl TypeUniverse {
                 self.add_known_type(builtin)?;
             }
             // Structurally recursive types.
-            Type::Optional { inner_type, .. } | Type::Sequence { inner_type, .. } => {
+            Type::Optional { inner_type, .. }
+            | Type::Sequence { inner_type, .. }
+            | Type::Compressed { inner_type, .. }
+            | Type::Shared { inner_type, .. }
+            | Type::Weak { inner_type, .. } => {
                 self.add_known_type(inner_type)?;
             }
             Type::Map {
@@ -120,6 +156,13 @@ impl TypeUniverse {
     pub fn iter_known_types(&self) -> impl Iterator<Item = &Type> {
         self.all_known_types.iter()
     }
+
+    /// Remove the types for which `is_removed` is true, along with the types that contain them.
+    pub(super) fn remove_types(&mut self, is_removed: impl Fn(&Type) -> bool) {
+        self.type_definitions.retain(|_, t| !is_removed(t));
+        self.all_known_types
+            .retain(|t| !t.iter_types().any(&is_removed));
+    }
 }
 
 #[cfg(test)]
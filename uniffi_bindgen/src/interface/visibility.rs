@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Hiding items from the bindings of a language
+//!
+//! The `hidden` option of a language's bindings config lists items that shouldn't be part of its
+//! bindings, for example to give Python tooling an admin API that the shipped Kotlin and Swift
+//! bindings don't have:
+//!
+//! ```toml
+//! [bindings.kotlin]
+//! hidden = ["AdminApi", "reset_database", "Store.dump"]
+//! ```
+//!
+//! Each entry is the name of a type, top-level function or constant, which removes the item
+//! completely, or `Type.name` for a method or constructor of an object.  The bindings are then
+//! generated from a copy of the `ComponentInterface` without these items, while the Rust library
+//! still exports them.
+//!
+//! Hidden types can't be used by the items that remain visible, since the bindings would have no
+//! way to represent them.  For the same reason, enum variants and the fields of records can't be
+//! hidden, and neither can the methods of callback interfaces, which the foreign code implements.
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Result};
+
+use super::{ComponentInterface, Type};
+
+impl ComponentInterface {
+    /// A copy of this interface without the items named in `names`, see the module docs
+    pub fn without_hidden_items(&self, names: &[String]) -> Result<Self> {
+        let mut ci = self.clone();
+        let mut hidden_types = BTreeSet::new();
+        for name in names {
+            match name.split_once('.') {
+                Some((type_name, member)) => ci.hide_member(type_name, member)?,
+                None => {
+                    if ci.hide_item(name)? {
+                        hidden_types.insert(name.as_str());
+                    }
+                }
+            }
+        }
+
+        if !hidden_types.is_empty() {
+            let is_hidden =
+                |t: &Type| matches!(defined_type_name(t), Some(n) if hidden_types.contains(n));
+            for (user, t) in ci.iter_item_types() {
+                if let Some(t) = t.iter_types().find(|t| is_hidden(t)) {
+                    bail!(
+                        "`{}` is hidden, but it's used by `{user}`",
+                        defined_type_name(t).unwrap_or_default(),
+                    );
+                }
+            }
+            ci.types.remove_types(is_hidden);
+            ci.errors
+                .retain(|name| !hidden_types.contains(name.as_str()));
+        }
+        // Hidden callback interfaces no longer need to lower their errors
+        ci.callback_interface_throws_types = ci
+            .callback_interfaces
+            .iter()
+            .flat_map(|c| c.methods.iter().filter_map(|m| m.throws_type()).cloned())
+            .collect();
+        Ok(ci)
+    }
+
+    /// Remove a top-level item, returning true if it was a type
+    fn hide_item(&mut self, name: &str) -> Result<bool> {
+        if self.records.remove(name).is_some()
+            || self.enums.remove(name).is_some()
+            || self.flags.remove(name).is_some()
+            || remove_by_name(&mut self.objects, name, |o| o.name())
+            || remove_by_name(&mut self.callback_interfaces, name, |c| c.name())
+        {
+            Ok(true)
+        } else if remove_by_name(&mut self.functions, name, |f| f.name())
+            || remove_by_name(&mut self.constants, name, |c| c.name())
+        {
+            Ok(false)
+        } else {
+            bail!("can't hide `{name}`, there's no exported item with that name")
+        }
+    }
+
+    /// Remove a method or constructor of an object
+    fn hide_member(&mut self, type_name: &str, member: &str) -> Result<()> {
+        if self.get_callback_interface_definition(type_name).is_some() {
+            bail!(
+                "can't hide `{type_name}.{member}`, the foreign code implements all the methods \
+                 of a callback interface"
+            );
+        }
+        let Some(obj) = self.objects.iter_mut().find(|o| o.name == type_name) else {
+            bail!(
+                "can't hide `{type_name}.{member}`, only the methods and constructors of objects \
+                 can be hidden"
+            );
+        };
+        if !(remove_by_name(&mut obj.methods, member, |m| m.name())
+            || remove_by_name(&mut obj.constructors, member, |c| c.name()))
+        {
+            bail!(
+                "can't hide `{type_name}.{member}`, `{type_name}` has no method or constructor \
+                 with that name"
+            );
+        }
+        Ok(())
+    }
+
+    /// The types used by each remaining item, including the errors it throws, along with the name
+    /// of the item
+    fn iter_item_types(&self) -> impl Iterator<Item = (&str, &Type)> {
+        let records = self
+            .records
+            .values()
+            .flat_map(|r| r.iter_types().map(move |t| (r.name(), t)));
+        let enums = self
+            .enums
+            .values()
+            .flat_map(|e| e.iter_types().map(move |t| (e.name(), t)));
+        let functions = self.functions.iter().flat_map(|f| {
+            f.iter_types()
+                .chain(f.throws_type())
+                .map(move |t| (f.name(), t))
+        });
+        let constants = self
+            .constants
+            .iter()
+            .flat_map(|c| c.iter_types().map(move |t| (c.name(), t)));
+        let objects = self.objects.iter().flat_map(|o| {
+            let throws_types = o
+                .methods
+                .iter()
+                .filter_map(|m| m.throws_type())
+                .chain(o.constructors.iter().filter_map(|c| c.throws_type()));
+            o.iter_types()
+                .chain(throws_types)
+                .map(move |t| (o.name(), t))
+        });
+        let callback_interfaces = self.callback_interfaces.iter().flat_map(|c| {
+            c.iter_types()
+                .chain(c.methods.iter().filter_map(|m| m.throws_type()))
+                .map(move |t| (c.name(), t))
+        });
+        records
+            .chain(enums)
+            .chain(functions)
+            .chain(constants)
+            .chain(objects)
+            .chain(callback_interfaces)
+    }
+}
+
+fn remove_by_name<T>(items: &mut Vec<T>, name: &str, item_name: impl Fn(&T) -> &str) -> bool {
+    let len = items.len();
+    items.retain(|item| item_name(item) != name);
+    items.len() != len
+}
+
+/// The name of a type that has a definition in the `ComponentInterface`
+fn defined_type_name(t: &Type) -> Option<&str> {
+    match t {
+        Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::Flags { name, .. }
+        | Type::Object { name, .. }
+        | Type::CallbackInterface { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"
+        namespace test {
+            void reset_database();
+            Stats stats();
+        };
+        dictionary Stats {
+            u32 count;
+        };
+        dictionary Report {
+            Stats stats;
+        };
+        interface Store {
+            constructor();
+            [Name=open]
+            constructor(string path);
+            [Throws=StoreError]
+            string get(string key);
+            string dump();
+        };
+        [Error]
+        enum StoreError { "NotFound" };
+        callback interface Listener {
+            [Throws=ListenerError]
+            void changed(string key);
+        };
+        [Error]
+        enum ListenerError { "Failed" };
+    "#;
+
+    fn hide(names: &[&str]) -> Result<ComponentInterface> {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        ci.without_hidden_items(&names.iter().map(|n| n.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_hide_function_and_methods() {
+        let ci = hide(&["reset_database", "Store.dump", "Store.open"]).unwrap();
+        assert!(ci.get_function_definition("reset_database").is_none());
+        assert!(ci.get_function_definition("stats").is_some());
+        let store = ci.get_object_definition("Store").unwrap();
+        let methods: Vec<_> = store.methods().into_iter().map(|m| m.name()).collect();
+        assert_eq!(methods, vec!["get"]);
+        assert_eq!(store.constructors().len(), 1);
+    }
+
+    #[test]
+    fn test_hide_types() {
+        let ci = hide(&["Report", "Store"]).unwrap();
+        assert!(ci.get_record_definition("Report").is_none());
+        assert!(ci.get_object_definition("Store").is_none());
+        assert!(ci.get_type("Report").is_none());
+        assert!(ci.get_type("Store").is_none());
+        assert!(!ci
+            .iter_types()
+            .any(|t| defined_type_name(t) == Some("Report")));
+        // The types used by the hidden items are still there
+        assert!(ci.get_type("Stats").is_some());
+    }
+
+    #[test]
+    fn test_hidden_type_still_used() {
+        let err = hide(&["Stats"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`Stats` is hidden, but it's used by `Report`"
+        );
+        // Hiding the users too is fine
+        assert!(hide(&["Stats", "Report", "stats"]).is_ok());
+    }
+
+    #[test]
+    fn test_hide_errors() {
+        let err = hide(&["StoreError"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`StoreError` is hidden, but it's used by `Store`"
+        );
+        let ci = hide(&["Store", "StoreError"]).unwrap();
+        assert!(!ci.is_name_used_as_error("StoreError"));
+
+        let listener_error = ComponentInterface::from_webidl(UDL)
+            .unwrap()
+            .get_type("ListenerError")
+            .unwrap();
+        assert!(hide(&["ListenerError"]).is_err());
+        let ci = hide(&["Listener"]).unwrap();
+        assert!(!ci.is_callback_interface_throws_type(listener_error.clone()));
+        assert!(ci.is_name_used_as_error("ListenerError"));
+        let ci = hide(&[]).unwrap();
+        assert!(ci.is_callback_interface_throws_type(listener_error));
+    }
+
+    #[test]
+    fn test_hide_unknown_items() {
+        assert!(hide(&["Missing"]).is_err());
+        assert!(hide(&["Store.missing"]).is_err());
+        assert!(hide(&["Stats.count"]).is_err());
+    }
+}
@@ -100,12 +100,23 @@ use std::io::prelude::*;
 use std::io::ErrorKind;
 use std::{collections::HashMap, process::Command, str::FromStr};
 
+pub mod android;
+pub mod api_diff;
 pub mod backend;
 pub mod bindings;
+pub mod bundle;
+pub mod external_generator;
+pub mod ffi_bench;
+pub mod fuzz;
+pub mod ide_index;
 pub mod interface;
 pub mod library_mode;
 pub mod macro_metadata;
+pub mod python_package;
 pub mod scaffolding;
+pub mod swift_package;
+pub mod udl_export;
+pub mod watch;
 
 use bindings::TargetLanguage;
 pub use interface::ComponentInterface;
@@ -168,7 +179,7 @@ impl BindingsConfig for EmptyBindingsConfig {
 //
 // If there is an error parsing the file then Err will be returned. If the file is missing or the
 // entry for the bindings is missing, then Ok(None) will be returned.
-fn load_bindings_config_toml(
+pub(crate) fn load_bindings_config_toml(
     crate_root: &Utf8Path,
     config_file_override: Option<&Utf8Path>,
 ) -> Result<Option<toml::Value>> {
@@ -251,7 +262,7 @@ pub fn generate_external_bindings<T: BindingGenerator>(
             load_bindings_config::<T::Config>(&component, crate_root, config_file_override)?;
         config.update_from_ci(&component);
         if let Some(ref library_file) = library_file {
-            if let Some(cdylib_name) = crate::library_mode::calc_cdylib_name(library_file.as_ref())
+            if let Some(cdylib_name) = library_mode::calc_cdylib_name(library_file.as_ref())
             {
                 config.update_from_cdylib_name(cdylib_name)
             }
@@ -295,15 +306,13 @@ pub fn generate_bindings(
     library_file: Option<&Utf8Path>,
     try_format_code: bool,
 ) -> Result<()> {
-    let mut component = parse_udl(udl_file)?;
-    if let Some(library_file) = library_file {
-        macro_metadata::add_to_ci_from_library(&mut component, library_file)?;
-    }
-    let crate_root = &guess_crate_root(udl_file).context("Failed to guess crate root")?;
-
-    let mut config = Config::load_initial(crate_root, config_file_override)?;
-    config.update_from_ci(&component);
+    let (component, config) = load_component(udl_file, config_file_override, library_file)?;
     let out_dir = get_out_dir(udl_file, out_dir_override)?;
+    let target_languages = if target_languages.is_empty() {
+        config.bindings.configured_languages()
+    } else {
+        target_languages
+    };
     for language in target_languages {
         bindings::write_bindings(
             &config.bindings,
@@ -317,6 +326,36 @@ pub fn generate_bindings(
     Ok(())
 }
 
+// Write the IDE index for the component, next to the bindings generated by `generate_bindings`.
+// See the `ide_index` module for details.
+pub fn generate_ide_index(
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    target_languages: &[TargetLanguage],
+    out_dir_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+) -> Result<Utf8PathBuf> {
+    let (component, config) = load_component(udl_file, config_file_override, library_file)?;
+    let out_dir = get_out_dir(udl_file, out_dir_override)?;
+    ide_index::write_ide_index(&component, &config, target_languages, &out_dir)
+}
+
+pub(crate) fn load_component(
+    udl_file: &Utf8Path,
+    config_file_override: Option<&Utf8Path>,
+    library_file: Option<&Utf8Path>,
+) -> Result<(ComponentInterface, Config)> {
+    let mut component = parse_udl(udl_file)?;
+    if let Some(library_file) = library_file {
+        macro_metadata::add_to_ci_from_library(&mut component, library_file)?;
+    }
+    let crate_root = &guess_crate_root(udl_file).context("Failed to guess crate root")?;
+
+    let mut config = Config::load_initial(crate_root, config_file_override)?;
+    config.update_from_ci(&component);
+    Ok((component, config))
+}
+
 pub fn dump_json(library_path: &Utf8Path) -> Result<String> {
     let metadata = macro_metadata::extract_from_library(library_path)?;
     Ok(serde_json::to_string_pretty(&metadata)?)
@@ -327,6 +366,40 @@ pub fn print_json(library_path: &Utf8Path) -> Result<()> {
     Ok(())
 }
 
+/// The model of the components in a UDL file, or in a library when `library_mode` is set, as JSON
+///
+/// This is the stable model that's sent to external generators, see [external_generator].
+pub fn dump_metadata(
+    source: &Utf8Path,
+    library_mode: bool,
+    crate_name: Option<String>,
+    library_file: Option<&Utf8Path>,
+) -> Result<String> {
+    let components = if library_mode {
+        library_mode::find_components(source, crate_name)?
+            .into_iter()
+            .map(|s| s.ci)
+            .collect()
+    } else {
+        vec![load_component(source, None, library_file)?.0]
+    };
+    let metadata = external_generator::ComponentMetadata::new(&components);
+    Ok(serde_json::to_string_pretty(&metadata)?)
+}
+
+pub fn print_metadata(
+    source: &Utf8Path,
+    library_mode: bool,
+    crate_name: Option<String>,
+    library_file: Option<&Utf8Path>,
+) -> Result<()> {
+    println!(
+        "{}",
+        dump_metadata(source, library_mode, crate_name, library_file)?
+    );
+    Ok(())
+}
+
 /// Guess the root directory of the crate from the path of its UDL file.
 ///
 /// For now, we assume that the UDL file is in `./src/something.udl` relative
@@ -344,7 +417,10 @@ pub fn guess_crate_root(udl_file: &Utf8Path) -> Result<&Utf8Path> {
     Ok(path_guess)
 }
 
-fn get_out_dir(udl_file: &Utf8Path, out_dir_override: Option<&Utf8Path>) -> Result<Utf8PathBuf> {
+pub(crate) fn get_out_dir(
+    udl_file: &Utf8Path,
+    out_dir_override: Option<&Utf8Path>,
+) -> Result<Utf8PathBuf> {
     Ok(match out_dir_override {
         Some(s) => {
             // Create the directory if it doesn't exist yet.
@@ -394,14 +470,27 @@ impl Config {
             Some(cfg) => Some(cfg.to_owned()),
             None => crate_root.join("uniffi.toml").canonicalize_utf8().ok(),
         };
-        let toml_config = match path {
+        let toml_config: toml::Value = match &path {
             Some(path) => {
                 let contents = fs::read_to_string(path).context("Failed to read config file")?;
                 toml::de::from_str(&contents)?
             }
             None => toml::Value::from(toml::value::Table::default()),
         };
-        Ok(toml_config.try_into()?)
+        let mut config: Config = toml_config.clone().try_into()?;
+        let config_dir = path.as_deref().and_then(Utf8Path::parent);
+        if let Some(bindings) = toml_config.get("bindings") {
+            if let Some(config_dir) = config_dir {
+                config.bindings.load_out_dirs(bindings, config_dir);
+            }
+            config.bindings.load_hidden_items(bindings)?;
+        }
+        Ok(config)
+    }
+
+    /// The languages to generate when none are passed, see [bindings::Config::configured_languages]
+    pub fn configured_languages(&self) -> Vec<TargetLanguage> {
+        self.bindings.configured_languages()
     }
 
     fn update_from_ci(&mut self, ci: &ComponentInterface) {
@@ -409,6 +498,8 @@ impl Config {
         self.bindings.swift.update_from_ci(ci);
         self.bindings.python.update_from_ci(ci);
         self.bindings.ruby.update_from_ci(ci);
+        self.bindings.wit.update_from_ci(ci);
+        self.bindings.c.update_from_ci(ci);
     }
 
     fn update_from_cdylib_name(&mut self, cdylib_name: &str) {
@@ -416,6 +507,8 @@ impl Config {
         self.bindings.swift.update_from_cdylib_name(cdylib_name);
         self.bindings.python.update_from_cdylib_name(cdylib_name);
         self.bindings.ruby.update_from_cdylib_name(cdylib_name);
+        self.bindings.wit.update_from_cdylib_name(cdylib_name);
+        self.bindings.c.update_from_cdylib_name(cdylib_name);
     }
 
     fn update_from_dependency_configs(&mut self, config_map: HashMap<&str, &Self>) {
@@ -443,6 +536,18 @@ impl Config {
                 .map(|(key, config)| (*key, &config.bindings.ruby))
                 .collect(),
         );
+        self.bindings.wit.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.wit))
+                .collect(),
+        );
+        self.bindings.c.update_from_dependency_configs(
+            config_map
+                .iter()
+                .map(|(key, config)| (*key, &config.bindings.c))
+                .collect(),
+        );
     }
 }
 
@@ -478,4 +583,91 @@ mod test {
         let not_a_crate_root = &this_crate_root.join("src/templates");
         assert!(guess_crate_root(&not_a_crate_root.join("src/example.udl")).is_err());
     }
+
+    #[test]
+    fn test_dump_metadata() {
+        let this_crate_root = Utf8PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+        let udl_file = this_crate_root.join("../examples/arithmetic/src/arithmetic.udl");
+        let metadata: serde_json::Value =
+            serde_json::from_str(&dump_metadata(&udl_file, false, None, None).unwrap()).unwrap();
+        assert_eq!(
+            metadata["protocol_version"],
+            external_generator::PROTOCOL_VERSION
+        );
+        let component = &metadata["components"][0];
+        assert_eq!(component["namespace"], "arithmetic");
+        let add = component["functions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|f| f["name"] == "add")
+            .unwrap();
+        assert_eq!(
+            add["arguments"][0],
+            serde_json::json!({
+                "name": "a",
+                "type": { "kind": "uint64" },
+                "by_ref": false,
+                "default_value": null,
+            })
+        );
+        assert_eq!(add["return_type"], serde_json::json!({ "kind": "uint64" }));
+        assert_eq!(add["is_async"], false);
+    }
+
+    #[test]
+    fn test_out_dirs_from_config() {
+        let bindings: toml::Value = toml::from_str(
+            r#"
+            [kotlin]
+            out_dir = "../android/src/main/kotlin"
+
+            [swift]
+            out_dir = "/ios/Sources"
+
+            [python]
+            cdylib_name = "arithmetical"
+            "#,
+        )
+        .unwrap();
+        let mut config = bindings::Config::default();
+        config.load_out_dirs(&bindings, Utf8Path::new("examples/arithmetic"));
+        assert_eq!(
+            config.configured_languages(),
+            vec![TargetLanguage::Kotlin, TargetLanguage::Swift]
+        );
+        assert_eq!(
+            config.out_dir(TargetLanguage::Kotlin).unwrap(),
+            "examples/arithmetic/../android/src/main/kotlin"
+        );
+        assert_eq!(
+            config.out_dir(TargetLanguage::Swift).unwrap(),
+            "/ios/Sources"
+        );
+        assert_eq!(config.out_dir(TargetLanguage::Python), None);
+    }
+
+    #[test]
+    fn test_hidden_items_from_config() {
+        let bindings: toml::Value = toml::from_str(
+            r#"
+            [kotlin]
+            hidden = ["AdminApi", "Store.dump"]
+
+            [python]
+            cdylib_name = "arithmetical"
+            "#,
+        )
+        .unwrap();
+        let mut config = bindings::Config::default();
+        config.load_hidden_items(&bindings).unwrap();
+        assert_eq!(
+            config.hidden_items(TargetLanguage::Kotlin),
+            ["AdminApi", "Store.dump"]
+        );
+        assert!(config.hidden_items(TargetLanguage::Python).is_empty());
+
+        let bindings: toml::Value = toml::from_str("[swift]\nhidden = \"AdminApi\"").unwrap();
+        assert!(config.load_hidden_items(&bindings).is_err());
+    }
 }
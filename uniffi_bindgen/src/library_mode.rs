@@ -21,7 +21,7 @@ use crate::{
 };
 use anyhow::{bail, Context};
 use camino::Utf8Path;
-use cargo_metadata::{MetadataCommand, Package};
+use cargo_metadata::{Metadata, MetadataCommand, Package};
 use std::{
     collections::{HashMap, HashSet},
     fs,
@@ -41,8 +41,85 @@ pub fn generate_bindings(
     let cargo_metadata = MetadataCommand::new()
         .exec()
         .context("error running cargo metadata")?;
+    generate_bindings_with_metadata(
+        &cargo_metadata,
+        library_path,
+        crate_name,
+        target_languages,
+        out_dir,
+        try_format_code,
+    )
+}
+
+/// Generate foreign bindings, finding the crates of the components in `cargo_metadata`
+///
+/// This is for callers that already ran `cargo metadata`, for example for a `--manifest-path`
+/// that's not in the current directory.
+pub fn generate_bindings_with_metadata(
+    cargo_metadata: &Metadata,
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    target_languages: &[TargetLanguage],
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<Vec<Source>> {
+    let cdylib_name = calc_cdylib_name(library_path);
+    let sources = find_components_with_metadata(cargo_metadata, library_path, crate_name)?;
+    fs::create_dir_all(out_dir)?;
+
+    for source in sources.iter() {
+        let languages = if target_languages.is_empty() {
+            source.config.bindings.configured_languages()
+        } else {
+            target_languages.to_vec()
+        };
+        for language in languages {
+            if cdylib_name.is_none()
+                && !matches!(
+                    language,
+                    TargetLanguage::Swift | TargetLanguage::Wit | TargetLanguage::C
+                )
+            {
+                bail!("Generate bindings for {language} requires a cdylib, but {library_path} was given");
+            }
+            bindings::write_bindings(
+                &source.config.bindings,
+                &source.ci,
+                out_dir,
+                language,
+                try_format_code,
+            )?;
+        }
+    }
+
+    Ok(sources)
+}
+
+/// Find the components in a library, along with their configs
+///
+/// If `crate_name` is set, only the component for that crate is returned.
+pub fn find_components(library_path: &Utf8Path, crate_name: Option<String>) -> Result<Vec<Source>> {
+    let cargo_metadata = MetadataCommand::new()
+        .exec()
+        .context("error running cargo metadata")?;
+    find_components_with_metadata(&cargo_metadata, library_path, crate_name)
+}
+
+/// Find the components in a library, finding their crates in `cargo_metadata`
+pub fn find_components_with_metadata(
+    cargo_metadata: &Metadata,
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+) -> Result<Vec<Source>> {
     let cdylib_name = calc_cdylib_name(library_path);
-    let mut sources = find_sources(&cargo_metadata, library_path, cdylib_name)?;
+    let mut sources = find_sources(cargo_metadata, library_path, cdylib_name)?;
+    let dependency_graph: HashMap<String, Vec<String>> = sources
+        .iter()
+        .map(|s| {
+            let dependencies = s.package.dependencies.iter().map(|d| d.name.clone());
+            (s.package.name.clone(), dependencies.collect())
+        })
+        .collect();
     for i in 0..sources.len() {
         // Partition up the sources list because we're eventually going to call
         // `update_from_dependency_configs()` which requires an exclusive reference to one source and
@@ -50,9 +127,9 @@ pub fn generate_bindings(
         let (sources_before, rest) = sources.split_at_mut(i);
         let (source, sources_after) = rest.split_first_mut().unwrap();
         let other_sources = sources_before.iter().chain(sources_after.iter());
-        // Calculate which configs come from dependent crates
-        let dependencies =
-            HashSet::<&str>::from_iter(source.package.dependencies.iter().map(|d| d.name.as_str()));
+        // Calculate which configs come from dependent crates.  This includes indirect
+        // dependencies, since a crate can use types that one of its dependencies re-exports.
+        let dependencies = transitive_dependencies(&source.package.name, &dependency_graph);
         let config_map: HashMap<&str, &Config> = other_sources
             .filter_map(|s| {
                 dependencies
@@ -63,7 +140,6 @@ pub fn generate_bindings(
         // We can finally call update_from_dependency_configs
         source.config.update_from_dependency_configs(config_map);
     }
-    fs::create_dir_all(out_dir)?;
     if let Some(crate_name) = &crate_name {
         let old_elements = sources.drain(..);
         let mut matches: Vec<_> = old_elements
@@ -75,23 +151,49 @@ pub fn generate_bindings(
             n => bail!("{n} crates named {crate_name} found in {library_path}"),
         }
     }
+    Ok(sources)
+}
 
-    for source in sources.iter() {
-        for &language in target_languages {
-            if cdylib_name.is_none() && language != TargetLanguage::Swift {
-                bail!("Generate bindings for {language} requires a cdylib, but {library_path} was given");
+// Find the names of all packages that `package_name` depends on, directly or through other
+// packages in `dependency_graph`.
+fn transitive_dependencies<'a>(
+    package_name: &str,
+    dependency_graph: &'a HashMap<String, Vec<String>>,
+) -> HashSet<&'a str> {
+    let mut dependencies = HashSet::new();
+    let mut to_visit: Vec<&str> = dependency_graph
+        .get(package_name)
+        .map(|deps| deps.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+    while let Some(name) = to_visit.pop() {
+        if let Some((name, deps)) = dependency_graph.get_key_value(name) {
+            if name != package_name && dependencies.insert(name.as_str()) {
+                to_visit.extend(deps.iter().map(String::as_str));
             }
-            bindings::write_bindings(
-                &source.config.bindings,
-                &source.ci,
-                out_dir,
-                language,
-                try_format_code,
-            )?;
         }
     }
+    dependencies
+}
 
-    Ok(sources)
+/// Describe the items of a component that are gated with `#[cfg(...)]`
+///
+/// This makes it clear why the bindings for libraries built with different features differ.
+/// Returns None if there are no gated items.
+pub fn gated_items_report(ci: &ComponentInterface) -> Option<String> {
+    let (enabled, disabled): (Vec<_>, Vec<_>) = ci.cfgs().partition(|cfg| cfg.enabled);
+    if enabled.is_empty() && disabled.is_empty() {
+        return None;
+    }
+    let mut report = format!("Gated items in {}:", ci.namespace());
+    for (heading, cfgs) in [("compiled in", enabled), ("left out", disabled)] {
+        if !cfgs.is_empty() {
+            report.push_str(&format!("\n  {heading}:"));
+            for cfg in cfgs {
+                report.push_str(&format!("\n    {} ({})", cfg.name, cfg.predicate));
+            }
+        }
+    }
+    Some(report)
 }
 
 // A single source that we generate bindings for
@@ -117,7 +219,7 @@ pub fn calc_cdylib_name(library_path: &Utf8Path) -> Option<&str> {
 }
 
 fn find_sources(
-    cargo_metadata: &cargo_metadata::Metadata,
+    cargo_metadata: &Metadata,
     library_path: &Utf8Path,
     cdylib_name: Option<&str>,
 ) -> Result<Vec<Source>> {
@@ -151,7 +253,7 @@ fn find_sources(
 }
 
 fn find_package_by_crate_name(
-    metadata: &cargo_metadata::Metadata,
+    metadata: &Metadata,
     crate_name: &str,
 ) -> Result<Package> {
     let matching: Vec<&Package> = metadata
@@ -220,6 +322,60 @@ mod test {
         );
     }
 
+    #[test]
+    fn transitive_dependencies_are_found() {
+        let graph = HashMap::from([
+            (
+                "app".to_string(),
+                vec!["ui".to_string(), "serde".to_string()],
+            ),
+            ("ui".to_string(), vec!["core".to_string()]),
+            ("core".to_string(), vec!["app".to_string()]),
+            ("unrelated".to_string(), vec![]),
+        ]);
+        assert_eq!(
+            transitive_dependencies("app", &graph),
+            HashSet::from(["ui", "core"])
+        );
+        assert_eq!(
+            transitive_dependencies("ui", &graph),
+            HashSet::from(["core", "app"])
+        );
+        assert!(transitive_dependencies("unrelated", &graph).is_empty());
+    }
+
+    #[test]
+    fn gated_items_are_reported() {
+        let cfg = |name: &str, predicate: &str, enabled| {
+            uniffi_meta::CfgMetadata {
+                module_path: "app".to_string(),
+                name: name.to_string(),
+                predicate: predicate.to_string(),
+                enabled,
+            }
+            .into()
+        };
+        let ci = ComponentInterface::from_metadata(MetadataGroup {
+            namespace: uniffi_meta::NamespaceMetadata {
+                crate_name: "app".to_string(),
+                name: "app".to_string(),
+                docstring: None,
+            },
+            items: [
+                cfg("sync", "feature = \"sync\"", true),
+                cfg("Store.compact", "not (windows)", true),
+                cfg("trace", "feature = \"tracing\"", false),
+            ]
+            .into(),
+        })
+        .unwrap();
+        assert_eq!(
+            gated_items_report(&ci).unwrap(),
+            "Gated items in app:\n  compiled in:\n    Store.compact (not (windows))\n    sync (feature = \"sync\")\n  left out:\n    trace (feature = \"tracing\")",
+        );
+        assert!(gated_items_report(&ComponentInterface::default()).is_none());
+    }
+
     /// Right now we unconditionally strip the `lib` prefix.
     ///
     /// Technically Windows DLLs do not start with a `lib` prefix,
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::interface::{CallbackInterface, ComponentInterface, Enum, Record, Type};
+use crate::interface::{ComponentInterface, Enum, Record, Type};
 use anyhow::{bail, Context};
 use uniffi_meta::{group_metadata, EnumMetadata, ErrorMetadata, Metadata, MetadataGroup};
 
@@ -78,6 +78,9 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
         Metadata::Func(meta) => {
             iface.add_function_definition(meta.into())?;
         }
+        Metadata::Constant(meta) => {
+            iface.add_constant_definition(meta.into())?;
+        }
         Metadata::Constructor(meta) => {
             iface.add_constructor_meta(meta)?;
         }
@@ -97,6 +100,13 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
             let flat = meta.variants.iter().all(|v| v.fields.is_empty());
             add_enum_to_ci(iface, meta, flat)?;
         }
+        Metadata::Flags(meta) => {
+            iface.types.add_known_type(&Type::Flags {
+                module_path: meta.module_path.clone(),
+                name: meta.name.clone(),
+            })?;
+            iface.add_flags_definition(meta.into())?;
+        }
         Metadata::Object(meta) => {
             iface.types.add_known_type(&Type::Object {
                 module_path: meta.module_path.clone(),
@@ -110,11 +120,14 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
                 module_path: meta.module_path.clone(),
                 name: meta.name.clone(),
             })?;
-            iface.add_callback_interface_definition(CallbackInterface::new(meta.name));
+            iface.add_callback_interface_definition(meta.into());
         }
         Metadata::TraitMethod(meta) => {
             iface.add_trait_method_meta(meta)?;
         }
+        Metadata::TraitConstant(meta) => {
+            iface.add_trait_constant_meta(meta)?;
+        }
         Metadata::Error(meta) => {
             iface.note_name_used_as_error(meta.name());
             match meta {
@@ -130,6 +143,21 @@ fn add_item_to_ci(iface: &mut ComponentInterface, item: Metadata) -> anyhow::Res
                 builtin: Box::new(meta.builtin),
             })?;
         }
+        Metadata::UniffiTrait(meta) => {
+            iface.add_uniffitrait_meta(meta)?;
+        }
+        Metadata::ObjectConcurrency(meta) => {
+            iface.add_object_concurrency_meta(meta)?;
+        }
+        Metadata::SubNamespace(meta) => {
+            iface.add_sub_namespace_meta(meta)?;
+        }
+        Metadata::SourceLocation(meta) => {
+            iface.add_source_location(meta);
+        }
+        Metadata::Cfg(meta) => {
+            iface.add_cfg(meta);
+        }
     }
     Ok(())
 }
@@ -0,0 +1,326 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a Python package for the components in a library.
+//!
+//! The package can be built into a wheel with any PEP 517 frontend, e.g. `pip wheel` or
+//! `python -m build`:
+//!
+//!   - `pyproject.toml`, which builds the package with setuptools.  The wheel includes the
+//!     library, so it's tagged for the platform the library was built for.
+//!   - `src/<name>/`, with the Python bindings of each component and the library, which the
+//!     bindings load from their own directory.
+//!   - `src/<name>/__init__.py`, which re-exports the bindings of every component.
+//!   - `setup.py`, if any component uses the `cffi` FFI backend, which builds their extension
+//!     modules with the wheel.
+
+use crate::{
+    bindings::{python, TargetLanguage},
+    library_mode::{self, calc_cdylib_name, Source},
+    Result,
+};
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use cargo_metadata::semver::Version;
+use fs_err as fs;
+
+/// Write a Python package named `name` for the library at `library_path` to `out_dir`.
+///
+/// The wheel is tagged with `platform_tag`, which defaults to the tag of the host platform.
+pub fn generate_python_package(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    name: &str,
+    platform_tag: Option<String>,
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    let library_name = calc_cdylib_name(library_path)
+        .with_context(|| format!("Python packages need a cdylib, but {library_path} was given"))?;
+    let platform_tag = match platform_tag {
+        Some(tag) => tag,
+        None => host_platform_tag(std::env::consts::OS, std::env::consts::ARCH)?,
+    };
+    let sources = library_mode::find_components(library_path, crate_name)?;
+    let package = sources
+        .iter()
+        .find(|s| s.crate_name == library_name)
+        .map(|s| &s.package)
+        .with_context(|| format!("couldn't find the crate that builds {library_path}"))?;
+
+    let package_dir = out_dir.join("src").join(name);
+    fs::create_dir_all(&package_dir)?;
+    let mut module_names = Vec::new();
+    let mut type_stubs = false;
+    let mut cffi_modules = Vec::new();
+    for source in dependency_order(&sources) {
+        let config = &source.config.bindings.python;
+        let hidden = source.config.bindings.hidden_items(TargetLanguage::Python);
+        let ci = source.ci.without_hidden_items(hidden)?;
+        python::write_bindings(config, &ci, &package_dir, try_format_code)?;
+        module_names.push(config.module_name());
+        type_stubs |= config.type_stubs();
+        if config.ffi_backend() == python::FfiBackend::Cffi {
+            cffi_modules.push(format!(
+                "src/{name}/{}_cffi_build.py:ffibuilder",
+                config.module_name()
+            ));
+        }
+    }
+    if type_stubs {
+        fs::write(package_dir.join("py.typed"), "")?;
+    }
+    fs::write(package_dir.join("__init__.py"), init_module(&module_names))?;
+    fs::copy(
+        library_path,
+        package_dir.join(library_path.file_name().unwrap()),
+    )?;
+    fs::write(
+        out_dir.join("pyproject.toml"),
+        pyproject(
+            name,
+            &python_version(&package.version),
+            &platform_tag,
+            !cffi_modules.is_empty(),
+        ),
+    )?;
+    if !cffi_modules.is_empty() {
+        fs::write(out_dir.join("setup.py"), setup_py(&cffi_modules))?;
+    }
+    Ok(())
+}
+
+/// The wheel platform tag for an `OS` and `ARCH` from `std::env::consts`
+fn host_platform_tag(os: &str, arch: &str) -> Result<String> {
+    Ok(match (os, arch) {
+        ("linux", arch) => format!("linux_{arch}"),
+        ("macos", "x86_64") => "macosx_10_12_x86_64".to_string(),
+        ("macos", "aarch64") => "macosx_11_0_arm64".to_string(),
+        ("windows", "x86") => "win32".to_string(),
+        ("windows", "x86_64") => "win_amd64".to_string(),
+        ("windows", "aarch64") => "win_arm64".to_string(),
+        _ => {
+            bail!("don't know the wheel platform tag for {arch} {os}, pass it with --platform-tag")
+        }
+    })
+}
+
+// Cargo versions are semver, which Python packaging tools don't all accept, so pre-releases are
+// converted to their PEP 440 spelling, e.g. `1.0.0-alpha.1` to `1.0.0a1`.  Pre-releases that PEP 440
+// has no spelling for become dev releases, and they're kept with the build metadata as a local
+// version label.
+fn python_version(version: &Version) -> String {
+    let release = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    let mut local = Vec::new();
+    let pre = if version.pre.is_empty() {
+        String::new()
+    } else {
+        let pre = version.pre.as_str();
+        let (label, number) = pre.split_once('.').unwrap_or((pre, ""));
+        let digits = label.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+        let (label, number) = if number.is_empty() && !digits.is_empty() {
+            (&label[..label.len() - digits.len()], digits)
+        } else {
+            (label, number)
+        };
+        let number = if number.is_empty() { "0" } else { number };
+        let prefix = match label.to_ascii_lowercase().as_str() {
+            "alpha" | "a" => Some("a"),
+            "beta" | "b" => Some("b"),
+            "rc" | "c" | "pre" | "preview" => Some("rc"),
+            "dev" => Some(".dev"),
+            _ => None,
+        };
+        match prefix {
+            Some(prefix) if number.chars().all(|c| c.is_ascii_digit()) => {
+                format!("{prefix}{number}")
+            }
+            _ => {
+                local.push(pre);
+                ".dev0".to_string()
+            }
+        }
+    };
+    if !version.build.is_empty() {
+        local.push(version.build.as_str());
+    }
+    if local.is_empty() {
+        format!("{release}{pre}")
+    } else {
+        // Local version labels can only have ASCII letters and digits, separated by dots
+        let local = local
+            .join(".")
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(".");
+        format!("{release}{pre}+{local}")
+    }
+}
+
+// Order the components so each one comes after the components it depends on, which is the order
+// `__init__.py` needs to import them in.
+fn dependency_order(sources: &[Source]) -> Vec<&Source> {
+    let mut ordered: Vec<&Source> = Vec::new();
+    let mut remaining: Vec<&Source> = sources.iter().collect();
+    while !remaining.is_empty() {
+        let pending = remaining.clone();
+        let (ready, not_ready): (Vec<_>, Vec<_>) = remaining.into_iter().partition(|source| {
+            source.package.dependencies.iter().all(|dep| {
+                !pending
+                    .iter()
+                    .any(|s| s.package.name == dep.name && s.crate_name != source.crate_name)
+            })
+        });
+        if ready.is_empty() {
+            // Only possible with dev-dependencies, which the bindings don't import from
+            ordered.extend(not_ready);
+            break;
+        }
+        ordered.extend(ready);
+        remaining = not_ready;
+    }
+    ordered
+}
+
+fn init_module(module_names: &[String]) -> String {
+    let names = module_names
+        .iter()
+        .map(|name| format!("\"{name}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let star_imports = module_names
+        .iter()
+        .map(|name| format!("from .{name} import *\n"))
+        .collect::<String>();
+    format!(
+        "# This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         # Trust me, you don't want to mess with it!\n\
+         \n\
+         import importlib\n\
+         import sys\n\
+         \n\
+         # The bindings import the types of other components by module name, so each module is\n\
+         # also registered under its own name, before the modules that depend on it are loaded.\n\
+         for _name in [{names}]:\n\
+         \x20   sys.modules.setdefault(_name, importlib.import_module(\".\" + _name, __name__))\n\
+         \n\
+         {star_imports}"
+    )
+}
+
+// With `uses_cffi`, the package needs cffi to build its extension modules, and to load them
+fn pyproject(name: &str, version: &str, platform_tag: &str, uses_cffi: bool) -> String {
+    let (build_requires, dependencies) = if uses_cffi {
+        (", \"cffi>=1.12\"", "dependencies = [\"cffi>=1.12\"]\n")
+    } else {
+        ("", "")
+    };
+    format!(
+        "# This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         # Trust me, you don't want to mess with it!\n\
+         \n\
+         [build-system]\n\
+         requires = [\"setuptools>=64\", \"wheel\"{build_requires}]\n\
+         build-backend = \"setuptools.build_meta\"\n\
+         \n\
+         [project]\n\
+         name = \"{name}\"\n\
+         version = \"{version}\"\n\
+         requires-python = \">=3.8\"\n\
+         {dependencies}\
+         \n\
+         [tool.setuptools.packages.find]\n\
+         where = [\"src\"]\n\
+         \n\
+         [tool.setuptools.package-data]\n\
+         \"{name}\" = [\"*.so\", \"*.dylib\", \"*.dll\", \"*.pyi\", \"py.typed\"]\n\
+         \n\
+         # The package includes the library, so the wheel only works on the platform it was built for\n\
+         [tool.distutils.bdist_wheel]\n\
+         plat-name = \"{platform_tag}\"\n"
+    )
+}
+
+// setuptools only builds cffi extension modules that are listed in `setup.py`
+fn setup_py(cffi_modules: &[String]) -> String {
+    let modules = cffi_modules
+        .iter()
+        .map(|module| format!("        \"{module}\",\n"))
+        .collect::<String>();
+    format!(
+        "# This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         # Trust me, you don't want to mess with it!\n\
+         \n\
+         from setuptools import setup\n\
+         \n\
+         setup(\n\
+         \x20   cffi_modules=[\n\
+         {modules}\
+         \x20   ],\n\
+         )\n"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn platform_tags() {
+        assert_eq!(
+            host_platform_tag("linux", "x86_64").unwrap(),
+            "linux_x86_64"
+        );
+        assert_eq!(
+            host_platform_tag("macos", "aarch64").unwrap(),
+            "macosx_11_0_arm64"
+        );
+        assert_eq!(host_platform_tag("windows", "x86_64").unwrap(), "win_amd64");
+        assert!(host_platform_tag("freebsd", "x86_64").is_err());
+    }
+
+    #[test]
+    fn init_module_imports() {
+        let init = init_module(&["geometry".to_string(), "shapes".to_string()]);
+        assert!(init.contains("for _name in [\"geometry\", \"shapes\"]:\n"));
+        assert!(init.ends_with("from .geometry import *\nfrom .shapes import *\n"));
+    }
+
+    #[test]
+    fn pyproject_metadata() {
+        let pyproject = pyproject("arithmetical", "0.1.0", "linux_x86_64", false);
+        assert!(pyproject.contains("name = \"arithmetical\"\nversion = \"0.1.0\"\n"));
+        assert!(pyproject.contains("\"arithmetical\" = [\"*.so\""));
+        assert!(pyproject.ends_with("plat-name = \"linux_x86_64\"\n"));
+        assert!(!pyproject.contains("cffi"));
+    }
+
+    #[test]
+    fn cffi_extension_modules() {
+        let pyproject = pyproject("arithmetical", "0.1.0", "linux_x86_64", true);
+        assert!(pyproject.contains("requires = [\"setuptools>=64\", \"wheel\", \"cffi>=1.12\"]\n"));
+        assert!(
+            pyproject.contains("requires-python = \">=3.8\"\ndependencies = [\"cffi>=1.12\"]\n")
+        );
+        let setup = setup_py(&["src/arithmetical/arithmetic_cffi_build.py:ffibuilder".to_string()]);
+        assert!(setup.contains(
+            "    cffi_modules=[\n        \"src/arithmetical/arithmetic_cffi_build.py:ffibuilder\",\n    ],\n"
+        ));
+    }
+
+    #[test]
+    fn python_versions() {
+        let python_version = |v: &str| python_version(&Version::parse(v).unwrap());
+        assert_eq!(python_version("1.2.3"), "1.2.3");
+        assert_eq!(python_version("1.0.0-alpha.1"), "1.0.0a1");
+        assert_eq!(python_version("1.0.0-beta2"), "1.0.0b2");
+        assert_eq!(python_version("1.0.0-rc.3"), "1.0.0rc3");
+        assert_eq!(python_version("1.0.0-alpha"), "1.0.0a0");
+        assert_eq!(python_version("1.0.0-dev.4"), "1.0.0.dev4");
+        assert_eq!(python_version("1.0.0+build.5"), "1.0.0+build.5");
+        assert_eq!(python_version("1.0.0-rc.1+git-abc"), "1.0.0rc1+git.abc");
+        assert_eq!(python_version("1.0.0-nightly.2"), "1.0.0.dev0+nightly.2");
+    }
+}
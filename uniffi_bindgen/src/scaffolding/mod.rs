@@ -35,11 +35,21 @@ mod filters {
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             Type::String => "String".into(),
             Type::Bytes => "Vec<u8>".into(),
             Type::Timestamp => "std::time::SystemTime".into(),
             Type::Duration => "std::time::Duration".into(),
-            Type::Enum { name, .. } | Type::Record { name, .. } => format!("r#{name}"),
+            Type::Url => "::uniffi::deps::url::Url".into(),
+            Type::Uuid => "::uniffi::deps::uuid::Uuid".into(),
+            Type::Decimal => "::uniffi::deps::rust_decimal::Decimal".into(),
+            Type::Json => "::uniffi::deps::serde_json::Value".into(),
+            Type::Path => "std::path::PathBuf".into(),
+            Type::IpAddr => "std::net::IpAddr".into(),
+            Type::SocketAddr => "std::net::SocketAddr".into(),
+            Type::Enum { name, .. } | Type::Record { name, .. } | Type::Flags { name, .. } => {
+                format!("r#{name}")
+            }
             Type::Object { name, imp, .. } => {
                 format!("std::sync::Arc<{}>", imp.rust_name_for(name))
             }
@@ -49,6 +59,21 @@ mod filters {
                 format!("std::option::Option<{}>", type_rs(inner_type)?)
             }
             Type::Sequence { inner_type } => format!("std::vec::Vec<{}>", type_rs(inner_type)?),
+            Type::Compressed { inner_type } => {
+                format!("::uniffi::Compressed<{}>", type_rs(inner_type)?)
+            }
+            Type::Shared { inner_type } => format!("::uniffi::Shared<{}>", type_rs(inner_type)?),
+            // `type_rs()` of an object is its `Arc`, so this names the object type itself
+            Type::Weak { inner_type } => match inner_type.as_ref() {
+                Type::Object { name, imp, .. } => {
+                    format!("std::sync::Weak<{}>", imp.rust_name_for(name))
+                }
+                _ => {
+                    return Err(askama::Error::Custom(
+                        anyhow::anyhow!("`Weak` references must point to objects").into(),
+                    ))
+                }
+            },
             Type::Map {
                 key_type,
                 value_type,
@@ -88,6 +113,9 @@ mod filters {
                 format!("::uniffi::FutureCallback<{}>", type_ffi(return_type)?)
             }
             FfiType::FutureCallbackData => "*const ()".into(),
+            FfiType::Struct(name) => {
+                format!("<r#{name} as ::uniffi::FfiConverter<crate::UniFfiTag>>::FfiType")
+            }
             FfiType::ForeignExecutorCallback => "::uniffi::ForeignExecutorCallback".into(),
         })
     }
@@ -111,6 +139,17 @@ mod filters {
         })
     }
 
+    // Map an object type to Rust code that specifies its `FfiConverterArc` implementation.
+    pub fn ffi_converter_arc(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Object { name, imp, .. } => format!(
+                "<{} as ::uniffi::FfiConverterArc<crate::UniFfiTag>>",
+                imp.rust_name_for(name)
+            ),
+            _ => unreachable!("{type_:?} is not an object type"),
+        })
+    }
+
     pub fn return_type<T: Callable>(callable: &T) -> Result<String, askama::Error> {
         let return_type = match callable.return_type() {
             Some(t) => type_rs(&t)?,
@@ -3,6 +3,7 @@
 // If the UDL callback interface and Rust trait's methods don't match, the Rust compiler will complain.
 // We generate:
 //  * an init function to accept that `ForeignCallback` from the foreign language, and stores it.
+//  * a detach function, for the foreign language to tell Rust that an object went away.
 //  * a holder for a `ForeignCallback`, of type `uniffi::ForeignCallbackInternals`.
 //  * a proxy `struct` which implements the `trait` that the Callback Interface corresponds to. This
 //    is the object that client code interacts with.
@@ -25,6 +26,21 @@ pub extern "C" fn {{ cbi.ffi_init_callback().name() }}(callback: uniffi::Foreign
     // The call status should be initialized to CALL_SUCCESS, so no need to modify it.
 }
 
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ cbi.ffi_detach_callback().name() }}(handle: u64, call_status: &mut uniffi::RustCallStatus) {
+    uniffi::rust_call(call_status, || {
+        {{ foreign_callback_internals }}.detach(handle);
+        Ok(())
+    })
+}
+
+impl uniffi::ForeignCallbackInterface for dyn r#{{ trait_name }} {
+    fn callback_internals() -> &'static uniffi::ForeignCallbackInternals {
+        &{{ foreign_callback_internals }}
+    }
+}
+
 // Make an implementation which will shell out to the foreign language.
 #[doc(hidden)]
 #[derive(Debug)]
@@ -34,19 +50,27 @@ struct {{ trait_impl }} {
 
 impl {{ trait_impl }} {
     fn new(handle: u64) -> Self {
+        uniffi::track_handle("{{ trait_name }}", handle);
+        {{ foreign_callback_internals }}.attach(handle);
         Self { handle }
     }
 }
 
 impl Drop for {{ trait_impl }} {
     fn drop(&mut self) {
-        {{ foreign_callback_internals }}.invoke_callback::<(), crate::UniFfiTag>(
-            self.handle, uniffi::IDX_CALLBACK_FREE, Default::default()
-        )
+        uniffi::untrack_handle("{{ trait_name }}", self.handle);
+        {{ foreign_callback_internals }}.unregister_handler(self as *const Self as usize, self.handle);
+        // The foreign code already forgot the objects that it detached
+        if {{ foreign_callback_internals }}.release(self.handle) {
+            {{ foreign_callback_internals }}.invoke_callback::<(), crate::UniFfiTag>(
+                self.handle, uniffi::IDX_CALLBACK_FREE, Default::default()
+            )
+        }
     }
 }
 
-uniffi::deps::static_assertions::assert_impl_all!({{ trait_impl }}: Send);
+// See `trait_impl()` in `uniffi_macros/src/export/callback_interface.rs`
+uniffi::deps::static_assertions::assert_impl_all!({{ trait_impl }}: Send, Sync);
 
 impl r#{{ trait_name }} for {{ trait_impl }} {
     {%- for meth in cbi.methods() %}
@@ -79,4 +103,8 @@ impl r#{{ trait_name }} for {{ trait_impl }} {
     {%- endfor %}
 }
 
+{%- if cbi.main_thread() %}
+::uniffi::scaffolding_ffi_converter_main_thread_callback_interface!(r#{{ trait_name }}, {{ trait_impl }});
+{%- else %}
 ::uniffi::scaffolding_ffi_converter_callback_interface!(r#{{ trait_name }}, {{ trait_impl }});
+{%- endif %}
@@ -30,6 +30,7 @@ uniffi::deps::static_assertions::assert_impl_all!({{ obj.rust_name() }}: Sync, S
 pub extern "C" fn {{ ffi_free.name() }}(ptr: *const std::os::raw::c_void, call_status: &mut uniffi::RustCallStatus) {
     uniffi::rust_call(call_status, || {
         assert!(!ptr.is_null());
+        uniffi::untrack_handle("{{ obj.name() }}", ptr as u64);
         {%- match obj.imp() -%}
         {%- when ObjectImpl::Trait %}
         {#- turn it into a Box<Arc<T> and explicitly drop it. #}
@@ -45,6 +46,7 @@ pub extern "C" fn {{ ffi_free.name() }}(ptr: *const std::os::raw::c_void, call_s
 {%- for cons in obj.constructors() %}
     #[doc(hidden)]
     #[no_mangle]
+    #[allow(clippy::useless_conversion)] // Errors are converted with `Into`, even when they're already the right type
     pub extern "C" fn r#{{ cons.ffi_func().name() }}(
         {%- call rs::arg_list_ffi_decl(cons.ffi_func()) %}
     ) -> *const std::os::raw::c_void /* *const {{ obj.name() }} */ {
@@ -129,5 +131,26 @@ pub extern "C" fn {{ ffi_free.name() }}(ptr: *const std::os::raw::c_void, call_s
                 PartialEq::ne({% call rs::_arg_list_rs_call(ne) -%})
             }
         {% call rs::method_decl_postscript(ne) %}
+{%          when UniffiTrait::Ord { cmp }%}
+    {% call rs::method_decl_prelude(cmp) %}
+            {
+                use ::std::cmp::Ord;
+                uniffi::deps::static_assertions::assert_impl_all!({{ obj.rust_name() }}: Ord); // This object has a trait method which requires `Ord` be implemented.
+                // `Ordering` is `repr(i8)`, with `Less` as -1 and `Greater` as 1.
+                Ord::cmp({% call rs::_arg_list_rs_call(cmp) -%}) as i8
+            }
+    {% call rs::method_decl_postscript(cmp) %}
+{%          when UniffiTrait::Restore { serialize, restore }%}
+    {% call rs::method_decl_prelude(serialize) %}
+            {
+                // This object has a trait method which requires `Restorable` be implemented.
+                ::uniffi::serialize_handle::<{{ obj.rust_name() }}>("{{ obj.name() }}", {% call rs::_arg_list_rs_call(serialize) -%})
+            }
+    {% call rs::method_decl_postscript(serialize) %}
+    {% call rs::method_decl_prelude(restore) %}
+            {
+                ::uniffi::restore_handle::<{{ obj.rust_name() }}>("{{ obj.name() }}", &{% call rs::_arg_list_rs_call(restore) -%})
+            }
+    {% call rs::method_decl_postscript(restore) %}
 {%      endmatch %}
 {% endfor %}
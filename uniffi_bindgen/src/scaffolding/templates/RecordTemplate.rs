@@ -6,11 +6,59 @@
 //
 // We define a unit-struct to implement the trait to sidestep Rust's orphan rule (ADR-0006). It's
 // public so other crates can refer to it via an `[External='crate'] typedef`
+//
+// `[ReprC]` records are marked `#[repr(C)]` here, which tells the macro to pass them as structs.
+//
+// `[Traits=(Ord)]` records get a scaffolding function that compares two of them with `Ord::cmp()`.
+//
+// The `{name}Patch` records for `[Patch]` dictionaries aren't provided by the caller, so we define
+// them here, along with the `apply_patch()` method on the patched record.
 #}
 
+{%- match rec.patch_for() %}
+{%- when Some(patched) %}
+#[doc = "Partial update for [`{{ patched }}`], see [`{{ patched }}::apply_patch`]"]
+#[derive(Default)]
+pub struct r#{{ rec.name() }} {
+    {%- for field in rec.fields() %}
+    pub r#{{ field.name() }}: {{ field.as_type().borrow()|type_rs }},
+    {%- endfor %}
+}
+
+impl r#{{ patched }} {
+    /// Overwrite the fields that are set in `patch`, leaving the others unchanged
+    pub fn apply_patch(&mut self, patch: r#{{ rec.name() }}) {
+        {%- for field in rec.fields() %}
+        if let ::std::option::Option::Some(value) = patch.r#{{ field.name() }} {
+            self.r#{{ field.name() }} = value;
+        }
+        {%- endfor %}
+    }
+}
+{%- when None %}
+{%- endmatch %}
+
 #[::uniffi::ffi_converter_record(tag = crate::UniFfiTag)]
+{%- if rec.is_repr_c() %}
+#[repr(C)]
+{%- endif %}
 struct r#{{ rec.name() }} {
     {%- for field in rec.fields() %}
     r#{{ field.name() }}: {{ field.as_type().borrow()|type_rs }},
     {%- endfor %}
 }
+
+{%- match rec.ffi_func_cmp() %}
+{%- when Some(cmp) %}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ cmp.name() }}(
+    a: <r#{{ rec.name() }} as ::uniffi::FfiConverter<crate::UniFfiTag>>::FfiType,
+    b: <r#{{ rec.name() }} as ::uniffi::FfiConverter<crate::UniFfiTag>>::FfiType,
+    call_status: &mut ::uniffi::RustCallStatus,
+) -> i8 {
+    ::uniffi::uniffi_record_cmp::<r#{{ rec.name() }}, crate::UniFfiTag>(a, b, call_status)
+}
+{%- when None %}
+{%- endmatch %}
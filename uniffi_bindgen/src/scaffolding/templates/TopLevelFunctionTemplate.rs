@@ -7,6 +7,8 @@
 #[doc(hidden)]
 #[no_mangle]
 #[allow(clippy::let_unit_value,clippy::unit_arg)] // The generated code uses the unit type like other types to keep things uniform
+#[allow(clippy::not_unsafe_ptr_arg_deref)] // Borrowed objects are used through the pointer that the foreign code passes
+#[allow(clippy::useless_conversion)] // Errors are converted with `Into`, even when they're already the right type
 pub extern "C" fn r#{{ func.ffi_func().name() }}(
     {% call rs::arg_list_ffi_decl(func.ffi_func()) %}
 ) {% call rs::return_signature(func) %} {
@@ -17,3 +19,28 @@ pub extern "C" fn r#{{ func.ffi_func().name() }}(
             {% call rs::to_rs_call(func) %}){% if func.throws() %}.map_err(Into::into){% endif %}
     )
 }
+{%- if ci.is_remote_callable(func) %}
+
+// The remote entry point of the function, see `uniffi::remote_call()`
+uniffi::remote_call_scaffolding! {
+    #[doc(hidden)]
+    #[no_mangle]
+    #[allow(clippy::let_unit_value,clippy::unit_arg,clippy::useless_conversion)]
+    pub extern "C" fn r#{{ func.remote_fn_name() }}(
+        uniffi_args: uniffi::RustBuffer,
+        call_status: &mut uniffi::RustCallStatus,
+    ) -> uniffi::RustBuffer {
+        uniffi::deps::log::debug!("{{ func.remote_fn_name() }}");
+        uniffi::remote_call::<_, {{ func|return_type }}, crate::UniFfiTag>(
+            uniffi_args,
+            call_status,
+            |{% if func.arguments().is_empty() %}_{% endif %}uniffi_args| (
+                {%- for arg in func.arguments() %}
+                uniffi::read_remote_arg::<{{ arg.as_type().borrow()|type_rs }}, crate::UniFfiTag>(uniffi_args, "{{ arg.name() }}"),
+                {%- endfor %}
+            ),
+            |({% for arg in func.arguments() %}r#{{ arg.name() }},{% endfor %})| {% call rs::to_rs_remote_call(func) %}{% if func.throws() %}.map_err(Into::into){% endif %},
+        )
+    }
+}
+{%- endif %}
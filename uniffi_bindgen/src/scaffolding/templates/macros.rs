@@ -8,7 +8,18 @@ r#{{ func.name() }}({% call _arg_list_rs_call(func) -%})
 
 {%- macro _arg_list_rs_call(func) %}
     {%- for arg in func.full_arguments() %}
+        {%- if arg.borrows_bytes() %}
+        {#  borrowed bytes are viewed in place, there's nothing to lift #}
+        r#{{ arg.name() }}.as_slice()
+        {%- else if arg.borrows_object() %}
+        {#  borrowed objects use the foreign code's reference, without cloning the Arc #}
+        unsafe { {{ arg.as_type().borrow()|ffi_converter_arc }}::lift_ref(r#{{ arg.name() }}) }
+        {%- else %}
+        {%- if arg.wipe_lifted_buffer() %}
+        match uniffi::try_lift_sensitive_from_rust_buffer::<{{ arg.as_type().borrow()|type_rs }}, crate::UniFfiTag>(r#{{ arg.name() }}) {
+        {%- else %}
         match {{- arg.as_type().borrow()|ffi_converter }}::try_lift(r#{{ arg.name() }}) {
+        {%- endif %}
         {%- if arg.by_ref() %}
         {#  args passed by reference get special treatment for traits and their Box<> #}
         {%-     if arg.is_trait_ref() %}
@@ -23,19 +34,39 @@ r#{{ func.name() }}({% call _arg_list_rs_call(func) -%})
 
         {#- If this function returns an error, we attempt to downcast errors doing arg
             conversions to this error. If the downcast fails or the function doesn't
-            return an error, we just panic.
+            return an error, the call fails with an invalid argument error.
         -#}
         {%- match func.throws_type() -%}
         {%- when Some with (e) %}
             Err(err) => return Err(uniffi::lower_anyhow_error_or_panic::<crate::UniFfiTag, {{ e|type_rs }}>(err, "{{ arg.name() }}")),
         {%- else %}
-            Err(err) => panic!("Failed to convert arg '{}': {}", "{{ arg.name() }}", err),
+            Err(err) => uniffi::lift_arg_failed("{{ arg.name() }}", err),
         {%- endmatch %}
         }
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
 
+{#-
+// Call a function with the args that its remote entry point read, which are in locals named after
+// the args.  Borrowed bytes are read into a `Vec<u8>`, which the call borrows.
+#}
+{%- macro to_rs_remote_call(func) -%}
+r#{{ func.name() }}(
+    {%- for arg in func.full_arguments() %}
+    {%- if arg.borrows_bytes() %}
+    r#{{ arg.name() }}.as_slice()
+    {%- else if arg.by_ref() %}
+    &r#{{ arg.name() }}
+    {%- else %}
+    r#{{ arg.name() }}
+    {%- endif %}
+    {%- if !loop.last %}, {% endif %}
+    {%- endfor -%}
+)
+{%- endmacro -%}
+
 {#-
 // Arglist as used in the _UniFFILib function declarations.
 // Note unfiltered name but type_ffi filters.
@@ -67,6 +98,8 @@ r#{{ func.name() }}({% call _arg_list_rs_call(func) -%})
 #[doc(hidden)]
 #[no_mangle]
 #[allow(clippy::let_unit_value,clippy::unit_arg)] // The generated code uses the unit type like other types to keep things uniform
+#[allow(clippy::not_unsafe_ptr_arg_deref)] // Borrowed objects are used through the pointer that the foreign code passes
+#[allow(clippy::useless_conversion)] // Errors are converted with `Into`, even when they're already the right type
 pub extern "C" fn r#{{ meth.ffi_func().name() }}(
     {%- call arg_list_ffi_decl(meth.ffi_func()) %}
 ) {% call return_signature(meth) %} {
@@ -0,0 +1,253 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Generate a Swift package for the components in a library.
+//!
+//! The package has everything SwiftPM needs except the compiled libraries, which depend on the
+//! machine that builds them:
+//!
+//!   - `Package.swift`, with a Swift target for the bindings that depends on a binary target
+//!     for the `<name>FFI.xcframework`.
+//!   - `Sources/<name>/`, with the Swift bindings of each component.
+//!   - `Headers/`, with the FFI header of each component and a `module.modulemap` that declares
+//!     their modules.
+//!   - `build-xcframework.sh`, which builds the static library for each target and assembles
+//!     the xcframework from them and the headers, using `uniffi-bindgen bundle`.
+
+use crate::{
+    bindings::{swift, TargetLanguage},
+    bundle, library_mode, Result,
+};
+use anyhow::{bail, Context};
+use camino::Utf8Path;
+use fs_err as fs;
+use std::process::Command;
+
+/// The targets that are built when none are passed
+pub const DEFAULT_TARGETS: &[&str] = &[
+    "aarch64-apple-ios",
+    "aarch64-apple-ios-sim",
+    "x86_64-apple-ios",
+    "aarch64-apple-darwin",
+    "x86_64-apple-darwin",
+];
+
+/// Write a Swift package named `name` for the library at `library_path` to `out_dir`.
+///
+/// `targets` are the Apple target triples that `build-xcframework.sh` builds the library for.
+pub fn generate_swift_package(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    name: &str,
+    targets: &[String],
+    out_dir: &Utf8Path,
+    try_format_code: bool,
+) -> Result<()> {
+    if targets.is_empty() {
+        bail!("no targets to build the Swift package for");
+    }
+    let platforms = package_platforms(targets)?;
+    let library_name = library_name(library_path)?;
+    let sources = library_mode::find_components(library_path, crate_name)?;
+    let package = sources
+        .iter()
+        .find(|s| s.crate_name == library_name)
+        .map(|s| &s.package)
+        .with_context(|| format!("couldn't find the crate that builds {library_path}"))?;
+
+    let sources_dir = out_dir.join("Sources").join(name);
+    let headers_dir = out_dir.join("Headers");
+    fs::create_dir_all(&sources_dir)?;
+    fs::create_dir_all(&headers_dir)?;
+    let mut modules = Vec::new();
+    for source in &sources {
+        let config = &source.config.bindings.swift;
+        let hidden = source.config.bindings.hidden_items(TargetLanguage::Swift);
+        let ci = source.ci.without_hidden_items(hidden)?;
+        let bindings = swift::generate_bindings(config, &ci)?;
+        let source_file = sources_dir.join(format!("{}.swift", config.module_name()));
+        fs::write(&source_file, bindings.library)?;
+        fs::write(headers_dir.join(config.header_filename()), bindings.header)?;
+        modules.push((config.ffi_module_name(), config.header_filename()));
+        if try_format_code {
+            if let Err(e) = Command::new("swiftformat")
+                .arg(source_file.as_str())
+                .output()
+            {
+                println!(
+                    "Warning: Unable to auto-format {} using swiftformat: {e:?}",
+                    source_file.file_name().unwrap(),
+                );
+            }
+        }
+    }
+    fs::write(headers_dir.join("module.modulemap"), modulemap(&modules))?;
+    fs::write(
+        out_dir.join("Package.swift"),
+        package_manifest(name, &platforms),
+    )?;
+
+    let script_path = out_dir.join("build-xcframework.sh");
+    fs::write(
+        &script_path,
+        build_script(
+            name,
+            library_name,
+            &package.name,
+            package.manifest_path.as_str(),
+            targets,
+        ),
+    )?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// The name of a library, without the `lib` prefix and the extension
+fn library_name(library_path: &Utf8Path) -> Result<&str> {
+    let stem = library_path
+        .file_stem()
+        .with_context(|| format!("{library_path} is not a library file"))?;
+    Ok(stem.strip_prefix("lib").unwrap_or(stem))
+}
+
+/// The entries of the `platforms` list in `Package.swift`
+fn package_platforms(targets: &[String]) -> Result<Vec<&'static str>> {
+    let mut platforms = Vec::new();
+    for target in targets {
+        let platform = match bundle::apple_platform(target) {
+            Some("ios") => ".iOS(.v13)",
+            Some(_) => ".macOS(.v10_15)",
+            None => bail!("`{target}` is not an Apple target"),
+        };
+        if !platforms.contains(&platform) {
+            platforms.push(platform);
+        }
+    }
+    Ok(platforms)
+}
+
+fn modulemap(modules: &[(String, String)]) -> String {
+    let mut contents = String::from(
+        "// This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         // Trust me, you don't want to mess with it!\n",
+    );
+    for (module_name, header_filename) in modules {
+        contents.push_str(&format!(
+            "module {module_name} {{\n    header \"{header_filename}\"\n    export *\n}}\n"
+        ));
+    }
+    contents
+}
+
+fn package_manifest(name: &str, platforms: &[&str]) -> String {
+    format!(
+        "// swift-tools-version:5.5\n\
+         // This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         // Trust me, you don't want to mess with it!\n\
+         \n\
+         import PackageDescription\n\
+         \n\
+         let package = Package(\n\
+         \x20   name: \"{name}\",\n\
+         \x20   platforms: [{platforms}],\n\
+         \x20   products: [\n\
+         \x20       .library(name: \"{name}\", targets: [\"{name}\"]),\n\
+         \x20   ],\n\
+         \x20   targets: [\n\
+         \x20       // Assembled by `build-xcframework.sh`\n\
+         \x20       .binaryTarget(name: \"{name}FFI\", path: \"{name}FFI.xcframework\"),\n\
+         \x20       .target(name: \"{name}\", dependencies: [\"{name}FFI\"]),\n\
+         \x20   ]\n\
+         )\n",
+        platforms = platforms.join(", "),
+    )
+}
+
+fn build_script(
+    name: &str,
+    library_name: &str,
+    package_name: &str,
+    manifest_path: &str,
+    targets: &[String],
+) -> String {
+    format!(
+        "#!/bin/sh\n\
+         # This file was autogenerated by some hot garbage in the `uniffi` crate.\n\
+         #\n\
+         # Builds `lib{library_name}.a` for each target, and assembles the libraries and the headers\n\
+         # into `{name}FFI.xcframework`.  Architectures for the same platform are merged with `lipo`,\n\
+         # so this needs to run on macOS.\n\
+         #\n\
+         # Set MANIFEST_PATH if the crate has moved, and UNIFFI_BINDGEN to the command that runs\n\
+         # your `uniffi-bindgen` binary.\n\
+         set -eu\n\
+         \n\
+         cd \"$(dirname \"$0\")\"\n\
+         MANIFEST_PATH=\"${{MANIFEST_PATH:-{manifest_path}}}\"\n\
+         UNIFFI_BINDGEN=\"${{UNIFFI_BINDGEN:-uniffi-bindgen}}\"\n\
+         TARGETS=\"{targets}\"\n\
+         TARGET_DIR=\"${{CARGO_TARGET_DIR:-$(cargo metadata --format-version 1 --no-deps --manifest-path \"$MANIFEST_PATH\" | sed -e 's/.*\"target_directory\":\"\\([^\"]*\\)\".*/\\1/')}}\"\n\
+         \n\
+         LIBS=\"\"\n\
+         for target in $TARGETS; do\n\
+         \x20   cargo build --manifest-path \"$MANIFEST_PATH\" --package {package_name} --lib --release --target \"$target\"\n\
+         \x20   LIBS=\"$LIBS --lib $target=$TARGET_DIR/$target/release/lib{library_name}.a\"\n\
+         done\n\
+         \n\
+         rm -rf build \"{name}FFI.xcframework\"\n\
+         $UNIFFI_BINDGEN bundle \"{name}FFI\" $LIBS --bindings-dir Headers --out-dir build\n\
+         mv \"build/apple/{name}FFI.xcframework\" .\n\
+         rm -rf build\n",
+        targets = targets.join(" "),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn library_names() {
+        assert_eq!(
+            library_name(Utf8Path::new("target/release/libarithmetical.a")).unwrap(),
+            "arithmetical"
+        );
+        assert_eq!(
+            library_name(Utf8Path::new("target/release/libarithmetical.dylib")).unwrap(),
+            "arithmetical"
+        );
+    }
+
+    #[test]
+    fn platforms() {
+        let targets =
+            |targets: &[&str]| -> Vec<String> { targets.iter().map(ToString::to_string).collect() };
+        assert_eq!(
+            package_platforms(&targets(DEFAULT_TARGETS)).unwrap(),
+            vec![".iOS(.v13)", ".macOS(.v10_15)"]
+        );
+        assert_eq!(
+            package_platforms(&targets(&["aarch64-apple-ios", "aarch64-apple-ios-sim"])).unwrap(),
+            vec![".iOS(.v13)"]
+        );
+        assert!(package_platforms(&targets(&["aarch64-linux-android"])).is_err());
+    }
+
+    #[test]
+    fn manifest() {
+        let manifest = package_manifest("Arithmetic", &[".iOS(.v13)"]);
+        assert!(manifest.starts_with("// swift-tools-version:5.5\n"));
+        assert!(manifest.contains("    platforms: [.iOS(.v13)],\n"));
+        assert!(manifest.contains(
+            "        .binaryTarget(name: \"ArithmeticFFI\", path: \"ArithmeticFFI.xcframework\"),\n"
+        ));
+        assert!(manifest.contains(
+            "        .target(name: \"Arithmetic\", dependencies: [\"ArithmeticFFI\"]),\n"
+        ));
+    }
+}
@@ -0,0 +1,786 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # UDL export
+//!
+//! Write the interface of a component as a UDL file, so crates that only use the proc-macros can
+//! still publish an interface definition.  The UDL is rebuilt from the [ComponentInterface], so
+//! it's in a canonical form: items are in the order of the component interface, with one
+//! declaration per line and 4-space indents.
+//!
+//! Some things that can be exported with the proc-macros can't be described in UDL, like async
+//! functions, constants, flags, non-exhaustive enums, enums with explicit discriminants and
+//! `Compressed` or `Shared` values and `Weak` references.  Those items are left out, with a comment
+//! in their place that says why.  Items that use a type that was left out are commented out too, so
+//! the UDL never refers to a type it doesn't define.
+
+use crate::{
+    interface::{
+        Argument, AsType, CallbackInterface, ComponentInterface, Constructor, Enum, ExternalKind,
+        Field, Function, Literal, Method, Object, ObjectImpl, Radix, Record, Type, UniffiTrait,
+    },
+    library_mode, Result,
+};
+use anyhow::bail;
+use camino::Utf8Path;
+use fs_err as fs;
+use std::collections::BTreeSet;
+
+/// Export the UDL of the components in a library.
+///
+/// Each component is written to `<namespace>.udl` in `out_dir`, or printed to stdout if there's
+/// no `out_dir`.
+pub fn export_udl(
+    library_path: &Utf8Path,
+    crate_name: Option<String>,
+    out_dir: Option<&Utf8Path>,
+) -> Result<()> {
+    let sources = library_mode::find_components(library_path, crate_name)?;
+    for source in &sources {
+        let udl = component_udl(&source.ci);
+        match out_dir {
+            Some(out_dir) => {
+                fs::create_dir_all(out_dir)?;
+                fs::write(out_dir.join(format!("{}.udl", source.ci.namespace())), udl)?;
+            }
+            None => print!("{udl}"),
+        }
+    }
+    Ok(())
+}
+
+/// The UDL for a component
+pub fn component_udl(ci: &ComponentInterface) -> String {
+    let omitted = omitted_types(ci);
+    let mut udl = format!(
+        "{}namespace {} {{\n",
+        docstring(ci.namespace_docstring()),
+        ci.namespace()
+    );
+    for constant in ci.constant_definitions() {
+        udl.push_str(&format!(
+            "    // constant `{}` can't be described in UDL\n",
+            constant.name()
+        ));
+    }
+    for func in ci.function_definitions() {
+        let item = check_uses(
+            &format!("function `{}`", func.name()),
+            func.iter_types(),
+            &omitted,
+        )
+        .and_then(|()| function(func));
+        udl.push_str(&item_or_comment(item, 1));
+    }
+    udl.push_str("};\n");
+
+    for (name, builtin) in ci.iter_custom_types() {
+        udl.push('\n');
+        let item = check_uses(
+            &format!("custom type `{name}`"),
+            builtin.iter_types(),
+            &omitted,
+        )
+        .and_then(|()| type_expr(builtin))
+        .map(|builtin| format!("[Custom]\ntypedef {builtin} {name};\n"));
+        udl.push_str(&item_or_comment(item, 0));
+    }
+    for (name, crate_name, kind) in ci.iter_external_types() {
+        let attribute = match kind {
+            ExternalKind::DataClass => "External",
+            ExternalKind::Interface => "ExternalInterface",
+            ExternalKind::Flags => "ExternalFlags",
+        };
+        udl.push_str(&format!(
+            "\n[{attribute}=\"{crate_name}\"]\ntypedef extern {name};\n"
+        ));
+    }
+    // `{name}Patch` records are generated from the `[Patch]` attribute of the record they update
+    for rec in ci.record_definitions().filter(|r| r.patch_for().is_none()) {
+        udl.push('\n');
+        let item = check_uses(
+            &format!("record `{}`", rec.name()),
+            rec.iter_types(),
+            &omitted,
+        )
+        .and_then(|()| record(ci, rec));
+        udl.push_str(&item_or_comment(item, 0));
+    }
+    for e in ci.enum_definitions() {
+        udl.push('\n');
+        let item = check_uses(&format!("enum `{}`", e.name()), e.iter_types(), &omitted)
+            .and_then(|()| enum_(ci, e));
+        udl.push_str(&item_or_comment(item, 0));
+    }
+    for flags in ci.flags_definitions() {
+        udl.push_str(&format!(
+            "\n// flags `{}` can't be described in UDL\n",
+            flags.name()
+        ));
+    }
+    for obj in ci.object_definitions() {
+        udl.push('\n');
+        udl.push_str(&object(obj, &omitted));
+    }
+    for cbi in ci.callback_interface_definitions() {
+        udl.push('\n');
+        udl.push_str(&callback_interface(cbi, &omitted));
+    }
+    udl
+}
+
+// The names of the types that are left out of the UDL, either because they can't be described in
+// UDL or because they use a type that was left out.
+fn omitted_types(ci: &ComponentInterface) -> BTreeSet<String> {
+    let mut omitted: BTreeSet<String> = ci
+        .flags_definitions()
+        .map(|flags| flags.name().to_string())
+        .collect();
+    loop {
+        let mut newly_omitted = Vec::new();
+        for (name, builtin) in ci.iter_custom_types() {
+            if omitted_type(builtin.iter_types(), &omitted).is_some() || type_expr(builtin).is_err()
+            {
+                newly_omitted.push(name.clone());
+            }
+        }
+        for rec in ci.record_definitions() {
+            if omitted_type(rec.iter_types(), &omitted).is_some() || record(ci, rec).is_err() {
+                newly_omitted.push(rec.name().to_string());
+            }
+        }
+        for e in ci.enum_definitions() {
+            if omitted_type(e.iter_types(), &omitted).is_some() || enum_(ci, e).is_err() {
+                newly_omitted.push(e.name().to_string());
+            }
+        }
+        let count = omitted.len();
+        omitted.extend(newly_omitted);
+        if omitted.len() == count {
+            return omitted;
+        }
+    }
+}
+
+// The first of `types` that was left out of the UDL
+fn omitted_type<'a>(
+    mut types: impl Iterator<Item = &'a Type>,
+    omitted: &BTreeSet<String>,
+) -> Option<&'a str> {
+    types.find_map(|type_| match type_ {
+        Type::Record { name, .. } | Type::Enum { name, .. } | Type::Custom { name, .. }
+            if omitted.contains(name) =>
+        {
+            Some(name.as_str())
+        }
+        _ => None,
+    })
+}
+
+// Fail if an item uses a type that was left out of the UDL.
+fn check_uses<'a>(
+    item: &str,
+    types: impl Iterator<Item = &'a Type>,
+    omitted: &BTreeSet<String>,
+) -> Result<()> {
+    match omitted_type(types, omitted) {
+        Some(name) => bail!("{item} uses `{name}`, which can't be described in UDL"),
+        None => Ok(()),
+    }
+}
+
+// Indent an item, or replace it with a comment if it can't be described in UDL.
+fn item_or_comment(item: Result<String>, depth: usize) -> String {
+    let indent = "    ".repeat(depth);
+    match item {
+        Ok(item) => item
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    "\n".to_string()
+                } else {
+                    format!("{indent}{line}\n")
+                }
+            })
+            .collect(),
+        // Every line of the message is commented out, so a multi-line message can't break the UDL
+        Err(e) => e
+            .to_string()
+            .lines()
+            .map(|line| format!("{indent}// {line}\n"))
+            .collect(),
+    }
+}
+
+fn docstring(docstring: Option<&str>) -> String {
+    docstring
+        .map(|docstring| {
+            docstring
+                .lines()
+                .map(|line| match line {
+                    "" => "///\n".to_string(),
+                    line => format!("/// {line}\n"),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn attributes(attrs: &[String]) -> String {
+    if attrs.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]\n", attrs.join(", "))
+    }
+}
+
+fn throws(throws_name: Option<&str>) -> Vec<String> {
+    throws_name
+        .map(|name| format!("Throws={name}"))
+        .into_iter()
+        .collect()
+}
+
+fn function(func: &Function) -> Result<String> {
+    if func.is_async() {
+        bail!("async function `{}` can't be described in UDL", func.name());
+    }
+    Ok(format!(
+        "{}{}{} {}({});\n",
+        docstring(func.docstring()),
+        attributes(&throws(func.throws_name())),
+        return_type(func.return_type())?,
+        func.name(),
+        arguments(func.arguments())?,
+    ))
+}
+
+fn constructor(cons: &Constructor) -> Result<String> {
+    let mut attrs = throws(cons.throws_name());
+    if !cons.is_primary_constructor() {
+        attrs.push(format!("Name={}", cons.name()));
+    }
+    Ok(format!(
+        "{}{}constructor({});\n",
+        docstring(cons.docstring()),
+        attributes(&attrs),
+        arguments(cons.arguments())?,
+    ))
+}
+
+fn method(meth: &Method) -> Result<String> {
+    if meth.is_async() {
+        bail!("async method `{}` can't be described in UDL", meth.name());
+    }
+    let mut attrs = throws(meth.throws_name());
+    if meth.takes_self_by_arc() {
+        attrs.push("Self=ByArc".to_string());
+    }
+    Ok(format!(
+        "{}{}{} {}({});\n",
+        docstring(meth.docstring()),
+        attributes(&attrs),
+        return_type(meth.return_type())?,
+        meth.name(),
+        arguments(meth.arguments())?,
+    ))
+}
+
+fn checked_method(meth: &Method, omitted: &BTreeSet<String>) -> Result<String> {
+    check_uses(
+        &format!("method `{}`", meth.name()),
+        meth.iter_types(),
+        omitted,
+    )
+    .and_then(|()| method(meth))
+}
+
+fn arguments(args: Vec<&Argument>) -> Result<String> {
+    Ok(args
+        .into_iter()
+        .map(argument)
+        .collect::<Result<Vec<_>>>()?
+        .join(", "))
+}
+
+fn argument(arg: &Argument) -> Result<String> {
+    let mut attrs = Vec::new();
+    if arg.by_ref() {
+        attrs.push("ByRef");
+    }
+    if arg.sensitive() {
+        attrs.push("Sensitive");
+    }
+    let attrs = if attrs.is_empty() {
+        String::new()
+    } else {
+        format!("[{}] ", attrs.join(", "))
+    };
+    let type_ = type_expr(&arg.as_type())?;
+    Ok(match arg.default_value() {
+        Some(default) => format!(
+            "{attrs}optional {type_} {} = {}",
+            arg.name(),
+            literal(default)?
+        ),
+        None => format!("{attrs}{type_} {}", arg.name()),
+    })
+}
+
+fn return_type(type_: Option<&Type>) -> Result<String> {
+    type_.map_or(Ok("void".to_string()), type_expr)
+}
+
+fn fields(fields: &[Field]) -> Result<String> {
+    fields
+        .iter()
+        .map(|field| {
+            let type_ = type_expr(&field.as_type())?;
+            Ok(match field.default_value() {
+                Some(default) => format!("{type_} {} = {};\n", field.name(), literal(default)?),
+                None => format!("{type_} {};\n", field.name()),
+            })
+        })
+        .collect()
+}
+
+fn record(ci: &ComponentInterface, rec: &Record) -> Result<String> {
+    let mut attrs = Vec::new();
+    if rec.is_repr_c() {
+        attrs.push("ReprC".to_string());
+    }
+    if rec.is_ord() {
+        attrs.push("Traits=(Ord)".to_string());
+    }
+    let patch_name = format!("{}Patch", rec.name());
+    if ci
+        .get_record_definition(&patch_name)
+        .and_then(|patch| patch.patch_for())
+        == Some(rec.name())
+    {
+        attrs.push("Patch".to_string());
+    }
+    Ok(format!(
+        "{}{}dictionary {} {{\n{}}};\n",
+        docstring(rec.docstring()),
+        attributes(&attrs),
+        rec.name(),
+        indent(&fields(rec.fields())?),
+    ))
+}
+
+fn enum_(ci: &ComponentInterface, e: &Enum) -> Result<String> {
+    if e.is_non_exhaustive() {
+        bail!(
+            "non-exhaustive enum `{}` can't be described in UDL",
+            e.name()
+        );
+    }
+    if e.has_discriminants() {
+        bail!(
+            "discriminants of enum `{}` can't be described in UDL",
+            e.name()
+        );
+    }
+    let is_error = ci.is_name_used_as_error(e.name());
+    let doc = docstring(e.docstring());
+    let error_attr = if is_error { "[Error]\n" } else { "" };
+    if e.is_flat() {
+        if e.variants().iter().any(|v| v.has_fields()) {
+            bail!(
+                "the error fields of flat error `{}` can't be described in UDL",
+                e.name()
+            );
+        }
+        let variants = e
+            .variants()
+            .iter()
+            .map(|v| format!("    \"{}\",\n", v.name()))
+            .collect::<String>();
+        return Ok(format!(
+            "{doc}{error_attr}enum {} {{\n{variants}}};\n",
+            e.name()
+        ));
+    }
+    let enum_attr = if is_error { error_attr } else { "[Enum]\n" };
+    let variants = e
+        .variants()
+        .iter()
+        .map(|v| {
+            let fields = v
+                .fields()
+                .iter()
+                .map(|f| Ok(format!("{} {}", type_expr(&f.as_type())?, f.name())))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(format!("    {}({});\n", v.name(), fields.join(", ")))
+        })
+        .collect::<Result<String>>()?;
+    Ok(format!(
+        "{doc}{enum_attr}interface {} {{\n{variants}}};\n",
+        e.name()
+    ))
+}
+
+fn object(obj: &Object, omitted: &BTreeSet<String>) -> String {
+    let mut attrs = Vec::new();
+    if obj.imp() == &ObjectImpl::Trait {
+        attrs.push("Trait".to_string());
+    }
+    let traits = obj
+        .uniffi_traits()
+        .into_iter()
+        .map(|t| match t {
+            UniffiTrait::Debug { .. } => "Debug",
+            UniffiTrait::Display { .. } => "Display",
+            UniffiTrait::Eq { .. } => "Eq",
+            UniffiTrait::Hash { .. } => "Hash",
+            UniffiTrait::Ord { .. } => "Ord",
+            UniffiTrait::Restore { .. } => "Restore",
+        })
+        .collect::<Vec<_>>();
+    if !traits.is_empty() {
+        attrs.push(format!("Traits=({})", traits.join(", ")));
+    }
+    let mut members = String::new();
+    for cons in obj.constructors() {
+        let item = check_uses(
+            &format!("constructor `{}`", cons.name()),
+            cons.iter_types(),
+            omitted,
+        )
+        .and_then(|()| constructor(cons));
+        members.push_str(&item_or_comment(item, 1));
+    }
+    for meth in obj.methods() {
+        members.push_str(&item_or_comment(checked_method(meth, omitted), 1));
+    }
+    for constant in obj.constants() {
+        members.push_str(&format!(
+            "    // constant `{}` can't be described in UDL\n",
+            constant.name()
+        ));
+    }
+    format!(
+        "{}{}interface {} {{\n{members}}};\n",
+        docstring(obj.docstring()),
+        attributes(&attrs),
+        obj.name()
+    )
+}
+
+fn callback_interface(cbi: &CallbackInterface, omitted: &BTreeSet<String>) -> String {
+    let mut members = String::new();
+    for meth in cbi.methods() {
+        members.push_str(&item_or_comment(checked_method(meth, omitted), 1));
+    }
+    for constant in cbi.constants() {
+        members.push_str(&format!(
+            "    // constant `{}` can't be described in UDL\n",
+            constant.name()
+        ));
+    }
+    format!(
+        "{}callback interface {} {{\n{members}}};\n",
+        docstring(cbi.docstring()),
+        cbi.name()
+    )
+}
+
+fn indent(lines: &str) -> String {
+    lines.lines().map(|line| format!("    {line}\n")).collect()
+}
+
+/// A type as it's written in UDL
+fn type_expr(type_: &Type) -> Result<String> {
+    Ok(match type_ {
+        Type::UInt8 => "u8".to_string(),
+        Type::Int8 => "i8".to_string(),
+        Type::UInt16 => "u16".to_string(),
+        Type::Int16 => "i16".to_string(),
+        Type::UInt32 => "u32".to_string(),
+        Type::Int32 => "i32".to_string(),
+        Type::UInt64 => "u64".to_string(),
+        Type::Int64 => "i64".to_string(),
+        Type::Float32 => "f32".to_string(),
+        Type::Float64 => "f64".to_string(),
+        Type::Boolean => "boolean".to_string(),
+        Type::Char => "char".to_string(),
+        Type::String => "string".to_string(),
+        Type::Bytes => "bytes".to_string(),
+        Type::Timestamp => "timestamp".to_string(),
+        Type::Duration => "duration".to_string(),
+        Type::Url => "url".to_string(),
+        Type::Uuid => "uuid".to_string(),
+        Type::Decimal => "decimal".to_string(),
+        Type::Json => "json".to_string(),
+        Type::Path => "path".to_string(),
+        Type::IpAddr => "ipaddr".to_string(),
+        Type::SocketAddr => "socketaddr".to_string(),
+        Type::ForeignExecutor => "ForeignExecutor".to_string(),
+        Type::Object { name, .. }
+        | Type::Record { name, .. }
+        | Type::Enum { name, .. }
+        | Type::CallbackInterface { name, .. }
+        | Type::External { name, .. }
+        | Type::Custom { name, .. } => name.clone(),
+        Type::Optional { inner_type } => format!("{}?", type_expr(inner_type)?),
+        Type::Sequence { inner_type } => format!("sequence<{}>", type_expr(inner_type)?),
+        Type::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "record<{}, {}>",
+            type_expr(key_type)?,
+            type_expr(value_type)?
+        ),
+        Type::Compressed { .. } => bail!("`Compressed` values can't be described in UDL"),
+        Type::Shared { .. } => bail!("`Shared` values can't be described in UDL"),
+        Type::Weak { .. } => bail!("`Weak` references can't be described in UDL"),
+        Type::Flags { name, .. } => bail!("flags `{name}` can't be described in UDL"),
+    })
+}
+
+/// A default value as it's written in UDL
+fn literal(literal: &Literal) -> Result<String> {
+    Ok(match literal {
+        Literal::Boolean(b) => b.to_string(),
+        Literal::String(s) | Literal::Enum(s, _) => format!("\"{s}\""),
+        Literal::UInt(v, Radix::Hexadecimal, _) => format!("{v:#x}"),
+        Literal::UInt(v, Radix::Octal, _) if *v != 0 => format!("0{v:o}"),
+        Literal::UInt(v, _, _) => v.to_string(),
+        Literal::Int(v, Radix::Hexadecimal, _) if *v >= 0 => format!("{v:#x}"),
+        Literal::Int(v, Radix::Octal, _) if *v > 0 => format!("0{v:o}"),
+        Literal::Int(v, _, _) => v.to_string(),
+        Literal::Float(s, _) => s.clone(),
+        Literal::EmptySequence => "[]".to_string(),
+        Literal::Null => "null".to_string(),
+        Literal::EmptyMap => bail!("empty map default values can't be described in UDL"),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const UDL: &str = r#"namespace geometry {
+    [Throws=GeometryError]
+    f64 gradient(Line ln, optional u32 precision = 0x10);
+    Point? intersection([ByRef] Line ln1, Line ln2);
+};
+
+[Custom]
+typedef string Label;
+
+dictionary Point {
+    f64 x;
+    f64 y;
+    Label? label = null;
+};
+
+dictionary Line {
+    Point start;
+    Point end;
+    sequence<string> tags = [];
+};
+
+[Error]
+enum GeometryError {
+    "Parallel",
+};
+
+enum Kind {
+    "Open",
+    "Closed",
+};
+
+[Enum]
+interface Shape {
+    Polygon(sequence<Point> points, Kind kind);
+    Circle(Point center, f64 radius);
+};
+
+[Traits=(Debug, Eq)]
+interface Canvas {
+    constructor();
+    [Name=with_size]
+    constructor(u32 width, u32 height);
+    [Throws=GeometryError]
+    void draw(Shape shape);
+    [Self=ByArc]
+    record<string, Shape> shapes();
+};
+
+callback interface Listener {
+    void changed(string name);
+};
+"#;
+
+    #[test]
+    fn round_trip() {
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let udl = component_udl(&ci);
+        let round_tripped = ComponentInterface::from_webidl(&udl).unwrap();
+        assert_eq!(component_udl(&round_tripped), udl);
+        assert!(udl.starts_with(
+            "namespace geometry {\n    [Throws=GeometryError]\n    \
+             f64 gradient(Line ln, optional u32 precision = 0x10);\n"
+        ));
+        assert!(udl.contains("[Custom]\ntypedef string Label;\n"));
+        assert!(udl.contains("    Label? label = null;\n"));
+        assert!(udl.contains("[Error]\nenum GeometryError {\n    \"Parallel\",\n};\n"));
+        assert!(udl.contains("    Polygon(sequence<Point> points, Kind kind);\n"));
+        assert!(udl.contains("[Traits=(Debug, Eq)]\ninterface Canvas {\n    constructor();\n"));
+        assert!(udl.contains("    [Name=with_size]\n    constructor(u32 width, u32 height);\n"));
+        assert!(udl.contains("    [Self=ByArc]\n    record<string, Shape> shapes();\n"));
+        assert!(udl.contains("callback interface Listener {\n    void changed(string name);\n"));
+    }
+
+    #[test]
+    fn docstrings() {
+        const UDL: &str = r#"
+/// Helpers for lines
+///
+/// Every function is exact.
+namespace geometry {
+    /// The gradient of `ln`
+    f64 gradient(f64 ln);
+};
+
+/// A point on a plane
+dictionary Point {
+    f64 x;
+    f64 y;
+};
+"#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let udl = component_udl(&ci);
+        assert!(udl.starts_with(
+            "/// Helpers for lines\n///\n/// Every function is exact.\nnamespace geometry {\n    \
+             /// The gradient of `ln`\n    f64 gradient(f64 ln);\n"
+        ));
+        assert!(udl.contains("/// A point on a plane\ndictionary Point {\n"));
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(
+            literal(&Literal::UInt(255, Radix::Hexadecimal, Type::UInt8)).unwrap(),
+            "0xff"
+        );
+        assert_eq!(
+            literal(&Literal::Int(8, Radix::Octal, Type::Int32)).unwrap(),
+            "010"
+        );
+        assert_eq!(
+            literal(&Literal::Int(-3, Radix::Decimal, Type::Int32)).unwrap(),
+            "-3"
+        );
+        assert_eq!(
+            literal(&Literal::Float("2.5".into(), Type::Float64)).unwrap(),
+            "2.5"
+        );
+        assert!(literal(&Literal::EmptyMap).is_err());
+    }
+
+    #[test]
+    fn unsupported_items_are_comments() {
+        assert_eq!(
+            item_or_comment(
+                type_expr(&Type::Compressed {
+                    inner_type: Box::new(Type::String)
+                })
+                .map(|t| format!("{t} compressed();\n")),
+                1
+            ),
+            "    // `Compressed` values can't be described in UDL\n"
+        );
+    }
+
+    #[test]
+    fn commented_out_items_round_trip() {
+        let record = |name: &str, field_type| {
+            uniffi_meta::RecordMetadata {
+                module_path: "archive".to_string(),
+                name: name.to_string(),
+                fields: vec![uniffi_meta::FieldMetadata {
+                    name: "data".to_string(),
+                    ty: field_type,
+                    default: None,
+                }],
+                repr_c: false,
+                ord: false,
+                patch_for: None,
+                docstring: None,
+            }
+            .into()
+        };
+        let func = |name: &str, inputs| {
+            uniffi_meta::FnMetadata {
+                module_path: "archive".to_string(),
+                name: name.to_string(),
+                is_async: false,
+                inputs,
+                return_type: Some(Type::String),
+                throws: None,
+                checksum: None,
+                docstring: None,
+            }
+            .into()
+        };
+        let shelf = Type::Record {
+            module_path: "archive".to_string(),
+            name: "Shelf".to_string(),
+        };
+        let ci = ComponentInterface::from_metadata(uniffi_meta::MetadataGroup {
+            namespace: uniffi_meta::NamespaceMetadata {
+                crate_name: "archive".to_string(),
+                name: "archive".to_string(),
+                docstring: None,
+            },
+            items: [
+                record(
+                    "Archive",
+                    Type::Compressed {
+                        inner_type: Box::new(Type::String),
+                    },
+                ),
+                record(
+                    "Shelf",
+                    Type::Record {
+                        module_path: "archive".to_string(),
+                        name: "Archive".to_string(),
+                    },
+                ),
+                func(
+                    "open_shelf",
+                    vec![uniffi_meta::FnParamMetadata::simple("shelf", shelf)],
+                ),
+                func("version", vec![]),
+            ]
+            .into(),
+        })
+        .unwrap();
+        let udl = component_udl(&ci);
+        assert!(udl.contains(
+            "    // function `open_shelf` uses `Shelf`, which can't be described in UDL\n"
+        ));
+        assert!(udl.contains("\n// `Compressed` values can't be described in UDL\n"));
+        assert!(
+            udl.contains("\n// record `Shelf` uses `Archive`, which can't be described in UDL\n")
+        );
+        let round_tripped = ComponentInterface::from_webidl(&udl).unwrap();
+        assert_eq!(
+            round_tripped
+                .function_definitions()
+                .iter()
+                .map(|f| f.name())
+                .collect::<Vec<_>>(),
+            ["version"]
+        );
+        assert_eq!(
+            component_udl(&round_tripped),
+            "namespace archive {\n    string version();\n};\n"
+        );
+    }
+}
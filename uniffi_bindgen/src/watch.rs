@@ -0,0 +1,79 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Regenerate bindings when the files they're generated from change, for `generate --watch`.
+//!
+//! The files are polled rather than watched with the OS APIs, since there are only a few of them
+//! and this works the same everywhere, including on network filesystems and in containers.
+
+use crate::Result;
+use camino::Utf8PathBuf;
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Call `regenerate` whenever one of `paths` changes.  This only returns when the process is
+/// stopped.
+///
+/// Errors are printed rather than returned, so that a UDL file that doesn't parse or a library
+/// that fails to build doesn't end the watch.
+pub fn watch(paths: &[Utf8PathBuf], mut regenerate: impl FnMut() -> Result<()>) {
+    let names = paths
+        .iter()
+        .map(|p| p.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("Watching {names} for changes");
+    let mut last = modification_times(paths);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut current = modification_times(paths);
+        if current == last {
+            continue;
+        }
+        // Wait until the files stop changing, e.g. while the linker is writing the library
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let next = modification_times(paths);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+        last = current;
+        match regenerate() {
+            Ok(()) => println!("Regenerated the bindings"),
+            Err(e) => eprintln!("Error: {e:?}"),
+        }
+    }
+}
+
+// Missing files have no time, so creating or deleting them also counts as a change
+fn modification_times(paths: &[Utf8PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| path.metadata().and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn missing_files_have_no_time() {
+        let manifest_dir = Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let paths = vec![
+            manifest_dir.join("Cargo.toml"),
+            manifest_dir.join("does/not/exist.udl"),
+        ];
+        let times = modification_times(&paths);
+        assert!(times[0].is_some());
+        assert_eq!(times[1], None);
+    }
+}
@@ -3,8 +3,9 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use anyhow::{Context, Result};
-use camino::Utf8Path;
-use std::env;
+use camino::{Utf8Path, Utf8PathBuf};
+use siphasher::sip::SipHasher13;
+use std::{env, fs, hash::Hasher, time::UNIX_EPOCH};
 
 /// Generate the rust "scaffolding" required to build a uniffi component.
 ///
@@ -16,9 +17,16 @@ use std::env;
 ///
 /// Given an UDL file named `example.udl`, the generated scaffolding will be written
 /// into a file named `example.uniffi.rs` in the `$OUT_DIR` directory.
+///
+/// The scaffolding is only regenerated when the contents of the UDL file change, or when the
+/// build script is rebuilt, for example for a new version of uniffi.  A hash of these is stored
+/// next to the scaffolding, in `example.uniffi.rs.hash`.
 pub fn generate_scaffolding(udl_file: impl AsRef<Utf8Path>) -> Result<()> {
     let udl_file = udl_file.as_ref();
 
+    // The UDL is the only file the scaffolding is generated from: UDL files can't include other
+    // files, and `uniffi.toml` only configures the foreign bindings.  Listing it here also stops
+    // Cargo from rerunning the build script whenever any file in the crate changes.
     println!("cargo:rerun-if-changed={udl_file}");
     // The UNIFFI_TESTS_DISABLE_EXTENSIONS variable disables some bindings, but it is evaluated
     // at *build* time, so we need to rebuild when it changes.
@@ -26,6 +34,47 @@ pub fn generate_scaffolding(udl_file: impl AsRef<Utf8Path>) -> Result<()> {
     // Why don't we just depend on uniffi-bindgen and call the public functions?
     // Calling the command line helps making sure that the generated swift/Kotlin/whatever
     // bindings were generated with the same version of uniffi as the Rust scaffolding code.
-    let out_dir = env::var("OUT_DIR").context("$OUT_DIR missing?!")?;
-    uniffi_bindgen::generate_component_scaffolding(udl_file, Some(out_dir.as_ref()), false)
+    let out_dir = Utf8PathBuf::from(env::var("OUT_DIR").context("$OUT_DIR missing?!")?);
+    let file_stem = udl_file.file_stem().context("not a file")?;
+    let scaffolding_path = out_dir.join(format!("{file_stem}.uniffi.rs"));
+    let hash_path = out_dir.join(format!("{file_stem}.uniffi.rs.hash"));
+
+    // Cargo reruns the build script when the UDL file's mtime changes, even if its contents
+    // didn't, for example after switching branches.  Skip the regeneration then.  This doesn't
+    // avoid recompiling the crate, which Cargo does whenever the build script reruns.
+    let hash = scaffolding_hash(udl_file)?;
+    if scaffolding_path.exists()
+        && fs::read_to_string(&hash_path).ok().as_deref() == Some(hash.as_str())
+    {
+        return Ok(());
+    }
+    uniffi_bindgen::generate_component_scaffolding(udl_file, Some(&out_dir), false)?;
+    fs::write(&hash_path, hash).with_context(|| format!("Failed to write {hash_path}"))?;
+    Ok(())
+}
+
+// Hash the inputs of the scaffolding: the UDL, and the build script that generates it.
+//
+// The build script is rebuilt whenever the uniffi crates change, so its executable stands in for
+// the version of uniffi, including for path dependencies whose version number doesn't change.
+// `DefaultHasher`'s algorithm may change between Rust versions, so this uses SipHash-1-3 with
+// fixed keys, like the checksums in `uniffi_meta`, and feeds it bytes with a fixed layout.
+fn scaffolding_hash(udl_file: &Utf8Path) -> Result<String> {
+    let udl = fs::read(udl_file).with_context(|| format!("Failed to read UDL from {udl_file}"))?;
+    let mut hasher = SipHasher13::new();
+    hasher.write_u64(udl.len() as u64);
+    hasher.write(&udl);
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    let build_script = env::current_exe()
+        .and_then(|path| path.metadata())
+        .context("Failed to read the build script's metadata")?;
+    hasher.write_u64(build_script.len());
+    let modified = build_script
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .unwrap_or_default();
+    hasher.write_u64(modified.as_secs());
+    hasher.write_u32(modified.subsec_nanos());
+    Ok(format!("{:016x}", hasher.finish()))
 }
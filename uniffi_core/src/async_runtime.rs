@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Async runtimes
+//!
+//! The futures of exported async functions are polled by the foreign executor, see
+//! [crate::ffi::rustfuture].  Futures that need the context of a Rust runtime while they're
+//! polled, like the timers and IO of tokio, are wrapped by an [AsyncRuntime] first.
+//!
+//! Functions and `impl` blocks select a runtime with `#[uniffi::export(async_runtime = ...)]`,
+//! either `"tokio"` or the path of a type that implements [AsyncRuntime], and methods can override
+//! the runtime of their `impl` block with `#[uniffi::async_runtime(...)]`.  `"none"` selects no
+//! runtime, for methods that don't need the one of their `impl` block.
+
+use crate::MaybeSend;
+use core::future::Future;
+
+/// A runtime that the futures of exported async functions can be polled in
+///
+/// This is implemented by a marker type, which is named in the `async_runtime` attribute.  A crate
+/// that only has a runtime on some targets can pick the wrapper with `#[cfg]` in its
+/// implementation, rather than on each function.
+pub trait AsyncRuntime {
+    /// The future that's polled instead of the future of the function
+    type Future<F: Future + MaybeSend>: Future<Output = F::Output> + MaybeSend;
+
+    /// Wrap the future of a call, so that it's polled in the runtime's context
+    fn wrap<F: Future + MaybeSend>(future: F) -> Self::Future<F>;
+}
+
+/// The runtime that's selected with `async_runtime = "tokio"`
+///
+/// The futures are polled in the context of a tokio runtime that's shared by the whole process,
+/// using [async_compat].
+#[cfg(feature = "tokio")]
+pub struct TokioRuntime;
+
+#[cfg(feature = "tokio")]
+impl AsyncRuntime for TokioRuntime {
+    type Future<F: Future + MaybeSend> = async_compat::Compat<F>;
+
+    fn wrap<F: Future + MaybeSend>(future: F) -> Self::Future<F> {
+        async_compat::Compat::new(future)
+    }
+}
@@ -0,0 +1,131 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Serializing the calls to an object
+//!
+//! The scaffolding of the methods in an `#[uniffi::export(serialized)]` impl block holds
+//! [`lock_object_calls`] for the duration of each call, so that calls to the same object from
+//! different foreign threads run one after the other.  Calls to different objects still run
+//! concurrently.
+//!
+//! The objects are identified by the pointer that the foreign code passes to the scaffolding.  Each
+//! object with a call in progress, or waiting, gets a lock of its own, which is removed after its
+//! last call so there's nothing to clean up when the object is freed.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Condvar, Mutex},
+};
+
+// The locks of the objects with a call in progress or waiting.  This map is only locked to look up
+// or remove an object's lock, never while waiting for a call to finish.
+static OBJECT_LOCKS: Mutex<Option<HashMap<usize, Arc<ObjectLock>>>> = Mutex::new(None);
+
+#[derive(Default)]
+struct ObjectLock {
+    // Is there a call in progress to the object?
+    busy: Mutex<bool>,
+    // Notified when a call to the object finishes
+    call_finished: Condvar,
+}
+
+/// Wait until there's no call in progress to the object at `ptr`, then hold off the other calls
+/// to it until the returned guard is dropped
+///
+/// A serialized method that calls back into the same object, for example through a callback
+/// interface, deadlocks.
+pub fn lock_object_calls(ptr: *const core::ffi::c_void) -> ObjectCallGuard {
+    let key = ptr as usize;
+    let lock = Arc::clone(
+        OBJECT_LOCKS
+            .lock()
+            .unwrap()
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_default(),
+    );
+    let mut busy = lock.busy.lock().unwrap();
+    while *busy {
+        busy = lock.call_finished.wait(busy).unwrap();
+    }
+    *busy = true;
+    drop(busy);
+    ObjectCallGuard { key, lock }
+}
+
+/// Returned by [`lock_object_calls`], lets the next call to the object run when it's dropped
+#[must_use]
+pub struct ObjectCallGuard {
+    key: usize,
+    lock: Arc<ObjectLock>,
+}
+
+impl Drop for ObjectCallGuard {
+    fn drop(&mut self) {
+        *self.lock.busy.lock().unwrap() = false;
+        self.lock.call_finished.notify_one();
+        // New references to the lock are only taken with the map locked, so if the map and this
+        // guard hold the only ones, no other call is waiting and the lock can go.
+        let mut locks = OBJECT_LOCKS.lock().unwrap();
+        if Arc::strong_count(&self.lock) == 2 {
+            if let Some(locks) = locks.as_mut() {
+                locks.remove(&self.key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_calls_are_serialized() {
+        let object = 1u8;
+        let ptr = &object as *const u8 as usize;
+        let in_progress = Arc::new(AtomicUsize::new(0));
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let in_progress = Arc::clone(&in_progress);
+                thread::spawn(move || {
+                    let _guard = lock_object_calls(ptr as *const core::ffi::c_void);
+                    assert_eq!(in_progress.fetch_add(1, Ordering::SeqCst), 0);
+                    thread::sleep(Duration::from_millis(10));
+                    in_progress.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_other_objects_arent_blocked() {
+        let (a, b) = (1u8, 2u8);
+        let _guard_a = lock_object_calls(&a as *const u8 as *const core::ffi::c_void);
+        // This would deadlock if `b` waited for the call to `a`
+        drop(lock_object_calls(
+            &b as *const u8 as *const core::ffi::c_void,
+        ));
+    }
+
+    #[test]
+    fn test_locks_are_removed() {
+        let object = 1u8;
+        let ptr = &object as *const u8 as *const core::ffi::c_void;
+        drop(lock_object_calls(ptr));
+        drop(lock_object_calls(ptr));
+        let locks = OBJECT_LOCKS.lock().unwrap();
+        assert!(!locks.as_ref().unwrap().contains_key(&(ptr as usize)));
+    }
+}
@@ -0,0 +1,119 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` impls for `chrono` types
+//!
+//! Enabled by the `chrono` feature.  `DateTime<Utc>` and `NaiveDate` are passed exactly like
+//! `SystemTime`, so the foreign bindings see their regular timestamp type.  A `NaiveDate` is
+//! lowered as midnight UTC on that date, and lifting one drops the time of day (in UTC).
+
+use crate::{
+    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
+    metadata, FfiConverter, MetadataBuffer, Result,
+};
+use anyhow::bail;
+use bytes::buf::Buf;
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use std::time::SystemTime;
+
+unsafe impl<UT> FfiConverter<UT> for DateTime<Utc> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: DateTime<Utc>, buf: &mut Vec<u8>) {
+        <SystemTime as FfiConverter<UT>>::write(obj.into(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<DateTime<Utc>> {
+        check_remaining(buf, 12)?;
+        let seconds = buf.get_i64();
+        let nanos = buf.get_u32();
+        // The buffer holds the magnitude of the offset from the epoch, with the sign on the
+        // seconds portion (see the `SystemTime` impl).  Chrono wants whole seconds rounded
+        // towards negative infinity, plus a positive nanosecond portion.
+        let (seconds, nanos) = if seconds >= 0 || nanos == 0 {
+            (seconds, nanos)
+        } else {
+            (seconds - 1, 1_000_000_000 - nanos)
+        };
+        match Utc.timestamp_opt(seconds, nanos).single() {
+            Some(datetime) => Ok(datetime),
+            None => bail!("timestamp out of range for chrono::DateTime: {seconds}s {nanos}ns"),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
+}
+
+unsafe impl<UT> FfiConverter<UT> for NaiveDate {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: NaiveDate, buf: &mut Vec<u8>) {
+        let midnight = obj.and_hms_opt(0, 0, 0).expect("midnight is a valid time");
+        <DateTime<Utc> as FfiConverter<UT>>::write(Utc.from_utc_datetime(&midnight), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<NaiveDate> {
+        Ok(<DateTime<Utc> as FfiConverter<UT>>::try_read(buf)?
+            .naive_utc()
+            .date())
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SYSTEM_TIME);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lower_into_rust_buffer, try_lift_from_rust_buffer, UniFfiTag};
+    use std::time::Duration;
+
+    fn roundtrip<T: FfiConverter<UniFfiTag>>(value: T) -> T {
+        try_lift_from_rust_buffer::<T, UniFfiTag>(lower_into_rust_buffer::<T, UniFfiTag>(value))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_datetime_matches_system_time() {
+        for (seconds, nanos) in [(0, 0), (1_500_000_000, 250), (-1_500_000_000, 250), (-3, 0)] {
+            let datetime = Utc.timestamp_opt(seconds, nanos).unwrap();
+            let system_time: SystemTime = datetime.into();
+
+            let mut datetime_buf = vec![];
+            <DateTime<Utc> as FfiConverter<UniFfiTag>>::write(datetime, &mut datetime_buf);
+            let mut system_time_buf = vec![];
+            <SystemTime as FfiConverter<UniFfiTag>>::write(system_time, &mut system_time_buf);
+            assert_eq!(datetime_buf, system_time_buf);
+
+            let read = <DateTime<Utc> as FfiConverter<UniFfiTag>>::try_read(
+                &mut system_time_buf.as_slice(),
+            )
+            .unwrap();
+            assert_eq!(read, datetime);
+            assert_eq!(roundtrip(datetime), datetime);
+        }
+    }
+
+    #[test]
+    fn test_naive_date() {
+        let date = NaiveDate::from_ymd_opt(1969, 7, 20).unwrap();
+        assert_eq!(roundtrip(date), date);
+
+        // Dates are sent as midnight UTC, and the time of day is dropped when lifting
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(date);
+        let system_time = try_lift_from_rust_buffer::<SystemTime, UniFfiTag>(buf).unwrap();
+        assert_eq!(
+            SystemTime::UNIX_EPOCH.duration_since(system_time).unwrap(),
+            Duration::from_secs(165 * 86_400)
+        );
+        let later = lower_into_rust_buffer::<_, UniFfiTag>(system_time + Duration::from_secs(3600));
+        assert_eq!(
+            try_lift_from_rust_buffer::<NaiveDate, UniFfiTag>(later).unwrap(),
+            date
+        );
+    }
+}
@@ -0,0 +1,218 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Compressed values
+//!
+//! Enabled by the `compression` feature.  [`Compressed<T>`] is passed over the FFI like `T`, and
+//! the foreign bindings see a plain `T`, but when its serialized form is larger than the
+//! threshold set by [set_compression_threshold], Rust compresses it before handing it to the
+//! foreign code.  Use it as the return type of the functions that return large, compressible
+//! values, or as the type of a record field that holds them.
+//!
+//! Values are compressed with raw deflate, which all the foreign languages can decompress with
+//! their standard libraries.  The serialized form is a one-byte algorithm tag followed by either:
+//!
+//!   - `ALGORITHM_NONE`: the value, serialized as usual.
+//!   - `ALGORITHM_DEFLATE`: the `u32` size of the serialized value, the `u32` size of the
+//!     compressed data, then the compressed data.
+//!
+//! The foreign code always sends values uncompressed, the values it sends are expected to be
+//! small.  When reading a compressed value, the data is decompressed up to the size that was sent
+//! with it, and values that don't decompress to exactly that size are rejected.
+
+use crate::{
+    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
+    metadata, FfiConverter, MetadataBuffer, Result,
+};
+use anyhow::bail;
+use bytes::buf::{Buf, BufMut};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use std::{
+    io::{Read, Write},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const ALGORITHM_NONE: u8 = 0;
+const ALGORITHM_DEFLATE: u8 = 1;
+
+// Serialized values smaller than this are sent uncompressed.
+static THRESHOLD: AtomicUsize = AtomicUsize::new(64 * 1024);
+
+/// Only compress [`Compressed`] values whose serialized form is at least `bytes` long
+///
+/// The default is 64 KiB.  Small values usually aren't worth the time it takes to compress them.
+pub fn set_compression_threshold(bytes: usize) {
+    THRESHOLD.store(bytes, Ordering::Relaxed);
+}
+
+/// A value that's compressed when it's passed to the foreign code, if it's large enough
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Compressed<T>(pub T);
+
+impl<T> Compressed<T> {
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Compressed<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+unsafe impl<UT, T: FfiConverter<UT>> FfiConverter<UT> for Compressed<T> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: Compressed<T>, buf: &mut Vec<u8>) {
+        let mut serialized = Vec::new();
+        T::write(obj.0, &mut serialized);
+        if serialized.len() >= THRESHOLD.load(Ordering::Relaxed) {
+            if let Some(compressed) = deflate(&serialized) {
+                buf.put_u8(ALGORITHM_DEFLATE);
+                buf.put_u32(serialized.len() as u32);
+                buf.put_u32(compressed.len() as u32);
+                buf.put_slice(&compressed);
+                return;
+            }
+        }
+        buf.put_u8(ALGORITHM_NONE);
+        buf.put_slice(&serialized);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Compressed<T>> {
+        check_remaining(buf, 1)?;
+        Ok(Compressed(match buf.get_u8() {
+            ALGORITHM_NONE => T::try_read(buf)?,
+            ALGORITHM_DEFLATE => {
+                check_remaining(buf, 8)?;
+                let size = buf.get_u32() as usize;
+                let compressed_size = buf.get_u32() as usize;
+                check_remaining(buf, compressed_size)?;
+                // Stop one byte past the expected size, so that bad data can't decompress into
+                // more memory than that
+                let mut serialized = Vec::new();
+                DeflateDecoder::new(&buf[..compressed_size])
+                    .take(size as u64 + 1)
+                    .read_to_end(&mut serialized)?;
+                buf.advance(compressed_size);
+                if serialized.len() != size {
+                    bail!("decompressed value has the wrong size");
+                }
+                let mut inner = serialized.as_slice();
+                let value = T::try_read(&mut inner)?;
+                if !inner.is_empty() {
+                    bail!("junk data left in buffer after decompressing value");
+                }
+                value
+            }
+            algorithm => bail!("unknown compression algorithm {algorithm}"),
+        }))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_COMPRESSED).concat(T::TYPE_ID_META);
+}
+
+// Compress `data`, or return `None` if that doesn't make it smaller or it's too large for the
+// `u32` sizes.
+fn deflate(data: &[u8]) -> Option<Vec<u8>> {
+    u32::try_from(data.len()).ok()?;
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(data).ok()?;
+    let compressed = encoder.finish().ok()?;
+    (compressed.len() < data.len()).then_some(compressed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+
+    #[test]
+    fn test_compressed_roundtrip() {
+        set_compression_threshold(1024);
+
+        // Small values are sent as-is
+        let small = "a".repeat(10);
+        let lowered =
+            <Compressed<String> as FfiConverter<UniFfiTag>>::lower(Compressed::new(small.clone()));
+        assert_eq!(lowered.len(), 1 + 4 + 10);
+        assert_eq!(
+            <Compressed<String> as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            Compressed::new(small)
+        );
+
+        // Large values are compressed
+        let large = "abcd".repeat(10_000);
+        let mut buf = Vec::new();
+        <Compressed<String> as FfiConverter<UniFfiTag>>::write(
+            Compressed::new(large.clone()),
+            &mut buf,
+        );
+        assert!(buf.len() < 1000);
+        assert_eq!(buf[0], ALGORITHM_DEFLATE);
+        assert_eq!(
+            <Compressed<String> as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap(),
+            Compressed::new(large)
+        );
+    }
+
+    #[test]
+    fn test_compression_threshold() {
+        set_compression_threshold(1024);
+        let write = |len: usize| {
+            let mut buf = Vec::new();
+            <Compressed<String> as FfiConverter<UniFfiTag>>::write(
+                Compressed::new("a".repeat(len)),
+                &mut buf,
+            );
+            buf[0]
+        };
+        // A string is serialized as its `i32` length, then its bytes
+        assert_eq!(write(1019), ALGORITHM_NONE);
+        assert_eq!(write(1020), ALGORITHM_DEFLATE);
+    }
+
+    // Serialize `value` as a compressed value of `size` bytes
+    fn deflated(value: &str, size: u32) -> Vec<u8> {
+        let mut serialized = Vec::new();
+        <String as FfiConverter<UniFfiTag>>::write(value.to_string(), &mut serialized);
+        let compressed = deflate(&serialized).unwrap();
+        let mut buf = vec![ALGORITHM_DEFLATE];
+        buf.put_u32(size);
+        buf.put_u32(compressed.len() as u32);
+        buf.put_slice(&compressed);
+        buf
+    }
+
+    #[test]
+    fn test_decompressed_size_is_checked() {
+        let value = "abcd".repeat(1000);
+        let size = 4 + value.len() as u32;
+        let read = |buf: Vec<u8>| {
+            <Compressed<String> as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice())
+        };
+        assert_eq!(
+            read(deflated(&value, size)).unwrap(),
+            Compressed::new(value.clone())
+        );
+        assert!(read(deflated(&value, size - 1)).is_err());
+        assert!(read(deflated(&value, size + 1)).is_err());
+        // Data that decompresses into much more than its size is only decompressed up to the
+        // size, rather than into memory that's never used
+        assert!(read(deflated(&"a".repeat(1_000_000), 10)).is_err());
+    }
+
+    #[test]
+    fn test_unknown_algorithm() {
+        let mut buf: &[u8] = &[7, 0, 0, 0, 0];
+        assert!(<Compressed<String> as FfiConverter<UniFfiTag>>::try_read(&mut buf).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` impl for `rust_decimal::Decimal`
+//!
+//! Enabled by the `rust_decimal` feature.  Decimals are passed as strings in plain (not
+//! scientific) notation, which every foreign decimal type can parse without losing precision.
+//! Lifting uses `Decimal::from_str_exact()`, so a value with more precision than a `Decimal` can
+//! hold is reported as an error instead of being silently rounded.
+
+use crate::{
+    ffi_converter_default_return, metadata, FfiConverter, MetadataBuffer, Result, RustBuffer,
+};
+use rust_decimal::Decimal;
+
+unsafe impl<UT> FfiConverter<UT> for Decimal {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Decimal) -> RustBuffer {
+        <String as FfiConverter<UT>>::lower(obj.to_string())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Decimal> {
+        Ok(Decimal::from_str_exact(
+            &<String as FfiConverter<UT>>::try_lift(v)?,
+        )?)
+    }
+
+    fn write(obj: Decimal, buf: &mut Vec<u8>) {
+        <String as FfiConverter<UT>>::write(obj.to_string(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Decimal> {
+        Ok(Decimal::from_str_exact(
+            &<String as FfiConverter<UT>>::try_read(buf)?,
+        )?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DECIMAL);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lower_into_rust_buffer, try_lift_from_rust_buffer, UniFfiTag};
+
+    #[test]
+    fn test_decimal_roundtrip() {
+        let value = Decimal::from_str_exact("-1234.5600").unwrap();
+        let lowered = <Decimal as FfiConverter<UniFfiTag>>::lower(value);
+        let lifted = <Decimal as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap();
+        assert_eq!(lifted, value);
+        // The scale is kept, so trailing zeros survive the round-trip.
+        assert_eq!(lifted.to_string(), "-1234.5600");
+
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(value);
+        assert_eq!(
+            try_lift_from_rust_buffer::<Decimal, UniFfiTag>(buf).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_invalid_decimal() {
+        for s in ["not a number", "1.00000000000000000000000000001"] {
+            let lowered = <String as FfiConverter<UniFfiTag>>::lower(s.to_string());
+            assert!(<Decimal as FfiConverter<UniFfiTag>>::try_lift(lowered).is_err());
+        }
+    }
+}
@@ -0,0 +1,170 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Opt-in pool of byte buffers backing `RustBuffer`s
+//!
+//! Every call that passes a record, a string, etc. allocates a `Vec<u8>` for its `RustBuffer`
+//! and frees it again once the value has been lifted.  For small, frequent calls that allocator
+//! traffic can dominate the cost of the call, so consumers can call [enable_buffer_pool] to keep
+//! freed buffers around and hand them out again for the next allocation.
+//!
+//! The pool is per-thread, so taking and returning buffers doesn't need any locking.  Buffers
+//! that are freed on a different thread than the one that allocated them simply end up in that
+//! thread's pool.
+//!
+//! Without the `std` feature there are no thread locals, so buffers are never pooled.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+
+#[cfg(feature = "std")]
+thread_local! {
+    static POOL: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+// Settings of the pool, the global ones are set by `enable_buffer_pool()`.  Tests use settings of
+// their own, so that they don't depend on each other, since each test runs on its own thread and so
+// has its own pool.
+struct PoolSettings {
+    // Maximum number of buffers kept in each thread's pool.  0 means the pool is disabled.
+    max_buffers: AtomicUsize,
+    // Buffers with a larger capacity than this are freed rather than kept in the pool.
+    max_capacity: AtomicUsize,
+}
+
+static SETTINGS: PoolSettings = PoolSettings::new();
+
+impl PoolSettings {
+    const fn new() -> Self {
+        Self {
+            max_buffers: AtomicUsize::new(0),
+            max_capacity: AtomicUsize::new(0),
+        }
+    }
+
+    fn enable(&self, max_buffers: usize, max_capacity: usize) {
+        self.max_capacity.store(max_capacity, Ordering::Relaxed);
+        self.max_buffers.store(max_buffers, Ordering::Relaxed);
+    }
+
+    fn disable(&self) {
+        self.max_buffers.store(0, Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        let _ = POOL.try_with(|pool| pool.borrow_mut().clear());
+    }
+
+    fn take(&self, capacity: usize) -> Vec<u8> {
+        if self.max_buffers.load(Ordering::Relaxed) == 0 {
+            return Vec::with_capacity(capacity);
+        }
+        #[cfg(feature = "std")]
+        if let Ok(Some(mut v)) = POOL.try_with(|pool| pool.borrow_mut().pop()) {
+            v.reserve(capacity);
+            return v;
+        }
+        Vec::with_capacity(capacity)
+    }
+
+    #[cfg(feature = "std")]
+    fn recycle(&self, mut v: Vec<u8>) {
+        let max_buffers = self.max_buffers.load(Ordering::Relaxed);
+        if max_buffers == 0
+            || v.capacity() == 0
+            || v.capacity() > self.max_capacity.load(Ordering::Relaxed)
+        {
+            return;
+        }
+        v.clear();
+        // If the thread-local has already been destroyed, `v` is simply dropped.
+        let _ = POOL.try_with(move |pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < max_buffers {
+                pool.push(v);
+            }
+        });
+    }
+}
+
+/// Keep up to `max_buffers` freed buffers per thread, for reuse by later `RustBuffer`s
+///
+/// Buffers whose capacity exceeds `max_capacity` bytes are never pooled, so that a single large
+/// call doesn't pin a large allocation for the rest of the program.
+pub fn enable_buffer_pool(max_buffers: usize, max_capacity: usize) {
+    SETTINGS.enable(max_buffers, max_capacity);
+}
+
+/// Stop pooling buffers
+///
+/// This frees the current thread's pooled buffers, other threads free theirs when they exit.
+pub fn disable_buffer_pool() {
+    SETTINGS.disable();
+}
+
+/// Get an empty `Vec<u8>` with at least `capacity` bytes of capacity, from the pool if possible
+pub(crate) fn take(capacity: usize) -> Vec<u8> {
+    SETTINGS.take(capacity)
+}
+
+/// Return a `Vec<u8>` to the pool, or free it if the pool is disabled or full
+#[cfg(feature = "std")]
+pub(crate) fn recycle(v: Vec<u8>) {
+    SETTINGS.recycle(v);
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn recycle(_v: Vec<u8>) {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buffer_pool() {
+        let settings = PoolSettings::new();
+        settings.enable(2, 1024);
+
+        let v = settings.take(16);
+        let ptr = v.as_ptr();
+        settings.recycle(v);
+        let v = settings.take(8);
+        assert_eq!(v.as_ptr(), ptr, "buffer should have been reused");
+        assert!(v.is_empty());
+
+        // Buffers over the capacity limit aren't kept.
+        settings.recycle(Vec::with_capacity(2048));
+        assert!(settings.take(0).capacity() < 2048);
+
+        settings.disable();
+        settings.recycle(v);
+        assert_eq!(settings.take(0).capacity(), 0);
+    }
+
+    #[test]
+    fn test_buffer_pool_limit() {
+        let settings = PoolSettings::new();
+        settings.enable(1, 1024);
+        settings.recycle(Vec::with_capacity(16));
+        // The pool is full, so this one is freed
+        settings.recycle(Vec::with_capacity(32));
+        assert_eq!(settings.take(0).capacity(), 16);
+        assert_eq!(settings.take(0).capacity(), 0);
+    }
+
+    #[test]
+    fn test_buffer_pool_threads() {
+        let settings = PoolSettings::new();
+        settings.enable(2, 1024);
+        let v = Vec::with_capacity(16);
+        let ptr = v.as_ptr();
+        settings.recycle(v);
+        // Each thread has its own pool
+        std::thread::scope(|s| {
+            s.spawn(|| assert_eq!(settings.take(0).capacity(), 0));
+        });
+        let v = settings.take(0);
+        assert_eq!(v.as_ptr(), ptr);
+    }
+}
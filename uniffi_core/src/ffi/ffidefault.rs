@@ -39,21 +39,21 @@ impl FfiDefault for () {
     fn ffi_default() {}
 }
 
-impl FfiDefault for *const std::ffi::c_void {
+impl FfiDefault for *const core::ffi::c_void {
     fn ffi_default() -> Self {
-        std::ptr::null()
+        core::ptr::null()
     }
 }
 
 impl FfiDefault for crate::RustBuffer {
     fn ffi_default() -> Self {
-        unsafe { Self::from_raw_parts(std::ptr::null_mut(), 0, 0) }
+        unsafe { Self::from_raw_parts(core::ptr::null_mut(), 0, 0) }
     }
 }
 
 impl FfiDefault for crate::ForeignExecutorHandle {
     fn ffi_default() -> Self {
-        Self(std::ptr::null())
+        Self(core::ptr::null())
     }
 }
 
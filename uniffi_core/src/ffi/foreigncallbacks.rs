@@ -112,11 +112,30 @@
 //! `ForeignCallback`. The caller of `ForeignCallback`, the `KeychainProxy` unpacks the returned buffer into the correct
 //! type and then returns to client code.
 //!
+//! ## Lifecycle
+//!
+//! `ForeignCallbackInternals` also keeps track of the handles that Rust holds, so that Rust code
+//! can be told when a foreign object starts and stops being used, and can check whether it's still
+//! there.  The generated handler calls [`ForeignCallbackInternals::attach`] when it's created and
+//! [`ForeignCallbackInternals::release`] when it's dropped.  The foreign code can also detach an
+//! object that Rust still holds, when it's closed or torn down, after which calling its methods
+//! fails instead of calling into the foreign code.  See [`ForeignCallbackInterface`] for the API
+//! that Rust code uses.
+//!
+//! Detaching doesn't wait for the calls that are already in progress, since the foreign code may
+//! be waiting on the thread that detaches the object, for example for `main_thread` callback
+//! interfaces.  A call that passed the check before the object was detached can reach the foreign
+//! code after it forgot the handle.  The bindings then fail the call with an unexpected error, like
+//! Rust does for the calls that are made after detaching.
+//!
 
 use crate::{FfiConverter, RustBuffer};
-use std::fmt;
-use std::os::raw::c_int;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use alloc::string::{String, ToString};
+use core::ffi::c_int;
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use std::{collections::BTreeMap, sync::Mutex};
 
 /// ForeignCallback is the Rust representation of a foreign language function.
 /// It is the basis for all callbacks interfaces. It is registered exactly once per callback interface,
@@ -139,7 +158,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 ///    - `0` is a deprecated way to signal that if the call succeeded, but did not modify buf_ptr
 ///    - `1` If the call succeeded.  For non-void functions the return value should be serialized
 ///      to buf_ptr.
-///   Note: The output buffer might still contain 0 bytes of data.
+///      Note: The output buffer might still contain 0 bytes of data.
 pub type ForeignCallback = unsafe extern "C" fn(
     handle: u64,
     method: u32,
@@ -151,8 +170,13 @@ pub type ForeignCallback = unsafe extern "C" fn(
 /// The method index used by the Drop trait to communicate to the foreign language side that Rust has finished with it,
 /// and it can be deleted from the handle map.
 pub const IDX_CALLBACK_FREE: u32 = 0;
+/// The method index used to read the value of one of the trait's constants from a foreign implementation.  The
+/// arguments are the index of the constant, and the value is returned like a method's return value.
+pub const IDX_CALLBACK_CONSTANT: u32 = i32::MAX as u32;
 pub const CALLBACK_SUCCESS: i32 = 0;
 pub const CALLBACK_ERROR: i32 = 1;
+/// The foreign code writes the exception's class name and message to the return buffer, see
+/// `CallbackError`.
 pub const CALLBACK_UNEXPECTED_ERROR: i32 = 2;
 
 // Overly-paranoid sanity checking to ensure that these types are
@@ -164,18 +188,56 @@ pub const CALLBACK_UNEXPECTED_ERROR: i32 = 2;
 // and thus this is a little paranoid.
 static_assertions::assert_eq_size!(usize, ForeignCallback);
 static_assertions::assert_eq_size!(usize, Option<ForeignCallback>);
+static_assertions::assert_eq_size!(usize, Option<LifecycleHook>);
+
+/// Called with the handle of a foreign object, see
+/// [`ForeignCallbackInternals::set_lifecycle_hooks`]
+pub type LifecycleHook = fn(handle: u64);
 
 /// Struct to hold a foreign callback.
 pub struct ForeignCallbackInternals {
     callback_ptr: AtomicUsize,
+    on_attach: AtomicUsize,
+    on_detach: AtomicUsize,
+    #[cfg(feature = "std")]
+    handles: Mutex<Handles>,
+}
+
+// The handles that Rust holds
+#[cfg(feature = "std")]
+struct Handles {
+    // The state of each handle, until Rust drops its last handler.  Some bindings return the same
+    // handle when an object is passed to Rust several times.
+    states: BTreeMap<u64, HandleState>,
+    // The handle of each boxed handler, by its address, see [`ForeignCallbackInterface::handle`]
+    handlers: BTreeMap<usize, u64>,
+}
+
+#[cfg(feature = "std")]
+struct HandleState {
+    handlers: usize,
+    detached: bool,
 }
 
 const EMPTY_PTR: usize = 0;
 
+impl Default for ForeignCallbackInternals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ForeignCallbackInternals {
     pub const fn new() -> Self {
         ForeignCallbackInternals {
             callback_ptr: AtomicUsize::new(EMPTY_PTR),
+            on_attach: AtomicUsize::new(EMPTY_PTR),
+            on_detach: AtomicUsize::new(EMPTY_PTR),
+            #[cfg(feature = "std")]
+            handles: Mutex::new(Handles {
+                states: BTreeMap::new(),
+                handlers: BTreeMap::new(),
+            }),
         }
     }
 
@@ -200,6 +262,197 @@ impl ForeignCallbackInternals {
         };
     }
 
+    /// Set the functions that are called when Rust starts and stops using a foreign object
+    ///
+    /// `on_attach` is called when Rust gets a handle to a foreign object, before the function
+    /// that it's passed to runs.  `on_detach` is called once for each handle, when Rust drops
+    /// the last handler for it or when the foreign code detaches the object, whichever comes
+    /// first.  The hooks replace the ones that were set before, and they aren't called for the
+    /// handles that were attached before they were set.
+    pub fn set_lifecycle_hooks(
+        &self,
+        on_attach: Option<LifecycleHook>,
+        on_detach: Option<LifecycleHook>,
+    ) {
+        self.on_attach.store(
+            on_attach.map_or(EMPTY_PTR, |f| f as usize),
+            Ordering::SeqCst,
+        );
+        self.on_detach.store(
+            on_detach.map_or(EMPTY_PTR, |f| f as usize),
+            Ordering::SeqCst,
+        );
+    }
+
+    fn lifecycle_hook(ptr: &AtomicUsize) -> Option<LifecycleHook> {
+        let ptr_value = ptr.load(Ordering::SeqCst);
+        // SAFETY: the hooks are only set in `set_lifecycle_hooks`, from `LifecycleHook` pointers.
+        unsafe { core::mem::transmute::<usize, Option<LifecycleHook>>(ptr_value) }
+    }
+
+    /// Record that a handler was created for `handle`
+    ///
+    /// This is called by the generated handler when it's lifted.  The handlers that the
+    /// scaffolding creates to run the default implementation of a method don't call this, since
+    /// the foreign code is only calling itself.
+    pub fn attach(&self, handle: u64) {
+        #[cfg(feature = "std")]
+        {
+            let mut handles = self.handles.lock().unwrap();
+            let state = handles.states.entry(handle).or_insert(HandleState {
+                handlers: 0,
+                detached: false,
+            });
+            state.handlers += 1;
+            if state.handlers > 1 {
+                return;
+            }
+        }
+        if let Some(on_attach) = Self::lifecycle_hook(&self.on_attach) {
+            on_attach(handle);
+        }
+    }
+
+    /// Record that a handler for `handle` was dropped
+    ///
+    /// This is called by the generated handler.  It returns `false` if the foreign code detached
+    /// the object already, in which case the foreign code forgot the handle and mustn't be asked
+    /// to free it.
+    pub fn release(&self, handle: u64) -> bool {
+        #[cfg(feature = "std")]
+        {
+            let mut handles = self.handles.lock().unwrap();
+            let Some(state) = handles.states.get_mut(&handle) else {
+                return true;
+            };
+            state.handlers -= 1;
+            let (remaining, detached) = (state.handlers, state.detached);
+            if remaining == 0 {
+                handles.states.remove(&handle);
+            }
+            drop(handles);
+            if detached {
+                return false;
+            }
+            // Other handlers still hold the handle
+            if remaining > 0 {
+                return true;
+            }
+        }
+        self.notify_detach(handle);
+        true
+    }
+
+    /// Detach the foreign object behind `handle`, because the foreign code is done with it
+    ///
+    /// This is called by the scaffolding function that the bindings call to detach an object.
+    /// The handlers that hold the handle are still around, but calling their methods fails
+    /// rather than calling the foreign code.  The calls that are already in progress aren't
+    /// waited for, see the module docs.  Without the `std` feature the handles aren't tracked,
+    /// so this does nothing.
+    pub fn detach(&self, handle: u64) {
+        #[cfg(feature = "std")]
+        {
+            let newly_detached = match self.handles.lock().unwrap().states.get_mut(&handle) {
+                Some(state) if !state.detached => {
+                    state.detached = true;
+                    true
+                }
+                _ => false,
+            };
+            if newly_detached {
+                self.notify_detach(handle);
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = handle;
+    }
+
+    /// Is the foreign object behind `handle` still alive?
+    ///
+    /// This is `false` once Rust dropped all of its handlers, or once the foreign code detached
+    /// the object.  Without the `std` feature the handles aren't tracked, and this is always
+    /// `true`.
+    pub fn is_alive(&self, handle: u64) -> bool {
+        #[cfg(feature = "std")]
+        {
+            matches!(
+                self.handles.lock().unwrap().states.get(&handle),
+                Some(state) if !state.detached
+            )
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = handle;
+            true
+        }
+    }
+
+    // Was the foreign object behind `handle` detached?  Unlike `!is_alive()`, this is `false` for
+    // the handles that were never attached, which the default method implementations use.
+    fn is_detached(&self, handle: u64) -> bool {
+        #[cfg(feature = "std")]
+        {
+            matches!(
+                self.handles.lock().unwrap().states.get(&handle),
+                Some(state) if state.detached
+            )
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = handle;
+            false
+        }
+    }
+
+    /// Record the address of a boxed handler, so that Rust code can find its handle
+    ///
+    /// This is called by the generated `FfiConverter` when it lifts a handler, and
+    /// [`Self::unregister_handler`] is called when it's dropped.
+    pub fn register_handler(&self, address: usize, handle: u64) {
+        #[cfg(feature = "std")]
+        self.handles
+            .lock()
+            .unwrap()
+            .handlers
+            .insert(address, handle);
+        #[cfg(not(feature = "std"))]
+        let _ = (address, handle);
+    }
+
+    /// Forget the address of a handler that was dropped, see [`Self::register_handler`]
+    pub fn unregister_handler(&self, address: usize, handle: u64) {
+        #[cfg(feature = "std")]
+        {
+            let mut handles = self.handles.lock().unwrap();
+            if handles.handlers.get(&address) == Some(&handle) {
+                handles.handlers.remove(&address);
+            }
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = (address, handle);
+    }
+
+    /// The handle of the handler at `address`, see [`ForeignCallbackInterface::handle`]
+    pub fn handle_at(&self, address: usize) -> Option<u64> {
+        #[cfg(feature = "std")]
+        {
+            self.handles.lock().unwrap().handlers.get(&address).copied()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = address;
+            None
+        }
+    }
+
+    // The lock isn't held while calling the hook, so that it can call `is_alive()`
+    fn notify_detach(&self, handle: u64) {
+        if let Some(on_detach) = Self::lifecycle_hook(&self.on_detach) {
+            on_detach(handle);
+        }
+    }
+
     fn call_callback(
         &self,
         handle: u64,
@@ -211,7 +464,7 @@ impl ForeignCallbackInternals {
         unsafe {
             // SAFETY: `callback_ptr` was set in `set_callback` from a ForeignCallback pointer, so
             // it's safe to transmute it back here.
-            let callback = std::mem::transmute::<usize, Option<ForeignCallback>>(ptr_value)
+            let callback = core::mem::transmute::<usize, Option<ForeignCallback>>(ptr_value)
                 .expect("Callback interface handler not set");
             callback(
                 handle,
@@ -228,25 +481,60 @@ impl ForeignCallbackInternals {
     where
         R: FfiConverter<UniFfiTag>,
     {
+        self.invoke_callback_with_fallback::<R, UniFfiTag>(
+            handle,
+            method,
+            args,
+            R::handle_callback_unexpected_error,
+        )
+    }
+
+    /// Invoke a callback interface method on the foreign side and return the result
+    ///
+    /// If the foreign code raises an unexpected error, `fallback` is called to produce the return
+    /// value.  This is used for methods with a `#[uniffi::on_error(...)]` attribute, whose Rust
+    /// signature may not be able to express the error.
+    pub fn invoke_callback_with_fallback<R, UniFfiTag>(
+        &self,
+        handle: u64,
+        method: u32,
+        args: RustBuffer,
+        fallback: impl FnOnce(UnexpectedUniFFICallbackError) -> R,
+    ) -> R
+    where
+        R: FfiConverter<UniFfiTag>,
+    {
+        self.try_invoke_callback::<R, UniFfiTag>(handle, method, args)
+            .unwrap_or_else(|e| fallback(e.into()))
+    }
+
+    /// Invoke a callback interface method on the foreign side and return the result, or the
+    /// unexpected error that the foreign code raised
+    ///
+    /// This is used for methods that return `Result<R, CallbackError>`.
+    pub fn try_invoke_callback<R, UniFfiTag>(
+        &self,
+        handle: u64,
+        method: u32,
+        args: RustBuffer,
+    ) -> Result<R, CallbackError>
+    where
+        R: FfiConverter<UniFfiTag>,
+    {
+        // Freeing is still forwarded, since the handle is released before the foreign code is
+        // asked to free it.  This check races with `detach()`, see the module docs.
+        if method != IDX_CALLBACK_FREE && self.is_detached(handle) {
+            return Err(CallbackError::Internal {
+                reason: String::from("The foreign object was detached"),
+            });
+        }
         let mut ret_rbuf = RustBuffer::new();
         let callback_result = self.call_callback(handle, method, args, &mut ret_rbuf);
         match callback_result {
-            CALLBACK_SUCCESS => R::lift_callback_return(ret_rbuf),
-            CALLBACK_ERROR => R::lift_callback_error(ret_rbuf),
+            CALLBACK_SUCCESS => Ok(R::lift_callback_return(ret_rbuf)),
+            CALLBACK_ERROR => Ok(R::lift_callback_error(ret_rbuf)),
             CALLBACK_UNEXPECTED_ERROR => {
-                let reason = if !ret_rbuf.is_empty() {
-                    match <String as FfiConverter<UniFfiTag>>::try_lift(ret_rbuf) {
-                        Ok(s) => s,
-                        Err(e) => {
-                            log::error!("{{ trait_name }} Error reading ret_buf: {e}");
-                            String::from("[Error reading reason]")
-                        }
-                    }
-                } else {
-                    RustBuffer::destroy(ret_rbuf);
-                    String::from("[Unknown Reason]")
-                };
-                R::handle_callback_unexpected_error(UnexpectedUniFFICallbackError { reason })
+                Err(CallbackError::from_rust_buffer::<UniFfiTag>(ret_rbuf))
             }
             // Other values should never be returned
             _ => panic!("Callback failed with unexpected return code"),
@@ -254,6 +542,138 @@ impl ForeignCallbackInternals {
     }
 }
 
+/// The lifecycle of the foreign implementations of a callback interface
+///
+/// This is implemented for `dyn Trait` for each callback interface `Trait`, so that Rust code can
+/// react when the foreign objects that it was given go away:
+///
+/// ```ignore
+/// fn delegate_detached(handle: u64) {
+///     log::info!("delegate {handle} is gone");
+/// }
+///
+/// <dyn Delegate as uniffi::ForeignCallbackInterface>::set_lifecycle_hooks(
+///     None,
+///     Some(delegate_detached),
+/// );
+/// ```
+pub trait ForeignCallbackInterface {
+    #[doc(hidden)]
+    fn callback_internals() -> &'static ForeignCallbackInternals;
+
+    /// Set the functions called when Rust starts and stops using a foreign object, see
+    /// [`ForeignCallbackInternals::set_lifecycle_hooks`]
+    fn set_lifecycle_hooks(on_attach: Option<LifecycleHook>, on_detach: Option<LifecycleHook>) {
+        Self::callback_internals().set_lifecycle_hooks(on_attach, on_detach)
+    }
+
+    /// Is the foreign object behind `handle` still alive?  See
+    /// [`ForeignCallbackInternals::is_alive`]
+    fn is_alive(handle: u64) -> bool {
+        Self::callback_internals().is_alive(handle)
+    }
+
+    /// The handle of the foreign object behind `obj`
+    ///
+    /// `obj` is the `Box<dyn Trait>` that Rust was given, or a reference to its contents.  This is
+    /// `None` for Rust implementations of the trait, and for foreign ones that were moved out of
+    /// their `Box`, for example with `Arc::from()`.  Without the `std` feature the handlers aren't
+    /// tracked, and this is always `None`.
+    fn handle(obj: &Self) -> Option<u64> {
+        Self::callback_internals().handle_at(obj as *const Self as *const () as usize)
+    }
+}
+
+/// Is the object behind `obj` still alive?
+///
+/// This is `false` once the foreign code detached a foreign implementation, and it's always
+/// `true` for Rust implementations.  `obj` is a reference to the contents of the
+/// `Box<dyn Trait>` that Rust was given, see [`ForeignCallbackInterface::handle`]:
+///
+/// ```ignore
+/// if uniffi::is_alive(&*self.delegate) {
+///     self.delegate.on_change();
+/// }
+/// ```
+pub fn is_alive<T: ForeignCallbackInterface + ?Sized>(obj: &T) -> bool {
+    match T::handle(obj) {
+        Some(handle) => T::is_alive(handle),
+        None => true,
+    }
+}
+
+/// Error for callback interface methods that return `Result<T, CallbackError>`
+///
+/// When the foreign code raises an exception that the method doesn't declare, Rust gets it as a
+/// `CallbackError` rather than panicking, so it can log the error and recover.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackError {
+    /// The foreign implementation raised an exception that isn't the method's declared error
+    UnexpectedException { class_name: String, message: String },
+    /// The call failed for some other reason, for example because the foreign code didn't follow
+    /// the FFI contract
+    Internal { reason: String },
+}
+
+impl CallbackError {
+    /// Read the error that the foreign code wrote for `CALLBACK_UNEXPECTED_ERROR`
+    ///
+    /// This is the name of the exception's class, then its message, both serialized as strings.
+    /// The bindings use an empty class name for errors in their own code, rather than the foreign
+    /// implementation's.
+    fn from_rust_buffer<UniFfiTag>(buf: RustBuffer) -> Self {
+        if buf.is_empty() {
+            RustBuffer::destroy(buf);
+            return Self::Internal {
+                reason: String::from("[Unknown Reason]"),
+            };
+        }
+        let bytes = buf.destroy_into_vec();
+        let mut buf = bytes.as_slice();
+        let mut read_string = || <String as FfiConverter<UniFfiTag>>::try_read(&mut buf);
+        match (read_string(), read_string()) {
+            (Ok(class_name), Ok(message)) if class_name.is_empty() => {
+                Self::Internal { reason: message }
+            }
+            (Ok(class_name), Ok(message)) => Self::UnexpectedException {
+                class_name,
+                message,
+            },
+            (Err(e), _) | (_, Err(e)) => {
+                log::error!("Error reading callback interface error: {e}");
+                Self::Internal {
+                    reason: String::from("[Error reading reason]"),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for CallbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedException {
+                class_name,
+                message,
+            } if message.is_empty() => write!(f, "{class_name}"),
+            Self::UnexpectedException {
+                class_name,
+                message,
+            } => write!(f, "{class_name}: {message}"),
+            Self::Internal { reason } => write!(f, "{reason}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CallbackError {}
+
+impl From<CallbackError> for UnexpectedUniFFICallbackError {
+    fn from(e: CallbackError) -> Self {
+        Self::from_reason(e.to_string())
+    }
+}
+
 /// Used when internal/unexpected error happened when calling a foreign callback, for example when
 /// a unknown exception is raised
 ///
@@ -279,4 +699,147 @@ impl fmt::Display for UnexpectedUniFFICallbackError {
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for UnexpectedUniFFICallbackError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+
+    fn unexpected_error_buf(class_name: &str, message: &str) -> RustBuffer {
+        let mut buf = Vec::new();
+        <String as FfiConverter<UniFfiTag>>::write(class_name.to_string(), &mut buf);
+        <String as FfiConverter<UniFfiTag>>::write(message.to_string(), &mut buf);
+        RustBuffer::from_vec(buf)
+    }
+
+    #[test]
+    fn test_callback_error_from_rust_buffer() {
+        assert_eq!(
+            CallbackError::from_rust_buffer::<UniFfiTag>(unexpected_error_buf(
+                "ValueError",
+                "boom"
+            )),
+            CallbackError::UnexpectedException {
+                class_name: "ValueError".to_string(),
+                message: "boom".to_string(),
+            }
+        );
+        assert_eq!(
+            CallbackError::from_rust_buffer::<UniFfiTag>(unexpected_error_buf(
+                "",
+                "Invalid handle"
+            )),
+            CallbackError::Internal {
+                reason: "Invalid handle".to_string(),
+            }
+        );
+        assert_eq!(
+            CallbackError::from_rust_buffer::<UniFfiTag>(RustBuffer::new()),
+            CallbackError::Internal {
+                reason: "[Unknown Reason]".to_string(),
+            }
+        );
+        assert_eq!(
+            CallbackError::from_rust_buffer::<UniFfiTag>(RustBuffer::from_vec(vec![0, 0])),
+            CallbackError::Internal {
+                reason: "[Error reading reason]".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_callback_error_reason() {
+        let e = CallbackError::UnexpectedException {
+            class_name: "java.lang.IllegalStateException".to_string(),
+            message: "not ready".to_string(),
+        };
+        assert_eq!(
+            UnexpectedUniFFICallbackError::from(e).reason,
+            "java.lang.IllegalStateException: not ready"
+        );
+    }
+
+    // The handles are only tracked with `std`
+    #[cfg(feature = "std")]
+    mod lifecycle {
+        use super::*;
+
+        static ATTACHED: AtomicUsize = AtomicUsize::new(0);
+        static DETACHED: AtomicUsize = AtomicUsize::new(0);
+
+        fn count_attach(_handle: u64) {
+            ATTACHED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn count_detach(_handle: u64) {
+            DETACHED.fetch_add(1, Ordering::SeqCst);
+        }
+
+        #[test]
+        fn test_lifecycle() {
+            let internals = ForeignCallbackInternals::new();
+            internals.set_lifecycle_hooks(Some(count_attach), Some(count_detach));
+            // Two handlers for the same handle only attach it once
+            internals.attach(1);
+            internals.attach(1);
+            assert_eq!(ATTACHED.load(Ordering::SeqCst), 1);
+            assert!(internals.is_alive(1));
+            assert!(internals.release(1));
+            assert!(internals.is_alive(1));
+            assert_eq!(DETACHED.load(Ordering::SeqCst), 0);
+            assert!(internals.release(1));
+            assert!(!internals.is_alive(1));
+            assert_eq!(DETACHED.load(Ordering::SeqCst), 1);
+
+            // Once the foreign code detached the object, dropping the handler doesn't free it again
+            internals.attach(2);
+            internals.detach(2);
+            assert!(!internals.is_alive(2));
+            assert_eq!(DETACHED.load(Ordering::SeqCst), 2);
+            internals.detach(2);
+            assert!(!internals.release(2));
+            assert_eq!(DETACHED.load(Ordering::SeqCst), 2);
+        }
+
+        #[test]
+        fn test_handler_addresses() {
+            let internals = ForeignCallbackInternals::new();
+            internals.register_handler(0x1000, 1);
+            assert_eq!(internals.handle_at(0x1000), Some(1));
+            assert_eq!(internals.handle_at(0x2000), None);
+            // Another handler at the same address replaced it, so dropping the first one doesn't
+            // forget the second
+            internals.register_handler(0x1000, 2);
+            internals.unregister_handler(0x1000, 1);
+            assert_eq!(internals.handle_at(0x1000), Some(2));
+            internals.unregister_handler(0x1000, 2);
+            assert_eq!(internals.handle_at(0x1000), None);
+        }
+
+        #[test]
+        fn test_unattached_handles() {
+            // The handlers for default method implementations don't attach their handle, and
+            // aren't treated as detached
+            let internals = ForeignCallbackInternals::new();
+            assert!(!internals.is_alive(1));
+            assert!(!internals.is_detached(1));
+            assert!(internals.release(1));
+        }
+
+        #[test]
+        fn test_detached_objects_arent_called() {
+            // No callback is set, so this would panic if it tried to call the foreign code
+            let internals = ForeignCallbackInternals::new();
+            internals.attach(1);
+            internals.detach(1);
+            assert_eq!(
+                internals.try_invoke_callback::<(), UniFfiTag>(1, 1, RustBuffer::new()),
+                Err(CallbackError::Internal {
+                    reason: "The foreign object was detached".to_string(),
+                })
+            );
+        }
+    }
+}
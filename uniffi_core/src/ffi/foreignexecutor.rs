@@ -4,15 +4,18 @@
 
 //! Schedule tasks using a foreign executor.
 
+use crate::MaybeSend;
+use alloc::boxed::Box;
+use core::{
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+#[cfg(feature = "std")]
 use std::{
     cell::UnsafeCell,
     future::Future,
-    panic,
     pin::Pin,
-    sync::{
-        atomic::{AtomicUsize, Ordering},
-        Arc, Mutex,
-    },
+    sync::{Arc, Mutex},
     task::{Context, Poll, Waker},
 };
 
@@ -125,7 +128,7 @@ fn get_foreign_executor_callback() -> ForeignExecutorCallback {
         0 => panic!("FOREIGN_EXECUTOR_CALLBACK not set"),
         // SAFETY: The below call is okay because we only store values in
         // FOREIGN_EXECUTOR_CALLBACK that were cast from a ForeignExecutorCallback.
-        n => unsafe { std::mem::transmute(n) },
+        n => unsafe { core::mem::transmute::<usize, ForeignExecutorCallback>(n) },
     }
 }
 
@@ -146,20 +149,29 @@ impl ForeignExecutor {
     /// need to await the result.
     ///
     /// Closure requirements:
-    ///   - Send: since the closure will likely run on a different thread
+    ///   - MaybeSend: since the closure will likely run on a different thread, except on
+    ///     single-threaded wasm32
     ///   - 'static: since it runs at an arbitrary time, so all references need to be 'static
     ///   - panic::UnwindSafe: if the closure panics, it should not corrupt any data
-    pub fn schedule<F: FnOnce() + Send + 'static + panic::UnwindSafe>(&self, delay: u32, task: F) {
+    pub fn schedule<F: FnOnce() + MaybeSend + 'static + panic::UnwindSafe>(
+        &self,
+        delay: u32,
+        task: F,
+    ) {
         ScheduledTask::new(task).schedule_callback(self.handle, delay)
     }
 
     /// Schedule a closure to be run and get a Future for the result
     ///
     /// Closure requirements:
-    ///   - Send: since the closure will likely run on a different thread
+    ///   - MaybeSend: since the closure will likely run on a different thread, except on
+    ///     single-threaded wasm32
     ///   - 'static: since it runs at an arbitrary time, so all references need to be 'static
     ///   - panic::UnwindSafe: if the closure panics, it should not corrupt any data
-    pub fn run<F: FnOnce() -> T + Send + 'static + panic::UnwindSafe, T>(
+    ///
+    /// This requires the `std` feature.
+    #[cfg(feature = "std")]
+    pub fn run<F: FnOnce() -> T + MaybeSend + 'static + panic::UnwindSafe, T>(
         &self,
         delay: u32,
         closure: F,
@@ -188,7 +200,7 @@ pub(crate) fn schedule_raw(
 
 impl Drop for ForeignExecutor {
     fn drop(&mut self) {
-        (get_foreign_executor_callback())(self.handle, 0, None, std::ptr::null());
+        (get_foreign_executor_callback())(self.handle, 0, None, core::ptr::null());
     }
 }
 /// Struct that handles the ForeignExecutor::schedule() method
@@ -198,7 +210,7 @@ struct ScheduledTask<F> {
 
 impl<F> ScheduledTask<F>
 where
-    F: FnOnce() + Send + 'static + panic::UnwindSafe,
+    F: FnOnce() + MaybeSend + 'static + panic::UnwindSafe,
 {
     fn new(task: F) -> Self {
         Self { task }
@@ -227,11 +239,13 @@ where
 }
 
 /// Struct that handles the ForeignExecutor::run() method
+#[cfg(feature = "std")]
 struct RunFuture<T, F> {
     inner: Arc<RunFutureInner<T, F>>,
 }
 
 // State inside the RunFuture Arc<>
+#[cfg(feature = "std")]
 struct RunFutureInner<T, F> {
     // SAFETY: we only access this once in the scheduled callback
     task: UnsafeCell<Option<F>>,
@@ -239,14 +253,16 @@ struct RunFutureInner<T, F> {
 }
 
 // State inside the RunFuture Mutex<>
+#[cfg(feature = "std")]
 struct RunFutureInner2<T> {
     result: Option<T>,
     waker: Option<Waker>,
 }
 
+#[cfg(feature = "std")]
 impl<T, F> RunFuture<T, F>
 where
-    F: FnOnce() -> T + Send + 'static + panic::UnwindSafe,
+    F: FnOnce() -> T + MaybeSend + 'static + panic::UnwindSafe,
 {
     fn new(task: F) -> Self {
         Self {
@@ -292,6 +308,7 @@ where
     }
 }
 
+#[cfg(feature = "std")]
 impl<T, F> Future for RunFuture<T, F> {
     type Output = T;
 
@@ -310,8 +327,9 @@ impl<T, F> Future for RunFuture<T, F> {
 /// Run a scheduled task, catching any panics.
 ///
 /// If there are panics, then we will log a warning and return None.
+#[cfg(feature = "std")]
 fn run_task<F: FnOnce() -> T + panic::UnwindSafe, T>(task: F) -> Option<T> {
-    match panic::catch_unwind(task) {
+    match std::panic::catch_unwind(task) {
         Ok(v) => Some(v),
         Err(cause) => {
             let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
@@ -327,6 +345,14 @@ fn run_task<F: FnOnce() -> T + panic::UnwindSafe, T>(task: F) -> Option<T> {
     }
 }
 
+/// Run a scheduled task
+///
+/// Without `std` panics can't be caught, they go to the `#[panic_handler]` instead.
+#[cfg(not(feature = "std"))]
+fn run_task<F: FnOnce() -> T + panic::UnwindSafe, T>(task: F) -> Option<T> {
+    Some(task())
+}
+
 #[cfg(test)]
 pub use test::MockEventLoop;
 
@@ -356,6 +382,9 @@ mod test {
         is_shutdown: bool,
     }
 
+    // The task data is only passed back to the task callback, on the thread that runs the tasks
+    unsafe impl Send for MockEventLoopInner {}
+
     static FOREIGN_EXECUTOR_CALLBACK_INIT: Once = Once::new();
 
     impl MockEventLoop {
@@ -525,7 +554,7 @@ mod test {
         drop(executor);
         // Calling drop should schedule a call with null task data.
         assert_eq!(eventloop.call_count(), 1);
-        assert_eq!(eventloop.last_call().1, None);
+        assert!(eventloop.last_call().1.is_none());
     }
 
     // Test that cancelled calls never run
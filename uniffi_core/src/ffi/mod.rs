@@ -4,18 +4,23 @@
 
 //! Types that can cross the FFI boundary.
 
+pub mod bufferpool;
 pub mod ffidefault;
 pub mod foreignbytes;
 pub mod foreigncallbacks;
 pub mod foreignexecutor;
+#[doc(hidden)]
+pub mod remotecalls;
 pub mod rustbuffer;
 pub mod rustcalls;
 pub mod rustfuture;
 
+pub use bufferpool::{disable_buffer_pool, enable_buffer_pool};
 pub use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
 pub use foreignexecutor::*;
+pub use remotecalls::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
 pub use rustfuture::*;
@@ -0,0 +1,231 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Remote calls
+//!
+//! Remote calls let a process that can't load the library, like a sandboxed process or a browser
+//! extension, call the functions of a component that's loaded by another process.
+//!
+//! With the `unstable-remote-calls` feature, the scaffolding adds a remote entry point for each
+//! sync top-level function, named by `uniffi_meta::remote_fn_symbol_name()`.  It takes the arguments
+//! serialized into a single `RustBuffer` and returns the result serialized in the same way, with
+//! the same `FfiConverter::write()` code that's used for values in `RustBuffer`s.  The bindings
+//! send the name of the entry point and the arguments over a transport that the application
+//! provides, the process that loaded the library calls the entry point, and sends the
+//! `RustCallStatus` code back with the returned buffer or the error buffer.
+//!
+//! Remote calls are unstable, so the items of this module are hidden from the docs.
+
+use super::rustcalls::{CALL_PANIC, CALL_SUCCESS};
+use crate::{rust_call, FfiConverter, RustBuffer, RustCallStatus};
+use alloc::{string::String, vec::Vec};
+use core::panic::UnwindSafe;
+
+#[cfg(feature = "std")]
+use super::rustcalls::InvalidArgument;
+
+/// Handle a call to a remote entry point
+///
+/// `read_args` reads the arguments out of `args`, then `call` makes the Rust call with them.  The
+/// call fails with `CALL_INVALID_ARGUMENT` if there's data left after the arguments.  The return
+/// value is written with [FfiConverter::write_return], and the arguments are wiped once the call
+/// is done, since they may hold sensitive values.
+#[doc(hidden)]
+pub fn remote_call<A, R, UT>(
+    args: RustBuffer,
+    call_status: &mut RustCallStatus,
+    read_args: impl FnOnce(&mut &[u8]) -> A + UnwindSafe,
+    call: impl FnOnce(A) -> R + UnwindSafe,
+) -> RustBuffer
+where
+    R: FfiConverter<UT>,
+{
+    let mut args = args.destroy_into_vec();
+    let result = rust_call(call_status, || {
+        let mut reader = args.as_slice();
+        let lifted_args = read_args(&mut reader);
+        if !reader.is_empty() {
+            data_after_remote_args(reader.len());
+        }
+        let mut buf = Vec::new();
+        R::write_return(call(lifted_args), &mut buf)?;
+        Ok(RustBuffer::from_vec(buf))
+    });
+    crate::wipe_vec(&mut args);
+    result
+}
+
+/// Read an argument of a remote call, failing the call if it can't be read
+#[doc(hidden)]
+pub fn read_remote_arg<T: FfiConverter<UT>, UT>(args: &mut &[u8], arg_name: &'static str) -> T {
+    match T::try_read(args) {
+        Ok(v) => v,
+        Err(err) => crate::lift_arg_failed(arg_name, err),
+    }
+}
+
+/// Call a remote entry point with arbitrary data as its arguments, for the fuzz targets generated
+/// by `uniffi-bindgen fuzz --calls`
+///
+/// Arguments that can't be read make the call fail with `CALL_INVALID_ARGUMENT`, and errors are
+/// expected, but a panic is a bug, so it's raised again for the fuzzer to report.
+///
+/// # Safety
+///
+/// `entry_point` must be a remote entry point of the scaffolding.
+#[doc(hidden)]
+pub unsafe fn fuzz_remote_call(
+    entry_point: unsafe extern "C" fn(RustBuffer, &mut RustCallStatus) -> RustBuffer,
+    data: &[u8],
+) {
+    let mut call_status = RustCallStatus::default();
+    let result = entry_point(RustBuffer::from_vec(data.to_vec()), &mut call_status);
+    if call_status.code == CALL_SUCCESS {
+        result.destroy();
+        return;
+    }
+    let error_buf = call_status.error_buf.assume_init().destroy_into_vec();
+    if call_status.code == CALL_PANIC {
+        // The message is followed by the backtrace, which the fuzzer reports anyway
+        let message =
+            <String as FfiConverter<crate::UniFfiTag>>::try_read(&mut error_buf.as_slice());
+        panic!("{}", message.unwrap_or_default());
+    }
+}
+
+// Like `lift_arg_failed()`, for data that isn't part of any argument
+#[cold]
+#[inline(never)]
+fn data_after_remote_args(len: usize) -> ! {
+    #[cfg(feature = "std")]
+    std::panic::resume_unwind(Box::new(InvalidArgument(format!(
+        "{len} bytes left over after the arguments"
+    ))));
+    #[cfg(not(feature = "std"))]
+    panic!("{len} bytes left over after the arguments")
+}
+
+/// Expands to the remote entry points of the scaffolding, with the `unstable-remote-calls` feature
+///
+/// The scaffolding wraps the entry points in this macro, so that the feature of `uniffi` decides
+/// whether they're compiled, rather than the crate that exports the functions.
+#[cfg(feature = "unstable-remote-calls")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! remote_call_scaffolding {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// Expands to the remote entry points of the scaffolding, with the `unstable-remote-calls` feature
+///
+/// The scaffolding wraps the entry points in this macro, so that the feature of `uniffi` decides
+/// whether they're compiled, rather than the crate that exports the functions.
+#[cfg(not(feature = "unstable-remote-calls"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! remote_call_scaffolding {
+    ($($item:item)*) => {};
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        ffi::rustcalls::{CALL_ERROR, CALL_INVALID_ARGUMENT},
+        lower_into_rust_buffer, UniFfiTag,
+    };
+
+    fn args(values: (u32, String)) -> RustBuffer {
+        let mut buf = Vec::new();
+        <u32 as FfiConverter<UniFfiTag>>::write(values.0, &mut buf);
+        <String as FfiConverter<UniFfiTag>>::write(values.1, &mut buf);
+        RustBuffer::from_vec(buf)
+    }
+
+    fn repeat(args: RustBuffer, call_status: &mut RustCallStatus) -> RustBuffer {
+        remote_call::<_, String, UniFfiTag>(
+            args,
+            call_status,
+            |args| {
+                (
+                    read_remote_arg::<u32, UniFfiTag>(args, "count"),
+                    read_remote_arg::<String, UniFfiTag>(args, "s"),
+                )
+            },
+            |(count, s)| s.repeat(count as usize),
+        )
+    }
+
+    #[test]
+    fn test_remote_call() {
+        let mut call_status = RustCallStatus::default();
+        let result = repeat(args((3, "ab".to_string())), &mut call_status);
+        assert_eq!(call_status.code, CALL_SUCCESS);
+        let result = result.destroy_into_vec();
+        assert_eq!(
+            <String as FfiConverter<UniFfiTag>>::try_read(&mut result.as_slice()).unwrap(),
+            "ababab"
+        );
+    }
+
+    #[test]
+    fn test_remote_call_with_leftover_data() {
+        let mut buf = args((3, "ab".to_string())).destroy_into_vec();
+        buf.push(0);
+        let mut call_status = RustCallStatus::default();
+        repeat(RustBuffer::from_vec(buf), &mut call_status);
+        assert_eq!(call_status.code, CALL_INVALID_ARGUMENT);
+    }
+
+    #[test]
+    fn test_remote_call_error() {
+        let mut call_status = RustCallStatus::default();
+        let result = remote_call::<_, Result<u32, String>, UniFfiTag>(
+            RustBuffer::new(),
+            &mut call_status,
+            |_| (),
+            |()| Err("failed".to_string()),
+        );
+        assert_eq!(call_status.code, CALL_ERROR);
+        assert_eq!(result.len(), 0);
+        assert_eq!(
+            unsafe { call_status.error_buf.assume_init() }.destroy_into_vec(),
+            lower_into_rust_buffer::<String, UniFfiTag>("failed".to_string()).destroy_into_vec()
+        );
+    }
+
+    extern "C" fn repeat_entry_point(
+        args: RustBuffer,
+        call_status: &mut RustCallStatus,
+    ) -> RustBuffer {
+        repeat(args, call_status)
+    }
+
+    extern "C" fn panicking_entry_point(
+        args: RustBuffer,
+        call_status: &mut RustCallStatus,
+    ) -> RustBuffer {
+        remote_call::<_, (), UniFfiTag>(args, call_status, |_| (), |()| panic!("fuzzed"))
+    }
+
+    #[test]
+    fn test_fuzz_remote_call() {
+        // Arguments that can't be read fail the call without panicking
+        unsafe { fuzz_remote_call(repeat_entry_point, &[0, 0, 0, 3, 0xff]) };
+        unsafe {
+            fuzz_remote_call(
+                repeat_entry_point,
+                &args((2, "a".to_string())).destroy_into_vec(),
+            )
+        };
+    }
+
+    #[test]
+    #[should_panic(expected = "fuzzed")]
+    fn test_fuzz_remote_call_panic() {
+        unsafe { fuzz_remote_call(panicking_entry_point, &[]) };
+    }
+}
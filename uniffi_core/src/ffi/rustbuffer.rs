@@ -2,7 +2,8 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::ffi::{rust_call, ForeignBytes, RustCallStatus};
+use crate::ffi::{bufferpool, rust_call, ForeignBytes, RustCallStatus};
+use alloc::{vec, vec::Vec};
 
 /// Support for passing an allocated-by-Rust buffer of bytes over the FFI.
 ///
@@ -130,7 +131,9 @@ impl RustBuffer {
             size < i32::MAX as usize,
             "RustBuffer requested size too large"
         );
-        Self::from_vec(vec![0u8; size])
+        let mut v = bufferpool::take(size);
+        v.resize(size, 0);
+        Self::from_vec(v)
     }
 
     /// Consumes a `Vec<u8>` and returns its raw parts as a `RustBuffer`.
@@ -145,7 +148,7 @@ impl RustBuffer {
     pub fn from_vec(v: Vec<u8>) -> Self {
         let capacity = i32::try_from(v.capacity()).expect("buffer capacity cannot fit into a i32.");
         let len = i32::try_from(v.len()).expect("buffer length cannot fit into a i32.");
-        let mut v = std::mem::ManuallyDrop::new(v);
+        let mut v = core::mem::ManuallyDrop::new(v);
         unsafe { Self::from_raw_parts(v.as_mut_ptr(), len, capacity) }
     }
 
@@ -182,12 +185,17 @@ impl RustBuffer {
 
     /// Reclaim memory stored in this `RustBuffer`.
     ///
+    /// If the buffer holds a lowered [crate::Secret], the memory is zeroed first.  If the buffer
+    /// pool is enabled, the memory is kept for reuse by a later buffer.
+    ///
     /// # Panics
     ///
     /// Panics if called on an invalid struct obtained from foreign-language code,
     /// which does not respect the invairiants on `len` and `capacity`.
     pub fn destroy(self) {
-        drop(self.destroy_into_vec());
+        let mut v = self.destroy_into_vec();
+        crate::secret::wipe_if_lowered(&mut v);
+        bufferpool::recycle(v);
     }
 }
 
@@ -296,6 +304,17 @@ mod test {
         assert_eq!(rbuf.destroy_into_vec(), vec![1u8, 2, 3]);
     }
 
+    #[test]
+    fn test_rustbuffer_reserve() {
+        let mut status = RustCallStatus::default();
+        let rbuf = RustBuffer::from_vec(vec![1u8, 2, 3]);
+        let rbuf = unsafe { uniffi_rustbuffer_reserve(rbuf, 100, &mut status) };
+        assert_eq!(status.code, 0);
+        let v = rbuf.destroy_into_vec();
+        assert_eq!(v, vec![1u8, 2, 3]);
+        assert!(v.capacity() >= 103);
+    }
+
     #[test]
     fn test_rustbuffer_empty() {
         let rbuf = RustBuffer::new();
@@ -7,13 +7,17 @@
 //! This module helps the scaffolding code make calls to rust functions and pass back the result to the FFI bindings code.
 //!
 //! It handles:
-//!    - Catching panics
+//!    - Catching panics, with the `std` feature.  Without it, a panic goes to the
+//!      `#[panic_handler]` of the crate.
 //!    - Adapting the result of `FfiConverter::lower_return()` into either a return value or an
 //!      exception
 
-use crate::{FfiConverter, FfiDefault, RustBuffer, UniFfiTag};
-use std::mem::MaybeUninit;
-use std::panic;
+#[cfg(feature = "std")]
+use crate::{FfiConverter, UniFfiTag};
+use crate::{FfiDefault, RustBuffer};
+use core::{mem::MaybeUninit, panic::UnwindSafe};
+#[cfg(feature = "std")]
+use std::any::Any;
 
 /// Represents the success/error of a rust call
 ///
@@ -44,11 +48,17 @@ use std::panic;
 ///  - `CALL_SUCCESS` (0) for successful calls
 ///  - `CALL_ERROR` (1) for calls that returned an `Err` value
 ///  - `CALL_PANIC` (2) for calls that panicked
+///  - `CALL_INVALID_ARGUMENT` (3) for calls with an argument that couldn't be lifted, like a zero
+///    `NonZeroU32`
 ///
 /// #### The `error_buf` field.
 ///
 /// - For `CALL_ERROR` this is a `RustBuffer` with the serialized error.  The consumer code is
 ///   responsible for freeing this `RustBuffer`.
+/// - For `CALL_PANIC` this is a `RustBuffer` with the message of the panic, then its backtrace,
+///   written like `String` values in a buffer, with a length prefix.  The backtrace is empty if
+///   none was captured.  The buffer is empty if the message couldn't be serialized.
+/// - For `CALL_INVALID_ARGUMENT` this is a `RustBuffer` with the message, lowered like a `String`.
 #[repr(C)]
 pub struct RustCallStatus {
     pub code: i8,
@@ -75,9 +85,19 @@ impl Default for RustCallStatus {
 }
 
 #[allow(dead_code)]
-const CALL_SUCCESS: i8 = 0; // CALL_SUCCESS is set by the calling code
-const CALL_ERROR: i8 = 1;
-const CALL_PANIC: i8 = 2;
+pub(crate) const CALL_SUCCESS: i8 = 0; // CALL_SUCCESS is set by the calling code
+pub(crate) const CALL_ERROR: i8 = 1;
+pub(crate) const CALL_PANIC: i8 = 2;
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) const CALL_INVALID_ARGUMENT: i8 = 3;
+
+/// The payload of the unwind when a scaffolding function fails to lift an argument
+///
+/// `lift_arg_failed()` unwinds with this rather than panicking, so that `rust_call()` can report
+/// the failure with `CALL_INVALID_ARGUMENT`.  The bindings raise a typed error for that, which the
+/// caller can catch, since it's the caller's mistake rather than a bug of the library.
+#[cfg(feature = "std")]
+pub(crate) struct InvalidArgument(pub(crate) String);
 
 /// Handle a scaffolding calls
 ///
@@ -93,14 +113,20 @@ const CALL_PANIC: i8 = 2;
 ///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing the error.  The calling
 ///       code is responsible for freeing the `RustBuffer`
 ///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
+/// - If an argument can't be lifted:
+///     - `out_status.code` will be set to `CALL_INVALID_ARGUMENT`
+///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing a
+///       serialized error message.  The calling code is responsible for freeing the `RustBuffer`
+///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
 /// - If the function panics:
 ///     - `out_status.code` will be set to `CALL_PANIC`
 ///     - `out_status.error_buf` will be set to a newly allocated `RustBuffer` containing a
-///       serialized error message.  The calling code is responsible for freeing the `RustBuffer`
+///       serialized error message.  If `RUST_BACKTRACE` is enabled, the message is followed by a
+///       backtrace of the panic.  The calling code is responsible for freeing the `RustBuffer`
 ///     - `FfiDefault::ffi_default()` is returned, although foreign code should ignore this value
 pub fn rust_call<F, R>(out_status: &mut RustCallStatus, callback: F) -> R
 where
-    F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
+    F: UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
     R: FfiDefault,
 {
     rust_call_with_out_status(out_status, callback).unwrap_or_else(R::ffi_default)
@@ -117,56 +143,99 @@ pub(crate) fn rust_call_with_out_status<F, R>(
     callback: F,
 ) -> Option<R>
 where
-    F: panic::UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
+    F: UnwindSafe + FnOnce() -> Result<R, RustBuffer>,
 {
-    let result = panic::catch_unwind(|| {
+    #[cfg(feature = "std")]
+    let result = std::panic::catch_unwind(|| {
         crate::panichook::ensure_setup();
         callback()
     });
+    // Without `std` panics can't be caught, they go to the `#[panic_handler]` instead
+    #[cfg(not(feature = "std"))]
+    let result: Result<_, core::convert::Infallible> = Ok(callback());
     match result {
         // Happy path.  Note: no need to update out_status in this case because the calling code
         // initializes it to CALL_SUCCESS
         Ok(Ok(v)) => Some(v),
         // Callback returned an Err.
         Ok(Err(buf)) => {
-            out_status.code = CALL_ERROR;
+            set_call_error(out_status, buf);
+            None
+        }
+        // Callback panicked
+        #[cfg(feature = "std")]
+        Err(cause) => {
+            set_call_panic(out_status, cause);
+            None
+        }
+        #[cfg(not(feature = "std"))]
+        Err(never) => match never {},
+    }
+}
+
+// The error handling for `rust_call_with_out_status` is kept out of that function, so that it's
+// compiled once instead of for every scaffolding function.
+
+#[inline(never)]
+fn set_call_error(out_status: &mut RustCallStatus, buf: RustBuffer) {
+    out_status.code = CALL_ERROR;
+    unsafe {
+        // Unsafe because we're setting the `MaybeUninit` value, see above for safety
+        // invariants.
+        out_status.error_buf.as_mut_ptr().write(buf);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cold]
+#[inline(never)]
+fn set_call_panic(out_status: &mut RustCallStatus, cause: Box<dyn Any + Send>) {
+    let cause = match cause.downcast::<InvalidArgument>() {
+        Ok(invalid) => {
+            out_status.code = CALL_INVALID_ARGUMENT;
+            let buf = <String as FfiConverter<UniFfiTag>>::lower(invalid.0);
             unsafe {
                 // Unsafe because we're setting the `MaybeUninit` value, see above for safety
                 // invariants.
                 out_status.error_buf.as_mut_ptr().write(buf);
             }
-            None
+            return;
         }
-        // Callback panicked
-        Err(cause) => {
-            out_status.code = CALL_PANIC;
-            // Try to coerce the cause into a RustBuffer containing a String.  Since this code can
-            // panic, we need to use a second catch_unwind().
-            let message_result = panic::catch_unwind(panic::AssertUnwindSafe(move || {
-                // The documentation suggests that it will *usually* be a str or String.
-                let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
-                    (*s).to_string()
-                } else if let Some(s) = cause.downcast_ref::<String>() {
-                    s.clone()
-                } else {
-                    "Unknown panic!".to_string()
-                };
-                log::error!("Caught a panic calling rust code: {:?}", message);
-                <String as FfiConverter<UniFfiTag>>::lower(message)
-            }));
-            if let Ok(buf) = message_result {
-                unsafe {
-                    // Unsafe because we're setting the `MaybeUninit` value, see above for safety
-                    // invariants.
-                    out_status.error_buf.as_mut_ptr().write(buf);
-                }
-            }
-            // Ignore the error case.  We've done all that we can at this point.  In the bindings
-            // code, we handle this by checking if `error_buf` still has an empty `RustBuffer` and
-            // using a generic message.
-            None
+        Err(cause) => cause,
+    };
+    out_status.code = CALL_PANIC;
+    // Try to coerce the cause into a RustBuffer containing a String.  Since this code can
+    // panic, we need to use a second catch_unwind().
+    let message_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+        // The documentation suggests that it will *usually* be a str or String.
+        let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
+            (*s).to_string()
+        } else if let Some(s) = cause.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic!".to_string()
+        };
+        log::error!("Caught a panic calling rust code: {:?}", message);
+        // If backtraces are enabled, pass the one captured by our panic hook along with the
+        // message, so that the bindings can put it in a field of the foreign exception.
+        let backtrace = crate::panichook::take_backtrace()
+            .map(|backtrace| backtrace.to_string())
+            .unwrap_or_default();
+        let mut buf = Vec::new();
+        <String as FfiConverter<UniFfiTag>>::write(message, &mut buf);
+        <String as FfiConverter<UniFfiTag>>::write(backtrace, &mut buf);
+        RustBuffer::from_vec(buf)
+    }));
+    if let Ok(buf) = message_result {
+        unsafe {
+            // Unsafe because we're setting the `MaybeUninit` value, see above for safety
+            // invariants.
+            out_status.error_buf.as_mut_ptr().write(buf);
         }
     }
+    // Ignore the error case.  We've done all that we can at this point.  In the bindings
+    // code, we handle this by checking if `error_buf` still has an empty `RustBuffer` and
+    // using a generic message.
 }
 
 #[cfg(test)]
@@ -240,11 +309,29 @@ mod test {
         });
         assert_eq!(status.code, CALL_PANIC);
         unsafe {
-            assert_eq!(
+            let buf = status.error_buf.assume_init().destroy_into_vec();
+            let mut buf = buf.as_slice();
+            let message = <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap();
+            assert_eq!(message, "Unexpected value: 2");
+            // The backtrace depends on `RUST_BACKTRACE`
+            <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap();
+            assert!(buf.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_rust_call_invalid_argument() {
+        let mut status = create_call_status();
+        rust_call(&mut status, || {
+            let value = crate::lift_arg::<core::num::NonZeroU32, UniFfiTag>(0, "value");
+            <u32 as FfiConverter<UniFfiTag>>::lower_return(value.get())
+        });
+        assert_eq!(status.code, CALL_INVALID_ARGUMENT);
+        unsafe {
+            let message =
                 <String as FfiConverter<UniFfiTag>>::try_lift(status.error_buf.assume_init())
-                    .unwrap(),
-                "Unexpected value: 2"
-            );
+                    .unwrap();
+            assert!(message.starts_with("Failed to convert arg 'value': "));
         }
     }
 }
@@ -117,17 +117,15 @@
 
 use crate::{
     ffi::foreignexecutor::RustTaskCallbackCode, rust_call_with_out_status, schedule_raw,
-    FfiConverter, FfiDefault, ForeignExecutor, ForeignExecutorHandle, RustCallStatus,
+    FfiConverter, FfiDefault, ForeignExecutor, ForeignExecutorHandle, MaybeSend, RustCallStatus,
 };
-use std::{
+use alloc::sync::Arc;
+use core::{
     cell::UnsafeCell,
     future::Future,
     panic,
     pin::Pin,
-    sync::{
-        atomic::{AtomicU32, Ordering},
-        Arc,
-    },
+    sync::atomic::{AtomicU32, Ordering},
     task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
 };
 
@@ -147,8 +145,9 @@ pub struct RustFuture<F, T, UT>
 where
     // The future needs to be `Send`, since it will move to whatever thread the foreign executor
     // chooses.  However, it doesn't need to be `Sync', since we don't share references between
-    // threads (see do_wake()).
-    F: Future<Output = T> + Send,
+    // threads (see do_wake()).  On single-threaded wasm32 it doesn't need to be `Send` either, see
+    // `MaybeSend`.
+    F: Future<Output = T> + MaybeSend,
     T: FfiConverter<UT>,
 {
     future: UnsafeCell<F>,
@@ -160,25 +159,26 @@ where
 
 // Mark `RustFuture` as `Send` + `Sync`, since we will be sharing it between threads.
 // This means we need to serialize access to any fields that aren't `Send` + `Sync` (`future`, `callback`, and `callback_data`).
-// See `do_wake()` for details on this.
+// See `do_wake()` for details on this.  On single-threaded wasm32 the future may not be `Send`,
+// but there's no other thread to share it with.
 
 unsafe impl<F, T, UT> Send for RustFuture<F, T, UT>
 where
-    F: Future<Output = T> + Send,
+    F: Future<Output = T> + MaybeSend,
     T: FfiConverter<UT>,
 {
 }
 
 unsafe impl<F, T, UT> Sync for RustFuture<F, T, UT>
 where
-    F: Future<Output = T> + Send,
+    F: Future<Output = T> + MaybeSend,
     T: FfiConverter<UT>,
 {
 }
 
 impl<F, T, UT> RustFuture<F, T, UT>
 where
-    F: Future<Output = T> + Send,
+    F: Future<Output = T> + MaybeSend,
     T: FfiConverter<UT>,
 {
     pub fn new(
@@ -22,17 +22,30 @@
 /// consumer crates.  To do this, it defines blanket impls like `impl<UT> FFIConverter<UT> for u8`.
 /// "UT" means an abitrary `UniFfiTag` type.
 use crate::{
-    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
-    lower_into_rust_buffer, metadata, try_lift_from_rust_buffer, FfiConverter, FutureCallback,
-    MetadataBuffer, Result, RustBuffer, RustCallStatus, UnexpectedUniFFICallbackError,
+    check_remaining, core_error, ffi_converter_default_return,
+    ffi_converter_rust_buffer_lift_and_lower, lower_into_rust_buffer, metadata,
+    try_lift_from_rust_buffer, FfiConverter, FutureCallback, MetadataBuffer, Result, RustBuffer,
+    RustCallStatus, UnexpectedUniFFICallbackError,
 };
+use alloc::{string::String, vec::Vec};
 use anyhow::bail;
 use bytes::buf::{Buf, BufMut};
+use core::{
+    convert::{Infallible, TryFrom},
+    mem::size_of,
+    num::{
+        NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8,
+    },
+    time::Duration,
+};
 use paste::paste;
+#[cfg(feature = "std")]
 use std::{
     collections::HashMap,
-    convert::{Infallible, TryFrom},
-    time::{Duration, SystemTime},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::PathBuf,
+    time::SystemTime,
 };
 
 /// Blanket implementation of `FfiConverter` for numeric primitives.
@@ -60,7 +73,7 @@ macro_rules! impl_ffi_converter_for_num_primitive {
                 }
 
                 fn try_read(buf: &mut &[u8]) -> Result<$T> {
-                    check_remaining(buf, std::mem::size_of::<$T>())?;
+                    check_remaining(buf, size_of::<$T>())?;
                     Ok(buf.[<get_ $T>]())
                 }
 
@@ -81,6 +94,51 @@ impl_ffi_converter_for_num_primitive!(i64, metadata::codes::TYPE_I64);
 impl_ffi_converter_for_num_primitive!(f32, metadata::codes::TYPE_F32);
 impl_ffi_converter_for_num_primitive!(f64, metadata::codes::TYPE_F64);
 
+/// Support for the `NonZero` integer types.
+///
+/// These are passed exactly like their integer type, so the bindings see a plain integer.  Zero
+/// fails to lift, which makes the call raise an exception in the foreign code like any other
+/// argument that can't be lifted.
+macro_rules! impl_ffi_converter_for_non_zero {
+    ($T:ty, $Int:ty) => {
+        unsafe impl<UT> FfiConverter<UT> for $T {
+            ffi_converter_default_return!(UT);
+
+            type FfiType = $Int;
+
+            fn lower(obj: $T) -> Self::FfiType {
+                obj.get()
+            }
+
+            fn try_lift(v: Self::FfiType) -> Result<$T> {
+                match <$T>::new(v) {
+                    Some(v) => Ok(v),
+                    None => bail!("{} must not be zero", stringify!($T)),
+                }
+            }
+
+            fn write(obj: $T, buf: &mut Vec<u8>) {
+                <$Int as FfiConverter<UT>>::write(obj.get(), buf);
+            }
+
+            fn try_read(buf: &mut &[u8]) -> Result<$T> {
+                <Self as FfiConverter<UT>>::try_lift(<$Int as FfiConverter<UT>>::try_read(buf)?)
+            }
+
+            const TYPE_ID_META: MetadataBuffer = <$Int as FfiConverter<UT>>::TYPE_ID_META;
+        }
+    };
+}
+
+impl_ffi_converter_for_non_zero!(NonZeroU8, u8);
+impl_ffi_converter_for_non_zero!(NonZeroI8, i8);
+impl_ffi_converter_for_non_zero!(NonZeroU16, u16);
+impl_ffi_converter_for_non_zero!(NonZeroI16, i16);
+impl_ffi_converter_for_non_zero!(NonZeroU32, u32);
+impl_ffi_converter_for_non_zero!(NonZeroI32, i32);
+impl_ffi_converter_for_non_zero!(NonZeroU64, u64);
+impl_ffi_converter_for_non_zero!(NonZeroI64, i64);
+
 /// Support for passing boolean values via the FFI.
 ///
 /// Booleans are passed as an `i8` in order to avoid problems with handling
@@ -114,6 +172,38 @@ unsafe impl<UT> FfiConverter<UT> for bool {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_BOOL);
 }
 
+/// Support for passing `char` values via the FFI.
+///
+/// Chars are passed as their `u32` Unicode scalar value, which is checked when lifting since the
+/// foreign code could pass any number, like an unpaired surrogate.
+unsafe impl<UT> FfiConverter<UT> for char {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = u32;
+
+    fn lower(obj: char) -> Self::FfiType {
+        u32::from(obj)
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<char> {
+        match char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!("{v:#x} is not a Unicode scalar value"),
+        }
+    }
+
+    fn write(obj: char, buf: &mut Vec<u8>) {
+        buf.put_u32(<Self as FfiConverter<UT>>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<char> {
+        check_remaining(buf, 4)?;
+        <Self as FfiConverter<UT>>::try_lift(buf.get_u32())
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_CHAR);
+}
+
 /// Support unit-type returns via the FFI.
 unsafe impl<UT> FfiConverter<UT> for () {
     // This actually isn't used, but we need to specify something
@@ -221,13 +311,13 @@ unsafe impl<UT> FfiConverter<UT> for String {
 
     fn try_read(buf: &mut &[u8]) -> Result<String> {
         check_remaining(buf, 4)?;
-        let len = usize::try_from(buf.get_i32())?;
+        let len = usize::try_from(buf.get_i32()).map_err(core_error)?;
         check_remaining(buf, len)?;
         // N.B: In the general case `Buf::chunk()` may return partial data.
         // But in the specific case of `<&[u8] as Buf>` it returns the full slice,
         // so there is no risk of having less than `len` bytes available here.
         let bytes = &buf.chunk()[..len];
-        let res = String::from_utf8(bytes.to_vec())?;
+        let res = String::from_utf8(bytes.to_vec()).map_err(core_error)?;
         buf.advance(len);
         Ok(res)
     }
@@ -249,6 +339,7 @@ unsafe impl<UT> FfiConverter<UT> for String {
 /// the sign of the seconds portion represents the direction of the offset
 /// overall. The sign of the seconds portion can then be used to determine
 /// if the total offset should be added to or subtracted from the unix epoch.
+#[cfg(feature = "std")]
 unsafe impl<UT> FfiConverter<UT> for SystemTime {
     ffi_converter_rust_buffer_lift_and_lower!(UT);
     ffi_converter_default_return!(UT);
@@ -312,6 +403,121 @@ unsafe impl<UT> FfiConverter<UT> for Duration {
     const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_DURATION);
 }
 
+/// Support for passing filesystem paths via the FFI.
+///
+/// Paths are written as an i32 length followed by the bytes of the path.  On Unix these are the
+/// raw bytes of the `OsStr`, so paths that aren't valid UTF-8 survive the trip.  Elsewhere the
+/// path is converted to UTF-8, replacing anything that can't be represented.
+#[cfg(feature = "std")]
+unsafe impl<UT> FfiConverter<UT> for PathBuf {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: PathBuf, buf: &mut Vec<u8>) {
+        let bytes = path_into_bytes(obj);
+        let len = i32::try_from(bytes.len()).unwrap();
+        buf.put_i32(len);
+        buf.put(bytes.as_slice());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<PathBuf> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32()).map_err(core_error)?;
+        check_remaining(buf, len)?;
+        let bytes = buf[..len].to_vec();
+        buf.advance(len);
+        path_from_bytes(bytes)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_PATH);
+}
+
+#[cfg(all(feature = "std", unix))]
+fn path_into_bytes(path: PathBuf) -> Vec<u8> {
+    use std::os::unix::ffi::OsStringExt;
+    path.into_os_string().into_vec()
+}
+
+#[cfg(all(feature = "std", not(unix)))]
+fn path_into_bytes(path: PathBuf) -> Vec<u8> {
+    path.to_string_lossy().into_owned().into_bytes()
+}
+
+#[cfg(all(feature = "std", unix))]
+fn path_from_bytes(bytes: Vec<u8>) -> Result<PathBuf> {
+    use std::os::unix::ffi::OsStringExt;
+    Ok(std::ffi::OsString::from_vec(bytes).into())
+}
+
+#[cfg(all(feature = "std", not(unix)))]
+fn path_from_bytes(bytes: Vec<u8>) -> Result<PathBuf> {
+    Ok(String::from_utf8(bytes)?.into())
+}
+
+/// Support for passing IP addresses via the FFI.
+///
+/// IP addresses are written as a u8 version, either 4 or 6, followed by the 4 or 16 octets of the
+/// address.
+#[cfg(feature = "std")]
+unsafe impl<UT> FfiConverter<UT> for IpAddr {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: IpAddr, buf: &mut Vec<u8>) {
+        match obj {
+            IpAddr::V4(addr) => {
+                buf.put_u8(4);
+                buf.put_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                buf.put_u8(6);
+                buf.put_slice(&addr.octets());
+            }
+        }
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<IpAddr> {
+        check_remaining(buf, 1)?;
+        Ok(match buf.get_u8() {
+            4 => {
+                check_remaining(buf, 4)?;
+                IpAddr::V4(Ipv4Addr::from(buf.get_u32()))
+            }
+            6 => {
+                check_remaining(buf, 16)?;
+                IpAddr::V6(Ipv6Addr::from(buf.get_u128()))
+            }
+            v => bail!("unexpected IP address version: {v}"),
+        })
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_IP_ADDR);
+}
+
+/// Support for passing socket addresses via the FFI.
+///
+/// Socket addresses are written as their IP address, followed by the u16 port.  The flow info and
+/// scope ID of IPv6 socket addresses aren't passed.
+#[cfg(feature = "std")]
+unsafe impl<UT> FfiConverter<UT> for SocketAddr {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: SocketAddr, buf: &mut Vec<u8>) {
+        <IpAddr as FfiConverter<UT>>::write(obj.ip(), buf);
+        buf.put_u16(obj.port());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<SocketAddr> {
+        let ip = <IpAddr as FfiConverter<UT>>::try_read(buf)?;
+        check_remaining(buf, 2)?;
+        Ok(SocketAddr::new(ip, buf.get_u16()))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SOCKET_ADDR);
+}
+
 /// Support for passing optional values via the FFI.
 ///
 /// Optional values are currently always passed by serializing to a buffer.
@@ -372,7 +578,7 @@ unsafe impl<UT, T: FfiConverter<UT>> FfiConverter<UT> for Vec<T> {
 
     fn try_read(buf: &mut &[u8]) -> Result<Vec<T>> {
         check_remaining(buf, 4)?;
-        let len = usize::try_from(buf.get_i32())?;
+        let len = usize::try_from(buf.get_i32()).map_err(core_error)?;
         let mut vec = Vec::with_capacity(len);
         for _ in 0..len {
             vec.push(<T as FfiConverter<UT>>::try_read(buf)?)
@@ -392,9 +598,10 @@ unsafe impl<UT, T: FfiConverter<UT>> FfiConverter<UT> for Vec<T> {
 /// We write a `i32` entries count followed by each entry (string
 /// key followed by the value) in turn.
 /// (It's a signed type due to limits of the JVM).
+#[cfg(feature = "std")]
 unsafe impl<K, V, UT> FfiConverter<UT> for HashMap<K, V>
 where
-    K: FfiConverter<UT> + std::hash::Hash + Eq,
+    K: FfiConverter<UT> + core::hash::Hash + Eq,
     V: FfiConverter<UT>,
 {
     ffi_converter_rust_buffer_lift_and_lower!(UT);
@@ -412,7 +619,7 @@ where
 
     fn try_read(buf: &mut &[u8]) -> Result<HashMap<K, V>> {
         check_remaining(buf, 4)?;
-        let len = usize::try_from(buf.get_i32())?;
+        let len = usize::try_from(buf.get_i32()).map_err(core_error)?;
         let mut map = HashMap::with_capacity(len);
         for _ in 0..len {
             let key = <K as FfiConverter<UT>>::try_read(buf)?;
@@ -442,7 +649,7 @@ unsafe impl<UT> FfiConverter<UT> for crate::ForeignExecutor {
 
     fn write(executor: Self, buf: &mut Vec<u8>) {
         // Use native endian when writing these values, so they can be casted to pointer values
-        match std::mem::size_of::<usize>() {
+        match size_of::<usize>() {
             // Use native endian when reading these values, so they can be casted to pointer values
             4 => buf.put_u32_ne(executor.handle.0 as u32),
             8 => buf.put_u64_ne(executor.handle.0 as u64),
@@ -455,7 +662,7 @@ unsafe impl<UT> FfiConverter<UT> for crate::ForeignExecutor {
     }
 
     fn try_read(buf: &mut &[u8]) -> Result<Self> {
-        let usize_val = match std::mem::size_of::<usize>() {
+        let usize_val = match size_of::<usize>() {
             // Use native endian when reading these values, so they can be casted to pointer values
             4 => buf.get_u32_ne() as usize,
             8 => buf.get_u64_ne() as usize,
@@ -506,6 +713,13 @@ where
         }
     }
 
+    fn write_return(v: Self, buf: &mut Vec<u8>) -> Result<(), RustBuffer> {
+        match v {
+            Ok(r) => R::write_return(r, buf),
+            Err(e) => Err(lower_into_rust_buffer(e)),
+        }
+    }
+
     fn lift_callback_return(buf: RustBuffer) -> Self {
         Ok(try_lift_from_rust_buffer::<R, UT>(buf)
             .expect("Error reading callback interface result"))
@@ -533,3 +747,70 @@ where
         .concat(R::TYPE_ID_META)
         .concat(E::TYPE_ID_META);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_path_roundtrip() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path = PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9"));
+        let lowered = <PathBuf as FfiConverter<UniFfiTag>>::lower(path.clone());
+        assert_eq!(
+            <PathBuf as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            path
+        );
+    }
+
+    #[test]
+    fn test_socket_addr_roundtrip() {
+        for addr in ["192.0.2.1:8080", "[2001:db8::1]:443"] {
+            let addr: SocketAddr = addr.parse().unwrap();
+            let lowered = <SocketAddr as FfiConverter<UniFfiTag>>::lower(addr);
+            assert_eq!(
+                <SocketAddr as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+                addr
+            );
+        }
+        let mut buf: &[u8] = &[5, 0, 0, 0, 0];
+        assert!(<IpAddr as FfiConverter<UniFfiTag>>::try_read(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_non_zero_lift() {
+        let lowered = <NonZeroU32 as FfiConverter<UniFfiTag>>::lower(NonZeroU32::new(7).unwrap());
+        assert_eq!(lowered, 7);
+        assert_eq!(
+            <NonZeroU32 as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            NonZeroU32::new(7).unwrap()
+        );
+        let err = <NonZeroU32 as FfiConverter<UniFfiTag>>::try_lift(0).unwrap_err();
+        assert_eq!(err.to_string(), "NonZeroU32 must not be zero");
+
+        let mut buf: &[u8] = &[0; 8];
+        assert!(<NonZeroI64 as FfiConverter<UniFfiTag>>::try_read(&mut buf).is_err());
+        // The bindings see a plain integer
+        assert_eq!(
+            <NonZeroI64 as FfiConverter<UniFfiTag>>::TYPE_ID_META.as_ref(),
+            <i64 as FfiConverter<UniFfiTag>>::TYPE_ID_META.as_ref()
+        );
+    }
+
+    #[test]
+    fn test_char_lift() {
+        for c in ['a', 'é', '\u{1f980}'] {
+            let lowered = <char as FfiConverter<UniFfiTag>>::lower(c);
+            assert_eq!(
+                <char as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+                c
+            );
+        }
+        // Surrogates and values past the last code point aren't chars
+        assert!(<char as FfiConverter<UniFfiTag>>::try_lift(0xd800).is_err());
+        assert!(<char as FfiConverter<UniFfiTag>>::try_lift(0x110000).is_err());
+    }
+}
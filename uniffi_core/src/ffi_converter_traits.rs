@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 
 use crate::{
     try_lift_from_rust_buffer, FfiDefault, MetadataBuffer, Result, RustBuffer, RustCallStatus,
@@ -101,6 +101,15 @@ pub unsafe trait FfiConverter<UT>: Sized {
     /// values of type Self::FfiType, this method is fallible.
     fn try_lift(v: Self::FfiType) -> Result<Self>;
 
+    /// Lift a value of an argument that's marked as sensitive
+    ///
+    /// Like `try_lift()`, but wipes any copy of the value that lifting leaves behind.  The default
+    /// is fine for values that are lifted in place, like primitives and strings.  Types that are
+    /// read out of a `RustBuffer` zero the buffer once they've been read.
+    fn try_lift_sensitive(v: Self::FfiType) -> Result<Self> {
+        Self::try_lift(v)
+    }
+
     /// Lift a Rust value for a callback interface method result
     fn lift_callback_return(buf: RustBuffer) -> Self {
         try_lift_from_rust_buffer(buf).expect("Error reading callback interface result")
@@ -137,6 +146,15 @@ pub unsafe trait FfiConverter<UT>: Sized {
     /// to the foreign language code via the RustBuffer.
     fn write(obj: Self, buf: &mut Vec<u8>);
 
+    /// Write a return value into a buffer, for a remote call
+    ///
+    /// Like [Self::lower_return], errors that should be thrown as exceptions are serialized into
+    /// `Err(buf)`.  See [crate::remote_call] for how remote calls are made.
+    fn write_return(obj: Self, buf: &mut Vec<u8>) -> Result<(), RustBuffer> {
+        Self::write(obj, buf);
+        Ok(())
+    }
+
     /// Read a rust value from a buffer, received over the FFI in serialized form.
     ///
     /// This trait method can be used for receiving data from the foreign language code in rust,
@@ -182,6 +200,16 @@ pub unsafe trait FfiConverterArc<UT> {
     fn lower(obj: Arc<Self>) -> Self::FfiType;
     fn lower_return(obj: Arc<Self>) -> Result<Self::ReturnType, RustBuffer>;
     fn try_lift(v: Self::FfiType) -> Result<Arc<Self>>;
+    /// Borrow the object behind a lowered value, without touching its reference count
+    ///
+    /// This is used for `&T` arguments and `&self` receivers, which only need the object for the
+    /// duration of the call.
+    ///
+    /// ## Safety
+    ///
+    /// `v` must have been obtained from `lower()` or `write()`, and the foreign code must keep its
+    /// reference alive for as long as the borrow is used.
+    unsafe fn lift_ref<'a>(v: Self::FfiType) -> &'a Self;
     fn lift_callback_return(buf: RustBuffer) -> Arc<Self> {
         try_lift_from_rust_buffer(buf).expect("Error reading callback interface result")
     }
@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Live handle diagnostics
+//!
+//! Foreign code that never frees an object, or that holds on to a callback interface that Rust
+//! never drops, leaks it for the rest of the process.  With the `handle-diagnostics` feature,
+//! UniFFI tracks the object handles that it passes to the foreign code, and the callback interface
+//! handles that Rust holds, along with a backtrace of where each handle was created.
+//!
+//! The bindings expose [live_handles_report] as `uniffiDebugLiveHandles()` in Kotlin and Swift,
+//! and `uniffi_debug_live_handles()` in Python.  Calling it in a long-running app shows which
+//! handles are still alive, and where they came from.
+//!
+//! Without the feature, tracking is a no-op and the report only says that it's disabled.
+
+use crate::{rust_call, RustBuffer, RustCallStatus};
+use alloc::string::String;
+#[cfg(feature = "handle-diagnostics")]
+use std::{backtrace::Backtrace, collections::BTreeMap, fmt::Write, sync::Mutex};
+
+// Backtraces for the live handles, keyed by type name and handle.  An object that's passed to the
+// foreign code multiple times gets the same handle each time, and a backtrace for each of them,
+// since the foreign code frees each one separately.
+#[cfg(feature = "handle-diagnostics")]
+static LIVE_HANDLES: Mutex<BTreeMap<(&'static str, u64), Vec<Backtrace>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Record that a handle was created
+///
+/// This is called by the generated code when an object is passed to the foreign code, and when
+/// Rust lifts a callback interface.  `type_name` is the name of the object or callback interface.
+#[doc(hidden)]
+pub fn track_handle(type_name: &'static str, handle: u64) {
+    #[cfg(not(feature = "handle-diagnostics"))]
+    let _ = (type_name, handle);
+    #[cfg(feature = "handle-diagnostics")]
+    {
+        let backtrace = Backtrace::force_capture();
+        LIVE_HANDLES
+            .lock()
+            .unwrap()
+            .entry((type_name, handle))
+            .or_default()
+            .push(backtrace);
+    }
+}
+
+/// Record that a handle recorded by [track_handle] was freed
+#[doc(hidden)]
+pub fn untrack_handle(type_name: &'static str, handle: u64) {
+    #[cfg(not(feature = "handle-diagnostics"))]
+    let _ = (type_name, handle);
+    #[cfg(feature = "handle-diagnostics")]
+    {
+        let mut live_handles = LIVE_HANDLES.lock().unwrap();
+        if let Some(backtraces) = live_handles.get_mut(&(type_name, handle)) {
+            backtraces.pop();
+            if backtraces.is_empty() {
+                live_handles.remove(&(type_name, handle));
+            }
+        }
+    }
+}
+
+/// List the live handles, with a backtrace of where each one was created
+///
+/// The report starts with a `{count} live handles` line.  Backtraces are only symbolized when
+/// the library has debug info.
+pub fn live_handles_report() -> String {
+    #[cfg(not(feature = "handle-diagnostics"))]
+    return String::from(
+        "handle diagnostics are disabled, enable the `handle-diagnostics` feature of uniffi",
+    );
+    #[cfg(feature = "handle-diagnostics")]
+    {
+        let live_handles = LIVE_HANDLES.lock().unwrap();
+        let count: usize = live_handles.values().map(Vec::len).sum();
+        let mut report = format!("{count} live handles\n");
+        for ((type_name, handle), backtraces) in live_handles.iter() {
+            for backtrace in backtraces {
+                let _ = write!(
+                    report,
+                    "\n{type_name} {handle:#x} created at:\n{backtrace}\n"
+                );
+            }
+        }
+        report
+    }
+}
+
+/// The scaffolding function behind `uniffiDebugLiveHandles()`, see `setup_scaffolding!`
+pub fn uniffi_debug_live_handles(call_status: &mut RustCallStatus) -> RustBuffer {
+    rust_call(call_status, || {
+        Ok(RustBuffer::from_vec(live_handles_report().into_bytes()))
+    })
+}
+
+#[cfg(all(test, feature = "handle-diagnostics"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_live_handles() {
+        track_handle("TestObject", 0x10);
+        track_handle("TestObject", 0x10);
+        track_handle("TestCallback", 1);
+        let report = live_handles_report();
+        assert_eq!(report.matches("TestObject 0x10 created at:").count(), 2);
+        assert!(report.contains("TestCallback 0x1 created at:"));
+
+        untrack_handle("TestObject", 0x10);
+        untrack_handle("TestCallback", 1);
+        let report = live_handles_report();
+        assert_eq!(report.matches("TestObject 0x10 created at:").count(), 1);
+        assert!(!report.contains("TestCallback"));
+
+        // Handles that weren't tracked are ignored
+        untrack_handle("TestCallback", 2);
+        untrack_handle("TestObject", 0x10);
+        assert!(!live_handles_report().contains("TestObject"));
+    }
+}
@@ -0,0 +1,171 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Handle tokens that outlive the process
+//!
+//! Objects exported with the `Restore` trait get a `serialize_handle()` method and a
+//! `restore_handle(token)` static function in the foreign bindings.  `serialize_handle()` saves
+//! the object's state with the [`HandleStore`] installed by [`set_handle_store`], and returns an
+//! opaque, signed token.  The token can be kept by the foreign code, for example in an Android
+//! `Bundle`, and passed to `restore_handle()` after the process was killed and restarted to get an
+//! equivalent object back.
+//!
+//! Tokens are deterministic: serializing two objects with the same state gives the same token, as
+//! long as the store's signatures are deterministic.  `restore_handle()` returns `None` for tokens
+//! that weren't signed by the store, or whose state the store no longer has.
+
+use std::sync::{Arc, RwLock};
+
+/// Objects that can be saved and restored with handle tokens
+///
+/// Implement this for objects exported with the `Restore` trait.
+pub trait Restorable: Sized {
+    /// Serialize the state needed to re-create this object
+    fn save_state(&self) -> Vec<u8>;
+
+    /// Re-create an object from the state returned by [Restorable::save_state]
+    ///
+    /// Return `None` if the state can't be used anymore, for example because it was written by an
+    /// older version of the app.
+    fn restore_state(state: &[u8]) -> Option<Self>;
+}
+
+/// Persistent storage for the state behind handle tokens
+///
+/// The store should keep the state somewhere that survives the process, like a file or a
+/// database, and should sign tokens with a key that only the app knows, so that the foreign code
+/// can't forge them.
+pub trait HandleStore: Send + Sync {
+    /// Store `state` under `key`, replacing any state previously stored under it
+    fn save(&self, key: &str, state: &[u8]);
+
+    /// Get the state stored under `key`
+    fn load(&self, key: &str) -> Option<Vec<u8>>;
+
+    /// Sign `key`, the signature is included in the token and checked when it's restored
+    fn sign(&self, key: &str) -> String;
+}
+
+static HANDLE_STORE: RwLock<Option<Arc<dyn HandleStore>>> = RwLock::new(None);
+
+/// Set the store used to save and restore handle tokens
+///
+/// This can be called multiple times, each call replaces the previously set store.
+pub fn set_handle_store(store: Arc<dyn HandleStore>) {
+    *HANDLE_STORE.write().unwrap() = Some(store);
+}
+
+fn handle_store() -> Arc<dyn HandleStore> {
+    HANDLE_STORE
+        .read()
+        .unwrap()
+        .clone()
+        .expect("no HandleStore set, call uniffi::set_handle_store() before using handle tokens")
+}
+
+/// Save `obj`'s state and return a token for it
+///
+/// This is called by the generated `serialize_handle()` method.  `type_name` is the name of the
+/// exported object, it's part of the token so that tokens for one type can't be used for another.
+///
+/// Panics if no store was set with [set_handle_store].
+pub fn serialize_handle<T: Restorable>(type_name: &str, obj: &T) -> String {
+    let store = handle_store();
+    let state = obj.save_state();
+    let key = format!("{type_name}-{:016x}", state_hash(type_name, &state));
+    store.save(&key, &state);
+    let signature = store.sign(&key);
+    format!("{key}.{signature}")
+}
+
+/// Re-create an object from a token returned by [serialize_handle]
+///
+/// This is called by the generated `restore_handle()` function.
+///
+/// Panics if no store was set with [set_handle_store].
+pub fn restore_handle<T: Restorable>(type_name: &str, token: &str) -> Option<Arc<T>> {
+    let store = handle_store();
+    let (key, signature) = token.split_once('.')?;
+    key.strip_prefix(type_name)?.strip_prefix('-')?;
+    if !constant_time_eq(store.sign(key).as_bytes(), signature.as_bytes()) {
+        return None;
+    }
+    let state = store.load(key)?;
+    T::restore_state(&state).map(Arc::new)
+}
+
+// 64-bit FNV-1a.  The standard library hashers aren't guaranteed to be stable between releases,
+// but the keys need to stay the same across app updates.
+fn state_hash(type_name: &str, state: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in type_name.bytes().chain([0]).chain(state.iter().copied()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+// Compare signatures without leaking how much of them matched through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u32);
+
+    impl Restorable for Counter {
+        fn save_state(&self) -> Vec<u8> {
+            self.0.to_be_bytes().to_vec()
+        }
+
+        fn restore_state(state: &[u8]) -> Option<Self> {
+            Some(Self(u32::from_be_bytes(state.try_into().ok()?)))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore(Mutex<HashMap<String, Vec<u8>>>);
+
+    impl HandleStore for MemoryStore {
+        fn save(&self, key: &str, state: &[u8]) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), state.to_vec());
+        }
+
+        fn load(&self, key: &str) -> Option<Vec<u8>> {
+            self.0.lock().unwrap().get(key).cloned()
+        }
+
+        fn sign(&self, key: &str) -> String {
+            format!("{:x}", state_hash("secret", key.as_bytes()))
+        }
+    }
+
+    #[test]
+    fn test_handle_tokens() {
+        set_handle_store(Arc::new(MemoryStore::default()));
+
+        let token = serialize_handle("Counter", &Counter(5));
+        assert_eq!(token, serialize_handle("Counter", &Counter(5)));
+        assert_ne!(token, serialize_handle("Counter", &Counter(6)));
+        assert_eq!(
+            restore_handle::<Counter>("Counter", &token).as_deref(),
+            Some(&Counter(5))
+        );
+
+        // Tokens are checked against the type name and the signature
+        assert!(restore_handle::<Counter>("Other", &token).is_none());
+        let (key, _) = token.split_once('.').unwrap();
+        assert!(restore_handle::<Counter>("Counter", &format!("{key}.0")).is_none());
+        assert!(restore_handle::<Counter>("Counter", "garbage").is_none());
+    }
+}
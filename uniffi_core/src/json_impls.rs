@@ -0,0 +1,71 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` impl for `serde_json::Value`
+//!
+//! Enabled by the `serde_json` feature.  Values are passed as JSON text, which every foreign
+//! language can parse with its standard JSON support.  Lifting fails if the text sent by the
+//! foreign code isn't valid JSON.
+
+use crate::{
+    ffi_converter_default_return, metadata, FfiConverter, MetadataBuffer, Result, RustBuffer,
+};
+use serde_json::Value;
+
+unsafe impl<UT> FfiConverter<UT> for Value {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Value) -> RustBuffer {
+        <String as FfiConverter<UT>>::lower(obj.to_string())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Value> {
+        Ok(serde_json::from_str(
+            &<String as FfiConverter<UT>>::try_lift(v)?,
+        )?)
+    }
+
+    fn write(obj: Value, buf: &mut Vec<u8>) {
+        <String as FfiConverter<UT>>::write(obj.to_string(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Value> {
+        Ok(serde_json::from_str(
+            &<String as FfiConverter<UT>>::try_read(buf)?,
+        )?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_JSON);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lower_into_rust_buffer, try_lift_from_rust_buffer, UniFfiTag};
+    use serde_json::json;
+
+    #[test]
+    fn test_json_roundtrip() {
+        let value = json!({"name": "uniffi", "tags": ["ffi", null], "stars": 1.5, "ok": true});
+        let lowered = <Value as FfiConverter<UniFfiTag>>::lower(value.clone());
+        assert_eq!(
+            <Value as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            value
+        );
+
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(value.clone());
+        assert_eq!(
+            try_lift_from_rust_buffer::<Value, UniFfiTag>(buf).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let lowered = <String as FfiConverter<UniFfiTag>>::lower("{not json".to_string());
+        assert!(<Value as FfiConverter<UniFfiTag>>::try_lift(lowered).is_err());
+    }
+}
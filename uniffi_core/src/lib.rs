@@ -29,42 +29,123 @@
 //!
 //! In addition to the core `FfiConverter` trait, we provide a handful of struct definitions useful
 //! for passing core rust types over the FFI, such as [`RustBuffer`].
+//!
+//! Without the default `std` feature, this crate is `no_std` and only needs `alloc`.  Panics then
+//! aren't caught, so they're handled by the crate's `#[panic_handler]`, and the types and
+//! features that need the standard library aren't available.
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(rust_2018_idioms, unused_qualifications)]
 
+pub extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
 use anyhow::bail;
 use bytes::buf::Buf;
 
 // Make Result<> public to support external impls of FfiConverter
 pub use anyhow::Result;
 
+mod async_runtime;
+#[cfg(feature = "std")]
+mod call_lock;
+#[cfg(feature = "chrono")]
+mod chrono_impls;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "rust_decimal")]
+mod decimal_impls;
 pub mod ffi;
 mod ffi_converter_impls;
 mod ffi_converter_traits;
+mod handle_diagnostics;
+#[cfg(feature = "std")]
+mod handles;
+#[cfg(feature = "serde_json")]
+mod json_impls;
+#[cfg(feature = "std")]
+mod logger;
+mod memlock;
 pub mod metadata;
-
+#[cfg(feature = "std")]
+mod progress;
+mod secret;
+mod shared;
+#[cfg(feature = "url")]
+mod url_impls;
+#[cfg(feature = "uuid")]
+mod uuid_impls;
+mod weak;
+
+pub use async_runtime::AsyncRuntime;
+#[cfg(feature = "tokio")]
+pub use async_runtime::TokioRuntime;
+#[cfg(feature = "std")]
+pub use call_lock::{lock_object_calls, ObjectCallGuard};
+#[cfg(feature = "compression")]
+pub use compression::{set_compression_threshold, Compressed};
 pub use ffi::*;
 pub use ffi_converter_traits::{FfiConverter, FfiConverterArc};
+pub use handle_diagnostics::{
+    live_handles_report, track_handle, uniffi_debug_live_handles, untrack_handle,
+};
+#[cfg(feature = "std")]
+pub use handles::{restore_handle, serialize_handle, set_handle_store, HandleStore, Restorable};
+#[cfg(feature = "std")]
+pub use logger::{install_logger, LogSink};
 pub use metadata::*;
+pub use panichook::{call_panic_hook, set_panic_hook, PanicHook};
+#[cfg(feature = "std")]
+pub use progress::ProgressSink;
+pub use secret::{Secret, Wipe};
+pub use shared::{set_shared_memory_threshold, Shared};
+pub use weak::{uniffi_weak_ref_clone, uniffi_weak_ref_free, uniffi_weak_ref_upgrade};
 
 // Re-export the libs that we use in the generated code,
 // so the consumer doesn't have to depend on them directly.
 pub mod deps {
+    pub use alloc;
     pub use anyhow;
     #[cfg(feature = "tokio")]
     pub use async_compat;
     pub use bytes;
     pub use log;
+    #[cfg(feature = "rust_decimal")]
+    pub use rust_decimal;
+    #[cfg(feature = "serde_json")]
+    pub use serde_json;
     pub use static_assertions;
+    #[cfg(feature = "url")]
+    pub use url;
+    #[cfg(feature = "uuid")]
+    pub use uuid;
 }
 
 mod panichook;
 
+/// `Send`, except on single-threaded WebAssembly
+///
+/// `wasm32` without the `atomics` target feature has a single thread, so the futures and tasks
+/// passed to the foreign executor don't need to be `Send` there.  This lets async functions await
+/// futures from `wasm-bindgen`, which aren't `Send`, while native targets still require it.
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+pub trait MaybeSend: Send {}
+
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// `Send`, except on single-threaded WebAssembly
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub trait MaybeSend {}
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+impl<T: ?Sized> MaybeSend for T {}
+
 const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 // For the significance of this magic number 10 here, and the reason that
 // it can't be a named constant, see the `check_compatible_version` function.
-static_assertions::const_assert!(PACKAGE_VERSION.as_bytes().len() < 10);
+static_assertions::const_assert!(PACKAGE_VERSION.len() < 10);
 
 /// Check whether the uniffi runtime version is compatible a given uniffi_bindgen version.
 ///
@@ -129,6 +210,20 @@ macro_rules! assert_compatible_version {
     };
 }
 
+/// The scaffolding function behind the contract check of the bindings, see `setup_scaffolding!`
+///
+/// Returns the version of UniFFI that the library was built with, followed by the contract
+/// version of its scaffolding, serialized as a `String` and a `u32`.  The bindings compare these
+/// to the versions they were generated from, so they can say which ones don't match.
+pub fn uniffi_contract_info(contract_version: u32, call_status: &mut RustCallStatus) -> RustBuffer {
+    rust_call(call_status, || {
+        let mut buf = Vec::new();
+        <String as FfiConverter<UniFfiTag>>::write(PACKAGE_VERSION.into(), &mut buf);
+        <u32 as FfiConverter<UniFfiTag>>::write(contract_version, &mut buf);
+        Ok(RustBuffer::from_vec(buf))
+    })
+}
+
 /// Struct to use when we want to lift/lower/serialize types inside the `uniffi` crate.
 struct UniFfiTag;
 
@@ -147,20 +242,46 @@ pub fn check_remaining(buf: &[u8], num_bytes: usize) -> Result<()> {
     Ok(())
 }
 
+/// Convert an error from `core` or `alloc`, like a failed integer or UTF-8 conversion, for `?`
+///
+/// With `std` the error is wrapped as usual.  Without it, only its message is kept, since
+/// `anyhow` can only wrap errors that implement `core::error::Error`, which needs Rust 1.81.
+#[cfg(feature = "std")]
+pub(crate) fn core_error(err: impl std::error::Error + Send + Sync + 'static) -> anyhow::Error {
+    err.into()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn core_error(err: impl core::fmt::Display) -> anyhow::Error {
+    anyhow::anyhow!("{err}")
+}
+
+/// Write a value with its size in bytes before it, as an `i32`.
+///
+/// This is used for the fields of `#[non_exhaustive]` enum variants, so that bindings generated
+/// before a variant was added can skip over it.
+pub fn write_with_length_prefix(buf: &mut Vec<u8>, write: impl FnOnce(&mut Vec<u8>)) {
+    let start = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    write(buf);
+    let len = i32::try_from(buf.len() - start - 4).expect("enum variant too large");
+    buf[start..start + 4].copy_from_slice(&len.to_be_bytes());
+}
+
 /// Helper function to lower an `anyhow::Error` that's wrapping an error type
 pub fn lower_anyhow_error_or_panic<UT, E>(err: anyhow::Error, arg_name: &str) -> RustBuffer
 where
-    E: 'static + FfiConverter<UT> + Sync + Send + std::fmt::Debug + std::fmt::Display,
+    E: 'static + FfiConverter<UT> + Sync + Send + core::fmt::Debug + core::fmt::Display,
 {
     match err.downcast::<E>() {
         Ok(actual_error) => lower_into_rust_buffer(actual_error),
-        Err(ohno) => panic!("Failed to convert arg '{arg_name}': {ohno}"),
+        Err(ohno) => lift_arg_failed(arg_name, ohno),
     }
 }
 
 /// Helper function to create a RustBuffer with a single value
 pub fn lower_into_rust_buffer<T: FfiConverter<UT>, UT>(obj: T) -> RustBuffer {
-    let mut buf = ::std::vec::Vec::new();
+    let mut buf = bufferpool::take(0);
     T::write(obj, &mut buf);
     RustBuffer::from_vec(buf)
 }
@@ -168,7 +289,86 @@ pub fn lower_into_rust_buffer<T: FfiConverter<UT>, UT>(obj: T) -> RustBuffer {
 /// Helper function to deserialize a RustBuffer with a single value
 pub fn try_lift_from_rust_buffer<T: FfiConverter<UT>, UT>(v: RustBuffer) -> Result<T> {
     let vec = v.destroy_into_vec();
-    let mut buf = vec.as_slice();
+    let result = try_read_whole_buffer(vec.as_slice());
+    bufferpool::recycle(vec);
+    result
+}
+
+/// Lift an argument of a scaffolding function, failing the call if it can't be lifted
+///
+/// The scaffolding calls this for each argument, so that the code that formats the error message
+/// is compiled once, rather than expanded for every argument.
+pub fn lift_arg<T: FfiConverter<UT>, UT>(v: T::FfiType, arg_name: &'static str) -> T {
+    match T::try_lift(v) {
+        Ok(v) => v,
+        Err(err) => lift_arg_failed(arg_name, err),
+    }
+}
+
+/// Like [lift_arg], for arguments marked as sensitive
+pub fn lift_sensitive_arg<T: FfiConverter<UT>, UT>(v: T::FfiType, arg_name: &'static str) -> T {
+    match T::try_lift_sensitive(v) {
+        Ok(v) => v,
+        Err(err) => lift_arg_failed(arg_name, err),
+    }
+}
+
+/// Fail a scaffolding call because an argument couldn't be lifted
+///
+/// This unwinds to `rust_call()`, which reports it with the `CALL_INVALID_ARGUMENT` status.  It
+/// doesn't panic, so the panic hook doesn't run.  Without `std` it panics, since the call can't
+/// be unwound.
+#[cold]
+#[inline(never)]
+pub fn lift_arg_failed(arg_name: &str, err: anyhow::Error) -> ! {
+    #[cfg(feature = "std")]
+    std::panic::resume_unwind(Box::new(InvalidArgument(format!(
+        "Failed to convert arg '{arg_name}': {err}"
+    ))));
+    #[cfg(not(feature = "std"))]
+    panic!("Failed to convert arg '{arg_name}': {err}")
+}
+
+/// The scaffolding function that compares two records that forward `Ord`
+///
+/// Both records are lifted, so they're consumed like any other argument.  `Ordering` is
+/// `repr(i8)`, with `Less` as -1 and `Greater` as 1.
+pub fn uniffi_record_cmp<T, UT>(
+    a: T::FfiType,
+    b: T::FfiType,
+    call_status: &mut RustCallStatus,
+) -> i8
+where
+    T: FfiConverter<UT> + Ord,
+    T::FfiType: core::panic::UnwindSafe,
+{
+    rust_call(call_status, || {
+        let a = lift_arg::<T, UT>(a, "a");
+        let b = lift_arg::<T, UT>(b, "b");
+        Ok(a.cmp(&b) as i8)
+    })
+}
+
+/// Lift a value from arbitrary data, for the fuzz targets generated by `uniffi-bindgen fuzz`
+///
+/// Malformed data must make lifting fail with an error, so the fuzzer reports any panic as a bug.
+pub fn fuzz_try_lift<T: FfiConverter<UT>, UT>(data: &[u8]) {
+    let _ = try_lift_from_rust_buffer::<T, UT>(RustBuffer::from_vec(data.to_vec()));
+}
+
+/// Like [try_lift_from_rust_buffer], but zeroes the buffer once the value has been read out
+///
+/// This is used for arguments marked as sensitive, so that secrets don't linger in freed
+/// memory after they've been copied into the lifted value.
+pub fn try_lift_sensitive_from_rust_buffer<T: FfiConverter<UT>, UT>(v: RustBuffer) -> Result<T> {
+    let mut vec = v.destroy_into_vec();
+    let result = try_read_whole_buffer(vec.as_slice());
+    wipe_vec(&mut vec);
+    bufferpool::recycle(vec);
+    result
+}
+
+fn try_read_whole_buffer<T: FfiConverter<UT>, UT>(mut buf: &[u8]) -> Result<T> {
     let value = T::try_read(&mut buf)?;
     match Buf::remaining(&buf) {
         0 => Ok(value),
@@ -176,6 +376,20 @@ pub fn try_lift_from_rust_buffer<T: FfiConverter<UT>, UT>(v: RustBuffer) -> Resu
     }
 }
 
+/// Overwrite the whole allocation of a `Vec<u8>` with zeroes
+///
+/// The writes are volatile so that the compiler can't elide them just because the vec is
+/// about to be dropped.
+fn wipe_vec(vec: &mut Vec<u8>) {
+    let ptr = vec.as_mut_ptr();
+    for i in 0..vec.capacity() {
+        // Safety: `ptr` points to an allocation of `capacity` bytes, and `u8` has no invalid
+        // bit patterns, so writing to the spare capacity is fine.
+        unsafe { core::ptr::write_volatile(ptr.add(i), 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
 /// Macro to implement returning values by simply lowering them and returning them
 ///
 /// This is what we use for all FfiConverters except for `Result`.  This would be nicer as a
@@ -186,7 +400,7 @@ macro_rules! ffi_converter_default_return {
         type ReturnType = <Self as $crate::FfiConverter<$uniffi_tag>>::FfiType;
         type FutureCallback = $crate::FutureCallback<Self::ReturnType>;
 
-        fn lower_return(v: Self) -> ::std::result::Result<Self::FfiType, $crate::RustBuffer> {
+        fn lower_return(v: Self) -> ::core::result::Result<Self::FfiType, $crate::RustBuffer> {
             Ok(<Self as $crate::FfiConverter<$uniffi_tag>>::lower(v))
         }
 
@@ -220,6 +434,10 @@ macro_rules! ffi_converter_rust_buffer_lift_and_lower {
         fn try_lift(buf: $crate::RustBuffer) -> $crate::Result<Self> {
             $crate::try_lift_from_rust_buffer::<Self, $uniffi_tag>(buf)
         }
+
+        fn try_lift_sensitive(buf: $crate::RustBuffer) -> $crate::Result<Self> {
+            $crate::try_lift_sensitive_from_rust_buffer::<Self, $uniffi_tag>(buf)
+        }
     };
 }
 
@@ -235,7 +453,10 @@ macro_rules! ffi_converter_forward {
             Self,
             $T,
             $existing_impl_tag,
-            $new_impl_tag
+            $new_impl_tag,
+            fn try_lift_sensitive(v: Self::FfiType) -> $crate::Result<Self> {
+                <$T as $crate::FfiConverter<$existing_impl_tag>>::try_lift_sensitive(v)
+            }
         );
     };
 }
@@ -248,10 +469,13 @@ macro_rules! ffi_converter_arc_forward {
     ($T:ty, $existing_impl_tag:ty, $new_impl_tag:ty) => {
         ::uniffi::do_ffi_converter_forward!(
             FfiConverterArc,
-            ::std::sync::Arc<Self>,
+            $crate::deps::alloc::sync::Arc<Self>,
             $T,
             $existing_impl_tag,
-            $new_impl_tag
+            $new_impl_tag,
+            unsafe fn lift_ref<'a>(v: Self::FfiType) -> &'a Self {
+                <$T as $crate::FfiConverterArc<$existing_impl_tag>>::lift_ref(v)
+            }
         );
     };
 }
@@ -260,7 +484,7 @@ macro_rules! ffi_converter_arc_forward {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! do_ffi_converter_forward {
-    ($trait:ident, $rust_type:ty, $T:ty, $existing_impl_tag:ty, $new_impl_tag:ty) => {
+    ($trait:ident, $rust_type:ty, $T:ty, $existing_impl_tag:ty, $new_impl_tag:ty $(, $($extra:tt)*)?) => {
         unsafe impl $crate::$trait<$new_impl_tag> for $T {
             type FfiType = <$T as $crate::$trait<$existing_impl_tag>>::FfiType;
             type ReturnType = <$T as $crate::$trait<$existing_impl_tag>>::FfiType;
@@ -272,7 +496,7 @@ macro_rules! do_ffi_converter_forward {
 
             fn lower_return(
                 v: $rust_type,
-            ) -> ::std::result::Result<Self::ReturnType, $crate::RustBuffer> {
+            ) -> ::core::result::Result<Self::ReturnType, $crate::RustBuffer> {
                 <$T as $crate::$trait<$existing_impl_tag>>::lower_return(v)
             }
 
@@ -280,7 +504,7 @@ macro_rules! do_ffi_converter_forward {
                 <$T as $crate::$trait<$existing_impl_tag>>::try_lift(v)
             }
 
-            fn write(obj: $rust_type, buf: &mut Vec<u8>) {
+            fn write(obj: $rust_type, buf: &mut $crate::deps::alloc::vec::Vec<u8>) {
                 <$T as $crate::$trait<$existing_impl_tag>>::write(obj, buf)
             }
 
@@ -304,6 +528,8 @@ macro_rules! do_ffi_converter_forward {
 
             const TYPE_ID_META: ::uniffi::MetadataBuffer =
                 <$T as $crate::$trait<$existing_impl_tag>>::TYPE_ID_META;
+
+            $($($extra)*)?
         }
     };
 }
@@ -337,4 +563,56 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn length_prefix() {
+        let mut buf = vec![1];
+        super::write_with_length_prefix(&mut buf, |buf| buf.extend_from_slice(&[2, 3, 4]));
+        assert_eq!(buf, vec![1, 0, 0, 0, 3, 2, 3, 4]);
+    }
+
+    #[test]
+    fn sensitive_lift_reads_value() {
+        let expected = vec!["secret".to_string(), "passphrase".to_string()];
+        let buf = super::lower_into_rust_buffer::<_, UniFfiTag>(expected.clone());
+        let result: Vec<String> =
+            super::try_lift_sensitive_from_rust_buffer::<_, UniFfiTag>(buf).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn wipe_vec_zeroes_spare_capacity() {
+        let mut vec = Vec::with_capacity(16);
+        vec.extend_from_slice(b"hunter2");
+        let capacity = vec.capacity();
+        super::wipe_vec(&mut vec);
+        // Safety: `wipe_vec` initialized the whole allocation
+        unsafe { vec.set_len(capacity) };
+        assert!(vec.iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn contract_info_has_versions() {
+        let mut call_status = super::RustCallStatus::default();
+        let buf = super::uniffi_contract_info(23, &mut call_status).destroy_into_vec();
+        let mut buf = buf.as_slice();
+        let version = <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            <u32 as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap(),
+            23
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn lift_arg_fails_with_arg_name() {
+        assert!(super::lift_arg::<bool, UniFfiTag>(1, "flag"));
+        let err =
+            std::panic::catch_unwind(|| super::lift_arg::<bool, UniFfiTag>(2, "flag")).unwrap_err();
+        let invalid = err
+            .downcast_ref::<super::ffi::rustcalls::InvalidArgument>()
+            .unwrap();
+        assert!(invalid.0.starts_with("Failed to convert arg 'flag': "));
+    }
 }
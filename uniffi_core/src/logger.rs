@@ -0,0 +1,206 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Forward Rust log records to the foreign code
+//!
+//! This module implements an opt-in bridge between the [`log`] crate and a foreign-implemented
+//! logger:
+//!
+//!   - [`install_logger`] installs a `log::Log` implementation that forwards all records to a
+//!     [`LogSink`].
+//!   - [`export_logger!`](crate::export_logger) exports a `Logger` callback interface, a
+//!     `LogLevel` enum and an `install_logger` function, so that the foreign code can implement
+//!     the sink itself (for example to forward records to Logcat, `os_log` or Python's `logging`
+//!     module).
+//!
+//! `tracing` events are forwarded too, as long as `tracing`'s `log` feature is enabled.
+
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Receives the log records forwarded by the logger installed with [`install_logger`]
+pub trait LogSink: Send + Sync {
+    fn log(&self, record: &log::Record<'_>);
+}
+
+// `log::Log` implementation that forwards records to the currently installed `LogSink`
+struct ForwardingLogger(RwLock<Option<Arc<dyn LogSink>>>);
+
+static FORWARDING_LOGGER: ForwardingLogger = ForwardingLogger(RwLock::new(None));
+
+impl log::Log for ForwardingLogger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        // Clone the sink, so that the lock isn't held while it's called.  Otherwise a sink that logs
+        // or installs another sink itself would deadlock.
+        let sink = self.0.read().unwrap().clone();
+        if let Some(sink) = sink {
+            sink.log(record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Forward all `log` records up to `max_level` to `sink`
+///
+/// This can be called multiple times, each call replaces the previously installed sink.
+///
+/// Fails if a different logger was already installed with `log::set_logger()`.
+pub fn install_logger(
+    sink: Box<dyn LogSink>,
+    max_level: log::LevelFilter,
+) -> Result<(), log::SetLoggerError> {
+    static INSTALLED: Mutex<bool> = Mutex::new(false);
+    let mut installed = INSTALLED.lock().unwrap();
+    if !*installed {
+        log::set_logger(&FORWARDING_LOGGER)?;
+        *installed = true;
+    }
+    drop(installed);
+    *FORWARDING_LOGGER.0.write().unwrap() = Some(sink.into());
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+/// Export a logging API for the foreign code
+///
+/// This defines and exports the following items in the calling crate:
+///
+///   - `LogLevel`: an enum with the `Error`, `Warn`, `Info`, `Debug` and `Trace` variants.
+///   - `Logger`: a callback interface with a single
+///     `log(level: LogLevel, target: String, message: String)` method.
+///   - `LoggerError`: the error returned by `install_logger`.
+///   - `install_logger(logger: Box<dyn Logger>, max_level: LogLevel)`: forwards all Rust log
+///     records up to `max_level` to `logger`.  It fails with `LoggerError::OtherLoggerInstalled`
+///     if a different logger was already installed with `log::set_logger()`.
+///
+/// The calling crate must also call `uniffi::setup_scaffolding!()` or
+/// `uniffi::include_scaffolding!()`.
+#[macro_export]
+macro_rules! export_logger {
+    () => {
+        #[derive(::std::clone::Clone, ::std::marker::Copy, ::std::fmt::Debug, ::uniffi::Enum)]
+        pub enum LogLevel {
+            Error,
+            Warn,
+            Info,
+            Debug,
+            Trace,
+        }
+
+        impl ::std::convert::From<$crate::deps::log::Level> for LogLevel {
+            fn from(level: $crate::deps::log::Level) -> Self {
+                match level {
+                    $crate::deps::log::Level::Error => Self::Error,
+                    $crate::deps::log::Level::Warn => Self::Warn,
+                    $crate::deps::log::Level::Info => Self::Info,
+                    $crate::deps::log::Level::Debug => Self::Debug,
+                    $crate::deps::log::Level::Trace => Self::Trace,
+                }
+            }
+        }
+
+        impl ::std::convert::From<LogLevel> for $crate::deps::log::LevelFilter {
+            fn from(level: LogLevel) -> Self {
+                match level {
+                    LogLevel::Error => Self::Error,
+                    LogLevel::Warn => Self::Warn,
+                    LogLevel::Info => Self::Info,
+                    LogLevel::Debug => Self::Debug,
+                    LogLevel::Trace => Self::Trace,
+                }
+            }
+        }
+
+        #[::uniffi::export(callback_interface)]
+        pub trait Logger: ::std::marker::Send + ::std::marker::Sync {
+            // A failing logger shouldn't be able to crash the Rust code
+            #[::uniffi::on_error(())]
+            fn log(
+                &self,
+                level: LogLevel,
+                target: ::std::string::String,
+                message: ::std::string::String,
+            );
+        }
+
+        #[derive(::std::fmt::Debug, ::uniffi::Error)]
+        pub enum LoggerError {
+            OtherLoggerInstalled,
+        }
+
+        struct UniffiForeignLogSink(::std::boxed::Box<dyn Logger>);
+
+        impl $crate::LogSink for UniffiForeignLogSink {
+            fn log(&self, record: &$crate::deps::log::Record<'_>) {
+                self.0.log(
+                    record.level().into(),
+                    record.target().to_owned(),
+                    record.args().to_string(),
+                );
+            }
+        }
+
+        #[::uniffi::export]
+        pub fn install_logger(
+            logger: ::std::boxed::Box<dyn Logger>,
+            max_level: LogLevel,
+        ) -> ::std::result::Result<(), LoggerError> {
+            $crate::install_logger(
+                ::std::boxed::Box::new(UniffiForeignLogSink(logger)),
+                max_level.into(),
+            )
+            .map_err(|_| LoggerError::OtherLoggerInstalled)
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Installs another sink from its `log()` method, which deadlocked when the lock was held while
+    // calling the sink
+    struct ReinstallingSink(Arc<Mutex<Vec<String>>>);
+
+    impl LogSink for ReinstallingSink {
+        fn log(&self, record: &log::Record<'_>) {
+            // Other tests may log in parallel
+            if record.target() == module_path!() {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+            install_logger(
+                Box::new(ReinstallingSink(self.0.clone())),
+                log::LevelFilter::Warn,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_install_logger() {
+        let messages = Arc::new(Mutex::new(Vec::new()));
+        install_logger(
+            Box::new(ReinstallingSink(messages.clone())),
+            log::LevelFilter::Warn,
+        )
+        .unwrap();
+        // Installing it again isn't an error
+        install_logger(
+            Box::new(ReinstallingSink(messages.clone())),
+            log::LevelFilter::Warn,
+        )
+        .unwrap();
+        log::warn!("first");
+        log::warn!("second");
+        log::info!("ignored");
+        assert_eq!(*messages.lock().unwrap(), ["first", "second"]);
+    }
+}
@@ -0,0 +1,183 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Locking the memory of [crate::Secret]s into RAM
+//!
+//! The pages of each secret's allocation are locked with `mlock()` on Unix and `VirtualLock()` on
+//! Windows, so that they're never written to swap.  The lock is per page and doesn't nest, while
+//! several small allocations can share a page, so each page is counted and only unlocked once the
+//! last secret in it is gone.
+//!
+//! Locking is best effort.  It fails once the process is over its limit of locked memory
+//! (`RLIMIT_MEMLOCK` on Unix, the minimum working set size on Windows), and the secret is then used
+//! without it.  Without the `std` feature, or on other platforms, memory isn't locked at all.
+
+#[cfg(all(feature = "std", any(unix, windows)))]
+pub use imp::{lock, unlock};
+
+#[cfg(not(all(feature = "std", any(unix, windows))))]
+pub fn lock(_ptr: *const u8, _len: usize) {}
+
+#[cfg(not(all(feature = "std", any(unix, windows))))]
+pub fn unlock(_ptr: *const u8, _len: usize) {}
+
+#[cfg(all(feature = "std", any(unix, windows)))]
+mod imp {
+    use super::sys;
+    use std::{
+        collections::BTreeMap,
+        sync::{Mutex, PoisonError},
+    };
+
+    static PAGES: Mutex<LockedPages> = Mutex::new(LockedPages::new());
+
+    /// Lock the pages of `len` bytes at `ptr`
+    pub fn lock(ptr: *const u8, len: usize) {
+        let page_size = sys::page_size();
+        let mut pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+        pages.lock(ptr as usize, len, page_size, |page| {
+            // Safety: the page belongs to a live allocation, locking it doesn't change its
+            // contents.
+            unsafe { sys::lock(page, page_size) }
+        });
+    }
+
+    /// Undo a call to [lock] with the same arguments, before the memory is freed
+    pub fn unlock(ptr: *const u8, len: usize) {
+        let page_size = sys::page_size();
+        let mut pages = PAGES.lock().unwrap_or_else(PoisonError::into_inner);
+        pages.unlock(ptr as usize, len, page_size, |page| {
+            // Safety: see `lock()`
+            unsafe { sys::unlock(page, page_size) }
+        });
+    }
+
+    // Number of locked allocations in each page, by page address.
+    pub(super) struct LockedPages(pub(super) BTreeMap<usize, usize>);
+
+    impl LockedPages {
+        pub(super) const fn new() -> Self {
+            Self(BTreeMap::new())
+        }
+
+        // Count an allocation in each of its pages, calling `lock_page` for the ones that weren't
+        // locked yet.
+        pub(super) fn lock(
+            &mut self,
+            addr: usize,
+            len: usize,
+            page_size: usize,
+            mut lock_page: impl FnMut(usize),
+        ) {
+            for page in pages(addr, len, page_size) {
+                let count = self.0.entry(page).or_insert(0);
+                if *count == 0 {
+                    lock_page(page);
+                }
+                *count += 1;
+            }
+        }
+
+        // Remove an allocation from the count of each of its pages, calling `unlock_page` for the
+        // ones that no longer hold any.
+        pub(super) fn unlock(
+            &mut self,
+            addr: usize,
+            len: usize,
+            page_size: usize,
+            mut unlock_page: impl FnMut(usize),
+        ) {
+            for page in pages(addr, len, page_size) {
+                if let Some(count) = self.0.get_mut(&page) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.0.remove(&page);
+                        unlock_page(page);
+                    }
+                }
+            }
+        }
+    }
+
+    fn pages(addr: usize, len: usize, page_size: usize) -> impl Iterator<Item = usize> {
+        let first = addr & !(page_size - 1);
+        let end = if len == 0 { first } else { addr + len };
+        (first..end).step_by(page_size)
+    }
+}
+
+#[cfg(all(feature = "std", unix))]
+mod sys {
+    pub fn page_size() -> usize {
+        // Safety: `sysconf()` has no preconditions
+        match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+            size if size > 0 => size as usize,
+            _ => 4096,
+        }
+    }
+
+    // Failures are ignored, see the module docs.
+    pub unsafe fn lock(page: usize, page_size: usize) {
+        libc::mlock(page as *const libc::c_void, page_size);
+    }
+
+    pub unsafe fn unlock(page: usize, page_size: usize) {
+        libc::munlock(page as *const libc::c_void, page_size);
+    }
+}
+
+#[cfg(all(feature = "std", windows))]
+mod sys {
+    use core::ffi::c_void;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn VirtualLock(address: *const c_void, size: usize) -> i32;
+        fn VirtualUnlock(address: *const c_void, size: usize) -> i32;
+    }
+
+    // Every architecture that Windows runs on uses 4 KiB pages.
+    pub fn page_size() -> usize {
+        4096
+    }
+
+    // Failures are ignored, see the module docs.
+    pub unsafe fn lock(page: usize, page_size: usize) {
+        VirtualLock(page as *const c_void, page_size);
+    }
+
+    pub unsafe fn unlock(page: usize, page_size: usize) {
+        VirtualUnlock(page as *const c_void, page_size);
+    }
+}
+
+#[cfg(all(test, feature = "std", any(unix, windows)))]
+mod test {
+    use super::{imp::LockedPages, *};
+
+    #[test]
+    fn test_pages_are_counted() {
+        let mut pages = LockedPages::new();
+        let mut locked = Vec::new();
+        // Two allocations sharing the page at 0x1000, the second one spans two pages
+        pages.lock(0x1010, 0x10, 0x1000, |page| locked.push(page));
+        pages.lock(0x1ff0, 0x20, 0x1000, |page| locked.push(page));
+        pages.lock(0x3000, 0, 0x1000, |page| locked.push(page));
+        assert_eq!(locked, vec![0x1000, 0x2000]);
+
+        let mut unlocked = Vec::new();
+        pages.unlock(0x1ff0, 0x20, 0x1000, |page| unlocked.push(page));
+        assert_eq!(unlocked, vec![0x2000]);
+        pages.unlock(0x1010, 0x10, 0x1000, |page| unlocked.push(page));
+        assert_eq!(unlocked, vec![0x2000, 0x1000]);
+        assert!(pages.0.is_empty());
+    }
+
+    #[test]
+    fn test_lock_unlock() {
+        let secret = vec![1u8; 10000];
+        lock(secret.as_ptr(), secret.capacity());
+        unlock(secret.as_ptr(), secret.capacity());
+    }
+}
@@ -1,34 +1,160 @@
-/// Initialize our panic handling hook to optionally log panics
-#[cfg(feature = "log_panics")]
+use core::fmt;
+use core::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "std")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::panic;
+#[cfg(feature = "std")]
+use std::sync::Once;
+
+#[cfg(feature = "std")]
+thread_local! {
+    // Backtrace captured by our panic hook for the most recent panic on this thread.
+    static LAST_PANIC_BACKTRACE: RefCell<Option<Backtrace>> = const { RefCell::new(None) };
+}
+
+/// A function that's called with each panic, see [`set_panic_hook()`]
+///
+/// It gets the description of the panic, with its location and message, which is the `PanicInfo`
+/// of the panic handler or panic hook.  That's a different type with and without `std` on recent
+/// compilers, but both display the same way.
+pub type PanicHook = fn(&dyn fmt::Display);
+
+// The `PanicHook` set by `set_panic_hook()`, as a pointer since there's no atomic function pointer.
+static PANIC_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Set a function that's called with each panic, for example to log it or to record it in a crash
+/// report
+///
+/// With the `std` feature, this installs a panic hook that calls `hook`, before the panic is
+/// raised as an exception in the foreign code.  Without `std`, panics can't be caught, and the
+/// `#[panic_handler]` of the component must call [`call_panic_hook()`] before it stops.
+///
+/// Setting a hook replaces the previous one.
+pub fn set_panic_hook(hook: PanicHook) {
+    PANIC_HOOK.store(hook as *mut (), Ordering::Release);
+    #[cfg(feature = "std")]
+    install_hook();
+}
+
+/// Call the hook set by [`set_panic_hook()`], if there is one
+///
+/// This is for the `#[panic_handler]` of `no_std` components, UniFFI calls it itself with `std`:
+///
+/// ```ignore
+/// #[panic_handler]
+/// fn panic(info: &core::panic::PanicInfo<'_>) -> ! {
+///     uniffi::call_panic_hook(info);
+///     loop {}
+/// }
+/// ```
+pub fn call_panic_hook(info: &dyn fmt::Display) {
+    let hook = PANIC_HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        // Safety: the pointer was stored by `set_panic_hook()`, from a `PanicHook`
+        let hook = unsafe { core::mem::transmute::<*mut (), PanicHook>(hook) };
+        hook(info);
+    }
+}
+
+/// Initialize our panic handling hook
+///
+/// The hook is process-wide, and it's called for every panic, including the ones that have
+/// nothing to do with UniFFI, so it's only installed when it's needed: when backtraces are enabled
+/// by `RUST_BACKTRACE` (or `RUST_LIB_BACKTRACE`), when the `log_panics` feature is enabled, or
+/// when `set_panic_hook()` is called.
+#[cfg(feature = "std")]
 pub fn ensure_setup() {
-    use std::sync::Once;
     static INIT_BACKTRACES: Once = Once::new();
     INIT_BACKTRACES.call_once(move || {
-        #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
+        // wasm32 doesn't have environment variables, `set_var()` panics there
+        #[cfg(all(
+            feature = "log_backtraces",
+            not(target_os = "android"),
+            not(target_family = "wasm")
+        ))]
         {
             std::env::set_var("RUST_BACKTRACE", "1");
         }
-        // Turn on a panic hook which logs both backtraces and the panic
-        // "Location" (file/line). We do both in case we've been stripped,
-        // ).
-        std::panic::set_hook(Box::new(move |panic_info| {
-            let (file, line) = if let Some(loc) = panic_info.location() {
-                (loc.file(), loc.line())
-            } else {
-                // Apparently this won't happen but rust has reserved the
-                // ability to start returning None from location in some cases
-                // in the future.
-                ("<unknown>", 0)
-            };
-            log::error!("### Rust `panic!` hit at file '{file}', line {line}");
-            #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
-            {
-                log::error!("  Complete stack trace:\n{:?}", backtrace::Backtrace::new());
+        if cfg!(feature = "log_panics") || backtraces_enabled() {
+            install_hook();
+        }
+    });
+}
+
+// Whether `Backtrace::capture()` captures backtraces, without capturing one to find out.
+#[cfg(feature = "std")]
+fn backtraces_enabled() -> bool {
+    match std::env::var_os("RUST_LIB_BACKTRACE").or_else(|| std::env::var_os("RUST_BACKTRACE")) {
+        Some(value) => value != "0",
+        None => false,
+    }
+}
+
+// Install the hook that calls the one set by `set_panic_hook()`, and records a backtrace for each
+// panic, which `rust_call()` forwards to the foreign side.  If the `log_panics` feature is enabled
+// we also log the panic location.  Then we call the previously installed hook, so that the hook of
+// the application isn't lost.
+#[cfg(feature = "std")]
+fn install_hook() {
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(move || {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |panic_info| {
+            call_panic_hook(panic_info);
+            let backtrace = Backtrace::capture();
+            if backtrace.status() == BacktraceStatus::Captured {
+                LAST_PANIC_BACKTRACE.with(|bt| *bt.borrow_mut() = Some(backtrace));
             }
+            #[cfg(feature = "log_panics")]
+            log_panic(panic_info.location());
+            previous_hook(panic_info);
         }));
     });
 }
 
-/// Initialize our panic handling hook to optionally log panics
-#[cfg(not(feature = "log_panics"))]
-pub fn ensure_setup() {}
+/// Take the backtrace recorded for the last panic on this thread, if there was one.
+#[cfg(feature = "std")]
+pub fn take_backtrace() -> Option<Backtrace> {
+    LAST_PANIC_BACKTRACE.with(|bt| bt.borrow_mut().take())
+}
+
+// Log both backtraces and the panic "Location" (file/line). We do both in case we've been
+// stripped.
+#[cfg(feature = "log_panics")]
+fn log_panic(location: Option<&panic::Location<'_>>) {
+    let (file, line) = if let Some(loc) = location {
+        (loc.file(), loc.line())
+    } else {
+        // Apparently this won't happen but rust has reserved the
+        // ability to start returning None from location in some cases
+        // in the future.
+        ("<unknown>", 0)
+    };
+    log::error!("### Rust `panic!` hit at file '{file}', line {line}");
+    #[cfg(all(feature = "log_backtraces", not(target_os = "android")))]
+    {
+        log::error!("  Complete stack trace:\n{}", Backtrace::force_capture());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    static PANICS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record_panic(info: &dyn fmt::Display) {
+        PANICS.lock().unwrap().push(info.to_string());
+    }
+
+    #[test]
+    fn test_panic_hook() {
+        set_panic_hook(record_panic);
+        call_panic_hook(&"boom");
+        assert!(PANICS.lock().unwrap().contains(&"boom".to_string()));
+    }
+}
@@ -0,0 +1,152 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Progress reporting for long-running functions
+//!
+//! An exported function, method or constructor can take a [`ProgressSink`] argument to report its
+//! progress.  The foreign code passes a plain closure that receives the number of completed steps
+//! and the total, like any closure argument, and the scaffolding wraps it in the sink:
+//!
+//! ```ignore
+//! #[uniffi::export]
+//! async fn download(url: String, progress: uniffi::ProgressSink) -> Vec<u8> {
+//!     // ...
+//!     progress.report(received, content_length);
+//!     // ...
+//! }
+//! ```
+//!
+//! Updates are throttled, so that functions can report every step without calling into the
+//! foreign code for each one: an update is dropped when the previous one was forwarded less than
+//! [`ProgressSink::DEFAULT_INTERVAL`] ago, unless it's the last one.  A `total` of 0 means that
+//! the total isn't known, and those updates are always throttled.
+
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Receives the progress of a long-running function, see the module docs
+pub struct ProgressSink {
+    callback: Box<dyn Fn(u64, u64) + Send + Sync>,
+    min_interval: Duration,
+    // When the last update was forwarded
+    last_report: Mutex<Option<Instant>>,
+}
+
+impl ProgressSink {
+    /// The minimum time between two forwarded updates, unless it's changed with
+    /// [`Self::with_min_interval`]
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Forward progress updates to `callback`
+    ///
+    /// The scaffolding calls this with the foreign closure.  Rust code can call it too, for
+    /// example to call an exported function from Rust tests.
+    pub fn new(callback: impl Fn(u64, u64) + Send + Sync + 'static) -> Self {
+        Self {
+            callback: Box::new(callback),
+            min_interval: Self::DEFAULT_INTERVAL,
+            last_report: Mutex::new(None),
+        }
+    }
+
+    /// Change the minimum time between two forwarded updates, `Duration::ZERO` forwards all of
+    /// them
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Report that `completed` out of `total` steps are done
+    ///
+    /// The update is dropped if the previous one was forwarded too recently, unless `completed`
+    /// reached a non-zero `total`, so that the foreign code always sees the end.
+    pub fn report(&self, completed: u64, total: u64) {
+        self.report_at(completed, total, Instant::now())
+    }
+
+    // `report()` at the time `now`, which the tests pass rather than waiting
+    fn report_at(&self, completed: u64, total: u64, now: Instant) {
+        {
+            let mut last_report = self.last_report.lock().unwrap();
+            let throttled = matches!(
+                *last_report,
+                Some(last) if now.duration_since(last) < self.min_interval
+            );
+            let is_last = total > 0 && completed >= total;
+            if throttled && !is_last {
+                return;
+            }
+            *last_report = Some(now);
+        }
+        // The lock isn't held while calling the foreign code, which may take a while
+        (self.callback)(completed, total);
+    }
+}
+
+impl fmt::Debug for ProgressSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressSink")
+            .field("min_interval", &self.min_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Arc;
+
+    type Reports = Arc<Mutex<Vec<(u64, u64)>>>;
+
+    fn recording_sink() -> (ProgressSink, Reports) {
+        let reports = Arc::new(Mutex::new(vec![]));
+        let sink_reports = Arc::clone(&reports);
+        let sink = ProgressSink::new(move |completed, total| {
+            sink_reports.lock().unwrap().push((completed, total))
+        });
+        (sink, reports)
+    }
+
+    #[test]
+    fn test_throttling() {
+        let (sink, reports) = recording_sink();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        sink.report_at(0, 10, at(0));
+        sink.report_at(1, 10, at(50));
+        sink.report_at(2, 10, at(100));
+        sink.report_at(3, 10, at(150));
+        // The last update is forwarded even if it's too soon
+        sink.report_at(10, 10, at(160));
+        assert_eq!(*reports.lock().unwrap(), vec![(0, 10), (2, 10), (10, 10)]);
+    }
+
+    #[test]
+    fn test_throttling_unknown_total() {
+        let (sink, reports) = recording_sink();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        sink.report_at(0, 0, at(0));
+        sink.report_at(1, 0, at(10));
+        sink.report_at(2, 0, at(100));
+        sink.report_at(3, 0, at(110));
+        assert_eq!(*reports.lock().unwrap(), vec![(0, 0), (2, 0)]);
+    }
+
+    #[test]
+    fn test_no_throttling() {
+        let (sink, reports) = recording_sink();
+        let sink = sink.with_min_interval(Duration::ZERO);
+        for completed in 0..=3 {
+            sink.report(completed, 3);
+        }
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![(0, 3), (1, 3), (2, 3), (3, 3)]
+        );
+    }
+}
@@ -0,0 +1,322 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Secret values that are wiped from memory when dropped
+//!
+//! [`Secret`] wraps a `String` or a `Vec<u8>` holding secret data, like a passphrase or key
+//! material.  It's passed over the FFI exactly like the value it wraps, so the foreign bindings
+//! see a plain `string` or `bytes`, but the Rust side makes sure the bytes don't outlive the
+//! value:
+//!
+//!   - Lifting a `Secret<String>` takes ownership of the `RustBuffer` without copying it, and
+//!     lifting a `Secret<Vec<u8>>` zeroes the `RustBuffer` once the bytes have been read out.
+//!   - While a `Secret` owns its allocation, the allocation is locked into RAM, see
+//!     [crate::memlock].  Handing it to the foreign code unlocks it.
+//!   - Dropping a `Secret` overwrites the whole allocation with zeroes.
+//!
+//! Lowering a `Secret` hands its bytes to the foreign code in a `RustBuffer`, which is zeroed when
+//! the foreign code frees it.  Those buffers are tracked, so other `RustBuffer`s are freed without
+//! being wiped.  Without the `std` feature they aren't tracked, and so aren't wiped.  The value the foreign code reads out of it is its own, and foreign
+//! strings are immutable and managed by the runtime, so UniFFI can't wipe them.
+//!
+//! Locked memory still ends up in core dumps.  A `Secret` that's written as part of a larger value, like a record field, can
+//! also leave copies behind when the buffer of that value grows.
+
+use crate::{
+    check_remaining, core_error, ffi_converter_default_return, memlock, metadata,
+    try_lift_sensitive_from_rust_buffer, wipe_vec, FfiConverter, MetadataBuffer, Result,
+    RustBuffer,
+};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use bytes::buf::{Buf, BufMut};
+use core::fmt;
+
+/// Types that can be wrapped in a [`Secret`]
+///
+/// This trait is sealed, it's implemented for `String` and `Vec<u8>`.
+pub trait Wipe: Default + private::Sealed {
+    /// Overwrite the whole allocation with zeroes, leaving an empty value
+    fn wipe(&mut self);
+}
+
+impl Wipe for Vec<u8> {
+    fn wipe(&mut self) {
+        wipe_vec(self);
+        self.clear();
+    }
+}
+
+impl Wipe for String {
+    fn wipe(&mut self) {
+        let mut bytes = core::mem::take(self).into_bytes();
+        bytes.wipe();
+        // Safety: the vec is empty now, and an empty string is valid UTF-8
+        *self = unsafe { String::from_utf8_unchecked(bytes) };
+    }
+}
+
+mod private {
+    use alloc::{string::String, vec::Vec};
+
+    pub trait Sealed {
+        // The start and size of the whole allocation
+        fn allocation(&self) -> (*const u8, usize);
+    }
+
+    impl Sealed for Vec<u8> {
+        fn allocation(&self) -> (*const u8, usize) {
+            (self.as_ptr(), self.capacity())
+        }
+    }
+
+    impl Sealed for String {
+        fn allocation(&self) -> (*const u8, usize) {
+            (self.as_ptr(), self.capacity())
+        }
+    }
+}
+
+/// A `String` or `Vec<u8>` that's zeroed when dropped
+///
+/// Use [`Secret::expose`] to access the value.  The `Debug` output never includes the value.
+pub struct Secret<T: Wipe>(T);
+
+impl<T: Wipe> Secret<T> {
+    pub fn new(value: T) -> Self {
+        let (ptr, len) = value.allocation();
+        memlock::lock(ptr, len);
+        Self(value)
+    }
+
+    /// Access the secret value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    // Move the value out without wiping it, for when ownership is handed to the foreign side.
+    // The empty value left behind doesn't allocate, so dropping it is a no-op.
+    fn into_inner(mut self) -> T {
+        let (ptr, len) = self.0.allocation();
+        memlock::unlock(ptr, len);
+        core::mem::take(&mut self.0)
+    }
+}
+
+impl<T: Wipe> Drop for Secret<T> {
+    fn drop(&mut self) {
+        let (ptr, len) = self.0.allocation();
+        self.0.wipe();
+        memlock::unlock(ptr, len);
+    }
+}
+
+impl<T: Wipe> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Wipe> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(<redacted>)")
+    }
+}
+
+/// Wipe the memory of a `RustBuffer` that's being freed, if a `Secret` was lowered into it
+pub(crate) fn wipe_if_lowered(vec: &mut Vec<u8>) {
+    if vec.capacity() > 0 && lowered::remove(vec.as_ptr()) {
+        wipe_vec(vec);
+    }
+}
+
+// The `RustBuffer`s that secrets were lowered into, by data pointer.  Freeing any other buffer only
+// costs an atomic load.
+#[cfg(feature = "std")]
+mod lowered {
+    use crate::RustBuffer;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::{
+        collections::BTreeSet,
+        sync::{Mutex, PoisonError},
+    };
+
+    static BUFFERS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    pub fn insert(buf: &RustBuffer) {
+        if buf.is_empty() {
+            return;
+        }
+        let mut buffers = BUFFERS.lock().unwrap_or_else(PoisonError::into_inner);
+        if buffers.insert(buf.data_pointer() as usize) {
+            COUNT.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    pub fn remove(ptr: *const u8) -> bool {
+        if COUNT.load(Ordering::SeqCst) == 0 {
+            return false;
+        }
+        let mut buffers = BUFFERS.lock().unwrap_or_else(PoisonError::into_inner);
+        let removed = buffers.remove(&(ptr as usize));
+        if removed {
+            COUNT.fetch_sub(1, Ordering::SeqCst);
+        }
+        removed
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod lowered {
+    use crate::RustBuffer;
+
+    pub fn insert(_buf: &RustBuffer) {}
+
+    pub fn remove(_ptr: *const u8) -> bool {
+        false
+    }
+}
+
+// Both `String` and `Vec<u8>` are serialized as an i32 length followed by the raw bytes.
+fn write_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    // TODO: it would be nice not to panic here.
+    let len = i32::try_from(bytes.len()).unwrap();
+    buf.put_i32(len);
+    buf.put(bytes);
+}
+
+fn read_bytes(buf: &mut &[u8]) -> Result<Vec<u8>> {
+    check_remaining(buf, 4)?;
+    let len = usize::try_from(buf.get_i32()).map_err(core_error)?;
+    check_remaining(buf, len)?;
+    let bytes = buf.chunk()[..len].to_vec();
+    buf.advance(len);
+    Ok(bytes)
+}
+
+/// `Secret<String>` is passed exactly like `String`.
+unsafe impl<UT> FfiConverter<UT> for Secret<String> {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Self) -> RustBuffer {
+        let buf = <String as FfiConverter<UT>>::lower(obj.into_inner());
+        lowered::insert(&buf);
+        buf
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Self> {
+        // This takes ownership of the buffer, so there's no copy left behind.
+        lowered::remove(v.data_pointer());
+        Ok(Self::new(<String as FfiConverter<UT>>::try_lift(v)?))
+    }
+
+    fn write(obj: Self, buf: &mut Vec<u8>) {
+        write_bytes(obj.expose().as_bytes(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self> {
+        let bytes = Secret::new(read_bytes(buf)?);
+        let string = core::str::from_utf8(bytes.expose())
+            .map_err(core_error)?
+            .to_string();
+        Ok(Self::new(string))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_STRING);
+}
+
+/// `Secret<Vec<u8>>` is passed exactly like `Vec<u8>`.
+unsafe impl<UT> FfiConverter<UT> for Secret<Vec<u8>> {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Self) -> RustBuffer {
+        // Allocate the whole buffer up front, growing it would leave a copy in freed memory.
+        let mut buf = Vec::with_capacity(4 + obj.expose().len());
+        write_bytes(obj.expose(), &mut buf);
+        let buf = RustBuffer::from_vec(buf);
+        lowered::insert(&buf);
+        buf
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Self> {
+        try_lift_sensitive_from_rust_buffer::<Self, UT>(v)
+    }
+
+    fn write(obj: Self, buf: &mut Vec<u8>) {
+        write_bytes(obj.expose(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self> {
+        Ok(Self::new(read_bytes(buf)?))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_VEC)
+        .concat(<u8 as FfiConverter<UT>>::TYPE_ID_META);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lower_into_rust_buffer, UniFfiTag};
+
+    #[test]
+    fn test_string_roundtrip() {
+        let secret = Secret::new("hunter2".to_string());
+        let lowered = <Secret<String> as FfiConverter<UniFfiTag>>::lower(secret);
+        let lifted = <Secret<String> as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap();
+        assert_eq!(lifted.expose(), "hunter2");
+
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(lifted);
+        let read = crate::try_lift_from_rust_buffer::<Secret<String>, UniFfiTag>(buf).unwrap();
+        assert_eq!(read.expose(), "hunter2");
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        let secret = Secret::new(vec![1u8, 2, 3]);
+        let lowered = <Secret<Vec<u8>> as FfiConverter<UniFfiTag>>::lower(secret);
+        // The buffer was allocated with the right size, rather than grown
+        let lowered = lowered.destroy_into_vec();
+        assert_eq!(lowered.capacity(), lowered.len());
+        let lowered = RustBuffer::from_vec(lowered);
+        // Secret<Vec<u8>> uses the same serialization as Vec<u8>
+        let lifted = crate::try_lift_from_rust_buffer::<Vec<u8>, UniFfiTag>(lowered).unwrap();
+        assert_eq!(lifted, vec![1, 2, 3]);
+
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(lifted);
+        let lifted = <Secret<Vec<u8>> as FfiConverter<UniFfiTag>>::try_lift(buf).unwrap();
+        assert_eq!(lifted.expose(), &vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_lowered_buffers_are_tracked() {
+        let lowered =
+            <Secret<String> as FfiConverter<UniFfiTag>>::lower(Secret::new("hunter2".to_string()));
+        let mut vec = lowered.destroy_into_vec();
+        wipe_if_lowered(&mut vec);
+        assert!(vec.iter().all(|b| *b == 0));
+        // Other buffers aren't wiped
+        let mut other = vec![1u8; 100];
+        wipe_if_lowered(&mut other);
+        assert_eq!(other, vec![1u8; 100]);
+    }
+
+    #[test]
+    fn test_wipe() {
+        let mut value = "hunter2".to_string();
+        value.wipe();
+        assert!(value.is_empty());
+        assert_eq!(
+            format!("{:?}", Secret::new(vec![1u8])),
+            "Secret(<redacted>)"
+        );
+    }
+}
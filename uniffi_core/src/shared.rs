@@ -0,0 +1,203 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Values passed in shared buffers
+//!
+//! [`Shared<T>`] is passed over the FFI like `T`, and the foreign bindings see a plain `T`.  The
+//! value is always serialized straight into the buffer of the call.  When the serialized form is
+//! at least as large as the threshold, which is set by [set_shared_memory_threshold] or
+//! [Shared::with_threshold], that buffer is handed over to the foreign code as it is, with the
+//! value in it, and the call continues in a new buffer that starts with a copy of the bytes that
+//! were written before the value.  The value itself is never copied, the foreign code reads it
+//! from where Rust wrote it, then frees the buffer.  Use it for multi-megabyte values, like media
+//! buffers or ML tensors, which would otherwise be copied whenever the call's buffer grows.
+//!
+//! The serialized form is a one-byte transport tag followed by either:
+//!
+//!   - `TRANSPORT_INLINE`: the value, serialized as usual.
+//!   - `TRANSPORT_SHARED`: the `i32` capacity, the `i32` length, the `i32` offset of the value
+//!     and the `u64` address of a `RustBuffer` that holds the serialized value.  Whoever reads the
+//!     descriptor owns that buffer, and frees it with the `rustbuffer_free` function of the
+//!     component, even if the value can't be read.
+//!
+//! The foreign code always sends values inline, the values it sends are expected to be small.
+//! Lifting a value that uses `TRANSPORT_SHARED` fails, without touching the address in it.
+
+use crate::{
+    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
+    metadata, FfiConverter, MetadataBuffer, Result,
+};
+use alloc::vec::Vec;
+use anyhow::bail;
+use bytes::buf::{Buf, BufMut};
+use core::{
+    mem::{self, ManuallyDrop},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const TRANSPORT_INLINE: u8 = 0;
+const TRANSPORT_SHARED: u8 = 1;
+
+// Serialized values smaller than this are sent inline.
+static THRESHOLD: AtomicUsize = AtomicUsize::new(1024 * 1024);
+
+/// Only pass [`Shared`] values in a shared buffer when their serialized form is at least `bytes`
+/// long
+///
+/// The default is 1 MiB.  Smaller values are cheaper to copy than to allocate a buffer for.  This
+/// applies to the whole process, use [Shared::with_threshold] to change it for a single value.
+pub fn set_shared_memory_threshold(bytes: usize) {
+    THRESHOLD.store(bytes, Ordering::Relaxed);
+}
+
+/// A value that's passed to the foreign code in a shared buffer, if it's large enough
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Shared<T> {
+    value: T,
+    threshold: Option<usize>,
+}
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            threshold: None,
+        }
+    }
+
+    /// Use a shared buffer when the serialized form of `value` is at least `bytes` long,
+    /// regardless of [set_shared_memory_threshold]
+    pub fn with_threshold(value: T, bytes: usize) -> Self {
+        Self {
+            value,
+            threshold: Some(bytes),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> From<T> for Shared<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+unsafe impl<UT, T: FfiConverter<UT>> FfiConverter<UT> for Shared<T> {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: Shared<T>, buf: &mut Vec<u8>) {
+        let threshold = obj
+            .threshold
+            .unwrap_or_else(|| THRESHOLD.load(Ordering::Relaxed));
+        let tag = buf.len();
+        buf.put_u8(TRANSPORT_INLINE);
+        T::write(obj.value, buf);
+        if buf.len() - tag - 1 < threshold {
+            return;
+        }
+        // The descriptor has the same fields as a `RustBuffer`, so that the foreign code can free
+        // it like the buffers it gets from any other call, plus the offset of the value in it.
+        if let (Ok(capacity), Ok(len), Ok(offset)) = (
+            i32::try_from(buf.capacity()),
+            i32::try_from(buf.len()),
+            i32::try_from(tag + 1),
+        ) {
+            let prefix = buf[..tag].to_vec();
+            let mut shared = ManuallyDrop::new(mem::replace(buf, prefix));
+            buf.put_u8(TRANSPORT_SHARED);
+            buf.put_i32(capacity);
+            buf.put_i32(len);
+            buf.put_i32(offset);
+            buf.put_u64(shared.as_mut_ptr() as u64);
+        }
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Shared<T>> {
+        check_remaining(buf, 1)?;
+        Ok(Shared::new(match buf.get_u8() {
+            TRANSPORT_INLINE => T::try_read(buf)?,
+            // Rust never lifts a descriptor: the address in it can't be trusted, and the foreign
+            // code only sends values inline.
+            TRANSPORT_SHARED => bail!("shared values can only be sent from Rust"),
+            transport => bail!("unknown transport {transport}"),
+        }))
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_SHARED).concat(T::TYPE_ID_META);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{RustBuffer, UniFfiTag};
+
+    #[test]
+    fn test_shared_roundtrip() {
+        // Small values are sent inline
+        let small = vec![7u8; 10];
+        let lowered = <Shared<Vec<u8>> as FfiConverter<UniFfiTag>>::lower(Shared::with_threshold(
+            small.clone(),
+            1024,
+        ));
+        assert_eq!(lowered.len(), 1 + 4 + 10);
+        assert_eq!(
+            <Shared<Vec<u8>> as FfiConverter<UniFfiTag>>::try_lift(lowered)
+                .unwrap()
+                .into_inner(),
+            small
+        );
+
+        // Large values only leave a descriptor in the buffer, after a copy of what was written
+        // before them
+        let large = vec![42u8; 100_000];
+        let mut buf = vec![1, 2, 3];
+        <Shared<Vec<u8>> as FfiConverter<UniFfiTag>>::write(
+            Shared::with_threshold(large.clone(), 1024),
+            &mut buf,
+        );
+        assert_eq!(buf.len(), 3 + 1 + 20);
+        assert_eq!(buf[..4], [1, 2, 3, TRANSPORT_SHARED]);
+        // Read the descriptor like the foreign code does
+        let mut descriptor = &buf[4..];
+        let capacity = descriptor.get_i32();
+        let len = descriptor.get_i32();
+        let offset = descriptor.get_i32() as usize;
+        let data = descriptor.get_u64() as usize as *mut u8;
+        assert!(descriptor.is_empty());
+        let serialized =
+            unsafe { RustBuffer::from_raw_parts(data, len, capacity) }.destroy_into_vec();
+        assert_eq!(
+            <Vec<u8> as FfiConverter<UniFfiTag>>::try_read(&mut &serialized[offset..]).unwrap(),
+            large
+        );
+    }
+
+    #[test]
+    fn test_forged_descriptor() {
+        // The address is never dereferenced or freed
+        let mut buf = vec![TRANSPORT_SHARED];
+        buf.put_i32(8);
+        buf.put_i32(8);
+        buf.put_i32(0);
+        buf.put_u64(0xdead_beef);
+        assert!(
+            <Shared<String> as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).is_err()
+        );
+        assert!(
+            <Shared<String> as FfiConverter<UniFfiTag>>::try_lift(RustBuffer::from_vec(buf))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unknown_transport() {
+        let mut buf: &[u8] = &[7, 0, 0, 0, 0];
+        assert!(<Shared<String> as FfiConverter<UniFfiTag>>::try_read(&mut buf).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` impl for `url::Url`
+//!
+//! Enabled by the `url` feature.  URLs are passed as strings, and are parsed when they're lifted,
+//! so an invalid URL from the foreign side is reported as an error instead of reaching Rust code.
+
+use crate::{
+    ffi_converter_default_return, metadata, FfiConverter, MetadataBuffer, Result, RustBuffer,
+};
+use url::Url;
+
+unsafe impl<UT> FfiConverter<UT> for Url {
+    ffi_converter_default_return!(UT);
+
+    type FfiType = RustBuffer;
+
+    fn lower(obj: Url) -> RustBuffer {
+        <String as FfiConverter<UT>>::lower(obj.into())
+    }
+
+    fn try_lift(v: RustBuffer) -> Result<Url> {
+        Ok(Url::parse(&<String as FfiConverter<UT>>::try_lift(v)?)?)
+    }
+
+    fn write(obj: Url, buf: &mut Vec<u8>) {
+        <String as FfiConverter<UT>>::write(obj.into(), buf)
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Url> {
+        Ok(Url::parse(&<String as FfiConverter<UT>>::try_read(buf)?)?)
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_URL);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{lower_into_rust_buffer, try_lift_from_rust_buffer, UniFfiTag};
+
+    #[test]
+    fn test_url_roundtrip() {
+        let url = Url::parse("https://example.com/path?query=1").unwrap();
+        let lowered = <Url as FfiConverter<UniFfiTag>>::lower(url.clone());
+        assert_eq!(
+            <Url as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            url
+        );
+
+        let buf = lower_into_rust_buffer::<_, UniFfiTag>(url.clone());
+        assert_eq!(
+            try_lift_from_rust_buffer::<Url, UniFfiTag>(buf).unwrap(),
+            url
+        );
+    }
+
+    #[test]
+    fn test_invalid_url() {
+        let lowered = <String as FfiConverter<UniFfiTag>>::lower("not a url".to_string());
+        assert!(<Url as FfiConverter<UniFfiTag>>::try_lift(lowered).is_err());
+    }
+}
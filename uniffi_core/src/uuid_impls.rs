@@ -0,0 +1,56 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! `FfiConverter` impl for `uuid::Uuid`
+//!
+//! Enabled by the `uuid` feature.  UUIDs are serialized as their 16 raw bytes, in the big-endian
+//! order used by `Uuid::as_bytes()`, which is cheaper to pass than the 36 character string form.
+
+use crate::{
+    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
+    metadata, FfiConverter, MetadataBuffer, Result,
+};
+use bytes::buf::{Buf, BufMut};
+use uuid::Uuid;
+
+unsafe impl<UT> FfiConverter<UT> for Uuid {
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: Uuid, buf: &mut Vec<u8>) {
+        buf.put_slice(obj.as_bytes());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Uuid> {
+        check_remaining(buf, 16)?;
+        let mut bytes = [0u8; 16];
+        buf.copy_to_slice(&mut bytes);
+        Ok(Uuid::from_bytes(bytes))
+    }
+
+    const TYPE_ID_META: MetadataBuffer = MetadataBuffer::from_code(metadata::codes::TYPE_UUID);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+
+    #[test]
+    fn test_uuid_roundtrip() {
+        let uuid = Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let lowered = <Uuid as FfiConverter<UniFfiTag>>::lower(uuid);
+        assert_eq!(lowered.len(), 16);
+        assert_eq!(
+            <Uuid as FfiConverter<UniFfiTag>>::try_lift(lowered).unwrap(),
+            uuid
+        );
+    }
+
+    #[test]
+    fn test_uuid_too_short() {
+        let mut buf: &[u8] = &[0u8; 15];
+        assert!(<Uuid as FfiConverter<UniFfiTag>>::try_read(&mut buf).is_err());
+    }
+}
@@ -0,0 +1,255 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Weak references to objects
+//!
+//! A `Weak<T>` of an exported object is passed to the foreign code as a handle to a boxed copy of
+//! the `Weak`, which doesn't keep the object alive.  The foreign code wraps the handle in a
+//! `WeakRef` that can check whether the object is still alive and upgrade to it, so Rust can hand
+//! out references to objects without creating reference cycles, like with the delegates that
+//! foreign code registers on its Rust objects.
+//!
+//! The handles are type-erased, so that every component only needs the 3 scaffolding functions
+//! below, rather than a set for each object:
+//!
+//!   - `weak_ref_upgrade`: returns a new strong reference to the object, lowered like any other
+//!     object, or 0 if it was dropped.
+//!   - `weak_ref_clone`: returns a new handle to the same object.  The foreign code writes a clone
+//!     when it passes a `WeakRef` to Rust, since Rust takes ownership of the handles it reads.
+//!   - `weak_ref_free`: frees a handle.
+//!
+//! In a buffer, the handle is written as a `u64`, like object pointers.
+
+use crate::{
+    check_remaining, ffi_converter_default_return, ffi_converter_rust_buffer_lift_and_lower,
+    metadata, rust_call, FfiConverter, FfiConverterArc, MetadataBuffer, Result, RustCallStatus,
+};
+use alloc::{boxed::Box, sync::Weak, vec::Vec};
+use anyhow::bail;
+use bytes::buf::{Buf, BufMut};
+use core::{any::Any, ffi::c_void};
+
+type AnyWeak = dyn Any + Send + Sync;
+
+/// A boxed `Weak<T>`, with the functions that need to know `T`
+struct WeakHandle {
+    weak: Box<AnyWeak>,
+    upgrade: fn(&AnyWeak) -> u64,
+    clone: fn(&AnyWeak) -> Box<AnyWeak>,
+}
+
+impl WeakHandle {
+    fn new<UT, T>(weak: Weak<T>) -> Self
+    where
+        T: FfiConverterArc<UT, FfiType = *const c_void> + Send + Sync + ?Sized + 'static,
+    {
+        Self {
+            weak: Box::new(weak),
+            upgrade: upgrade_weak::<UT, T>,
+            clone: clone_weak::<T>,
+        }
+    }
+
+    fn into_raw(self) -> u64 {
+        Box::into_raw(Box::new(self)) as usize as u64
+    }
+
+    /// Borrow the handle behind a lowered value
+    ///
+    /// ## Safety
+    ///
+    /// `handle` must have been returned by `into_raw()` and not freed yet.
+    unsafe fn from_raw<'a>(handle: u64) -> &'a Self {
+        &*(handle as usize as *const Self)
+    }
+
+    /// Take ownership of the handle behind a lowered value
+    ///
+    /// ## Safety
+    ///
+    /// `handle` must have been returned by `into_raw()` and not freed yet.
+    unsafe fn take(handle: u64) -> Box<Self> {
+        Box::from_raw(handle as usize as *mut Self)
+    }
+}
+
+fn downcast<T: ?Sized + 'static>(weak: &AnyWeak) -> &Weak<T> {
+    weak.downcast_ref()
+        .expect("the handle functions were created for this type")
+}
+
+fn upgrade_weak<UT, T>(weak: &AnyWeak) -> u64
+where
+    T: FfiConverterArc<UT, FfiType = *const c_void> + ?Sized + 'static,
+{
+    match downcast::<T>(weak).upgrade() {
+        Some(obj) => T::lower(obj) as usize as u64,
+        None => 0,
+    }
+}
+
+fn clone_weak<T: Send + Sync + ?Sized + 'static>(weak: &AnyWeak) -> Box<AnyWeak> {
+    Box::new(downcast::<T>(weak).clone())
+}
+
+unsafe impl<UT, T> FfiConverter<UT> for Weak<T>
+where
+    T: FfiConverterArc<UT, FfiType = *const c_void> + Send + Sync + ?Sized + 'static,
+{
+    ffi_converter_rust_buffer_lift_and_lower!(UT);
+    ffi_converter_default_return!(UT);
+
+    fn write(obj: Weak<T>, buf: &mut Vec<u8>) {
+        buf.put_u64(WeakHandle::new::<UT, T>(obj).into_raw());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Weak<T>> {
+        check_remaining(buf, 8)?;
+        // Safety: the foreign code only writes handles that it got from `write()` or
+        // `weak_ref_clone`, and passes their ownership to us.
+        let handle = unsafe { WeakHandle::take(buf.get_u64()) };
+        match handle.weak.downcast::<Weak<T>>() {
+            Ok(weak) => Ok(*weak),
+            Err(_) => bail!("weak reference to a different type"),
+        }
+    }
+
+    const TYPE_ID_META: MetadataBuffer =
+        MetadataBuffer::from_code(metadata::codes::TYPE_WEAK).concat(T::TYPE_ID_META);
+}
+
+/// The scaffolding function behind `WeakRef.upgrade()`, see `setup_scaffolding!`
+///
+/// ## Safety
+///
+/// `handle` must be a weak reference handle that hasn't been freed.
+pub unsafe fn uniffi_weak_ref_upgrade(handle: u64, call_status: &mut RustCallStatus) -> u64 {
+    rust_call(call_status, || {
+        let handle = WeakHandle::from_raw(handle);
+        Ok((handle.upgrade)(handle.weak.as_ref()))
+    })
+}
+
+/// The scaffolding function that copies a `WeakRef` handle, see `setup_scaffolding!`
+///
+/// ## Safety
+///
+/// `handle` must be a weak reference handle that hasn't been freed.
+pub unsafe fn uniffi_weak_ref_clone(handle: u64, call_status: &mut RustCallStatus) -> u64 {
+    rust_call(call_status, || {
+        let handle = WeakHandle::from_raw(handle);
+        Ok(WeakHandle {
+            weak: (handle.clone)(handle.weak.as_ref()),
+            upgrade: handle.upgrade,
+            clone: handle.clone,
+        }
+        .into_raw())
+    })
+}
+
+/// The scaffolding function that frees a `WeakRef` handle, see `setup_scaffolding!`
+///
+/// ## Safety
+///
+/// `handle` must be a weak reference handle that hasn't been freed.
+pub unsafe fn uniffi_weak_ref_free(handle: u64, call_status: &mut RustCallStatus) {
+    rust_call(call_status, || {
+        drop(WeakHandle::take(handle));
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::UniFfiTag;
+    use alloc::sync::Arc;
+
+    struct TestObject;
+
+    unsafe impl FfiConverterArc<UniFfiTag> for TestObject {
+        type FfiType = *const c_void;
+        type ReturnType = *const c_void;
+        type FutureCallback = crate::FutureCallback<*const c_void>;
+
+        fn lower(obj: Arc<Self>) -> *const c_void {
+            Arc::into_raw(obj) as *const c_void
+        }
+
+        fn lower_return(obj: Arc<Self>) -> Result<*const c_void, crate::RustBuffer> {
+            Ok(Self::lower(obj))
+        }
+
+        fn try_lift(v: *const c_void) -> Result<Arc<Self>> {
+            Ok(unsafe { Arc::from_raw(v as *const Self) })
+        }
+
+        unsafe fn lift_ref<'a>(v: *const c_void) -> &'a Self {
+            &*(v as *const Self)
+        }
+
+        fn write(obj: Arc<Self>, buf: &mut Vec<u8>) {
+            buf.put_u64(Self::lower(obj) as u64);
+        }
+
+        fn try_read(buf: &mut &[u8]) -> Result<Arc<Self>> {
+            check_remaining(buf, 8)?;
+            Self::try_lift(buf.get_u64() as *const c_void)
+        }
+
+        fn invoke_future_callback(
+            callback: Self::FutureCallback,
+            callback_data: *const (),
+            return_value: *const c_void,
+            call_status: RustCallStatus,
+        ) {
+            callback(callback_data, return_value, call_status)
+        }
+
+        const TYPE_ID_META: MetadataBuffer =
+            MetadataBuffer::from_code(metadata::codes::TYPE_INTERFACE);
+    }
+
+    fn read_handle(weak: Weak<TestObject>) -> u64 {
+        let mut buf = Vec::new();
+        <Weak<TestObject> as FfiConverter<UniFfiTag>>::write(weak, &mut buf);
+        buf.as_slice().get_u64()
+    }
+
+    #[test]
+    fn test_weak_ref_upgrade() {
+        let obj = Arc::new(TestObject);
+        let handle = read_handle(Arc::downgrade(&obj));
+        let mut status = RustCallStatus::default();
+        unsafe {
+            let ptr = uniffi_weak_ref_upgrade(handle, &mut status);
+            assert_eq!(ptr, Arc::as_ptr(&obj) as usize as u64);
+            // Upgrading returned a new strong reference
+            assert_eq!(Arc::strong_count(&obj), 2);
+            drop(<TestObject as FfiConverterArc<UniFfiTag>>::try_lift(
+                ptr as *const c_void,
+            ));
+
+            drop(obj);
+            assert_eq!(uniffi_weak_ref_upgrade(handle, &mut status), 0);
+            uniffi_weak_ref_free(handle, &mut status);
+        }
+    }
+
+    #[test]
+    fn test_weak_ref_roundtrip() {
+        let obj = Arc::new(TestObject);
+        let handle = read_handle(Arc::downgrade(&obj));
+        let mut status = RustCallStatus::default();
+        let copy = unsafe { uniffi_weak_ref_clone(handle, &mut status) };
+        assert_ne!(copy, handle);
+        unsafe { uniffi_weak_ref_free(handle, &mut status) };
+
+        let buf = copy.to_be_bytes();
+        let weak =
+            <Weak<TestObject> as FfiConverter<UniFfiTag>>::try_read(&mut buf.as_slice()).unwrap();
+        assert!(Arc::ptr_eq(&weak.upgrade().unwrap(), &obj));
+        assert_eq!(Arc::weak_count(&obj), 1);
+    }
+}
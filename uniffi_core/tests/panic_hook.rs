@@ -0,0 +1,32 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The panic hook is process-global, so this test is in a binary of its own.
+
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uniffi_core::{rust_call, FfiConverter, RustBuffer, RustCallStatus};
+
+// Builtin types implement `FfiConverter` for any tag
+struct UniFfiTag;
+
+static APP_HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn test_app_panic_hook_is_kept() {
+    // Our hook is only installed when backtraces are enabled, before the first call
+    std::env::set_var("RUST_BACKTRACE", "1");
+    panic::set_hook(Box::new(|_| APP_HOOK_CALLED.store(true, Ordering::SeqCst)));
+    let mut status = RustCallStatus::default();
+    rust_call(&mut status, || -> Result<(), RustBuffer> { panic!("boom") });
+    assert!(APP_HOOK_CALLED.load(Ordering::SeqCst));
+
+    // The backtrace is sent after the message, rather than in it
+    let buf = unsafe { status.error_buf.assume_init() }.destroy_into_vec();
+    let mut buf = buf.as_slice();
+    let message = <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap();
+    assert_eq!(message, "boom");
+    let backtrace = <String as FfiConverter<UniFfiTag>>::try_read(&mut buf).unwrap();
+    assert!(!backtrace.is_empty());
+}
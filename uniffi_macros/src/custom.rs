@@ -30,7 +30,11 @@ pub(crate) fn expand_ffi_converter_custom_type(
                 <#ident as crate::UniffiCustomTypeConverter>::into_custom(<#builtin as ::uniffi::FfiConverter<crate::UniFfiTag>>::try_lift(v)?)
             }
 
-            fn write(obj: #ident, buf: &mut Vec<u8>) {
+            fn try_lift_sensitive(v: Self::FfiType) -> ::uniffi::Result<#ident> {
+                <#ident as crate::UniffiCustomTypeConverter>::into_custom(<#builtin as ::uniffi::FfiConverter<crate::UniFfiTag>>::try_lift_sensitive(v)?)
+            }
+
+            fn write(obj: #ident, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
                 <#builtin as ::uniffi::FfiConverter<crate::UniFfiTag>>::write(<#ident as crate::UniffiCustomTypeConverter>::from_custom(obj), buf);
             }
 
@@ -1,14 +1,21 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{Data, DataEnum, DeriveInput, Field, Index, Path};
+use syn::{
+    parse_quote, Data, DataEnum, DeriveInput, Expr, ExprLit, ExprUnary, Field, Index, Lit, Path,
+    UnOp,
+};
 
 use crate::util::{
-    create_metadata_items, ident_to_string, mod_path, tagged_impl_header,
+    create_metadata_items, ident_to_string, mod_path, source_location_items, tagged_impl_header,
     try_metadata_value_from_usize, try_read_field, ArgumentNotAllowedHere, AttributeSliceExt,
     CommonAttr,
 };
 
-pub fn expand_enum(input: DeriveInput) -> TokenStream {
+/// Generate the `FfiConverter` and the metadata for an enum
+///
+/// For `#[uniffi::remote(Enum)]`, the enum is defined in another crate, so `FfiConverter` is
+/// only implemented for the local `UniFfiTag`.
+pub fn expand_enum(input: DeriveInput, remote: bool) -> TokenStream {
     let enum_ = match input.data {
         Data::Enum(e) => e,
         _ => {
@@ -23,10 +30,15 @@ pub fn expand_enum(input: DeriveInput) -> TokenStream {
         .parse_uniffi_attr_args::<ArgumentNotAllowedHere>()
         .err()
         .map(syn::Error::into_compile_error);
-    let ffi_converter_impl = enum_ffi_converter_impl(ident, &enum_, None);
+    let non_exhaustive = input
+        .attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("non_exhaustive"));
+    let tag: Option<Path> = remote.then(|| parse_quote!(crate::UniFfiTag));
+    let ffi_converter_impl = enum_ffi_converter_impl(ident, &enum_, tag.as_ref(), non_exhaustive);
 
-    let meta_static_var =
-        enum_meta_static_var(ident, &enum_).unwrap_or_else(syn::Error::into_compile_error);
+    let meta_static_var = enum_meta_static_var(ident, &enum_, non_exhaustive)
+        .unwrap_or_else(syn::Error::into_compile_error);
 
     quote! {
         #attr_error
@@ -37,9 +49,10 @@ pub fn expand_enum(input: DeriveInput) -> TokenStream {
 
 pub(crate) fn expand_enum_ffi_converter(attr: CommonAttr, input: DeriveInput) -> TokenStream {
     match input.data {
-        Data::Enum(e) => enum_ffi_converter_impl(&input.ident, &e, attr.tag.as_ref()),
+        // UDL can't declare `#[non_exhaustive]` enums
+        Data::Enum(e) => enum_ffi_converter_impl(&input.ident, &e, attr.tag.as_ref(), false),
         _ => syn::Error::new(
-            proc_macro2::Span::call_site(),
+            Span::call_site(),
             "This attribute must only be used on enums",
         )
         .into_compile_error(),
@@ -50,12 +63,14 @@ pub(crate) fn enum_ffi_converter_impl(
     ident: &Ident,
     enum_: &DataEnum,
     tag: Option<&Path>,
+    non_exhaustive: bool,
 ) -> TokenStream {
     enum_or_error_ffi_converter_impl(
         ident,
         enum_,
         tag,
         false,
+        non_exhaustive,
         quote! { ::uniffi::metadata::codes::TYPE_ENUM },
     )
 }
@@ -71,6 +86,7 @@ pub(crate) fn rich_error_ffi_converter_impl(
         enum_,
         tag,
         handle_unknown_callback_error,
+        false,
         quote! { ::uniffi::metadata::codes::TYPE_ENUM },
     )
 }
@@ -80,6 +96,7 @@ fn enum_or_error_ffi_converter_impl(
     enum_: &DataEnum,
     tag: Option<&Path>,
     handle_unknown_callback_error: bool,
+    non_exhaustive: bool,
     metadata_type_code: TokenStream,
 ) -> TokenStream {
     let name = ident_to_string(ident);
@@ -93,16 +110,36 @@ fn enum_or_error_ffi_converter_impl(
         let fields = v.fields.iter().map(|f| &f.ident);
         let idx = Index::from(i + 1);
         let write_fields = v.fields.iter().map(write_field);
+        // The fields of `#[non_exhaustive]` enums are written with their size, so that the
+        // bindings can skip variants that were added after they were generated.
+        let write_fields = if non_exhaustive {
+            quote! {
+                ::uniffi::write_with_length_prefix(buf, |buf| { #(#write_fields)* });
+            }
+        } else {
+            quote! { #(#write_fields)* }
+        };
 
         quote! {
             Self::#v_ident { #(#fields),* } => {
                 ::uniffi::deps::bytes::BufMut::put_i32(buf, #idx);
-                #(#write_fields)*
+                #write_fields
             }
         }
     });
+    // Needed for `#[uniffi::remote(Enum)]` on an enum from another crate
+    let unknown_variant_arm = non_exhaustive.then(|| {
+        let msg = format!("Unknown variant of {ident}");
+        quote! {
+            #[allow(unreachable_patterns)]
+            _ => ::core::panic!(#msg),
+        }
+    });
     let write_impl = quote! {
-        match obj { #(#write_match_arms)* }
+        match obj {
+            #(#write_match_arms)*
+            #unknown_variant_arm
+        }
     };
 
     let try_read_match_arms = enum_.variants.iter().enumerate().map(|(i, v)| {
@@ -133,11 +170,11 @@ fn enum_or_error_ffi_converter_impl(
             ::uniffi::ffi_converter_rust_buffer_lift_and_lower!(crate::UniFfiTag);
             ::uniffi::ffi_converter_default_return!(crate::UniFfiTag);
 
-            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+            fn write(obj: Self, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
                 #write_impl
             }
 
-            fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+            fn try_read(buf: &mut &[::core::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
                 #try_read_impl
             }
 
@@ -159,7 +196,11 @@ fn write_field(f: &Field) -> TokenStream {
     }
 }
 
-pub(crate) fn enum_meta_static_var(ident: &Ident, enum_: &DataEnum) -> syn::Result<TokenStream> {
+pub(crate) fn enum_meta_static_var(
+    ident: &Ident,
+    enum_: &DataEnum,
+    non_exhaustive: bool,
+) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
     let module_path = mod_path()?;
 
@@ -167,9 +208,15 @@ pub(crate) fn enum_meta_static_var(ident: &Ident, enum_: &DataEnum) -> syn::Resu
         ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::ENUM)
             .concat_str(#module_path)
             .concat_str(#name)
+            .concat_bool(#non_exhaustive)
     };
     metadata_expr.extend(variant_metadata(enum_)?);
-    Ok(create_metadata_items("enum", &name, metadata_expr, None))
+    let metadata_items = create_metadata_items("enum", &name, metadata_expr, None);
+    let location_items = source_location_items("enum", &name, &module_path, &name, ident.span());
+    Ok(quote! {
+        #metadata_items
+        #location_items
+    })
 }
 
 pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
@@ -201,9 +248,17 @@ pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
                     .collect::<syn::Result<Vec<_>>>()?;
 
                     let name = ident_to_string(&v.ident);
+                    let discr = match &v.discriminant {
+                        Some((_, expr)) => {
+                            let discr = discriminant_value(expr)?;
+                            quote! { .concat_bool(true).concat_str(#discr) }
+                        }
+                        None => quote! { .concat_bool(false) },
+                    };
                     let field_types = v.fields.iter().map(|f| &f.ty);
                     Ok(quote! {
                         .concat_str(#name)
+                        #discr
                         .concat_value(#fields_len)
                             #(
                                 .concat_str(#field_names)
@@ -217,6 +272,40 @@ pub fn variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
         .collect()
 }
 
+/// Get the base-10 digits of an explicit discriminant (`Foo = 3`)
+///
+/// Only integer literals, optionally negated, are supported since the value needs to be known
+/// when the metadata is generated.
+fn discriminant_value(expr: &Expr) -> syn::Result<String> {
+    let (negative, lit) = match expr {
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => (true, expr.as_ref()),
+        expr => (false, expr),
+    };
+    let value = match lit {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse::<i128>()?,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                expr,
+                "UniFFI only supports integer literals as enum discriminants",
+            ))
+        }
+    };
+    let value = if negative { -value } else { value };
+    if i64::try_from(value).is_err() {
+        return Err(syn::Error::new_spanned(
+            expr,
+            "UniFFI limits enum discriminants to the range of an i64",
+        ));
+    }
+    Ok(value.to_string())
+}
+
 /// Generate the `handle_callback_unexpected_error()` implementation
 ///
 /// If handle_unknown_callback_error is true, this will use the `From<UnexpectedUniFFICallbackError>`
@@ -229,7 +318,7 @@ pub(crate) fn handle_callback_unexpected_error_fn(
 ) -> Option<TokenStream> {
     handle_unknown_callback_error.then(|| quote! {
         fn handle_callback_unexpected_error(e: ::uniffi::UnexpectedUniFFICallbackError) -> Self {
-            <Self as ::std::convert::From<::uniffi::UnexpectedUniFFICallbackError>>::from(e)
+            <Self as ::core::convert::From<::uniffi::UnexpectedUniFFICallbackError>>::from(e)
         }
     })
 }
@@ -2,19 +2,23 @@ use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataEnum, DeriveInput, Index, Path, Token,
+    parse_quote, Data, DataEnum, DeriveInput, Field, Index, Path, Token, Variant,
 };
 
 use crate::{
     enum_::{handle_callback_unexpected_error_fn, rich_error_ffi_converter_impl, variant_metadata},
     util::{
         chain, create_metadata_items, either_attribute_arg, ident_to_string, mod_path,
-        parse_comma_separated, tagged_impl_header, try_metadata_value_from_usize,
-        AttributeSliceExt, UniffiAttributeArgs,
+        parse_comma_separated, source_location_items, tagged_impl_header,
+        try_metadata_value_from_usize, AttributeSliceExt, UniffiAttributeArgs,
     },
 };
 
-pub fn expand_error(input: DeriveInput) -> syn::Result<TokenStream> {
+/// Generate the `FfiConverter` and the metadata for an error
+///
+/// For `#[uniffi::remote(Error)]`, the enum is defined in another crate, so `FfiConverter` is
+/// only implemented for the local `UniFfiTag`.
+pub fn expand_error(input: DeriveInput, remote: bool) -> syn::Result<TokenStream> {
     let enum_ = match input.data {
         Data::Enum(e) => e,
         _ => {
@@ -26,21 +30,31 @@ pub fn expand_error(input: DeriveInput) -> syn::Result<TokenStream> {
     };
 
     let ident = &input.ident;
-    let attr = input.attrs.parse_uniffi_attr_args::<ErrorAttr>()?;
+    let mut attr = input.attrs.parse_uniffi_attr_args::<ErrorAttr>()?;
+    if remote {
+        attr.tag = Some(parse_quote!(crate::UniFfiTag));
+    }
+    let flat = attr.flat.is_some();
     let ffi_converter_impl = error_ffi_converter_impl(ident, &enum_, &attr);
-    let meta_static_var = error_meta_static_var(ident, &enum_, attr.flat.is_some())
-        .unwrap_or_else(syn::Error::into_compile_error);
+    let meta_static_var =
+        error_meta_static_var(ident, &enum_, flat).unwrap_or_else(syn::Error::into_compile_error);
 
     let variant_errors: TokenStream = enum_
         .variants
         .iter()
         .flat_map(|variant| {
-            chain(
-                variant.attrs.uniffi_attr_args_not_allowed_here(),
+            let field_errors: Vec<_> = if flat {
+                error_fields(variant).err().into_iter().collect()
+            } else {
                 variant
                     .fields
                     .iter()
-                    .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here()),
+                    .flat_map(|field| field.attrs.uniffi_attr_args_not_allowed_here())
+                    .collect()
+            };
+            chain(
+                variant.attrs.uniffi_attr_args_not_allowed_here(),
+                field_errors,
             )
         })
         .map(syn::Error::into_compile_error)
@@ -57,7 +71,7 @@ pub(crate) fn expand_ffi_converter_error(attr: ErrorAttr, input: DeriveInput) ->
     match input.data {
         Data::Enum(e) => error_ffi_converter_impl(&input.ident, &e, &attr),
         _ => syn::Error::new(
-            proc_macro2::Span::call_site(),
+            Span::call_site(),
             "This attribute must only be used on enums",
         )
         .into_compile_error(),
@@ -87,6 +101,7 @@ fn error_ffi_converter_impl(ident: &Ident, enum_: &DataEnum, attr: &ErrorAttr) -
 //
 // These are errors where we only lower the to_string() value, rather than any assocated data.
 // We lower the to_string() value unconditionally, whether the enum has associated data or not.
+// The fields marked with `#[uniffi(error_field)]` are written after it.
 fn flat_error_ffi_converter_impl(
     ident: &Ident,
     enum_: &DataEnum,
@@ -105,17 +120,28 @@ fn flat_error_ffi_converter_impl(
         let match_arms = enum_.variants.iter().enumerate().map(|(i, v)| {
             let v_ident = &v.ident;
             let idx = Index::from(i + 1);
+            // Invalid error fields are reported by `expand_error()`
+            let fields = error_fields(v).unwrap_or_default();
+            let field_idents = fields.iter().map(|f| &f.ident);
+            let write_fields = fields.iter().map(|f| {
+                let ident = &f.ident;
+                let ty = &f.ty;
+                quote! {
+                    <#ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::write(#ident, buf);
+                }
+            });
 
             quote! {
-                Self::#v_ident { .. } => {
+                Self::#v_ident { #(#field_idents,)* .. } => {
                     ::uniffi::deps::bytes::BufMut::put_i32(buf, #idx);
-                    <::std::string::String as ::uniffi::FfiConverter<crate::UniFfiTag>>::write(error_msg, buf);
+                    <::uniffi::deps::alloc::string::String as ::uniffi::FfiConverter<crate::UniFfiTag>>::write(error_msg, buf);
+                    #(#write_fields)*
                 }
             }
         });
 
         quote! {
-            let error_msg = ::std::string::ToString::to_string(&obj);
+            let error_msg = ::uniffi::deps::alloc::string::ToString::to_string(&obj);
             match obj { #(#match_arms)* }
         }
     };
@@ -136,7 +162,7 @@ fn flat_error_ffi_converter_impl(
             })
         }
     } else {
-        quote! { ::std::panic!("try_read not supported for flat errors") }
+        quote! { ::core::panic!("try_read not supported for flat errors") }
     };
 
     let handle_callback_unexpected_error =
@@ -148,11 +174,11 @@ fn flat_error_ffi_converter_impl(
             ::uniffi::ffi_converter_rust_buffer_lift_and_lower!(crate::UniFfiTag);
             ::uniffi::ffi_converter_default_return!(crate::UniFfiTag);
 
-            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+            fn write(obj: Self, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
                 #write_impl
             }
 
-            fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+            fn try_read(buf: &mut &[::core::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
                 #try_read_impl
             }
 
@@ -185,18 +211,61 @@ pub(crate) fn error_meta_static_var(
     } else {
         metadata_expr.extend(variant_metadata(enum_)?);
     }
-    Ok(create_metadata_items("error", &name, metadata_expr, None))
+    let metadata_items = create_metadata_items("error", &name, metadata_expr, None);
+    let location_items = source_location_items("error", &name, &module_path, &name, ident.span());
+    Ok(quote! {
+        #metadata_items
+        #location_items
+    })
 }
 
 pub fn flat_error_variant_metadata(enum_: &DataEnum) -> syn::Result<Vec<TokenStream>> {
     let variants_len =
         try_metadata_value_from_usize(enum_.variants.len(), "UniFFI limits enums to 256 variants")?;
-    Ok(std::iter::once(quote! { .concat_value(#variants_len) })
+    std::iter::once(Ok(quote! { .concat_value(#variants_len) }))
         .chain(enum_.variants.iter().map(|v| {
             let name = ident_to_string(&v.ident);
-            quote! { .concat_str(#name) }
+            // Invalid error fields are reported by `expand_error()`
+            let fields = error_fields(v).unwrap_or_default();
+            let fields_len = try_metadata_value_from_usize(
+                fields.len(),
+                "UniFFI limits enum variants to 256 fields",
+            )?;
+            let field_names = fields
+                .iter()
+                .map(|f| ident_to_string(f.ident.as_ref().unwrap()));
+            let field_types = fields.iter().map(|f| &f.ty);
+            Ok(quote! {
+                .concat_str(#name)
+                .concat_value(#fields_len)
+                #(
+                    .concat_str(#field_names)
+                    .concat(<#field_types as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+                    // field defaults not yet supported for errors
+                    .concat_bool(false)
+                )*
+            })
         }))
-        .collect())
+        .collect()
+}
+
+/// The fields of a flat error variant that are passed to the foreign code along with the error
+/// message, which are the named fields marked with `#[uniffi(error_field)]`
+fn error_fields(variant: &Variant) -> syn::Result<Vec<&Field>> {
+    let mut fields = Vec::new();
+    for field in &variant.fields {
+        let attr = field.attrs.parse_uniffi_attr_args::<ErrorFieldAttr>()?;
+        if attr.error_field.is_some() {
+            if field.ident.is_none() {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "UniFFI only supports named fields as error fields",
+                ));
+            }
+            fields.push(field);
+        }
+    }
+    Ok(fields)
 }
 
 mod kw {
@@ -204,6 +273,7 @@ mod kw {
     syn::custom_keyword!(flat_error);
     syn::custom_keyword!(with_try_read);
     syn::custom_keyword!(handle_unknown_callback_error);
+    syn::custom_keyword!(error_field);
 }
 
 #[derive(Default)]
@@ -258,6 +328,26 @@ impl UniffiAttributeArgs for ErrorAttr {
     }
 }
 
+/// Attributes of the fields of flat error variants
+#[derive(Default)]
+struct ErrorFieldAttr {
+    error_field: Option<kw::error_field>,
+}
+
+impl UniffiAttributeArgs for ErrorFieldAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        Ok(Self {
+            error_field: Some(input.parse()?),
+        })
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            error_field: either_attribute_arg(self.error_field, other.error_field)?,
+        })
+    }
+}
+
 // So ErrorAttr can be used with `parse_macro_input!`
 impl Parse for ErrorAttr {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
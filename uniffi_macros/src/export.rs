@@ -3,13 +3,16 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{visit_mut::VisitMut, Item, Path, Type};
 
 mod attributes;
 mod callback_interface;
+mod constant;
 mod item;
 mod scaffolding;
+mod trait_constant;
+mod utrait;
 
 use self::{
     attributes::ExportAttributeArguments,
@@ -18,10 +21,14 @@ use self::{
 };
 use crate::{
     object::interface_meta_static_var,
-    util::{ident_to_string, mod_path, tagged_impl_header},
+    util::{
+        cfg_gated_items, create_metadata_items, ident_to_string, mod_path, tagged_impl_header,
+        try_metadata_value_from_usize,
+    },
 };
+pub(crate) use attributes::AsyncRuntime;
 pub use callback_interface::ffi_converter_callback_interface_impl;
-use uniffi_meta::free_fn_symbol_name;
+use uniffi_meta::{free_fn_symbol_name, ConcurrencyPolicy};
 
 // TODO(jplatte): Ensure no generics, …
 // TODO(jplatte): Aggregate errors instead of short-circuiting, wherever possible
@@ -31,6 +38,17 @@ pub(crate) fn expand_export(
     args: ExportAttributeArguments,
     mod_path: String,
 ) -> syn::Result<TokenStream> {
+    // The item itself is part of the output.  Exported traits are output with their constants
+    // moved out of them, other items are output unchanged.
+    let exported_item = match &item {
+        Item::Trait(t) => trait_constant::rewrite_trait(
+            t,
+            &trait_constant::trait_constants(t)?,
+            args.callback_interface.is_some(),
+        ),
+        _ => item.to_token_stream(),
+    };
+
     // If the input is an `impl` block, rewrite any uses of the `Self` type
     // alias to the actual type, so we don't have to special-case it in the
     // metadata collection or scaffolding code generation (which generates
@@ -39,7 +57,46 @@ pub(crate) fn expand_export(
 
     let metadata = ExportItem::new(item, &args)?;
 
-    match metadata {
+    if let (Some(main_thread), None) = (&args.main_thread, &args.callback_interface) {
+        return Err(syn::Error::new_spanned(
+            main_thread,
+            "`main_thread` can only be used on callback interfaces",
+        ));
+    }
+
+    if let (Some(serialized), Some(_)) = (&args.serialized, &args.concurrent) {
+        return Err(syn::Error::new_spanned(
+            serialized,
+            "an impl block can't be both `serialized` and `concurrent`",
+        ));
+    }
+    if !matches!(metadata, ExportItem::Impl { .. }) && args.concurrency().is_some() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`serialized` and `concurrent` can only be used on impl blocks",
+        ));
+    }
+
+    if let Some(namespace) = &args.namespace {
+        if !matches!(metadata, ExportItem::Function { .. }) {
+            return Err(syn::Error::new_spanned(
+                namespace,
+                "`namespace` can only be used on functions, types are always in the main namespace",
+            ));
+        }
+        if let Err(e) = uniffi_meta::check_sub_namespace_name(&namespace.value()) {
+            return Err(syn::Error::new_spanned(namespace, e));
+        }
+    }
+
+    if !matches!(metadata, ExportItem::Struct { .. }) && !args.uniffi_traits().is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`Debug`, `Display`, `Eq`, `Hash` and `Ord` can only be exported for structs",
+        ));
+    }
+
+    let output = match metadata {
         ExportItem::Function { sig } => gen_fn_scaffolding(sig, &args),
         ExportItem::Impl { items, self_ident } => {
             if let Some(rt) = &args.async_runtime {
@@ -54,6 +111,27 @@ pub(crate) fn expand_export(
                 }
             }
 
+            if args.serialized.is_some() {
+                if let Some(sig) = items.iter().find_map(|item| match item {
+                    ImplItem::Method(sig) if sig.is_async => Some(sig),
+                    _ => None,
+                }) {
+                    return Err(syn::Error::new(
+                        sig.span,
+                        "async methods can't be `serialized`, move them to another impl block",
+                    ));
+                }
+            }
+            if items.is_empty() && args.concurrency().is_some() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "an empty impl block can't declare `serialized` or `concurrent`",
+                ));
+            }
+            let concurrency_items = args
+                .concurrency()
+                .map(|policy| concurrency_metadata_items(&self_ident, &items, policy, &mod_path));
+
             let item_tokens: TokenStream = items
                 .into_iter()
                 .map(|item| match item {
@@ -61,12 +139,17 @@ pub(crate) fn expand_export(
                     ImplItem::Method(sig) => gen_method_scaffolding(sig, &args),
                 })
                 .collect::<syn::Result<_>>()?;
-            Ok(quote_spanned! { self_ident.span() => #item_tokens })
+            Ok(quote_spanned! { self_ident.span() =>
+                #item_tokens
+                #concurrency_items
+            })
         }
         ExportItem::Trait {
             items,
             self_ident,
+            constants,
             callback_interface: false,
+            cfg,
         } => {
             if let Some(rt) = args.async_runtime {
                 return Err(syn::Error::new_spanned(rt, "not supported for traits"));
@@ -80,12 +163,13 @@ pub(crate) fn expand_export(
                 #[doc(hidden)]
                 #[no_mangle]
                 pub extern "C" fn #free_fn_ident(
-                    ptr: *const ::std::ffi::c_void,
+                    ptr: *const ::core::ffi::c_void,
                     call_status: &mut ::uniffi::RustCallStatus
                 ) {
                     ::uniffi::rust_call(call_status, || {
                         assert!(!ptr.is_null());
-                        drop(unsafe { ::std::boxed::Box::from_raw(ptr as *mut ::std::sync::Arc<dyn #self_ident>) });
+                        ::uniffi::untrack_handle(#name, ptr as u64);
+                        drop(unsafe { ::uniffi::deps::alloc::boxed::Box::from_raw(ptr as *mut ::uniffi::deps::alloc::sync::Arc<dyn #self_ident>) });
                         Ok(())
                     });
                 }
@@ -110,63 +194,122 @@ pub(crate) fn expand_export(
             let meta_static_var = interface_meta_static_var(&self_ident, true, &mod_path)
                 .unwrap_or_else(syn::Error::into_compile_error);
             let ffi_converter_tokens = ffi_converter_trait_impl(&self_ident, None);
+            let constant_metadata_items =
+                constants.iter().map(|c| c.metadata_items(&name, &mod_path));
 
-            Ok(quote_spanned! { self_ident.span() =>
+            let tokens = quote_spanned! { self_ident.span() =>
                 #meta_static_var
                 #free_tokens
                 #ffi_converter_tokens
                 #impl_tokens
-            })
+                #(#constant_metadata_items)*
+            };
+            cfg_gated_items("interface", &name, &mod_path, &name, &cfg, tokens)
         }
         ExportItem::Trait {
             items,
             self_ident,
+            constants,
             callback_interface: true,
+            cfg,
         } => {
             let trait_name = ident_to_string(&self_ident);
-            let trait_impl_ident = Ident::new(
-                &format!("UniFFICallbackHandler{trait_name}"),
-                Span::call_site(),
-            );
-            let internals_ident = Ident::new(
-                &format!(
-                    "UNIFFI_FOREIGN_CALLBACK_INTERNALS_{}",
-                    trait_name.to_ascii_uppercase()
-                ),
-                Span::call_site(),
-            );
-
-            let trait_impl = callback_interface::trait_impl(
-                &trait_impl_ident,
+            let tokens = callback_interface::expand(
                 &self_ident,
-                &internals_ident,
                 &items,
-            )
-            .unwrap_or_else(|e| e.into_compile_error());
-            let metadata_items = callback_interface::metadata_items(&self_ident, &items, &mod_path)
-                .unwrap_or_else(|e| vec![e.into_compile_error()]);
-
-            let init_ident = Ident::new(
-                &uniffi_meta::init_callback_fn_symbol_name(&mod_path, &trait_name),
-                Span::call_site(),
+                &constants,
+                &mod_path,
+                args.main_thread.is_some(),
+                false,
             );
+            cfg_gated_items(
+                "callback_interface",
+                &trait_name,
+                &mod_path,
+                &trait_name,
+                &cfg,
+                tokens,
+            )
+        }
+        ExportItem::Struct {
+            self_ident,
+            uniffi_traits,
+        } => {
+            if let Some(rt) = args.async_runtime {
+                return Err(syn::Error::new_spanned(rt, "not supported for structs"));
+            }
+            if uniffi_traits.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    self_ident,
+                    "exported structs need at least one of `Debug`, `Display`, `Eq`, `Hash` or `Ord`",
+                ));
+            }
+            utrait::expand_uniffi_trait_export(self_ident, uniffi_traits)
+        }
+        ExportItem::Constant {
+            ident,
+            ty,
+            value,
+            cfg,
+        } => {
+            if let Some(rt) = args.async_runtime {
+                return Err(syn::Error::new_spanned(rt, "not supported for constants"));
+            }
+            constant::expand_constant(&ident, &ty, &value, &cfg, &mod_path)
+        }
+    }?;
 
-            Ok(quote! {
-                #[doc(hidden)]
-                static #internals_ident: ::uniffi::ForeignCallbackInternals = ::uniffi::ForeignCallbackInternals::new();
-
-                #[doc(hidden)]
-                #[no_mangle]
-                pub extern "C" fn #init_ident(callback: ::uniffi::ForeignCallback, _: &mut ::uniffi::RustCallStatus) {
-                    #internals_ident.set_callback(callback);
-                }
-
-                #trait_impl
+    Ok(quote! {
+        #exported_item
+        #output
+    })
+}
 
-                        #(#metadata_items)*
-            })
-        }
-    }
+/// Generate the metadata that declares the concurrency policy of an impl block, along with the
+/// names of its methods so that `uniffi_bindgen` can check that the other impl blocks of the object
+/// agree
+fn concurrency_metadata_items(
+    self_ident: &Ident,
+    items: &[ImplItem],
+    policy: ConcurrencyPolicy,
+    mod_path: &str,
+) -> TokenStream {
+    let self_name = ident_to_string(self_ident);
+    let method_names: Vec<_> = items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(sig) => Some(sig.name.as_str()),
+            ImplItem::Constructor(_) => None,
+        })
+        .collect();
+    let method_count = match try_metadata_value_from_usize(
+        method_names.len(),
+        "UniFFI limits impl blocks to 256 methods",
+    ) {
+        Ok(v) => v,
+        Err(e) => return e.into_compile_error(),
+    };
+    let policy = policy as u8;
+    // Several impl blocks of the same object can declare a policy, so the item is also named after
+    // the first method or constructor of the block, which is unique to it.  Empty blocks are
+    // rejected in `expand_export`.
+    let first_item = items.first().map(|item| match item {
+        ImplItem::Constructor(sig) | ImplItem::Method(sig) => sig.name.as_str(),
+    });
+    let item_name = format!("{self_name}_{}", first_item.unwrap_or_default());
+    create_metadata_items(
+        "object_concurrency",
+        &item_name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::OBJECT_CONCURRENCY)
+                .concat_str(#mod_path)
+                .concat_str(#self_name)
+                .concat_value(#policy)
+                .concat_value(#method_count)
+                #(.concat_str(#method_names))*
+        },
+        None,
+    )
 }
 
 pub(crate) fn ffi_converter_trait_impl(trait_ident: &Ident, tag: Option<&Path>) -> TokenStream {
@@ -179,36 +322,42 @@ pub(crate) fn ffi_converter_trait_impl(trait_ident: &Ident, tag: Option<&Path>)
 
     quote! {
         unsafe #impl_spec {
-            type FfiType = *const ::std::os::raw::c_void;
+            type FfiType = *const ::core::ffi::c_void;
             type ReturnType = Self::FfiType;
             type FutureCallback = ::uniffi::FutureCallback<Self::ReturnType>;
 
-            fn lower(obj: ::std::sync::Arc<Self>) -> Self::FfiType {
-                ::std::boxed::Box::into_raw(::std::boxed::Box::new(obj)) as *const ::std::os::raw::c_void
+            fn lower(obj: ::uniffi::deps::alloc::sync::Arc<Self>) -> Self::FfiType {
+                let ptr = ::uniffi::deps::alloc::boxed::Box::into_raw(::uniffi::deps::alloc::boxed::Box::new(obj)) as *const ::core::ffi::c_void;
+                ::uniffi::track_handle(#name, ptr as u64);
+                ptr
             }
 
-            fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                let foreign_arc = ::std::boxed::Box::leak(unsafe { ::std::boxed::Box::from_raw(v as *mut ::std::sync::Arc<Self>) });
+            fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::uniffi::deps::alloc::sync::Arc<Self>> {
+                let foreign_arc = ::uniffi::deps::alloc::boxed::Box::leak(unsafe { ::uniffi::deps::alloc::boxed::Box::from_raw(v as *mut ::uniffi::deps::alloc::sync::Arc<Self>) });
                 // Take a clone for our own use.
-                Ok(::std::sync::Arc::clone(foreign_arc))
+                Ok(::uniffi::deps::alloc::sync::Arc::clone(foreign_arc))
             }
 
-            fn write(obj: ::std::sync::Arc<Self>, buf: &mut Vec<u8>) {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
+            unsafe fn lift_ref<'a>(v: Self::FfiType) -> &'a Self {
+                &**(v as *const ::uniffi::deps::alloc::sync::Arc<Self>)
+            }
+
+            fn write(obj: ::uniffi::deps::alloc::sync::Arc<Self>, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
+                ::uniffi::deps::static_assertions::const_assert!(::core::mem::size_of::<*const ::core::ffi::c_void>() <= 8);
                 ::uniffi::deps::bytes::BufMut::put_u64(
                     buf,
                     <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj) as u64,
                 );
             }
 
-            fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
+            fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::uniffi::deps::alloc::sync::Arc<Self>> {
+                ::uniffi::deps::static_assertions::const_assert!(::core::mem::size_of::<*const ::core::ffi::c_void>() <= 8);
                 ::uniffi::check_remaining(buf, 8)?;
                 <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(
                     ::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
             }
 
-            fn lower_return(v: ::std::sync::Arc<Self>) -> ::std::result::Result<Self::FfiType, ::uniffi::RustBuffer> {
+            fn lower_return(v: ::uniffi::deps::alloc::sync::Arc<Self>) -> ::core::result::Result<Self::FfiType, ::uniffi::RustBuffer> {
                 Ok(<Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(v))
             }
 
@@ -273,6 +422,10 @@ pub fn rewrite_self_type(item: &mut Item) {
                 _ => syn::visit_mut::visit_type_mut(self, i),
             }
         }
+
+        // Receivers keep `Self`, which is how `ReceiverArg` recognizes `&self` and
+        // `self: Arc<Self>`
+        fn visit_receiver_mut(&mut self, _i: &mut syn::Receiver) {}
     }
 
     let mut visitor = RewriteSelfVisitor(&item.self_ty);
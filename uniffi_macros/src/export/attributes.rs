@@ -4,18 +4,69 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use syn::{
     parse::{Parse, ParseStream},
-    Attribute, LitStr, Meta, PathArguments, PathSegment, Token,
+    punctuated::Punctuated,
+    Attribute, Ident, LitStr, Meta, Path, PathArguments, PathSegment, Token,
 };
+use uniffi_meta::{ConcurrencyPolicy, UniffiTraitDiscriminants};
 
 pub(crate) mod kw {
     syn::custom_keyword!(async_runtime);
     syn::custom_keyword!(callback_interface);
+    syn::custom_keyword!(concurrent);
+    syn::custom_keyword!(Debug);
+    syn::custom_keyword!(Display);
+    syn::custom_keyword!(Eq);
+    syn::custom_keyword!(Hash);
+    syn::custom_keyword!(main_thread);
+    syn::custom_keyword!(namespace);
+    syn::custom_keyword!(Ord);
+    syn::custom_keyword!(Restore);
+    syn::custom_keyword!(serialized);
 }
 
 #[derive(Default)]
 pub struct ExportAttributeArguments {
     pub(crate) async_runtime: Option<AsyncRuntime>,
     pub(crate) callback_interface: Option<kw::callback_interface>,
+    pub(crate) concurrent: Option<kw::concurrent>,
+    pub(crate) debug: Option<kw::Debug>,
+    pub(crate) display: Option<kw::Display>,
+    pub(crate) eq: Option<kw::Eq>,
+    pub(crate) hash: Option<kw::Hash>,
+    pub(crate) main_thread: Option<kw::main_thread>,
+    /// The sub-namespace of an exported function
+    pub(crate) namespace: Option<LitStr>,
+    pub(crate) ord: Option<kw::Ord>,
+    pub(crate) restore: Option<kw::Restore>,
+    pub(crate) serialized: Option<kw::serialized>,
+}
+
+impl ExportAttributeArguments {
+    /// The traits that should be forwarded to the foreign code, in the order we generate them
+    pub(crate) fn uniffi_traits(&self) -> Vec<UniffiTraitDiscriminants> {
+        [
+            (self.debug.is_some(), UniffiTraitDiscriminants::Debug),
+            (self.display.is_some(), UniffiTraitDiscriminants::Display),
+            (self.eq.is_some(), UniffiTraitDiscriminants::Eq),
+            (self.hash.is_some(), UniffiTraitDiscriminants::Hash),
+            (self.ord.is_some(), UniffiTraitDiscriminants::Ord),
+            (self.restore.is_some(), UniffiTraitDiscriminants::Restore),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, t)| enabled.then_some(t))
+        .collect()
+    }
+
+    /// The concurrency policy declared for the methods of an impl block
+    pub(crate) fn concurrency(&self) -> Option<ConcurrencyPolicy> {
+        if self.serialized.is_some() {
+            Some(ConcurrencyPolicy::Serialized)
+        } else if self.concurrent.is_some() {
+            Some(ConcurrencyPolicy::Concurrent)
+        } else {
+            None
+        }
+    }
 }
 
 impl Parse for ExportAttributeArguments {
@@ -39,6 +90,58 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 callback_interface: input.parse()?,
                 ..Self::default()
             })
+        } else if lookahead.peek(kw::concurrent) {
+            Ok(Self {
+                concurrent: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Debug) {
+            Ok(Self {
+                debug: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Display) {
+            Ok(Self {
+                display: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Eq) {
+            Ok(Self {
+                eq: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Hash) {
+            Ok(Self {
+                hash: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::main_thread) {
+            Ok(Self {
+                main_thread: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::namespace) {
+            let _: kw::namespace = input.parse()?;
+            let _: Token![=] = input.parse()?;
+            Ok(Self {
+                namespace: Some(input.parse()?),
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Ord) {
+            Ok(Self {
+                ord: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Restore) {
+            Ok(Self {
+                restore: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::serialized) {
+            Ok(Self {
+                serialized: input.parse()?,
+                ..Self::default()
+            })
         } else {
             Ok(Self::default())
         }
@@ -51,22 +154,41 @@ impl UniffiAttributeArgs for ExportAttributeArguments {
                 self.callback_interface,
                 other.callback_interface,
             )?,
+            concurrent: either_attribute_arg(self.concurrent, other.concurrent)?,
+            debug: either_attribute_arg(self.debug, other.debug)?,
+            display: either_attribute_arg(self.display, other.display)?,
+            eq: either_attribute_arg(self.eq, other.eq)?,
+            hash: either_attribute_arg(self.hash, other.hash)?,
+            main_thread: either_attribute_arg(self.main_thread, other.main_thread)?,
+            namespace: either_attribute_arg(self.namespace, other.namespace)?,
+            ord: either_attribute_arg(self.ord, other.ord)?,
+            restore: either_attribute_arg(self.restore, other.restore)?,
+            serialized: either_attribute_arg(self.serialized, other.serialized)?,
         })
     }
 }
 
 pub(crate) enum AsyncRuntime {
     Tokio(LitStr),
+    /// No runtime, to opt a method out of the runtime of its `impl` block
+    None(LitStr),
+    /// A type that implements `uniffi::AsyncRuntime`
+    Custom(Path),
 }
 
 impl Parse for AsyncRuntime {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        if !input.peek(LitStr) {
+            return Ok(Self::Custom(input.parse()?));
+        }
         let lit: LitStr = input.parse()?;
         match lit.value().as_str() {
             "tokio" => Ok(Self::Tokio(lit)),
+            "none" => Ok(Self::None(lit)),
             _ => Err(syn::Error::new_spanned(
                 lit,
-                "unknown async runtime, currently only `tokio` is supported",
+                "unknown async runtime, use `\"tokio\"`, `\"none\"` or the path of a type that \
+                 implements `uniffi::AsyncRuntime`",
             )),
         }
     }
@@ -75,7 +197,8 @@ impl Parse for AsyncRuntime {
 impl ToTokens for AsyncRuntime {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         match self {
-            AsyncRuntime::Tokio(lit) => lit.to_tokens(tokens),
+            AsyncRuntime::Tokio(lit) | AsyncRuntime::None(lit) => lit.to_tokens(tokens),
+            AsyncRuntime::Custom(path) => path.to_tokens(tokens),
         }
     }
 }
@@ -83,6 +206,14 @@ impl ToTokens for AsyncRuntime {
 #[derive(Default)]
 pub(super) struct ExportedImplFnAttributes {
     pub constructor: bool,
+    /// Runtime of an async method, from `#[uniffi::async_runtime(...)]`.  This overrides the
+    /// runtime of the `impl` block.
+    pub async_runtime: Option<AsyncRuntime>,
+    /// Value returned by a callback interface method when the foreign code raises an unexpected
+    /// error, from `#[uniffi::on_error(...)]`
+    pub on_error: Option<syn::Expr>,
+    /// Arguments that carry secret data, from `#[uniffi::sensitive(...)]`
+    pub sensitive: Vec<Ident>,
 }
 
 impl ExportedImplFnAttributes {
@@ -99,13 +230,6 @@ impl ExportedImplFnAttributes {
             }
             ensure_no_path_args(fst)?;
 
-            if let Meta::List(_) | Meta::NameValue(_) = &attr.meta {
-                return Err(syn::Error::new_spanned(
-                    &attr.meta,
-                    "attribute arguments are not currently recognized in this position",
-                ));
-            }
-
             if segs.len() != 2 {
                 return Err(syn::Error::new_spanned(
                     segs,
@@ -116,6 +240,42 @@ impl ExportedImplFnAttributes {
             ensure_no_path_args(snd)?;
 
             match snd.ident.to_string().as_str() {
+                "on_error" => {
+                    if this.on_error.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate on_error attribute",
+                        ));
+                    }
+                    this.on_error = Some(attr.parse_args()?);
+                }
+                "async_runtime" => {
+                    if this.async_runtime.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate async_runtime attribute",
+                        ));
+                    }
+                    this.async_runtime = Some(attr.parse_args()?);
+                }
+                "sensitive" => {
+                    if !this.sensitive.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "duplicate sensitive attribute",
+                        ));
+                    }
+                    this.sensitive = attr
+                        .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?
+                        .into_iter()
+                        .collect();
+                }
+                _ if matches!(attr.meta, Meta::List(_) | Meta::NameValue(_)) => {
+                    return Err(syn::Error::new_spanned(
+                        &attr.meta,
+                        "attribute arguments are not currently recognized in this position",
+                    ));
+                }
                 "constructor" => {
                     if this.constructor {
                         return Err(syn::Error::new_spanned(
@@ -3,20 +3,144 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use crate::{
-    export::ImplItem,
-    fnsig::{FnKind, FnSignature},
-    util::{create_metadata_items, ident_to_string, mod_path, tagged_impl_header},
+    export::{trait_constant::TraitConstant, ImplItem},
+    fnsig::{FnKind, FnSignature, ReceiverArg},
+    util::{
+        create_metadata_items, ident_to_string, mod_path, source_location_items, tagged_impl_header,
+    },
 };
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use std::iter;
-use syn::{Ident, Path};
+use syn::{parse_quote, Block, Ident, Path};
 
-pub(super) fn trait_impl(
+/// Generate the handler, the scaffolding and the metadata for a callback interface
+///
+/// `closure` is set for the callback interfaces generated for closure arguments, see
+/// `closure_items()`.
+pub(super) fn expand(
+    self_ident: &Ident,
+    items: &[ImplItem],
+    constants: &[TraitConstant],
+    mod_path: &str,
+    main_thread: bool,
+    closure: bool,
+) -> TokenStream {
+    let trait_name = ident_to_string(self_ident);
+    let trait_impl_ident = Ident::new(
+        &format!("UniFFICallbackHandler{trait_name}"),
+        Span::call_site(),
+    );
+    let internals_ident = Ident::new(
+        &format!(
+            "UNIFFI_FOREIGN_CALLBACK_INTERNALS_{}",
+            trait_name.to_ascii_uppercase()
+        ),
+        Span::call_site(),
+    );
+
+    let trait_impl = trait_impl(
+        &trait_impl_ident,
+        self_ident,
+        &internals_ident,
+        items,
+        constants,
+        main_thread,
+    )
+    .unwrap_or_else(|e| e.into_compile_error());
+    let metadata_items =
+        metadata_items(self_ident, items, constants, mod_path, main_thread, closure)
+            .unwrap_or_else(|e| vec![e.into_compile_error()]);
+
+    let init_ident = Ident::new(
+        &uniffi_meta::init_callback_fn_symbol_name(mod_path, &trait_name),
+        Span::call_site(),
+    );
+    let detach_ident = Ident::new(
+        &uniffi_meta::detach_callback_fn_symbol_name(mod_path, &trait_name),
+        Span::call_site(),
+    );
+
+    quote! {
+        #[doc(hidden)]
+        static #internals_ident: ::uniffi::ForeignCallbackInternals = ::uniffi::ForeignCallbackInternals::new();
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #init_ident(callback: ::uniffi::ForeignCallback, _: &mut ::uniffi::RustCallStatus) {
+            #internals_ident.set_callback(callback);
+        }
+
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #detach_ident(handle: u64, call_status: &mut ::uniffi::RustCallStatus) {
+            ::uniffi::rust_call(call_status, || {
+                #internals_ident.detach(handle);
+                Ok(())
+            })
+        }
+
+        impl ::uniffi::ForeignCallbackInterface for dyn #self_ident {
+            fn callback_internals() -> &'static ::uniffi::ForeignCallbackInternals {
+                &#internals_ident
+            }
+        }
+
+        #trait_impl
+
+        #(#metadata_items)*
+    }
+}
+
+/// Generate a callback interface for each closure argument of a function
+///
+/// The foreign closure is passed as a callback interface with a single `invoke` method, whose
+/// trait is only used by the scaffolding.  It lifts the handler, then wraps it in a Rust closure
+/// that calls `invoke`.
+pub(super) fn closure_items(sig: &FnSignature) -> syn::Result<TokenStream> {
+    sig.args
+        .iter()
+        .filter_map(|arg| arg.closure.as_ref())
+        .map(|closure| {
+            let ident = &closure.trait_ident;
+            let output = &closure.output;
+            let params: Vec<_> = closure
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| {
+                    let param = format_ident!("v{i}");
+                    quote! { #param: #ty }
+                })
+                .collect();
+            let method_sig: syn::Signature = parse_quote! {
+                fn invoke(&self, #(#params),*) #output
+            };
+            let items = [ImplItem::Method(FnSignature::new_trait_method(
+                ident.clone(),
+                method_sig,
+                0,
+            )?)];
+            let callback_interface = expand(ident, &items, &[], &sig.mod_path, false, true);
+            Ok(quote! {
+                #[doc(hidden)]
+                trait #ident: ::core::marker::Send + ::core::marker::Sync {
+                    fn invoke(&self, #(#params),*) #output;
+                }
+
+                #callback_interface
+            })
+        })
+        .collect()
+}
+
+fn trait_impl(
     ident: &Ident,
     trait_ident: &Ident,
     internals_ident: &Ident,
     items: &[ImplItem],
+    constants: &[TraitConstant],
+    main_thread: bool,
 ) -> syn::Result<TokenStream> {
     let trait_impl_methods = items
         .iter()
@@ -25,46 +149,125 @@ pub(super) fn trait_impl(
             _ => unreachable!("traits have no constructors"),
         })
         .collect::<syn::Result<TokenStream>>()?;
-    let ffi_converter_tokens = ffi_converter_callback_interface_impl(trait_ident, ident, None);
+    let constant_reader_impls = constants
+        .iter()
+        .map(|c| c.reader_method_impl(internals_ident));
+    let default_methods = items.iter().filter_map(|item| match item {
+        ImplItem::Method(sig) => sig
+            .default_body
+            .as_ref()
+            .map(|body| gen_default_method(sig, body)),
+        _ => None,
+    });
+    let default_scaffolding = items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Method(sig) if sig.default_body.is_some() => {
+                Some(gen_default_scaffolding(sig, ident))
+            }
+            _ => None,
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let ffi_converter_tokens =
+        ffi_converter_callback_interface_impl(trait_ident, ident, None, main_thread);
+    let trait_name = ident_to_string(trait_ident);
 
     Ok(quote! {
         #[doc(hidden)]
         #[derive(Debug)]
         struct #ident {
             handle: u64,
+            attached: bool,
         }
 
         impl #ident {
             fn new(handle: u64) -> Self {
-                Self { handle }
+                ::uniffi::track_handle(#trait_name, handle);
+                #internals_ident.attach(handle);
+                Self { handle, attached: true }
             }
+
+            // For the default method implementations, which the foreign object calls on itself.
+            // This doesn't attach the handle, so the lifecycle hooks aren't called.
+            fn new_untracked(handle: u64) -> Self {
+                ::uniffi::track_handle(#trait_name, handle);
+                Self { handle, attached: false }
+            }
+
+            #(#default_methods)*
         }
 
-        impl ::std::ops::Drop for #ident {
+        impl ::core::ops::Drop for #ident {
             fn drop(&mut self) {
-                #internals_ident.invoke_callback::<(), crate::UniFfiTag>(
-                    self.handle, ::uniffi::IDX_CALLBACK_FREE, ::std::default::Default::default()
-                )
+                ::uniffi::untrack_handle(#trait_name, self.handle);
+                if !self.attached {
+                    return #internals_ident.invoke_callback::<(), crate::UniFfiTag>(
+                        self.handle, ::uniffi::IDX_CALLBACK_FREE, ::core::default::Default::default()
+                    );
+                }
+                #internals_ident.unregister_handler(self as *const Self as usize, self.handle);
+                // The foreign code already forgot the objects that it detached
+                if #internals_ident.release(self.handle) {
+                    #internals_ident.invoke_callback::<(), crate::UniFfiTag>(
+                        self.handle, ::uniffi::IDX_CALLBACK_FREE, ::core::default::Default::default()
+                    )
+                }
             }
         }
 
-        ::uniffi::deps::static_assertions::assert_impl_all!(#ident: Send);
+        // The handler only holds a handle, so it's `Send` even when the foreign implementation isn't
+        // thread-safe.  For `main_thread` callback interfaces, the bindings run every call on the
+        // main thread, which is why Rust can also get them as `Box<dyn Trait + Send + Sync>` when
+        // the trait itself doesn't require `Send`.
+        ::uniffi::deps::static_assertions::assert_impl_all!(#ident: Send, Sync);
 
         impl #trait_ident for #ident {
             #trait_impl_methods
+            #(#constant_reader_impls)*
         }
 
         #ffi_converter_tokens
+        #(#default_scaffolding)*
     })
 }
 
+/// Generate the `FfiConverter` implementation for `Box<dyn Trait>`
+///
+/// For `main_thread` callback interfaces, this also implements it for
+/// `Box<dyn Trait + Send + Sync>`, so that traits which don't require `Send` can be used from any
+/// thread.
 pub fn ffi_converter_callback_interface_impl(
     trait_ident: &Ident,
     trait_impl_ident: &Ident,
     tag: Option<&Path>,
+    main_thread: bool,
+) -> TokenStream {
+    let dyn_trait = quote! { dyn #trait_ident };
+    let impl_tokens = callback_interface_converter(&dyn_trait, trait_ident, trait_impl_ident, tag);
+    if !main_thread {
+        return impl_tokens;
+    }
+    let dyn_send_trait = quote! { dyn #trait_ident + ::core::marker::Send + ::core::marker::Sync };
+    let send_impl_tokens =
+        callback_interface_converter(&dyn_send_trait, trait_ident, trait_impl_ident, tag);
+    quote! {
+        #impl_tokens
+        #send_impl_tokens
+    }
+}
+
+fn callback_interface_converter(
+    dyn_trait: &TokenStream,
+    trait_ident: &Ident,
+    trait_impl_ident: &Ident,
+    tag: Option<&Path>,
 ) -> TokenStream {
     let name = ident_to_string(trait_ident);
-    let impl_spec = tagged_impl_header("FfiConverter", &quote! { ::std::boxed::Box<dyn #trait_ident> }, tag);
+    let impl_spec = tagged_impl_header(
+        "FfiConverter",
+        &quote! { ::uniffi::deps::alloc::boxed::Box<#dyn_trait> },
+        tag,
+    );
     let tag = match tag {
         Some(t) => quote! { #t },
         None => quote! { T },
@@ -93,12 +296,16 @@ pub fn ffi_converter_callback_interface_impl(
                 panic!("Lowering CallbackInterface not supported")
             }
 
-            fn write(_obj: Self, _buf: &mut ::std::vec::Vec<u8>) {
+            fn write(_obj: Self, _buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
                 panic!("Writing CallbackInterface not supported")
             }
 
             fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<Self> {
-                Ok(Box::new(<#trait_impl_ident>::new(v)))
+                let handler = ::uniffi::deps::alloc::boxed::Box::new(<#trait_impl_ident>::new(v));
+                // Rust code finds the handle of a `Box<dyn Trait>` by the handler's address
+                <dyn #trait_ident as ::uniffi::ForeignCallbackInterface>::callback_internals()
+                    .register_handler(&*handler as *const #trait_impl_ident as usize, v);
+                Ok(handler)
             }
 
             fn try_read(buf: &mut &[u8]) -> ::uniffi::deps::anyhow::Result<Self> {
@@ -124,6 +331,8 @@ fn gen_method_impl(sig: &FnSignature, internals_ident: &Ident) -> syn::Result<To
         return_ty,
         kind,
         receiver,
+        on_error,
+        callback_result_ty,
         ..
     } = sig;
     let index = match kind {
@@ -139,32 +348,118 @@ fn gen_method_impl(sig: &FnSignature, internals_ident: &Ident) -> syn::Result<To
         }
     };
 
-    if receiver.is_none() {
+    // The handler is used as a `Box<dyn Trait>`, which can't be called with `self: Arc<Self>`
+    if *receiver != Some(ReceiverArg::Ref) {
         return Err(syn::Error::new(
             sig.span,
             "callback interface methods must take &self as their first argument",
         ));
     }
+    // The foreign code takes ownership of the objects it's passed, which a borrow can't give it
+    if let Some(arg) = sig.args.iter().find(|a| a.borrowed_object.is_some()) {
+        return Err(syn::Error::new_spanned(
+            &arg.ident,
+            "callback interface methods can't borrow objects, use `Arc<T>` instead",
+        ));
+    }
+    // Closures can only be passed from the foreign code to Rust
+    if let Some(arg) = sig.args.iter().find(|a| a.closure.is_some()) {
+        return Err(syn::Error::new_spanned(
+            &arg.ident,
+            "callback interface methods can't take closures",
+        ));
+    }
     let params = sig.params();
     let buf_ident = Ident::new("uniffi_args_buf", Span::call_site());
     let write_exprs = sig.write_exprs(&buf_ident);
+    let invoke_callback = match (on_error, callback_result_ty) {
+        (Some(fallback), _) => quote! {
+            #internals_ident.invoke_callback_with_fallback::<#return_ty, crate::UniFfiTag>(
+                self.handle, #index, uniffi_args_rbuf, |_| #fallback
+            )
+        },
+        (None, Some(ok_ty)) => quote! {
+            #internals_ident.try_invoke_callback::<#ok_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
+        },
+        (None, None) => quote! {
+            #internals_ident.invoke_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
+        },
+    };
 
     Ok(quote! {
         fn #ident(&self, #(#params),*) -> #return_ty {
             #[allow(unused_mut)]
-            let mut #buf_ident = ::std::vec::Vec::new();
+            let mut #buf_ident = ::uniffi::deps::alloc::vec::Vec::new();
             #(#write_exprs;)*
             let uniffi_args_rbuf = ::uniffi::RustBuffer::from_vec(#buf_ident);
 
-            #internals_ident.invoke_callback::<#return_ty, crate::UniFfiTag>(self.handle, #index, uniffi_args_rbuf)
+            #invoke_callback
+        }
+    })
+}
+
+fn default_method_ident(sig: &FnSignature) -> Ident {
+    format_ident!("uniffi_default_{}", sig.ident)
+}
+
+/// A copy of the default body of a provided method
+///
+/// The handler's implementation of the trait method calls the foreign implementation, so the
+/// default body is copied into an inherent method where it can still be called.
+fn gen_default_method(sig: &FnSignature, body: &Block) -> TokenStream {
+    let ident = default_method_ident(sig);
+    let params = sig.params();
+    let return_ty = &sig.return_ty;
+    quote! {
+        fn #ident(&self, #(#params),*) -> #return_ty #body
+    }
+}
+
+/// The scaffolding function that runs the default body of a provided method
+///
+/// Foreign implementations that don't override the method call this with a handle to
+/// themselves.
+fn gen_default_scaffolding(sig: &FnSignature, handler_ident: &Ident) -> syn::Result<TokenStream> {
+    let ffi_ident = sig.scaffolding_fn_ident()?;
+    let default_ident = default_method_ident(sig);
+    let name = &sig.name;
+    let params = sig.scaffolding_params();
+    let param_lifts = sig.lift_exprs();
+    let call = quote! { uniffi_self.#default_ident(#(#param_lifts,)*) };
+    // The foreign code sees methods that return `CallbackError` as returning the `Ok` type, so
+    // errors from the foreign methods that the default body calls become panics
+    let (return_ty, call) = match &sig.callback_result_ty {
+        Some(ok_ty) => (
+            ok_ty,
+            quote! { #call.unwrap_or_else(|e| ::core::panic!("{}", e)) },
+        ),
+        None => (&sig.return_ty, call),
+    };
+
+    Ok(quote! {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_ident(
+            uniffi_handle: u64,
+            #(#params,)*
+            call_status: &mut ::uniffi::RustCallStatus,
+        ) -> <#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::ReturnType {
+            ::uniffi::deps::log::debug!(#name);
+            ::uniffi::rust_call(call_status, || {
+                let uniffi_self = #handler_ident::new_untracked(uniffi_handle);
+                <#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::lower_return(#call)
+            })
         }
     })
 }
 
-pub(super) fn metadata_items(
+fn metadata_items(
     self_ident: &Ident,
     items: &[ImplItem],
+    constants: &[TraitConstant],
     module_path: &str,
+    main_thread: bool,
+    closure: bool,
 ) -> syn::Result<Vec<TokenStream>> {
     let trait_name = ident_to_string(self_ident);
     let callback_interface_items = create_metadata_items(
@@ -174,14 +469,29 @@ pub(super) fn metadata_items(
             ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CALLBACK_INTERFACE)
                 .concat_str(#module_path)
                 .concat_str(#trait_name)
+                .concat_bool(#main_thread)
+                .concat_bool(#closure)
         },
         None,
     );
+    let location_items = source_location_items(
+        "callback_interface",
+        &trait_name,
+        module_path,
+        &trait_name,
+        self_ident.span(),
+    );
 
     iter::once(Ok(callback_interface_items))
+        .chain(iter::once(Ok(location_items)))
         .chain(items.iter().map(|item| match item {
             ImplItem::Method(sig) => sig.metadata_items(),
             _ => unreachable!("traits have no constructors"),
         }))
+        .chain(
+            constants
+                .iter()
+                .map(|c| Ok(c.metadata_items(&trait_name, module_path))),
+        )
         .collect()
 }
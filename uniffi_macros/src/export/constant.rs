@@ -0,0 +1,46 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Exported constants
+//!
+//! The value of the constant is stored in the metadata and the bindings declare the constant
+//! themselves, so there's no scaffolding.  Like for trait constants, this means the value must be
+//! a literal.
+
+use crate::util::{cfg_gated_items, create_metadata_items, ident_to_string};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Attribute, Expr, Ident};
+
+use super::trait_constant::{is_str_type, value_concat_calls};
+
+pub(super) fn expand_constant(
+    ident: &Ident,
+    ty: &syn::Type,
+    value: &Expr,
+    cfg: &[Attribute],
+    mod_path: &str,
+) -> syn::Result<TokenStream> {
+    let name = ident_to_string(ident);
+    // `&str` constants are described as strings
+    let ffi_type = if is_str_type(ty) {
+        quote! { ::uniffi::deps::alloc::string::String }
+    } else {
+        quote! { #ty }
+    };
+    let concat_value = value_concat_calls(value)?;
+    let metadata_items = create_metadata_items(
+        "constant",
+        &name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONSTANT)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat(<#ffi_type as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+                #concat_value
+        },
+        None,
+    );
+    cfg_gated_items("constant", &name, mod_path, &name, cfg, metadata_items)
+}
@@ -2,11 +2,18 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::fnsig::FnSignature;
+use crate::{
+    fnsig::{callback_result_ok_type, FnSignature},
+    util::cfg_attrs,
+};
 use proc_macro2::{Ident, Span};
-use quote::ToTokens;
+use quote::{quote, ToTokens};
+use uniffi_meta::UniffiTraitDiscriminants;
 
-use super::attributes::{ExportAttributeArguments, ExportedImplFnAttributes};
+use super::{
+    attributes::{ExportAttributeArguments, ExportedImplFnAttributes},
+    trait_constant::{trait_constants, TraitConstant},
+};
 
 pub(super) enum ExportItem {
     Function {
@@ -19,7 +26,21 @@ pub(super) enum ExportItem {
     Trait {
         self_ident: Ident,
         items: Vec<ImplItem>,
+        constants: Vec<TraitConstant>,
         callback_interface: bool,
+        /// `#[cfg(...)]` attributes of the trait
+        cfg: Vec<syn::Attribute>,
+    },
+    Struct {
+        self_ident: Ident,
+        uniffi_traits: Vec<UniffiTraitDiscriminants>,
+    },
+    Constant {
+        ident: Ident,
+        ty: syn::Type,
+        value: syn::Expr,
+        /// `#[cfg(...)]` attributes of the constant
+        cfg: Vec<syn::Attribute>,
     },
 }
 
@@ -27,16 +48,47 @@ impl ExportItem {
     pub fn new(item: syn::Item, args: &ExportAttributeArguments) -> syn::Result<Self> {
         match item {
             syn::Item::Fn(item) => {
-                let sig = FnSignature::new_function(item.sig)?;
+                let attrs = ExportedImplFnAttributes::new(&item.attrs)?;
+                if attrs.constructor {
+                    return Err(syn::Error::new(
+                        Span::call_site(),
+                        "constructors must be in an impl block",
+                    ));
+                }
+                if let Some(on_error) = attrs.on_error {
+                    return Err(syn::Error::new_spanned(
+                        on_error,
+                        "on_error is only supported for callback interface methods",
+                    ));
+                }
+                if let Some(async_runtime) = attrs.async_runtime {
+                    return Err(syn::Error::new_spanned(
+                        async_runtime,
+                        "use `#[uniffi::export(async_runtime = ...)]` for functions",
+                    ));
+                }
+                let mut sig = FnSignature::new_function(item.sig)?;
+                sig.cfg = cfg_attrs(&item.attrs);
+                sig.set_sensitive(&attrs.sensitive)?;
                 Ok(Self::Function { sig })
             }
             syn::Item::Impl(item) => Self::from_impl(item),
             syn::Item::Trait(item) => Self::from_trait(item, args.callback_interface.is_some()),
-            // FIXME: Support const / static?
+            syn::Item::Struct(item) => Ok(Self::Struct {
+                self_ident: item.ident,
+                uniffi_traits: args.uniffi_traits(),
+            }),
+            syn::Item::Const(item) => Ok(Self::Constant {
+                cfg: cfg_attrs(&item.attrs),
+                ident: item.ident,
+                ty: *item.ty,
+                value: *item.expr,
+            }),
+            // FIXME: Support static?
             _ => Err(syn::Error::new(
                 Span::call_site(),
-                "unsupported item: only functions and impl \
-                 blocks may be annotated with this attribute",
+                "unsupported item: only functions, impl blocks, traits, \
+                 structs and constants may be annotated with this attribute",
             )),
         }
     }
@@ -68,6 +120,7 @@ impl ExportItem {
             }
         };
 
+        let impl_cfg = cfg_attrs(&item.attrs);
         let items = item
             .items
             .into_iter()
@@ -83,13 +136,36 @@ impl ExportItem {
                 };
 
                 let attrs = ExportedImplFnAttributes::new(&impl_fn.attrs)?;
+                if let Some(on_error) = attrs.on_error {
+                    return Err(syn::Error::new_spanned(
+                        on_error,
+                        "on_error is only supported for callback interface methods",
+                    ));
+                }
+                let mut sig = if attrs.constructor {
+                    FnSignature::new_constructor(self_ident.clone(), impl_fn.sig)?
+                } else {
+                    FnSignature::new_method(self_ident.clone(), impl_fn.sig)?
+                };
+                sig.cfg = impl_cfg
+                    .iter()
+                    .cloned()
+                    .chain(cfg_attrs(&impl_fn.attrs))
+                    .collect();
+                if let Some(async_runtime) = &attrs.async_runtime {
+                    if !sig.is_async {
+                        return Err(syn::Error::new_spanned(
+                            async_runtime,
+                            "this attribute is only allowed on async functions",
+                        ));
+                    }
+                }
+                sig.async_runtime = attrs.async_runtime;
+                sig.set_sensitive(&attrs.sensitive)?;
                 let item = if attrs.constructor {
-                    ImplItem::Constructor(FnSignature::new_constructor(
-                        self_ident.clone(),
-                        impl_fn.sig,
-                    )?)
+                    ImplItem::Constructor(sig)
                 } else {
-                    ImplItem::Method(FnSignature::new_method(self_ident.clone(), impl_fn.sig)?)
+                    ImplItem::Method(sig)
                 };
 
                 Ok(item)
@@ -111,9 +187,13 @@ impl ExportItem {
         }
 
         let self_ident = item.ident.to_owned();
+        let cfg = cfg_attrs(&item.attrs);
+        let constants = trait_constants(&item)?;
         let items = item
             .items
             .into_iter()
+            // Constants are moved out of the trait, see `trait_constant.rs`
+            .filter(|item| !matches!(item, syn::TraitItem::Const(_)))
             .enumerate()
             .map(|(i, item)| {
                 let tim = match item {
@@ -121,23 +201,70 @@ impl ExportItem {
                     _ => {
                         return Err(syn::Error::new_spanned(
                             item,
-                            "only fn's are supported in traits annotated with uniffi::export",
+                            "only fn's and consts are supported in traits annotated with uniffi::export",
                         ));
                     }
                 };
 
                 let attrs = ExportedImplFnAttributes::new(&tim.attrs)?;
+                if let Some(async_runtime) = attrs.async_runtime {
+                    return Err(syn::Error::new_spanned(
+                        async_runtime,
+                        "not supported for traits",
+                    ));
+                }
+                let method_cfg = cfg_attrs(&tim.attrs);
+                if callback_interface && !method_cfg.is_empty() {
+                    // The foreign side implements every method of the interface, so the methods
+                    // can't differ between builds.
+                    return Err(syn::Error::new_spanned(
+                        &method_cfg[0],
+                        "callback interface methods can't be gated with `cfg`",
+                    ));
+                }
                 let item = if attrs.constructor {
                     return Err(syn::Error::new_spanned(
                         tim,
                         "exported traits can not have constructors",
                     ));
                 } else {
-                    ImplItem::Method(FnSignature::new_trait_method(
-                        self_ident.clone(),
-                        tim.sig,
-                        i as u32,
-                    )?)
+                    // Only callback interfaces call foreign code that can raise unexpected errors
+                    let callback_result_ty = callback_result_ok_type(&tim.sig.output)
+                        .filter(|_| callback_interface)
+                        .map(|ty| quote! { #ty });
+                    let mut sig =
+                        FnSignature::new_trait_method(self_ident.clone(), tim.sig, i as u32)?;
+                    if attrs.on_error.is_some() && !callback_interface {
+                        return Err(syn::Error::new_spanned(
+                            attrs.on_error,
+                            "on_error is only supported for callback interface methods",
+                        ));
+                    }
+                    if attrs.on_error.is_some() && callback_result_ty.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            attrs.on_error,
+                            "on_error can't be used on methods that return `CallbackError`",
+                        ));
+                    }
+                    if callback_interface {
+                        if let Some(ident) = attrs.sensitive.first() {
+                            // The arguments are lowered by Rust and lifted by the foreign code
+                            return Err(syn::Error::new_spanned(
+                                ident,
+                                "sensitive is not supported for callback interface methods",
+                            ));
+                        }
+                    }
+                    sig.set_sensitive(&attrs.sensitive)?;
+                    sig.on_error = attrs.on_error;
+                    sig.callback_result_ty = callback_result_ty;
+                    sig.cfg = method_cfg;
+                    // Only callback interfaces need the default, Rust implementations of other
+                    // traits already have it
+                    if callback_interface {
+                        sig.default_body = tim.default;
+                    }
+                    ImplItem::Method(sig)
                 };
 
                 Ok(item)
@@ -147,7 +274,9 @@ impl ExportItem {
         Ok(Self::Trait {
             items,
             self_ident,
+            constants,
             callback_interface,
+            cfg,
         })
     }
 }
@@ -2,12 +2,18 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use std::iter;
 
-use super::attributes::{AsyncRuntime, ExportAttributeArguments};
-use crate::fnsig::{FnKind, FnSignature, NamedArg};
+use super::{
+    attributes::{AsyncRuntime, ExportAttributeArguments},
+    callback_interface::closure_items,
+};
+use crate::{
+    fnsig::{FnKind, FnSignature, NamedArg, ReceiverArg},
+    util::{create_metadata_items, ident_to_string},
+};
 
 pub(super) fn gen_fn_scaffolding(
     sig: FnSignature,
@@ -28,13 +34,77 @@ pub(super) fn gen_fn_scaffolding(
         }
     }
     let metadata_items = sig.metadata_items()?;
+    let sub_namespace_items = arguments
+        .namespace
+        .as_ref()
+        .map(|namespace| sub_namespace_metadata_items(&sig, &namespace.value()));
     let scaffolding_func = gen_ffi_function(&sig, arguments)?;
-    Ok(quote! {
+    let remote_func = gen_remote_function(&sig);
+    let closure_items = closure_items(&sig)?;
+    sig.cfg_gated(quote! {
         #scaffolding_func
+        #remote_func
         #metadata_items
+        #sub_namespace_items
+        #closure_items
     })
 }
 
+// The remote entry point of a top-level function, see `uniffi::remote_call()`
+//
+// Objects, callback interfaces and closures are handles, which only make sense in the process
+// that created them, so functions that pass them don't get one.
+fn gen_remote_function(sig: &FnSignature) -> TokenStream {
+    if sig.is_async || sig.returns_handle || sig.args.iter().any(|a| a.has_handle) {
+        return quote! {};
+    }
+    let ident = &sig.ident;
+    let remote_ident = Ident::new(
+        &uniffi_meta::remote_fn_symbol_name(&sig.mod_path, &sig.name),
+        Span::call_site(),
+    );
+    let name = &sig.name;
+    let return_ty = &sig.return_ty;
+    let arg_idents = sig.args.iter().map(|a| &a.ident);
+    let read_exprs = sig.args.iter().map(NamedArg::read_remote_expr);
+    let arg_exprs = sig.args.iter().map(NamedArg::remote_arg_expr);
+    quote! {
+        ::uniffi::remote_call_scaffolding! {
+            #[doc(hidden)]
+            #[no_mangle]
+            pub extern "C" fn #remote_ident(
+                uniffi_args: ::uniffi::RustBuffer,
+                call_status: &mut ::uniffi::RustCallStatus,
+            ) -> ::uniffi::RustBuffer {
+                ::uniffi::deps::log::debug!(#name);
+                ::uniffi::remote_call::<_, #return_ty, crate::UniFfiTag>(
+                    uniffi_args,
+                    call_status,
+                    |uniffi_args| (#(#read_exprs,)*),
+                    |(#(#arg_idents,)*)| #ident(#(#arg_exprs,)*),
+                )
+            }
+        }
+    }
+}
+
+// The metadata that puts a function in a sub-namespace, see `SubNamespaceMetadata`
+fn sub_namespace_metadata_items(sig: &FnSignature, namespace: &str) -> TokenStream {
+    let mod_path = &sig.mod_path;
+    let name = &sig.name;
+    create_metadata_items(
+        "sub_namespace",
+        name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::SUB_NAMESPACE)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat_str(#namespace)
+        },
+        None,
+    )
+}
+
 pub(super) fn gen_constructor_scaffolding(
     sig: FnSignature,
     arguments: &ExportAttributeArguments,
@@ -50,9 +120,11 @@ pub(super) fn gen_constructor_scaffolding(
     }
     let metadata_items = sig.metadata_items()?;
     let scaffolding_func = gen_ffi_function(&sig, arguments)?;
-    Ok(quote! {
+    let closure_items = closure_items(&sig)?;
+    sig.cfg_gated(quote! {
         #scaffolding_func
         #metadata_items
+        #closure_items
     })
 }
 
@@ -70,9 +142,11 @@ pub(super) fn gen_method_scaffolding(
     };
 
     let metadata_items = sig.metadata_items()?;
-    Ok(quote! {
+    let closure_items = closure_items(&sig)?;
+    sig.cfg_gated(quote! {
         #scaffolding_func
         #metadata_items
+        #closure_items
     })
 }
 
@@ -101,27 +175,52 @@ impl ScaffoldingBits {
 
     fn new_for_method(sig: &FnSignature, self_ident: &Ident, is_trait: bool) -> Self {
         let ident = &sig.ident;
-        let ffi_converter = if is_trait {
-            quote! {
-                <::std::sync::Arc<dyn #self_ident> as ::uniffi::FfiConverter<crate::UniFfiTag>>
-            }
+        let self_ty = if is_trait {
+            quote! { dyn #self_ident }
         } else {
-            quote! {
-                <::std::sync::Arc<#self_ident> as ::uniffi::FfiConverter<crate::UniFfiTag>>
-            }
+            quote! { #self_ident }
+        };
+        let ffi_converter = quote! {
+            <::uniffi::deps::alloc::sync::Arc<#self_ty> as ::uniffi::FfiConverter<crate::UniFfiTag>>
         };
         let params: Vec<_> = iter::once(quote! { uniffi_self_lowered: #ffi_converter::FfiType })
             .chain(sig.scaffolding_params())
             .collect();
         let param_lifts = sig.lift_exprs();
 
+        // Sync `&self` methods borrow the foreign code's reference for the duration of the call.
+        // Otherwise, lifting clones the `Arc`, so that async methods can keep it alive and
+        // `self: Arc<Self>` methods can take it.  For `self`, the foreign code has given up its own
+        // `Arc`, so take ownership of that instead, and move the object out of it.
+        let pre_fn_call = if sig.receiver == Some(ReceiverArg::Ref) && !sig.is_async {
+            quote! {
+                let uniffi_self = unsafe {
+                    <#self_ty as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lift_ref(uniffi_self_lowered)
+                };
+            }
+        } else if sig.receiver == Some(ReceiverArg::Value) {
+            let self_name = ident_to_string(self_ident);
+            quote! {
+                ::uniffi::untrack_handle(#self_name, uniffi_self_lowered as u64);
+                let uniffi_self = unsafe {
+                    ::uniffi::deps::alloc::sync::Arc::from_raw(uniffi_self_lowered as *const #self_ident)
+                };
+                let uniffi_self = ::uniffi::deps::alloc::sync::Arc::try_unwrap(uniffi_self).unwrap_or_else(|_| {
+                    ::core::panic!("Failed to consume 'self': the object is still referenced")
+                });
+            }
+        } else {
+            quote! {
+                let uniffi_self = ::uniffi::lift_arg::<::uniffi::deps::alloc::sync::Arc<#self_ty>, crate::UniFfiTag>(
+                    uniffi_self_lowered,
+                    "self",
+                );
+            }
+        };
+
         Self {
             params,
-            pre_fn_call: quote! {
-                let uniffi_self = #ffi_converter::try_lift(uniffi_self_lowered).unwrap_or_else(|err| {
-                    ::std::panic!("Failed to convert arg 'self': {}", err)
-                });
-            },
+            pre_fn_call,
             rust_fn_call: quote! { uniffi_self.#ident(#(#param_lifts,)*) },
         }
     }
@@ -143,7 +242,7 @@ impl ScaffoldingBits {
 ///
 /// `pre_fn_call` is the statements that we should execute before the rust call
 /// `rust_fn` is the Rust function to call.
-fn gen_ffi_function(
+pub(super) fn gen_ffi_function(
     sig: &FnSignature,
     arguments: &ExportAttributeArguments,
 ) -> syn::Result<TokenStream> {
@@ -160,6 +259,15 @@ fn gen_ffi_function(
         FnKind::Constructor { self_ident } => ScaffoldingBits::new_for_constructor(sig, self_ident),
     };
 
+    // The methods of a `serialized` impl block wait for the other calls to the object to finish
+    let pre_fn_call = match &sig.kind {
+        FnKind::Method { .. } if arguments.serialized.is_some() => quote! {
+            let _uniffi_call_guard = ::uniffi::lock_object_calls(uniffi_self_lowered);
+            #pre_fn_call
+        },
+        _ => pre_fn_call,
+    };
+
     let ffi_ident = sig.scaffolding_fn_ident()?;
     let name = &sig.name;
     let return_ty = &sig.return_ty;
@@ -168,6 +276,7 @@ fn gen_ffi_function(
         quote! {
             #[doc(hidden)]
             #[no_mangle]
+            #[allow(clippy::not_unsafe_ptr_arg_deref)]
             pub extern "C" fn #ffi_ident(
                 #(#params,)*
                 call_status: &mut ::uniffi::RustCallStatus,
@@ -180,10 +289,20 @@ fn gen_ffi_function(
             }
         }
     } else {
-        let mut future_expr = rust_fn_call;
-        if matches!(arguments.async_runtime, Some(AsyncRuntime::Tokio(_))) {
-            future_expr = quote! { ::uniffi::deps::async_compat::Compat::new(#future_expr) }
-        }
+        // The runtime of a method overrides the one of its `impl` block
+        let future_expr = match sig
+            .async_runtime
+            .as_ref()
+            .or(arguments.async_runtime.as_ref())
+        {
+            Some(AsyncRuntime::Tokio(_)) => {
+                quote! { <::uniffi::TokioRuntime as ::uniffi::AsyncRuntime>::wrap(#rust_fn_call) }
+            }
+            Some(AsyncRuntime::Custom(path)) => {
+                quote! { <#path as ::uniffi::AsyncRuntime>::wrap(#rust_fn_call) }
+            }
+            Some(AsyncRuntime::None(_)) | None => rust_fn_call,
+        };
 
         quote! {
             #[doc(hidden)]
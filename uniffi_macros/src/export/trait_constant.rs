@@ -0,0 +1,251 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Associated constants of exported traits
+//!
+//! Traits with associated constants can't be used as trait objects, so the constants are moved
+//! out of the trait into an inherent impl on `dyn Trait`, where Rust code can still read them as
+//! `<dyn Trait>::NAME`.  Callback interfaces also get a provided method for each constant, which
+//! reads the value from the foreign implementation.
+
+use crate::util::{cfg_attrs, create_metadata_items, ident_to_string};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Attribute, Expr, ExprLit, ExprUnary, Ident, ItemTrait, Lit, TraitItem, UnOp};
+
+pub(super) struct TraitConstant {
+    ident: Ident,
+    attrs: Vec<Attribute>,
+    ty: syn::Type,
+    value: Expr,
+    // Index of the constant, used to read it from callback interface implementations
+    index: u32,
+    // `&str` constants are passed as a `String`
+    is_str: bool,
+}
+
+impl TraitConstant {
+    fn new(item: &syn::TraitItemConst, index: u32) -> syn::Result<Self> {
+        if !item.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &item.generics,
+                "generic constants are not supported in traits annotated with uniffi::export",
+            ));
+        }
+        let value = match &item.default {
+            Some((_, value)) => value.clone(),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    "constants in traits annotated with uniffi::export need a value",
+                ));
+            }
+        };
+        Ok(Self {
+            ident: item.ident.clone(),
+            attrs: item.attrs.clone(),
+            ty: item.ty.clone(),
+            value,
+            index,
+            is_str: is_str_type(&item.ty),
+        })
+    }
+
+    /// The type used to pass the constant over the FFI
+    fn ffi_type(&self) -> TokenStream {
+        if self.is_str {
+            quote! { ::uniffi::deps::alloc::string::String }
+        } else {
+            self.ty.to_token_stream()
+        }
+    }
+
+    /// The provided method that callback interfaces get for reading this constant
+    fn reader_ident(&self) -> Ident {
+        Ident::new(
+            &ident_to_string(&self.ident).to_ascii_lowercase(),
+            self.ident.span(),
+        )
+    }
+
+    /// Declaration of the provided method used to read the constant, for callback interfaces
+    pub(super) fn reader_method(&self, trait_ident: &Ident) -> TokenStream {
+        let ident = &self.ident;
+        let reader_ident = self.reader_ident();
+        let ffi_type = self.ffi_type();
+        let doc = format!(
+            "The value of `{ident}` for this implementation, foreign implementations report the value \
+             from their bindings"
+        );
+        let value = if self.is_str {
+            quote! { ::uniffi::deps::alloc::string::String::from(<dyn #trait_ident>::#ident) }
+        } else {
+            quote! { <dyn #trait_ident>::#ident }
+        };
+        quote! {
+            #[doc = #doc]
+            fn #reader_ident(&self) -> #ffi_type {
+                #value
+            }
+        }
+    }
+
+    /// Implementation of the reader method for the foreign implementations of a callback interface
+    pub(super) fn reader_method_impl(&self, internals_ident: &Ident) -> TokenStream {
+        let reader_ident = self.reader_ident();
+        let ffi_type = self.ffi_type();
+        let index = self.index;
+        quote! {
+            fn #reader_ident(&self) -> #ffi_type {
+                let mut uniffi_args_buf = ::uniffi::deps::alloc::vec::Vec::new();
+                <u32 as ::uniffi::FfiConverter<crate::UniFfiTag>>::write(#index, &mut uniffi_args_buf);
+                let uniffi_args_rbuf = ::uniffi::RustBuffer::from_vec(uniffi_args_buf);
+                #internals_ident.invoke_callback::<#ffi_type, crate::UniFfiTag>(
+                    self.handle, ::uniffi::IDX_CALLBACK_CONSTANT, uniffi_args_rbuf
+                )
+            }
+        }
+    }
+
+    pub(super) fn metadata_items(&self, trait_name: &str, module_path: &str) -> TokenStream {
+        let name = ident_to_string(&self.ident);
+        let ffi_type = self.ffi_type();
+        let index = self.index;
+        let concat_value = match value_concat_calls(&self.value) {
+            Ok(tokens) => tokens,
+            Err(e) => return e.into_compile_error(),
+        };
+        create_metadata_items(
+            "trait_constant",
+            &format!("{trait_name}_{name}"),
+            quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_CONSTANT)
+                    .concat_str(#module_path)
+                    .concat_str(#trait_name)
+                    .concat_u32(#index)
+                    .concat_str(#name)
+                    .concat(<#ffi_type as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+                    #concat_value
+            },
+            None,
+        )
+    }
+}
+
+/// Get the constants declared in an exported trait
+pub(super) fn trait_constants(item: &ItemTrait) -> syn::Result<Vec<TraitConstant>> {
+    item.items
+        .iter()
+        .filter_map(|item| match item {
+            TraitItem::Const(c) => Some(c),
+            _ => None,
+        })
+        .enumerate()
+        .map(|(i, c)| TraitConstant::new(c, i as u32))
+        .collect()
+}
+
+/// Output an exported trait, with its constants moved to an inherent impl on `dyn Trait`
+pub(super) fn rewrite_trait(
+    item: &ItemTrait,
+    constants: &[TraitConstant],
+    callback_interface: bool,
+) -> TokenStream {
+    let mut item = item.clone();
+    item.items.retain(|i| !matches!(i, TraitItem::Const(_)));
+    if callback_interface {
+        for c in constants {
+            item.items
+                .push(syn::parse2(c.reader_method(&item.ident)).expect("invalid reader method"));
+        }
+    }
+
+    let vis = &item.vis;
+    let trait_ident = &item.ident;
+    let cfg = cfg_attrs(&item.attrs);
+    let consts = constants.iter().map(|c| {
+        let TraitConstant {
+            ident,
+            attrs,
+            ty,
+            value,
+            ..
+        } = c;
+        quote! {
+            #(#attrs)*
+            #vis const #ident: #ty = #value;
+        }
+    });
+    let inherent_impl = (!constants.is_empty()).then(|| {
+        quote! {
+            #(#cfg)*
+            impl dyn #trait_ident {
+                #(#consts)*
+            }
+        }
+    });
+
+    quote! {
+        #item
+        #inherent_impl
+    }
+}
+
+/// Is `ty` a `&str`?
+pub(super) fn is_str_type(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Reference(r) if matches!(&*r.elem, syn::Type::Path(p) if p.path.is_ident("str"))
+    )
+}
+
+/// `MetadataBuffer` calls that add the literal `value` to the metadata
+pub(super) fn value_concat_calls(value: &Expr) -> syn::Result<TokenStream> {
+    let (lit, negative) = match value {
+        Expr::Lit(ExprLit { lit, .. }) => (lit, false),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match &**expr {
+            Expr::Lit(ExprLit { lit, .. }) => (lit, true),
+            _ => return Err(not_a_literal(value)),
+        },
+        Expr::Group(g) => return value_concat_calls(&g.expr),
+        _ => return Err(not_a_literal(value)),
+    };
+    let sign = if negative { "-" } else { "" };
+    Ok(match lit {
+        Lit::Int(i) => {
+            let digits = format!("{sign}{}", i.base10_digits());
+            quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_INT)
+                .concat_str(#digits)
+            }
+        }
+        Lit::Float(f) => {
+            let digits = format!("{sign}{}", f.base10_digits());
+            quote! {
+                .concat_value(::uniffi::metadata::codes::LIT_FLOAT)
+                .concat_str(#digits)
+            }
+        }
+        Lit::Str(s) if !negative => quote! {
+            .concat_value(::uniffi::metadata::codes::LIT_STR)
+            .concat_str(#s)
+        },
+        Lit::Bool(b) if !negative => quote! {
+            .concat_value(::uniffi::metadata::codes::LIT_BOOL)
+            .concat_bool(#b)
+        },
+        _ => return Err(not_a_literal(value)),
+    })
+}
+
+fn not_a_literal(value: &Expr) -> syn::Error {
+    syn::Error::new_spanned(
+        value,
+        "the value of an exported constant must be a string, integer, float or bool literal",
+    )
+}
@@ -0,0 +1,167 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use uniffi_meta::UniffiTraitDiscriminants;
+
+use super::{attributes::ExportAttributeArguments, scaffolding::gen_ffi_function};
+use crate::{
+    fnsig::FnSignature,
+    util::{create_metadata_items, ident_to_string},
+};
+
+/// Generate the scaffolding and metadata for the Rust traits exported with
+/// `#[uniffi::export(Debug, Display, Eq, Hash, Ord, Restore)]`.
+///
+/// Each trait is forwarded by a hidden inherent method on the object type, which gets a regular
+/// method scaffolding function.  The metadata for that method is packed inside the metadata for
+/// the trait, so that the bindings can map it to the corresponding foreign method (`toString()`,
+/// `__eq__`, etc.) rather than exposing it as a normal method.
+///
+/// `Restore` also needs a static function to create the object from a token, which is generated
+/// as a hidden free function and packed inside the trait metadata the same way.
+pub(super) fn expand_uniffi_trait_export(
+    self_ident: Ident,
+    uniffi_traits: Vec<UniffiTraitDiscriminants>,
+) -> syn::Result<TokenStream> {
+    let mut impl_items = Vec::new();
+    let mut global_items = Vec::new();
+    let object_name = ident_to_string(&self_ident);
+    for trait_id in uniffi_traits {
+        let (assertion, methods) = match trait_id {
+            UniffiTraitDiscriminants::Debug => (
+                quote! { ::core::fmt::Debug },
+                vec![quote! {
+                    fn uniffi_trait_debug(&self) -> ::uniffi::deps::alloc::string::String {
+                        ::uniffi::deps::alloc::format!("{:?}", self)
+                    }
+                }],
+            ),
+            UniffiTraitDiscriminants::Display => (
+                quote! { ::core::fmt::Display },
+                vec![quote! {
+                    fn uniffi_trait_display(&self) -> ::uniffi::deps::alloc::string::String {
+                        ::uniffi::deps::alloc::format!("{}", self)
+                    }
+                }],
+            ),
+            UniffiTraitDiscriminants::Eq => (
+                quote! { ::core::cmp::PartialEq },
+                vec![
+                    quote! {
+                        fn uniffi_trait_eq_eq(&self, other: ::uniffi::deps::alloc::sync::Arc<#self_ident>) -> bool {
+                            ::core::cmp::PartialEq::eq(self, &*other)
+                        }
+                    },
+                    quote! {
+                        fn uniffi_trait_eq_ne(&self, other: ::uniffi::deps::alloc::sync::Arc<#self_ident>) -> bool {
+                            ::core::cmp::PartialEq::ne(self, &*other)
+                        }
+                    },
+                ],
+            ),
+            UniffiTraitDiscriminants::Hash => (
+                quote! { ::core::hash::Hash },
+                vec![quote! {
+                    fn uniffi_trait_hash(&self) -> u64 {
+                        let mut s = ::std::collections::hash_map::DefaultHasher::new();
+                        ::core::hash::Hash::hash(self, &mut s);
+                        ::core::hash::Hasher::finish(&s)
+                    }
+                }],
+            ),
+            UniffiTraitDiscriminants::Ord => (
+                quote! { ::core::cmp::Ord },
+                vec![quote! {
+                    fn uniffi_trait_ord_cmp(&self, other: ::uniffi::deps::alloc::sync::Arc<#self_ident>) -> i8 {
+                        // `Ordering` is `repr(i8)`, with `Less` as -1 and `Greater` as 1.
+                        ::core::cmp::Ord::cmp(self, &*other) as i8
+                    }
+                }],
+            ),
+            UniffiTraitDiscriminants::Restore => (
+                quote! { ::uniffi::Restorable },
+                vec![quote! {
+                    fn uniffi_trait_restore_serialize(&self) -> ::uniffi::deps::alloc::string::String {
+                        ::uniffi::serialize_handle(#object_name, self)
+                    }
+                }],
+            ),
+        };
+        let functions = match trait_id {
+            UniffiTraitDiscriminants::Restore => {
+                let ident = format_ident!("uniffi_trait_restore_{}", self_ident);
+                vec![quote! {
+                    #[doc(hidden)]
+                    #[allow(non_snake_case)]
+                    fn #ident(
+                        token: ::uniffi::deps::alloc::string::String,
+                    ) -> ::core::option::Option<::uniffi::deps::alloc::sync::Arc<#self_ident>> {
+                        ::uniffi::restore_handle(#object_name, &token)
+                    }
+                }]
+            }
+            _ => vec![],
+        };
+
+        let mut method_metas = Vec::new();
+        for method in methods {
+            let (ffi_func, method_meta) = process_uniffi_trait_method(&method, &self_ident)?;
+            impl_items.push(method);
+            global_items.push(ffi_func);
+            method_metas.push(method_meta);
+        }
+        for function in functions {
+            let (ffi_func, function_meta) = process_uniffi_trait_function(&function)?;
+            global_items.push(function);
+            global_items.push(ffi_func);
+            method_metas.push(function_meta);
+        }
+
+        let discr = trait_id as u8;
+        global_items.push(quote! {
+            ::uniffi::deps::static_assertions::assert_impl_all!(#self_ident: #assertion);
+        });
+        global_items.push(create_metadata_items(
+            "uniffi_trait",
+            &format!("{object_name}_{trait_id:?}"),
+            quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::UNIFFI_TRAIT)
+                    .concat_value(#discr)
+                    #(.concat(#method_metas))*
+            },
+            None,
+        ));
+    }
+
+    Ok(quote! {
+        #[doc(hidden)]
+        impl #self_ident {
+            #(#impl_items)*
+        }
+        #(#global_items)*
+    })
+}
+
+/// Generate the scaffolding function and the metadata expression for a trait method
+fn process_uniffi_trait_method(
+    method: &TokenStream,
+    self_ident: &Ident,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let item: syn::ItemFn = syn::parse2(method.clone())?;
+    let sig = FnSignature::new_method(self_ident.clone(), item.sig)?;
+    let ffi_func = gen_ffi_function(&sig, &ExportAttributeArguments::default())?;
+    Ok((ffi_func, sig.metadata_expr()?))
+}
+
+/// Generate the scaffolding function and the metadata expression for a trait's static function
+fn process_uniffi_trait_function(
+    function: &TokenStream,
+) -> syn::Result<(TokenStream, TokenStream)> {
+    let item: syn::ItemFn = syn::parse2(function.clone())?;
+    let sig = FnSignature::new_function(item.sig)?;
+    let ffi_func = gen_ffi_function(&sig, &ExportAttributeArguments::default())?;
+    Ok((ffi_func, sig.metadata_expr()?))
+}
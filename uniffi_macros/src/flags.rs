@@ -0,0 +1,130 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{parse::ParseStream, punctuated::Punctuated, Data, DeriveInput, Token};
+
+use crate::util::{
+    create_metadata_items, ident_to_string, mod_path, source_location_items, tagged_impl_header,
+    try_metadata_value_from_usize, AttributeSliceExt, UniffiAttributeArgs,
+};
+
+/// Generate the `FfiConverter` and the metadata for a set of bit flags
+///
+/// This doesn't look at the fields of the struct, instead it relies on the API that the
+/// `bitflags` crate generates: an associated constant for each flag, `const fn bits()` and
+/// `from_bits_truncate()`.  Derives can't see associated constants, so the flag names are listed
+/// in a `#[uniffi(flags(...))]` attribute.
+pub fn expand_flags(input: DeriveInput) -> syn::Result<TokenStream> {
+    if !matches!(input.data, Data::Struct(_)) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "This derive must only be used on structs",
+        ));
+    }
+
+    let ident = &input.ident;
+    let attr: FlagsAttr = input.attrs.parse_uniffi_attr_args()?;
+    let flags = match attr.flags {
+        Some(flags) => flags,
+        None => {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "list the flags with `#[uniffi(flags(...))]`",
+            ))
+        }
+    };
+    let name = ident_to_string(ident);
+    let mod_path = mod_path()?;
+    let impl_spec = tagged_impl_header("FfiConverter", ident, None);
+
+    let flags_len =
+        try_metadata_value_from_usize(flags.len(), "UniFFI limits flags to 256 values")?;
+    let flag_metadata = flags.iter().map(|flag| {
+        let flag_name = ident_to_string(flag);
+        quote! {
+            .concat_str(#flag_name)
+            .concat_u32(#ident::#flag.bits())
+        }
+    });
+    let metadata_items = create_metadata_items(
+        "flags",
+        &name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::FLAGS)
+                .concat_str(#mod_path)
+                .concat_str(#name)
+                .concat_value(#flags_len)
+                #(#flag_metadata)*
+        },
+        None,
+    );
+    let location_items = source_location_items("flags", &name, &mod_path, &name, ident.span());
+
+    Ok(quote! {
+        #[automatically_derived]
+        unsafe #impl_spec {
+            ::uniffi::ffi_converter_default_return!(crate::UniFfiTag);
+
+            type FfiType = ::core::primitive::u32;
+
+            fn lower(obj: Self) -> Self::FfiType {
+                obj.bits()
+            }
+
+            fn try_lift(v: Self::FfiType) -> ::uniffi::deps::anyhow::Result<Self> {
+                ::core::result::Result::Ok(Self::from_bits_truncate(v))
+            }
+
+            fn write(obj: Self, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
+                ::uniffi::deps::bytes::BufMut::put_u32(buf, obj.bits());
+            }
+
+            fn try_read(buf: &mut &[::core::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+                ::uniffi::check_remaining(buf, 4)?;
+                ::core::result::Result::Ok(Self::from_bits_truncate(
+                    ::uniffi::deps::bytes::Buf::get_u32(buf),
+                ))
+            }
+
+            const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_FLAGS)
+                .concat_str(#mod_path)
+                .concat_str(#name);
+        }
+
+        #metadata_items
+        #location_items
+    })
+}
+
+mod kw {
+    syn::custom_keyword!(flags);
+}
+
+#[derive(Default)]
+struct FlagsAttr {
+    flags: Option<Vec<Ident>>,
+}
+
+impl UniffiAttributeArgs for FlagsAttr {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let kw = input.parse::<kw::flags>()?;
+        let content;
+        syn::parenthesized!(content in input);
+        let flags = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+        if flags.is_empty() {
+            return Err(syn::Error::new(kw.span, "expected at least one flag"));
+        }
+        Ok(Self {
+            flags: Some(flags.into_iter().collect()),
+        })
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        match (self.flags, other.flags) {
+            (Some(_), Some(flags)) => Err(syn::Error::new_spanned(
+                &flags[0],
+                "the flags can only be listed once",
+            )),
+            (a, b) => Ok(Self { flags: a.or(b) }),
+        }
+    }
+}
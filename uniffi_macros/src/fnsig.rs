@@ -2,12 +2,19 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use crate::util::{
-    create_metadata_items, ident_to_string, mod_path, try_metadata_value_from_usize,
+use crate::{
+    export::AsyncRuntime,
+    util::{
+        cfg_gated_items, create_metadata_items, ident_to_string, mod_path, source_location_items,
+        try_metadata_value_from_usize,
+    },
 };
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::{spanned::Spanned, FnArg, Ident, Pat, Receiver, ReturnType};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_quote, spanned::Spanned, FnArg, GenericArgument, Ident, Pat, PathArguments, Receiver,
+    ReturnType,
+};
 
 pub(crate) struct FnSignature {
     pub kind: FnKind,
@@ -19,6 +26,21 @@ pub(crate) struct FnSignature {
     pub receiver: Option<ReceiverArg>,
     pub args: Vec<NamedArg>,
     pub return_ty: TokenStream,
+    /// Does the return type contain a handle, see `contains_handle()`
+    pub returns_handle: bool,
+    /// Fallback value for callback interface methods, see `ExportedImplFnAttributes::on_error`
+    pub on_error: Option<syn::Expr>,
+    /// For callback interface methods that return `Result<T, CallbackError>`, the type `T`.  The
+    /// foreign code sees these methods as returning `T`, and unexpected errors become `Err`
+    pub callback_result_ty: Option<TokenStream>,
+    /// `#[cfg(...)]` attributes of the function, and of the `impl` block for methods and
+    /// constructors
+    pub cfg: Vec<syn::Attribute>,
+    /// Body of a provided method of a callback interface, which foreign implementations can
+    /// fall back to
+    pub default_body: Option<syn::Block>,
+    /// Runtime of an async method, see `ExportedImplFnAttributes::async_runtime`
+    pub async_runtime: Option<AsyncRuntime>,
 }
 
 impl FnSignature {
@@ -45,9 +67,9 @@ impl FnSignature {
     pub(crate) fn new(kind: FnKind, sig: syn::Signature) -> syn::Result<Self> {
         let span = sig.span();
         let ident = sig.ident;
-        let output = match sig.output {
-            ReturnType::Default => quote! { () },
-            ReturnType::Type(_, ty) => quote! { #ty },
+        let (output, returns_handle) = match sig.output {
+            ReturnType::Default => (quote! { () }, false),
+            ReturnType::Type(_, ty) => (quote! { #ty }, contains_handle(&ty)),
         };
         let is_async = sig.asyncness.is_some();
 
@@ -69,7 +91,22 @@ impl FnSignature {
                 }) => r,
                 _ => unreachable!(),
             });
-        let args = input_iter
+        if receiver == Some(ReceiverArg::Value) {
+            if is_async {
+                return Err(syn::Error::new(
+                    span,
+                    "Async methods can't take `self` by value",
+                ));
+            }
+            if matches!(kind, FnKind::TraitMethod { .. }) {
+                // Trait objects are unsized, so they can't be moved out of their `Arc`
+                return Err(syn::Error::new(
+                    span,
+                    "Exported trait methods can't take `self` by value",
+                ));
+            }
+        }
+        let mut args = input_iter
             .map(|a| {
                 a.and_then(|a| match a.kind {
                     ArgKind::Named(named) => Ok(named),
@@ -80,6 +117,40 @@ impl FnSignature {
             })
             .collect::<syn::Result<Vec<_>>>()?;
 
+        // Name the callback interfaces of closure arguments after the function, so that they're
+        // unique in the crate
+        let fn_prefix = match &kind {
+            FnKind::Function => upper_camel_case(&ident_to_string(&ident)),
+            FnKind::Method { self_ident }
+            | FnKind::Constructor { self_ident }
+            | FnKind::TraitMethod { self_ident, .. } => format!(
+                "{}{}",
+                ident_to_string(self_ident),
+                upper_camel_case(&ident_to_string(&ident))
+            ),
+        };
+        for closure in args.iter_mut().filter_map(|a| a.closure.as_mut()) {
+            closure.trait_ident = Ident::new(
+                &format!("{fn_prefix}{}", closure.trait_ident),
+                Span::call_site(),
+            );
+        }
+
+        if is_async {
+            if let Some(arg) = args.iter().find(|a| a.borrowed_bytes) {
+                return Err(syn::Error::new_spanned(
+                    &arg.ident,
+                    "Async functions can't borrow bytes, since the call outlives the borrow. Use `Vec<u8>` instead",
+                ));
+            }
+            if let Some(arg) = args.iter().find(|a| a.borrowed_object.is_some()) {
+                return Err(syn::Error::new_spanned(
+                    &arg.ident,
+                    "Async functions can't borrow objects, since the call outlives the borrow. Use `Arc<T>` instead",
+                ));
+            }
+        }
+
         Ok(Self {
             kind,
             span,
@@ -90,9 +161,39 @@ impl FnSignature {
             receiver,
             args,
             return_ty: output,
+            returns_handle,
+            on_error: None,
+            callback_result_ty: None,
+            cfg: Vec::new(),
+            default_body: None,
+            async_runtime: None,
         })
     }
 
+    /// Mark the arguments named by a `#[uniffi::sensitive(...)]` attribute
+    pub(crate) fn set_sensitive(&mut self, idents: &[Ident]) -> syn::Result<()> {
+        for ident in idents {
+            let arg = match self.args.iter_mut().find(|a| a.ident == *ident) {
+                Some(arg) => arg,
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "not an argument of this function",
+                    ))
+                }
+            };
+            if arg.borrowed_object.is_some() || arg.closure.is_some() {
+                // Objects and closures are passed as handles, there's no data to wipe
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "objects and closures can't be sensitive",
+                ));
+            }
+            arg.sensitive = true;
+        }
+        Ok(())
+    }
+
     /// Lift expressions for each of our arguments
     pub fn lift_exprs(&self) -> impl Iterator<Item = TokenStream> + '_ {
         self.args.iter().map(NamedArg::lift_expr)
@@ -132,8 +233,8 @@ impl FnSignature {
         self.args.iter().map(NamedArg::scaffolding_param)
     }
 
-    /// Generate metadata items for this function
-    pub(crate) fn metadata_items(&self) -> syn::Result<TokenStream> {
+    /// Generate the metadata expression for this function
+    pub(crate) fn metadata_expr(&self) -> syn::Result<TokenStream> {
         let Self {
             name,
             return_ty,
@@ -148,78 +249,120 @@ impl FnSignature {
             "UniFFI limits functions to 256 arguments",
         )?;
         let arg_metadata_calls = self.args.iter().map(NamedArg::metadata_calls);
+        let takes_self_by_arc = self.receiver == Some(ReceiverArg::Arc);
+        let takes_self_by_value = self.receiver == Some(ReceiverArg::Value);
 
         match &self.kind {
-            FnKind::Function => Ok(create_metadata_items(
-                "func",
-                name,
-                quote! {
-                    ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::FUNC)
+            FnKind::Function => Ok(quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::FUNC)
+                    .concat_str(#mod_path)
+                    .concat_str(#name)
+                    .concat_bool(#is_async)
+                    .concat_value(#args_len)
+                    #(#arg_metadata_calls)*
+                    .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+            }),
+
+            FnKind::Method { self_ident } => {
+                let object_name = ident_to_string(self_ident);
+                Ok(quote! {
+                    ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::METHOD)
                         .concat_str(#mod_path)
+                        .concat_str(#object_name)
                         .concat_str(#name)
                         .concat_bool(#is_async)
+                        .concat_bool(#takes_self_by_arc)
+                        .concat_bool(#takes_self_by_value)
                         .concat_value(#args_len)
                         #(#arg_metadata_calls)*
                         .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
-                },
-                Some(self.checksum_symbol_name()),
-            )),
-
-            FnKind::Method { self_ident } => {
-                let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
-                    "method",
-                    &format!("{object_name}_{name}"),
-                    quote! {
-                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::METHOD)
-                            .concat_str(#mod_path)
-                            .concat_str(#object_name)
-                            .concat_str(#name)
-                            .concat_bool(#is_async)
-                            .concat_value(#args_len)
-                            #(#arg_metadata_calls)*
-                            .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
-                    },
-                    Some(self.checksum_symbol_name()),
-                ))
+                })
             }
 
             FnKind::TraitMethod { self_ident, index } => {
                 let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
-                    "method",
-                    &format!("{object_name}_{name}"),
-                    quote! {
-                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_METHOD)
-                            .concat_str(#mod_path)
-                            .concat_str(#object_name)
-                            .concat_u32(#index)
-                            .concat_str(#name)
-                            .concat_bool(#is_async)
-                            .concat_value(#args_len)
-                            #(#arg_metadata_calls)*
-                            .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
-                    },
-                    Some(self.checksum_symbol_name()),
-                ))
+                let has_default = self.default_body.is_some();
+                let return_ty = self.callback_result_ty.as_ref().unwrap_or(return_ty);
+                Ok(quote! {
+                    ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TRAIT_METHOD)
+                        .concat_str(#mod_path)
+                        .concat_str(#object_name)
+                        .concat_u32(#index)
+                        .concat_str(#name)
+                        .concat_bool(#is_async)
+                        .concat_bool(#has_default)
+                        .concat_bool(#takes_self_by_arc)
+                        .concat_value(#args_len)
+                        #(#arg_metadata_calls)*
+                        .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+                })
             }
 
             FnKind::Constructor { self_ident } => {
                 let object_name = ident_to_string(self_ident);
-                Ok(create_metadata_items(
+                Ok(quote! {
+                    ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONSTRUCTOR)
+                        .concat_str(#mod_path)
+                        .concat_str(#object_name)
+                        .concat_str(#name)
+                        .concat_value(#args_len)
+                        #(#arg_metadata_calls)*
+                        .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
+                })
+            }
+        }
+    }
+
+    /// Generate metadata items for this function
+    pub(crate) fn metadata_items(&self) -> syn::Result<TokenStream> {
+        let (kind, name, location_name) = self.metadata_names();
+        let metadata_items = create_metadata_items(
+            kind,
+            &name,
+            self.metadata_expr()?,
+            Some(self.checksum_symbol_name()),
+        );
+        let location_items =
+            source_location_items(kind, &name, &self.mod_path, &location_name, self.span);
+        Ok(quote! {
+            #metadata_items
+            #location_items
+        })
+    }
+
+    /// Compile the scaffolding and metadata for this function only when the function is compiled
+    pub(crate) fn cfg_gated(&self, tokens: TokenStream) -> syn::Result<TokenStream> {
+        let (kind, name, location_name) = self.metadata_names();
+        cfg_gated_items(
+            kind,
+            &name,
+            &self.mod_path,
+            &location_name,
+            &self.cfg,
+            tokens,
+        )
+    }
+
+    // The kind of the metadata items, the name used in their symbols and the item name used in
+    // the location and cfg metadata
+    fn metadata_names(&self) -> (&'static str, String, String) {
+        match &self.kind {
+            FnKind::Function => ("func", self.name.clone(), self.name.clone()),
+            FnKind::Method { self_ident } | FnKind::TraitMethod { self_ident, .. } => {
+                let self_name = ident_to_string(self_ident);
+                (
+                    "method",
+                    format!("{self_name}_{}", self.name),
+                    format!("{self_name}.{}", self.name),
+                )
+            }
+            FnKind::Constructor { self_ident } => {
+                let self_name = ident_to_string(self_ident);
+                (
                     "constructor",
-                    &format!("{object_name}_{name}"),
-                    quote! {
-                        ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CONSTRUCTOR)
-                            .concat_str(#mod_path)
-                            .concat_str(#object_name)
-                            .concat_str(#name)
-                            .concat_value(#args_len)
-                            #(#arg_metadata_calls)*
-                            .concat(<#return_ty as ::uniffi::FfiConverter<crate::UniFfiTag>>::TYPE_ID_META)
-                    },
-                    Some(self.checksum_symbol_name()),
-                ))
+                    format!("{self_name}_{}", self.name),
+                    format!("{self_name}.{}", self.name),
+                )
             }
         }
     }
@@ -269,50 +412,172 @@ impl TryFrom<FnArg> for Arg {
         let kind = match syn_arg {
             FnArg::Typed(p) => match *p.pat {
                 Pat::Ident(i) => {
-                    let ty = *p.ty;
-                    let ty = quote! { #ty };
                     if i.ident == "self" {
-                        Ok(ArgKind::Receiver(ReceiverArg))
+                        Ok(ArgKind::Receiver(ReceiverArg::Ref))
                     } else {
-                        Ok(ArgKind::Named(NamedArg::new(i.ident, ty)))
+                        NamedArg::new(i.ident, &p.ty).map(ArgKind::Named)
                     }
                 }
                 _ => Err(syn::Error::new_spanned(p, "Argument name missing")),
             },
-            FnArg::Receiver(Receiver { .. }) => Ok(ArgKind::Receiver(ReceiverArg)),
+            FnArg::Receiver(r) => ReceiverArg::new(&r).map(ArgKind::Receiver),
         }?;
 
         Ok(Self { span, kind })
     }
 }
 
-// A unit struct is kind of silly now, but eventually we may want to differentiate between methods
-// that input &self vs Arc<Self
-pub(crate) struct ReceiverArg;
+/// How a method takes `self`
+///
+/// For `&self` and `self: Arc<Self>`, the scaffolding lifts an `Arc` clone of the object.  For
+/// `self`, the foreign code hands over its own reference, which must be the last one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReceiverArg {
+    /// `&self`
+    Ref,
+    /// `self: Arc<Self>`, the method gets its own reference to the object
+    Arc,
+    /// `self`, the method consumes the object and the foreign code can't use it anymore
+    Value,
+}
+
+impl ReceiverArg {
+    fn new(receiver: &Receiver) -> syn::Result<Self> {
+        match &*receiver.ty {
+            syn::Type::Reference(r) if r.mutability.is_none() && is_self(&r.elem) => Ok(Self::Ref),
+            ty if is_arc_self(ty) => Ok(Self::Arc),
+            ty if is_self(ty) => Ok(Self::Value),
+            _ => Err(syn::Error::new_spanned(
+                receiver,
+                "exported methods must take `&self`, `self: Arc<Self>` or `self`",
+            )),
+        }
+    }
+}
+
+fn is_self(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("Self"))
+}
+
+// Matches `Arc<Self>`, with or without the path to `Arc`
+fn is_arc_self(ty: &syn::Type) -> bool {
+    let last = match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+        _ => None,
+    };
+    match last {
+        Some(last) if last.ident == "Arc" => match &last.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                matches!(&args.args[0], GenericArgument::Type(ty) if is_self(ty))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
 
 pub(crate) struct NamedArg {
     pub(crate) ident: Ident,
     pub(crate) name: String,
     pub(crate) ty: TokenStream,
+    /// `&[u8]` args are passed as a pointer to the foreign bytes, rather than being copied into a
+    /// `RustBuffer`
+    pub(crate) borrowed_bytes: bool,
+    /// For `&T` args, the object type `T`.  These borrow the foreign code's reference to the
+    /// object for the duration of the call, rather than lifting a clone of its `Arc`
+    pub(crate) borrowed_object: Option<TokenStream>,
+    /// For `impl Fn(...)`, `Box<dyn Fn(...)>` and `ProgressSink` args, the closure's signature
+    pub(crate) closure: Option<ClosureArg>,
+    /// Set by `#[uniffi::sensitive(...)]`.  The buffer the arg is lifted from is wiped.
+    pub(crate) sensitive: bool,
+    /// Does the type contain a handle, see `contains_handle()`
+    pub(crate) has_handle: bool,
+}
+
+/// The signature of a closure argument
+///
+/// The foreign code passes the closure as a callback interface, which is generated for the
+/// argument with a single `invoke` method.
+pub(crate) struct ClosureArg {
+    /// The callback interface trait, named after the function and the argument
+    pub(crate) trait_ident: Ident,
+    pub(crate) inputs: Vec<syn::Type>,
+    pub(crate) output: ReturnType,
+    pub(crate) kind: ClosureKind,
+}
+
+/// How the Rust function takes a closure argument
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClosureKind {
+    /// `impl Fn(...)`
+    Impl,
+    /// `Box<dyn Fn(...)>`
+    Boxed,
+    /// `uniffi::ProgressSink`, which wraps a `Fn(u64, u64)` that receives the number of completed
+    /// steps and the total
+    ProgressSink,
 }
 
 impl NamedArg {
-    pub(crate) fn new(ident: Ident, ty: TokenStream) -> Self {
-        Self {
+    pub(crate) fn new(ident: Ident, ty: &syn::Type) -> syn::Result<Self> {
+        let borrowed_bytes = is_byte_slice_ref(ty);
+        let borrowed_object = object_ref_referent(ty);
+        if let syn::Type::Reference(r) = ty {
+            if !borrowed_bytes && borrowed_object.is_none() && !is_str(&r.elem) {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "Only objects, `&str` and `&[u8]` can be passed by reference, \
+                     pass this argument by value",
+                ));
+            }
+        }
+        Ok(Self {
             name: ident_to_string(&ident),
+            ty: quote! { #ty },
+            borrowed_bytes,
+            // Spanned to the type, so that the error for a type that isn't an object points to
+            // the argument
+            borrowed_object: borrowed_object.map(|obj| quote_spanned! { obj.span()=> #obj }),
+            closure: ClosureArg::new(&ident, ty),
+            sensitive: false,
+            has_handle: contains_handle(ty),
             ident,
-            ty,
-        }
+        })
     }
 
     /// Generate the expression for this argument's FfiConverter
+    ///
+    /// For borrowed bytes, this is the converter for `Vec<u8>`, which serializes the same way as
+    /// `bytes` and is used when the bytes need to be written into a buffer.
     pub(crate) fn ffi_converter(&self) -> TokenStream {
-        let ty = &self.ty;
-        quote! { <#ty as ::uniffi::FfiConverter<crate::UniFfiTag>> }
+        if self.borrowed_bytes {
+            return quote! { <::uniffi::deps::alloc::vec::Vec<u8> as ::uniffi::FfiConverter<crate::UniFfiTag>> };
+        }
+        if let Some(obj) = &self.borrowed_object {
+            return quote_spanned! { obj.span()=>
+                <::uniffi::deps::alloc::sync::Arc<#obj> as ::uniffi::FfiConverter<crate::UniFfiTag>>
+            };
+        }
+        let lift_ty = self.lift_ty();
+        quote! { <#lift_ty as ::uniffi::FfiConverter<crate::UniFfiTag>> }
+    }
+
+    // The type that's lifted for this arg, unless it's borrowed
+    fn lift_ty(&self) -> TokenStream {
+        match &self.closure {
+            Some(closure) => {
+                let trait_ident = &closure.trait_ident;
+                quote! { ::uniffi::deps::alloc::boxed::Box<dyn #trait_ident> }
+            }
+            None => self.ty.clone(),
+        }
     }
 
     /// Generate the expression for this argument's FfiType
     pub(crate) fn ffi_type(&self) -> TokenStream {
+        if self.borrowed_bytes {
+            return quote! { ::uniffi::ForeignBytes };
+        }
         let ffi_converter = self.ffi_converter();
         quote! { #ffi_converter::FfiType }
     }
@@ -334,11 +599,50 @@ impl NamedArg {
     /// Generate the expression to lift the scaffolding parameter for this arg
     pub(crate) fn lift_expr(&self) -> TokenStream {
         let ident = &self.ident;
-        let ffi_converter = self.ffi_converter();
-        let panic_fmt = format!("Failed to convert arg '{}': {{}}", self.name);
-        quote! {
-            #ffi_converter::try_lift(#ident)
-                .unwrap_or_else(|err| ::std::panic!(#panic_fmt, err))
+        if self.borrowed_bytes {
+            return quote! { #ident.as_slice() };
+        }
+        if let Some(obj) = &self.borrowed_object {
+            let lift_ref = quote_spanned! { obj.span()=>
+                <#obj as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lift_ref
+            };
+            return quote! { unsafe { #lift_ref(#ident) } };
+        }
+        let lift_ty = self.lift_ty();
+        let name = &self.name;
+        let lift = if self.sensitive {
+            quote! { ::uniffi::lift_sensitive_arg::<#lift_ty, crate::UniFfiTag>(#ident, #name) }
+        } else {
+            quote! { ::uniffi::lift_arg::<#lift_ty, crate::UniFfiTag>(#ident, #name) }
+        };
+        match &self.closure {
+            Some(closure) => closure.wrap_handler(lift),
+            None => lift,
+        }
+    }
+
+    /// Generate the expression to read this arg from the arguments of a remote call
+    ///
+    /// Borrowed bytes are read into an owned value, that `remote_arg_expr()` borrows.  Functions
+    /// that borrow objects don't have a remote entry point.
+    pub(crate) fn read_remote_expr(&self) -> TokenStream {
+        let name = &self.name;
+        let read_ty = if self.borrowed_bytes {
+            quote! { ::uniffi::deps::alloc::vec::Vec<u8> }
+        } else {
+            self.lift_ty()
+        };
+        quote! { ::uniffi::read_remote_arg::<#read_ty, crate::UniFfiTag>(uniffi_args, #name) }
+    }
+
+    /// Generate the expression that passes the value read by `read_remote_expr()` to the Rust
+    /// function
+    pub(crate) fn remote_arg_expr(&self) -> TokenStream {
+        let ident = &self.ident;
+        if self.borrowed_bytes {
+            quote! { #ident.as_slice() }
+        } else {
+            quote! { #ident }
         }
     }
 
@@ -346,16 +650,279 @@ impl NamedArg {
     pub(crate) fn write_expr(&self, buf_ident: &Ident) -> TokenStream {
         let ident = &self.ident;
         let ffi_converter = self.ffi_converter();
+        if self.borrowed_bytes {
+            return quote! { #ffi_converter::write(#ident.to_vec(), &mut #buf_ident) };
+        }
         quote! { #ffi_converter::write(#ident, &mut #buf_ident) }
     }
 
     pub(crate) fn metadata_calls(&self) -> TokenStream {
         let name = &self.name;
+        let by_ref = self.borrowed_object.is_some();
+        let sensitive = self.sensitive;
+        if self.borrowed_bytes {
+            return quote! {
+                .concat_str(#name)
+                .concat_value(::uniffi::metadata::codes::TYPE_BYTES)
+                .concat_bool(true)
+                .concat_bool(#sensitive)
+            };
+        }
         let ffi_converter = self.ffi_converter();
         quote! {
             .concat_str(#name)
             .concat(#ffi_converter::TYPE_ID_META)
+            .concat_bool(#by_ref)
+            .concat_bool(#sensitive)
+        }
+    }
+}
+
+impl ClosureArg {
+    fn new(arg_ident: &Ident, ty: &syn::Type) -> Option<Self> {
+        let trait_ident = Ident::new(
+            &upper_camel_case(&ident_to_string(arg_ident)),
+            Span::call_site(),
+        );
+        if is_progress_sink(ty) {
+            return Some(Self {
+                trait_ident,
+                inputs: vec![parse_quote!(u64), parse_quote!(u64)],
+                output: ReturnType::Default,
+                kind: ClosureKind::ProgressSink,
+            });
         }
+        let (bounds, kind) = match ty {
+            syn::Type::ImplTrait(t) => (&t.bounds, ClosureKind::Impl),
+            _ => match last_path_segment(ty) {
+                Some(last) if last.ident == "Box" => match &last.arguments {
+                    PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                        match &args.args[0] {
+                            GenericArgument::Type(syn::Type::TraitObject(t)) => {
+                                (&t.bounds, ClosureKind::Boxed)
+                            }
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            },
+        };
+        let fn_args = bounds.iter().find_map(|bound| match bound {
+            syn::TypeParamBound::Trait(t) => {
+                let last = t.path.segments.last()?;
+                match &last.arguments {
+                    PathArguments::Parenthesized(args) if last.ident == "Fn" => Some(args),
+                    _ => None,
+                }
+            }
+            _ => None,
+        })?;
+        Some(Self {
+            trait_ident,
+            inputs: fn_args.inputs.iter().cloned().collect(),
+            output: fn_args.output.clone(),
+            kind,
+        })
+    }
+
+    /// Wrap the lifted callback interface handler in a closure that calls it
+    fn wrap_handler(&self, handler: TokenStream) -> TokenStream {
+        let output = &self.output;
+        let (params, names): (Vec<_>, Vec<_>) = self
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let name = Ident::new(&format!("v{i}"), Span::call_site());
+                (quote! { #name: #ty }, name)
+            })
+            .unzip();
+        let closure = quote! {
+            move |#(#params),*| #output { uniffi_handler.invoke(#(#names),*) }
+        };
+        let closure = match self.kind {
+            ClosureKind::Impl => closure,
+            ClosureKind::Boxed => quote! { ::uniffi::deps::alloc::boxed::Box::new(#closure) },
+            ClosureKind::ProgressSink => quote! { ::uniffi::ProgressSink::new(#closure) },
+        };
+        quote! {
+            {
+                let uniffi_handler = #handler;
+                #closure
+            }
+        }
+    }
+}
+
+/// Convert a snake case identifier, like an argument name, to upper camel case
+fn upper_camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Is `ty` a `&[u8]`?
+fn is_byte_slice_ref(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Reference(r) if r.mutability.is_none() => match &*r.elem {
+            syn::Type::Slice(s) => {
+                matches!(&*s.elem, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("u8"))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// If `ty` is a `&T` for an object type `T`, get `T`
+///
+/// We can't tell object types from records and enums here, so this matches any reference to a
+/// trait object, or to a named type other than the builtin types.  Types with generic arguments,
+/// like `Vec<T>`, are never objects.  For records and enums, the `lift_ref()` call fails to
+/// compile, with an error that points to the argument.
+fn object_ref_referent(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Reference(r) if r.mutability.is_none() => match &*r.elem {
+            syn::Type::Path(p) if p.qself.is_none() && !is_builtin_or_generic(&p.path) => {
+                Some(&r.elem)
+            }
+            syn::Type::TraitObject(_) => Some(&r.elem),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Does `ty` contain an object, a callback interface or another handle?
+///
+/// Handles only make sense in the process that created them, so functions that pass them don't
+/// get a remote entry point.  This follows `ComponentInterface::is_remote_callable()`, which also
+/// excludes shared and compressed values, but it only sees the syntax: a record with an object
+/// field isn't caught here, and the bindings don't make remote calls to those functions.
+pub(crate) fn contains_handle(ty: &syn::Type) -> bool {
+    const HANDLE_TYPES: &[&str] = &[
+        "Arc",
+        "Weak",
+        "ForeignExecutor",
+        "ProgressSink",
+        "Shared",
+        "Compressed",
+    ];
+    match ty {
+        syn::Type::Reference(r) => object_ref_referent(ty).is_some() || contains_handle(&r.elem),
+        syn::Type::TraitObject(_) | syn::Type::ImplTrait(_) => true,
+        syn::Type::Path(p) => match p.path.segments.last() {
+            Some(last) if HANDLE_TYPES.iter().any(|name| last.ident == name) => true,
+            Some(last) => match &last.arguments {
+                PathArguments::AngleBracketed(args) => args.args.iter().any(
+                    |arg| matches!(arg, GenericArgument::Type(ty) if contains_handle(ty)),
+                ),
+                _ => false,
+            },
+            None => false,
+        },
+        syn::Type::Tuple(t) => t.elems.iter().any(contains_handle),
+        syn::Type::Array(a) => contains_handle(&a.elem),
+        syn::Type::Slice(s) => contains_handle(&s.elem),
+        syn::Type::Paren(p) => contains_handle(&p.elem),
+        syn::Type::Group(g) => contains_handle(&g.elem),
+        _ => false,
+    }
+}
+
+fn is_str(ty: &syn::Type) -> bool {
+    matches!(ty, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("str"))
+}
+
+// Is `path` a builtin type, or a type with generic arguments?  Builtin types are matched on their
+// last segment, so that `std::time::Duration` matches too.
+fn is_builtin_or_generic(path: &syn::Path) -> bool {
+    const BUILTIN_TYPES: &[&str] = &[
+        "bool",
+        "i8",
+        "u8",
+        "i16",
+        "u16",
+        "i32",
+        "u32",
+        "i64",
+        "u64",
+        "f32",
+        "f64",
+        "str",
+        "String",
+        "SystemTime",
+        "Duration",
+    ];
+    let is_builtin = match path.segments.last() {
+        Some(last) => BUILTIN_TYPES.iter().any(|name| last.ident == name),
+        None => true,
+    };
+    is_builtin
+        || path
+            .segments
+            .iter()
+            .any(|segment| !segment.arguments.is_empty())
+}
+
+/// If `output` is a `Result<T, CallbackError>`, get `T`
+///
+/// This matches on the last segment of the paths, so it works with or without the `uniffi::`
+/// prefix, and with aliases of `Result` named `Result`.
+pub(crate) fn callback_result_ok_type(output: &ReturnType) -> Option<&syn::Type> {
+    let ty = match output {
+        ReturnType::Type(_, ty) => &**ty,
+        ReturnType::Default => return None,
+    };
+    let args = match last_path_segment(ty) {
+        Some(last) if last.ident == "Result" => match &last.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 2 => args,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match (&args.args[0], &args.args[1]) {
+        (GenericArgument::Type(ok), GenericArgument::Type(err)) if is_callback_error(err) => {
+            Some(ok)
+        }
+        _ => None,
+    }
+}
+
+fn is_callback_error(ty: &syn::Type) -> bool {
+    matches!(last_path_segment(ty), Some(last) if last.ident == "CallbackError")
+}
+
+// Is `ty` written as `uniffi::ProgressSink`?  The full path is required, so that a type of the
+// user's that's also named `ProgressSink` is passed like any other type.
+fn is_progress_sink(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => {
+            let segments: Vec<_> = p.path.segments.iter().collect();
+            matches!(
+                segments.as_slice(),
+                [uniffi, sink] if uniffi.ident == "uniffi"
+                    && uniffi.arguments.is_empty()
+                    && sink.ident == "ProgressSink"
+                    && sink.arguments.is_empty()
+            )
+        }
+        _ => false,
+    }
+}
+
+fn last_path_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+        _ => None,
     }
 }
 
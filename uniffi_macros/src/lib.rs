@@ -21,6 +21,7 @@ mod custom;
 mod enum_;
 mod error;
 mod export;
+mod flags;
 mod fnsig;
 mod object;
 mod record;
@@ -29,8 +30,8 @@ mod test;
 mod util;
 
 use self::{
-    enum_::expand_enum, error::expand_error, export::expand_export, object::expand_object,
-    record::expand_record,
+    enum_::expand_enum, error::expand_error, export::expand_export, flags::expand_flags,
+    object::expand_object, record::expand_record,
 };
 
 struct IdentPair {
@@ -108,23 +109,37 @@ pub fn export(attr_args: TokenStream, input: TokenStream) -> TokenStream {
         let item = syn::parse(input)?;
         expand_export(item, args, mod_path)
     };
-    let output = gen_output().unwrap_or_else(syn::Error::into_compile_error);
 
-    quote! {
-        #input2
-        #output
+    // `expand_export` outputs the item itself, since exported traits need to be rewritten.  If it
+    // fails, output the item unchanged so that the error isn't followed by lots of unrelated ones.
+    match gen_output() {
+        Ok(output) => output.into(),
+        Err(e) => {
+            let error = e.into_compile_error();
+            quote! {
+                #input2
+                #error
+            }
+            .into()
+        }
     }
-    .into()
 }
 
 #[proc_macro_derive(Record, attributes(uniffi))]
 pub fn derive_record(input: TokenStream) -> TokenStream {
-    expand_record(parse_macro_input!(input)).into()
+    expand_record(parse_macro_input!(input), false).into()
 }
 
 #[proc_macro_derive(Enum)]
 pub fn derive_enum(input: TokenStream) -> TokenStream {
-    expand_enum(parse_macro_input!(input)).into()
+    expand_enum(parse_macro_input!(input), false).into()
+}
+
+#[proc_macro_derive(Flags, attributes(uniffi))]
+pub fn derive_flags(input: TokenStream) -> TokenStream {
+    expand_flags(parse_macro_input!(input))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
 #[proc_macro_derive(Object)]
@@ -140,11 +155,33 @@ pub fn derive_object(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(Error, attributes(uniffi))]
 pub fn derive_error(input: TokenStream) -> TokenStream {
-    expand_error(parse_macro_input!(input))
+    expand_error(parse_macro_input!(input), false)
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
 
+/// Expose a type that's defined in another crate
+///
+/// This inputs a copy of the type's definition, with `Record`, `Enum` or `Error` as the
+/// argument, and outputs the same code as the derive, but not the item itself.  The type must be
+/// in scope under the same name, for example with a `use` statement.
+///
+/// Because of the orphan rules, `FfiConverter` is only implemented for the local `UniFfiTag`, so
+/// other crates that use the type need a `uniffi::use_remote_type!` call.
+#[proc_macro_attribute]
+pub fn remote(attrs: TokenStream, input: TokenStream) -> TokenStream {
+    let kind: Ident = parse_macro_input!(attrs);
+    let input = parse_macro_input!(input);
+    match kind.to_string().as_str() {
+        "Record" => expand_record(input, true),
+        "Enum" => expand_enum(input, true),
+        "Error" => expand_error(input, true).unwrap_or_else(syn::Error::into_compile_error),
+        _ => syn::Error::new_spanned(kind, "expected `Record`, `Enum` or `Error`")
+            .into_compile_error(),
+    }
+    .into()
+}
+
 /// Generate the `FfiConverter` implementation for a Custom Type - ie,
 /// for a `<T>` which implements `UniffiCustomTypeConverter`.
 #[proc_macro]
@@ -250,6 +287,24 @@ pub fn scaffolding_ffi_converter_callback_interface(tokens: TokenStream) -> Toke
         &input.lhs,
         &input.rhs,
         Some(&syn::parse_quote!(crate::UniFfiTag)),
+        false,
+    )
+    .into()
+}
+
+/// Generate the FfiConverter implementations for a `[MainThread]` callback interface for the
+/// scaffolding code
+#[doc(hidden)]
+#[proc_macro]
+pub fn scaffolding_ffi_converter_main_thread_callback_interface(
+    tokens: TokenStream,
+) -> TokenStream {
+    let input: IdentPair = syn::parse_macro_input!(tokens);
+    export::ffi_converter_callback_interface_impl(
+        &input.lhs,
+        &input.rhs,
+        Some(&syn::parse_quote!(crate::UniFfiTag)),
+        true,
     )
     .into()
 }
@@ -272,7 +327,7 @@ pub fn include_scaffolding(component_name: TokenStream) -> TokenStream {
     let name = syn::parse_macro_input!(component_name as LitStr);
     if std::env::var("OUT_DIR").is_err() {
         quote! {
-            ::std::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
+            ::core::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
         }
     } else {
         let udl_name = name.value();
@@ -295,7 +350,7 @@ pub fn include_scaffolding(component_name: TokenStream) -> TokenStream {
             Ok(path) => path.display().to_string(),
             Err(_) => {
                 return quote! {
-                    ::std::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
+                    ::core::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
                 }.into();
             }
         };
@@ -315,7 +370,7 @@ pub fn include_scaffolding(component_name: TokenStream) -> TokenStream {
                 const _: &[u8] = include_bytes!(#toml_path);
             }
 
-            ::std::include!(::std::concat!(::std::env!("OUT_DIR"), "/", #name, ".uniffi.rs"));
+            ::core::include!(::core::concat!(::core::env!("OUT_DIR"), "/", #name, ".uniffi.rs"));
         }
     }.into()
 }
@@ -351,6 +406,12 @@ fn use_udl_simple_type(tokens: TokenStream) -> TokenStream {
     .into()
 }
 
+// Use a remote type that a dependent crate exposes with `#[uniffi::remote]`
+#[proc_macro]
+pub fn use_remote_type(tokens: TokenStream) -> TokenStream {
+    use_udl_simple_type(tokens)
+}
+
 #[proc_macro]
 pub fn use_udl_object(tokens: TokenStream) -> TokenStream {
     let util::ExternalTypeItem {
@@ -380,12 +441,12 @@ pub fn generate_and_include_scaffolding(udl_file: TokenStream) -> TokenStream {
     let udl_file_path = Utf8Path::new(&udl_file_string);
     if std::env::var("OUT_DIR").is_err() {
         quote! {
-            ::std::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
+            ::core::compile_error!("This macro assumes the crate has a build.rs script, but $OUT_DIR is not present");
         }
     } else if let Err(e) = uniffi_build::generate_scaffolding(udl_file_path) {
         let err = format!("{e:#}");
         quote! {
-            ::std::compile_error!(concat!("Failed to generate scaffolding from UDL file at ", #udl_file, ": ", #err));
+            ::core::compile_error!(concat!("Failed to generate scaffolding from UDL file at ", #udl_file, ": ", #err));
         }
     } else {
         // We know the filename is good because `generate_scaffolding` succeeded,
@@ -409,3 +470,30 @@ pub fn generate_and_include_scaffolding(udl_file: TokenStream) -> TokenStream {
 pub fn constructor(_attrs: TokenStream, input: TokenStream) -> TokenStream {
     input
 }
+
+/// A dummy macro that does nothing.
+///
+/// Like [`macro@constructor`], this is only a marker for `#[uniffi::export(callback_interface)]`,
+/// which reads the fallback expression from it.
+#[proc_macro_attribute]
+pub fn on_error(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing.
+///
+/// Like [`macro@constructor`], this is only a marker for `#[uniffi::export]`, which reads the
+/// runtime of an async method from it.
+#[proc_macro_attribute]
+pub fn async_runtime(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
+
+/// A dummy macro that does nothing.
+///
+/// Like [`macro@constructor`], this is only a marker for `#[uniffi::export]`, which reads the
+/// arguments that carry secret data from it.
+#[proc_macro_attribute]
+pub fn sensitive(_attrs: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
@@ -4,8 +4,8 @@ use syn::{DeriveInput, Path};
 use uniffi_meta::free_fn_symbol_name;
 
 use crate::util::{
-    create_metadata_items, ident_to_string, mod_path, tagged_impl_header, ArgumentNotAllowedHere,
-    AttributeSliceExt, CommonAttr,
+    create_metadata_items, ident_to_string, mod_path, source_location_items, tagged_impl_header,
+    ArgumentNotAllowedHere, AttributeSliceExt, CommonAttr,
 };
 
 pub fn expand_object(input: DeriveInput, module_path: String) -> TokenStream {
@@ -25,14 +25,15 @@ pub fn expand_object(input: DeriveInput, module_path: String) -> TokenStream {
         #[doc(hidden)]
         #[no_mangle]
         pub extern "C" fn #free_fn_ident(
-            ptr: *const ::std::ffi::c_void,
+            ptr: *const ::core::ffi::c_void,
             call_status: &mut ::uniffi::RustCallStatus
         ) {
             ::uniffi::rust_call(call_status, || {
-                ::std::assert!(!ptr.is_null());
+                ::core::assert!(!ptr.is_null());
+                ::uniffi::untrack_handle(#name, ptr as u64);
                 let ptr = ptr.cast::<#ident>();
                 unsafe {
-                    ::std::sync::Arc::decrement_strong_count(ptr);
+                    ::uniffi::deps::alloc::sync::Arc::decrement_strong_count(ptr);
                 }
                 Ok(())
             });
@@ -66,8 +67,8 @@ pub(crate) fn interface_impl(ident: &Ident, tag: Option<&Path>) -> TokenStream {
         /// across threads.
         unsafe #impl_spec {
             // Don't use a pointer to <T> as that requires a `pub <T>`
-            type FfiType = *const ::std::os::raw::c_void;
-            type ReturnType = *const ::std::os::raw::c_void;
+            type FfiType = *const ::core::ffi::c_void;
+            type ReturnType = *const ::core::ffi::c_void;
             type FutureCallback = ::uniffi::FutureCallback<Self::ReturnType>;
 
             /// When lowering, we have an owned `Arc` and we transfer that ownership
@@ -79,8 +80,10 @@ pub(crate) fn interface_impl(ident: &Ident, tag: Option<&Path>) -> TokenStream {
             /// Safety: when freeing the resulting pointer, the foreign-language code must
             /// call the destructor function specific to the type `T`. Calling the destructor
             /// function for other types may lead to undefined behaviour.
-            fn lower(obj: ::std::sync::Arc<Self>) -> Self::FfiType {
-                ::std::sync::Arc::into_raw(obj) as Self::FfiType
+            fn lower(obj: ::uniffi::deps::alloc::sync::Arc<Self>) -> Self::FfiType {
+                let ptr = ::uniffi::deps::alloc::sync::Arc::into_raw(obj) as Self::FfiType;
+                ::uniffi::track_handle(#name, ptr as u64);
+                ptr
             }
 
             /// When lifting, we receive a "borrow" of the `Arc` that is owned by
@@ -88,12 +91,22 @@ pub(crate) fn interface_impl(ident: &Ident, tag: Option<&Path>) -> TokenStream {
             ///
             /// Safety: the provided value must be a pointer previously obtained by calling
             /// the `lower()` or `write()` method of this impl.
-            fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::std::sync::Arc<Self>> {
+            fn try_lift(v: Self::FfiType) -> ::uniffi::Result<::uniffi::deps::alloc::sync::Arc<Self>> {
                 let v = v as *const #ident;
                 // We musn't drop the `Arc` that is owned by the foreign-language code.
-                let foreign_arc = ::std::mem::ManuallyDrop::new(unsafe { ::std::sync::Arc::<Self>::from_raw(v) });
+                let foreign_arc = ::core::mem::ManuallyDrop::new(unsafe { ::uniffi::deps::alloc::sync::Arc::<Self>::from_raw(v) });
                 // Take a clone for our own use.
-                Ok(::std::sync::Arc::clone(&*foreign_arc))
+                Ok(::uniffi::deps::alloc::sync::Arc::clone(&*foreign_arc))
+            }
+
+            /// When borrowing, we use the object behind the `Arc` that is owned by the
+            /// foreign-language code directly, without making a clone.
+            ///
+            /// Safety: the provided value must be a pointer previously obtained by calling
+            /// the `lower()` or `write()` method of this impl, and the foreign-language code
+            /// must not free it while the borrow is in use.
+            unsafe fn lift_ref<'a>(v: Self::FfiType) -> &'a Self {
+                &*(v as *const #ident)
             }
 
             /// When writing as a field of a complex structure, make a clone and transfer ownership
@@ -104,8 +117,8 @@ pub(crate) fn interface_impl(ident: &Ident, tag: Option<&Path>) -> TokenStream {
             /// Safety: when freeing the resulting pointer, the foreign-language code must
             /// call the destructor function specific to the type `T`. Calling the destructor
             /// function for other types may lead to undefined behaviour.
-            fn write(obj: ::std::sync::Arc<Self>, buf: &mut Vec<u8>) {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
+            fn write(obj: ::uniffi::deps::alloc::sync::Arc<Self>, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
+                ::uniffi::deps::static_assertions::const_assert!(::core::mem::size_of::<*const ::core::ffi::c_void>() <= 8);
                 ::uniffi::deps::bytes::BufMut::put_u64(buf, <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(obj) as u64);
             }
 
@@ -114,13 +127,13 @@ pub(crate) fn interface_impl(ident: &Ident, tag: Option<&Path>) -> TokenStream {
             ///
             /// Safety: the buffer must contain a pointer previously obtained by calling
             /// the `lower()` or `write()` method of this impl.
-            fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::std::sync::Arc<Self>> {
-                ::uniffi::deps::static_assertions::const_assert!(::std::mem::size_of::<*const ::std::ffi::c_void>() <= 8);
+            fn try_read(buf: &mut &[u8]) -> ::uniffi::Result<::uniffi::deps::alloc::sync::Arc<Self>> {
+                ::uniffi::deps::static_assertions::const_assert!(::core::mem::size_of::<*const ::core::ffi::c_void>() <= 8);
                 ::uniffi::check_remaining(buf, 8)?;
                 <Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::try_lift(::uniffi::deps::bytes::Buf::get_u64(buf) as Self::FfiType)
             }
 
-            fn lower_return(v: ::std::sync::Arc<Self>) -> ::std::result::Result<Self::FfiType, ::uniffi::RustBuffer> {
+            fn lower_return(v: ::uniffi::deps::alloc::sync::Arc<Self>) -> ::core::result::Result<Self::FfiType, ::uniffi::RustBuffer> {
                 Ok(<Self as ::uniffi::FfiConverterArc<crate::UniFfiTag>>::lower(v))
             }
 
@@ -147,7 +160,7 @@ pub(crate) fn interface_meta_static_var(
     module_path: &str,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
-    Ok(create_metadata_items(
+    let metadata_items = create_metadata_items(
         "interface",
         &name,
         quote! {
@@ -157,5 +170,11 @@ pub(crate) fn interface_meta_static_var(
                     .concat_bool(#is_trait)
         },
         None,
-    ))
+    );
+    let location_items =
+        source_location_items("interface", &name, module_path, &name, ident.span());
+    Ok(quote! {
+        #metadata_items
+        #location_items
+    })
 }
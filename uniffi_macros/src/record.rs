@@ -1,17 +1,23 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
-    Data, DataStruct, DeriveInput, Field, Lit, Path, Token,
+    parse_quote,
+    punctuated::Punctuated,
+    Attribute, Data, DataStruct, DeriveInput, Field, Lit, Path, Token, Visibility,
 };
 
 use crate::util::{
-    create_metadata_items, either_attribute_arg, ident_to_string, mod_path, tagged_impl_header,
-    try_metadata_value_from_usize, try_read_field, ArgumentNotAllowedHere, AttributeSliceExt,
+    create_metadata_items, either_attribute_arg, ident_to_string, mod_path, source_location_items,
+    tagged_impl_header, try_metadata_value_from_usize, try_read_field, AttributeSliceExt,
     CommonAttr, UniffiAttributeArgs,
 };
 
-pub fn expand_record(input: DeriveInput) -> TokenStream {
+/// Generate the `FfiConverter` and the metadata for a record
+///
+/// For `#[uniffi::remote(Record)]`, the struct is defined in another crate, so `FfiConverter` is
+/// only implemented for the local `UniFfiTag`.
+pub fn expand_record(input: DeriveInput, remote: bool) -> TokenStream {
     let record = match input.data {
         Data::Struct(s) => s,
         _ => {
@@ -24,38 +30,176 @@ pub fn expand_record(input: DeriveInput) -> TokenStream {
     };
 
     let ident = &input.ident;
-    let attr_error = input
+    let (attr_error, attrs) = match input
         .attrs
-        .parse_uniffi_attr_args::<ArgumentNotAllowedHere>()
-        .err()
-        .map(syn::Error::into_compile_error);
-    let ffi_converter = record_ffi_converter_impl(ident, &record, None)
+        .parse_uniffi_attr_args::<RecordAttributeArguments>()
+    {
+        // `apply_patch()` can't be added to a type from another crate
+        Ok(RecordAttributeArguments {
+            patch: Some(patch), ..
+        }) if remote => (
+            Some(
+                syn::Error::new_spanned(patch, "remote records can't be patched")
+                    .into_compile_error(),
+            ),
+            RecordAttributeArguments::default(),
+        ),
+        Ok(attrs) => (None, attrs),
+        Err(e) => (
+            Some(e.into_compile_error()),
+            RecordAttributeArguments::default(),
+        ),
+    };
+    let ord = attrs.ord.is_some();
+    let tag: Option<Path> = remote.then(|| parse_quote!(crate::UniFfiTag));
+    let repr_c = is_repr_c_record(&input.attrs, &record);
+    let ffi_converter = record_ffi_converter_impl(ident, &record, repr_c, tag.as_ref())
         .unwrap_or_else(syn::Error::into_compile_error);
-    let meta_static_var =
-        record_meta_static_var(ident, &record).unwrap_or_else(syn::Error::into_compile_error);
+    let meta_static_var = record_meta_static_var(ident, &record, repr_c, ord, None)
+        .unwrap_or_else(syn::Error::into_compile_error);
+    let cmp_fn = ord.then(|| record_cmp_fn(ident).unwrap_or_else(syn::Error::into_compile_error));
+    let patch_items = attrs.patch.is_some().then(|| {
+        record_patch_items(ident, &input.vis, &record)
+            .unwrap_or_else(syn::Error::into_compile_error)
+    });
 
     quote! {
         #attr_error
         #ffi_converter
         #meta_static_var
+        #cmp_fn
+        #patch_items
+    }
+}
+
+/// Generate the scaffolding function that compares two records with `Ord::cmp()`, for
+/// `#[uniffi(Ord)]`
+fn record_cmp_fn(ident: &Ident) -> syn::Result<TokenStream> {
+    let cmp_ident = Ident::new(
+        &uniffi_meta::record_cmp_fn_symbol_name(&mod_path()?, &ident_to_string(ident)),
+        Span::call_site(),
+    );
+    Ok(quote! {
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #cmp_ident(
+            a: <#ident as ::uniffi::FfiConverter<crate::UniFfiTag>>::FfiType,
+            b: <#ident as ::uniffi::FfiConverter<crate::UniFfiTag>>::FfiType,
+            call_status: &mut ::uniffi::RustCallStatus,
+        ) -> i8 {
+            ::uniffi::uniffi_record_cmp::<#ident, crate::UniFfiTag>(a, b, call_status)
+        }
+    })
+}
+
+/// Generate the companion `{ident}Patch` record for `#[uniffi(patch)]`
+///
+/// The patch record has the same fields, each wrapped in an `Option` that defaults to `None`,
+/// and `{ident}::apply_patch()` overwrites the fields that are set in a patch.
+fn record_patch_items(
+    ident: &Ident,
+    vis: &Visibility,
+    record: &DataStruct,
+) -> syn::Result<TokenStream> {
+    let patch_ident = format_ident!("{ident}Patch");
+    let mut patch_record = record.clone();
+    for f in patch_record.fields.iter_mut() {
+        let ty = &f.ty;
+        f.ty = parse_quote! { ::core::option::Option<#ty> };
+        f.attrs = vec![parse_quote! { #[uniffi(default = None)] }];
     }
+
+    let fields = patch_record.fields.iter().map(|f| {
+        let vis = &f.vis;
+        let ident = &f.ident;
+        let ty = &f.ty;
+        quote! { #vis #ident: #ty }
+    });
+    let idents: Vec<_> = record.fields.iter().map(|f| &f.ident).collect();
+    let doc = format!("Partial update for [`{ident}`], see [`{ident}::apply_patch`]");
+    let ffi_converter = record_ffi_converter_impl(&patch_ident, &patch_record, false, None)?;
+    let meta_static_var =
+        record_meta_static_var(&patch_ident, &patch_record, false, false, Some(ident))?;
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Default)]
+        #vis struct #patch_ident {
+            #( #fields, )*
+        }
+
+        impl #ident {
+            /// Overwrite the fields that are set in `patch`, leaving the others unchanged
+            #vis fn apply_patch(&mut self, patch: #patch_ident) {
+                #(
+                    if let ::core::option::Option::Some(value) = patch.#idents {
+                        self.#idents = value;
+                    }
+                )*
+            }
+        }
+
+        #ffi_converter
+        #meta_static_var
+    })
 }
 
 pub(crate) fn expand_record_ffi_converter(attr: CommonAttr, input: DeriveInput) -> TokenStream {
     match input.data {
-        Data::Struct(s) => record_ffi_converter_impl(&input.ident, &s, attr.tag.as_ref())
-            .unwrap_or_else(syn::Error::into_compile_error),
+        Data::Struct(s) => {
+            let repr_c = is_repr_c_record(&input.attrs, &s);
+            record_ffi_converter_impl(&input.ident, &s, repr_c, attr.tag.as_ref())
+                .unwrap_or_else(syn::Error::into_compile_error)
+        }
         _ => syn::Error::new(
-            proc_macro2::Span::call_site(),
+            Span::call_site(),
             "This attribute must only be used on structs",
         )
         .into_compile_error(),
     }
 }
 
+/// Should this record be passed as a `#[repr(C)]` struct rather than in a `RustBuffer`?
+///
+/// This is the case for `#[repr(C)]` structs whose fields are all integers or floats.  Other
+/// `#[repr(C)]` structs are serialized like any other record.  The field types are checked
+/// syntactically, so type aliases of numeric types don't count.
+fn is_repr_c_record(attrs: &[Attribute], record: &DataStruct) -> bool {
+    let has_repr_c = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("repr"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|meta| meta.path().is_ident("C"));
+
+    has_repr_c
+        && !record.fields.is_empty()
+        && record.fields.iter().all(|f| is_repr_c_field_type(&f.ty))
+}
+
+// These must match the field types accepted for `[ReprC]` dictionaries by `uniffi_udl`.
+fn is_repr_c_field_type(ty: &syn::Type) -> bool {
+    const NUMERIC_TYPES: &[&str] = &[
+        "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Group(g) => is_repr_c_field_type(&g.elem),
+        syn::Type::Paren(p) => is_repr_c_field_type(&p.elem),
+        syn::Type::Path(p) if p.qself.is_none() => matches!(
+            p.path.segments.last(),
+            Some(seg) if seg.arguments.is_none() && NUMERIC_TYPES.iter().any(|name| seg.ident == name)
+        ),
+        _ => false,
+    }
+}
+
 pub(crate) fn record_ffi_converter_impl(
     ident: &Ident,
     record: &DataStruct,
+    repr_c: bool,
     tag: Option<&Path>,
 ) -> syn::Result<TokenStream> {
     let impl_spec = tagged_impl_header("FfiConverter", ident, tag);
@@ -64,17 +208,37 @@ pub(crate) fn record_ffi_converter_impl(
     let write_impl: TokenStream = record.fields.iter().map(write_field).collect();
     let try_read_fields: TokenStream = record.fields.iter().map(try_read_field).collect();
 
+    if repr_c {
+        return Ok(repr_c_ffi_converter_impl(
+            impl_spec,
+            record,
+            quote! {
+                fn write(obj: Self, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
+                    #write_impl
+                }
+
+                fn try_read(buf: &mut &[::core::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+                    Ok(Self { #try_read_fields })
+                }
+
+                const TYPE_ID_META: ::uniffi::MetadataBuffer = ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::TYPE_RECORD)
+                    .concat_str(#mod_path)
+                    .concat_str(#name);
+            },
+        ));
+    }
+
     Ok(quote! {
         #[automatically_derived]
         unsafe #impl_spec {
             ::uniffi::ffi_converter_rust_buffer_lift_and_lower!(crate::UniFfiTag);
             ::uniffi::ffi_converter_default_return!(crate::UniFfiTag);
 
-            fn write(obj: Self, buf: &mut ::std::vec::Vec<u8>) {
+            fn write(obj: Self, buf: &mut ::uniffi::deps::alloc::vec::Vec<u8>) {
                 #write_impl
             }
 
-            fn try_read(buf: &mut &[::std::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
+            fn try_read(buf: &mut &[::core::primitive::u8]) -> ::uniffi::deps::anyhow::Result<Self> {
                 Ok(Self { #try_read_fields })
             }
 
@@ -85,6 +249,57 @@ pub(crate) fn record_ffi_converter_impl(
     })
 }
 
+// `#[repr(C)]` records are passed as a struct with the same fields.  We don't rely on the layout
+// of the record itself, since UDL records don't need to be `#[repr(C)]`, so lowering and lifting
+// copy the fields to and from a hidden `#[repr(C)]` struct.
+fn repr_c_ffi_converter_impl(
+    impl_spec: TokenStream,
+    record: &DataStruct,
+    serialization_impl: TokenStream,
+) -> TokenStream {
+    let idents: Vec<_> = record.fields.iter().map(|f| &f.ident).collect();
+    let tys = record.fields.iter().map(|f| &f.ty);
+
+    quote! {
+        const _: () = {
+            #[repr(C)]
+            #[doc(hidden)]
+            pub struct UniffiReprC {
+                #( pub #idents: #tys, )*
+            }
+
+            impl ::uniffi::FfiDefault for UniffiReprC {
+                fn ffi_default() -> Self {
+                    Self {
+                        #( #idents: ::uniffi::FfiDefault::ffi_default(), )*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            unsafe #impl_spec {
+                ::uniffi::ffi_converter_default_return!(crate::UniFfiTag);
+
+                type FfiType = UniffiReprC;
+
+                fn lower(obj: Self) -> UniffiReprC {
+                    UniffiReprC {
+                        #( #idents: obj.#idents, )*
+                    }
+                }
+
+                fn try_lift(v: UniffiReprC) -> ::uniffi::Result<Self> {
+                    Ok(Self {
+                        #( #idents: v.#idents, )*
+                    })
+                }
+
+                #serialization_impl
+            }
+        };
+    }
+}
+
 fn write_field(f: &Field) -> TokenStream {
     let ident = &f.ident;
     let ty = &f.ty;
@@ -97,6 +312,40 @@ fn write_field(f: &Field) -> TokenStream {
 mod kw {
     syn::custom_keyword!(default);
     syn::custom_keyword!(None);
+    syn::custom_keyword!(patch);
+    syn::custom_keyword!(Ord);
+}
+
+#[derive(Default)]
+pub struct RecordAttributeArguments {
+    patch: Option<kw::patch>,
+    ord: Option<kw::Ord>,
+}
+
+impl UniffiAttributeArgs for RecordAttributeArguments {
+    fn parse_one(input: ParseStream<'_>) -> syn::Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(kw::patch) {
+            Ok(Self {
+                patch: input.parse()?,
+                ..Self::default()
+            })
+        } else if lookahead.peek(kw::Ord) {
+            Ok(Self {
+                ord: input.parse()?,
+                ..Self::default()
+            })
+        } else {
+            Err(lookahead.error())
+        }
+    }
+
+    fn merge(self, other: Self) -> syn::Result<Self> {
+        Ok(Self {
+            patch: either_attribute_arg(self.patch, other.patch)?,
+            ord: either_attribute_arg(self.ord, other.ord)?,
+        })
+    }
 }
 
 pub enum FieldDefault {
@@ -150,6 +399,9 @@ impl UniffiAttributeArgs for FieldAttributeArguments {
 pub(crate) fn record_meta_static_var(
     ident: &Ident,
     record: &DataStruct,
+    repr_c: bool,
+    ord: bool,
+    patch_for: Option<&Ident>,
 ) -> syn::Result<TokenStream> {
     let name = ident_to_string(ident);
     let module_path = mod_path()?;
@@ -185,7 +437,15 @@ pub(crate) fn record_meta_static_var(
         })
         .collect::<syn::Result<_>>()?;
 
-    Ok(create_metadata_items(
+    let concat_patch_for = match patch_for {
+        Some(base) => {
+            let base = ident_to_string(base);
+            quote! { .concat_bool(true).concat_str(#base) }
+        }
+        None => quote! { .concat_bool(false) },
+    };
+
+    let metadata_items = create_metadata_items(
         "record",
         &name,
         quote! {
@@ -194,9 +454,17 @@ pub(crate) fn record_meta_static_var(
                 .concat_str(#name)
                 .concat_value(#fields_len)
                 #concat_fields
+                .concat_bool(#repr_c)
+                .concat_bool(#ord)
+                #concat_patch_for
         },
         None,
-    ))
+    );
+    let location_items = source_location_items("record", &name, &module_path, &name, ident.span());
+    Ok(quote! {
+        #metadata_items
+        #location_items
+    })
 }
 
 fn default_value_concat_calls(default: FieldDefault) -> syn::Result<TokenStream> {
@@ -12,6 +12,7 @@ use uniffi_meta::UNIFFI_CONTRACT_VERSION;
 pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let module_path = mod_path()?;
     let ffi_contract_version_ident = format_ident!("ffi_{namespace}_uniffi_contract_version");
+    let ffi_contract_info_ident = format_ident!("ffi_{namespace}_uniffi_contract_info");
     let namespace_upper = namespace.to_ascii_uppercase();
     let namespace_const_ident = format_ident!("UNIFFI_META_CONST_NAMESPACE_{namespace_upper}");
     let namespace_static_ident = format_ident!("UNIFFI_META_NAMESPACE_{namespace_upper}");
@@ -19,6 +20,10 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
     let ffi_rustbuffer_from_bytes_ident = format_ident!("ffi_{namespace}_rustbuffer_from_bytes");
     let ffi_rustbuffer_free_ident = format_ident!("ffi_{namespace}_rustbuffer_free");
     let ffi_rustbuffer_reserve_ident = format_ident!("ffi_{namespace}_rustbuffer_reserve");
+    let ffi_debug_live_handles_ident = format_ident!("ffi_{namespace}_uniffi_debug_live_handles");
+    let ffi_weak_ref_upgrade_ident = format_ident!("ffi_{namespace}_weak_ref_upgrade");
+    let ffi_weak_ref_clone_ident = format_ident!("ffi_{namespace}_weak_ref_clone");
+    let ffi_weak_ref_free_ident = format_ident!("ffi_{namespace}_weak_ref_free");
     let reexport_hack_ident = format_ident!("{namespace}_uniffi_reexport_hack");
 
     Ok(quote! {
@@ -39,6 +44,14 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
             #UNIFFI_CONTRACT_VERSION
         }
 
+        // The versions that the bindings compare to their own, to explain a mismatch.
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_contract_info_ident(call_status: &mut ::uniffi::RustCallStatus) -> ::uniffi::RustBuffer {
+            ::uniffi::uniffi_contract_info(#UNIFFI_CONTRACT_VERSION, call_status)
+        }
+
 
         /// Export namespace metadata.
         ///
@@ -84,6 +97,42 @@ pub fn setup_scaffolding(namespace: String) -> Result<TokenStream> {
             ::uniffi::ffi::uniffi_rustbuffer_reserve(buf, additional, call_status)
         }
 
+        // Report of the live handles, for `uniffiDebugLiveHandles()`.  This is only useful with the
+        // `handle-diagnostics` feature, but it's always exported so that the bindings don't
+        // depend on the feature.
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub extern "C" fn #ffi_debug_live_handles_ident(call_status: &mut ::uniffi::RustCallStatus) -> ::uniffi::RustBuffer {
+            ::uniffi::uniffi_debug_live_handles(call_status)
+        }
+
+        // Handle functions for the `WeakRef`s of the bindings.  The handles are type-erased, so
+        // these work for every object of the component.
+        //
+        // See `uniffi_core/src/weak.rs` for documentation on these functions
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_weak_ref_upgrade_ident(handle: u64, call_status: &mut ::uniffi::RustCallStatus) -> u64 {
+            ::uniffi::uniffi_weak_ref_upgrade(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_weak_ref_clone_ident(handle: u64, call_status: &mut ::uniffi::RustCallStatus) -> u64 {
+            ::uniffi::uniffi_weak_ref_clone(handle, call_status)
+        }
+
+        #[allow(clippy::missing_safety_doc, missing_docs)]
+        #[doc(hidden)]
+        #[no_mangle]
+        pub unsafe extern "C" fn #ffi_weak_ref_free_ident(handle: u64, call_status: &mut ::uniffi::RustCallStatus) {
+            ::uniffi::uniffi_weak_ref_free(handle, call_status);
+        }
+
         // Code to re-export the UniFFI scaffolding functions.
         //
         // Rust won't always re-export the functions from dependencies
@@ -3,7 +3,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use std::path::{Path as StdPath, PathBuf};
 use syn::{
     ext::IdentExt,
@@ -127,6 +127,99 @@ pub fn create_metadata_items(
     }
 }
 
+/// Create the metadata items that record where an exported item is defined
+///
+/// `file!()` and `line!()` are spanned to `span`, so they expand to the location of the item
+/// rather than the location of the macro.  `name` is the item name, or `Type.name` for methods and
+/// constructors.
+pub fn source_location_items(
+    kind: &str,
+    symbol_name: &str,
+    module_path: &str,
+    name: &str,
+    span: Span,
+) -> TokenStream {
+    let location = quote_spanned! { span =>
+        .concat_str(::core::file!())
+        .concat_u32(::core::line!())
+    };
+    create_metadata_items(
+        &format!("location_{kind}"),
+        symbol_name,
+        quote! {
+            ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::SOURCE_LOCATION)
+                .concat_str(#module_path)
+                .concat_str(#name)
+                #location
+        },
+        None,
+    )
+}
+
+/// Get the `#[cfg(...)]` attributes of an item
+pub fn cfg_attrs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Compile the code generated for an item only when the item itself is compiled
+///
+/// Attribute macros see the `#[cfg(...)]` attributes that follow them, and the ones inside an
+/// `impl` block or trait, before they're evaluated.  `tokens` is wrapped in an anonymous const
+/// behind the same predicate, and metadata items that record the predicate are created for both
+/// outcomes, so that the bindings generator can report the items that were left out.  `name` is
+/// the item name, or `Type.name` for methods and constructors.
+pub fn cfg_gated_items(
+    kind: &str,
+    symbol_name: &str,
+    module_path: &str,
+    name: &str,
+    cfg: &[Attribute],
+    tokens: TokenStream,
+) -> syn::Result<TokenStream> {
+    let predicates = cfg
+        .iter()
+        .map(|a| Ok(a.meta.require_list()?.tokens.clone()))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let predicate = match predicates.as_slice() {
+        [] => return Ok(tokens),
+        [predicate] => predicate.clone(),
+        predicates => quote! { all(#(#predicates),*) },
+    };
+    let predicate_str = predicate.to_string();
+    let cfg_items = |enabled: bool| {
+        create_metadata_items(
+            &format!("cfg_{kind}"),
+            symbol_name,
+            quote! {
+                ::uniffi::MetadataBuffer::from_code(::uniffi::metadata::codes::CFG)
+                    .concat_str(#module_path)
+                    .concat_str(#name)
+                    .concat_str(#predicate_str)
+                    .concat_bool(#enabled)
+            },
+            None,
+        )
+    };
+    let enabled_items = cfg_items(true);
+    let disabled_items = cfg_items(false);
+    Ok(quote! {
+        #[cfg(#predicate)]
+        const _: () = {
+            #tokens
+            #enabled_items
+        };
+
+        #[cfg(not(#predicate))]
+        const _: () = {
+            #disabled_items
+        };
+    })
+}
+
 pub fn try_metadata_value_from_usize(value: usize, error_message: &str) -> syn::Result<u8> {
     value
         .try_into()
@@ -236,11 +329,11 @@ pub(crate) struct CommonAttr {
     /// External typedef).  This is natural for UDL-based generation, since you always need to
     /// define the external type in the UDL file.
     ///
-    /// With proc-macros this system isn't so natural.  Instead, we plan to use this system:
+    /// With proc-macros this system isn't so natural.  Instead, we use this system:
     ///   - Most of the time, types aren't remote and we use the blanket impl.
-    ///   - When types are remote, we'll need a special syntax to define an `FfiConverter` for them
-    ///     and also a special declaration to use the types in other crates.  This requires some
-    ///     extra work for the consumer, but it should be rare.
+    ///   - When types are remote, `#[uniffi::remote]` defines an `FfiConverter` for them using
+    ///     the local tag and other crates use them with `uniffi::use_remote_type!`.  This requires
+    ///     some extra work for the consumer, but it should be rare.
     pub tag: Option<Path>,
 }
 
@@ -275,16 +368,17 @@ impl Parse for CommonAttr {
 /// Specifies a type from a dependent crate
 pub struct ExternalTypeItem {
     pub crate_ident: Ident,
-    pub sep: Token![,],
     pub type_ident: Ident,
 }
 
 impl Parse for ExternalTypeItem {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let crate_ident = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let type_ident = input.parse()?;
         Ok(Self {
-            crate_ident: input.parse()?,
-            sep: input.parse()?,
-            type_ident: input.parse()?,
+            crate_ident,
+            type_ident,
         })
     }
 }
@@ -19,6 +19,15 @@ pub fn fn_symbol_name(namespace: &str, name: &str) -> String {
     format!("uniffi_{namespace}_fn_func_{name}")
 }
 
+/// FFI symbol name for the remote entry point of a top-level function
+///
+/// Remote entry points take the arguments and return the result serialized in a `RustBuffer`, so
+/// that the call can be forwarded from another process.
+pub fn remote_fn_symbol_name(namespace: &str, name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_remote_func_{name}")
+}
+
 /// FFI symbol name for an object constructor
 pub fn constructor_symbol_name(namespace: &str, object_name: &str, name: &str) -> String {
     let object_name = object_name.to_ascii_lowercase();
@@ -39,12 +48,24 @@ pub fn free_fn_symbol_name(namespace: &str, object_name: &str) -> String {
     format!("uniffi_{namespace}_fn_free_{object_name}")
 }
 
+/// FFI symbol name for the function that compares two records with `Ord::cmp()`
+pub fn record_cmp_fn_symbol_name(namespace: &str, record_name: &str) -> String {
+    let record_name = record_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_cmp_{record_name}")
+}
+
 /// FFI symbol name for the `init_callback` function for a callback interface
 pub fn init_callback_fn_symbol_name(namespace: &str, callback_interface_name: &str) -> String {
     let callback_interface_name = callback_interface_name.to_ascii_lowercase();
     format!("uniffi_{namespace}_fn_init_callback_{callback_interface_name}")
 }
 
+/// FFI symbol name for the function that detaches a foreign object from a callback interface
+pub fn detach_callback_fn_symbol_name(namespace: &str, callback_interface_name: &str) -> String {
+    let callback_interface_name = callback_interface_name.to_ascii_lowercase();
+    format!("uniffi_{namespace}_fn_detach_callback_{callback_interface_name}")
+}
+
 /// FFI checksum symbol name for a top-level function
 pub fn fn_checksum_symbol_name(namespace: &str, name: &str) -> String {
     let name = name.to_ascii_lowercase();
@@ -39,6 +39,7 @@ pub fn group_metadata(items: Vec<Metadata>) -> Result<Vec<MetadataGroup>> {
             ),
             Metadata::Record(meta) => (format!("record `{}`", meta.name), &meta.module_path),
             Metadata::Enum(meta) => (format!("enum `{}`", meta.name), &meta.module_path),
+            Metadata::Flags(meta) => (format!("flags `{}`", meta.name), &meta.module_path),
             Metadata::Object(meta) => (format!("object `{}`", meta.name), &meta.module_path),
             Metadata::CallbackInterface(meta) => (
                 format!("callback interface `{}`", meta.name),
@@ -47,8 +48,30 @@ pub fn group_metadata(items: Vec<Metadata>) -> Result<Vec<MetadataGroup>> {
             Metadata::TraitMethod(meta) => {
                 (format!("trait method`{}`", meta.name), &meta.module_path)
             }
+            Metadata::TraitConstant(meta) => (
+                format!("trait constant `{}.{}`", meta.trait_name, meta.name),
+                &meta.module_path,
+            ),
             Metadata::Error(meta) => (format!("error `{}`", meta.name()), meta.module_path()),
+            Metadata::Constant(meta) => (format!("constant `{}`", meta.name), &meta.module_path),
             Metadata::CustomType(meta) => (format!("custom `{}`", meta.name), &meta.module_path),
+            Metadata::UniffiTrait(meta) => (
+                format!("uniffi trait for `{}`", meta.self_name()),
+                meta.module_path(),
+            ),
+            Metadata::ObjectConcurrency(meta) => (
+                format!("concurrency policy of `{}`", meta.self_name),
+                &meta.module_path,
+            ),
+            Metadata::SubNamespace(meta) => (
+                format!("sub-namespace of `{}`", meta.fn_name),
+                &meta.module_path,
+            ),
+            Metadata::SourceLocation(meta) => (
+                format!("source location of `{}`", meta.name),
+                &meta.module_path,
+            ),
+            Metadata::Cfg(meta) => (format!("cfg of `{}`", meta.name), &meta.module_path),
         };
 
         let crate_name = calc_crate_name(module_path);
@@ -175,6 +198,13 @@ impl<'a> ExternalTypeConverter<'a> {
                     kind: ExternalKind::DataClass,
                 }
             }
+            Type::Flags { module_path, name } if self.is_module_path_external(&module_path) => {
+                Type::External {
+                    module_path,
+                    name,
+                    kind: ExternalKind::Flags,
+                }
+            }
             Type::Custom {
                 module_path, name, ..
             } if self.is_module_path_external(&module_path) => {
@@ -215,6 +245,15 @@ impl<'a> ExternalTypeConverter<'a> {
             Type::Sequence { inner_type } => Type::Sequence {
                 inner_type: Box::new(self.convert_type(*inner_type)),
             },
+            Type::Compressed { inner_type } => Type::Compressed {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
+            Type::Shared { inner_type } => Type::Shared {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
+            Type::Weak { inner_type } => Type::Weak {
+                inner_type: Box::new(self.convert_type(*inner_type)),
+            },
             Type::Map {
                 key_type,
                 value_type,
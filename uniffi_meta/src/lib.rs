@@ -25,7 +25,7 @@ mod metadata;
 // `docs/uniffi-versioning.md` for details.
 //
 // Once we get to 1.0, then we'll need to update the scheme to something like 100 + major_version
-pub const UNIFFI_CONTRACT_VERSION: u32 = 23;
+pub const UNIFFI_CONTRACT_VERSION: u32 = 24;
 
 /// Similar to std::hash::Hash.
 ///
@@ -41,6 +41,12 @@ impl Checksum for bool {
     }
 }
 
+impl Checksum for u32 {
+    fn checksum<H: Hasher>(&self, state: &mut H) {
+        state.write(&self.to_le_bytes());
+    }
+}
+
 impl Checksum for u64 {
     fn checksum<H: Hasher>(&self, state: &mut H) {
         state.write(&self.to_le_bytes());
@@ -122,6 +128,7 @@ impl Checksum for &str {
 pub struct NamespaceMetadata {
     pub crate_name: String,
     pub name: String,
+    pub docstring: Option<String>,
 }
 
 // UDL file included with `include_scaffolding!()`
@@ -142,6 +149,7 @@ pub struct FnMetadata {
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
+    pub docstring: Option<String>,
 }
 
 impl FnMetadata {
@@ -149,6 +157,10 @@ impl FnMetadata {
         fn_symbol_name(&self.module_path, &self.name)
     }
 
+    pub fn remote_symbol_name(&self) -> String {
+        remote_fn_symbol_name(&self.module_path, &self.name)
+    }
+
     pub fn checksum_symbol_name(&self) -> String {
         fn_checksum_symbol_name(&self.module_path, &self.name)
     }
@@ -162,6 +174,7 @@ pub struct ConstructorMetadata {
     pub inputs: Vec<FnParamMetadata>,
     pub throws: Option<Type>,
     pub checksum: Option<u16>,
+    pub docstring: Option<String>,
 }
 
 impl ConstructorMetadata {
@@ -187,8 +200,11 @@ pub struct MethodMetadata {
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
-    pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
+    pub takes_self_by_arc: bool, // `self: Arc<Self>`, only used by the UDL scaffolding and exporter.
+    // `self`, the method consumes the object so the foreign code can't use it after the call
+    pub takes_self_by_value: bool,
     pub checksum: Option<u16>,
+    pub docstring: Option<String>,
 }
 
 impl MethodMetadata {
@@ -210,11 +226,14 @@ pub struct TraitMethodMetadata {
     pub index: u32,
     pub name: String,
     pub is_async: bool,
+    // Does the trait provide a default implementation that foreign implementations can call?
+    pub has_default: bool,
     pub inputs: Vec<FnParamMetadata>,
     pub return_type: Option<Type>,
     pub throws: Option<Type>,
-    pub takes_self_by_arc: bool, // unused except by rust udl bindgen.
+    pub takes_self_by_arc: bool, // `self: Arc<Self>`, only used by the UDL scaffolding and exporter.
     pub checksum: Option<u16>,
+    pub docstring: Option<String>,
 }
 
 impl TraitMethodMetadata {
@@ -227,6 +246,30 @@ impl TraitMethodMetadata {
     }
 }
 
+/// A constant declared in an exported trait
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct TraitConstantMetadata {
+    pub module_path: String,
+    pub trait_name: String,
+    // Note: like for `TraitMethodMetadata`, the position of `index` causes the constants to be
+    // ordered correctly in MetadataGroup.items
+    pub index: u32,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Type,
+    pub value: LiteralMetadata,
+}
+
+/// An exported `const` item
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ConstantMetadata {
+    pub module_path: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: Type,
+    pub value: LiteralMetadata,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct FnParamMetadata {
     pub name: String,
@@ -235,6 +278,7 @@ pub struct FnParamMetadata {
     pub by_ref: bool,
     pub optional: bool,
     pub default: Option<LiteralMetadata>,
+    pub sensitive: bool,
 }
 
 impl FnParamMetadata {
@@ -245,6 +289,7 @@ impl FnParamMetadata {
             by_ref: false,
             optional: false,
             default: None,
+            sensitive: false,
         }
     }
 }
@@ -283,6 +328,14 @@ pub struct RecordMetadata {
     pub module_path: String,
     pub name: String,
     pub fields: Vec<FieldMetadata>,
+    /// Passed over the FFI as a `#[repr(C)]` struct rather than serialized into a `RustBuffer`.
+    /// Only records whose fields are all integers or floats can be passed this way.
+    pub repr_c: bool,
+    /// Forwards the Rust `Ord` implementation to the foreign code
+    pub ord: bool,
+    /// For the companion `{name}Patch` record generated for a record, the name of that record
+    pub patch_for: Option<String>,
+    pub docstring: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
@@ -298,11 +351,31 @@ pub struct EnumMetadata {
     pub module_path: String,
     pub name: String,
     pub variants: Vec<VariantMetadata>,
+    /// `true` for `#[non_exhaustive]` enums, which can get variants the bindings don't know about
+    pub non_exhaustive: bool,
+    pub docstring: Option<String>,
+}
+
+/// A set of bit flags, from `#[derive(uniffi::Flags)]`
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct FlagsMetadata {
+    pub module_path: String,
+    pub name: String,
+    pub flags: Vec<FlagMetadata>,
+    pub docstring: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct FlagMetadata {
+    pub name: String,
+    pub value: u32,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct VariantMetadata {
     pub name: String,
+    /// Explicit discriminant (`Foo = 3`), if one was given
+    pub discr: Option<i64>,
     pub fields: Vec<FieldMetadata>,
 }
 
@@ -312,12 +385,210 @@ pub struct ObjectMetadata {
     pub name: String,
     pub imp: types::ObjectImpl,
     pub uniffi_traits: Vec<UniffiTraitMetadata>,
+    pub docstring: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct CallbackInterfaceMetadata {
     pub module_path: String,
     pub name: String,
+    /// The bindings run the calls to the foreign implementation on the app's main thread
+    pub main_thread: bool,
+    /// Generated for a closure argument, rather than declared by the user.  The bindings present
+    /// these as the language's function type, and the only method is called `invoke`.
+    pub closure: bool,
+    pub docstring: Option<String>,
+}
+
+/// How the methods of an object can be called from several threads
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum ConcurrencyPolicy {
+    /// `#[uniffi::export(serialized)]`: the scaffolding runs one call to the object at a time
+    Serialized,
+    /// `#[uniffi::export(concurrent)]`: the object handles concurrent calls itself
+    Concurrent,
+}
+
+impl ConcurrencyPolicy {
+    pub fn from(v: u8) -> anyhow::Result<Self> {
+        Ok(match v {
+            0 => ConcurrencyPolicy::Serialized,
+            1 => ConcurrencyPolicy::Concurrent,
+            _ => anyhow::bail!("invalid concurrency policy {v}"),
+        })
+    }
+
+    /// The name of the policy, as in the `#[uniffi::export]` attribute
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConcurrencyPolicy::Serialized => "serialized",
+            ConcurrencyPolicy::Concurrent => "concurrent",
+        }
+    }
+}
+
+/// The concurrency policy declared on an exported `impl` block, with the methods of the block
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct ObjectConcurrencyMetadata {
+    pub module_path: String,
+    pub self_name: String,
+    pub policy: ConcurrencyPolicy,
+    pub methods: Vec<String>,
+}
+
+/// The sub-namespace that an exported function is in, from `#[uniffi::export(namespace = "...")]`
+///
+/// The bindings group the functions of each sub-namespace, for example in a Kotlin package or a
+/// Python submodule of their own.  The functions are still exported from the same library.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SubNamespaceMetadata {
+    pub module_path: String,
+    pub fn_name: String,
+    pub namespace: String,
+}
+
+// Names that can't be used for a sub-namespace, because the bindings would declare it next to
+// these.  The Python keywords, the modules and builtins that the Python bindings use, the Kotlin
+// keywords for a package name, and the names that upper camel case into a Swift keyword or a
+// Swift type that the bindings use, for the enum.
+const RESERVED_SUB_NAMESPACE_NAMES: &[&str] = &[
+    // Python
+    "and",
+    "as",
+    "assert",
+    "async",
+    "await",
+    "break",
+    "class",
+    "continue",
+    "def",
+    "del",
+    "elif",
+    "else",
+    "except",
+    "finally",
+    "for",
+    "from",
+    "global",
+    "if",
+    "import",
+    "in",
+    "is",
+    "lambda",
+    "nonlocal",
+    "not",
+    "or",
+    "pass",
+    "raise",
+    "return",
+    "try",
+    "while",
+    "with",
+    "yield",
+    "asyncio",
+    "atexit",
+    "cffi",
+    "concurrent",
+    "contextlib",
+    "ctypes",
+    "datetime",
+    "decimal",
+    "enum",
+    "ipaddress",
+    "os",
+    "pathlib",
+    "platform",
+    "shutil",
+    "struct",
+    "sys",
+    "tempfile",
+    "threading",
+    "types",
+    "typing",
+    "uuid",
+    "weakref",
+    "bool",
+    "bytes",
+    "dict",
+    "float",
+    "int",
+    "isinstance",
+    "len",
+    "list",
+    "object",
+    "print",
+    "property",
+    "range",
+    "set",
+    "str",
+    "super",
+    "tuple",
+    "type",
+    // Kotlin
+    "do",
+    "false",
+    "fun",
+    "interface",
+    "null",
+    "package",
+    "this",
+    "throw",
+    "true",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    // Swift
+    "any",
+    "array",
+    "character",
+    "data",
+    "date",
+    "dictionary",
+    "double",
+    "error",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "never",
+    "optional",
+    "protocol",
+    "result",
+    "self",
+    "string",
+    "task",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "void",
+];
+
+/// Check that `name` can be used as a sub-namespace: it must be snake_case and can't clash with a
+/// keyword or a name that the bindings use.
+pub fn check_sub_namespace_name(name: &str) -> anyhow::Result<()> {
+    let mut chars = name.chars();
+    let is_snake_case = matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !is_snake_case {
+        anyhow::bail!("sub-namespace names must be snake_case, like `storage`");
+    }
+    if RESERVED_SUB_NAMESPACE_NAMES.contains(&name) {
+        anyhow::bail!(
+            "`{name}` is a keyword or a name used by the bindings, it can't be a sub-namespace"
+        );
+    }
+    Ok(())
+}
+
+impl RecordMetadata {
+    /// FFI symbol name for the function that compares two of these records, if it forwards `Ord`
+    pub fn cmp_ffi_symbol_name(&self) -> String {
+        record_cmp_fn_symbol_name(&self.module_path, &self.name)
+    }
 }
 
 impl ObjectMetadata {
@@ -345,6 +616,66 @@ pub enum UniffiTraitMetadata {
     Hash {
         hash: MethodMetadata,
     },
+    Ord {
+        cmp: MethodMetadata,
+    },
+    Restore {
+        serialize: MethodMetadata,
+        restore: FnMetadata,
+    },
+}
+
+impl UniffiTraitMetadata {
+    pub fn module_path(&self) -> &String {
+        &match self {
+            UniffiTraitMetadata::Debug { fmt } => fmt,
+            UniffiTraitMetadata::Display { fmt } => fmt,
+            UniffiTraitMetadata::Eq { eq, .. } => eq,
+            UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
+            UniffiTraitMetadata::Restore { serialize, .. } => serialize,
+        }
+        .module_path
+    }
+
+    pub fn self_name(&self) -> &String {
+        &match self {
+            UniffiTraitMetadata::Debug { fmt } => fmt,
+            UniffiTraitMetadata::Display { fmt } => fmt,
+            UniffiTraitMetadata::Eq { eq, .. } => eq,
+            UniffiTraitMetadata::Hash { hash } => hash,
+            UniffiTraitMetadata::Ord { cmp } => cmp,
+            UniffiTraitMetadata::Restore { serialize, .. } => serialize,
+        }
+        .self_name
+    }
+}
+
+/// Discriminants for [UniffiTraitMetadata], these are the values written to the metadata buffers
+/// by the proc-macros.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UniffiTraitDiscriminants {
+    Debug,
+    Display,
+    Eq,
+    Hash,
+    Ord,
+    Restore,
+}
+
+impl UniffiTraitDiscriminants {
+    pub fn from(v: u8) -> anyhow::Result<Self> {
+        Ok(match v {
+            0 => UniffiTraitDiscriminants::Debug,
+            1 => UniffiTraitDiscriminants::Display,
+            2 => UniffiTraitDiscriminants::Eq,
+            3 => UniffiTraitDiscriminants::Hash,
+            4 => UniffiTraitDiscriminants::Ord,
+            5 => UniffiTraitDiscriminants::Restore,
+            _ => anyhow::bail!("invalid trait discriminant {v}"),
+        })
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
@@ -366,6 +697,32 @@ impl ErrorMetadata {
     }
 }
 
+/// Where an item exported with the proc-macros is defined
+///
+/// This isn't part of the interface, it's only used by tools like the IDE index.  `name` is the
+/// item's name, or `Type.name` for methods and constructors.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SourceLocationMetadata {
+    pub module_path: String,
+    pub name: String,
+    pub file: String,
+    pub line: u32,
+}
+
+/// The `#[cfg(...)]` predicate that an exported item is compiled behind
+///
+/// Like `SourceLocationMetadata`, this isn't part of the interface.  It's recorded for items that
+/// were compiled into the library, with `enabled` set, and also for the items that weren't, so that
+/// bindings generators can report which gated items are missing.  `name` is the item's name, or
+/// `Type.name` for methods and constructors.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct CfgMetadata {
+    pub module_path: String,
+    pub name: String,
+    pub predicate: String,
+    pub enabled: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct CustomTypeMetadata {
     pub module_path: String,
@@ -385,6 +742,7 @@ pub fn checksum<T: Checksum>(val: &T) -> u16 {
 
 /// Enum covering all the possible metadata types
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[allow(clippy::large_enum_variant)] // Metadata is only handled at bindgen time
 pub enum Metadata {
     Namespace(NamespaceMetadata),
     UdlFile(UdlFile),
@@ -393,11 +751,19 @@ pub enum Metadata {
     CallbackInterface(CallbackInterfaceMetadata),
     Record(RecordMetadata),
     Enum(EnumMetadata),
+    Flags(FlagsMetadata),
     Error(ErrorMetadata),
     Constructor(ConstructorMetadata),
     Method(MethodMetadata),
     TraitMethod(TraitMethodMetadata),
+    TraitConstant(TraitConstantMetadata),
+    Constant(ConstantMetadata),
     CustomType(CustomTypeMetadata),
+    UniffiTrait(UniffiTraitMetadata),
+    ObjectConcurrency(ObjectConcurrencyMetadata),
+    SubNamespace(SubNamespaceMetadata),
+    SourceLocation(SourceLocationMetadata),
+    Cfg(CfgMetadata),
 }
 
 impl Metadata {
@@ -448,6 +814,12 @@ impl From<EnumMetadata> for Metadata {
     }
 }
 
+impl From<FlagsMetadata> for Metadata {
+    fn from(v: FlagsMetadata) -> Self {
+        Self::Flags(v)
+    }
+}
+
 impl From<ErrorMetadata> for Metadata {
     fn from(e: ErrorMetadata) -> Self {
         Self::Error(e)
@@ -472,8 +844,73 @@ impl From<TraitMethodMetadata> for Metadata {
     }
 }
 
+impl From<TraitConstantMetadata> for Metadata {
+    fn from(v: TraitConstantMetadata) -> Self {
+        Self::TraitConstant(v)
+    }
+}
+
+impl From<ConstantMetadata> for Metadata {
+    fn from(v: ConstantMetadata) -> Self {
+        Self::Constant(v)
+    }
+}
+
+impl From<ObjectConcurrencyMetadata> for Metadata {
+    fn from(v: ObjectConcurrencyMetadata) -> Self {
+        Self::ObjectConcurrency(v)
+    }
+}
+
+impl From<SubNamespaceMetadata> for Metadata {
+    fn from(v: SubNamespaceMetadata) -> Self {
+        Self::SubNamespace(v)
+    }
+}
+
+impl From<SourceLocationMetadata> for Metadata {
+    fn from(v: SourceLocationMetadata) -> Self {
+        Self::SourceLocation(v)
+    }
+}
+
+impl From<CfgMetadata> for Metadata {
+    fn from(v: CfgMetadata) -> Self {
+        Self::Cfg(v)
+    }
+}
+
 impl From<CustomTypeMetadata> for Metadata {
     fn from(v: CustomTypeMetadata) -> Self {
         Self::CustomType(v)
     }
 }
+
+impl From<UniffiTraitMetadata> for Metadata {
+    fn from(v: UniffiTraitMetadata) -> Self {
+        Self::UniffiTrait(v)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use siphasher::sip::SipHasher13;
+
+    fn checksum<T: Checksum>(value: &T) -> u64 {
+        let mut hasher = SipHasher13::new();
+        value.checksum(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_checksum_u32() {
+        // The bytes are hashed in the same order on every platform
+        let mut hasher = SipHasher13::new();
+        hasher.write(&[0x78, 0x56, 0x34, 0x12]);
+        assert_eq!(checksum(&0x12345678u32), hasher.finish());
+        assert_ne!(checksum(&1u32), checksum(&2u32));
+        // A `u32` field doesn't hash like the `u64` with the same value
+        assert_ne!(checksum(&1u32), checksum(&1u64));
+    }
+}
@@ -21,6 +21,14 @@ pub mod codes {
     pub const UDL_FILE: u8 = 8;
     pub const CALLBACK_INTERFACE: u8 = 9;
     pub const TRAIT_METHOD: u8 = 10;
+    pub const UNIFFI_TRAIT: u8 = 11;
+    pub const TRAIT_CONSTANT: u8 = 12;
+    pub const SOURCE_LOCATION: u8 = 13;
+    pub const CFG: u8 = 14;
+    pub const CONSTANT: u8 = 15;
+    pub const FLAGS: u8 = 16;
+    pub const OBJECT_CONCURRENCY: u8 = 17;
+    pub const SUB_NAMESPACE: u8 = 18;
     //pub const UNKNOWN: u8 = 255;
 
     // Type codes
@@ -50,6 +58,19 @@ pub mod codes {
     pub const TYPE_RESULT: u8 = 23;
     //pub const TYPE_FUTURE: u8 = 24;
     pub const TYPE_FOREIGN_EXECUTOR: u8 = 25;
+    pub const TYPE_BYTES: u8 = 26;
+    pub const TYPE_URL: u8 = 27;
+    pub const TYPE_UUID: u8 = 28;
+    pub const TYPE_DECIMAL: u8 = 29;
+    pub const TYPE_COMPRESSED: u8 = 30;
+    pub const TYPE_JSON: u8 = 31;
+    pub const TYPE_PATH: u8 = 32;
+    pub const TYPE_IP_ADDR: u8 = 33;
+    pub const TYPE_SOCKET_ADDR: u8 = 34;
+    pub const TYPE_FLAGS: u8 = 35;
+    pub const TYPE_SHARED: u8 = 36;
+    pub const TYPE_WEAK: u8 = 37;
+    pub const TYPE_CHAR: u8 = 38;
     pub const TYPE_UNIT: u8 = 255;
 
     // Literal codes
@@ -40,6 +40,7 @@ impl<'a> MetadataReader<'a> {
             codes::NAMESPACE => NamespaceMetadata {
                 crate_name: self.read_string()?,
                 name: self.read_string()?,
+                docstring: None,
             }
             .into(),
             codes::UDL_FILE => UdlFile {
@@ -51,11 +52,19 @@ impl<'a> MetadataReader<'a> {
             codes::CONSTRUCTOR => self.read_constructor()?.into(),
             codes::METHOD => self.read_method()?.into(),
             codes::RECORD => self.read_record()?.into(),
-            codes::ENUM => self.read_enum(false)?.into(),
+            codes::ENUM => self.read_enum()?.into(),
+            codes::FLAGS => self.read_flags()?.into(),
             codes::ERROR => self.read_error()?.into(),
             codes::INTERFACE => self.read_object()?.into(),
             codes::CALLBACK_INTERFACE => self.read_callback_interface()?.into(),
             codes::TRAIT_METHOD => self.read_trait_method()?.into(),
+            codes::UNIFFI_TRAIT => self.read_uniffi_trait()?.into(),
+            codes::TRAIT_CONSTANT => self.read_trait_constant()?.into(),
+            codes::CONSTANT => self.read_constant()?.into(),
+            codes::OBJECT_CONCURRENCY => self.read_object_concurrency()?.into(),
+            codes::SUB_NAMESPACE => self.read_sub_namespace()?.into(),
+            codes::SOURCE_LOCATION => self.read_source_location()?.into(),
+            codes::CFG => self.read_cfg()?.into(),
             _ => bail!("Unexpected metadata code: {value:?}"),
         })
     }
@@ -117,10 +126,19 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_F32 => Type::Float32,
             codes::TYPE_F64 => Type::Float64,
             codes::TYPE_BOOL => Type::Boolean,
+            codes::TYPE_CHAR => Type::Char,
             codes::TYPE_STRING => Type::String,
             codes::TYPE_DURATION => Type::Duration,
             codes::TYPE_SYSTEM_TIME => Type::Timestamp,
             codes::TYPE_FOREIGN_EXECUTOR => Type::ForeignExecutor,
+            codes::TYPE_BYTES => Type::Bytes,
+            codes::TYPE_URL => Type::Url,
+            codes::TYPE_UUID => Type::Uuid,
+            codes::TYPE_DECIMAL => Type::Decimal,
+            codes::TYPE_JSON => Type::Json,
+            codes::TYPE_PATH => Type::Path,
+            codes::TYPE_IP_ADDR => Type::IpAddr,
+            codes::TYPE_SOCKET_ADDR => Type::SocketAddr,
             codes::TYPE_RECORD => Type::Record {
                 module_path: self.read_string()?,
                 name: self.read_string()?,
@@ -129,6 +147,10 @@ impl<'a> MetadataReader<'a> {
                 module_path: self.read_string()?,
                 name: self.read_string()?,
             },
+            codes::TYPE_FLAGS => Type::Flags {
+                module_path: self.read_string()?,
+                name: self.read_string()?,
+            },
             codes::TYPE_INTERFACE => Type::Object {
                 module_path: self.read_string()?,
                 name: self.read_string()?,
@@ -149,6 +171,15 @@ impl<'a> MetadataReader<'a> {
             codes::TYPE_VEC => Type::Sequence {
                 inner_type: Box::new(self.read_type()?),
             },
+            codes::TYPE_COMPRESSED => Type::Compressed {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_SHARED => Type::Shared {
+                inner_type: Box::new(self.read_type()?),
+            },
+            codes::TYPE_WEAK => Type::Weak {
+                inner_type: Box::new(self.read_type()?),
+            },
             codes::TYPE_HASH_MAP => Type::Map {
                 key_type: Box::new(self.read_type()?),
                 value_type: Box::new(self.read_type()?),
@@ -159,6 +190,13 @@ impl<'a> MetadataReader<'a> {
         })
     }
 
+    fn read_optional_string(&mut self) -> Result<Option<String>> {
+        Ok(match self.read_bool()? {
+            true => Some(self.read_string()?),
+            false => None,
+        })
+    }
+
     fn read_optional_type(&mut self) -> Result<Option<Type>> {
         Ok(match self.peek_u8()? {
             codes::TYPE_UNIT => {
@@ -197,6 +235,7 @@ impl<'a> MetadataReader<'a> {
             return_type,
             throws,
             checksum: self.calc_checksum(),
+            docstring: None,
         })
     }
 
@@ -223,6 +262,7 @@ impl<'a> MetadataReader<'a> {
             inputs,
             throws,
             checksum: self.calc_checksum(),
+            docstring: None,
         })
     }
 
@@ -231,6 +271,8 @@ impl<'a> MetadataReader<'a> {
         let self_name = self.read_string()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let takes_self_by_arc = self.read_bool()?;
+        let takes_self_by_value = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         Ok(MethodMetadata {
@@ -241,8 +283,10 @@ impl<'a> MetadataReader<'a> {
             inputs,
             return_type,
             throws,
-            takes_self_by_arc: false, // not emitted by macros
+            takes_self_by_arc,
+            takes_self_by_value,
             checksum: self.calc_checksum(),
+            docstring: None,
         })
     }
 
@@ -251,28 +295,64 @@ impl<'a> MetadataReader<'a> {
             module_path: self.read_string()?,
             name: self.read_string()?,
             fields: self.read_fields()?,
+            repr_c: self.read_bool()?,
+            ord: self.read_bool()?,
+            patch_for: self.read_optional_string()?,
+            docstring: None,
         })
     }
 
-    fn read_enum(&mut self, is_flat_error: bool) -> Result<EnumMetadata> {
+    fn read_enum(&mut self) -> Result<EnumMetadata> {
         let module_path = self.read_string()?;
         let name = self.read_string()?;
-        let variants = if is_flat_error {
-            self.read_flat_variants()?
-        } else {
-            self.read_variants()?
-        };
+        let non_exhaustive = self.read_bool()?;
+        let variants = self.read_variants()?;
 
         Ok(EnumMetadata {
             module_path,
             name,
             variants,
+            non_exhaustive,
+            docstring: None,
+        })
+    }
+
+    fn read_flags(&mut self) -> Result<FlagsMetadata> {
+        let module_path = self.read_string()?;
+        let name = self.read_string()?;
+        let len = self.read_u8()?;
+        let flags = (0..len)
+            .map(|_| {
+                Ok(FlagMetadata {
+                    name: self.read_string()?,
+                    value: self.read_u32()?,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(FlagsMetadata {
+            module_path,
+            name,
+            flags,
+            docstring: None,
         })
     }
 
     fn read_error(&mut self) -> Result<ErrorMetadata> {
         let is_flat = self.read_bool()?;
-        let enum_ = self.read_enum(is_flat)?;
+        let module_path = self.read_string()?;
+        let name = self.read_string()?;
+        let variants = if is_flat {
+            self.read_flat_variants()?
+        } else {
+            self.read_variants()?
+        };
+        let enum_ = EnumMetadata {
+            module_path,
+            name,
+            variants,
+            non_exhaustive: false,
+            docstring: None,
+        };
         Ok(ErrorMetadata::Enum { enum_, is_flat })
     }
 
@@ -282,13 +362,57 @@ impl<'a> MetadataReader<'a> {
             name: self.read_string()?,
             imp: ObjectImpl::from_is_trait(self.read_bool()?),
             uniffi_traits: vec![], // TODO: not yet emitted
+            docstring: None,
         })
     }
 
+    fn read_uniffi_trait(&mut self) -> Result<UniffiTraitMetadata> {
+        let discriminant = UniffiTraitDiscriminants::from(self.read_u8()?)?;
+        Ok(match discriminant {
+            UniffiTraitDiscriminants::Debug => UniffiTraitMetadata::Debug {
+                fmt: self.read_trait_method_item()?,
+            },
+            UniffiTraitDiscriminants::Display => UniffiTraitMetadata::Display {
+                fmt: self.read_trait_method_item()?,
+            },
+            UniffiTraitDiscriminants::Eq => UniffiTraitMetadata::Eq {
+                eq: self.read_trait_method_item()?,
+                ne: self.read_trait_method_item()?,
+            },
+            UniffiTraitDiscriminants::Hash => UniffiTraitMetadata::Hash {
+                hash: self.read_trait_method_item()?,
+            },
+            UniffiTraitDiscriminants::Ord => UniffiTraitMetadata::Ord {
+                cmp: self.read_trait_method_item()?,
+            },
+            UniffiTraitDiscriminants::Restore => UniffiTraitMetadata::Restore {
+                serialize: self.read_trait_method_item()?,
+                restore: self.read_trait_fn_item()?,
+            },
+        })
+    }
+
+    // Read one of the method items stored inside a `UNIFFI_TRAIT` item
+    fn read_trait_method_item(&mut self) -> Result<MethodMetadata> {
+        let code = self.read_u8()?;
+        ensure!(code == codes::METHOD, "expected METHOD but read {code}");
+        self.read_method()
+    }
+
+    // Read one of the function items stored inside a `UNIFFI_TRAIT` item
+    fn read_trait_fn_item(&mut self) -> Result<FnMetadata> {
+        let code = self.read_u8()?;
+        ensure!(code == codes::FUNC, "expected FUNC but read {code}");
+        self.read_func()
+    }
+
     fn read_callback_interface(&mut self) -> Result<CallbackInterfaceMetadata> {
         Ok(CallbackInterfaceMetadata {
             module_path: self.read_string()?,
             name: self.read_string()?,
+            main_thread: self.read_bool()?,
+            closure: self.read_bool()?,
+            docstring: None,
         })
     }
 
@@ -298,6 +422,8 @@ impl<'a> MetadataReader<'a> {
         let index = self.read_u32()?;
         let name = self.read_string()?;
         let is_async = self.read_bool()?;
+        let has_default = self.read_bool()?;
+        let takes_self_by_arc = self.read_bool()?;
         let inputs = self.read_inputs()?;
         let (return_type, throws) = self.read_return_type()?;
         Ok(TraitMethodMetadata {
@@ -306,11 +432,85 @@ impl<'a> MetadataReader<'a> {
             index,
             name,
             is_async,
+            has_default,
             inputs,
             return_type,
             throws,
-            takes_self_by_arc: false, // not emitted by macros
+            takes_self_by_arc,
             checksum: self.calc_checksum(),
+            docstring: None,
+        })
+    }
+
+    fn read_trait_constant(&mut self) -> Result<TraitConstantMetadata> {
+        let module_path = self.read_string()?;
+        let trait_name = self.read_string()?;
+        let index = self.read_u32()?;
+        let name = self.read_string()?;
+        let ty = self.read_type()?;
+        let value = self.read_literal(&name, &ty)?;
+        Ok(TraitConstantMetadata {
+            module_path,
+            trait_name,
+            index,
+            name,
+            ty,
+            value,
+        })
+    }
+
+    fn read_constant(&mut self) -> Result<ConstantMetadata> {
+        let module_path = self.read_string()?;
+        let name = self.read_string()?;
+        let ty = self.read_type()?;
+        let value = self.read_literal(&name, &ty)?;
+        Ok(ConstantMetadata {
+            module_path,
+            name,
+            ty,
+            value,
+        })
+    }
+
+    fn read_object_concurrency(&mut self) -> Result<ObjectConcurrencyMetadata> {
+        let module_path = self.read_string()?;
+        let self_name = self.read_string()?;
+        let policy = ConcurrencyPolicy::from(self.read_u8()?)?;
+        let len = self.read_u8()?;
+        let methods = (0..len)
+            .map(|_| self.read_string())
+            .collect::<Result<_>>()?;
+        Ok(ObjectConcurrencyMetadata {
+            module_path,
+            self_name,
+            policy,
+            methods,
+        })
+    }
+
+    fn read_sub_namespace(&mut self) -> Result<SubNamespaceMetadata> {
+        Ok(SubNamespaceMetadata {
+            module_path: self.read_string()?,
+            fn_name: self.read_string()?,
+            namespace: self.read_string()?,
+        })
+    }
+
+    fn read_source_location(&mut self) -> Result<SourceLocationMetadata> {
+        Ok(SourceLocationMetadata {
+            module_path: self.read_string()?,
+            name: self.read_string()?,
+            file: self.read_string()?,
+            line: self.read_u32()?,
+        })
+    }
+
+    fn read_cfg(&mut self) -> Result<CfgMetadata> {
+        Ok(CfgMetadata {
+            module_path: self.read_string()?,
+            name: self.read_string()?,
+            predicate: self.read_string()?,
+            enabled: self.read_bool()?,
         })
     }
 
@@ -332,6 +532,7 @@ impl<'a> MetadataReader<'a> {
             .map(|_| {
                 Ok(VariantMetadata {
                     name: self.read_string()?,
+                    discr: self.read_discr()?,
                     fields: self.read_fields()?,
                 })
             })
@@ -344,7 +545,9 @@ impl<'a> MetadataReader<'a> {
             .map(|_| {
                 Ok(VariantMetadata {
                     name: self.read_string()?,
-                    fields: vec![],
+                    discr: None,
+                    // The fields marked with `#[uniffi(error_field)]`
+                    fields: self.read_fields()?,
                 })
             })
             .collect()
@@ -357,8 +560,9 @@ impl<'a> MetadataReader<'a> {
                 Ok(FnParamMetadata {
                     name: self.read_string()?,
                     ty: self.read_type()?,
+                    by_ref: self.read_bool()?,
+                    sensitive: self.read_bool()?,
                     // not emitted by macros
-                    by_ref: false,
                     optional: false,
                     default: None,
                 })
@@ -372,18 +576,30 @@ impl<'a> MetadataReader<'a> {
         Some(checksum_metadata(metadata_buf))
     }
 
+    fn read_discr(&mut self) -> Result<Option<i64>> {
+        let has_discr = self.read_bool()?;
+        if !has_discr {
+            return Ok(None);
+        }
+        let base10_digits = self.read_string()?;
+        Ok(Some(base10_digits.parse()?))
+    }
+
     fn read_default(&mut self, name: &str, ty: &Type) -> Result<Option<LiteralMetadata>> {
         let has_default = self.read_bool()?;
         if !has_default {
             return Ok(None);
         }
+        self.read_literal(name, ty).map(Some)
+    }
 
+    fn read_literal(&mut self, name: &str, ty: &Type) -> Result<LiteralMetadata> {
         let literal_kind = self.read_u8()?;
-        Ok(Some(match literal_kind {
+        Ok(match literal_kind {
             codes::LIT_STR => {
                 ensure!(
                     matches!(ty, Type::String),
-                    "field {name} of type {ty:?} can't have a default value of type string"
+                    "{name} of type {ty:?} can't have a value of type string"
                 );
                 LiteralMetadata::String(self.read_string()?)
             }
@@ -394,7 +610,7 @@ impl<'a> MetadataReader<'a> {
                         LiteralMetadata::$variant(
                             base10_digits
                                 .parse::<$ty>()
-                                .with_context(|| format!("parsing default for field {name}"))?
+                                .with_context(|| format!("parsing the value of {name}"))?
                                 .into(),
                             Radix::Decimal,
                             ty.to_owned(),
@@ -412,7 +628,7 @@ impl<'a> MetadataReader<'a> {
                     Type::UInt64 => parse_int!(u64, UInt),
                     Type::Int64 => parse_int!(i64, Int),
                     _ => {
-                        bail!("field {name} of type {ty:?} can't have a default value of type integer");
+                        bail!("{name} of type {ty:?} can't have a value of type integer");
                     }
                 }
             }
@@ -421,12 +637,12 @@ impl<'a> MetadataReader<'a> {
                     LiteralMetadata::Float(self.read_string()?, ty.to_owned())
                 }
                 _ => {
-                    bail!("field {name} of type {ty:?} can't have a default value of type float");
+                    bail!("{name} of type {ty:?} can't have a value of type float");
                 }
             },
             codes::LIT_BOOL => LiteralMetadata::Boolean(self.read_bool()?),
             codes::LIT_NULL => LiteralMetadata::Null,
             _ => bail!("Unexpected literal kind code: {literal_kind:?}"),
-        }))
+        })
     }
 }
@@ -56,6 +56,8 @@ pub enum ExternalKind {
     Interface,
     // Either a record or enum
     DataClass,
+    // Passed as its bits, like local flags
+    Flags,
 }
 
 /// Represents all the different high-level types that can be used in a component interface.
@@ -75,10 +77,19 @@ pub enum Type {
     Float32,
     Float64,
     Boolean,
+    // A Unicode scalar value, Rust's `char`
+    Char,
     String,
     Bytes,
     Timestamp,
     Duration,
+    Url,
+    Uuid,
+    Decimal,
+    Json,
+    Path,
+    IpAddr,
+    SocketAddr,
     Object {
         // The module path to the object
         module_path: String,
@@ -97,6 +108,11 @@ pub enum Type {
         module_path: String,
         name: String,
     },
+    // A set of named bit flags, passed across the FFI as a `u32`
+    Flags {
+        module_path: String,
+        name: String,
+    },
     CallbackInterface {
         module_path: String,
         name: String,
@@ -112,6 +128,18 @@ pub enum Type {
         key_type: Box<Type>,
         value_type: Box<Type>,
     },
+    // `uniffi::Compressed<T>`, which the bindings present as a plain `T`
+    Compressed {
+        inner_type: Box<Type>,
+    },
+    // `uniffi::Shared<T>`, which the bindings present as a plain `T`
+    Shared {
+        inner_type: Box<Type>,
+    },
+    // `std::sync::Weak<T>`, a reference to an object that doesn't keep it alive
+    Weak {
+        inner_type: Box<Type>,
+    },
     // An FfiConverter we `use` from an external crate
     External {
         module_path: String,
@@ -129,9 +157,11 @@ pub enum Type {
 impl Type {
     pub fn iter_types(&self) -> TypeIterator<'_> {
         let nested_types = match self {
-            Type::Optional { inner_type } | Type::Sequence { inner_type } => {
-                inner_type.iter_types()
-            }
+            Type::Optional { inner_type }
+            | Type::Sequence { inner_type }
+            | Type::Compressed { inner_type }
+            | Type::Shared { inner_type }
+            | Type::Weak { inner_type } => inner_type.iter_types(),
             Type::Map {
                 key_type,
                 value_type,
@@ -15,6 +15,12 @@ use std::{
     process::{Command, Stdio},
 };
 
+mod runners;
+pub use runners::{
+    ForeignTestRunner, GradleTest, Pytest, TestCase, TestEnvironment, TestOutcome, TestReport,
+    XcTest,
+};
+
 // A source to compile for a test
 #[derive(Debug)]
 pub struct CompileSource {
@@ -146,6 +152,21 @@ impl UniFFITestHelper {
     pub fn cdylib_path(&self) -> Result<Utf8PathBuf> {
         Self::find_cdylib_path(&self.package)
     }
+
+    /// Run a foreign-language test suite and collect its results
+    ///
+    /// This creates `env.results_dir`, runs the suite and parses the JUnit XML reports that it
+    /// wrote.  Failed tests don't make this return an error, call [TestReport::ensure_passed] to
+    /// check them.  An error is returned if the suite didn't report any tests, or if it failed
+    /// without reporting a failed test, for example because it didn't compile.
+    pub fn run_test_suite(
+        &self,
+        runner: &dyn ForeignTestRunner,
+        env: &TestEnvironment,
+    ) -> Result<TestReport> {
+        println!("Running {} tests for {}", runner.name(), self.name);
+        runners::run_test_suite(runner, env)
+    }
 }
 
 fn get_cargo_metadata() -> Metadata {
@@ -162,9 +183,11 @@ fn get_cargo_build_messages() -> Vec<Message> {
         .spawn()
         .expect("Error running cargo build");
     let output = std::io::BufReader::new(child.stdout.take().unwrap());
-    Message::parse_stream(output)
+    let messages = Message::parse_stream(output)
         .map(|m| m.expect("Error parsing cargo build messages"))
-        .collect()
+        .collect();
+    child.wait().expect("Error waiting for cargo build");
+    messages
 }
 
 fn hash_path(path: &Utf8Path) -> String {
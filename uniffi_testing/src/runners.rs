@@ -0,0 +1,632 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+License, v. 2.0. If a copy of the MPL was not distributed with this
+* file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Runners for foreign-language test suites
+//!
+//! Test scripts are run directly, but real test suites need their test framework: pytest, a
+//! Gradle test task running JUnit tests, or XCTest through SwiftPM.  A [ForeignTestRunner] builds
+//! the command that runs a suite against the bindings in a test's `out_dir`.  Every runner
+//! writes JUnit XML reports, which [UniFFITestHelper::run_test_suite] parses into a
+//! [TestReport], so that `cargo test` lists the foreign tests that failed.
+//!
+//! [UniFFITestHelper::run_test_suite]: crate::UniFFITestHelper::run_test_suite
+
+use anyhow::{bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use fs_err as fs;
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{env, ffi::OsString, fmt, process::Command};
+
+/// Where a test suite finds the bindings, and where it writes its reports
+#[derive(Debug, Clone)]
+pub struct TestEnvironment {
+    /// Directory with the generated bindings and the fixture's cdylib
+    pub out_dir: Utf8PathBuf,
+    /// Directory for the JUnit XML reports, created before the suite runs
+    pub results_dir: Utf8PathBuf,
+    /// Paths to add to the language's search path: `PYTHONPATH` for pytest, the test classpath for
+    /// Gradle and the import search path for XCTest
+    pub search_paths: Vec<Utf8PathBuf>,
+    /// Directories with the native libraries that the bindings load
+    pub library_dirs: Vec<Utf8PathBuf>,
+    /// Extra arguments for the compiler, only used by XCTest to pass flags to `swiftc`
+    pub compiler_args: Vec<String>,
+}
+
+impl TestEnvironment {
+    /// Create an environment for the bindings and libraries in `out_dir`
+    ///
+    /// The reports are written to the `test-results` subdirectory.
+    pub fn new(out_dir: impl Into<Utf8PathBuf>) -> Self {
+        let out_dir = out_dir.into();
+        Self {
+            results_dir: out_dir.join("test-results"),
+            search_paths: vec![out_dir.clone()],
+            library_dirs: vec![out_dir.clone()],
+            compiler_args: vec![],
+            out_dir,
+        }
+    }
+
+    // Join `paths` with the existing value of the `var` environment variable
+    fn join_with_env(paths: &[Utf8PathBuf], var: &str) -> Result<OsString> {
+        let existing = env::var_os(var).unwrap_or_default();
+        Ok(env::join_paths(
+            paths
+                .iter()
+                .map(|p| p.as_std_path().to_path_buf())
+                .chain(env::split_paths(&existing)),
+        )?)
+    }
+}
+
+/// Runs a foreign-language test suite
+pub trait ForeignTestRunner {
+    /// Name of the runner, used in messages
+    fn name(&self) -> &str;
+
+    /// Build the command that runs the suite
+    ///
+    /// The command must write its JUnit XML reports to the files returned by `reports()`.
+    fn command(&self, env: &TestEnvironment) -> Result<Command>;
+
+    /// List the JUnit XML reports written by the command
+    ///
+    /// This is called after the command exits.  The default lists the `.xml` files in
+    /// `results_dir`.
+    fn reports(&self, env: &TestEnvironment) -> Result<Vec<Utf8PathBuf>> {
+        let mut reports = vec![];
+        for entry in fs::read_dir(&env.results_dir)? {
+            let path = Utf8PathBuf::try_from(entry?.path())?;
+            if path.extension() == Some("xml") {
+                reports.push(path);
+            }
+        }
+        reports.sort();
+        Ok(reports)
+    }
+}
+
+/// Runs a Python test suite with pytest
+///
+/// The bindings can be imported directly, since `out_dir` is added to `PYTHONPATH`.
+#[derive(Debug, Clone)]
+pub struct Pytest {
+    tests: Utf8PathBuf,
+    args: Vec<String>,
+}
+
+impl Pytest {
+    /// Run the tests in `tests`, a test file or a directory that pytest collects tests from
+    pub fn new(tests: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            tests: tests.into(),
+            args: vec![],
+        }
+    }
+
+    /// Add an argument for pytest, for example `-k` to select tests
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+impl ForeignTestRunner for Pytest {
+    fn name(&self) -> &str {
+        "pytest"
+    }
+
+    fn command(&self, env: &TestEnvironment) -> Result<Command> {
+        let mut command = Command::new("python3");
+        command
+            .current_dir(&env.out_dir)
+            .env(
+                "PYTHONPATH",
+                TestEnvironment::join_with_env(&env.search_paths, "PYTHONPATH")?,
+            )
+            .args(["-m", "pytest", "-p", "no:cacheprovider"])
+            .arg(format!("--junitxml={}", env.results_dir.join("pytest.xml")))
+            .arg(&self.tests)
+            .args(&self.args);
+        Ok(command)
+    }
+}
+
+/// Runs a Gradle test task, usually the JUnit tests of a Kotlin project
+///
+/// The project's build script gets the setup from environment variables:
+///   - `UNIFFI_CLASSPATH`: the jars and directories with the compiled bindings and their
+///     dependencies, like JNA, to add to the test classpath
+///   - `UNIFFI_LIBRARY_PATH`: the directories with the native libraries, for the
+///     `jna.library.path` system property of the test JVM
+///   - `UNIFFI_TEST_RESULTS_DIR`: where the test task must write its JUnit XML reports
+///
+/// The project's `gradlew` wrapper is used if it has one, otherwise `gradle`.
+#[derive(Debug, Clone)]
+pub struct GradleTest {
+    project_dir: Utf8PathBuf,
+    task: String,
+}
+
+impl GradleTest {
+    /// Run the `test` task of the project in `project_dir`
+    pub fn new(project_dir: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            project_dir: project_dir.into(),
+            task: "test".to_string(),
+        }
+    }
+
+    /// Run another test task, for example `jvmTest` in a multiplatform project
+    pub fn task(mut self, task: impl Into<String>) -> Self {
+        self.task = task.into();
+        self
+    }
+}
+
+impl ForeignTestRunner for GradleTest {
+    fn name(&self) -> &str {
+        "gradle"
+    }
+
+    fn command(&self, env: &TestEnvironment) -> Result<Command> {
+        let wrapper = self.project_dir.join("gradlew");
+        let mut command = if wrapper.exists() {
+            Command::new(wrapper)
+        } else {
+            Command::new("gradle")
+        };
+        // Gradle skips test tasks whose inputs didn't change, which wouldn't write any reports, so
+        // clean the task's outputs first.
+        let mut task_chars = self.task.chars();
+        let clean_task = match task_chars.next() {
+            Some(first) => format!("clean{}{}", first.to_uppercase(), task_chars.as_str()),
+            None => bail!("empty Gradle task name"),
+        };
+        command
+            .current_dir(&self.project_dir)
+            .env(
+                "UNIFFI_CLASSPATH",
+                TestEnvironment::join_with_env(&env.search_paths, "CLASSPATH")?,
+            )
+            .env(
+                "UNIFFI_LIBRARY_PATH",
+                TestEnvironment::join_with_env(&env.library_dirs, "UNIFFI_LIBRARY_PATH")?,
+            )
+            .env("UNIFFI_TEST_RESULTS_DIR", &env.results_dir)
+            .arg("--project-dir")
+            .arg(&self.project_dir)
+            .arg(clean_task)
+            .arg(&self.task);
+        Ok(command)
+    }
+}
+
+/// Runs the XCTest tests of a SwiftPM package with `swift test`
+///
+/// The compiled bindings module is found through the search paths and library directories, and
+/// `compiler_args` are passed on to `swiftc`, for example to load the bindings' module map.
+#[derive(Debug, Clone)]
+pub struct XcTest {
+    package_dir: Utf8PathBuf,
+    filter: Option<String>,
+}
+
+impl XcTest {
+    /// Run the tests of the package in `package_dir`
+    pub fn new(package_dir: impl Into<Utf8PathBuf>) -> Self {
+        Self {
+            package_dir: package_dir.into(),
+            filter: None,
+        }
+    }
+
+    /// Only run the tests that match `filter`, see `swift test --filter`
+    pub fn filter(mut self, filter: impl Into<String>) -> Self {
+        self.filter = Some(filter.into());
+        self
+    }
+}
+
+impl ForeignTestRunner for XcTest {
+    fn name(&self) -> &str {
+        "XCTest"
+    }
+
+    fn command(&self, env: &TestEnvironment) -> Result<Command> {
+        let mut command = Command::new("swift");
+        command
+            .arg("test")
+            .arg("--package-path")
+            .arg(&self.package_dir)
+            // SwiftPM only writes the xUnit report for parallel runs
+            .arg("--parallel")
+            .arg("--xunit-output")
+            .arg(env.results_dir.join("xctest.xml"));
+        for path in &env.search_paths {
+            command.args(["-Xswiftc", "-I", "-Xswiftc", path.as_str()]);
+        }
+        for dir in &env.library_dirs {
+            command.args(["-Xlinker", "-L", "-Xlinker", dir.as_str()]);
+            command.args(["-Xlinker", "-rpath", "-Xlinker", dir.as_str()]);
+        }
+        for arg in &env.compiler_args {
+            command.args(["-Xswiftc", arg]);
+        }
+        if let Some(filter) = &self.filter {
+            command.arg("--filter").arg(filter);
+        }
+        Ok(command)
+    }
+}
+
+// See `UniFFITestHelper::run_test_suite()`
+pub(crate) fn run_test_suite(
+    runner: &dyn ForeignTestRunner,
+    env: &TestEnvironment,
+) -> Result<TestReport> {
+    if env.results_dir.exists() {
+        fs::remove_dir_all(&env.results_dir)?;
+    }
+    fs::create_dir_all(&env.results_dir)?;
+    let status = runner
+        .command(env)?
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Error running {}: {e}", runner.name()))?
+        .wait()?;
+    let report = TestReport::from_junit_xml_files(&runner.reports(env)?)?;
+    println!("{}: {report}", runner.name());
+    if !status.success() && report.failed().next().is_none() {
+        bail!(
+            "{} failed ({status}) without reporting a failed test",
+            runner.name()
+        );
+    }
+    // A suite that doesn't find its tests, because of a wrong path or filter, would pass otherwise
+    if report.cases.is_empty() {
+        bail!("{} didn't find any tests", runner.name());
+    }
+    Ok(report)
+}
+
+/// The results of a test suite
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestReport {
+    pub cases: Vec<TestCase>,
+}
+
+/// A test from a [TestReport]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCase {
+    /// The test's class or module, empty if the report doesn't have one
+    pub class_name: String,
+    pub name: String,
+    pub outcome: TestOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    /// The test failed or raised an error.  `details` is usually the stack trace.
+    Failed {
+        message: String,
+        details: String,
+    },
+    Skipped,
+}
+
+impl TestReport {
+    /// Parse a JUnit XML report
+    ///
+    /// This reads the `<testcase>` elements, and the `<failure>`, `<error>` and `<skipped>`
+    /// elements inside them, which is the part of the format that pytest, Gradle and SwiftPM
+    /// agree on.  Test suites are flattened.
+    pub fn from_junit_xml(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        let mut cases = vec![];
+        // The test case whose element is open, and whether its failure element is open
+        let mut current: Option<TestCase> = None;
+        let mut in_failure = false;
+        loop {
+            let event = reader.read_event()?;
+            match &event {
+                Event::Start(e) | Event::Empty(e) => {
+                    let is_empty = matches!(event, Event::Empty(_));
+                    match e.name().as_ref() {
+                        b"testcase" => {
+                            let case = TestCase {
+                                class_name: attribute(e, b"classname")?,
+                                name: attribute(e, b"name")?,
+                                outcome: TestOutcome::Passed,
+                            };
+                            if is_empty {
+                                cases.push(case);
+                            } else {
+                                current = Some(case);
+                            }
+                        }
+                        b"failure" | b"error" => {
+                            if let Some(case) = &mut current {
+                                case.outcome = TestOutcome::Failed {
+                                    message: attribute(e, b"message")?,
+                                    details: String::new(),
+                                };
+                                in_failure = !is_empty;
+                            }
+                        }
+                        b"skipped" => {
+                            if let Some(case) = &mut current {
+                                case.outcome = TestOutcome::Skipped;
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                Event::Text(t) if in_failure => push_details(&mut current, &t.unescape()?),
+                Event::CData(c) if in_failure => {
+                    push_details(&mut current, &String::from_utf8_lossy(c))
+                }
+                Event::End(e) => match e.name().as_ref() {
+                    b"testcase" => cases.extend(current.take()),
+                    b"failure" | b"error" => in_failure = false,
+                    _ => (),
+                },
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+        Ok(Self { cases })
+    }
+
+    /// Parse the JUnit XML reports at `paths` into a single report
+    pub fn from_junit_xml_files(paths: &[impl AsRef<Utf8Path>]) -> Result<Self> {
+        let mut report = Self::default();
+        for path in paths {
+            let path = path.as_ref();
+            match Self::from_junit_xml(&fs::read_to_string(path)?) {
+                Ok(r) => report.cases.extend(r.cases),
+                Err(e) => bail!("Error parsing the test report {path}: {e}"),
+            }
+        }
+        Ok(report)
+    }
+
+    pub fn passed(&self) -> impl Iterator<Item = &TestCase> {
+        self.with_outcome(|o| matches!(o, TestOutcome::Passed))
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &TestCase> {
+        self.with_outcome(|o| matches!(o, TestOutcome::Failed { .. }))
+    }
+
+    pub fn skipped(&self) -> impl Iterator<Item = &TestCase> {
+        self.with_outcome(|o| matches!(o, TestOutcome::Skipped))
+    }
+
+    fn with_outcome(
+        &self,
+        filter: impl Fn(&TestOutcome) -> bool,
+    ) -> impl Iterator<Item = &TestCase> {
+        self.cases.iter().filter(move |c| filter(&c.outcome))
+    }
+
+    /// Return an error that lists the failed tests, if there are any
+    pub fn ensure_passed(&self) -> Result<()> {
+        if self.failed().next().is_some() {
+            bail!("Foreign tests failed: {self}");
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped",
+            self.passed().count(),
+            self.failed().count(),
+            self.skipped().count()
+        )?;
+        for case in self.failed() {
+            if let TestOutcome::Failed { message, details } = &case.outcome {
+                write!(f, "\n\n{case}: {message}")?;
+                if !details.is_empty() {
+                    write!(f, "\n{details}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TestCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.class_name.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}.{}", self.class_name, self.name)
+        }
+    }
+}
+
+// Add the text inside a failure element to the failure details of a test case
+fn push_details(case: &mut Option<TestCase>, text: &str) {
+    if let Some(TestCase {
+        outcome: TestOutcome::Failed { details, .. },
+        ..
+    }) = case
+    {
+        details.push_str(text);
+    }
+}
+
+// Get the unescaped value of an attribute, or an empty string if the element doesn't have it
+fn attribute(e: &BytesStart<'_>, name: &[u8]) -> Result<String> {
+    for attr in e.attributes() {
+        let attr = attr?;
+        if attr.key.as_ref() == name {
+            return Ok(attr.unescape_value()?.into_owned());
+        }
+    }
+    Ok(String::new())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_junit_xml() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+            <testsuites>
+              <testsuite name="pytest" tests="4">
+                <testcase classname="test_arithmetic" name="test_add" time="0.001" />
+                <testcase classname="test_arithmetic" name="test_sub">
+                  <failure message="assert 1 == 2">Traceback &amp; more</failure>
+                </testcase>
+                <testcase classname="test_arithmetic" name="test_div">
+                  <error message="ZeroDivisionError"><![CDATA[at <div>]]></error>
+                </testcase>
+                <testcase name="test_mul"><skipped message="not yet" /></testcase>
+              </testsuite>
+            </testsuites>"#;
+        let report = TestReport::from_junit_xml(xml).unwrap();
+        assert_eq!(
+            report.cases,
+            vec![
+                TestCase {
+                    class_name: "test_arithmetic".into(),
+                    name: "test_add".into(),
+                    outcome: TestOutcome::Passed,
+                },
+                TestCase {
+                    class_name: "test_arithmetic".into(),
+                    name: "test_sub".into(),
+                    outcome: TestOutcome::Failed {
+                        message: "assert 1 == 2".into(),
+                        details: "Traceback & more".into(),
+                    },
+                },
+                TestCase {
+                    class_name: "test_arithmetic".into(),
+                    name: "test_div".into(),
+                    outcome: TestOutcome::Failed {
+                        message: "ZeroDivisionError".into(),
+                        details: "at <div>".into(),
+                    },
+                },
+                TestCase {
+                    class_name: "".into(),
+                    name: "test_mul".into(),
+                    outcome: TestOutcome::Skipped,
+                },
+            ]
+        );
+        assert!(report.ensure_passed().is_err());
+        assert!(report.to_string().starts_with(
+            "1 passed, 2 failed, 1 skipped\n\ntest_arithmetic.test_sub: assert 1 == 2"
+        ));
+    }
+
+    // Writes `report` to the results directory, then exits with `exit_code`
+    struct ScriptRunner {
+        report: Option<&'static str>,
+        exit_code: i32,
+    }
+
+    impl ForeignTestRunner for ScriptRunner {
+        fn name(&self) -> &str {
+            "script"
+        }
+
+        fn command(&self, env: &TestEnvironment) -> Result<Command> {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(r#"[ -z "$REPORT" ] || printf '%s' "$REPORT" > "$RESULTS_DIR/report.xml"; exit $EXIT_CODE"#)
+                .env("REPORT", self.report.unwrap_or_default())
+                .env("RESULTS_DIR", &env.results_dir)
+                .env("EXIT_CODE", self.exit_code.to_string());
+            Ok(command)
+        }
+    }
+
+    fn run(name: &str, report: Option<&'static str>, exit_code: i32) -> Result<TestReport> {
+        let out_dir = Utf8PathBuf::try_from(env::temp_dir())
+            .unwrap()
+            .join(format!("uniffi-runners-{name}-{}", std::process::id()));
+        let env = TestEnvironment::new(&out_dir);
+        let result = run_test_suite(&ScriptRunner { report, exit_code }, &env);
+        fs::remove_dir_all(&out_dir).unwrap();
+        result
+    }
+
+    const PASSING: &str = r#"<testsuite><testcase classname="t" name="test_ok" /></testsuite>"#;
+    const FAILING: &str = r#"<testsuite>
+        <testcase classname="t" name="test_ok" />
+        <testcase classname="t" name="test_bad"><failure message="boom" /></testcase>
+    </testsuite>"#;
+
+    #[test]
+    fn test_run_passing_suite() {
+        let report = run("passing", Some(PASSING), 0).unwrap();
+        assert_eq!(report.passed().count(), 1);
+        report.ensure_passed().unwrap();
+    }
+
+    #[test]
+    fn test_run_failing_suite() {
+        let report = run("failing", Some(FAILING), 1).unwrap();
+        let err = report.ensure_passed().unwrap_err().to_string();
+        assert!(err.contains("t.test_bad: boom"), "{err}");
+    }
+
+    #[test]
+    fn test_run_suite_without_tests() {
+        let err = run("empty", Some("<testsuite />"), 0).unwrap_err();
+        assert_eq!(err.to_string(), "script didn't find any tests");
+        let err = run("no-report", None, 0).unwrap_err();
+        assert_eq!(err.to_string(), "script didn't find any tests");
+    }
+
+    #[test]
+    fn test_run_suite_that_fails_without_a_failed_test() {
+        let err = run("broken", Some(PASSING), 2).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("script failed (exit status: 2) without reporting a failed test"));
+    }
+
+    #[test]
+    fn test_pytest_command() {
+        let mut env = TestEnvironment::new("/out");
+        env.search_paths.push("/deps".into());
+        let command = Pytest::new("/tests")
+            .arg("-k")
+            .arg("add")
+            .command(&env)
+            .unwrap();
+        assert_eq!(command.get_program(), "python3");
+        let args: Vec<_> = command.get_args().map(|a| a.to_str().unwrap()).collect();
+        assert_eq!(
+            args,
+            [
+                "-m",
+                "pytest",
+                "-p",
+                "no:cacheprovider",
+                "--junitxml=/out/test-results/pytest.xml",
+                "/tests",
+                "-k",
+                "add",
+            ]
+        );
+    }
+}
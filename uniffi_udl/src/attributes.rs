@@ -40,6 +40,14 @@ pub(super) enum Attribute {
     Custom,
     // The interface described is implemented as a trait.
     Trait,
+    // The argument carries secret data, like a passphrase.
+    Sensitive,
+    // The dictionary is passed over the FFI as a `#[repr(C)]` struct.
+    ReprC,
+    // Generate a companion `{name}Patch` dictionary for the dictionary.
+    Patch,
+    // The callback interface's methods are called on the foreign main thread.
+    MainThread,
 }
 
 impl Attribute {
@@ -66,6 +74,10 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                 "Error" => Ok(Attribute::Error),
                 "Custom" => Ok(Attribute::Custom),
                 "Trait" => Ok(Attribute::Trait),
+                "Sensitive" => Ok(Attribute::Sensitive),
+                "ReprC" => Ok(Attribute::ReprC),
+                "Patch" => Ok(Attribute::Patch),
+                "MainThread" => Ok(Attribute::MainThread),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
@@ -82,6 +94,10 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
                         crate_name: name_from_id_or_string(&identity.rhs),
                         kind: ExternalKind::Interface,
                     }),
+                    "ExternalFlags" => Ok(Attribute::External {
+                        crate_name: name_from_id_or_string(&identity.rhs),
+                        kind: ExternalKind::Flags,
+                    }),
                     _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
@@ -181,6 +197,54 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
     }
 }
 
+/// Attributes that can be attached to a `dictionary` definition in the UDL.
+/// There's only one case here: using `[ReprC]` to pass the record as a `#[repr(C)]` struct.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct DictionaryAttributes(Vec<Attribute>);
+
+impl DictionaryAttributes {
+    pub fn repr_c(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::ReprC))
+    }
+
+    pub fn patch(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Patch))
+    }
+
+    pub fn ord(&self) -> bool {
+        self.0.iter().any(
+            |attr| matches!(attr, Attribute::Traits(traits) if traits.iter().any(|t| t == "Ord")),
+        )
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for DictionaryAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::ReprC | Attribute::Patch => Ok(()),
+            // Records compare and print their fields in the foreign code, so only `Ord` needs Rust
+            Attribute::Traits(traits) if traits.iter().all(|t| t == "Ord") => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for dictionaries")),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<DictionaryAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for DictionaryAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a function.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for functions that
@@ -233,7 +297,8 @@ impl<T: TryInto<FunctionAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
 /// Represents UDL attributes that might appear on a function argument.
 ///
 /// This supports the `[ByRef]` attribute for arguments that should be passed
-/// by reference in the generated Rust scaffolding.
+/// by reference in the generated Rust scaffolding, and the `[Sensitive]` attribute
+/// for arguments that carry secret data.
 #[derive(Debug, Clone, Checksum, Default)]
 pub(super) struct ArgumentAttributes(Vec<Attribute>);
 
@@ -241,6 +306,12 @@ impl ArgumentAttributes {
     pub fn by_ref(&self) -> bool {
         self.0.iter().any(|attr| matches!(attr, Attribute::ByRef))
     }
+
+    pub fn sensitive(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Sensitive))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ArgumentAttributes {
@@ -249,7 +320,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ArgumentAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::ByRef => Ok(()),
+            Attribute::ByRef | Attribute::Sensitive => Ok(()),
             _ => bail!(format!("{attr:?} not supported for arguments")),
         })?;
         Ok(Self(attrs))
@@ -331,6 +402,45 @@ impl<T: TryInto<InterfaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
     }
 }
 
+/// Attributes that can be attached to a `callback interface` definition in the UDL.
+/// There's only one case here: using `[MainThread]` to call the foreign implementation on the
+/// main thread.
+#[derive(Debug, Clone, Checksum, Default)]
+pub(super) struct CallbackInterfaceAttributes(Vec<Attribute>);
+
+impl CallbackInterfaceAttributes {
+    pub fn main_thread(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::MainThread))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for CallbackInterfaceAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::MainThread => Ok(()),
+            _ => bail!(format!("{attr:?} not supported for callback interfaces")),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<CallbackInterfaceAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for CallbackInterfaceAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a constructor.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for constructors that can produce
@@ -473,9 +583,7 @@ impl TypedefAttributes {
     }
 
     pub(super) fn is_custom(&self) -> bool {
-        self.0
-            .iter()
-            .any(|attr| matches!(attr, Attribute::Custom { .. }))
+        self.0.iter().any(|attr| matches!(attr, Attribute::Custom))
     }
 
     pub(super) fn external_kind(&self) -> Option<ExternalKind> {
@@ -627,7 +735,7 @@ mod test {
 
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
         let attrs = FunctionAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.get_throws_err(), None));
+        assert!(attrs.get_throws_err().is_none());
     }
 
     #[test]
@@ -675,12 +783,12 @@ mod test {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Throws=Error]").unwrap();
         let attrs = ConstructorAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), Some("Error")));
-        assert!(matches!(attrs.get_name(), None));
+        assert!(attrs.get_name().is_none());
 
         let (_, node) =
             weedle::attribute::ExtendedAttributeList::parse("[Name=MyFactory]").unwrap();
         let attrs = ConstructorAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.get_throws_err(), None));
+        assert!(attrs.get_throws_err().is_none());
         assert!(matches!(attrs.get_name(), Some("MyFactory")));
 
         let (_, node) =
@@ -711,11 +819,29 @@ mod test {
     fn test_byref_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
         let attrs = ArgumentAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.by_ref(), true));
+        assert!(attrs.by_ref());
 
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
         let attrs = ArgumentAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.by_ref(), false));
+        assert!(!attrs.by_ref());
+    }
+
+    #[test]
+    fn test_sensitive_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Sensitive]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(attrs.sensitive());
+        assert!(!attrs.by_ref());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ByRef, Sensitive]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(attrs.sensitive());
+        assert!(attrs.by_ref());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = ArgumentAttributes::try_from(&node).unwrap();
+        assert!(!attrs.sensitive());
     }
 
     #[test]
@@ -729,6 +855,55 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_repr_c_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ReprC]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.repr_c());
+        assert!(!attrs.patch());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Error]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Error not supported for dictionaries");
+    }
+
+    #[test]
+    fn test_ord_dictionary_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Traits=(Ord)]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.ord());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Traits=(Ord, Hash)]").unwrap();
+        let err = DictionaryAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Traits([\"Ord\", \"Hash\"]) not supported for dictionaries"
+        );
+    }
+
+    #[test]
+    fn test_patch_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Patch]").unwrap();
+        let attrs = DictionaryAttributes::try_from(&node).unwrap();
+        assert!(attrs.patch());
+        assert!(!attrs.repr_c());
+    }
+
+    #[test]
+    fn test_main_thread_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[MainThread]").unwrap();
+        let attrs = CallbackInterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.main_thread());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Trait]").unwrap();
+        let err = CallbackInterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Trait not supported for callback interfaces"
+        );
+    }
+
     #[test]
     fn test_trait_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Trait]").unwrap();
@@ -744,15 +919,15 @@ mod test {
     fn test_enum_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum]").unwrap();
         let attrs = InterfaceAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.contains_enum_attr(), true));
+        assert!(attrs.contains_enum_attr());
 
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
         let attrs = InterfaceAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.contains_enum_attr(), false));
+        assert!(!attrs.contains_enum_attr());
 
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Trait]").unwrap();
         let attrs = InterfaceAttributes::try_from(&node).unwrap();
-        assert!(matches!(attrs.contains_enum_attr(), false));
+        assert!(!attrs.contains_enum_attr());
 
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Trait, Enum]").unwrap();
         let err = InterfaceAttributes::try_from(&node).unwrap_err();
@@ -790,6 +965,12 @@ mod test {
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(!attrs.is_custom());
         assert_eq!(attrs.get_crate_name(), "crate_name");
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ExternalFlags=crate_name]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert_eq!(attrs.get_crate_name(), "crate_name");
+        assert_eq!(attrs.external_kind(), Some(ExternalKind::Flags));
     }
 
     #[test]
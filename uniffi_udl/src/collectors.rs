@@ -5,7 +5,7 @@
 //! # Collects metadata from UDL.
 
 use crate::attributes;
-use crate::converters::APIConverter;
+use crate::converters::{patch_record, APIConverter};
 use crate::finder;
 use crate::resolver::TypeResolver;
 use anyhow::{bail, Result};
@@ -22,6 +22,8 @@ pub(crate) struct InterfaceCollector {
     pub types: TypeCollector,
     /// The output we collect and supply to our consumer.
     pub items: BTreeSet<uniffi_meta::Metadata>,
+    /// The docstring of the namespace, which documents the component as a whole.
+    pub namespace_docstring: Option<String>,
 }
 
 impl InterfaceCollector {
@@ -135,6 +137,7 @@ impl From<InterfaceCollector> for uniffi_meta::MetadataGroup {
             namespace: uniffi_meta::NamespaceMetadata {
                 crate_name: Default::default(),
                 name: value.types.namespace,
+                docstring: value.namespace_docstring,
             },
             items: value.items,
         }
@@ -180,7 +183,11 @@ impl APIBuilder for weedle::Definition<'_> {
                 }
             }
             weedle::Definition::Dictionary(d) => {
-                let rec = d.convert(ci)?;
+                let attrs = attributes::DictionaryAttributes::try_from(d.attributes.as_ref())?;
+                let rec: uniffi_meta::RecordMetadata = d.convert(ci)?;
+                if attrs.patch() {
+                    ci.add_definition(patch_record(&rec).into())?;
+                }
                 ci.add_definition(rec.into())?;
             }
             weedle::Definition::Interface(d) => {
@@ -216,6 +223,7 @@ impl APIBuilder for weedle::NamespaceDefinition<'_> {
         if self.identifier.0 != ci.types.namespace {
             bail!("duplicate namespace definition");
         }
+        ci.namespace_docstring = self.docstring.as_ref().map(|d| d.0.clone());
         for func in self.members.body.convert(ci)? {
             ci.add_definition(func.into())?;
         }
@@ -61,13 +61,14 @@ impl APIConverter<FnParamMetadata> for weedle::argument::SingleArgument<'_> {
             None => None,
             Some(v) => Some(convert_default_value(&v.value, &type_)?),
         };
-        let by_ref = ArgumentAttributes::try_from(self.attributes.as_ref())?.by_ref();
+        let attrs = ArgumentAttributes::try_from(self.attributes.as_ref())?;
         Ok(FnParamMetadata {
             name: self.identifier.0.to_string(),
             ty: type_,
-            by_ref,
+            by_ref: attrs.by_ref(),
             optional: self.optional.is_some(),
             default,
+            sensitive: attrs.sensitive(),
         })
     }
 }
@@ -104,6 +105,7 @@ impl APIConverter<FnMetadata> for weedle::namespace::OperationNamespaceMember<'_
             inputs: self.args.body.list.convert(ci)?,
             throws,
             checksum: None,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -126,6 +128,7 @@ impl APIConverter<ConstructorMetadata> for weedle::interface::ConstructorInterfa
             inputs: self.args.body.list.convert(ci)?,
             throws,
             checksum: None,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -169,7 +172,9 @@ impl APIConverter<MethodMetadata> for weedle::interface::OperationInterfaceMembe
             return_type,
             throws,
             takes_self_by_arc,
+            takes_self_by_value: false, // not supported in UDL
             checksum: None,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -208,12 +213,14 @@ impl APIConverter<TraitMethodMetadata> for weedle::interface::OperationInterface
                     name
                 }
             },
-            is_async: false, // not supported in udl
+            is_async: false,    // not supported in udl
+            has_default: false, // not supported in udl
             inputs: self.args.body.list.convert(ci)?,
             return_type,
             throws,
             takes_self_by_arc,
             checksum: None,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -24,10 +24,13 @@ impl APIConverter<EnumMetadata> for weedle::EnumDefinition<'_> {
                 .map::<Result<_>, _>(|v| {
                     Ok(VariantMetadata {
                         name: v.0.to_string(),
+                        discr: None,
                         fields: vec![],
                     })
                 })
                 .collect::<Result<Vec<_>>>()?,
+            non_exhaustive: false,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -46,10 +49,13 @@ impl APIConverter<ErrorMetadata> for weedle::EnumDefinition<'_> {
                     .map::<Result<_>, _>(|v| {
                         Ok(VariantMetadata {
                             name: v.0.to_string(),
+                            discr: None,
                             fields: vec![],
                         })
                     })
                     .collect::<Result<Vec<_>>>()?,
+                non_exhaustive: false,
+                docstring: self.docstring.as_ref().map(|d| d.0.clone()),
             },
             is_flat: true,
         })
@@ -80,6 +86,8 @@ impl APIConverter<EnumMetadata> for weedle::InterfaceDefinition<'_> {
                 .collect::<Result<Vec<_>>>()?,
             // Enums declared using the `[Enum] interface` syntax might have variants with fields.
             //flat: false,
+            non_exhaustive: false,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -107,6 +115,8 @@ impl APIConverter<ErrorMetadata> for weedle::InterfaceDefinition<'_> {
                         ),
                     })
                     .collect::<Result<Vec<_>>>()?,
+                non_exhaustive: false,
+                docstring: self.docstring.as_ref().map(|d| d.0.clone()),
             },
             is_flat: false,
         })
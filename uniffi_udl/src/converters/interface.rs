@@ -8,8 +8,8 @@ use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 use uniffi_meta::{
-    ConstructorMetadata, FnParamMetadata, MethodMetadata, ObjectImpl, ObjectMetadata, Type,
-    UniffiTraitMetadata,
+    ConstructorMetadata, FnMetadata, FnParamMetadata, MethodMetadata, ObjectImpl, ObjectMetadata,
+    Type, UniffiTraitMetadata,
 };
 
 impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
@@ -69,9 +69,24 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                 return_type,
                 throws: None,
                 takes_self_by_arc: false,
+                takes_self_by_value: false,
                 checksum: None,
+                docstring: None,
             })
         };
+        // The `other` argument of the trait methods that compare two objects
+        let make_other_param = || FnParamMetadata {
+            name: "other".to_string(),
+            ty: Type::Object {
+                module_path: ci.module_path(),
+                name: object_name.to_string(),
+                imp: object_impl,
+            },
+            by_ref: true,
+            default: None,
+            optional: false,
+            sensitive: false,
+        };
         // Trait methods are in the Metadata.
         let uniffi_traits = attributes
             .get_traits()
@@ -87,38 +102,60 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
                     "Eq" => UniffiTraitMetadata::Eq {
                         eq: make_trait_method(
                             "uniffi_trait_eq_eq",
-                            vec![FnParamMetadata {
-                                name: "other".to_string(),
-                                ty: Type::Object {
-                                    module_path: ci.module_path(),
-                                    name: object_name.to_string(),
-                                    imp: object_impl,
-                                },
-                                by_ref: true,
-                                default: None,
-                                optional: false,
-                            }],
+                            vec![make_other_param()],
                             Some(Type::Boolean),
                         )?,
                         ne: make_trait_method(
                             "uniffi_trait_eq_ne",
-                            vec![FnParamMetadata {
-                                name: "other".to_string(),
-                                ty: Type::Object {
-                                    module_path: ci.module_path(),
-                                    name: object_name.to_string(),
-                                    imp: object_impl,
-                                },
-                                by_ref: true,
-                                default: None,
-                                optional: false,
-                            }],
+                            vec![make_other_param()],
                             Some(Type::Boolean),
                         )?,
                     },
                     "Hash" => UniffiTraitMetadata::Hash {
                         hash: make_trait_method("uniffi_trait_hash", vec![], Some(Type::UInt64))?,
                     },
+                    "Ord" => UniffiTraitMetadata::Ord {
+                        cmp: make_trait_method(
+                            "uniffi_trait_ord_cmp",
+                            vec![make_other_param()],
+                            Some(Type::Int8),
+                        )?,
+                    },
+                    "Restore" => {
+                        if object_impl == ObjectImpl::Trait {
+                            bail!("Trait interfaces can not implement Restore: \"{object_name}\"")
+                        }
+                        UniffiTraitMetadata::Restore {
+                            serialize: make_trait_method(
+                                "uniffi_trait_restore_serialize",
+                                vec![],
+                                Some(Type::String),
+                            )?,
+                            restore: FnMetadata {
+                                module_path: ci.module_path(),
+                                name: format!("uniffi_trait_restore_{object_name}"),
+                                is_async: false,
+                                inputs: vec![FnParamMetadata {
+                                    name: "token".to_string(),
+                                    ty: Type::String,
+                                    by_ref: false,
+                                    default: None,
+                                    optional: false,
+                                    sensitive: false,
+                                }],
+                                return_type: Some(Type::Optional {
+                                    inner_type: Box::new(Type::Object {
+                                        module_path: ci.module_path(),
+                                        name: object_name.to_string(),
+                                        imp: object_impl,
+                                    }),
+                                }),
+                                throws: None,
+                                checksum: None,
+                                docstring: None,
+                            },
+                        }
+                    }
                     _ => bail!("Invalid trait name: {}", trait_name),
                 })
             })
@@ -128,6 +165,7 @@ impl APIConverter<ObjectMetadata> for weedle::InterfaceDefinition<'_> {
             name: object_name.to_string(),
             imp: object_impl,
             uniffi_traits,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
@@ -2,12 +2,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use crate::attributes::{CallbackInterfaceAttributes, DictionaryAttributes};
 use crate::literal::convert_default_value;
 use crate::InterfaceCollector;
 use anyhow::{bail, Result};
 
 use uniffi_meta::{
-    CallbackInterfaceMetadata, FieldMetadata, RecordMetadata, TraitMethodMetadata, VariantMetadata,
+    CallbackInterfaceMetadata, FieldMetadata, LiteralMetadata, RecordMetadata, TraitMethodMetadata,
+    Type, VariantMetadata,
 };
 
 mod callables;
@@ -63,6 +65,7 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
         };
         Ok(VariantMetadata {
             name,
+            discr: None,
             fields: self
                 .args
                 .body
@@ -76,20 +79,72 @@ impl APIConverter<VariantMetadata> for weedle::interface::OperationInterfaceMemb
 
 impl APIConverter<RecordMetadata> for weedle::DictionaryDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<RecordMetadata> {
-        if self.attributes.is_some() {
-            bail!("dictionary attributes are not supported yet");
-        }
+        let attributes = DictionaryAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("dictionary inheritance is not supported");
         }
+        let name = self.identifier.0.to_string();
+        let fields: Vec<FieldMetadata> = self.members.body.convert(ci)?;
+        let repr_c = attributes.repr_c();
+        if repr_c && !fields.iter().all(|f| is_repr_c_field_type(&f.ty)) {
+            bail!("[ReprC] dictionary `{name}` can only have integer and float fields");
+        }
         Ok(RecordMetadata {
             module_path: ci.module_path(),
-            name: self.identifier.0.to_string(),
-            fields: self.members.body.convert(ci)?,
+            name,
+            fields,
+            repr_c,
+            ord: attributes.ord(),
+            patch_for: None,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
 
+/// The companion `{name}Patch` record for a `[Patch]` dictionary
+///
+/// Each field is optional and defaults to `null`, so foreign code only needs to set the fields
+/// that it wants to change.
+pub(crate) fn patch_record(record: &RecordMetadata) -> RecordMetadata {
+    RecordMetadata {
+        module_path: record.module_path.clone(),
+        name: format!("{}Patch", record.name),
+        fields: record
+            .fields
+            .iter()
+            .map(|f| FieldMetadata {
+                name: f.name.clone(),
+                ty: Type::Optional {
+                    inner_type: Box::new(f.ty.clone()),
+                },
+                default: Some(LiteralMetadata::Null),
+            })
+            .collect(),
+        repr_c: false,
+        ord: false,
+        patch_for: Some(record.name.clone()),
+        docstring: None,
+    }
+}
+
+// Types that can be fields of a `[ReprC]` dictionary.  These must match the field types accepted
+// for `#[repr(C)]` records by `uniffi_macros`.
+fn is_repr_c_field_type(type_: &Type) -> bool {
+    matches!(
+        type_,
+        Type::UInt8
+            | Type::Int8
+            | Type::UInt16
+            | Type::Int16
+            | Type::UInt32
+            | Type::Int32
+            | Type::UInt64
+            | Type::Int64
+            | Type::Float32
+            | Type::Float64
+    )
+}
+
 impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<FieldMetadata> {
         if self.attributes.is_some() {
@@ -110,9 +165,7 @@ impl APIConverter<FieldMetadata> for weedle::dictionary::DictionaryMember<'_> {
 
 impl APIConverter<CallbackInterfaceMetadata> for weedle::CallbackInterfaceDefinition<'_> {
     fn convert(&self, ci: &mut InterfaceCollector) -> Result<CallbackInterfaceMetadata> {
-        if self.attributes.is_some() {
-            bail!("callback interface attributes are not supported yet");
-        }
+        let attributes = CallbackInterfaceAttributes::try_from(self.attributes.as_ref())?;
         if self.inheritance.is_some() {
             bail!("callback interface inheritance is not supported");
         }
@@ -141,6 +194,9 @@ impl APIConverter<CallbackInterfaceMetadata> for weedle::CallbackInterfaceDefini
         Ok(CallbackInterfaceMetadata {
             module_path: ci.module_path(),
             name: object_name.to_string(),
+            main_thread: attributes.main_thread(),
+            closure: false,
+            docstring: self.docstring.as_ref().map(|d| d.0.clone()),
         })
     }
 }
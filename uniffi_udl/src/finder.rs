@@ -19,10 +19,12 @@
 
 use std::convert::TryFrom;
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 
 use super::TypeCollector;
-use crate::attributes::{InterfaceAttributes, TypedefAttributes};
+use crate::attributes::{
+    CallbackInterfaceAttributes, DictionaryAttributes, InterfaceAttributes, TypedefAttributes,
+};
 use uniffi_meta::Type;
 
 /// Trait to help with an early "type discovery" phase when processing the UDL.
@@ -85,6 +87,18 @@ impl TypeFinder for weedle::InterfaceDefinition<'_> {
 impl TypeFinder for weedle::DictionaryDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeCollector) -> Result<()> {
         let name = self.identifier.0.to_string();
+        let attrs = DictionaryAttributes::try_from(self.attributes.as_ref())?;
+        // `[Patch]` dictionaries also define a companion `{name}Patch` dictionary.
+        if attrs.patch() {
+            let patch_name = format!("{name}Patch");
+            types.add_type_definition(
+                &patch_name,
+                Type::Record {
+                    name: patch_name.clone(),
+                    module_path: types.module_path(),
+                },
+            )?;
+        }
         types.add_type_definition(
             self.identifier.0,
             Type::Record {
@@ -148,9 +162,8 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
 
 impl TypeFinder for weedle::CallbackInterfaceDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeCollector) -> Result<()> {
-        if self.attributes.is_some() {
-            bail!("no typedef attributes are currently supported");
-        }
+        // Only validate the attributes here, they're read when converting the definition
+        CallbackInterfaceAttributes::try_from(self.attributes.as_ref())?;
         let name = self.identifier.0.to_string();
         types.add_type_definition(
             self.identifier.0,
@@ -203,6 +216,21 @@ mod test {
                 assert!(
                     matches!(types.get_type_definition("TestRecord").unwrap(), Type::Record { name, .. } if name == "TestRecord")
                 );
+                assert!(types.get_type_definition("TestRecordPatch").is_none());
+            },
+        );
+
+        test_a_finding(
+            r#"
+            [Patch]
+            dictionary TestRecord {
+                u32 field;
+            };
+        "#,
+            |types| {
+                assert!(
+                    matches!(types.get_type_definition("TestRecordPatch").unwrap(), Type::Record { name, .. } if name == "TestRecordPatch")
+                );
             },
         );
 
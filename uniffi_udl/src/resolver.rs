@@ -196,6 +196,7 @@ impl TypeResolver for weedle::types::DoubleType {
 pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
     match name {
         "string" => Some(Type::String),
+        "char" => Some(Type::Char),
         "bytes" => Some(Type::Bytes),
         "u8" => Some(Type::UInt8),
         "i8" => Some(Type::Int8),
@@ -209,6 +210,13 @@ pub(crate) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "f64" => Some(Type::Float64),
         "timestamp" => Some(Type::Timestamp),
         "duration" => Some(Type::Duration),
+        "url" => Some(Type::Url),
+        "uuid" => Some(Type::Uuid),
+        "decimal" => Some(Type::Decimal),
+        "json" => Some(Type::Json),
+        "path" => Some(Type::Path),
+        "ipaddr" => Some(Type::IpAddr),
+        "socketaddr" => Some(Type::SocketAddr),
         "ForeignExecutor" => Some(Type::ForeignExecutor),
         _ => None,
     }
@@ -97,6 +97,11 @@ ast_types! {
         )),
     )
 
+    /// Parses the `///` lines before a definition, without the `///` and the space after it
+    struct Docstring(
+        String = crate::whitespace::docstring,
+    )
+
     /// Parses rhs of an assignment expression. Ex: `= 45`
     #[derive(Copy)]
     struct Default<'a> {
@@ -23,7 +23,7 @@
 
 use self::argument::ArgumentList;
 use self::attribute::ExtendedAttributeList;
-use self::common::{Braced, Identifier, Parenthesized, PunctuatedNonEmpty};
+use self::common::{Braced, Docstring, Identifier, Parenthesized, PunctuatedNonEmpty};
 use self::dictionary::DictionaryMembers;
 use self::interface::{Inheritance, InterfaceMembers};
 use self::literal::StringLit;
@@ -107,8 +107,9 @@ ast_types! {
             arguments: Parenthesized<ArgumentList<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? callback interface identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? callback interface identifier ( : inheritance )? { members };`
         CallbackInterface(struct CallbackInterfaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             callback: term!(callback),
             interface: term!(interface),
@@ -117,8 +118,9 @@ ast_types! {
             members: Braced<InterfaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? interface identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? interface identifier ( : inheritance )? { members };`
         Interface(struct InterfaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             interface: term!(interface),
             identifier: Identifier<'a>,
@@ -135,16 +137,18 @@ ast_types! {
             members: Braced<MixinMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? namespace identifier { members };`
+        /// Parses `docstring? [attributes]? namespace identifier { members };`
         Namespace(struct NamespaceDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             namespace: term!(namespace),
             identifier: Identifier<'a>,
             members: Braced<NamespaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? dictionary identifier ( : inheritance )? { members };`
+        /// Parses `docstring? [attributes]? dictionary identifier ( : inheritance )? { members };`
         Dictionary(struct DictionaryDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             dictionary: term!(dictionary),
             identifier: Identifier<'a>,
@@ -189,8 +193,9 @@ ast_types! {
             members: Braced<NamespaceMembers<'a>>,
             semi_colon: term!(;),
         }),
-        /// Parses `[attributes]? enum identifier { values };`
+        /// Parses `docstring? [attributes]? enum identifier { values };`
         Enum(struct EnumDefinition<'a> {
+            docstring: Option<Docstring>,
             attributes: Option<ExtendedAttributeList<'a>>,
             enum_: term!(enum),
             identifier: Identifier<'a>,
@@ -263,6 +268,33 @@ mod test {
         members.body.len() == 2;
     });
 
+    test!(should_parse_dictionary_docstring { "/// A dictionary\n///\n///   indented\ndictionary A { long c; };" =>
+        "";
+        DictionaryDefinition;
+        docstring == Some(Docstring("A dictionary\n\n  indented".to_string()));
+        identifier.0 == "A";
+    });
+
+    test!(should_parse_namespace_docstrings { "/// Some functions\nnamespace ns { /// Does nothing\n void noop(); };" =>
+        "";
+        NamespaceDefinition;
+        docstring == Some(Docstring("Some functions".to_string()));
+        members.body.len() == 1;
+    });
+
+    test!(should_not_parse_comments_as_docstrings { "//// Not a docstring\n// Nor this\nenum name { \"first\" };" =>
+        "";
+        EnumDefinition;
+        docstring.is_none();
+        identifier.0 == "name";
+    });
+
+    test!(should_ignore_docstrings_of_other_definitions { "/// A short\ntypedef short Short;" =>
+        "";
+        TypedefDefinition;
+        identifier.0 == "Short";
+    });
+
     test!(should_parse_dictionary_inherited { "dictionary C : B { long e; long f; };" =>
         "";
         DictionaryDefinition;
@@ -386,6 +418,12 @@ mod test {
         CallbackDefinition;
     });
 
+    test!(should_parse_with_line_comment_at_the_end { "callback AsyncOperationCallback = undefined (DOMString status);
+        // This is a comment" =>
+        "";
+        CallbackDefinition;
+    });
+
     test!(should_parse_with_block_comments { "
         /* This is a comment */
         callback AsyncOperationCallback = undefined (DOMString status);
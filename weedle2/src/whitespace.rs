@@ -2,13 +2,13 @@ use nom::{IResult, Parser};
 
 pub(crate) fn sp(input: &str) -> IResult<&str, &str> {
     nom::combinator::recognize(nom::multi::many0(nom::branch::alt((
-        // ignores line comments
+        // ignores line comments, including one on the last line of the input
         nom::combinator::value(
             (),
             nom::sequence::tuple((
                 nom::bytes::complete::tag("//"),
-                nom::bytes::complete::take_until("\n"),
-                nom::bytes::complete::tag("\n"),
+                nom::bytes::complete::take_till(|c| c == '\n'),
+                line_end,
             )),
         ),
         // ignores whitespace
@@ -25,10 +25,81 @@ pub(crate) fn sp(input: &str) -> IResult<&str, &str> {
     ))))(input)
 }
 
+/// Like `sp`, but stops at `///` docstring lines, so the definition that follows can parse them.
+/// Docstring lines at the end of the input are ignored, since there's nothing left to document.
+pub(crate) fn sp_before_docstring(input: &str) -> IResult<&str, &str> {
+    nom::combinator::recognize(nom::multi::many0(nom::branch::alt((
+        // ignores line comments that aren't docstrings
+        nom::combinator::value(
+            (),
+            nom::sequence::tuple((
+                nom::combinator::not(docstring_start),
+                nom::bytes::complete::tag("//"),
+                nom::bytes::complete::take_till(|c| c == '\n'),
+                line_end,
+            )),
+        ),
+        // ignores whitespace
+        nom::combinator::value((), nom::character::complete::multispace1),
+        // ignores block comments
+        nom::combinator::value(
+            (),
+            nom::sequence::tuple((
+                nom::bytes::complete::tag("/*"),
+                nom::bytes::complete::take_until("*/"),
+                nom::bytes::complete::tag("*/"),
+            )),
+        ),
+        // ignores docstrings at the end of the input
+        nom::combinator::value(
+            (),
+            nom::sequence::tuple((
+                nom::combinator::peek(docstring_start),
+                sp,
+                nom::combinator::eof,
+            )),
+        ),
+    ))))(input)
+}
+
+/// The newline at the end of a line comment, or the end of the input
+fn line_end(input: &str) -> IResult<&str, &str> {
+    nom::branch::alt((nom::bytes::complete::tag("\n"), nom::combinator::eof))(input)
+}
+
+/// The `///` at the start of a docstring line.  More slashes make a regular comment.
+fn docstring_start(input: &str) -> IResult<&str, &str> {
+    nom::sequence::terminated(
+        nom::bytes::complete::tag("///"),
+        nom::combinator::not(nom::bytes::complete::tag("/")),
+    )(input)
+}
+
+/// Parses consecutive `///` lines into the text of a docstring, without the `///` and the space
+/// that follows it
+pub(crate) fn docstring(input: &str) -> IResult<&str, String> {
+    nom::combinator::map(
+        nom::multi::many1(nom::sequence::preceded(
+            nom::sequence::pair(sp_before_docstring, docstring_start),
+            nom::bytes::complete::take_till(|c| c == '\n'),
+        )),
+        |lines: Vec<&str>| {
+            lines
+                .iter()
+                .map(|line| {
+                    let line = line.trim_end_matches('\r');
+                    line.strip_prefix(' ').unwrap_or(line)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+    )(input)
+}
+
 /// ws also ignores line & block comments
 pub(crate) fn ws<'a, F>(inner: F) -> impl FnMut(&'a str) -> IResult<&str, &str>
 where
     F: Parser<&'a str, &'a str, nom::error::Error<&'a str>>,
 {
-    nom::sequence::delimited(sp, inner, sp)
+    nom::sequence::delimited(sp, inner, sp_before_docstring)
 }